@@ -14,7 +14,7 @@ use crate::{
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
     Address, AptosErrorCode, AsConverter, IdentifierWrapper, LedgerInfo, MoveModuleBytecode,
-    MoveResource, MoveStructTag, MoveValue, TableItemRequest, U64,
+    MoveResource, MoveStructTag, MoveValue, RawTableItemRequest, TableItemRequest, U64,
 };
 use aptos_state_view::StateView;
 use aptos_types::{
@@ -130,6 +130,40 @@ impl StateApi {
             ledger_version.0,
         )
     }
+
+    /// Get raw table item
+    ///
+    /// Get a table item from the table identified by {table_handle} in the
+    /// path and the BCS-serialized "key" (RawTableItemRequest) provided in the
+    /// request body.
+    ///
+    /// Unlike the get table item endpoint, this doesn't require the types of the
+    /// key and of the value, which makes it suitable for clients that don't know
+    /// them, e.g., to execute transactions locally against remote state. The
+    /// value is returned as raw bytes: hex-encoded in JSON, as-is in BCS.
+    #[oai(
+        path = "/tables/:table_handle/raw_item",
+        method = "post",
+        operation_id = "get_raw_table_item",
+        tag = "ApiTags::Tables"
+    )]
+    async fn get_raw_table_item(
+        &self,
+        accept_type: AcceptType,
+        table_handle: Path<Address>,
+        table_item_request: Json<RawTableItemRequest>,
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_raw_table_item")?;
+        self.context
+            .check_api_output_enabled("Get raw table item", &accept_type)?;
+        self.raw_table_item(
+            &accept_type,
+            table_handle.0,
+            table_item_request.0,
+            ledger_version.0,
+        )
+    }
 }
 
 impl StateApi {
@@ -336,4 +370,50 @@ impl StateApi {
             }
         }
     }
+
+    pub fn raw_table_item(
+        &self,
+        accept_type: &AcceptType,
+        table_handle: Address,
+        table_item_request: RawTableItemRequest,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<MoveValue> {
+        let key = table_item_request.key;
+        let (ledger_info, ledger_version, state_view) =
+            self.preprocess_request(ledger_version.map(|inner| inner.0))?;
+
+        let state_key = StateKey::table_item(TableHandle(table_handle.into()), key.0.clone());
+        let bytes = state_view
+            .get_state_value(&state_key)
+            .context(format!(
+                "Failed when trying to retrieve table item from the DB with key: {}",
+                key
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?
+            .ok_or_else(|| {
+                table_item_not_found(
+                    table_handle,
+                    &serde_json::Value::String(key.to_string()),
+                    ledger_version,
+                    &ledger_info,
+                )
+            })?;
+
+        match accept_type {
+            AcceptType::Json => BasicResponse::try_from_json((
+                MoveValue::Bytes(bytes.into()),
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+            AcceptType::Bcs => {
+                BasicResponse::try_from_encoded((bytes, &ledger_info, BasicResponseStatus::Ok))
+            }
+        }
+    }
 }
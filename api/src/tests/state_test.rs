@@ -143,6 +143,11 @@ async fn test_get_table_item() {
         .to_owned();
 
     assert_table_item(ctx, &tt["u8_table"], "u8", "u8", 1u8, 1u8).await;
+    let u8_table = tt["u8_table"]["handle"].as_str().unwrap().parse().unwrap();
+    let raw_item = ctx
+        .post(&get_raw_table_item(u8_table), json!({ "key": "0x01" }))
+        .await;
+    assert_eq!(raw_item, json!("0x01"));
     assert_table_item(ctx, &tt["u64_table"], "u64", "u64", "1", "1").await;
     assert_table_item(ctx, &tt["u128_table"], "u128", "u128", "1", "1").await;
     assert_table_item(ctx, &tt["bool_table"], "bool", "bool", true, true).await;
@@ -222,6 +227,10 @@ fn get_table_item(handle: AccountAddress) -> String {
     format!("/tables/{}/item", handle)
 }
 
+fn get_raw_table_item(handle: AccountAddress) -> String {
+    format!("/tables/{}/raw_item", handle)
+}
+
 async fn make_test_tables(ctx: &mut TestContext, account: &mut LocalAccount) {
     let module = build_test_module(account.address()).await;
 
@@ -915,8 +915,19 @@ impl TransactionsApi {
                 BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, &ledger_info)
             })?;
 
-        let raw_message = match request.secondary_signers {
-            Some(secondary_signer_addresses) => {
+        let raw_message = match (request.secondary_signers, request.fee_payer) {
+            (secondary_signer_addresses, Some(fee_payer)) => {
+                signing_message(&RawTransactionWithData::new_multi_agent_with_fee_payer(
+                    raw_txn,
+                    secondary_signer_addresses
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|v| v.into())
+                        .collect(),
+                    fee_payer.into(),
+                ))
+            }
+            (Some(secondary_signer_addresses), None) => {
                 signing_message(&RawTransactionWithData::new_multi_agent(
                     raw_txn,
                     secondary_signer_addresses
@@ -925,7 +936,7 @@ impl TransactionsApi {
                         .collect(),
                 ))
             }
-            None => raw_txn.signing_message(),
+            (None, None) => raw_txn.signing_message(),
         };
 
         BasicResponse::try_from_json((
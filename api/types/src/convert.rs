@@ -4,8 +4,9 @@
 use crate::{
     transaction::{
         DecodedTableData, DeleteModule, DeleteResource, DeleteTableItem, DeletedTableData,
-        ModuleBundlePayload, StateCheckpointTransaction, UserTransactionRequestInner, WriteModule,
-        WriteResource, WriteTableItem,
+        ModuleBundlePayload, MultisigPayload, MultisigTransactionPayload,
+        StateCheckpointTransaction, UserTransactionRequestInner, WriteModule, WriteResource,
+        WriteTableItem,
     },
     Bytecode, DirectWriteSet, EntryFunctionId, EntryFunctionPayload, Event, HexEncodedBytes,
     MoveFunction, MoveModuleBytecode, MoveResource, MoveScriptBytecode, MoveValue,
@@ -21,7 +22,8 @@ use aptos_types::{
     contract_event::{ContractEvent, EventWithVersion},
     state_store::{state_key::StateKey, table::TableHandle},
     transaction::{
-        EntryFunction, ExecutionStatus, ModuleBundle, RawTransaction, Script, SignedTransaction,
+        EntryFunction, ExecutionStatus, ModuleBundle, Multisig, RawTransaction, Script,
+        SignedTransaction,
     },
     vm_status::AbortLocation,
     write_set::WriteOp,
@@ -163,34 +165,55 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                     .collect::<Result<Vec<_>>>()?,
             }),
             EntryFunction(fun) => {
-                let (module, function, ty_args, args) = fun.into_inner();
-                let func_args = self
-                    .inner
-                    .view_function_arguments(&module, &function, &args);
-                let json_args = match func_args {
-                    Ok(values) => values
-                        .into_iter()
-                        .map(|v| MoveValue::try_from(v)?.json())
-                        .collect::<Result<_>>()?,
-                    Err(_e) => args
-                        .into_iter()
-                        .map(|arg| HexEncodedBytes::from(arg).json())
-                        .collect::<Result<_>>()?,
+                TransactionPayload::EntryFunctionPayload(self.try_into_entry_function_payload(fun)?)
+            }
+            Multisig(multisig) => {
+                let transaction_payload = match multisig.transaction_payload {
+                    Some(aptos_types::transaction::MultisigTransactionPayload::EntryFunction(
+                        fun,
+                    )) => Some(MultisigTransactionPayload::EntryFunctionPayload(
+                        self.try_into_entry_function_payload(fun)?,
+                    )),
+                    None => None,
                 };
-
-                TransactionPayload::EntryFunctionPayload(EntryFunctionPayload {
-                    arguments: json_args,
-                    function: EntryFunctionId {
-                        module: module.into(),
-                        name: function.into(),
-                    },
-                    type_arguments: ty_args.into_iter().map(|arg| arg.into()).collect(),
+                TransactionPayload::MultisigPayload(MultisigPayload {
+                    multisig_address: multisig.multisig_address.into(),
+                    transaction_payload,
                 })
             }
         };
         Ok(ret)
     }
 
+    pub fn try_into_entry_function_payload(
+        &self,
+        fun: aptos_types::transaction::EntryFunction,
+    ) -> Result<EntryFunctionPayload> {
+        let (module, function, ty_args, args) = fun.into_inner();
+        let func_args = self
+            .inner
+            .view_function_arguments(&module, &function, &args);
+        let json_args = match func_args {
+            Ok(values) => values
+                .into_iter()
+                .map(|v| MoveValue::try_from(v)?.json())
+                .collect::<Result<_>>()?,
+            Err(_e) => args
+                .into_iter()
+                .map(|arg| HexEncodedBytes::from(arg).json())
+                .collect::<Result<_>>()?,
+        };
+
+        Ok(EntryFunctionPayload {
+            arguments: json_args,
+            function: EntryFunctionId {
+                module: module.into(),
+                name: function.into(),
+            },
+            type_arguments: ty_args.into_iter().map(|arg| arg.into()).collect(),
+        })
+    }
+
     pub fn try_into_write_set_payload(
         &self,
         payload: aptos_types::transaction::WriteSetPayload,
@@ -462,39 +485,7 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
 
         let ret = match payload {
             TransactionPayload::EntryFunctionPayload(entry_func_payload) => {
-                let EntryFunctionPayload {
-                    function,
-                    type_arguments,
-                    arguments,
-                } = entry_func_payload;
-
-                let module = function.module.clone();
-                let code = self.inner.get_module(&module.clone().into())? as Rc<dyn Bytecode>;
-                let func = code
-                    .find_entry_function(function.name.0.as_ident_str())
-                    .ok_or_else(|| format_err!("could not find entry function by {}", function))?;
-                ensure!(
-                    func.generic_type_params.len() == type_arguments.len(),
-                    "expect {} type arguments for entry function {}, but got {}",
-                    func.generic_type_params.len(),
-                    function,
-                    type_arguments.len()
-                );
-                let args = self
-                    .try_into_vm_values(func, arguments)?
-                    .iter()
-                    .map(bcs::to_bytes)
-                    .collect::<Result<_, bcs::Error>>()?;
-
-                Target::EntryFunction(EntryFunction::new(
-                    module.into(),
-                    function.name.into(),
-                    type_arguments
-                        .into_iter()
-                        .map(|v| v.try_into())
-                        .collect::<Result<_>>()?,
-                    args,
-                ))
+                Target::EntryFunction(self.try_into_aptos_core_entry_function(entry_func_payload)?)
             }
             TransactionPayload::ModuleBundlePayload(payload) => {
                 Target::ModuleBundle(ModuleBundle::new(
@@ -530,10 +521,65 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                     None => return Err(anyhow::anyhow!("invalid transaction script bytecode")),
                 }
             }
+            TransactionPayload::MultisigPayload(multisig) => {
+                let transaction_payload = match multisig.transaction_payload {
+                    Some(MultisigTransactionPayload::EntryFunctionPayload(entry_func_payload)) => {
+                        Some(
+                            aptos_types::transaction::MultisigTransactionPayload::EntryFunction(
+                                self.try_into_aptos_core_entry_function(entry_func_payload)?,
+                            ),
+                        )
+                    }
+                    None => None,
+                };
+                Target::Multisig(Multisig {
+                    multisig_address: multisig.multisig_address.into(),
+                    transaction_payload,
+                })
+            }
         };
         Ok(ret)
     }
 
+    pub fn try_into_aptos_core_entry_function(
+        &self,
+        entry_func_payload: EntryFunctionPayload,
+    ) -> Result<EntryFunction> {
+        let EntryFunctionPayload {
+            function,
+            type_arguments,
+            arguments,
+        } = entry_func_payload;
+
+        let module = function.module.clone();
+        let code = self.inner.get_module(&module.clone().into())? as Rc<dyn Bytecode>;
+        let func = code
+            .find_entry_function(function.name.0.as_ident_str())
+            .ok_or_else(|| format_err!("could not find entry function by {}", function))?;
+        ensure!(
+            func.generic_type_params.len() == type_arguments.len(),
+            "expect {} type arguments for entry function {}, but got {}",
+            func.generic_type_params.len(),
+            function,
+            type_arguments.len()
+        );
+        let args = self
+            .try_into_vm_values(func, arguments)?
+            .iter()
+            .map(bcs::to_bytes)
+            .collect::<Result<_, bcs::Error>>()?;
+
+        Ok(EntryFunction::new(
+            module.into(),
+            function.name.into(),
+            type_arguments
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<_>>()?,
+            args,
+        ))
+    }
+
     pub fn try_into_vm_values(
         &self,
         func: MoveFunction,
@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::MoveType;
+use crate::{HexEncodedBytes, MoveType};
 use poem_openapi::Object;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -12,3 +12,9 @@ pub struct TableItemRequest {
     pub value_type: MoveType,
     pub key: Value,
 }
+
+/// Table item request for the raw table item API, where the key is already BCS-serialized
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct RawTableItemRequest {
+    pub key: HexEncodedBytes,
+}
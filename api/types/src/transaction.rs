@@ -10,6 +10,7 @@ use anyhow::{bail, Context as AnyhowContext};
 use aptos_crypto::{
     ed25519::{self, Ed25519PublicKey},
     multi_ed25519::{self, MultiEd25519PublicKey},
+    secp256r1_ecdsa::{self, Secp256r1EcdsaPublicKey},
 };
 use aptos_types::{
     account_address::AccountAddress,
@@ -17,7 +18,7 @@ use aptos_types::{
     contract_event::{ContractEvent, EventWithVersion},
     transaction::{
         authenticator::{AccountAuthenticator, TransactionAuthenticator},
-        Script, SignedTransaction, TransactionOutput, TransactionWithProof,
+        webauthn, Script, SignedTransaction, TransactionOutput, TransactionWithProof,
     },
 };
 
@@ -398,6 +399,9 @@ pub struct EncodeSubmissionRequest {
     pub transaction: UserTransactionRequestInner,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub secondary_signers: Option<Vec<Address>>,
+    /// The account paying for gas, for sponsored transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<Address>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
@@ -488,6 +492,22 @@ pub enum TransactionPayload {
     EntryFunctionPayload(EntryFunctionPayload),
     ScriptPayload(ScriptPayload),
     ModuleBundlePayload(ModuleBundlePayload),
+    MultisigPayload(MultisigPayload),
+}
+
+/// A multisig transaction, executing the next approved transaction of a multisig account
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MultisigPayload {
+    pub multisig_address: Address,
+    /// The payload to execute, which may be omitted if it is already stored on chain
+    pub transaction_payload: Option<MultisigTransactionPayload>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[oai(one_of, discriminator_name = "type", rename_all = "snake_case")]
+pub enum MultisigTransactionPayload {
+    EntryFunctionPayload(EntryFunctionPayload),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
@@ -649,6 +669,8 @@ pub enum TransactionSignature {
     Ed25519Signature(Ed25519Signature),
     MultiEd25519Signature(MultiEd25519Signature),
     MultiAgentSignature(MultiAgentSignature),
+    SingleSenderSignature(SingleSenderSignature),
+    FeePayerSignature(FeePayerSignature),
 }
 
 impl TryFrom<TransactionSignature> for TransactionAuthenticator {
@@ -658,6 +680,8 @@ impl TryFrom<TransactionSignature> for TransactionAuthenticator {
             TransactionSignature::Ed25519Signature(sig) => sig.try_into()?,
             TransactionSignature::MultiEd25519Signature(sig) => sig.try_into()?,
             TransactionSignature::MultiAgentSignature(sig) => sig.try_into()?,
+            TransactionSignature::SingleSenderSignature(sig) => sig.try_into()?,
+            TransactionSignature::FeePayerSignature(sig) => sig.try_into()?,
         })
     }
 }
@@ -778,12 +802,81 @@ impl TryFrom<MultiEd25519Signature> for AccountAuthenticator {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct Secp256r1EcdsaSignature {
+    pub public_key: HexEncodedBytes,
+    pub signature: HexEncodedBytes,
+}
+
+impl TryFrom<Secp256r1EcdsaSignature> for AccountAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Secp256r1EcdsaSignature) -> Result<Self, Self::Error> {
+        let Secp256r1EcdsaSignature {
+            public_key,
+            signature,
+        } = value;
+        Ok(AccountAuthenticator::secp256r1_ecdsa(
+            public_key
+                .inner()
+                .try_into()
+                .context("Failed to parse given public_key bytes as a Secp256r1EcdsaPublicKey")?,
+            signature
+                .inner()
+                .try_into()
+                .context("Failed to parse given signature as a Secp256r1EcdsaSignature")?,
+        ))
+    }
+}
+
+/// A WebAuthn assertion, e.g., produced by a passkey.
+///
+/// The signature is computed by the authenticator over the authenticator data concatenated with
+/// the SHA2-256 hash of the client data JSON, whose challenge must be the SHA3-256 hash of the
+/// transaction signing message, encoded in base64url.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct WebAuthnSignature {
+    pub public_key: HexEncodedBytes,
+    pub signature: HexEncodedBytes,
+    pub authenticator_data: HexEncodedBytes,
+    pub client_data_json: HexEncodedBytes,
+}
+
+impl TryFrom<WebAuthnSignature> for AccountAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: WebAuthnSignature) -> Result<Self, Self::Error> {
+        let WebAuthnSignature {
+            public_key,
+            signature,
+            authenticator_data,
+            client_data_json,
+        } = value;
+        Ok(AccountAuthenticator::webauthn(
+            public_key
+                .inner()
+                .try_into()
+                .context("Failed to parse given public_key bytes as a Secp256r1EcdsaPublicKey")?,
+            webauthn::WebAuthnSignature::new(
+                signature
+                    .inner()
+                    .try_into()
+                    .context("Failed to parse given signature as a Secp256r1EcdsaSignature")?,
+                authenticator_data.into(),
+                client_data_json.into(),
+            ),
+        ))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[oai(one_of, discriminator_name = "type", rename_all = "snake_case")]
 pub enum AccountSignature {
     Ed25519Signature(Ed25519Signature),
     MultiEd25519Signature(MultiEd25519Signature),
+    Secp256r1EcdsaSignature(Secp256r1EcdsaSignature),
+    WebAuthnSignature(WebAuthnSignature),
 }
 
 impl TryFrom<AccountSignature> for AccountAuthenticator {
@@ -793,6 +886,8 @@ impl TryFrom<AccountSignature> for AccountAuthenticator {
         Ok(match sig {
             AccountSignature::Ed25519Signature(s) => s.try_into()?,
             AccountSignature::MultiEd25519Signature(s) => s.try_into()?,
+            AccountSignature::Secp256r1EcdsaSignature(s) => s.try_into()?,
+            AccountSignature::WebAuthnSignature(s) => s.try_into()?,
         })
     }
 }
@@ -827,6 +922,59 @@ impl TryFrom<MultiAgentSignature> for TransactionAuthenticator {
     }
 }
 
+/// A transaction with a single sender, authenticated by any of the supported account signatures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct SingleSenderSignature {
+    pub sender: AccountSignature,
+}
+
+impl TryFrom<SingleSenderSignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SingleSenderSignature) -> Result<Self, Self::Error> {
+        Ok(TransactionAuthenticator::single_sender(
+            value.sender.try_into()?,
+        ))
+    }
+}
+
+/// A sponsored transaction, where the fee payer pays for gas on behalf of the sender.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct FeePayerSignature {
+    pub sender: AccountSignature,
+    pub secondary_signer_addresses: Vec<Address>,
+    pub secondary_signers: Vec<AccountSignature>,
+    pub fee_payer_address: Address,
+    pub fee_payer_signer: AccountSignature,
+}
+
+impl TryFrom<FeePayerSignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: FeePayerSignature) -> Result<Self, Self::Error> {
+        let FeePayerSignature {
+            sender,
+            secondary_signer_addresses,
+            secondary_signers,
+            fee_payer_address,
+            fee_payer_signer,
+        } = value;
+        Ok(TransactionAuthenticator::fee_payer(
+            sender.try_into()?,
+            secondary_signer_addresses
+                .into_iter()
+                .map(|a| a.into())
+                .collect(),
+            secondary_signers
+                .into_iter()
+                .map(|s| s.try_into())
+                .collect::<anyhow::Result<_>>()?,
+            fee_payer_address.into(),
+            fee_payer_signer.try_into()?,
+        ))
+    }
+}
+
 impl From<(&Ed25519PublicKey, &ed25519::Ed25519Signature)> for Ed25519Signature {
     fn from((pk, sig): (&Ed25519PublicKey, &ed25519::Ed25519Signature)) -> Self {
         Self {
@@ -865,6 +1013,36 @@ impl
     }
 }
 
+impl
+    From<(
+        &Secp256r1EcdsaPublicKey,
+        &secp256r1_ecdsa::Secp256r1EcdsaSignature,
+    )> for Secp256r1EcdsaSignature
+{
+    fn from(
+        (pk, sig): (
+            &Secp256r1EcdsaPublicKey,
+            &secp256r1_ecdsa::Secp256r1EcdsaSignature,
+        ),
+    ) -> Self {
+        Self {
+            public_key: pk.to_bytes().to_vec().into(),
+            signature: sig.to_bytes().to_vec().into(),
+        }
+    }
+}
+
+impl From<(&Secp256r1EcdsaPublicKey, &webauthn::WebAuthnSignature)> for WebAuthnSignature {
+    fn from((pk, sig): (&Secp256r1EcdsaPublicKey, &webauthn::WebAuthnSignature)) -> Self {
+        Self {
+            public_key: pk.to_bytes().to_vec().into(),
+            signature: sig.signature.to_bytes().to_vec().into(),
+            authenticator_data: sig.authenticator_data.clone().into(),
+            client_data_json: sig.client_data_json.clone().into(),
+        }
+    }
+}
+
 impl From<&AccountAuthenticator> for AccountSignature {
     fn from(auth: &AccountAuthenticator) -> Self {
         use AccountAuthenticator::*;
@@ -877,6 +1055,14 @@ impl From<&AccountAuthenticator> for AccountSignature {
                 public_key,
                 signature,
             } => Self::MultiEd25519Signature((public_key, signature).into()),
+            Secp256r1Ecdsa {
+                public_key,
+                signature,
+            } => Self::Secp256r1EcdsaSignature((public_key, signature).into()),
+            WebAuthn {
+                public_key,
+                signature,
+            } => Self::WebAuthnSignature((public_key, signature).into()),
         }
     }
 }
@@ -922,6 +1108,25 @@ impl From<TransactionAuthenticator> for TransactionSignature {
             } => Self::MultiAgentSignature(
                 (sender, secondary_signer_addresses, secondary_signers).into(),
             ),
+            SingleSender { sender } => Self::SingleSenderSignature(SingleSenderSignature {
+                sender: sender.into(),
+            }),
+            FeePayer {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer_signer,
+            } => Self::FeePayerSignature(FeePayerSignature {
+                sender: sender.into(),
+                secondary_signer_addresses: secondary_signer_addresses
+                    .iter()
+                    .map(|address| (*address).into())
+                    .collect(),
+                secondary_signers: secondary_signers.iter().map(|s| s.into()).collect(),
+                fee_payer_address: (*fee_payer_address).into(),
+                fee_payer_signer: fee_payer_signer.into(),
+            }),
         }
     }
 }
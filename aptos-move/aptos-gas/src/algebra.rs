@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use move_core_types::gas_algebra::{GasQuantity, InternalGasUnit, UnitDiv};
+use move_core_types::gas_algebra::{Arg, Byte, GasQuantity, InternalGasUnit, UnitDiv};
 
 pub use gas_algebra_ext::{
     AbstractValueSize, AbstractValueSizePerArg, AbstractValueUnit, InternalGasPerAbstractValueUnit,
@@ -20,3 +20,8 @@ pub type GasScalingFactor = GasQuantity<UnitDiv<InternalGasUnit, GasUnit>>;
 pub type Fee = GasQuantity<Octa>;
 
 pub type FeePerGasUnit = GasQuantity<UnitDiv<Octa, GasUnit>>;
+
+/// Fee per state slot. Like new items in the write set gas, slots are counted as args.
+pub type FeePerSlot = GasQuantity<UnitDiv<Octa, Arg>>;
+
+pub type FeePerByte = GasQuantity<UnitDiv<Octa, Byte>>;
@@ -25,6 +25,40 @@ crate::natives::define_gas_parameters_for_natives!(GasParameters, "aptos_framewo
     [.bls12381.per_msg_hashing, "bls12381.per_msg_hashing", 1],
     [.bls12381.per_byte_hashing, "bls12381.per_byte_hashing", 1],
 
+    [.bls12381_algebra.scalar.deserialize, optional "bls12381_algebra.scalar.deserialize", 1],
+    [.bls12381_algebra.scalar.add, optional "bls12381_algebra.scalar.add", 1],
+    [.bls12381_algebra.scalar.sub, optional "bls12381_algebra.scalar.sub", 1],
+    [.bls12381_algebra.scalar.mul, optional "bls12381_algebra.scalar.mul", 1],
+    [.bls12381_algebra.scalar.neg, optional "bls12381_algebra.scalar.neg", 1],
+    [.bls12381_algebra.scalar.inv, optional "bls12381_algebra.scalar.inv", 1],
+
+    [.bls12381_algebra.g1.constant, optional "bls12381_algebra.g1.constant", 1],
+    [.bls12381_algebra.g1.deserialize, optional "bls12381_algebra.g1.deserialize", 1],
+    [.bls12381_algebra.g1.compress, optional "bls12381_algebra.g1.compress", 1],
+    [.bls12381_algebra.g1.add, optional "bls12381_algebra.g1.add", 1],
+    [.bls12381_algebra.g1.neg, optional "bls12381_algebra.g1.neg", 1],
+    [.bls12381_algebra.g1.scalar_mul, optional "bls12381_algebra.g1.scalar_mul", 1],
+    [.bls12381_algebra.g1.hash_to_base, optional "bls12381_algebra.g1.hash_to_base", 1],
+    [.bls12381_algebra.g1.hash_to_per_byte, optional "bls12381_algebra.g1.hash_to_per_byte", 1],
+
+    [.bls12381_algebra.g2.constant, optional "bls12381_algebra.g2.constant", 1],
+    [.bls12381_algebra.g2.deserialize, optional "bls12381_algebra.g2.deserialize", 1],
+    [.bls12381_algebra.g2.compress, optional "bls12381_algebra.g2.compress", 1],
+    [.bls12381_algebra.g2.add, optional "bls12381_algebra.g2.add", 1],
+    [.bls12381_algebra.g2.neg, optional "bls12381_algebra.g2.neg", 1],
+    [.bls12381_algebra.g2.scalar_mul, optional "bls12381_algebra.g2.scalar_mul", 1],
+    [.bls12381_algebra.g2.hash_to_base, optional "bls12381_algebra.g2.hash_to_base", 1],
+    [.bls12381_algebra.g2.hash_to_per_byte, optional "bls12381_algebra.g2.hash_to_per_byte", 1],
+
+    [.bls12381_algebra.gt.constant, optional "bls12381_algebra.gt.constant", 1],
+    [.bls12381_algebra.gt.deserialize, optional "bls12381_algebra.gt.deserialize", 1],
+    [.bls12381_algebra.gt.mul, optional "bls12381_algebra.gt.mul", 1],
+    [.bls12381_algebra.gt.inv, optional "bls12381_algebra.gt.inv", 1],
+    [.bls12381_algebra.gt.pow, optional "bls12381_algebra.gt.pow", 1],
+
+    [.bls12381_algebra.per_miller_loop, optional "bls12381_algebra.per_miller_loop", 1],
+    [.bls12381_algebra.final_exponentiation, optional "bls12381_algebra.final_exponentiation", 1],
+
     [.ed25519.base, "signature.base", 1],
     [.ed25519.per_pubkey_deserialize, "signature.per_pubkey_deserialize", 1],
     [.ed25519.per_pubkey_small_order_check, "signature.per_pubkey_small_order_check", 1],
@@ -74,6 +108,18 @@ crate::natives::define_gas_parameters_for_natives!(GasParameters, "aptos_framewo
     [.hash.keccak256.base, optional "hash.keccak256.base", 1],
     [.hash.keccak256.per_byte, optional "hash.keccak256.per_byte", 1],
 
+    [.hash.sha2_512.base, optional "hash.sha2_512.base", 1],
+    [.hash.sha2_512.per_byte, optional "hash.sha2_512.per_byte", 1],
+
+    [.hash.sha3_512.base, optional "hash.sha3_512.base", 1],
+    [.hash.sha3_512.per_byte, optional "hash.sha3_512.per_byte", 1],
+
+    [.hash.ripemd160.base, optional "hash.ripemd160.base", 1],
+    [.hash.ripemd160.per_byte, optional "hash.ripemd160.per_byte", 1],
+
+    [.hash.blake2b_256.base, optional "hash.blake2b_256.base", 1],
+    [.hash.blake2b_256.per_byte, optional "hash.blake2b_256.per_byte", 1],
+
     [.type_info.type_of.base, "type_info.type_of.base", 1],
     [.type_info.type_of.per_byte_in_str, "type_info.type_of.per_abstract_memory_unit", 1],
     [.type_info.type_name.base, "type_info.type_name.base", 1],
@@ -5,8 +5,10 @@
 //! parameters and traits to help manipulate them.
 
 use crate::{
-    algebra::Gas, instr::InstructionGasParameters, misc::MiscGasParameters,
-    transaction::TransactionGasParameters,
+    algebra::{FeePerGasUnit, Gas},
+    instr::InstructionGasParameters,
+    misc::MiscGasParameters,
+    transaction::{StorageFee, TransactionGasParameters},
 };
 use aptos_types::{state_store::state_key::StateKey, write_set::WriteOp};
 use move_binary_format::errors::{Location, PartialVMError, PartialVMResult, VMResult};
@@ -143,6 +145,37 @@ impl InitialGasSchedule for AptosGasParameters {
     }
 }
 
+/// A gas meter that, in addition to the charges made by the Move VM, can charge the
+/// transaction-level costs, such as the intrinsic gas and the write set gas.
+///
+/// This is implemented by [`AptosGasMeter`] and by gas meters wrapping it, e.g., the
+/// [`GasProfiler`](crate::GasProfiler).
+pub trait TransactionGasMeter: GasMeter {
+    /// Returns the remaining balance, rounded down to external gas units.
+    fn balance(&self) -> Gas;
+
+    /// Returns the remaining balance, measured in internal gas units.
+    fn balance_internal(&self) -> InternalGas;
+
+    fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()>;
+
+    fn charge_write_set_gas<'a>(
+        &mut self,
+        ops: impl IntoIterator<Item = (&'a StateKey, &'a WriteOp)>,
+    ) -> VMResult<()>;
+
+    /// Charges the storage fee of a write set, converted into gas units at the given gas unit
+    /// price, and returns the fee to hold as a deposit along with the refund for the state it
+    /// frees, in Octas.
+    ///
+    /// Each write op comes with the size of the state value it overwrites or deletes, if any.
+    fn charge_storage_fee<'a>(
+        &mut self,
+        ops: impl IntoIterator<Item = (&'a StateKey, &'a WriteOp, Option<NumBytes>)>,
+        gas_unit_price: FeePerGasUnit,
+    ) -> VMResult<StorageFee>;
+}
+
 /// The official gas meter used inside the Aptos VM.
 /// It maintains an internal gas counter, measured in internal gas units, and carries an environment
 /// consisting all the gas parameters, which it can lookup when performing gas calcuations.
@@ -161,11 +194,6 @@ impl AptosGasMeter {
         }
     }
 
-    pub fn balance(&self) -> Gas {
-        self.balance
-            .to_unit_round_down_with_params(&self.gas_params.txn)
-    }
-
     #[inline]
     fn charge(&mut self, amount: InternalGas) -> PartialVMResult<()> {
         match self.balance.checked_sub(amount) {
@@ -469,17 +497,47 @@ impl GasMeter for AptosGasMeter {
     }
 }
 
-impl AptosGasMeter {
-    pub fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()> {
+impl TransactionGasMeter for AptosGasMeter {
+    fn balance(&self) -> Gas {
+        self.balance
+            .to_unit_round_down_with_params(&self.gas_params.txn)
+    }
+
+    fn balance_internal(&self) -> InternalGas {
+        self.balance
+    }
+
+    fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()> {
         let cost = self.gas_params.txn.calculate_intrinsic_gas(txn_size);
         self.charge(cost).map_err(|e| e.finish(Location::Undefined))
     }
 
-    pub fn charge_write_set_gas<'a>(
+    fn charge_write_set_gas<'a>(
         &mut self,
         ops: impl IntoIterator<Item = (&'a StateKey, &'a WriteOp)>,
     ) -> VMResult<()> {
         let cost = self.gas_params.txn.calculate_write_set_gas(ops);
         self.charge(cost).map_err(|e| e.finish(Location::Undefined))
     }
+
+    fn charge_storage_fee<'a>(
+        &mut self,
+        ops: impl IntoIterator<Item = (&'a StateKey, &'a WriteOp, Option<NumBytes>)>,
+        gas_unit_price: FeePerGasUnit,
+    ) -> VMResult<StorageFee> {
+        let fee = self.gas_params.txn.calculate_storage_fee(ops);
+
+        // The fee is paid in gas units, rounded up. There is no way to pay for it when the gas
+        // unit price is zero, so it is waived then, and so is the refund.
+        let charge = u64::from(fee.charge);
+        let gas = match u64::from(gas_unit_price) {
+            0 => return Ok(StorageFee::zero()),
+            price => Gas::new(charge / price + u64::from(charge % price != 0)),
+        };
+        let cost = gas.to_unit_with_params(&self.gas_params.txn);
+        self.charge(cost)
+            .map_err(|e| e.finish(Location::Undefined))?;
+
+        Ok(fee)
+    }
 }
@@ -0,0 +1,598 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains a gas meter that wraps another one and records where the gas went:
+//! per Move function, per native function and per storage operation.
+//!
+//! The recorded [`GasProfile`] can be exported as folded stacks, which can be rendered as a
+//! flamegraph by tools such as `inferno-flamegraph` or `flamegraph.pl`, or as a summary table.
+
+use crate::{
+    algebra::{FeePerGasUnit, Gas},
+    gas_meter::TransactionGasMeter,
+    transaction::StorageFee,
+};
+use aptos_types::{access_path::Path, state_store::state_key::StateKey, write_set::WriteOp};
+use move_binary_format::errors::{PartialVMResult, VMResult};
+use move_core_types::{
+    gas_algebra::{InternalGas, NumArgs, NumBytes},
+    language_storage::ModuleId,
+};
+use move_vm_types::{
+    gas::{GasMeter, SimpleInstruction},
+    views::{TypeView, ValueView},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+const INTRINSIC_FRAME: &str = "[intrinsic]";
+const LOAD_RESOURCE_FRAME: &str = "[load resource]";
+const STORAGE_FEE_FRAME: &str = "[storage fee]";
+
+/// The gas charged during the execution of a transaction, broken down by call stack.
+///
+/// All amounts are measured in internal gas units, except for the storage fee.
+#[derive(Clone, Debug, Default)]
+pub struct GasProfile {
+    /// Gas charged to each call stack, where the outermost frame comes first.
+    stacks: BTreeMap<Vec<String>, InternalGas>,
+    /// Number of times each function has been called.
+    calls: BTreeMap<String, u64>,
+    /// Functions that have been recognized as native functions.
+    natives: BTreeSet<String>,
+    /// The storage fee deposited and refunded, if the write set has been charged for.
+    storage_fee: Option<StorageFee>,
+}
+
+/// A line of the summary table.
+struct SummaryEntry {
+    calls: u64,
+    exclusive: InternalGas,
+    inclusive: InternalGas,
+}
+
+impl GasProfile {
+    /// Returns the total amount of gas recorded.
+    pub fn total(&self) -> InternalGas {
+        self.stacks
+            .values()
+            .fold(InternalGas::zero(), |total, cost| total + *cost)
+    }
+
+    /// Returns the storage fee deposited and refunded, in Octas, if the write set has been
+    /// charged for.
+    pub fn storage_fee(&self) -> Option<StorageFee> {
+        self.storage_fee
+    }
+
+    /// Returns the gas charged to each call stack, where the outermost frame comes first.
+    pub fn stacks(&self) -> &BTreeMap<Vec<String>, InternalGas> {
+        &self.stacks
+    }
+
+    /// Exports the profile in the folded stack format, i.e., one `frame;frame;frame cost` line
+    /// per call stack, which is understood by most flamegraph tools.
+    pub fn to_folded_stacks(&self) -> String {
+        let mut folded = String::new();
+        for (stack, cost) in &self.stacks {
+            if *cost == InternalGas::zero() {
+                continue;
+            }
+            writeln!(folded, "{} {}", stack.join(";"), u64::from(*cost)).unwrap();
+        }
+        folded
+    }
+
+    /// Renders a table listing every function and storage operation, ordered by the gas spent
+    /// in the function itself (exclusive) and including its callees (inclusive).
+    pub fn summary_table(&self) -> String {
+        let mut entries: BTreeMap<&str, SummaryEntry> = BTreeMap::new();
+        for (stack, cost) in &self.stacks {
+            // Recursive calls shouldn't count the same gas multiple times.
+            let mut seen = BTreeSet::new();
+            for frame in stack {
+                if seen.insert(frame.as_str()) {
+                    let entry = entries.entry(frame).or_insert_with(|| SummaryEntry {
+                        calls: self.calls.get(frame).copied().unwrap_or(0),
+                        exclusive: InternalGas::zero(),
+                        inclusive: InternalGas::zero(),
+                    });
+                    entry.inclusive += *cost;
+                }
+            }
+            if let Some(entry) = stack
+                .last()
+                .and_then(|frame| entries.get_mut(frame.as_str()))
+            {
+                entry.exclusive += *cost;
+            }
+        }
+
+        let mut entries = entries.into_iter().collect::<Vec<_>>();
+        entries.sort_by(|(name1, entry1), (name2, entry2)| {
+            u64::from(entry2.exclusive)
+                .cmp(&u64::from(entry1.exclusive))
+                .then_with(|| u64::from(entry2.inclusive).cmp(&u64::from(entry1.inclusive)))
+                .then_with(|| name1.cmp(name2))
+        });
+
+        let total = u64::from(self.total());
+        let percentage = |cost: InternalGas| match total {
+            0 => 0.0,
+            total => u64::from(cost) as f64 * 100.0 / total as f64,
+        };
+
+        let mut table = String::new();
+        writeln!(
+            table,
+            "{:>16} {:>8} {:>16} {:>8} {:>8}  name",
+            "exclusive", "%", "inclusive", "%", "calls"
+        )
+        .unwrap();
+        for (name, entry) in entries {
+            let calls = match entry.calls {
+                0 => "-".to_string(),
+                calls => calls.to_string(),
+            };
+            let kind = if self.natives.contains(name) {
+                " (native)"
+            } else {
+                ""
+            };
+            writeln!(
+                table,
+                "{:>16} {:>7.2}% {:>16} {:>7.2}% {:>8}  {}{}",
+                u64::from(entry.exclusive),
+                percentage(entry.exclusive),
+                u64::from(entry.inclusive),
+                percentage(entry.inclusive),
+                calls,
+                name,
+                kind,
+            )
+            .unwrap();
+        }
+        writeln!(
+            table,
+            "{:>16} {:>7.2}%  total (internal gas units)",
+            total, 100.0
+        )
+        .unwrap();
+        if let Some(storage_fee) = self.storage_fee {
+            writeln!(
+                table,
+                "storage fee: {} Octas deposited, {} Octas refunded",
+                u64::from(storage_fee.charge),
+                u64::from(storage_fee.refund),
+            )
+            .unwrap();
+        }
+        table
+    }
+}
+
+/// A gas meter that forwards all charges to the underlying gas meter and records them in a
+/// [`GasProfile`].
+///
+/// The Move VM doesn't tell the gas meter when a frame is entered or left, so the call stack is
+/// reconstructed from the charges: a frame is pushed when a call is charged and popped when the
+/// `Ret` instruction is charged. A call immediately followed by a native function charge is
+/// recorded as a call to a native function.
+pub struct GasProfiler<G> {
+    base: G,
+    /// Names of the frames on the call stack, where the outermost frame comes first.
+    frames: Vec<String>,
+    /// A call that has been charged, while it's not known yet whether the callee is native.
+    pending_call: Option<(String, InternalGas)>,
+    profile: GasProfile,
+}
+
+impl<G: TransactionGasMeter> GasProfiler<G> {
+    /// Creates a profiler whose outermost frame is the entry function being executed.
+    pub fn new_function(base: G, module_id: &ModuleId, func_name: &str) -> Self {
+        Self::new(base, function_name(module_id, func_name))
+    }
+
+    /// Creates a profiler whose outermost frame is a script.
+    pub fn new_script(base: G) -> Self {
+        Self::new(base, "script".to_string())
+    }
+
+    /// Creates a profiler with an arbitrary name for its outermost frame.
+    pub fn new(base: G, root: String) -> Self {
+        let mut profile = GasProfile::default();
+        profile.calls.insert(root.clone(), 1);
+        Self {
+            base,
+            frames: vec![root],
+            pending_call: None,
+            profile,
+        }
+    }
+
+    /// Returns the profile recorded so far.
+    pub fn profile(&self) -> &GasProfile {
+        &self.profile
+    }
+
+    /// Consumes the profiler, returning the profile along with the underlying gas meter.
+    pub fn finish(self) -> (GasProfile, G) {
+        (self.profile, self.base)
+    }
+
+    /// Forwards a charge to the underlying gas meter, measuring its cost.
+    fn measure<R>(&mut self, charge: impl FnOnce(&mut G) -> R) -> (R, InternalGas) {
+        let balance = self.base.balance_internal();
+        let res = charge(&mut self.base);
+        let cost = balance
+            .checked_sub(self.base.balance_internal())
+            .unwrap_or_else(InternalGas::zero);
+        (res, cost)
+    }
+
+    fn record(&mut self, leaf: Option<&str>, cost: InternalGas) {
+        let mut stack = self.frames.clone();
+        stack.extend(leaf.map(str::to_string));
+        *self
+            .profile
+            .stacks
+            .entry(stack)
+            .or_insert_with(InternalGas::zero) += cost;
+    }
+
+    /// Enters the frame of the pending call, if any: as something other than a native function
+    /// charge follows the call, the callee must be a Move function.
+    fn enter_pending_call(&mut self) {
+        if let Some((name, cost)) = self.pending_call.take() {
+            self.frames.push(name);
+            self.record(None, cost);
+        }
+    }
+
+    fn call(&mut self, module_id: &ModuleId, func_name: &str, cost: InternalGas) {
+        self.enter_pending_call();
+        let name = function_name(module_id, func_name);
+        *self.profile.calls.entry(name.clone()).or_insert(0) += 1;
+        self.pending_call = Some((name, cost));
+    }
+
+    /// Forwards an instruction charge to the underlying gas meter, recording it in the current
+    /// frame.
+    fn instr(&mut self, charge: impl FnOnce(&mut G) -> PartialVMResult<()>) -> PartialVMResult<()> {
+        self.enter_pending_call();
+        let (res, cost) = self.measure(charge);
+        self.record(None, cost);
+        res
+    }
+}
+
+fn function_name(module_id: &ModuleId, func_name: &str) -> String {
+    format!("{}::{}", module_id.short_str_lossless(), func_name)
+}
+
+fn write_op_name(key: &StateKey, op: &WriteOp) -> String {
+    let kind = match op {
+        WriteOp::Creation(_) => "create",
+        WriteOp::Modification(_) => "modify",
+        WriteOp::Deletion => "delete",
+    };
+    let key = match key {
+        StateKey::AccessPath(access_path) => match access_path.get_path() {
+            Path::Code(module_id) => format!("code {}", module_id.short_str_lossless()),
+            Path::Resource(struct_tag) => format!(
+                "{} at {}",
+                struct_tag,
+                access_path.address.short_str_lossless()
+            ),
+        },
+        StateKey::TableItem { handle, .. } => {
+            format!("table item of {}", handle.0.short_str_lossless())
+        }
+        StateKey::Raw(_) => "raw".to_string(),
+    };
+    format!("[{}] {}", kind, key)
+}
+
+impl<G: TransactionGasMeter> GasMeter for GasProfiler<G> {
+    fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
+        let is_ret = matches!(instr, SimpleInstruction::Ret);
+        let res = self.instr(|base| base.charge_simple_instr(instr));
+        // Never leave the outermost frame, which doesn't correspond to a charged call.
+        if is_ret && self.frames.len() > 1 {
+            self.frames.pop();
+        }
+        res
+    }
+
+    fn charge_native_function(&mut self, amount: InternalGas) -> PartialVMResult<()> {
+        let (res, cost) = self.measure(|base| base.charge_native_function(amount));
+        match self.pending_call.take() {
+            Some((name, call_cost)) => {
+                self.record(Some(&name), call_cost + cost);
+                self.profile.natives.insert(name);
+            }
+            None => self.record(None, cost),
+        }
+        res
+    }
+
+    fn charge_load_resource(&mut self, loaded: Option<NumBytes>) -> PartialVMResult<()> {
+        self.enter_pending_call();
+        let (res, cost) = self.measure(|base| base.charge_load_resource(loaded));
+        self.record(Some(LOAD_RESOURCE_FRAME), cost);
+        res
+    }
+
+    fn charge_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let (res, cost) = self.measure(|base| base.charge_call(module_id, func_name, args));
+        self.call(module_id, func_name, cost);
+        res
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let (res, cost) =
+            self.measure(|base| base.charge_call_generic(module_id, func_name, ty_args, args));
+        self.call(module_id, func_name, cost);
+        res
+    }
+
+    fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_ld_const(size))
+    }
+
+    fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_copy_loc(val))
+    }
+
+    fn charge_move_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_move_loc(val))
+    }
+
+    fn charge_store_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_store_loc(val))
+    }
+
+    fn charge_pack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_pack(is_generic, args))
+    }
+
+    fn charge_unpack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_unpack(is_generic, args))
+    }
+
+    fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_read_ref(val))
+    }
+
+    fn charge_write_ref(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_write_ref(val))
+    }
+
+    fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_eq(lhs, rhs))
+    }
+
+    fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_neq(lhs, rhs))
+    }
+
+    fn charge_borrow_global(
+        &mut self,
+        is_mut: bool,
+        is_generic: bool,
+        ty: impl TypeView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_borrow_global(is_mut, is_generic, ty, is_success))
+    }
+
+    fn charge_exists(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        exists: bool,
+    ) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_exists(is_generic, ty, exists))
+    }
+
+    fn charge_move_from(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_move_from(is_generic, ty, val))
+    }
+
+    fn charge_move_to(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        val: impl ValueView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_move_to(is_generic, ty, val, is_success))
+    }
+
+    fn charge_vec_pack<'a>(
+        &mut self,
+        ty: impl TypeView + 'a,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_vec_pack(ty, args))
+    }
+
+    fn charge_vec_unpack(
+        &mut self,
+        ty: impl TypeView,
+        expect_num_elements: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_vec_unpack(ty, expect_num_elements))
+    }
+
+    fn charge_vec_len(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_vec_len(ty))
+    }
+
+    fn charge_vec_borrow(
+        &mut self,
+        is_mut: bool,
+        ty: impl TypeView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_vec_borrow(is_mut, ty, is_success))
+    }
+
+    fn charge_vec_push_back(
+        &mut self,
+        ty: impl TypeView,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_vec_push_back(ty, val))
+    }
+
+    fn charge_vec_pop_back(
+        &mut self,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_vec_pop_back(ty, val))
+    }
+
+    fn charge_vec_swap(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        self.instr(|base| base.charge_vec_swap(ty))
+    }
+}
+
+impl<G: TransactionGasMeter> TransactionGasMeter for GasProfiler<G> {
+    fn balance(&self) -> Gas {
+        self.base.balance()
+    }
+
+    fn balance_internal(&self) -> InternalGas {
+        self.base.balance_internal()
+    }
+
+    fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()> {
+        let (res, cost) = self.measure(|base| base.charge_intrinsic_gas_for_transaction(txn_size));
+        self.record(Some(INTRINSIC_FRAME), cost);
+        res
+    }
+
+    fn charge_write_set_gas<'a>(
+        &mut self,
+        ops: impl IntoIterator<Item = (&'a StateKey, &'a WriteOp)>,
+    ) -> VMResult<()> {
+        // The execution is over by the time the write set is charged, so the storage
+        // operations are recorded in the outermost frame.
+        self.pending_call = None;
+        self.frames.truncate(1);
+
+        // The write set gas is the sum of the gas of the individual operations, so they can be
+        // charged one by one.
+        for (key, op) in ops {
+            let (res, cost) = self.measure(|base| base.charge_write_set_gas([(key, op)]));
+            self.record(Some(&write_op_name(key, op)), cost);
+            res?;
+        }
+        Ok(())
+    }
+
+    fn charge_storage_fee<'a>(
+        &mut self,
+        ops: impl IntoIterator<Item = (&'a StateKey, &'a WriteOp, Option<NumBytes>)>,
+        gas_unit_price: FeePerGasUnit,
+    ) -> VMResult<StorageFee> {
+        let (res, cost) = self.measure(|base| base.charge_storage_fee(ops, gas_unit_price));
+        self.frames.truncate(1);
+        self.record(Some(STORAGE_FEE_FRAME), cost);
+        if let Ok(storage_fee) = &res {
+            self.profile.storage_fee = Some(*storage_fee);
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AptosGasMeter, AptosGasParameters, InitialGasSchedule};
+    use move_core_types::{account_address::AccountAddress, ident_str};
+    use move_vm_types::values::Value;
+
+    fn no_args() -> impl ExactSizeIterator<Item = Value> {
+        Vec::new().into_iter()
+    }
+
+    #[test]
+    fn profile_calls_and_natives() {
+        let module_id = ModuleId::new(AccountAddress::ONE, ident_str!("m").to_owned());
+        let base = AptosGasMeter::new(AptosGasParameters::initial(), 1_000_000);
+        let initial_balance = base.balance_internal();
+        let mut profiler = GasProfiler::new_function(base, &module_id, "entry");
+
+        profiler
+            .charge_intrinsic_gas_for_transaction(NumBytes::new(100))
+            .unwrap();
+        profiler
+            .charge_simple_instr(SimpleInstruction::LdU8)
+            .unwrap();
+        profiler.charge_call(&module_id, "f", no_args()).unwrap();
+        profiler
+            .charge_simple_instr(SimpleInstruction::LdU8)
+            .unwrap();
+        profiler
+            .charge_call(&module_id, "native", no_args())
+            .unwrap();
+        profiler.charge_native_function(100.into()).unwrap();
+        profiler
+            .charge_simple_instr(SimpleInstruction::Ret)
+            .unwrap();
+        profiler
+            .charge_simple_instr(SimpleInstruction::Ret)
+            .unwrap();
+
+        let (profile, base) = profiler.finish();
+        let stacks = profile
+            .stacks()
+            .keys()
+            .map(|stack| stack.join(";"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stacks,
+            vec![
+                "0x1::m::entry",
+                "0x1::m::entry;0x1::m::f",
+                "0x1::m::entry;0x1::m::f;0x1::m::native",
+                "0x1::m::entry;[intrinsic]",
+            ]
+        );
+        assert_eq!(
+            Some(profile.total()),
+            initial_balance.checked_sub(base.balance_internal())
+        );
+        assert!(profile.to_folded_stacks().lines().count() >= 3);
+        assert!(profile.summary_table().contains("0x1::m::native (native)"));
+    }
+}
@@ -27,6 +27,7 @@ mod params;
 mod algebra;
 mod aptos_framework;
 mod gas_meter;
+mod gas_profiler;
 mod instr;
 mod misc;
 mod move_stdlib;
@@ -36,12 +37,13 @@ mod transaction;
 pub use algebra::*;
 pub use gas_meter::{
     AptosGasMeter, AptosGasParameters, FromOnChainGasSchedule, InitialGasSchedule,
-    NativeGasParameters, ToOnChainGasSchedule,
+    NativeGasParameters, ToOnChainGasSchedule, TransactionGasMeter,
 };
+pub use gas_profiler::{GasProfile, GasProfiler};
 pub use instr::InstructionGasParameters;
 pub use misc::{AbstractValueSizeGasParameters, MiscGasParameters};
 pub use move_core_types::gas_algebra::{
     Arg, Byte, GasQuantity, InternalGas, InternalGasPerArg, InternalGasPerByte, InternalGasUnit,
     NumArgs, NumBytes, UnitDiv,
 };
-pub use transaction::TransactionGasParameters;
+pub use transaction::{StorageFee, TransactionGasParameters};
@@ -4,7 +4,7 @@
 //! This module defines all the gas parameters for transactions, along with their initial values
 //! in the genesis and a mapping between the Rust representation and the on-chain gas schedule.
 
-use crate::algebra::{FeePerGasUnit, Gas, GasScalingFactor, GasUnit};
+use crate::algebra::{Fee, FeePerByte, FeePerGasUnit, FeePerSlot, Gas, GasScalingFactor, GasUnit};
 use aptos_types::{state_store::state_key::StateKey, write_set::WriteOp};
 use move_core_types::gas_algebra::{
     InternalGas, InternalGasPerArg, InternalGasPerByte, InternalGasUnit, NumArgs, NumBytes,
@@ -96,9 +96,44 @@ crate::params::define_gas_parameters!(
             "write_data.per_byte_in_val",
             100
         ],
+        // Storage fees, charged in Octas for the long-lived growth of the state, on top of the
+        // gas for writing the data, and refunded when the state shrinks.
+        // The size of a slot is the size of its key plus the size of its value.
+        // These are optional so that gas schedules predating them remain valid.
+        [
+            storage_fee_per_state_slot_create: FeePerSlot,
+            optional "storage_fee.per_state_slot_create",
+            0
+        ],
+        [
+            storage_fee_per_state_byte: FeePerByte,
+            optional "storage_fee.per_state_byte",
+            0
+        ],
     ]
 );
 
+/// The storage fee of a write set: the fee charged for the state it allocates and the refund for
+/// the state it frees, both in Octas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageFee {
+    pub charge: Fee,
+    pub refund: Fee,
+}
+
+impl StorageFee {
+    pub fn zero() -> Self {
+        Self {
+            charge: Fee::zero(),
+            refund: Fee::zero(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        u64::from(self.charge) == 0 && u64::from(self.refund) == 0
+    }
+}
+
 impl TransactionGasParameters {
     // TODO(Gas): Right now we are relying on this to avoid div by zero errors when using the all-zero
     //            gas parameters. See if there's a better way we can handle this.
@@ -167,6 +202,64 @@ impl TransactionGasParameters {
 
         cost_ops + cost_new_items + cost_bytes
     }
+
+    /// Calculates the storage fee of a write set. Each write op comes with the size of the state
+    /// value it overwrites or deletes, or None if there is no such value.
+    ///
+    /// New slots are charged per slot and per byte, while growing slots are charged for the extra
+    /// bytes only. Conversely, deleting or shrinking slots is refunded at the same rates.
+    pub fn calculate_storage_fee<'a>(
+        &self,
+        ops: impl IntoIterator<Item = (&'a StateKey, &'a WriteOp, Option<NumBytes>)>,
+    ) -> StorageFee {
+        use WriteOp::*;
+
+        let mut num_slots_created = NumArgs::zero();
+        let mut num_slots_deleted = NumArgs::zero();
+        let mut num_bytes_allocated = NumBytes::zero();
+        let mut num_bytes_freed = NumBytes::zero();
+
+        for (key, op, old_value_size) in ops.into_iter() {
+            let key_size = NumBytes::new(
+                key.encode()
+                    .expect("Should be able to serialize state key")
+                    .len() as u64,
+            );
+            let old_size = old_value_size.map(|value_size| key_size + value_size);
+            let new_size = match op {
+                Creation(data) | Modification(data) => {
+                    Some(key_size + NumBytes::new(data.len() as u64))
+                }
+                Deletion => None,
+            };
+
+            match (old_size, new_size) {
+                (None, Some(new_size)) => {
+                    num_slots_created += 1.into();
+                    num_bytes_allocated += new_size;
+                }
+                (Some(old_size), None) => {
+                    num_slots_deleted += 1.into();
+                    num_bytes_freed += old_size;
+                }
+                (Some(old_size), Some(new_size)) => {
+                    if new_size > old_size {
+                        num_bytes_allocated += new_size.checked_sub(old_size).unwrap();
+                    } else {
+                        num_bytes_freed += old_size.checked_sub(new_size).unwrap();
+                    }
+                }
+                (None, None) => (),
+            }
+        }
+
+        StorageFee {
+            charge: self.storage_fee_per_state_slot_create * num_slots_created
+                + self.storage_fee_per_state_byte * num_bytes_allocated,
+            refund: self.storage_fee_per_state_slot_create * num_slots_deleted
+                + self.storage_fee_per_state_byte * num_bytes_freed,
+        }
+    }
 }
 
 impl ToUnitWithParams<InternalGasUnit> for GasUnit {
@@ -184,3 +277,42 @@ impl ToUnitFractionalWithParams<GasUnit> for InternalGasUnit {
         (1, params.scaling_factor().into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_fee() {
+        let mut params = TransactionGasParameters::zeros();
+        params.storage_fee_per_state_slot_create = 100.into();
+        params.storage_fee_per_state_byte = 1.into();
+
+        // Each key takes 10 bytes once encoded: a tag byte followed by the raw bytes.
+        let keys = (0..4u8)
+            .map(|i| StateKey::Raw(vec![i; 9]))
+            .collect::<Vec<_>>();
+        let ops = vec![
+            // A new slot of 20 bytes.
+            (&keys[0], WriteOp::Creation(vec![0; 10]), None),
+            // A slot growing by 5 bytes.
+            (&keys[1], WriteOp::Modification(vec![0; 10]), Some(5)),
+            // A deleted slot of 40 bytes.
+            (&keys[2], WriteOp::Deletion, Some(30)),
+            // A slot shrinking by 10 bytes.
+            (&keys[3], WriteOp::Modification(vec![0; 10]), Some(20)),
+        ];
+
+        let fee = params.calculate_storage_fee(
+            ops.iter()
+                .map(|(key, op, old_size)| (*key, op, old_size.map(NumBytes::new))),
+        );
+        assert_eq!(
+            fee,
+            StorageFee {
+                charge: (100 + 20 + 5).into(),
+                refund: (100 + 40 + 10).into(),
+            }
+        );
+    }
+}
@@ -25,7 +25,9 @@ use aptos_aggregator::{
     transaction::{ChangeSetExt, TransactionOutputExt},
 };
 use aptos_crypto::HashValue;
-use aptos_gas::AptosGasMeter;
+use aptos_gas::{
+    AptosGasMeter, GasProfile, GasProfiler, NumBytes, StorageFee, TransactionGasMeter,
+};
 use aptos_logger::prelude::*;
 use aptos_module_verifier::module_init::verify_module_init_function;
 use aptos_state_view::StateView;
@@ -34,21 +36,22 @@ use aptos_types::vm_status::AbortLocation;
 use aptos_types::{
     account_config,
     block_metadata::BlockMetadata,
-    on_chain_config::{new_epoch_event_key, GasSchedule, Version},
+    on_chain_config::{new_epoch_event_key, FeatureFlag, GasSchedule, Version},
     transaction::{
-        ChangeSet, ExecutionStatus, ModuleBundle, SignatureCheckedTransaction, SignedTransaction,
+        authenticator::Scheme, ChangeSet, EntryFunction, ExecutionStatus, ModuleBundle, Multisig,
+        MultisigTransactionPayload, Script, SignatureCheckedTransaction, SignedTransaction,
         Transaction, TransactionOutput, TransactionPayload, TransactionStatus, VMValidatorResult,
         WriteSetPayload,
     },
     vm_status::{StatusCode, VMStatus},
-    write_set::WriteSet,
+    write_set::{WriteOp, WriteSet},
 };
 use fail::fail_point;
-use framework::natives::code::PublishRequest;
+use framework::{check_module_compatibility, natives::code::PublishRequest};
 use move_deps::{
     move_binary_format::{
         access::ModuleAccess,
-        errors::{verification_error, Location, PartialVMError, VMResult},
+        errors::{verification_error, Location, PartialVMError, VMError, VMResult},
         CompiledModule, IndexKind,
     },
     move_core_types::{
@@ -153,7 +156,7 @@ impl AptosVM {
     pub fn failed_transaction_cleanup<S: MoveResolverExt>(
         &self,
         error_code: VMStatus,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
         txn_data: &TransactionMetadata,
         storage: &S,
         log_context: &AdapterLogSchema,
@@ -171,7 +174,7 @@ impl AptosVM {
     fn failed_transaction_cleanup_and_keep_vm_status<S: MoveResolverExt>(
         &self,
         error_code: VMStatus,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
         txn_data: &TransactionMetadata,
         storage: &S,
         log_context: &AdapterLogSchema,
@@ -226,8 +229,9 @@ impl AptosVM {
         &self,
         storage: &S,
         user_txn_change_set_ext: ChangeSetExt,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
         txn_data: &TransactionMetadata,
+        storage_fee: StorageFee,
         log_context: &AdapterLogSchema,
     ) -> Result<(VMStatus, TransactionOutputExt), VMStatus> {
         let storage_with_changes =
@@ -255,8 +259,13 @@ impl AptosVM {
             .0
             .new_session(&storage_with_changes, SessionId::txn_meta(txn_data));
 
-        self.0
-            .run_success_epilogue(&mut session, gas_meter.balance(), txn_data, log_context)?;
+        self.0.run_success_epilogue(
+            &mut session,
+            gas_meter.balance(),
+            storage_fee,
+            txn_data,
+            log_context,
+        )?;
 
         let epilogue_change_set_ext = session
             .finish()
@@ -291,7 +300,7 @@ impl AptosVM {
         &self,
         storage: &S,
         mut session: SessionExt<S>,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
         txn_data: &TransactionMetadata,
         payload: &TransactionPayload,
         log_context: &AdapterLogSchema,
@@ -351,29 +360,197 @@ impl AptosVM {
                         gas_meter,
                     )
                 }
-                TransactionPayload::ModuleBundle(_) => {
+                TransactionPayload::ModuleBundle(_) | TransactionPayload::Multisig(_) => {
                     return Err(VMStatus::Error(StatusCode::UNREACHABLE));
                 }
             }
             .map_err(|e| e.into_vm_status())?;
 
-            self.resolve_pending_code_publish(&mut session, gas_meter)?;
+            self.finish_user_session(storage, session, gas_meter, txn_data, log_context)
+        }
+    }
 
-            let session_output = session.finish().map_err(|e| e.into_vm_status())?;
-            let change_set_ext = session_output.into_change_set(&mut ())?;
+    /// Executes the next approved transaction of a multisig account, with the multisig account as
+    /// the signer. The sender must be one of the owners of the multisig account.
+    fn execute_multisig_transaction<S: MoveResolverExt + StateView>(
+        &self,
+        storage: &S,
+        mut session: SessionExt<S>,
+        gas_meter: &mut impl TransactionGasMeter,
+        txn_data: &TransactionMetadata,
+        payload: &Multisig,
+        log_context: &AdapterLogSchema,
+    ) -> Result<(VMStatus, TransactionOutputExt), VMStatus> {
+        fail_point!("move_adapter::execute_multisig_transaction", |_| {
+            Err(VMStatus::Error(
+                StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+            ))
+        });
 
-            // Charge gas for write set
-            gas_meter.charge_write_set_gas(change_set_ext.write_set().iter())?;
-            // TODO(Gas): Charge for aggregator writes
+        gas_meter
+            .charge_intrinsic_gas_for_transaction(txn_data.transaction_size())
+            .map_err(|e| e.into_vm_status())?;
 
-            self.success_transaction_cleanup(
-                storage,
-                change_set_ext,
+        // Check that the sender is allowed to execute the next transaction of the multisig account
+        // and get the payload to execute, which can be omitted from the transaction if it's already
+        // stored on chain.
+        let provided_payload = match &payload.transaction_payload {
+            Some(transaction_payload) => bcs::to_bytes(transaction_payload)
+                .map_err(|_| VMStatus::Error(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR))?,
+            None => vec![],
+        };
+        let return_values = session
+            .execute_function_bypass_visibility(
+                &MULTISIG_ACCOUNT_MODULE,
+                VALIDATE_MULTISIG_TRANSACTION,
+                vec![],
+                serialize_values(&vec![
+                    MoveValue::Signer(txn_data.sender()),
+                    MoveValue::Address(payload.multisig_address),
+                    MoveValue::vector_u8(provided_payload),
+                ]),
                 gas_meter,
-                txn_data,
-                log_context,
             )
-        }
+            .map_err(|e| e.into_vm_status())?;
+        let payload_bytes: Vec<u8> = return_values
+            .return_values
+            .first()
+            .and_then(|(bytes, _layout)| bcs::from_bytes(bytes).ok())
+            .ok_or(VMStatus::Error(
+                StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+            ))?;
+        let entry_function = match bcs::from_bytes::<MultisigTransactionPayload>(&payload_bytes) {
+            Ok(MultisigTransactionPayload::EntryFunction(entry_function)) => entry_function,
+            Err(_) => return Err(VMStatus::Error(StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT)),
+        };
+
+        // Execute the approved payload as the multisig account, in a session of its own. The
+        // validation above doesn't write anything, and if the payload fails its effects must be
+        // discarded without discarding the cleanup which resolves the transaction.
+        drop(session);
+        let mut payload_session = self.0.new_session(storage, SessionId::txn_meta(txn_data));
+        let payload_result = Self::execute_multisig_entry_function(
+            &mut payload_session,
+            gas_meter,
+            payload.multisig_address,
+            &entry_function,
+        );
+        let (mut session, cleanup_function_name, error_args) = match payload_result {
+            Ok(()) => (
+                payload_session,
+                SUCCESSFUL_TRANSACTION_EXECUTION_CLEANUP,
+                vec![],
+            ),
+            Err(err) if TransactionStatus::from(err.clone()).is_discarded() => return Err(err),
+            Err(err) => {
+                let (abort_location, error_type, error_code) = match &err {
+                    VMStatus::MoveAbort(location, code) => {
+                        (abort_location_name(location), "MoveAbort", *code)
+                    }
+                    VMStatus::ExecutionFailure {
+                        status_code,
+                        location,
+                        ..
+                    } => (
+                        abort_location_name(location),
+                        "ExecutionFailure",
+                        *status_code as u64,
+                    ),
+                    _ => (String::new(), "Error", err.status_code() as u64),
+                };
+                (
+                    self.0.new_session(storage, SessionId::txn_meta(txn_data)),
+                    FAILED_TRANSACTION_EXECUTION_CLEANUP,
+                    vec![
+                        MoveValue::vector_u8(abort_location.into_bytes()),
+                        MoveValue::vector_u8(error_type.as_bytes().to_vec()),
+                        MoveValue::U64(error_code),
+                    ],
+                )
+            }
+        };
+
+        // Resolve the transaction so it can't be executed again, whether or not its payload
+        // succeeded, so that it never blocks the transactions after it.
+        let mut cleanup_args = vec![
+            MoveValue::Address(txn_data.sender()),
+            MoveValue::Address(payload.multisig_address),
+            MoveValue::vector_u8(payload_bytes),
+        ];
+        cleanup_args.extend(error_args);
+        session
+            .execute_function_bypass_visibility(
+                &MULTISIG_ACCOUNT_MODULE,
+                cleanup_function_name,
+                vec![],
+                serialize_values(&cleanup_args),
+                gas_meter,
+            )
+            .map_err(|e| e.into_vm_status())?;
+
+        self.finish_user_session(storage, session, gas_meter, txn_data, log_context)
+    }
+
+    /// Executes the entry function approved by the owners of a multisig account, with the multisig
+    /// account as the signer.
+    fn execute_multisig_entry_function<S: MoveResolverExt>(
+        session: &mut SessionExt<S>,
+        gas_meter: &mut impl TransactionGasMeter,
+        multisig_address: AccountAddress,
+        entry_function: &EntryFunction,
+    ) -> Result<(), VMStatus> {
+        let function = session.load_function(
+            entry_function.module(),
+            entry_function.function(),
+            entry_function.ty_args(),
+        )?;
+        let args = transaction_arg_validation::validate_combine_signer_and_txn_args(
+            session,
+            vec![multisig_address],
+            entry_function.args().to_vec(),
+            &function,
+        )?;
+        session
+            .execute_entry_function(
+                entry_function.module(),
+                entry_function.function(),
+                entry_function.ty_args().to_vec(),
+                args,
+                gas_meter,
+            )
+            .map_err(|e| e.into_vm_status())?;
+        Ok(())
+    }
+
+    /// Finishes the session a user payload was executed in, charges gas for the writes it
+    /// produced and runs the success epilogue.
+    fn finish_user_session<S: MoveResolverExt + StateView>(
+        &self,
+        storage: &S,
+        mut session: SessionExt<S>,
+        gas_meter: &mut impl TransactionGasMeter,
+        txn_data: &TransactionMetadata,
+        log_context: &AdapterLogSchema,
+    ) -> Result<(VMStatus, TransactionOutputExt), VMStatus> {
+        self.resolve_pending_code_publish(&mut session, gas_meter)?;
+
+        let session_output = session.finish().map_err(|e| e.into_vm_status())?;
+        let change_set_ext = session_output.into_change_set(&mut ())?;
+
+        // Charge gas for write set
+        gas_meter.charge_write_set_gas(change_set_ext.write_set().iter())?;
+        // TODO(Gas): Charge for aggregator writes
+        let storage_fee =
+            Self::charge_storage_fee(storage, change_set_ext.write_set(), gas_meter, txn_data)?;
+
+        self.success_transaction_cleanup(
+            storage,
+            change_set_ext,
+            gas_meter,
+            txn_data,
+            storage_fee,
+            log_context,
+        )
     }
 
     fn verify_module_bundle<S: MoveResolverExt>(
@@ -407,7 +584,7 @@ impl AptosVM {
     fn execute_module_initialization<S: MoveResolverExt>(
         &self,
         session: &mut SessionExt<S>,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
         modules: &[CompiledModule],
         senders: &[AccountAddress],
     ) -> VMResult<()> {
@@ -462,7 +639,7 @@ impl AptosVM {
         &self,
         storage: &S,
         mut session: SessionExt<S>,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
         txn_data: &TransactionMetadata,
         modules: &ModuleBundle,
         log_context: &AdapterLogSchema,
@@ -499,15 +676,51 @@ impl AptosVM {
         // Charge gas for write set
         gas_meter.charge_write_set_gas(change_set_ext.write_set().iter())?;
         // TODO(Gas): Charge for aggregator writes
+        let storage_fee =
+            Self::charge_storage_fee(storage, change_set_ext.write_set(), gas_meter, txn_data)?;
+
+        self.success_transaction_cleanup(
+            storage,
+            change_set_ext,
+            gas_meter,
+            txn_data,
+            storage_fee,
+            log_context,
+        )
+    }
+
+    /// Charges the storage fee of a write set, which depends on the size of the state values it
+    /// overwrites or deletes, and returns the fee to deposit and the refund for the state it frees.
+    fn charge_storage_fee<S: StateView>(
+        storage: &S,
+        write_set: &WriteSet,
+        gas_meter: &mut impl TransactionGasMeter,
+        txn_data: &TransactionMetadata,
+    ) -> Result<StorageFee, VMStatus> {
+        let ops = write_set
+            .iter()
+            .map(|(key, op)| {
+                let old_value_size = match op {
+                    WriteOp::Creation(_) => None,
+                    WriteOp::Modification(_) | WriteOp::Deletion => storage
+                        .get_state_value(key)
+                        .map_err(|_| VMStatus::Error(StatusCode::STORAGE_ERROR))?
+                        .map(|value| NumBytes::new(value.len() as u64)),
+                };
+                Ok((key, op, old_value_size))
+            })
+            .collect::<Result<Vec<_>, VMStatus>>()?;
 
-        self.success_transaction_cleanup(storage, change_set_ext, gas_meter, txn_data, log_context)
+        gas_meter
+            .charge_storage_fee(ops, txn_data.gas_unit_price())
+            .map_err(|e| e.into_vm_status())
     }
 
     /// Resolve a pending code publish request registered via the NativeCodeContext.
     fn resolve_pending_code_publish<S: MoveResolverExt>(
         &self,
         session: &mut SessionExt<S>,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
     ) -> VMResult<()> {
         if let Some(PublishRequest {
             destination,
@@ -527,7 +740,9 @@ impl AptosVM {
 
             // Publish the bundle
             if check_compat {
-                session.publish_module_bundle(bundle.into_inner(), destination, gas_meter)?
+                session
+                    .publish_module_bundle(bundle.into_inner(), destination, gas_meter)
+                    .map_err(|err| Self::explain_incompatible_upgrade(session, &modules, err))?
             } else {
                 session.publish_module_bundle_relax_compatibility(
                     bundle.into_inner(),
@@ -543,6 +758,40 @@ impl AptosVM {
         }
     }
 
+    /// If publishing failed the compatibility check, attaches the changes which make the
+    /// modules incompatible with the deployed ones to the error.
+    fn explain_incompatible_upgrade<S: MoveResolverExt>(
+        session: &mut SessionExt<S>,
+        modules: &[CompiledModule],
+        err: VMError,
+    ) -> VMError {
+        if err.major_status() != StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE {
+            return err;
+        }
+        let mut incompatibilities = vec![];
+        for module in modules {
+            let old_module = match session.get_data_store().load_module(&module.self_id()) {
+                Ok(bytes) => CompiledModule::deserialize(&bytes),
+                Err(_) => continue,
+            };
+            if let Ok(old_module) = old_module {
+                incompatibilities.extend(
+                    check_module_compatibility(&old_module, module)
+                        .into_iter()
+                        .map(|incompatibility| {
+                            format!("{}: {}", module.self_id().name(), incompatibility)
+                        }),
+                );
+            }
+        }
+        if incompatibilities.is_empty() {
+            return err;
+        }
+        PartialVMError::new(StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE)
+            .with_message(incompatibilities.join("; "))
+            .finish(err.location().clone())
+    }
+
     /// Validate a publish request.
     fn validate_publish_request(
         &self,
@@ -607,6 +856,14 @@ impl AptosVM {
             TransactionPayload::ModuleBundle(m) => {
                 self.execute_modules(storage, session, &mut gas_meter, &txn_data, m, log_context)
             }
+            TransactionPayload::Multisig(payload) => self.execute_multisig_transaction(
+                storage,
+                session,
+                &mut gas_meter,
+                &txn_data,
+                payload,
+                log_context,
+            ),
         };
 
         let gas_usage = txn_data
@@ -823,7 +1080,103 @@ impl AptosVM {
         let vm = AptosVM::new(state_view);
         let simulation_vm = AptosSimulationVM(vm);
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
-        simulation_vm.simulate_signed_transaction(&state_view.as_move_resolver(), txn, &log_context)
+        let (vm_status, output, _gas_meter) = simulation_vm.simulate_signed_transaction(
+            &state_view.as_move_resolver(),
+            txn,
+            &log_context,
+            |gas_meter| gas_meter,
+        );
+        (vm_status, output)
+    }
+
+    /// Simulates a signed transaction like `simulate_signed_transaction`, recording the gas
+    /// charged per function, native function and storage operation along the way.
+    /// The gas profile is None if the transaction was discarded before being executed.
+    pub fn simulate_signed_transaction_with_gas_profiling(
+        txn: &SignedTransaction,
+        state_view: &impl StateView,
+    ) -> (VMStatus, TransactionOutputExt, Option<GasProfile>) {
+        let vm = AptosVM::new(state_view);
+        let simulation_vm = AptosSimulationVM(vm);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let (vm_status, output, gas_profiler) = simulation_vm.simulate_signed_transaction(
+            &state_view.as_move_resolver(),
+            txn,
+            &log_context,
+            |gas_meter| match txn.payload() {
+                TransactionPayload::EntryFunction(entry_function) => GasProfiler::new_function(
+                    gas_meter,
+                    entry_function.module(),
+                    entry_function.function().as_str(),
+                ),
+                TransactionPayload::Script(_) => GasProfiler::new_script(gas_meter),
+                TransactionPayload::ModuleBundle(_) => {
+                    GasProfiler::new(gas_meter, "module bundle".to_string())
+                }
+                TransactionPayload::Multisig(multisig) => {
+                    GasProfiler::new(gas_meter, format!("multisig {}", multisig.multisig_address))
+                }
+            },
+        );
+        (
+            vm_status,
+            output,
+            gas_profiler.map(|gas_profiler| gas_profiler.finish().0),
+        )
+    }
+
+    /// Executes `script` the way an approved governance proposal is executed, but in a session
+    /// of its own: there is no sender, so neither prologue nor epilogue run.  Gas is charged up
+    /// to the maximum a transaction may use, and code published by the script is applied like in
+    /// a regular transaction.
+    ///
+    /// Approving the proposal is up to the caller, e.g., by executing against a state view in
+    /// which `aptos_governance::resolve` doesn't check votes.  Returns the changes made by the
+    /// script along with the gas it used.
+    pub fn execute_governance_script(
+        script: &Script,
+        state_view: &impl StateView,
+    ) -> Result<(ChangeSetExt, u64), VMStatus> {
+        let vm = AptosVM::new(state_view);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let gas_params = vm.0.get_gas_parameters(&log_context)?;
+        let max_gas_amount = gas_params.txn.maximum_number_of_gas_units;
+        let mut gas_meter = AptosGasMeter::new(gas_params.clone(), max_gas_amount);
+
+        let storage = state_view.as_move_resolver();
+        let mut session = vm.0.new_session(
+            &storage,
+            SessionId::Txn {
+                sender: AccountAddress::ZERO,
+                sequence_number: 0,
+                script_hash: HashValue::sha3_256_of(script.code()).to_vec(),
+            },
+        );
+        let loaded_func = session.load_script(script.code(), script.ty_args().to_vec())?;
+        let args = transaction_arg_validation::validate_combine_signer_and_txn_args(
+            &session,
+            vec![],
+            convert_txn_args(script.args()),
+            &loaded_func,
+        )?;
+        session
+            .execute_script(
+                script.code(),
+                script.ty_args().to_vec(),
+                args,
+                &mut gas_meter,
+            )
+            .map_err(|e| e.into_vm_status())?;
+        vm.resolve_pending_code_publish(&mut session, &mut gas_meter)?;
+
+        let session_output = session.finish().map_err(|e| e.into_vm_status())?;
+        let change_set_ext = session_output.into_change_set(&mut ())?;
+        gas_meter.charge_write_set_gas(change_set_ext.write_set().iter())?;
+
+        let gas_used = max_gas_amount
+            .checked_sub(gas_meter.balance())
+            .expect("Balance should always be less than or equal to max gas amount");
+        Ok((change_set_ext, gas_used.into()))
     }
 
     fn run_prologue_with_payload<S: MoveResolverExt>(
@@ -838,14 +1191,19 @@ impl AptosVM {
                 self.0.check_gas(txn_data, log_context)?;
                 self.0.run_script_prologue(session, txn_data, log_context)
             }
-            TransactionPayload::EntryFunction(_) => {
-                // NOTE: Script and EntryFunction shares the same prologue
+            TransactionPayload::EntryFunction(_) | TransactionPayload::Multisig(_) => {
+                // NOTE: Script, EntryFunction and Multisig share the same prologue
                 self.0.check_gas(txn_data, log_context)?;
                 self.0.run_script_prologue(session, txn_data, log_context)
             }
             TransactionPayload::ModuleBundle(_module) => {
                 self.0.check_gas(txn_data, log_context)?;
-                self.0.run_module_prologue(session, txn_data, log_context)
+                // NOTE: Sponsored transactions always go through the fee payer prologue
+                if txn_data.fee_payer().is_some() {
+                    self.0.run_script_prologue(session, txn_data, log_context)
+                } else {
+                    self.0.run_module_prologue(session, txn_data, log_context)
+                }
             }
         }
     }
@@ -927,6 +1285,38 @@ impl VMAdapter for AptosVM {
             return Err(VMStatus::Error(StatusCode::SIGNERS_CONTAIN_DUPLICATES));
         }
 
+        let authenticator = txn.authenticator();
+        if authenticator.fee_payer_address().is_some()
+            && !self
+                .0
+                .get_features()
+                .is_enabled(FeatureFlag::FEE_PAYER_ENABLED)
+        {
+            return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING));
+        }
+
+        if matches!(txn.payload(), TransactionPayload::Multisig(_))
+            && !self
+                .0
+                .get_features()
+                .is_enabled(FeatureFlag::MULTISIG_ACCOUNTS)
+        {
+            return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING));
+        }
+
+        let uses_secp256r1_ecdsa = std::iter::once(authenticator.sender())
+            .chain(authenticator.secondary_signers())
+            .chain(authenticator.fee_payer_signer())
+            .any(|auth| matches!(auth.scheme(), Scheme::Secp256r1Ecdsa));
+        if uses_secp256r1_ecdsa
+            && !self
+                .0
+                .get_features()
+                .is_enabled(FeatureFlag::SECP256R1_ECDSA_AUTHENTICATOR)
+        {
+            return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING));
+        }
+
         Ok(())
     }
 
@@ -1034,18 +1424,33 @@ impl AptosSimulationVM {
     }
 
     /*
-    Executes a SignedTransaction without performing signature verification
+    Executes a SignedTransaction without performing signature verification. The gas meter used
+    for the execution is built by `new_gas_meter` and returned along with the output, unless the
+    transaction was discarded before being executed.
      */
-    fn simulate_signed_transaction<S: MoveResolverExt + StateView>(
+    fn simulate_signed_transaction<S, G, F>(
         &self,
         storage: &S,
         txn: &SignedTransaction,
         log_context: &AdapterLogSchema,
-    ) -> (VMStatus, TransactionOutputExt) {
+        new_gas_meter: F,
+    ) -> (VMStatus, TransactionOutputExt, Option<G>)
+    where
+        S: MoveResolverExt + StateView,
+        G: TransactionGasMeter,
+        F: FnOnce(AptosGasMeter) -> G,
+    {
+        macro_rules! discard {
+            ($err: expr) => {{
+                let (vm_status, output) = discard_error_vm_status($err);
+                return (vm_status, output, None);
+            }};
+        }
+
         // simulation transactions should not carry valid signatures, otherwise malicious fullnodes
         // may execute them without user's explicit permission.
         if txn.signature_is_valid() {
-            return discard_error_vm_status(VMStatus::Error(StatusCode::INVALID_SIGNATURE));
+            discard!(VMStatus::Error(StatusCode::INVALID_SIGNATURE));
         }
 
         // Revalidate the transaction.
@@ -1054,14 +1459,17 @@ impl AptosSimulationVM {
         if let Err(err) =
             self.validate_simulated_transaction::<S>(&mut session, txn, &txn_data, log_context)
         {
-            return discard_error_vm_status(err);
+            discard!(err);
         };
 
         let gas_params = match self.0 .0.get_gas_parameters(log_context) {
-            Err(err) => return discard_error_vm_status(err),
+            Err(err) => discard!(err),
             Ok(s) => s,
         };
-        let mut gas_meter = AptosGasMeter::new(gas_params.clone(), txn_data.max_gas_amount());
+        let mut gas_meter = new_gas_meter(AptosGasMeter::new(
+            gas_params.clone(),
+            txn_data.max_gas_amount(),
+        ));
 
         let result = match txn.payload() {
             payload @ TransactionPayload::Script(_)
@@ -1079,25 +1487,47 @@ impl AptosSimulationVM {
                 self.0
                     .execute_modules(storage, session, &mut gas_meter, &txn_data, m, log_context)
             }
+            TransactionPayload::Multisig(payload) => self.0.execute_multisig_transaction(
+                storage,
+                session,
+                &mut gas_meter,
+                &txn_data,
+                payload,
+                log_context,
+            ),
         };
 
-        match result {
+        let (vm_status, output) = match result {
             Ok(output) => output,
             Err(err) => {
                 let txn_status = TransactionStatus::from(err.clone());
                 if txn_status.is_discarded() {
                     discard_error_vm_status(err)
                 } else {
-                    let (vm_status, output) = self.0.failed_transaction_cleanup_and_keep_vm_status(
+                    self.0.failed_transaction_cleanup_and_keep_vm_status(
                         err,
                         &mut gas_meter,
                         &txn_data,
                         storage,
                         log_context,
-                    );
-                    (vm_status, output)
+                    )
                 }
             }
+        };
+        (vm_status, output, Some(gas_meter))
+    }
+}
+
+/// Name of the module a failed multisig payload aborted in, e.g. `0x1::coin`, or `script`.
+fn abort_location_name(location: &AbortLocation) -> String {
+    match location {
+        AbortLocation::Module(module) => {
+            format!(
+                "0x{}::{}",
+                module.address().short_str_lossless(),
+                module.name()
+            )
         }
+        AbortLocation::Script => "script".to_string(),
     }
 }
@@ -13,14 +13,18 @@ use crate::{
 use aptos_aggregator::transaction::TransactionOutputExt;
 use aptos_gas::{
     AbstractValueSizeGasParameters, AptosGasParameters, FromOnChainGasSchedule, Gas,
-    NativeGasParameters,
+    NativeGasParameters, StorageFee,
 };
 use aptos_logger::prelude::*;
 use aptos_state_view::StateView;
 use aptos_types::transaction::AbortInfo;
 use aptos_types::{
-    account_config::{TransactionValidation, APTOS_TRANSACTION_VALIDATION, CORE_CODE_ADDRESS},
-    on_chain_config::{GasSchedule, OnChainConfig, Version},
+    account_config::{
+        TransactionValidation, APTOS_TRANSACTION_VALIDATION, CORE_CODE_ADDRESS,
+        FEE_PAYER_PROLOGUE_NAME, USER_EPILOGUE_GAS_PAYER_NAME,
+        USER_EPILOGUE_WITH_STORAGE_REFUND_NAME,
+    },
+    on_chain_config::{Features, GasSchedule, OnChainConfig, Version},
     transaction::{ExecutionStatus, TransactionOutput, TransactionStatus},
     vm_status::{StatusCode, VMStatus},
 };
@@ -46,6 +50,7 @@ pub struct AptosVMImpl {
     move_vm: Arc<MoveVmExt>,
     gas_params: Option<AptosGasParameters>,
     version: Option<Version>,
+    features: Features,
     transaction_validation: Option<TransactionValidation>,
     metadata_cache: DashMap<ModuleId, Option<RuntimeModuleMetadata>>,
 }
@@ -77,10 +82,12 @@ impl AptosVMImpl {
             move_vm: Arc::new(inner),
             gas_params,
             version: None,
+            features: Features::default(),
             transaction_validation: None,
             metadata_cache: Default::default(),
         };
         vm.version = Version::fetch_config(&storage);
+        vm.features = Features::fetch_config(&storage).unwrap_or_default();
         vm.transaction_validation = Self::get_transaction_validation(&StorageAdapter::new(state));
         vm
     }
@@ -98,6 +105,7 @@ impl AptosVMImpl {
             move_vm: Arc::new(inner),
             gas_params: Some(gas_params),
             version: Some(version),
+            features: Features::default(),
             transaction_validation: None,
             metadata_cache: Default::default(),
         }
@@ -146,6 +154,11 @@ impl AptosVMImpl {
         })
     }
 
+    /// Returns the feature flags enabled on chain.
+    pub fn get_features(&self) -> &Features {
+        &self.features
+    }
+
     pub fn check_gas(
         &self,
         txn_data: &TransactionMetadata,
@@ -230,8 +243,9 @@ impl AptosVMImpl {
         Ok(())
     }
 
-    /// Run the prologue of a transaction by calling into either `SCRIPT_PROLOGUE_NAME` function
-    /// or `MULTI_AGENT_SCRIPT_PROLOGUE_NAME` function stored in the `ACCOUNT_MODULE` on chain.
+    /// Run the prologue of a transaction by calling into either `SCRIPT_PROLOGUE_NAME` function,
+    /// `MULTI_AGENT_SCRIPT_PROLOGUE_NAME` function or `FEE_PAYER_PROLOGUE_NAME` function stored
+    /// in the `ACCOUNT_MODULE` on chain.
     pub(crate) fn run_script_prologue<S: MoveResolverExt>(
         &self,
         session: &mut SessionExt<S>,
@@ -251,7 +265,25 @@ impl AptosVMImpl {
             .iter()
             .map(|auth_key| MoveValue::vector_u8(auth_key.to_vec()))
             .collect();
-        let args = if txn_data.is_multi_agent() {
+        let args = if let Some(fee_payer) = txn_data.fee_payer() {
+            let fee_payer_auth_key = txn_data
+                .fee_payer_authentication_key
+                .clone()
+                .ok_or(VMStatus::Error(StatusCode::UNREACHABLE))?;
+            vec![
+                MoveValue::Signer(txn_data.sender),
+                MoveValue::U64(txn_sequence_number),
+                MoveValue::vector_u8(txn_authentication_key),
+                MoveValue::vector_address(txn_data.secondary_signers()),
+                MoveValue::Vector(secondary_auth_keys),
+                MoveValue::Address(fee_payer),
+                MoveValue::vector_u8(fee_payer_auth_key),
+                MoveValue::U64(txn_gas_price.into()),
+                MoveValue::U64(txn_max_gas_units.into()),
+                MoveValue::U64(txn_expiration_timestamp_secs),
+                MoveValue::U8(chain_id.id()),
+            ]
+        } else if txn_data.is_multi_agent() {
             vec![
                 MoveValue::Signer(txn_data.sender),
                 MoveValue::U64(txn_sequence_number),
@@ -275,10 +307,14 @@ impl AptosVMImpl {
                 MoveValue::vector_u8(txn_data.script_hash.clone()),
             ]
         };
-        let prologue_function_name = if txn_data.is_multi_agent() {
-            &transaction_validation.multi_agent_prologue_name
+        let prologue_function_name = if txn_data.fee_payer().is_some() {
+            FEE_PAYER_PROLOGUE_NAME
+        } else if txn_data.is_multi_agent() {
+            transaction_validation
+                .multi_agent_prologue_name
+                .as_ident_str()
         } else {
-            &transaction_validation.script_prologue_name
+            transaction_validation.script_prologue_name.as_ident_str()
         };
         session
             .execute_function_bypass_visibility(
@@ -335,10 +371,14 @@ impl AptosVMImpl {
 
     /// Run the epilogue of a transaction by calling into `EPILOGUE_NAME` function stored
     /// in the `ACCOUNT_MODULE` on chain.
+    ///
+    /// The storage fee charged as part of the gas used is held as a deposit, and the refund for
+    /// the state freed by the transaction is paid back to the gas payer on top of the unused gas.
     pub(crate) fn run_success_epilogue<S: MoveResolverExt>(
         &self,
         session: &mut SessionExt<S>,
         gas_remaining: Gas,
+        storage_fee: StorageFee,
         txn_data: &TransactionMetadata,
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
@@ -352,19 +392,51 @@ impl AptosVMImpl {
         let txn_sequence_number = txn_data.sequence_number();
         let txn_gas_price = txn_data.gas_unit_price();
         let txn_max_gas_units = txn_data.max_gas_amount();
-        session
-            .execute_function_bypass_visibility(
-                &transaction_validation.module_id(),
-                &transaction_validation.user_epilogue_name,
-                // TODO: Deprecate this once we remove gas currency on the Move side.
-                vec![],
-                serialize_values(&vec![
+        let (epilogue_function_name, args) = match txn_data.fee_payer() {
+            // Only the dedicated epilogue handles storage fees, so it is only called when there
+            // is something to deposit or refund.
+            fee_payer if !storage_fee.is_zero() => (
+                USER_EPILOGUE_WITH_STORAGE_REFUND_NAME,
+                vec![
                     MoveValue::Signer(txn_data.sender),
+                    MoveValue::Address(fee_payer.unwrap_or(txn_data.sender)),
                     MoveValue::U64(txn_sequence_number),
                     MoveValue::U64(txn_gas_price.into()),
                     MoveValue::U64(txn_max_gas_units.into()),
                     MoveValue::U64(gas_remaining.into()),
-                ]),
+                    MoveValue::U64(storage_fee.charge.into()),
+                    MoveValue::U64(storage_fee.refund.into()),
+                ],
+            ),
+            Some(fee_payer) => (
+                USER_EPILOGUE_GAS_PAYER_NAME,
+                vec![
+                    MoveValue::Signer(txn_data.sender),
+                    MoveValue::Address(fee_payer),
+                    MoveValue::U64(txn_sequence_number),
+                    MoveValue::U64(txn_gas_price.into()),
+                    MoveValue::U64(txn_max_gas_units.into()),
+                    MoveValue::U64(gas_remaining.into()),
+                ],
+            ),
+            None => (
+                transaction_validation.user_epilogue_name.as_ident_str(),
+                vec![
+                    MoveValue::Signer(txn_data.sender),
+                    MoveValue::U64(txn_sequence_number),
+                    MoveValue::U64(txn_gas_price.into()),
+                    MoveValue::U64(txn_max_gas_units.into()),
+                    MoveValue::U64(gas_remaining.into()),
+                ],
+            ),
+        };
+        session
+            .execute_function_bypass_visibility(
+                &transaction_validation.module_id(),
+                epilogue_function_name,
+                // TODO: Deprecate this once we remove gas currency on the Move side.
+                vec![],
+                serialize_values(&args),
                 &mut UnmeteredGasMeter,
             )
             .map(|_return_vals| ())
@@ -385,29 +457,42 @@ impl AptosVMImpl {
         let txn_sequence_number = txn_data.sequence_number();
         let txn_gas_price = txn_data.gas_unit_price();
         let txn_max_gas_units = txn_data.max_gas_amount();
-        session
-            .execute_function_bypass_visibility(
-                &transaction_validation.module_id(),
-                &transaction_validation.user_epilogue_name,
-                // TODO: Deprecate this once we remove gas currency on the Move side.
-                vec![],
-                serialize_values(&vec![
+        let (epilogue_function_name, args) = match txn_data.fee_payer() {
+            Some(fee_payer) => (
+                USER_EPILOGUE_GAS_PAYER_NAME,
+                vec![
                     MoveValue::Signer(txn_data.sender),
+                    MoveValue::Address(fee_payer),
                     MoveValue::U64(txn_sequence_number),
                     MoveValue::U64(txn_gas_price.into()),
                     MoveValue::U64(txn_max_gas_units.into()),
                     MoveValue::U64(gas_remaining.into()),
-                ]),
+                ],
+            ),
+            None => (
+                transaction_validation.user_epilogue_name.as_ident_str(),
+                vec![
+                    MoveValue::Signer(txn_data.sender),
+                    MoveValue::U64(txn_sequence_number),
+                    MoveValue::U64(txn_gas_price.into()),
+                    MoveValue::U64(txn_max_gas_units.into()),
+                    MoveValue::U64(gas_remaining.into()),
+                ],
+            ),
+        };
+        session
+            .execute_function_bypass_visibility(
+                &transaction_validation.module_id(),
+                epilogue_function_name,
+                // TODO: Deprecate this once we remove gas currency on the Move side.
+                vec![],
+                serialize_values(&args),
                 &mut UnmeteredGasMeter,
             )
             .map(|_return_vals| ())
             .map_err(expect_no_verification_errors)
             .or_else(|e| {
-                expect_only_successful_execution(
-                    e,
-                    transaction_validation.user_epilogue_name.as_str(),
-                    log_context,
-                )
+                expect_only_successful_execution(e, epilogue_function_name.as_str(), log_context)
             })
     }
 
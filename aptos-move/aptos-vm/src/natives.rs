@@ -52,7 +52,9 @@ pub fn configure_for_unit_test() {
     move_unit_test::extensions::set_extension_hook(Box::new(unit_test_extensions_hook))
 }
 
-fn unit_test_extensions_hook(exts: &mut NativeContextExtensions) {
+/// Adds the native extensions Aptos natives rely on in unit tests, except for the table context,
+/// which needs the storage the tests run against.
+pub fn unit_test_extensions_hook(exts: &mut NativeContextExtensions) {
     exts.add(NativeCodeContext::default());
     exts.add(NativeTransactionContext::new(vec![1]));
     exts.add(NativeAggregatorContext::new([0; 32], &*DUMMY_RESOLVER));
@@ -0,0 +1,120 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A lightweight prediction of the keys accessed by a transaction, used to give the parallel
+//! executor dependency hints before a block is executed.
+//!
+//! Unlike the analysis in `read_write_set_analysis.rs`, this doesn't require precomputed
+//! summaries of the published modules: it only recognizes the most common entry functions
+//! (i.e., coin transfers), and gives up on everything else. The prediction needn't be sound,
+//! as the parallel executor still validates all reads.
+
+use crate::{adapter_common::PreprocessedTransaction, create_access_path};
+use aptos_parallel_executor::task::Accesses;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::{AccountResource, BlockResource, CoinStoreResource},
+    state_store::state_key::StateKey,
+    timestamp::TimestampResource,
+    transaction::TransactionPayload,
+};
+use move_deps::move_core_types::{
+    ident_str,
+    language_storage::{StructTag, TypeTag, CORE_CODE_ADDRESS},
+    move_resource::MoveStructType,
+};
+
+/// Predicts the accesses of the given transaction, returns None if they can't be predicted.
+pub(crate) fn predict_accesses(txn: &PreprocessedTransaction) -> Option<Accesses<StateKey>> {
+    match txn {
+        PreprocessedTransaction::BlockMetadata(_) => {
+            let keys = vec![
+                resource_key(CORE_CODE_ADDRESS, TimestampResource::struct_tag()),
+                resource_key(CORE_CODE_ADDRESS, BlockResource::struct_tag()),
+            ];
+            Some(Accesses {
+                keys_read: keys.clone(),
+                keys_written: keys,
+            })
+        }
+        PreprocessedTransaction::InvalidSignature | PreprocessedTransaction::StateCheckpoint => {
+            Some(Accesses {
+                keys_read: vec![],
+                keys_written: vec![],
+            })
+        }
+        PreprocessedTransaction::WaypointWriteSet(_) => None,
+        PreprocessedTransaction::UserTransaction(txn) => {
+            let entry_function = match txn.payload() {
+                TransactionPayload::EntryFunction(entry_function) => entry_function,
+                _ => return None,
+            };
+            if entry_function.module().address() != &CORE_CODE_ADDRESS {
+                return None;
+            }
+
+            let sender = txn.sender();
+            let module = entry_function.module().name();
+            let function = entry_function.function();
+            let (recipient, coin_type) = if module == ident_str!("coin")
+                && function == ident_str!("transfer")
+            {
+                match entry_function.ty_args() {
+                    [coin_type] => (decode_recipient(entry_function.args())?, coin_type.clone()),
+                    _ => return None,
+                }
+            } else if module == ident_str!("aptos_account") && function == ident_str!("transfer") {
+                (
+                    decode_recipient(entry_function.args())?,
+                    CoinStoreResource::type_params().pop()?,
+                )
+            } else {
+                return None;
+            };
+
+            // The prologue and epilogue access the sender's account and pay the gas fee from
+            // the sender's AptosCoin store, the transfer itself touches both coin stores.
+            let mut keys_written = vec![
+                resource_key(sender, AccountResource::struct_tag()),
+                resource_key(sender, CoinStoreResource::struct_tag()),
+                resource_key(sender, coin_store_struct_tag(coin_type.clone())),
+                resource_key(recipient, coin_store_struct_tag(coin_type)),
+            ];
+            keys_written.sort();
+            keys_written.dedup();
+
+            let mut keys_read = keys_written.clone();
+            keys_read.push(resource_key(
+                CORE_CODE_ADDRESS,
+                TimestampResource::struct_tag(),
+            ));
+            // `aptos_account::transfer` creates the recipient's account if it doesn't exist.
+            if module == ident_str!("aptos_account") {
+                keys_read.push(resource_key(recipient, AccountResource::struct_tag()));
+            }
+
+            Some(Accesses {
+                keys_read,
+                keys_written,
+            })
+        }
+    }
+}
+
+fn decode_recipient(args: &[Vec<u8>]) -> Option<AccountAddress> {
+    args.first()
+        .and_then(|arg| bcs::from_bytes::<AccountAddress>(arg).ok())
+}
+
+fn coin_store_struct_tag(coin_type: TypeTag) -> StructTag {
+    StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: CoinStoreResource::MODULE_NAME.to_owned(),
+        name: CoinStoreResource::STRUCT_NAME.to_owned(),
+        type_params: vec![coin_type],
+    }
+}
+
+fn resource_key(address: AccountAddress, tag: StructTag) -> StateKey {
+    StateKey::AccessPath(create_access_path(address, tag))
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+mod access_prediction;
 mod storage_wrapper;
 mod vm_wrapper;
 
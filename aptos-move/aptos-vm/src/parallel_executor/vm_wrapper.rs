@@ -6,12 +6,14 @@ use crate::{
     aptos_vm::AptosVM,
     data_cache::StorageAdapter,
     logging::AdapterLogSchema,
-    parallel_executor::{storage_wrapper::VersionedView, AptosTransactionOutput},
+    parallel_executor::{
+        access_prediction, storage_wrapper::VersionedView, AptosTransactionOutput,
+    },
 };
 use aptos_logger::prelude::*;
 use aptos_parallel_executor::{
     executor::MVHashMapView,
-    task::{ExecutionStatus, ExecutorTask},
+    task::{Accesses, ExecutionStatus, ExecutorTask},
 };
 use aptos_state_view::StateView;
 use aptos_types::{state_store::state_key::StateKey, write_set::WriteOp};
@@ -54,6 +56,13 @@ impl<'a, S: 'a + StateView> ExecutorTask for AptosVMWrapper<'a, S> {
         }
     }
 
+    fn predict_accesses(
+        _argument: &'a S,
+        txn: &PreprocessedTransaction,
+    ) -> Option<Accesses<StateKey>> {
+        access_prediction::predict_accesses(txn)
+    }
+
     fn execute_transaction(
         &self,
         view: &MVHashMapView<StateKey, WriteOp>,
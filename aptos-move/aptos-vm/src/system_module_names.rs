@@ -22,3 +22,17 @@ pub const MULTI_AGENT_SCRIPT_PROLOGUE_NAME: &IdentStr = ident_str!("multi_agent_
 pub const MODULE_PROLOGUE_NAME: &IdentStr = ident_str!("module_prologue");
 pub const USER_EPILOGUE_NAME: &IdentStr = ident_str!("epilogue");
 pub const BLOCK_PROLOGUE: &IdentStr = ident_str!("block_prologue");
+
+/// The ModuleId for the multisig account module
+pub static MULTISIG_ACCOUNT_MODULE: Lazy<ModuleId> = Lazy::new(|| {
+    ModuleId::new(
+        account_config::CORE_CODE_ADDRESS,
+        ident_str!("multisig_account").to_owned(),
+    )
+});
+
+pub const VALIDATE_MULTISIG_TRANSACTION: &IdentStr = ident_str!("validate_multisig_transaction");
+pub const SUCCESSFUL_TRANSACTION_EXECUTION_CLEANUP: &IdentStr =
+    ident_str!("successful_transaction_execution_cleanup");
+pub const FAILED_TRANSACTION_EXECUTION_CLEANUP: &IdentStr =
+    ident_str!("failed_transaction_execution_cleanup");
@@ -16,6 +16,8 @@ pub struct TransactionMetadata {
     pub authentication_key: Vec<u8>,
     pub secondary_signers: Vec<AccountAddress>,
     pub secondary_authentication_keys: Vec<Vec<u8>>,
+    pub fee_payer: Option<AccountAddress>,
+    pub fee_payer_authentication_key: Option<Vec<u8>>,
     pub sequence_number: u64,
     pub max_gas_amount: Gas,
     pub gas_unit_price: FeePerGasUnit,
@@ -37,6 +39,11 @@ impl TransactionMetadata {
                 .iter()
                 .map(|account_auth| account_auth.authentication_key().to_vec())
                 .collect(),
+            fee_payer: txn.authenticator().fee_payer_address(),
+            fee_payer_authentication_key: txn
+                .authenticator()
+                .fee_payer_signer()
+                .map(|signer| signer.authentication_key().to_vec()),
             sequence_number: txn.sequence_number(),
             max_gas_amount: txn.max_gas_amount().into(),
             gas_unit_price: txn.gas_unit_price().into(),
@@ -47,6 +54,7 @@ impl TransactionMetadata {
                 TransactionPayload::Script(s) => HashValue::sha3_256_of(s.code()).to_vec(),
                 TransactionPayload::EntryFunction(_) => vec![],
                 TransactionPayload::ModuleBundle(_) => vec![],
+                TransactionPayload::Multisig(_) => vec![],
             },
         }
     }
@@ -67,6 +75,10 @@ impl TransactionMetadata {
         self.secondary_signers.to_owned()
     }
 
+    pub fn fee_payer(&self) -> Option<AccountAddress> {
+        self.fee_payer.to_owned()
+    }
+
     pub fn authentication_key(&self) -> &[u8] {
         &self.authentication_key
     }
@@ -102,6 +114,8 @@ impl Default for TransactionMetadata {
             authentication_key: AuthenticationKey::ed25519(&public_key).to_vec(),
             secondary_signers: vec![],
             secondary_authentication_keys: vec![],
+            fee_payer: None,
+            fee_payer_authentication_key: None,
             sequence_number: 0,
             max_gas_amount: 100_000_000.into(),
             gas_unit_price: 0.into(),
@@ -3,14 +3,19 @@
 
 use crate::AptosPackageHooks;
 use aptos::move_tool::MemberId;
-use aptos_crypto::ed25519::Ed25519PrivateKey;
+use aptos_crypto::ed25519::{Ed25519PrivateKey, Ed25519Signature};
 use aptos_crypto::{PrivateKey, Uniform};
+use aptos_gas::{AptosGasParameters, GasProfile, InitialGasSchedule, ToOnChainGasSchedule};
 use aptos_types::{
     access_path::AccessPath,
     account_address::AccountAddress,
+    contract_event::ContractEvent,
+    on_chain_config::{access_path_for_config, FeatureFlag, Features, GasSchedule, OnChainConfig},
     state_store::state_key::StateKey,
     transaction::{EntryFunction, SignedTransaction, TransactionPayload, TransactionStatus},
+    write_set::{WriteOp, WriteSetMut},
 };
+use aptos_vm::AptosVM;
 use cached_packages::aptos_stdlib;
 use framework::{BuildOptions, BuiltPackage};
 use language_e2e_tests::{
@@ -26,6 +31,7 @@ use rand::{
 };
 use serde::de::DeserializeOwned;
 use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::path::Path;
 
 /// A simple test harness for defining Move e2e tests.
@@ -154,6 +160,37 @@ impl MoveHarness {
         self.run(txn)
     }
 
+    /// Simulates a transaction, based on provided payload, and returns its status along with a
+    /// profile of the gas it was charged. The effects of the transaction are not applied.
+    pub fn profile_transaction_payload_gas(
+        &mut self,
+        account: &Account,
+        payload: TransactionPayload,
+    ) -> (TransactionStatus, GasProfile) {
+        let seq_no = *self.txn_seq_no.get(account.address()).unwrap();
+        let raw_txn = account
+            .transaction()
+            .sequence_number(seq_no)
+            .max_gas_amount(1_000_000)
+            .gas_unit_price(1)
+            .payload(payload)
+            .raw();
+        // Simulated transactions must not carry a valid signature.
+        let txn = SignedTransaction::new(
+            raw_txn,
+            account.pubkey.clone(),
+            Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
+        );
+        let (_, output, profile) = AptosVM::simulate_signed_transaction_with_gas_profiling(
+            &txn,
+            self.executor.get_state_view(),
+        );
+        (
+            output.txn_output().status().to_owned(),
+            profile.expect("transaction should not be discarded"),
+        )
+    }
+
     /// Creates a transaction which runs the specified entry point `fun`. Arguments need to be
     /// provided in bcs-serialized form.
     pub fn create_entry_function(
@@ -236,7 +273,8 @@ impl MoveHarness {
             .set_block_time(current_time + seconds * 1_000_000)
     }
 
-    pub fn new_epoch(&mut self) {
+    /// Starts a new epoch, returning the events emitted by the block prologue.
+    pub fn new_epoch(&mut self) -> Vec<ContractEvent> {
         self.fast_forward(7200);
         self.executor.new_block()
     }
@@ -245,10 +283,10 @@ impl MoveHarness {
         &mut self,
         proposer: AccountAddress,
         failed_proposer_indices: Vec<u32>,
-    ) {
+    ) -> Vec<ContractEvent> {
         self.fast_forward(1);
         self.executor
-            .new_block_with_metadata(proposer, failed_proposer_indices);
+            .new_block_with_metadata(proposer, failed_proposer_indices)
     }
 
     pub fn read_state_value(&self, state_key: &StateKey) -> Option<Vec<u8>> {
@@ -284,6 +322,42 @@ impl MoveHarness {
         )
     }
 
+    /// Enables the given feature flags by directly writing the `std::features::Features`
+    /// resource.
+    pub fn enable_features(&mut self, flags: Vec<FeatureFlag>) {
+        let state_key = StateKey::AccessPath(access_path_for_config(Features::CONFIG_ID));
+        let mut features: Features = self
+            .read_state_value(&state_key)
+            .map(|bytes| bcs::from_bytes(&bytes).expect("features must deserialize"))
+            .unwrap_or_default();
+        for flag in flags {
+            features.enable(flag);
+        }
+        let write_set = WriteSetMut::new(vec![(
+            state_key,
+            WriteOp::Modification(bcs::to_bytes(&features).unwrap()),
+        )])
+        .freeze()
+        .unwrap();
+        self.executor.apply_write_set(&write_set);
+    }
+
+    /// Replaces the on-chain gas schedule with the initial gas parameters, as modified by `f`.
+    pub fn modify_gas_params(&mut self, f: impl FnOnce(&mut AptosGasParameters)) {
+        let mut gas_params = AptosGasParameters::initial();
+        f(&mut gas_params);
+        let gas_schedule = GasSchedule {
+            entries: gas_params.to_on_chain_gas_schedule(),
+        };
+        let write_set = WriteSetMut::new(vec![(
+            StateKey::AccessPath(access_path_for_config(GasSchedule::CONFIG_ID)),
+            WriteOp::Modification(bcs::to_bytes(&gas_schedule).unwrap()),
+        )])
+        .freeze()
+        .unwrap();
+        self.executor.apply_write_set(&write_set);
+    }
+
     /// Checks whether resource exists.
     pub fn exists_resource(&self, addr: &AccountAddress, struct_tag: StructTag) -> bool {
         self.read_resource_raw(addr, struct_tag).is_some()
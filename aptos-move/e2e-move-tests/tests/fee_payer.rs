@@ -0,0 +1,98 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    on_chain_config::FeatureFlag,
+    transaction::{ExecutionStatus, TransactionStatus},
+    vm_status::StatusCode,
+};
+use cached_packages::aptos_stdlib;
+use e2e_move_tests::MoveHarness;
+use language_e2e_tests::account::Account;
+
+fn balance(harness: &MoveHarness, account: &Account) -> u64 {
+    harness
+        .executor
+        .read_coin_store_resource(account)
+        .unwrap()
+        .coin()
+}
+
+fn sequence_number(harness: &MoveHarness, account: &Account) -> u64 {
+    harness
+        .executor
+        .read_account_resource(account)
+        .unwrap()
+        .sequence_number()
+}
+
+#[test]
+fn fee_payer_pays_for_gas() {
+    let mut harness = MoveHarness::new();
+    let sender = harness.new_account_with_key_pair();
+    let fee_payer = harness.new_account_with_key_pair();
+    let receiver = harness.new_account_with_key_pair();
+
+    let build_txn = |sender: &Account, fee_payer: &Account| {
+        sender
+            .transaction()
+            .fee_payer(fee_payer.clone())
+            .sequence_number(10)
+            .max_gas_amount(100_000)
+            .gas_unit_price(1)
+            .payload(aptos_stdlib::aptos_coin_transfer(*receiver.address(), 100))
+            .sign_fee_payer()
+    };
+
+    // Sponsored transactions are rejected until the feature is enabled.
+    assert_eq!(
+        harness.run(build_txn(&sender, &fee_payer)),
+        TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+
+    harness.enable_features(vec![FeatureFlag::FEE_PAYER_ENABLED]);
+    let sender_balance = balance(&harness, &sender);
+    let fee_payer_balance = balance(&harness, &fee_payer);
+
+    let output = harness
+        .executor
+        .execute_transaction(build_txn(&sender, &fee_payer));
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    harness.executor.apply_write_set(output.write_set());
+
+    // The sender only pays for the transfer, the fee payer pays for gas.
+    assert_eq!(balance(&harness, &sender), sender_balance - 100);
+    assert_eq!(
+        balance(&harness, &fee_payer),
+        fee_payer_balance - output.gas_used()
+    );
+    // Only the sender's sequence number is used.
+    assert_eq!(sequence_number(&harness, &sender), 11);
+    assert_eq!(sequence_number(&harness, &fee_payer), 10);
+}
+
+#[test]
+fn fee_payer_with_invalid_signature() {
+    let mut harness = MoveHarness::new();
+    harness.enable_features(vec![FeatureFlag::FEE_PAYER_ENABLED]);
+    let sender = harness.new_account_with_key_pair();
+    let fee_payer = harness.new_account_with_key_pair();
+    let impostor = harness.new_account_with_key_pair();
+
+    // The fee payer must sign with the key of the account paying for gas.
+    let mut fee_payer = Account::new_genesis_account(*fee_payer.address());
+    fee_payer.rotate_key(impostor.privkey.clone(), impostor.pubkey.clone());
+    let txn = sender
+        .transaction()
+        .fee_payer(fee_payer)
+        .sequence_number(10)
+        .payload(aptos_stdlib::aptos_coin_transfer(*impostor.address(), 100))
+        .sign_fee_payer();
+    assert_eq!(
+        harness.run(txn),
+        TransactionStatus::Discard(StatusCode::INVALID_AUTH_KEY)
+    );
+}
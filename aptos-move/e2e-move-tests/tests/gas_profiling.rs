@@ -0,0 +1,38 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::account_address::AccountAddress;
+use cached_packages::aptos_stdlib;
+use e2e_move_tests::{assert_success, MoveHarness};
+
+#[test]
+fn profile_coin_transfer() {
+    let mut h = MoveHarness::new();
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap());
+
+    let (status, profile) = h.profile_transaction_payload_gas(
+        &alice,
+        aptos_stdlib::aptos_account_transfer(*bob.address(), 100),
+    );
+    assert_success!(status);
+
+    let stacks = profile
+        .stacks()
+        .keys()
+        .map(|stack| stack.join(";"))
+        .collect::<Vec<_>>();
+    let root = "0x1::aptos_account::transfer";
+    assert!(stacks.iter().all(|stack| stack.starts_with(root)));
+    assert!(stacks
+        .iter()
+        .any(|stack| stack.starts_with(&format!("{};0x1::coin::transfer", root))));
+    assert!(stacks.iter().any(|stack| stack.ends_with("[intrinsic]")));
+    assert!(stacks
+        .iter()
+        .any(|stack| stack.contains("[modify] 0x1::coin::CoinStore")));
+
+    let folded = profile.to_folded_stacks();
+    assert!(folded.lines().count() <= stacks.len());
+    assert!(profile.summary_table().contains(root));
+}
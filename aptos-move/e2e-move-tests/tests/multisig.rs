@@ -0,0 +1,212 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto::HashValue;
+use aptos_types::{
+    account_address::AccountAddress,
+    on_chain_config::FeatureFlag,
+    transaction::{
+        ExecutionStatus, Multisig, MultisigTransactionPayload, TransactionPayload,
+        TransactionStatus,
+    },
+    vm_status::StatusCode,
+};
+use cached_packages::aptos_stdlib;
+use e2e_move_tests::{assert_abort, assert_success, MoveHarness};
+use language_e2e_tests::account::Account;
+
+/// Mirrors `multisig_account::get_next_multisig_account_address`.
+fn multisig_address(creator: &Account, sequence_number: u64) -> AccountAddress {
+    let mut bytes = creator.address().to_vec();
+    bytes.extend(b"aptos_framework::multisig_account");
+    bytes.extend(bcs::to_bytes(&sequence_number).unwrap());
+    AccountAddress::new(*HashValue::sha3_256_of(&bytes))
+}
+
+fn balance(harness: &MoveHarness, address: &AccountAddress) -> u64 {
+    harness
+        .executor
+        .read_coin_store_resource_at_address(address)
+        .unwrap()
+        .coin()
+}
+
+fn transfer_payload(receiver: &Account, amount: u64) -> MultisigTransactionPayload {
+    match aptos_stdlib::aptos_coin_transfer(*receiver.address(), amount) {
+        TransactionPayload::EntryFunction(entry_function) => {
+            MultisigTransactionPayload::EntryFunction(entry_function)
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn execute_payload(
+    multisig_address: AccountAddress,
+    transaction_payload: Option<MultisigTransactionPayload>,
+) -> TransactionPayload {
+    TransactionPayload::Multisig(Multisig {
+        multisig_address,
+        transaction_payload,
+    })
+}
+
+#[test]
+fn multisig_transaction_is_feature_gated() {
+    let mut harness = MoveHarness::new();
+    let owner = harness.new_account_with_key_pair();
+
+    let txn = owner
+        .transaction()
+        .sequence_number(10)
+        .payload(execute_payload(AccountAddress::random(), None))
+        .sign();
+    assert_eq!(
+        harness.run(txn),
+        TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+}
+
+#[test]
+fn multisig_transaction_executes_after_approval() {
+    let mut harness = MoveHarness::new();
+    harness.enable_features(vec![FeatureFlag::MULTISIG_ACCOUNTS]);
+    let owner1 = harness.new_account_with_key_pair();
+    let owner2 = harness.new_account_with_key_pair();
+    let receiver = harness.new_account_with_key_pair();
+
+    let multisig = multisig_address(&owner1, 10);
+    assert_success!(harness.run_transaction_payload(
+        &owner1,
+        aptos_stdlib::multisig_account_create(vec![*owner2.address()], 2),
+    ));
+    assert_success!(
+        harness.run_transaction_payload(&owner1, aptos_stdlib::aptos_coin_transfer(multisig, 1000))
+    );
+
+    let payload = transfer_payload(&receiver, 100);
+    assert_success!(harness.run_transaction_payload(
+        &owner1,
+        aptos_stdlib::multisig_account_create_transaction(
+            multisig,
+            bcs::to_bytes(&payload).unwrap(),
+        ),
+    ));
+
+    // Only the proposer has approved so far.
+    assert_abort!(
+        harness.run_transaction_payload(&owner2, execute_payload(multisig, None)),
+        0x30007
+    );
+
+    assert_success!(harness.run_transaction_payload(
+        &owner2,
+        aptos_stdlib::multisig_account_approve_transaction(multisig, 1),
+    ));
+
+    // A provided payload must match the approved one.
+    assert_abort!(
+        harness.run_transaction_payload(
+            &owner2,
+            execute_payload(multisig, Some(transfer_payload(&receiver, 200))),
+        ),
+        0x1000A
+    );
+
+    let receiver_balance = balance(&harness, receiver.address());
+    assert_success!(
+        harness.run_transaction_payload(&owner2, execute_payload(multisig, Some(payload)))
+    );
+    assert_eq!(balance(&harness, &multisig), 900);
+    assert_eq!(
+        balance(&harness, receiver.address()),
+        receiver_balance + 100
+    );
+
+    // The transaction can't be executed twice.
+    assert_abort!(
+        harness.run_transaction_payload(&owner1, execute_payload(multisig, None)),
+        0x60006
+    );
+}
+
+#[test]
+fn multisig_transaction_resolved_when_payload_aborts() {
+    let mut harness = MoveHarness::new();
+    harness.enable_features(vec![FeatureFlag::MULTISIG_ACCOUNTS]);
+    let owner = harness.new_account_with_key_pair();
+    let receiver = harness.new_account_with_key_pair();
+
+    let multisig = multisig_address(&owner, 10);
+    assert_success!(
+        harness.run_transaction_payload(&owner, aptos_stdlib::multisig_account_create(vec![], 1))
+    );
+    assert_success!(
+        harness.run_transaction_payload(&owner, aptos_stdlib::aptos_coin_transfer(multisig, 1000))
+    );
+
+    // The first transaction transfers more than the multisig account holds, the second doesn't.
+    let failing_payload = transfer_payload(&receiver, 2000);
+    let payload = transfer_payload(&receiver, 100);
+    for payload in [&failing_payload, &payload] {
+        assert_success!(harness.run_transaction_payload(
+            &owner,
+            aptos_stdlib::multisig_account_create_transaction(
+                multisig,
+                bcs::to_bytes(payload).unwrap(),
+            ),
+        ));
+    }
+
+    // The aborted payload is discarded, but the transaction is resolved with a failure event.
+    let receiver_balance = balance(&harness, receiver.address());
+    let txn = harness.create_transaction_payload(&owner, execute_payload(multisig, None));
+    let output = harness.executor.execute_transaction(txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    harness.executor.apply_write_set(output.write_set());
+    assert!(output
+        .events()
+        .iter()
+        .any(|event| event.type_tag().to_string()
+            == "0x1::multisig_account::TransactionExecutionFailedEvent"));
+    assert_eq!(balance(&harness, &multisig), 1000);
+    assert_eq!(balance(&harness, receiver.address()), receiver_balance);
+
+    // The next transaction in line can then be executed.
+    assert_success!(
+        harness.run_transaction_payload(&owner, execute_payload(multisig, Some(payload)))
+    );
+    assert_eq!(balance(&harness, &multisig), 900);
+    assert_eq!(
+        balance(&harness, receiver.address()),
+        receiver_balance + 100
+    );
+}
+
+#[test]
+fn multisig_transaction_requires_owner() {
+    let mut harness = MoveHarness::new();
+    harness.enable_features(vec![FeatureFlag::MULTISIG_ACCOUNTS]);
+    let owner = harness.new_account_with_key_pair();
+    let outsider = harness.new_account_with_key_pair();
+
+    let multisig = multisig_address(&owner, 10);
+    assert_success!(
+        harness.run_transaction_payload(&owner, aptos_stdlib::multisig_account_create(vec![], 1))
+    );
+    let payload = transfer_payload(&outsider, 1);
+    assert_success!(harness.run_transaction_payload(
+        &owner,
+        aptos_stdlib::multisig_account_create_transaction(
+            multisig,
+            bcs::to_bytes(&payload).unwrap(),
+        ),
+    ));
+
+    assert_abort!(
+        harness.run_transaction_payload(&outsider, execute_payload(multisig, None)),
+        0x50003
+    );
+}
@@ -0,0 +1,139 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto::{secp256r1_ecdsa::Secp256r1EcdsaPrivateKey, PrivateKey, Uniform};
+use aptos_types::{
+    account_address::AccountAddress,
+    chain_id::ChainId,
+    on_chain_config::FeatureFlag,
+    transaction::{
+        authenticator::{AccountAuthenticator, AuthenticationKey},
+        webauthn::WebAuthnSignature,
+        RawTransaction, SignedTransaction, TransactionStatus,
+    },
+    vm_status::StatusCode,
+};
+use cached_packages::aptos_stdlib;
+use e2e_move_tests::{assert_success, MoveHarness};
+use language_e2e_tests::account::{Account, DEFAULT_EXPIRATION_TIME};
+use rand::rngs::OsRng;
+
+const RP_ID: &str = "aptoslabs.com";
+const ORIGIN: &str = "https://aptoslabs.com";
+
+/// Creates an account whose authentication key is derived from a fresh Secp256r1 ECDSA key.
+fn new_secp256r1_account(
+    harness: &mut MoveHarness,
+) -> (AccountAddress, Secp256r1EcdsaPrivateKey, Account) {
+    let funder = harness.new_account_with_key_pair();
+    let private_key = Secp256r1EcdsaPrivateKey::generate(&mut OsRng);
+    let address = AuthenticationKey::secp256r1_ecdsa(&private_key.public_key()).derived_address();
+    assert_success!(harness.run_transaction_payload(
+        &funder,
+        aptos_stdlib::aptos_account_transfer(address, 10_000_000),
+    ));
+    (address, private_key, funder)
+}
+
+fn transfer(sender: AccountAddress, sequence_number: u64, receiver: &Account) -> RawTransaction {
+    RawTransaction::new(
+        sender,
+        sequence_number,
+        aptos_stdlib::aptos_coin_transfer(*receiver.address(), 100),
+        100_000,
+        1,
+        DEFAULT_EXPIRATION_TIME,
+        ChainId::test(),
+    )
+}
+
+#[test]
+fn secp256r1_ecdsa_transaction() {
+    let mut harness = MoveHarness::new();
+    harness.enable_features(vec![FeatureFlag::SECP256R1_ECDSA_AUTHENTICATOR]);
+    let (address, private_key, receiver) = new_secp256r1_account(&mut harness);
+
+    let txn = transfer(address, 0, &receiver)
+        .sign_secp256r1_ecdsa(&private_key, private_key.public_key())
+        .unwrap()
+        .into_inner();
+    assert_success!(harness.run(txn));
+
+    // The sequence number was bumped by the first transaction.
+    let txn = transfer(address, 1, &receiver)
+        .sign_secp256r1_ecdsa(&private_key, private_key.public_key())
+        .unwrap()
+        .into_inner();
+    assert_success!(harness.run(txn));
+}
+
+#[test]
+fn webauthn_transaction() {
+    let mut harness = MoveHarness::new();
+    harness.enable_features(vec![FeatureFlag::SECP256R1_ECDSA_AUTHENTICATOR]);
+    let (address, private_key, receiver) = new_secp256r1_account(&mut harness);
+
+    let txn = transfer(address, 0, &receiver)
+        .sign_webauthn(
+            &private_key,
+            private_key.public_key(),
+            WebAuthnSignature::authenticator_data(RP_ID, 1),
+            ORIGIN,
+        )
+        .unwrap()
+        .into_inner();
+    assert_success!(harness.run(txn));
+}
+
+#[test]
+fn webauthn_transaction_with_wrong_challenge() {
+    let mut harness = MoveHarness::new();
+    harness.enable_features(vec![FeatureFlag::SECP256R1_ECDSA_AUTHENTICATOR]);
+    let (address, private_key, receiver) = new_secp256r1_account(&mut harness);
+
+    // An assertion for another transaction can't be replayed.
+    let other_txn = transfer(address, 1, &receiver);
+    let signature = WebAuthnSignature::sign(
+        &other_txn,
+        &private_key,
+        WebAuthnSignature::authenticator_data(RP_ID, 1),
+        ORIGIN,
+    );
+    let txn = SignedTransaction::new_single_sender(
+        transfer(address, 0, &receiver),
+        AccountAuthenticator::webauthn(private_key.public_key(), signature),
+    );
+    assert_eq!(
+        harness.run(txn),
+        TransactionStatus::Discard(StatusCode::INVALID_SIGNATURE)
+    );
+}
+
+#[test]
+fn secp256r1_ecdsa_transactions_are_gated() {
+    let mut harness = MoveHarness::new();
+    let (address, private_key, receiver) = new_secp256r1_account(&mut harness);
+
+    let txn = transfer(address, 0, &receiver)
+        .sign_secp256r1_ecdsa(&private_key, private_key.public_key())
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        harness.run(txn),
+        TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+
+    let txn = transfer(address, 0, &receiver)
+        .sign_webauthn(
+            &private_key,
+            private_key.public_key(),
+            WebAuthnSignature::authenticator_data(RP_ID, 1),
+            ORIGIN,
+        )
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        harness.run(txn),
+        TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+}
@@ -8,7 +8,16 @@ use e2e_move_tests::{
     initialize_staking, join_validator_set, leave_validator_set, rotate_consensus_key,
     setup_staking, unlock_stake, withdraw_stake, MoveHarness,
 };
-use move_deps::move_core_types::language_storage::CORE_CODE_ADDRESS;
+use move_deps::move_core_types::{
+    language_storage::CORE_CODE_ADDRESS, parser::parse_type_tag, value::MoveValue,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct DistributeRewardsEvent {
+    pool_address: AccountAddress,
+    rewards_amount: u64,
+}
 
 #[test]
 fn test_staking_end_to_end() {
@@ -219,3 +228,62 @@ fn test_staking_rewards_pending_inactive() {
         stake_amount + 570
     );
 }
+
+#[test]
+fn test_staking_rewards_include_transaction_fees() {
+    let mut harness = MoveHarness::new();
+    // Distribute all fees to the block proposers, without burning any.
+    harness.executor.exec(
+        "transaction_fee",
+        "initialize_fee_collection_and_distribution",
+        vec![],
+        vec![
+            MoveValue::Signer(CORE_CODE_ADDRESS)
+                .simple_serialize()
+                .unwrap(),
+            MoveValue::U8(0).simple_serialize().unwrap(),
+        ],
+    );
+    let validator = harness.new_account_at(AccountAddress::from_hex_literal("0x123").unwrap());
+    let sender = harness.new_account_at(AccountAddress::from_hex_literal("0x234").unwrap());
+    let validator_address = *validator.address();
+    let sender_address = *sender.address();
+    let rewards_per_epoch = 285;
+    assert_success!(setup_staking(&mut harness, &validator, 25_000_000));
+    harness.new_epoch();
+
+    // The validator proposes a block with a transaction, whose fee is assigned to the validator
+    // at the start of the next block.
+    harness.new_block_with_metadata(validator_address, vec![]);
+    let balance = |harness: &MoveHarness| {
+        harness
+            .executor
+            .read_coin_store_resource(&sender)
+            .unwrap()
+            .coin()
+    };
+    let balance_before = balance(&harness);
+    assert_success!(harness.run_transaction_payload(
+        &sender,
+        aptos_stdlib::aptos_coin_transfer(sender_address, 1),
+    ));
+    let fee = balance_before - balance(&harness);
+    assert!(fee > 0);
+
+    // The fee is added to the stake pool along with the rewards, and the event accounts for both.
+    let active_before = get_stake_pool(&harness, &validator_address).active;
+    let event_type = parse_type_tag("0x1::stake::DistributeRewardsEvent").unwrap();
+    let events = harness
+        .new_epoch()
+        .into_iter()
+        .filter(|event| event.type_tag() == &event_type)
+        .map(|event| bcs::from_bytes::<DistributeRewardsEvent>(event.event_data()).unwrap())
+        .filter(|event| event.pool_address == validator_address)
+        .collect::<Vec<_>>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].rewards_amount, rewards_per_epoch + fee);
+    assert_eq!(
+        get_stake_pool(&harness, &validator_address).active,
+        active_before + rewards_per_epoch + fee
+    );
+}
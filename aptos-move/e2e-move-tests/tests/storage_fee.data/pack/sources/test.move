@@ -0,0 +1,25 @@
+module 0xCAFE::test {
+    use std::signer;
+
+    struct Foo has key {
+        data: vector<u8>,
+    }
+
+    struct Bar has key {
+        data: vector<u8>,
+    }
+
+    public entry fun create_foo(s: &signer, data: vector<u8>) {
+        move_to(s, Foo { data });
+    }
+
+    /// Deletes `Foo` and creates `Bar` from its data in the same transaction.
+    public entry fun foo_to_bar(s: &signer) acquires Foo {
+        let Foo { data } = move_from<Foo>(signer::address_of(s));
+        move_to(s, Bar { data });
+    }
+
+    public entry fun delete_bar(s: &signer) acquires Bar {
+        let Bar { data: _ } = move_from<Bar>(signer::address_of(s));
+    }
+}
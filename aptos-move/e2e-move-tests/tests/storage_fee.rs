@@ -0,0 +1,83 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{ExecutionStatus, SignedTransaction, TransactionOutput, TransactionStatus},
+};
+use e2e_move_tests::{assert_success, MoveHarness};
+use language_e2e_tests::account::Account;
+
+mod common;
+
+const STORAGE_FEE_PER_SLOT: u64 = 10_000;
+
+fn balance(h: &MoveHarness, account: &Account) -> u64 {
+    h.executor.read_coin_store_resource(account).unwrap().coin()
+}
+
+/// Runs the transaction, applies its write set and returns its output along with the amount
+/// the sender's balance went down by, which is negative if the refund exceeds the gas used.
+fn run(h: &mut MoveHarness, account: &Account, txn: SignedTransaction) -> (TransactionOutput, i64) {
+    let balance_before = balance(h, account);
+    let output = h.executor.execute_transaction(txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    h.executor.apply_write_set(output.write_set());
+    (output, balance_before as i64 - balance(h, account) as i64)
+}
+
+#[test]
+fn storage_fee_refund() {
+    let mut h = MoveHarness::new();
+    // Only charge per slot, so the fees don't depend on the size of keys and values.
+    h.modify_gas_params(|gas_params| {
+        gas_params.txn.storage_fee_per_state_slot_create = STORAGE_FEE_PER_SLOT.into();
+    });
+
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    assert_success!(h.publish_package(&acc, &common::test_dir_path("storage_fee.data/pack")));
+
+    // Creating a resource pays for a new slot, as part of the gas used.
+    let txn = h.create_entry_function(
+        &acc,
+        str::parse("0xcafe::test::create_foo").unwrap(),
+        vec![],
+        vec![bcs::to_bytes(&vec![0u8; 100]).unwrap()],
+    );
+    let (output, charged) = run(&mut h, &acc, txn);
+    assert!(output.gas_used() > STORAGE_FEE_PER_SLOT);
+    assert_eq!(charged, output.gas_used() as i64);
+
+    // Replacing it with another resource frees a slot and pays for a new one. The refund comes
+    // back on top of the gas used, which still includes the storage fee.
+    let txn = h.create_entry_function(
+        &acc,
+        str::parse("0xcafe::test::foo_to_bar").unwrap(),
+        vec![],
+        vec![],
+    );
+    let (output, charged) = run(&mut h, &acc, txn);
+    assert!(output.gas_used() > STORAGE_FEE_PER_SLOT);
+    assert_eq!(
+        charged,
+        output.gas_used() as i64 - STORAGE_FEE_PER_SLOT as i64
+    );
+
+    // Only deleting a resource pays no storage fee, but still gets the refund out of the
+    // deposits, even if it exceeds the gas used.
+    let txn = h.create_entry_function(
+        &acc,
+        str::parse("0xcafe::test::delete_bar").unwrap(),
+        vec![],
+        vec![],
+    );
+    let (output, charged) = run(&mut h, &acc, txn);
+    assert!(output.gas_used() > 0);
+    assert_eq!(
+        charged,
+        output.gas_used() as i64 - STORAGE_FEE_PER_SLOT as i64
+    );
+}
@@ -163,6 +163,7 @@ impl Default for Account {
 pub struct TransactionBuilder {
     pub sender: Account,
     pub secondary_signers: Vec<Account>,
+    pub fee_payer: Option<Account>,
     pub sequence_number: Option<u64>,
     pub program: Option<TransactionPayload>,
     pub max_gas_amount: Option<u64>,
@@ -176,6 +177,7 @@ impl TransactionBuilder {
         Self {
             sender,
             secondary_signers: Vec::new(),
+            fee_payer: None,
             sequence_number: None,
             program: None,
             max_gas_amount: None,
@@ -190,6 +192,11 @@ impl TransactionBuilder {
         self
     }
 
+    pub fn fee_payer(mut self, fee_payer: Account) -> Self {
+        self.fee_payer = Some(fee_payer);
+        self
+    }
+
     pub fn sequence_number(mut self, sequence_number: u64) -> Self {
         self.sequence_number = Some(sequence_number);
         self
@@ -290,6 +297,38 @@ impl TransactionBuilder {
         .unwrap()
         .into_inner()
     }
+
+    pub fn sign_fee_payer(self) -> SignedTransaction {
+        let secondary_signer_addresses: Vec<AccountAddress> = self
+            .secondary_signers
+            .iter()
+            .map(|signer| *signer.address())
+            .collect();
+        let secondary_private_keys = self
+            .secondary_signers
+            .iter()
+            .map(|signer| &signer.privkey)
+            .collect();
+        let fee_payer = self.fee_payer.expect("fee payer not set");
+        RawTransaction::new(
+            *self.sender.address(),
+            self.sequence_number.expect("sequence number not set"),
+            self.program.expect("transaction payload not set"),
+            self.max_gas_amount.unwrap_or(gas_costs::TXN_RESERVED),
+            self.gas_unit_price.unwrap_or(0),
+            self.ttl.unwrap_or(DEFAULT_EXPIRATION_TIME),
+            ChainId::test(),
+        )
+        .sign_fee_payer(
+            &self.sender.privkey,
+            secondary_signer_addresses,
+            secondary_private_keys,
+            *fee_payer.address(),
+            &fee_payer.privkey,
+        )
+        .unwrap()
+        .into_inner()
+    }
 }
 
 //---------------------------------------------------------------------------
@@ -29,6 +29,7 @@ use aptos_types::{
         CORE_CODE_ADDRESS,
     },
     block_metadata::BlockMetadata,
+    contract_event::ContractEvent,
     on_chain_config::{OnChainConfig, ValidatorSet, Version},
     state_store::state_key::StateKey,
     transaction::{
@@ -475,11 +476,11 @@ impl FakeExecutor {
         &self.data_store
     }
 
-    pub fn new_block(&mut self) {
-        self.new_block_with_timestamp(self.block_time + 1);
+    pub fn new_block(&mut self) -> Vec<ContractEvent> {
+        self.new_block_with_timestamp(self.block_time + 1)
     }
 
-    pub fn new_block_with_timestamp(&mut self, time_microseconds: u64) {
+    pub fn new_block_with_timestamp(&mut self, time_microseconds: u64) -> Vec<ContractEvent> {
         self.block_time = time_microseconds;
 
         let validator_set = ValidatorSet::fetch_config(&self.data_store.as_move_resolver())
@@ -489,11 +490,13 @@ impl FakeExecutor {
         self.new_block_with_metadata(proposer, vec![])
     }
 
+    /// Executes the prologue of a new block proposed by `proposer`, and returns the events it
+    /// emitted.
     pub fn new_block_with_metadata(
         &mut self,
         proposer: AccountAddress,
         failed_proposer_indices: Vec<u32>,
-    ) {
+    ) -> Vec<ContractEvent> {
         let validator_set = ValidatorSet::fetch_config(&self.data_store.as_move_resolver())
             .expect("Unable to retrieve the validator set from storage");
         let new_block = BlockMetadata::new(
@@ -515,6 +518,7 @@ impl FakeExecutor {
         assert_eq!(event.key(), &new_block_event_key());
         assert!(bcs::from_bytes::<NewBlockEvent>(event.event_data()).is_ok());
         self.apply_write_set(output.write_set());
+        output.events().to_vec()
     }
 
     fn module(name: &str) -> ModuleId {
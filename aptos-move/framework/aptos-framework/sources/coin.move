@@ -6,6 +6,8 @@ module aptos_framework::coin {
     use std::signer;
 
     use aptos_framework::account;
+    use aptos_framework::aggregator_factory;
+    use aptos_framework::aggregator::{Self, Aggregator};
     use aptos_framework::optional_aggregator::{Self, OptionalAggregator};
     use aptos_framework::system_addresses;
     use aptos_std::event::{Self, EventHandle};
@@ -14,6 +16,7 @@ module aptos_framework::coin {
 
     friend aptos_framework::aptos_coin;
     friend aptos_framework::genesis;
+    friend aptos_framework::transaction_fee;
 
     //
     // Errors.
@@ -49,6 +52,9 @@ module aptos_framework::coin {
     /// Cannot upgrade the total supply of coins to different implementation.
     const ECOIN_SUPPLY_UPGRADE_NOT_SUPPORTED: u64 = 11;
 
+    /// The value of aggregatable coin used for transaction fees redistribution does not fit in u64.
+    const EAGGREGATABLE_COIN_VALUE_TOO_LARGE: u64 = 12;
+
     /// Core data structures
 
     /// Main structure representing a coin/token in an account's custody.
@@ -57,6 +63,14 @@ module aptos_framework::coin {
         value: u64,
     }
 
+    /// Represents a coin with aggregator as its value. This allows to update
+    /// the coin in every transaction avoiding read-modify-write conflicts. Only
+    /// used for gas fees distribution by Aptos Framework (0x1).
+    struct AggregatableCoin<phantom CoinType> has store {
+        /// Amount of aggregatable coin this address has.
+        value: Aggregator,
+    }
+
     /// A holder of a specific coin types and associated event handles.
     /// These are kept in a single resource to ensure locality of data.
     struct CoinStore<phantom CoinType> has key {
@@ -66,6 +80,9 @@ module aptos_framework::coin {
         withdraw_events: EventHandle<WithdrawEvent>,
     }
 
+    /// Maximum possible aggregatable coin value.
+    const MAX_U64: u128 = 18446744073709551615;
+
     /// Maximum possible coin supply.
     const MAX_U128: u128 = 340282366920938463463374607431768211455;
 
@@ -108,6 +125,61 @@ module aptos_framework::coin {
     /// Capability required to burn coins.
     struct BurnCapability<phantom CoinType> has copy, store { }
 
+    //
+    // Aggregatable coin functions
+    //
+
+    /// Creates a new aggregatable coin with value overflowing on `MAX_U64`. Note that this function can
+    /// only be called by Aptos Framework (0x1) account for now because of `create_aggregator`.
+    public(friend) fun initialize_aggregatable_coin<CoinType>(aptos_framework: &signer): AggregatableCoin<CoinType> {
+        let aggregator = aggregator_factory::create_aggregator(aptos_framework, MAX_U64);
+        AggregatableCoin<CoinType> {
+            value: aggregator,
+        }
+    }
+
+    /// Returns true if the value of aggregatable coin is zero.
+    public(friend) fun is_aggregatable_coin_zero<CoinType>(coin: &AggregatableCoin<CoinType>): bool {
+        let amount = aggregator::read(&coin.value);
+        amount == 0
+    }
+
+    /// Drains the aggregatable coin, setting it to zero and returning a standard coin.
+    public(friend) fun drain_aggregatable_coin<CoinType>(coin: &mut AggregatableCoin<CoinType>): Coin<CoinType> {
+        let amount = aggregator::read(&coin.value);
+        assert!(amount <= MAX_U64, error::out_of_range(EAGGREGATABLE_COIN_VALUE_TOO_LARGE));
+        aggregator::sub(&mut coin.value, amount);
+        Coin<CoinType> {
+            value: (amount as u64),
+        }
+    }
+
+    /// Merges `coin` into aggregatable coin (`dst_coin`).
+    public(friend) fun merge_aggregatable_coin<CoinType>(dst_coin: &mut AggregatableCoin<CoinType>, coin: Coin<CoinType>) {
+        let Coin { value } = coin;
+        let amount = (value as u128);
+        aggregator::add(&mut dst_coin.value, amount);
+    }
+
+    /// Collects a specified amount of coin from an account into aggregatable coin.
+    /// Only the sender's `CoinStore` is modified, the aggregatable coin only
+    /// receives a delta, so that collecting fees from many transactions in a block
+    /// does not serialize their execution.
+    public(friend) fun collect_into_aggregatable_coin<CoinType>(
+        account_addr: address,
+        amount: u64,
+        dst_coin: &mut AggregatableCoin<CoinType>,
+    ) acquires CoinStore {
+        // Skip collecting if amount is zero.
+        if (amount == 0) {
+            return
+        };
+
+        let coin_store = borrow_global_mut<CoinStore<CoinType>>(account_addr);
+        let coin = extract(&mut coin_store.coin, amount);
+        merge_aggregatable_coin(dst_coin, coin);
+    }
+
     //
     // Total supply config
     //
@@ -385,9 +457,6 @@ module aptos_framework::coin {
         Coin<CoinType> { value: amount }
     }
 
-    #[test_only]
-    use aptos_framework::aggregator_factory;
-
     public fun register<CoinType>(account: &signer) {
         let account_addr = signer::address_of(account);
         assert!(
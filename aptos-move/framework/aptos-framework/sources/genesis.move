@@ -20,6 +20,11 @@ module aptos_framework::genesis {
     use aptos_framework::version;
     use aptos_framework::chain_status;
 
+    struct AccountMap has drop {
+        account_address: address,
+        balance: u64,
+    }
+
     struct ValidatorConfiguration has copy, drop {
         owner_address: address,
         operator_address: address,
@@ -93,6 +98,7 @@ module aptos_framework::genesis {
         // Ensure we can create aggregators for supply, but not enable it for common use just yet.
         aggregator_factory::initialize_aggregator_factory(&aptos_framework_account);
         coin::initialize_supply_config(&aptos_framework_account);
+        transaction_fee::initialize_storage_fee_deposits(&aptos_framework_account);
 
         chain_id::initialize(&aptos_framework_account, chain_id);
         reconfiguration::initialize(&aptos_framework_account);
@@ -124,6 +130,21 @@ module aptos_framework::genesis {
         aptos_coin::configure_accounts_for_test(aptos_framework, &core_resources, mint_cap);
     }
 
+    /// Creates the given accounts and mints each of them its initial balance.
+    /// Must be called before `create_initialize_validators`, which destroys the mint capability.
+    fun create_accounts(aptos_framework: &signer, accounts: vector<AccountMap>) {
+        let i = 0;
+        let num_accounts = vector::length(&accounts);
+        while (i < num_accounts) {
+            let account_map = vector::borrow(&accounts, i);
+            let account = account::create_account(account_map.account_address);
+            coin::register<AptosCoin>(&account);
+            aptos_coin::mint(aptos_framework, account_map.account_address, account_map.balance);
+
+            i = i + 1;
+        };
+    }
+
     /// Sets up the initial validator set for the network.
     /// The validator "owner" accounts, and their authentication
     /// Addresses (and keys) are encoded in the `owners`
@@ -0,0 +1,702 @@
+/// Enhanced multisig account standard on Aptos. This is different from the native multisig scheme support enforced via
+/// the account's auth key.
+///
+/// This module allows creating a flexible and powerful multisig account with seamless support for updating owners
+/// without changing the auth key. Users can choose to store transaction payloads waiting for owner signatures on chain
+/// and owners approve or reject them on chain. Once a transaction has collected enough approvals, any owner can
+/// execute it by sending a transaction with the multisig transaction payload, which runs the stored payload with the
+/// multisig account as the signer.
+///
+/// The workflow is as follows:
+/// 1. An owner creates the multisig account with `create`, specifying the additional owners and the number of
+/// approvals required. The multisig account is a resource account derived from the creator's address and sequence
+/// number, and its auth key is rotated to 0 so it can only act through approved transactions.
+/// 2. An owner proposes a transaction with `create_transaction`, passing the BCS-encoded `MultisigTransactionPayload`
+/// to execute. Proposing a transaction counts as an approval from its creator.
+/// 3. Other owners `approve_transaction` or `reject_transaction`. An owner can change their vote at any time before
+/// the transaction is resolved.
+/// 4. Once the next transaction in line has enough approvals, any owner can execute it with a transaction whose
+/// payload is `TransactionPayload::Multisig`. If the payload aborts, its effects are discarded but the transaction is
+/// still resolved, with a `TransactionExecutionFailedEvent`. If instead it has enough rejections, any owner can remove it
+/// with `execute_rejected_transaction`.
+///
+/// Transactions are resolved strictly in the order they were created. Owners and the number of required signatures
+/// can only be changed by the multisig account itself, i.e. through an approved transaction calling `add_owners`,
+/// `remove_owners` or `update_signatures_required`.
+module aptos_framework::multisig_account {
+    use std::bcs;
+    use std::error;
+    use std::features;
+    use std::signer;
+    use std::string::{Self, String};
+    use std::vector;
+    use aptos_std::event::{Self, EventHandle};
+    use aptos_std::simple_map::{Self, SimpleMap};
+    use aptos_std::table::{Self, Table};
+    use aptos_framework::account::{Self, SignerCapability};
+    use aptos_framework::aptos_coin::AptosCoin;
+    use aptos_framework::coin;
+    use aptos_framework::timestamp;
+
+    /// The salt used to derive multisig account addresses from the creator's address and sequence number.
+    const DOMAIN_SEPARATOR: vector<u8> = b"aptos_framework::multisig_account";
+
+    const ZERO_AUTH_KEY: vector<u8> = x"0000000000000000000000000000000000000000000000000000000000000000";
+
+    /// Owner list cannot contain the same address more than once.
+    const EDUPLICATE_OWNER: u64 = 1;
+    /// Specified account is not a multisig account.
+    const EACCOUNT_NOT_MULTISIG: u64 = 2;
+    /// Account executing this operation is not an owner of the multisig account.
+    const ENOT_OWNER: u64 = 3;
+    /// Transaction payload cannot be empty.
+    const EPAYLOAD_CANNOT_BE_EMPTY: u64 = 4;
+    /// Multisig account must have at least one owner.
+    const ENOT_ENOUGH_OWNERS: u64 = 5;
+    /// Transaction with specified sequence number does not exist.
+    const ETRANSACTION_NOT_FOUND: u64 = 6;
+    /// Transaction has not received enough approvals to be executed.
+    const ENOT_ENOUGH_APPROVALS: u64 = 7;
+    /// Transaction has not received enough rejections to be removed.
+    const ENOT_ENOUGH_REJECTIONS: u64 = 8;
+    /// Number of signatures required must be more than zero and at most the total number of owners.
+    const EINVALID_SIGNATURES_REQUIRED: u64 = 9;
+    /// The payload provided for execution does not match the payload stored on chain.
+    const EPAYLOAD_DOES_NOT_MATCH: u64 = 10;
+    /// Multisig accounts have not been enabled on this network yet.
+    const EMULTISIG_ACCOUNTS_NOT_ENABLED: u64 = 11;
+    /// The multisig account itself cannot be one of its owners.
+    const EOWNER_CANNOT_BE_MULTISIG_ACCOUNT_ITSELF: u64 = 12;
+
+    /// Represents a multisig account's configurations and transactions.
+    struct MultisigAccount has key {
+        /// The list of all owner addresses.
+        owners: vector<address>,
+        /// The number of signatures required to pass a transaction (k in k-of-n).
+        num_signatures_required: u64,
+        /// Map from transaction sequence number to the pending transaction.
+        transactions: Table<u64, MultisigTransaction>,
+        /// The sequence number of the last transaction that was executed or rejected.
+        last_executed_sequence_number: u64,
+        /// The sequence number to assign to the next transaction created. This is not the same as
+        /// the multisig account's sequence number in its `account::Account` resource.
+        next_sequence_number: u64,
+        /// The signer capability used to execute approved transactions as the multisig account.
+        signer_cap: SignerCapability,
+
+        // Events.
+        add_owners_events: EventHandle<AddOwnersEvent>,
+        remove_owners_events: EventHandle<RemoveOwnersEvent>,
+        update_signature_required_events: EventHandle<UpdateSignaturesRequiredEvent>,
+        create_transaction_events: EventHandle<CreateTransactionEvent>,
+        vote_events: EventHandle<VoteEvent>,
+        execute_rejected_transaction_events: EventHandle<ExecuteRejectedTransactionEvent>,
+        execute_transaction_events: EventHandle<TransactionExecutionSucceededEvent>,
+        transaction_execution_failed_events: EventHandle<TransactionExecutionFailedEvent>,
+    }
+
+    /// A transaction to be executed by a multisig account, once it has collected enough approvals.
+    struct MultisigTransaction has copy, drop, store {
+        /// The BCS-encoded `MultisigTransactionPayload` to execute.
+        payload: vector<u8>,
+        /// Owner address to vote (true for approval, false for rejection).
+        votes: SimpleMap<address, bool>,
+        /// The owner who created this transaction.
+        creator: address,
+        /// The timestamp in seconds when the transaction was created.
+        creation_time_secs: u64,
+    }
+
+    /// Event emitted when new owners are added to the multisig account.
+    struct AddOwnersEvent has drop, store {
+        owners_added: vector<address>,
+    }
+
+    /// Event emitted when owners are removed from the multisig account.
+    struct RemoveOwnersEvent has drop, store {
+        owners_removed: vector<address>,
+    }
+
+    /// Event emitted when the number of signatures required is updated.
+    struct UpdateSignaturesRequiredEvent has drop, store {
+        old_num_signatures_required: u64,
+        new_num_signatures_required: u64,
+    }
+
+    /// Event emitted when a transaction is created.
+    struct CreateTransactionEvent has drop, store {
+        creator: address,
+        sequence_number: u64,
+        transaction: MultisigTransaction,
+    }
+
+    /// Event emitted when an owner approves or rejects a transaction.
+    struct VoteEvent has drop, store {
+        owner: address,
+        sequence_number: u64,
+        approved: bool,
+    }
+
+    /// Event emitted when a transaction with enough rejections is removed.
+    struct ExecuteRejectedTransactionEvent has drop, store {
+        sequence_number: u64,
+        num_rejections: u64,
+        executor: address,
+    }
+
+    /// Event emitted when a transaction is executed.
+    struct TransactionExecutionSucceededEvent has drop, store {
+        executor: address,
+        sequence_number: u64,
+        transaction_payload: vector<u8>,
+        num_approvals: u64,
+    }
+
+    /// Event emitted when the payload of a transaction aborts. The transaction is still resolved, so the next one in
+    /// line can be executed.
+    struct TransactionExecutionFailedEvent has drop, store {
+        executor: address,
+        sequence_number: u64,
+        transaction_payload: vector<u8>,
+        num_approvals: u64,
+        execution_error: ExecutionError,
+    }
+
+    /// Details of why the payload of a transaction failed to execute.
+    struct ExecutionError has copy, drop, store {
+        /// The module the payload aborted in, if any.
+        abort_location: String,
+        /// The kind of failure, e.g. "MoveAbort" or "ExecutionFailure".
+        error_type: String,
+        /// The abort code, or the VM status code for other failures.
+        error_code: u64,
+    }
+
+    ////////////////////////// View functions ///////////////////////////////
+
+    /// Return the multisig account's owners.
+    public fun owners(multisig_account: address): vector<address> acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        borrow_global<MultisigAccount>(multisig_account).owners
+    }
+
+    /// Return the number of signatures required to execute or reject a transaction.
+    public fun num_signatures_required(multisig_account: address): u64 acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        borrow_global<MultisigAccount>(multisig_account).num_signatures_required
+    }
+
+    /// Return the sequence number of the last executed or rejected transaction.
+    public fun last_resolved_sequence_number(multisig_account: address): u64 acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        borrow_global<MultisigAccount>(multisig_account).last_executed_sequence_number
+    }
+
+    /// Return the sequence number that will be assigned to the next transaction created.
+    public fun next_sequence_number(multisig_account: address): u64 acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        borrow_global<MultisigAccount>(multisig_account).next_sequence_number
+    }
+
+    /// Return the pending transaction with the given sequence number.
+    public fun get_transaction(
+        multisig_account: address,
+        sequence_number: u64,
+    ): MultisigTransaction acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global<MultisigAccount>(multisig_account);
+        assert_transaction_exists(multisig_account_resource, sequence_number);
+        *table::borrow(&multisig_account_resource.transactions, sequence_number)
+    }
+
+    /// Return true if the transaction with the given sequence number is next in line and has enough approvals.
+    public fun can_be_executed(multisig_account: address, sequence_number: u64): bool acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global<MultisigAccount>(multisig_account);
+        assert_transaction_exists(multisig_account_resource, sequence_number);
+        let (num_approvals, _) = num_approvals_and_rejections(multisig_account_resource, sequence_number);
+        sequence_number == multisig_account_resource.last_executed_sequence_number + 1 &&
+            num_approvals >= multisig_account_resource.num_signatures_required
+    }
+
+    /// Return true if the transaction with the given sequence number is next in line and has enough rejections.
+    public fun can_be_rejected(multisig_account: address, sequence_number: u64): bool acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global<MultisigAccount>(multisig_account);
+        assert_transaction_exists(multisig_account_resource, sequence_number);
+        let (_, num_rejections) = num_approvals_and_rejections(multisig_account_resource, sequence_number);
+        sequence_number == multisig_account_resource.last_executed_sequence_number + 1 &&
+            num_rejections >= multisig_account_resource.num_signatures_required
+    }
+
+    /// Return the address of the multisig account that `creator` would create with their next transaction.
+    public fun get_next_multisig_account_address(creator: address): address {
+        let seed = DOMAIN_SEPARATOR;
+        vector::append(&mut seed, bcs::to_bytes(&account::get_sequence_number(creator)));
+        account::create_resource_address(&creator, seed)
+    }
+
+    ////////////////////////// Multisig account creation ///////////////////////////////
+
+    /// Creates a new multisig account owned by the sender and `additional_owners`, which requires
+    /// `num_signatures_required` approvals to execute a transaction.
+    public entry fun create(
+        owner: &signer,
+        additional_owners: vector<address>,
+        num_signatures_required: u64,
+    ) {
+        assert!(features::multisig_accounts_enabled(), error::unavailable(EMULTISIG_ACCOUNTS_NOT_ENABLED));
+
+        let owner_address = signer::address_of(owner);
+        let seed = DOMAIN_SEPARATOR;
+        vector::append(&mut seed, bcs::to_bytes(&account::get_sequence_number(owner_address)));
+        let (multisig_signer, signer_cap) = account::create_resource_account(owner, seed);
+        // The multisig account can only act through approved transactions, never through its own key.
+        account::rotate_authentication_key_internal(&multisig_signer, ZERO_AUTH_KEY);
+        coin::register<AptosCoin>(&multisig_signer);
+
+        let owners = additional_owners;
+        vector::push_back(&mut owners, owner_address);
+        let multisig_address = signer::address_of(&multisig_signer);
+        validate_owners(&owners, multisig_address);
+        assert!(
+            num_signatures_required > 0 && num_signatures_required <= vector::length(&owners),
+            error::invalid_argument(EINVALID_SIGNATURES_REQUIRED),
+        );
+
+        move_to(&multisig_signer, MultisigAccount {
+            owners,
+            num_signatures_required,
+            transactions: table::new<u64, MultisigTransaction>(),
+            // First transaction will start at sequence number 1 instead of 0.
+            last_executed_sequence_number: 0,
+            next_sequence_number: 1,
+            signer_cap,
+            add_owners_events: account::new_event_handle<AddOwnersEvent>(&multisig_signer),
+            remove_owners_events: account::new_event_handle<RemoveOwnersEvent>(&multisig_signer),
+            update_signature_required_events: account::new_event_handle<UpdateSignaturesRequiredEvent>(&multisig_signer),
+            create_transaction_events: account::new_event_handle<CreateTransactionEvent>(&multisig_signer),
+            vote_events: account::new_event_handle<VoteEvent>(&multisig_signer),
+            execute_rejected_transaction_events: account::new_event_handle<ExecuteRejectedTransactionEvent>(&multisig_signer),
+            execute_transaction_events: account::new_event_handle<TransactionExecutionSucceededEvent>(&multisig_signer),
+            transaction_execution_failed_events: account::new_event_handle<TransactionExecutionFailedEvent>(&multisig_signer),
+        });
+    }
+
+    ////////////////////////// Self-updates ///////////////////////////////
+
+    /// Add new owners to the multisig account. This can only be invoked by the multisig account itself, through an
+    /// approved transaction.
+    public entry fun add_owners(multisig_account: &signer, new_owners: vector<address>) acquires MultisigAccount {
+        let multisig_address = signer::address_of(multisig_account);
+        assert_multisig_account_exists(multisig_address);
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_address);
+
+        vector::append(&mut multisig_account_resource.owners, new_owners);
+        validate_owners(&multisig_account_resource.owners, multisig_address);
+        event::emit_event(&mut multisig_account_resource.add_owners_events, AddOwnersEvent {
+            owners_added: new_owners,
+        });
+    }
+
+    /// Remove owners from the multisig account. This can only be invoked by the multisig account itself, through an
+    /// approved transaction. Owners that are not part of the multisig account are ignored.
+    public entry fun remove_owners(
+        multisig_account: &signer,
+        owners_to_remove: vector<address>,
+    ) acquires MultisigAccount {
+        let multisig_address = signer::address_of(multisig_account);
+        assert_multisig_account_exists(multisig_address);
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_address);
+
+        let owners = &mut multisig_account_resource.owners;
+        let owners_removed = vector::empty<address>();
+        let i = 0;
+        let len = vector::length(&owners_to_remove);
+        while (i < len) {
+            let owner_to_remove = *vector::borrow(&owners_to_remove, i);
+            let (found, index) = vector::index_of(owners, &owner_to_remove);
+            if (found) {
+                vector::push_back(&mut owners_removed, owner_to_remove);
+                vector::swap_remove(owners, index);
+            };
+            i = i + 1;
+        };
+
+        // There must always be at least one owner, and enough owners to reach the number of signatures required.
+        let num_owners = vector::length(owners);
+        assert!(num_owners > 0, error::invalid_state(ENOT_ENOUGH_OWNERS));
+        assert!(
+            num_owners >= multisig_account_resource.num_signatures_required,
+            error::invalid_state(ENOT_ENOUGH_OWNERS),
+        );
+        event::emit_event(&mut multisig_account_resource.remove_owners_events, RemoveOwnersEvent { owners_removed });
+    }
+
+    /// Update the number of signatures required to execute or reject a transaction. This can only be invoked by the
+    /// multisig account itself, through an approved transaction.
+    public entry fun update_signatures_required(
+        multisig_account: &signer,
+        new_num_signatures_required: u64,
+    ) acquires MultisigAccount {
+        let multisig_address = signer::address_of(multisig_account);
+        assert_multisig_account_exists(multisig_address);
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_address);
+
+        assert!(
+            new_num_signatures_required > 0 &&
+                new_num_signatures_required <= vector::length(&multisig_account_resource.owners),
+            error::invalid_argument(EINVALID_SIGNATURES_REQUIRED),
+        );
+        let old_num_signatures_required = multisig_account_resource.num_signatures_required;
+        multisig_account_resource.num_signatures_required = new_num_signatures_required;
+        event::emit_event(
+            &mut multisig_account_resource.update_signature_required_events,
+            UpdateSignaturesRequiredEvent { old_num_signatures_required, new_num_signatures_required },
+        );
+    }
+
+    ////////////////////////// Multisig transaction flow ///////////////////////////////
+
+    /// Create a multisig transaction, which will have one approval initially (from the creator).
+    public entry fun create_transaction(
+        owner: &signer,
+        multisig_account: address,
+        payload: vector<u8>,
+    ) acquires MultisigAccount {
+        assert!(vector::length(&payload) > 0, error::invalid_argument(EPAYLOAD_CANNOT_BE_EMPTY));
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_account);
+        assert_is_owner(owner, multisig_account_resource);
+
+        let creator = signer::address_of(owner);
+        let votes = simple_map::create<address, bool>();
+        simple_map::add(&mut votes, creator, true);
+        let transaction = MultisigTransaction {
+            payload,
+            votes,
+            creator,
+            creation_time_secs: timestamp::now_seconds(),
+        };
+
+        let sequence_number = multisig_account_resource.next_sequence_number;
+        multisig_account_resource.next_sequence_number = sequence_number + 1;
+        table::add(&mut multisig_account_resource.transactions, sequence_number, transaction);
+        event::emit_event(
+            &mut multisig_account_resource.create_transaction_events,
+            CreateTransactionEvent { creator, sequence_number, transaction },
+        );
+    }
+
+    /// Approve a multisig transaction.
+    public entry fun approve_transaction(
+        owner: &signer,
+        multisig_account: address,
+        sequence_number: u64,
+    ) acquires MultisigAccount {
+        vote_transaction(owner, multisig_account, sequence_number, true);
+    }
+
+    /// Reject a multisig transaction.
+    public entry fun reject_transaction(
+        owner: &signer,
+        multisig_account: address,
+        sequence_number: u64,
+    ) acquires MultisigAccount {
+        vote_transaction(owner, multisig_account, sequence_number, false);
+    }
+
+    /// Remove the next transaction in line if it has enough rejections.
+    public entry fun execute_rejected_transaction(
+        owner: &signer,
+        multisig_account: address,
+    ) acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_account);
+        assert_is_owner(owner, multisig_account_resource);
+
+        let sequence_number = multisig_account_resource.last_executed_sequence_number + 1;
+        assert_transaction_exists(multisig_account_resource, sequence_number);
+        let (_, num_rejections) = num_approvals_and_rejections(multisig_account_resource, sequence_number);
+        assert!(
+            num_rejections >= multisig_account_resource.num_signatures_required,
+            error::invalid_state(ENOT_ENOUGH_REJECTIONS),
+        );
+
+        table::remove(&mut multisig_account_resource.transactions, sequence_number);
+        multisig_account_resource.last_executed_sequence_number = sequence_number;
+        event::emit_event(
+            &mut multisig_account_resource.execute_rejected_transaction_events,
+            ExecuteRejectedTransactionEvent {
+                sequence_number,
+                num_rejections,
+                executor: signer::address_of(owner),
+            },
+        );
+    }
+
+    ////////////////////////// To be called by VM only ///////////////////////////////
+
+    /// Called by the VM before executing a multisig transaction payload, to check that the sender is an owner and
+    /// that the next transaction in line has enough approvals. Returns the payload to execute: the one stored on chain
+    /// if `payload` is empty, otherwise `payload` itself after checking it matches the stored one.
+    fun validate_multisig_transaction(
+        owner: &signer,
+        multisig_account: address,
+        payload: vector<u8>,
+    ): vector<u8> acquires MultisigAccount {
+        assert!(features::multisig_accounts_enabled(), error::unavailable(EMULTISIG_ACCOUNTS_NOT_ENABLED));
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global<MultisigAccount>(multisig_account);
+        assert_is_owner(owner, multisig_account_resource);
+
+        let sequence_number = multisig_account_resource.last_executed_sequence_number + 1;
+        assert_transaction_exists(multisig_account_resource, sequence_number);
+        let (num_approvals, _) = num_approvals_and_rejections(multisig_account_resource, sequence_number);
+        assert!(
+            num_approvals >= multisig_account_resource.num_signatures_required,
+            error::invalid_state(ENOT_ENOUGH_APPROVALS),
+        );
+
+        let stored_payload = table::borrow(&multisig_account_resource.transactions, sequence_number).payload;
+        assert!(
+            vector::is_empty(&payload) || payload == stored_payload,
+            error::invalid_argument(EPAYLOAD_DOES_NOT_MATCH),
+        );
+        stored_payload
+    }
+
+    /// Called by the VM after the payload of the next transaction in line was executed successfully.
+    fun successful_transaction_execution_cleanup(
+        executor: address,
+        multisig_account: address,
+        transaction_payload: vector<u8>,
+    ) acquires MultisigAccount {
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_account);
+        let sequence_number = multisig_account_resource.last_executed_sequence_number + 1;
+        let (num_approvals, _) = num_approvals_and_rejections(multisig_account_resource, sequence_number);
+
+        table::remove(&mut multisig_account_resource.transactions, sequence_number);
+        multisig_account_resource.last_executed_sequence_number = sequence_number;
+        event::emit_event(
+            &mut multisig_account_resource.execute_transaction_events,
+            TransactionExecutionSucceededEvent {
+                executor,
+                sequence_number,
+                transaction_payload,
+                num_approvals,
+            },
+        );
+    }
+
+    /// Called by the VM after the payload of the next transaction in line failed to execute. The effects of the payload
+    /// are discarded, but the transaction is resolved anyway so that it doesn't block the transactions after it.
+    fun failed_transaction_execution_cleanup(
+        executor: address,
+        multisig_account: address,
+        transaction_payload: vector<u8>,
+        abort_location: vector<u8>,
+        error_type: vector<u8>,
+        error_code: u64,
+    ) acquires MultisigAccount {
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_account);
+        let sequence_number = multisig_account_resource.last_executed_sequence_number + 1;
+        let (num_approvals, _) = num_approvals_and_rejections(multisig_account_resource, sequence_number);
+
+        table::remove(&mut multisig_account_resource.transactions, sequence_number);
+        multisig_account_resource.last_executed_sequence_number = sequence_number;
+        event::emit_event(
+            &mut multisig_account_resource.transaction_execution_failed_events,
+            TransactionExecutionFailedEvent {
+                executor,
+                sequence_number,
+                transaction_payload,
+                num_approvals,
+                execution_error: ExecutionError {
+                    abort_location: string::utf8(abort_location),
+                    error_type: string::utf8(error_type),
+                    error_code,
+                },
+            },
+        );
+    }
+
+    ////////////////////////// Private functions ///////////////////////////////
+
+    fun vote_transaction(
+        owner: &signer,
+        multisig_account: address,
+        sequence_number: u64,
+        approved: bool,
+    ) acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_account);
+        assert_is_owner(owner, multisig_account_resource);
+        assert_transaction_exists(multisig_account_resource, sequence_number);
+
+        let transaction = table::borrow_mut(&mut multisig_account_resource.transactions, sequence_number);
+        let owner_address = signer::address_of(owner);
+        if (simple_map::contains_key(&transaction.votes, &owner_address)) {
+            *simple_map::borrow_mut(&mut transaction.votes, &owner_address) = approved;
+        } else {
+            simple_map::add(&mut transaction.votes, owner_address, approved);
+        };
+
+        event::emit_event(
+            &mut multisig_account_resource.vote_events,
+            VoteEvent { owner: owner_address, sequence_number, approved },
+        );
+    }
+
+    /// Count the votes on a transaction, only taking into account those of current owners.
+    fun num_approvals_and_rejections(
+        multisig_account_resource: &MultisigAccount,
+        sequence_number: u64,
+    ): (u64, u64) {
+        let owners = &multisig_account_resource.owners;
+        let transaction = table::borrow(&multisig_account_resource.transactions, sequence_number);
+        let num_approvals = 0;
+        let num_rejections = 0;
+        let i = 0;
+        let len = vector::length(owners);
+        while (i < len) {
+            let owner = vector::borrow(owners, i);
+            if (simple_map::contains_key(&transaction.votes, owner)) {
+                if (*simple_map::borrow(&transaction.votes, owner)) {
+                    num_approvals = num_approvals + 1;
+                } else {
+                    num_rejections = num_rejections + 1;
+                };
+            };
+            i = i + 1;
+        };
+        (num_approvals, num_rejections)
+    }
+
+    fun validate_owners(owners: &vector<address>, multisig_account: address) {
+        let distinct_owners = vector::empty<address>();
+        let i = 0;
+        let len = vector::length(owners);
+        while (i < len) {
+            let owner = *vector::borrow(owners, i);
+            assert!(owner != multisig_account, error::invalid_argument(EOWNER_CANNOT_BE_MULTISIG_ACCOUNT_ITSELF));
+            assert!(!vector::contains(&distinct_owners, &owner), error::invalid_argument(EDUPLICATE_OWNER));
+            vector::push_back(&mut distinct_owners, owner);
+            i = i + 1;
+        }
+    }
+
+    fun assert_is_owner(owner: &signer, multisig_account_resource: &MultisigAccount) {
+        assert!(
+            vector::contains(&multisig_account_resource.owners, &signer::address_of(owner)),
+            error::permission_denied(ENOT_OWNER),
+        );
+    }
+
+    fun assert_transaction_exists(multisig_account_resource: &MultisigAccount, sequence_number: u64) {
+        assert!(
+            table::contains(&multisig_account_resource.transactions, sequence_number),
+            error::not_found(ETRANSACTION_NOT_FOUND),
+        );
+    }
+
+    fun assert_multisig_account_exists(multisig_account: address) {
+        assert!(exists<MultisigAccount>(multisig_account), error::invalid_state(EACCOUNT_NOT_MULTISIG));
+    }
+
+    #[test_only]
+    use aptos_framework::aptos_account::create_account;
+
+    #[test_only]
+    fun setup(aptos_framework: &signer, owners: vector<address>) {
+        timestamp::set_time_has_started_for_testing(aptos_framework);
+        let (burn_cap, mint_cap) = aptos_framework::aptos_coin::initialize_for_test(aptos_framework);
+        coin::destroy_burn_cap(burn_cap);
+        coin::destroy_mint_cap(mint_cap);
+        features::change_feature_flags(aptos_framework, vector[features::get_multisig_accounts_feature()], vector[]);
+        let i = 0;
+        while (i < vector::length(&owners)) {
+            create_account(*vector::borrow(&owners, i));
+            i = i + 1;
+        };
+    }
+
+    #[test(aptos_framework = @aptos_framework, owner_1 = @0x123, owner_2 = @0x124, owner_3 = @0x125)]
+    public entry fun test_create_and_vote(
+        aptos_framework: signer,
+        owner_1: signer,
+        owner_2: signer,
+        owner_3: signer,
+    ) acquires MultisigAccount {
+        let owner_1_addr = signer::address_of(&owner_1);
+        let owner_2_addr = signer::address_of(&owner_2);
+        let owner_3_addr = signer::address_of(&owner_3);
+        setup(&aptos_framework, vector[owner_1_addr, owner_2_addr, owner_3_addr]);
+
+        let multisig_account = get_next_multisig_account_address(owner_1_addr);
+        create(&owner_1, vector[owner_2_addr, owner_3_addr], 2);
+        assert!(num_signatures_required(multisig_account) == 2, 0);
+        assert!(vector::length(&owners(multisig_account)) == 3, 1);
+        assert!(account::get_authentication_key(multisig_account) == ZERO_AUTH_KEY, 2);
+
+        create_transaction(&owner_1, multisig_account, b"payload");
+        assert!(!can_be_executed(multisig_account, 1), 3);
+        approve_transaction(&owner_2, multisig_account, 1);
+        assert!(can_be_executed(multisig_account, 1), 4);
+        assert!(validate_multisig_transaction(&owner_3, multisig_account, vector[]) == b"payload", 5);
+
+        // Changing a vote is taken into account.
+        reject_transaction(&owner_2, multisig_account, 1);
+        assert!(!can_be_executed(multisig_account, 1), 6);
+        reject_transaction(&owner_3, multisig_account, 1);
+        assert!(can_be_rejected(multisig_account, 1), 7);
+        execute_rejected_transaction(&owner_1, multisig_account);
+        assert!(last_resolved_sequence_number(multisig_account) == 1, 8);
+        assert!(next_sequence_number(multisig_account) == 2, 9);
+    }
+
+    #[test(aptos_framework = @aptos_framework, owner_1 = @0x123, owner_2 = @0x124)]
+    #[expected_failure(abort_code = 0x30007)]
+    public entry fun test_execute_without_enough_approvals_should_fail(
+        aptos_framework: signer,
+        owner_1: signer,
+        owner_2: signer,
+    ) acquires MultisigAccount {
+        let owner_1_addr = signer::address_of(&owner_1);
+        let owner_2_addr = signer::address_of(&owner_2);
+        setup(&aptos_framework, vector[owner_1_addr, owner_2_addr]);
+
+        let multisig_account = get_next_multisig_account_address(owner_1_addr);
+        create(&owner_1, vector[owner_2_addr], 2);
+        create_transaction(&owner_1, multisig_account, b"payload");
+        validate_multisig_transaction(&owner_1, multisig_account, vector[]);
+    }
+
+    #[test(aptos_framework = @aptos_framework, owner_1 = @0x123, non_owner = @0x124)]
+    #[expected_failure(abort_code = 0x50003)]
+    public entry fun test_create_transaction_by_non_owner_should_fail(
+        aptos_framework: signer,
+        owner_1: signer,
+        non_owner: signer,
+    ) acquires MultisigAccount {
+        let owner_1_addr = signer::address_of(&owner_1);
+        setup(&aptos_framework, vector[owner_1_addr, signer::address_of(&non_owner)]);
+
+        let multisig_account = get_next_multisig_account_address(owner_1_addr);
+        create(&owner_1, vector[], 1);
+        create_transaction(&non_owner, multisig_account, b"payload");
+    }
+
+    #[test(aptos_framework = @aptos_framework, owner_1 = @0x123, owner_2 = @0x124)]
+    #[expected_failure(abort_code = 0x10001)]
+    public entry fun test_create_with_duplicate_owners_should_fail(
+        aptos_framework: signer,
+        owner_1: signer,
+        owner_2: signer,
+    ) {
+        let owner_1_addr = signer::address_of(&owner_1);
+        let owner_2_addr = signer::address_of(&owner_2);
+        setup(&aptos_framework, vector[owner_1_addr, owner_2_addr]);
+        create(&owner_1, vector[owner_2_addr, owner_2_addr], 2);
+    }
+}
@@ -10,6 +10,7 @@ module aptos_framework::reconfiguration {
     use aptos_framework::state_storage;
     use aptos_framework::system_addresses;
     use aptos_framework::timestamp;
+    use aptos_framework::transaction_fee;
     use aptos_framework::chain_status;
 
     friend aptos_framework::aptos_governance;
@@ -114,7 +115,11 @@ module aptos_framework::reconfiguration {
             return
         };
 
-        // Call stake to compute the new validator set and distribute rewards.
+        // Process the collected transaction fees before the new validator set is computed, so that
+        // the fees of the last block in this epoch are distributed to its proposer as well.
+        transaction_fee::process_collected_fees();
+
+        // Call stake to compute the new validator set and distribute rewards and transaction fees.
         stake::on_new_epoch();
         state_storage::on_reconfig();
 
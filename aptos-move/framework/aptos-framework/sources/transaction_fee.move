@@ -1,15 +1,188 @@
+/// This module provides an interface to burn or collect and redistribute transaction fees.
 module aptos_framework::transaction_fee {
-    use aptos_framework::coin::{Self, BurnCapability};
+    use aptos_framework::coin::{Self, AggregatableCoin, BurnCapability, Coin};
     use aptos_framework::aptos_coin::AptosCoin;
+    use aptos_framework::stake;
     use aptos_framework::system_addresses;
+    use std::error;
+    use std::option::{Self, Option};
 
+    friend aptos_framework::block;
     friend aptos_framework::genesis;
+    friend aptos_framework::reconfiguration;
     friend aptos_framework::transaction_validation;
 
+    /// Gas fees are already being collected and the struct holding
+    /// information about collected amounts is already published.
+    const EALREADY_COLLECTING_FEES: u64 = 1;
+
+    /// The burn percentage is out of range [0, 100].
+    const EINVALID_BURN_PERCENTAGE: u64 = 3;
+
+    /// Storage fee deposits are already being held and the struct holding them
+    /// is already published.
+    const EALREADY_HOLDING_STORAGE_FEES: u64 = 4;
+
+    /// Stores burn capability to burn the gas fees.
     struct AptosCoinCapabilities has key {
         burn_cap: BurnCapability<AptosCoin>,
     }
 
+    /// Stores information about the block proposer and the amount of fees
+    /// collected when executing the block.
+    struct CollectedFeesPerBlock has key {
+        amount: AggregatableCoin<AptosCoin>,
+        proposer: Option<address>,
+        burn_percentage: u8,
+    }
+
+    /// Holds the storage fees paid for allocating state, so that they can be refunded
+    /// when the state is freed. Refunds are paid at the current storage fee rates, so
+    /// they are capped at the amount deposited.
+    struct StorageFeeDeposits has key {
+        amount: AggregatableCoin<AptosCoin>,
+    }
+
+    /// Initializes the resource storing information about gas fees collection and
+    /// distribution. Should be called by on-chain governance.
+    public fun initialize_fee_collection_and_distribution(aptos_framework: &signer, burn_percentage: u8) {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        assert!(
+            !exists<CollectedFeesPerBlock>(@aptos_framework),
+            error::already_exists(EALREADY_COLLECTING_FEES)
+        );
+        assert!(burn_percentage <= 100, error::out_of_range(EINVALID_BURN_PERCENTAGE));
+
+        // Make sure staking module is aware of transaction fees collection.
+        stake::initialize_validator_fees(aptos_framework);
+
+        // Initially, no fees are collected and the block proposer is not set.
+        let collected_fees = CollectedFeesPerBlock {
+            amount: coin::initialize_aggregatable_coin(aptos_framework),
+            proposer: option::none(),
+            burn_percentage,
+        };
+        move_to(aptos_framework, collected_fees);
+    }
+
+    /// Returns true if transaction fees are collected and distributed to block proposers
+    /// rather than burnt in the epilogue.
+    public fun is_fees_collection_enabled(): bool {
+        exists<CollectedFeesPerBlock>(@aptos_framework)
+    }
+
+    /// Initializes the resource holding storage fee deposits. Called during genesis,
+    /// or by on-chain governance for existing chains.
+    public fun initialize_storage_fee_deposits(aptos_framework: &signer) {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        assert!(
+            !exists<StorageFeeDeposits>(@aptos_framework),
+            error::already_exists(EALREADY_HOLDING_STORAGE_FEES)
+        );
+        move_to(aptos_framework, StorageFeeDeposits {
+            amount: coin::initialize_aggregatable_coin(aptos_framework),
+        });
+    }
+
+    /// Returns true if storage fees are held as deposits and refunded when state is
+    /// freed, rather than treated like the rest of the transaction fee.
+    public fun is_storage_fee_deposits_enabled(): bool {
+        exists<StorageFeeDeposits>(@aptos_framework)
+    }
+
+    /// Sets the burn percentage for collected fees to a new value. Should be called by on-chain governance.
+    public fun upgrade_burn_percentage(
+        aptos_framework: &signer,
+        new_burn_percentage: u8
+    ) acquires AptosCoinCapabilities, CollectedFeesPerBlock {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        assert!(new_burn_percentage <= 100, error::out_of_range(EINVALID_BURN_PERCENTAGE));
+
+        // Prior to upgrading the burn percentage, make sure to process collected
+        // fees. Otherwise we would use the new (incorrect) burn_percentage when
+        // processing fees later!
+        process_collected_fees();
+
+        if (is_fees_collection_enabled()) {
+            // Upgrade has no effect unless fees are being collected.
+            let burn_percentage = &mut borrow_global_mut<CollectedFeesPerBlock>(@aptos_framework).burn_percentage;
+            *burn_percentage = new_burn_percentage
+        }
+    }
+
+    /// Registers the proposer of the block for gas fees collection. This function
+    /// can only be called at the beginning of the block.
+    public(friend) fun register_proposer_for_fee_collection(proposer_addr: address) acquires CollectedFeesPerBlock {
+        if (is_fees_collection_enabled()) {
+            let collected_fees = borrow_global_mut<CollectedFeesPerBlock>(@aptos_framework);
+            let _ = option::swap_or_fill(&mut collected_fees.proposer, proposer_addr);
+        }
+    }
+
+    /// Burns a specified fraction of the coin.
+    fun burn_coin_fraction(coin: &mut Coin<AptosCoin>, burn_percentage: u8) acquires AptosCoinCapabilities {
+        assert!(burn_percentage <= 100, error::out_of_range(EINVALID_BURN_PERCENTAGE));
+
+        let collected_amount = coin::value(coin);
+        // Multiplication is done in u128 so that it cannot overflow.
+        let amount_to_burn = (((burn_percentage as u128) * (collected_amount as u128) / 100) as u64);
+        if (amount_to_burn > 0) {
+            let coin_to_burn = coin::extract(coin, amount_to_burn);
+            coin::burn(
+                coin_to_burn,
+                &borrow_global<AptosCoinCapabilities>(@aptos_framework).burn_cap,
+            );
+        }
+    }
+
+    /// Calculates the fee which should be distributed to the block proposer at the
+    /// end of an epoch, and records it in the system. This function can only be called
+    /// at the beginning of the block or during reconfiguration.
+    public(friend) fun process_collected_fees() acquires AptosCoinCapabilities, CollectedFeesPerBlock {
+        if (!is_fees_collection_enabled()) {
+            return
+        };
+        let collected_fees = borrow_global_mut<CollectedFeesPerBlock>(@aptos_framework);
+
+        // If there are no collected fees, only unset the proposer. See the rationale for
+        // setting proposer to option::none() below.
+        if (coin::is_aggregatable_coin_zero(&collected_fees.amount)) {
+            if (option::is_some(&collected_fees.proposer)) {
+                let _ = option::extract(&mut collected_fees.proposer);
+            };
+            return
+        };
+
+        // Otherwise get the collected fee, and check if it can distributed later.
+        let coin = coin::drain_aggregatable_coin(&mut collected_fees.amount);
+        if (option::is_some(&collected_fees.proposer)) {
+            // Extract the address of proposer here and reset it to option::none(). This
+            // is particularly useful to avoid any undesired side-effects where coins are
+            // collected but never distributed or distributed to the wrong account.
+            // With this design, processing collected fees enforces that all fees will be burnt
+            // unless the proposer is specified in the block prologue. When we have a governance
+            // proposal that triggers reconfiguration, we distribute pending fees and burn the
+            // fee for the proposal. Otherwise, that fee would be leaked to the next block.
+            let proposer = option::extract(&mut collected_fees.proposer);
+
+            // Since the block can be produced by the VM itself, we have to make sure we catch
+            // this case.
+            if (proposer == @vm_reserved) {
+                burn_coin_fraction(&mut coin, 100);
+                coin::destroy_zero(coin);
+                return
+            };
+
+            burn_coin_fraction(&mut coin, collected_fees.burn_percentage);
+            stake::add_transaction_fee(proposer, coin);
+            return
+        };
+
+        // If checks did not pass, simply burn all collected coins and return none.
+        burn_coin_fraction(&mut coin, 100);
+        coin::destroy_zero(coin)
+    }
+
     /// Burn transaction fees in epilogue.
     public(friend) fun burn_fee(account: address, fee: u64) acquires AptosCoinCapabilities {
         coin::burn_from<AptosCoin>(
@@ -19,9 +192,225 @@ module aptos_framework::transaction_fee {
         );
     }
 
+    /// Collect transaction fees in epilogue.
+    public(friend) fun collect_fee(account: address, fee: u64) acquires CollectedFeesPerBlock {
+        let collected_fees = borrow_global_mut<CollectedFeesPerBlock>(@aptos_framework);
+
+        // Here, we are always optimistic and always collect fees. If the proposer is not set,
+        // or we cannot redistribute fees later for some reason (e.g. account cannot receive AptoCoin)
+        // we burn them all at once. This way we avoid having a check for every transaction epilogue.
+        // Note that the aggregatable coin is only updated via a delta, so collecting fees does not
+        // introduce conflicts between transactions of the same block.
+        let collected_amount = &mut collected_fees.amount;
+        coin::collect_into_aggregatable_coin<AptosCoin>(account, fee, collected_amount);
+    }
+
+    /// Deposits the storage fee paid by the account in epilogue. Like collected fees,
+    /// deposits only update the aggregatable coin via a delta.
+    public(friend) fun deposit_storage_fee(account: address, fee: u64) acquires StorageFeeDeposits {
+        let deposits = borrow_global_mut<StorageFeeDeposits>(@aptos_framework);
+        coin::collect_into_aggregatable_coin<AptosCoin>(account, fee, &mut deposits.amount);
+    }
+
+    /// Refunds the storage fee for the state freed by a transaction to the account in
+    /// epilogue, out of the deposits.
+    public(friend) fun refund_storage_fee(account: address, refund: u64) acquires StorageFeeDeposits {
+        let deposits = borrow_global_mut<StorageFeeDeposits>(@aptos_framework);
+        let coin = coin::drain_aggregatable_coin(&mut deposits.amount);
+        let refund = if (refund < coin::value(&coin)) { refund } else { coin::value(&coin) };
+        coin::deposit(account, coin::extract(&mut coin, refund));
+        coin::merge_aggregatable_coin(&mut deposits.amount, coin);
+    }
+
     /// Only called during genesis.
     public(friend) fun store_aptos_coin_burn_cap(aptos_framework: &signer, burn_cap: BurnCapability<AptosCoin>) {
         system_addresses::assert_aptos_framework(aptos_framework);
         move_to(aptos_framework, AptosCoinCapabilities { burn_cap })
     }
+
+    #[test_only]
+    use aptos_framework::aggregator_factory;
+
+    #[test(aptos_framework = @aptos_framework)]
+    fun test_initialize_fee_collection_and_distribution(aptos_framework: signer) acquires CollectedFeesPerBlock {
+        aggregator_factory::initialize_aggregator_factory_for_test(&aptos_framework);
+        initialize_fee_collection_and_distribution(&aptos_framework, 25);
+
+        // Check struct has been published.
+        assert!(exists<CollectedFeesPerBlock>(@aptos_framework), 0);
+
+        // Check that initial balance is 0 and there is no proposer set.
+        let collected_fees = borrow_global<CollectedFeesPerBlock>(@aptos_framework);
+        assert!(coin::is_aggregatable_coin_zero(&collected_fees.amount), 0);
+        assert!(option::is_none(&collected_fees.proposer), 0);
+        assert!(collected_fees.burn_percentage == 25, 0);
+    }
+
+    #[test(aptos_framework = @aptos_framework)]
+    #[expected_failure(abort_code = 0x20003)]
+    fun test_initialize_with_invalid_burn_percentage(aptos_framework: signer) {
+        aggregator_factory::initialize_aggregator_factory_for_test(&aptos_framework);
+        initialize_fee_collection_and_distribution(&aptos_framework, 101);
+    }
+
+    #[test(aptos_framework = @aptos_framework)]
+    fun test_burn_fraction_calculation(aptos_framework: signer) acquires AptosCoinCapabilities {
+        use aptos_framework::aptos_coin;
+        let (burn_cap, mint_cap) = aptos_coin::initialize_for_test(&aptos_framework);
+        store_aptos_coin_burn_cap(&aptos_framework, burn_cap);
+
+        let c1 = coin::mint<AptosCoin>(100, &mint_cap);
+        assert!(*option::borrow(&coin::supply<AptosCoin>()) == 100, 0);
+
+        // Burning 25%.
+        burn_coin_fraction(&mut c1, 25);
+        assert!(coin::value(&c1) == 75, 0);
+        assert!(*option::borrow(&coin::supply<AptosCoin>()) == 75, 0);
+
+        // Burning 0%.
+        burn_coin_fraction(&mut c1, 0);
+        assert!(coin::value(&c1) == 75, 0);
+        assert!(*option::borrow(&coin::supply<AptosCoin>()) == 75, 0);
+
+        // Burning remaining 100%.
+        burn_coin_fraction(&mut c1, 100);
+        assert!(coin::value(&c1) == 0, 0);
+        assert!(*option::borrow(&coin::supply<AptosCoin>()) == 0, 0);
+
+        coin::destroy_zero(c1);
+        coin::destroy_mint_cap(mint_cap);
+    }
+
+    #[test(aptos_framework = @aptos_framework, alice = @0xa11ce, bob = @0xb0b)]
+    fun test_fees_distribution(
+        aptos_framework: signer,
+        alice: signer,
+        bob: signer,
+    ) acquires AptosCoinCapabilities, CollectedFeesPerBlock {
+        use std::signer;
+        use aptos_framework::aptos_account;
+        use aptos_framework::aptos_coin;
+
+        // Initialization.
+        let (burn_cap, mint_cap) = aptos_coin::initialize_for_test(&aptos_framework);
+        store_aptos_coin_burn_cap(&aptos_framework, burn_cap);
+        initialize_fee_collection_and_distribution(&aptos_framework, 10);
+
+        // Create dummy accounts.
+        let alice_addr = signer::address_of(&alice);
+        let bob_addr = signer::address_of(&bob);
+        aptos_account::create_account(alice_addr);
+        aptos_account::create_account(bob_addr);
+        coin::deposit(alice_addr, coin::mint(10000, &mint_cap));
+        coin::deposit(bob_addr, coin::mint(10000, &mint_cap));
+        assert!(*option::borrow(&coin::supply<AptosCoin>()) == 20000, 0);
+
+        // Block 1 starts.
+        process_collected_fees();
+        register_proposer_for_fee_collection(alice_addr);
+
+        // Check that there was no fees distribution in the first block.
+        let collected_fees = borrow_global<CollectedFeesPerBlock>(@aptos_framework);
+        assert!(coin::is_aggregatable_coin_zero(&collected_fees.amount), 0);
+        assert!(*option::borrow(&collected_fees.proposer) == alice_addr, 0);
+        assert!(*option::borrow(&coin::supply<AptosCoin>()) == 20000, 0);
+
+        // Simulate transaction fee collection - here we simply collect some fees from Bob.
+        collect_fee(bob_addr, 100);
+        collect_fee(bob_addr, 500);
+        collect_fee(bob_addr, 400);
+
+        // Now Bob must have 1000 less in his account. Alice remains untouched.
+        assert!(coin::balance<AptosCoin>(alice_addr) == 10000, 0);
+        assert!(coin::balance<AptosCoin>(bob_addr) == 9000, 0);
+
+        // Block 2 starts.
+        process_collected_fees();
+        register_proposer_for_fee_collection(bob_addr);
+
+        // Collected fees from Bob must have been assigned to Alice.
+        assert!(stake::get_validator_fee(alice_addr) == 900, 0);
+        assert!(coin::balance<AptosCoin>(alice_addr) == 10000, 0);
+        assert!(coin::balance<AptosCoin>(bob_addr) == 9000, 0);
+
+        // Also, aggregator coin is drained and total supply is slightly changed (10% of 1000 is burnt).
+        let collected_fees = borrow_global<CollectedFeesPerBlock>(@aptos_framework);
+        assert!(coin::is_aggregatable_coin_zero(&collected_fees.amount), 0);
+        assert!(*option::borrow(&collected_fees.proposer) == bob_addr, 0);
+        assert!(*option::borrow(&coin::supply<AptosCoin>()) == 19900, 0);
+
+        // Simulate transaction fee collection one more time.
+        collect_fee(bob_addr, 5000);
+        collect_fee(bob_addr, 4000);
+
+        assert!(coin::balance<AptosCoin>(alice_addr) == 10000, 0);
+        assert!(coin::balance<AptosCoin>(bob_addr) == 0, 0);
+
+        // Block 3 starts, produced by the VM.
+        process_collected_fees();
+        register_proposer_for_fee_collection(@vm_reserved);
+
+        // Collected fees should have been assigned to Bob because he was the previous proposer.
+        assert!(stake::get_validator_fee(alice_addr) == 900, 0);
+        assert!(stake::get_validator_fee(bob_addr) == 8100, 0);
+        assert!(*option::borrow(&coin::supply<AptosCoin>()) == 19000, 0);
+
+        // Fees collected in a block produced by the VM are burnt entirely.
+        collect_fee(alice_addr, 1000);
+        process_collected_fees();
+        assert!(coin::balance<AptosCoin>(alice_addr) == 9000, 0);
+        assert!(*option::borrow(&coin::supply<AptosCoin>()) == 18000, 0);
+
+        // The proposer is unset, so fees collected until the next block prologue are burnt too.
+        let collected_fees = borrow_global<CollectedFeesPerBlock>(@aptos_framework);
+        assert!(option::is_none(&collected_fees.proposer), 0);
+
+        coin::destroy_mint_cap(mint_cap);
+    }
+
+    #[test(aptos_framework = @aptos_framework, alice = @0xa11ce, bob = @0xb0b)]
+    fun test_storage_fee_refunds(
+        aptos_framework: signer,
+        alice: signer,
+        bob: signer,
+    ) acquires StorageFeeDeposits {
+        use std::signer;
+        use aptos_framework::aptos_account;
+        use aptos_framework::aptos_coin;
+
+        // Initialization.
+        let (burn_cap, mint_cap) = aptos_coin::initialize_for_test(&aptos_framework);
+        store_aptos_coin_burn_cap(&aptos_framework, burn_cap);
+        initialize_storage_fee_deposits(&aptos_framework);
+        assert!(is_storage_fee_deposits_enabled(), 0);
+
+        // Create dummy accounts.
+        let alice_addr = signer::address_of(&alice);
+        let bob_addr = signer::address_of(&bob);
+        aptos_account::create_account(alice_addr);
+        aptos_account::create_account(bob_addr);
+        coin::deposit(alice_addr, coin::mint(10000, &mint_cap));
+        coin::deposit(bob_addr, coin::mint(10000, &mint_cap));
+
+        // Deposits are held rather than burnt.
+        deposit_storage_fee(alice_addr, 300);
+        deposit_storage_fee(bob_addr, 200);
+        assert!(coin::balance<AptosCoin>(alice_addr) == 9700, 0);
+        assert!(coin::balance<AptosCoin>(bob_addr) == 9800, 0);
+        assert!(*option::borrow(&coin::supply<AptosCoin>()) == 20000, 0);
+
+        // Anyone freeing state is refunded, even if someone else paid for it.
+        refund_storage_fee(bob_addr, 400);
+        assert!(coin::balance<AptosCoin>(bob_addr) == 10200, 0);
+
+        // Refunds can't exceed what is left in the deposits.
+        refund_storage_fee(alice_addr, 400);
+        assert!(coin::balance<AptosCoin>(alice_addr) == 9800, 0);
+        assert!(coin::is_aggregatable_coin_zero(&borrow_global<StorageFeeDeposits>(@aptos_framework).amount), 0);
+        refund_storage_fee(alice_addr, 100);
+        assert!(coin::balance<AptosCoin>(alice_addr) == 9800, 0);
+        assert!(*option::borrow(&coin::supply<AptosCoin>()) == 20000, 0);
+
+        coin::destroy_mint_cap(mint_cap);
+    }
 }
@@ -28,6 +28,9 @@ module aptos_framework::transaction_validation {
     /// Transaction exceeded its allocated max gas
     const EOUT_OF_GAS: u64 = 6;
 
+    /// The storage fee exceeds the transaction fee
+    const EINVALID_STORAGE_FEE: u64 = 7;
+
     /// Prologue errors. These are separated out from the other errors in this
     /// module since they are mapped separately to major VM statuses, and are
     /// important to the semantics of the system.
@@ -63,6 +66,7 @@ module aptos_framework::transaction_validation {
 
     fun prologue_common(
         sender: signer,
+        gas_payer: address,
         txn_sequence_number: u64,
         txn_authentication_key: vector<u8>,
         txn_gas_price: u64,
@@ -103,10 +107,10 @@ module aptos_framework::transaction_validation {
 
         let max_transaction_fee = txn_gas_price * txn_max_gas_units;
         assert!(
-            coin::is_account_registered<AptosCoin>(transaction_sender),
+            coin::is_account_registered<AptosCoin>(gas_payer),
             error::invalid_argument(PROLOGUE_ECANT_PAY_GAS_DEPOSIT),
         );
-        let balance = coin::balance<AptosCoin>(transaction_sender);
+        let balance = coin::balance<AptosCoin>(gas_payer);
         assert!(balance >= max_transaction_fee, error::invalid_argument(PROLOGUE_ECANT_PAY_GAS_DEPOSIT));
     }
 
@@ -119,7 +123,8 @@ module aptos_framework::transaction_validation {
         txn_expiration_time: u64,
         chain_id: u8,
     ) {
-        prologue_common(sender, txn_sequence_number, txn_public_key, txn_gas_price, txn_max_gas_units, txn_expiration_time, chain_id)
+        let gas_payer = signer::address_of(&sender);
+        prologue_common(sender, gas_payer, txn_sequence_number, txn_public_key, txn_gas_price, txn_max_gas_units, txn_expiration_time, chain_id)
     }
 
     fun script_prologue(
@@ -132,7 +137,8 @@ module aptos_framework::transaction_validation {
         chain_id: u8,
         _script_hash: vector<u8>,
     ) {
-        prologue_common(sender, txn_sequence_number, txn_public_key, txn_gas_price, txn_max_gas_units, txn_expiration_time, chain_id)
+        let gas_payer = signer::address_of(&sender);
+        prologue_common(sender, gas_payer, txn_sequence_number, txn_public_key, txn_gas_price, txn_max_gas_units, txn_expiration_time, chain_id)
     }
 
     fun multi_agent_script_prologue(
@@ -146,8 +152,15 @@ module aptos_framework::transaction_validation {
         txn_expiration_time: u64,
         chain_id: u8,
     ) {
-        prologue_common(sender, txn_sequence_number, txn_sender_public_key, txn_gas_price, txn_max_gas_units, txn_expiration_time, chain_id);
+        let gas_payer = signer::address_of(&sender);
+        prologue_common(sender, gas_payer, txn_sequence_number, txn_sender_public_key, txn_gas_price, txn_max_gas_units, txn_expiration_time, chain_id);
+        multi_agent_common_prologue(secondary_signer_addresses, secondary_signer_public_key_hashes);
+    }
 
+    fun multi_agent_common_prologue(
+        secondary_signer_addresses: vector<address>,
+        secondary_signer_public_key_hashes: vector<vector<u8>>,
+    ) {
         let num_secondary_signers = vector::length(&secondary_signer_addresses);
 
         assert!(
@@ -169,14 +182,79 @@ module aptos_framework::transaction_validation {
         }
     }
 
+    /// Prologue for sponsored transactions, where the fee payer pays for gas on behalf of the
+    /// sender. The fee payer's sequence number is neither checked nor incremented.
+    fun fee_payer_script_prologue(
+        sender: signer,
+        txn_sequence_number: u64,
+        txn_sender_public_key: vector<u8>,
+        secondary_signer_addresses: vector<address>,
+        secondary_signer_public_key_hashes: vector<vector<u8>>,
+        fee_payer_address: address,
+        fee_payer_public_key_hash: vector<u8>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        txn_expiration_time: u64,
+        chain_id: u8,
+    ) {
+        assert!(account::exists_at(fee_payer_address), error::invalid_argument(PROLOGUE_EACCOUNT_DOES_NOT_EXIST));
+        assert!(
+            fee_payer_public_key_hash == account::get_authentication_key(fee_payer_address),
+            error::invalid_argument(PROLOGUE_EINVALID_ACCOUNT_AUTH_KEY),
+        );
+        prologue_common(sender, fee_payer_address, txn_sequence_number, txn_sender_public_key, txn_gas_price, txn_max_gas_units, txn_expiration_time, chain_id);
+        multi_agent_common_prologue(secondary_signer_addresses, secondary_signer_public_key_hashes);
+    }
+
     /// Epilogue function is run after a transaction is successfully executed.
     /// Called by the Adapter
     fun epilogue(
         account: signer,
-        _txn_sequence_number: u64,
+        txn_sequence_number: u64,
         txn_gas_price: u64,
         txn_max_gas_units: u64,
         gas_units_remaining: u64
+    ) {
+        let addr = signer::address_of(&account);
+        epilogue_gas_payer(account, addr, txn_sequence_number, txn_gas_price, txn_max_gas_units, gas_units_remaining);
+    }
+
+    /// Epilogue function with explicit gas payer specified, is run after a transaction is successfully executed.
+    /// Called by the Adapter
+    fun epilogue_gas_payer(
+        account: signer,
+        gas_payer: address,
+        txn_sequence_number: u64,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        gas_units_remaining: u64
+    ) {
+        epilogue_with_storage_refund(
+            account,
+            gas_payer,
+            txn_sequence_number,
+            txn_gas_price,
+            txn_max_gas_units,
+            gas_units_remaining,
+            0,
+            0,
+        );
+    }
+
+    /// Epilogue function which also handles storage fees, is run after a transaction that
+    /// allocates or frees state is successfully executed. The storage fee paid as part of the
+    /// gas used is held as a deposit, and the refund for the state freed by the transaction is
+    /// paid back to the gas payer out of the deposits.
+    /// Called by the Adapter
+    fun epilogue_with_storage_refund(
+        account: signer,
+        gas_payer: address,
+        _txn_sequence_number: u64,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        gas_units_remaining: u64,
+        storage_fee: u64,
+        storage_fee_refund: u64,
     ) {
         assert!(txn_max_gas_units >= gas_units_remaining, error::invalid_argument(EOUT_OF_GAS));
         let gas_used = txn_max_gas_units - gas_units_remaining;
@@ -186,16 +264,34 @@ module aptos_framework::transaction_validation {
             error::out_of_range(EOUT_OF_GAS)
         );
         let transaction_fee_amount = txn_gas_price * gas_used;
-        let addr = signer::address_of(&account);
+        assert!(storage_fee <= transaction_fee_amount, error::invalid_argument(EINVALID_STORAGE_FEE));
         // it's important to maintain the error code consistent with vm
         // to do failed transaction cleanup.
         assert!(
-            coin::balance<AptosCoin>(addr) >= transaction_fee_amount,
+            coin::balance<AptosCoin>(gas_payer) >= transaction_fee_amount,
             error::out_of_range(PROLOGUE_ECANT_PAY_GAS_DEPOSIT),
         );
-        transaction_fee::burn_fee(addr, transaction_fee_amount);
+        // Until storage fee deposits are enabled, the storage fee is treated like the rest of the
+        // transaction fee and nothing is refunded.
+        let storage_fee_deposits_enabled = transaction_fee::is_storage_fee_deposits_enabled();
+        if (storage_fee_deposits_enabled) {
+            transaction_fee::deposit_storage_fee(gas_payer, storage_fee);
+            transaction_fee_amount = transaction_fee_amount - storage_fee;
+        };
+        if (transaction_fee::is_fees_collection_enabled()) {
+            // Fees are collected into an aggregatable coin and distributed to the block proposer
+            // when the next block starts, so collecting them does not serialize execution.
+            transaction_fee::collect_fee(gas_payer, transaction_fee_amount);
+        } else {
+            // Otherwise, just burn the fee.
+            transaction_fee::burn_fee(gas_payer, transaction_fee_amount);
+        };
+        if (storage_fee_deposits_enabled && storage_fee_refund > 0) {
+            transaction_fee::refund_storage_fee(gas_payer, storage_fee_refund);
+        };
 
         // Increment sequence number
+        let addr = signer::address_of(&account);
         account::increment_sequence_number(addr);
     }
 }
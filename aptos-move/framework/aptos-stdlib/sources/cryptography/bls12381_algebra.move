@@ -0,0 +1,509 @@
+/// This module contains functions for arithmetic over the groups of the BLS12-381 pairing-friendly elliptic curve,
+/// which can be used to verify zkSNARK proofs (e.g., Groth16) or to build threshold cryptosystems.
+///
+/// The module provides four structs:
+///
+///  - `Scalar`, an element of the scalar field $\mathbb{F}_r$, where $r$ is the (prime) order of all three groups.
+///
+///  - `G1` and `G2`, points in the prime-order subgroups $\mathbb{G}_1$ and $\mathbb{G}_2$ of the curve and of its
+/// twist, written additively.
+///
+///  - `Gt`, an element of the target group $\mathbb{G}_T$ of the pairing $e: \mathbb{G}_1 \times \mathbb{G}_2
+/// \rightarrow \mathbb{G}_T$, written multiplicatively.
+///
+/// Points deserialize from (and serialize to) the compressed or uncompressed encodings of the
+/// [ZCash BLS12-381 format](https://github.com/zkcrypto/pairing/blob/master/src/bls12_381/README.md#serialization),
+/// while scalars use a canonical 32-byte little-endian encoding.
+///
+/// One invariant maintained by this module is that all structs store valid elements of their group (or field), so
+/// that the only place where the (relatively expensive) validity checks happen is deserialization. Elements are
+/// stored in a unique encoding, so they can be compared with `==`.
+
+module aptos_std::bls12381_algebra {
+    use std::bcs;
+    use std::error;
+    use std::option::{Self, Option};
+    use std::vector;
+
+    //
+    // Constants
+    //
+
+    /// The size of a canonically-encoded scalar, in bytes.
+    const SCALAR_NUM_BYTES: u64 = 32;
+
+    /// The maximum size of a hash-to-curve domain separation tag, in bytes.
+    const MAX_DST_NUM_BYTES: u64 = 255;
+
+    //
+    // Reasons for error codes
+    //
+
+    /// The number of scalars does not match the number of points.
+    const E_DIFFERENT_NUM_POINTS_AND_SCALARS: u64 = 1;
+    /// The number of G1 elements does not match the number of G2 elements.
+    const E_DIFFERENT_NUM_G1_AND_G2: u64 = 2;
+    /// The domain separation tag of a hash-to-curve must be between 1 and 255 bytes.
+    const E_INVALID_DST: u64 = 3;
+
+    //
+    // Structs
+    //
+
+    /// An element of the scalar field, stored as a canonical little-endian encoding.
+    struct Scalar has copy, drop, store {
+        data: vector<u8>
+    }
+
+    /// A point in $\mathbb{G}_1$, stored as its uncompressed encoding.
+    struct G1 has copy, drop, store {
+        data: vector<u8>
+    }
+
+    /// A point in $\mathbb{G}_2$, stored as its uncompressed encoding.
+    struct G2 has copy, drop, store {
+        data: vector<u8>
+    }
+
+    /// An element of $\mathbb{G}_T$, stored as the encoding of its underlying $\mathbb{F}_{q^{12}}$ element.
+    struct Gt has copy, drop, store {
+        data: vector<u8>
+    }
+
+    //
+    // Scalars
+    //
+
+    /// Returns the scalar encoded by `bytes`, if they are a canonical 32-byte little-endian encoding.
+    public fun scalar_from_bytes(bytes: vector<u8>): Option<Scalar> {
+        if (scalar_is_canonical_internal(bytes)) {
+            option::some(Scalar { data: bytes })
+        } else {
+            option::none()
+        }
+    }
+
+    /// Returns the scalar `n`.
+    public fun scalar_from_u64(n: u64): Scalar {
+        let data = bcs::to_bytes(&n);
+        while (vector::length(&data) < SCALAR_NUM_BYTES) {
+            vector::push_back(&mut data, 0);
+        };
+        Scalar { data }
+    }
+
+    public fun scalar_zero(): Scalar {
+        scalar_from_u64(0)
+    }
+
+    public fun scalar_one(): Scalar {
+        scalar_from_u64(1)
+    }
+
+    public fun scalar_add(a: &Scalar, b: &Scalar): Scalar {
+        Scalar { data: scalar_add_internal(a.data, b.data) }
+    }
+
+    public fun scalar_sub(a: &Scalar, b: &Scalar): Scalar {
+        Scalar { data: scalar_sub_internal(a.data, b.data) }
+    }
+
+    public fun scalar_mul(a: &Scalar, b: &Scalar): Scalar {
+        Scalar { data: scalar_mul_internal(a.data, b.data) }
+    }
+
+    public fun scalar_neg(a: &Scalar): Scalar {
+        Scalar { data: scalar_neg_internal(a.data) }
+    }
+
+    /// Returns the multiplicative inverse of `a`, or `None` if `a` is zero.
+    public fun scalar_inv(a: &Scalar): Option<Scalar> {
+        let (data, success) = scalar_inv_internal(a.data);
+        if (success) {
+            option::some(Scalar { data })
+        } else {
+            option::none()
+        }
+    }
+
+    /// Returns the canonical 32-byte little-endian encoding of the scalar.
+    public fun scalar_to_bytes(s: &Scalar): vector<u8> {
+        s.data
+    }
+
+    //
+    // G1
+    //
+
+    public fun g1_identity(): G1 {
+        G1 { data: g1_identity_internal() }
+    }
+
+    public fun g1_generator(): G1 {
+        G1 { data: g1_generator_internal() }
+    }
+
+    /// Deserializes a point from its 48-byte compressed or 96-byte uncompressed encoding. Returns `None` if the
+    /// encoding is invalid, or if the point is not in the prime-order subgroup.
+    public fun g1_from_bytes(bytes: vector<u8>): Option<G1> {
+        let (data, success) = g1_deserialize_internal(bytes);
+        if (success) {
+            option::some(G1 { data })
+        } else {
+            option::none()
+        }
+    }
+
+    /// Returns the 48-byte compressed encoding of the point.
+    public fun g1_to_bytes(p: &G1): vector<u8> {
+        g1_compress_internal(p.data)
+    }
+
+    /// Returns the 96-byte uncompressed encoding of the point.
+    public fun g1_to_uncompressed_bytes(p: &G1): vector<u8> {
+        p.data
+    }
+
+    public fun g1_add(a: &G1, b: &G1): G1 {
+        G1 { data: g1_add_internal(a.data, b.data) }
+    }
+
+    public fun g1_sub(a: &G1, b: &G1): G1 {
+        G1 { data: g1_add_internal(a.data, g1_neg_internal(b.data)) }
+    }
+
+    public fun g1_neg(a: &G1): G1 {
+        G1 { data: g1_neg_internal(a.data) }
+    }
+
+    public fun g1_scalar_mul(p: &G1, s: &Scalar): G1 {
+        G1 { data: g1_scalar_mul_internal(p.data, s.data) }
+    }
+
+    /// Computes $\sum_i s_i P_i$, which is much faster than computing each of the scalar multiplications separately.
+    public fun g1_multi_scalar_mul(points: &vector<G1>, scalars: &vector<Scalar>): G1 {
+        assert!(
+            vector::length(points) == vector::length(scalars),
+            error::invalid_argument(E_DIFFERENT_NUM_POINTS_AND_SCALARS)
+        );
+        G1 { data: g1_multi_scalar_mul_internal(*points, *scalars) }
+    }
+
+    /// Hashes `msg` to a point, using the `BLS12381G1_XMD:SHA-256_SSWU_RO_` suite of
+    /// [RFC 9380](https://datatracker.ietf.org/doc/rfc9380/) with the domain separation tag `dst`.
+    public fun g1_hash_to(dst: vector<u8>, msg: vector<u8>): G1 {
+        assert_valid_dst(&dst);
+        G1 { data: g1_hash_to_internal(dst, msg) }
+    }
+
+    //
+    // G2
+    //
+
+    public fun g2_identity(): G2 {
+        G2 { data: g2_identity_internal() }
+    }
+
+    public fun g2_generator(): G2 {
+        G2 { data: g2_generator_internal() }
+    }
+
+    /// Deserializes a point from its 96-byte compressed or 192-byte uncompressed encoding. Returns `None` if the
+    /// encoding is invalid, or if the point is not in the prime-order subgroup.
+    public fun g2_from_bytes(bytes: vector<u8>): Option<G2> {
+        let (data, success) = g2_deserialize_internal(bytes);
+        if (success) {
+            option::some(G2 { data })
+        } else {
+            option::none()
+        }
+    }
+
+    /// Returns the 96-byte compressed encoding of the point.
+    public fun g2_to_bytes(p: &G2): vector<u8> {
+        g2_compress_internal(p.data)
+    }
+
+    /// Returns the 192-byte uncompressed encoding of the point.
+    public fun g2_to_uncompressed_bytes(p: &G2): vector<u8> {
+        p.data
+    }
+
+    public fun g2_add(a: &G2, b: &G2): G2 {
+        G2 { data: g2_add_internal(a.data, b.data) }
+    }
+
+    public fun g2_sub(a: &G2, b: &G2): G2 {
+        G2 { data: g2_add_internal(a.data, g2_neg_internal(b.data)) }
+    }
+
+    public fun g2_neg(a: &G2): G2 {
+        G2 { data: g2_neg_internal(a.data) }
+    }
+
+    public fun g2_scalar_mul(p: &G2, s: &Scalar): G2 {
+        G2 { data: g2_scalar_mul_internal(p.data, s.data) }
+    }
+
+    /// Computes $\sum_i s_i P_i$, which is much faster than computing each of the scalar multiplications separately.
+    public fun g2_multi_scalar_mul(points: &vector<G2>, scalars: &vector<Scalar>): G2 {
+        assert!(
+            vector::length(points) == vector::length(scalars),
+            error::invalid_argument(E_DIFFERENT_NUM_POINTS_AND_SCALARS)
+        );
+        G2 { data: g2_multi_scalar_mul_internal(*points, *scalars) }
+    }
+
+    /// Hashes `msg` to a point, using the `BLS12381G2_XMD:SHA-256_SSWU_RO_` suite of
+    /// [RFC 9380](https://datatracker.ietf.org/doc/rfc9380/) with the domain separation tag `dst`.
+    public fun g2_hash_to(dst: vector<u8>, msg: vector<u8>): G2 {
+        assert_valid_dst(&dst);
+        G2 { data: g2_hash_to_internal(dst, msg) }
+    }
+
+    //
+    // Gt
+    //
+
+    public fun gt_identity(): Gt {
+        Gt { data: gt_identity_internal() }
+    }
+
+    /// Returns $e(g_1, g_2)$, where $g_1$ and $g_2$ are the generators of $\mathbb{G}_1$ and $\mathbb{G}_2$.
+    public fun gt_generator(): Gt {
+        Gt { data: gt_generator_internal() }
+    }
+
+    /// Deserializes an element from its 576-byte encoding. Returns `None` if the encoding is invalid, or if the element
+    /// is not in the prime-order subgroup of $\mathbb{F}_{q^{12}}^*$.
+    public fun gt_from_bytes(bytes: vector<u8>): Option<Gt> {
+        let (data, success) = gt_deserialize_internal(bytes);
+        if (success) {
+            option::some(Gt { data })
+        } else {
+            option::none()
+        }
+    }
+
+    public fun gt_to_bytes(e: &Gt): vector<u8> {
+        e.data
+    }
+
+    public fun gt_mul(a: &Gt, b: &Gt): Gt {
+        Gt { data: gt_mul_internal(a.data, b.data) }
+    }
+
+    public fun gt_inv(a: &Gt): Gt {
+        Gt { data: gt_inv_internal(a.data) }
+    }
+
+    public fun gt_pow(a: &Gt, s: &Scalar): Gt {
+        Gt { data: gt_pow_internal(a.data, s.data) }
+    }
+
+    //
+    // Pairings
+    //
+
+    /// Computes the pairing $e(P, Q)$.
+    public fun pairing(p: &G1, q: &G2): Gt {
+        Gt { data: pairing_internal(p.data, q.data) }
+    }
+
+    /// Computes $\prod_i e(P_i, Q_i)$, which is much faster than computing each of the pairings separately.
+    public fun multi_pairing(ps: &vector<G1>, qs: &vector<G2>): Gt {
+        assert!(
+            vector::length(ps) == vector::length(qs),
+            error::invalid_argument(E_DIFFERENT_NUM_G1_AND_G2)
+        );
+        Gt { data: multi_pairing_internal(*ps, *qs) }
+    }
+
+    //
+    // Helpers
+    //
+
+    fun assert_valid_dst(dst: &vector<u8>) {
+        let len = vector::length(dst);
+        assert!(len > 0 && len <= MAX_DST_NUM_BYTES, error::invalid_argument(E_INVALID_DST));
+    }
+
+    //
+    // Native functions
+    //
+
+    native fun scalar_is_canonical_internal(bytes: vector<u8>): bool;
+    native fun scalar_add_internal(a: vector<u8>, b: vector<u8>): vector<u8>;
+    native fun scalar_sub_internal(a: vector<u8>, b: vector<u8>): vector<u8>;
+    native fun scalar_mul_internal(a: vector<u8>, b: vector<u8>): vector<u8>;
+    native fun scalar_neg_internal(a: vector<u8>): vector<u8>;
+    native fun scalar_inv_internal(a: vector<u8>): (vector<u8>, bool);
+
+    native fun g1_identity_internal(): vector<u8>;
+    native fun g1_generator_internal(): vector<u8>;
+    native fun g1_deserialize_internal(bytes: vector<u8>): (vector<u8>, bool);
+    native fun g1_compress_internal(p: vector<u8>): vector<u8>;
+    native fun g1_add_internal(a: vector<u8>, b: vector<u8>): vector<u8>;
+    native fun g1_neg_internal(a: vector<u8>): vector<u8>;
+    native fun g1_scalar_mul_internal(p: vector<u8>, s: vector<u8>): vector<u8>;
+    native fun g1_multi_scalar_mul_internal(points: vector<G1>, scalars: vector<Scalar>): vector<u8>;
+    native fun g1_hash_to_internal(dst: vector<u8>, msg: vector<u8>): vector<u8>;
+
+    native fun g2_identity_internal(): vector<u8>;
+    native fun g2_generator_internal(): vector<u8>;
+    native fun g2_deserialize_internal(bytes: vector<u8>): (vector<u8>, bool);
+    native fun g2_compress_internal(p: vector<u8>): vector<u8>;
+    native fun g2_add_internal(a: vector<u8>, b: vector<u8>): vector<u8>;
+    native fun g2_neg_internal(a: vector<u8>): vector<u8>;
+    native fun g2_scalar_mul_internal(p: vector<u8>, s: vector<u8>): vector<u8>;
+    native fun g2_multi_scalar_mul_internal(points: vector<G2>, scalars: vector<Scalar>): vector<u8>;
+    native fun g2_hash_to_internal(dst: vector<u8>, msg: vector<u8>): vector<u8>;
+
+    native fun gt_identity_internal(): vector<u8>;
+    native fun gt_generator_internal(): vector<u8>;
+    native fun gt_deserialize_internal(bytes: vector<u8>): (vector<u8>, bool);
+    native fun gt_mul_internal(a: vector<u8>, b: vector<u8>): vector<u8>;
+    native fun gt_inv_internal(a: vector<u8>): vector<u8>;
+    native fun gt_pow_internal(a: vector<u8>, s: vector<u8>): vector<u8>;
+
+    native fun pairing_internal(p: vector<u8>, q: vector<u8>): vector<u8>;
+    native fun multi_pairing_internal(ps: vector<G1>, qs: vector<G2>): vector<u8>;
+
+    //
+    // Testing
+    //
+
+    #[test_only]
+    /// The compressed encoding of the generator of G1.
+    const G1_GENERATOR_COMPRESSED: vector<u8> = x"97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+
+    #[test_only]
+    /// The order r of the scalar field, in little-endian.
+    const ORDER_R: vector<u8> = x"01000000fffffffffe5bfeff02a4bd5305d8a10908d83933487d9d2953a7ed73";
+
+    #[test]
+    fun test_scalar_arithmetic() {
+        let two = scalar_from_u64(2);
+        let three = scalar_from_u64(3);
+        let six = scalar_from_u64(6);
+
+        assert!(scalar_mul(&two, &three) == six, 1);
+        assert!(scalar_add(&three, &three) == six, 2);
+        assert!(scalar_sub(&six, &three) == three, 3);
+        assert!(scalar_add(&six, &scalar_neg(&six)) == scalar_zero(), 4);
+
+        let six_inv = option::extract(&mut scalar_inv(&six));
+        assert!(scalar_mul(&six, &six_inv) == scalar_one(), 5);
+        assert!(option::is_none(&scalar_inv(&scalar_zero())), 6);
+    }
+
+    #[test]
+    fun test_scalar_from_bytes() {
+        let minus_one = scalar_neg(&scalar_one());
+        assert!(scalar_from_bytes(scalar_to_bytes(&minus_one)) == option::some(minus_one), 1);
+
+        // The order itself is not reduced, and encodings must be exactly 32 bytes long.
+        assert!(option::is_none(&scalar_from_bytes(ORDER_R)), 2);
+        assert!(option::is_none(&scalar_from_bytes(x"01")), 3);
+    }
+
+    #[test]
+    fun test_g1_serialization() {
+        let g = g1_generator();
+        assert!(g1_to_bytes(&g) == G1_GENERATOR_COMPRESSED, 1);
+        assert!(g1_from_bytes(G1_GENERATOR_COMPRESSED) == option::some(g), 2);
+        assert!(g1_from_bytes(g1_to_uncompressed_bytes(&g)) == option::some(g), 3);
+
+        let id = g1_identity();
+        assert!(g1_from_bytes(g1_to_bytes(&id)) == option::some(id), 4);
+
+        assert!(option::is_none(&g1_from_bytes(x"01")), 5);
+    }
+
+    #[test]
+    fun test_g1_arithmetic() {
+        let g = g1_generator();
+        let two_g = g1_add(&g, &g);
+        let three_g = g1_scalar_mul(&g, &scalar_from_u64(3));
+
+        assert!(g1_scalar_mul(&g, &scalar_from_u64(2)) == two_g, 1);
+        assert!(g1_sub(&three_g, &two_g) == g, 2);
+        assert!(g1_add(&g, &g1_neg(&g)) == g1_identity(), 3);
+        assert!(g1_scalar_mul(&g, &scalar_zero()) == g1_identity(), 4);
+
+        // 2 * (2g) + 5 * g = 9g
+        let msm = g1_multi_scalar_mul(
+            &vector[two_g, g],
+            &vector[scalar_from_u64(2), scalar_from_u64(5)]
+        );
+        assert!(msm == g1_scalar_mul(&g, &scalar_from_u64(9)), 5);
+    }
+
+    #[test]
+    fun test_g2_arithmetic() {
+        let g = g2_generator();
+        let two_g = g2_add(&g, &g);
+
+        assert!(g2_scalar_mul(&g, &scalar_from_u64(2)) == two_g, 1);
+        assert!(g2_sub(&two_g, &g) == g, 2);
+        assert!(g2_add(&g, &g2_neg(&g)) == g2_identity(), 3);
+        assert!(g2_from_bytes(g2_to_bytes(&two_g)) == option::some(two_g), 4);
+
+        let msm = g2_multi_scalar_mul(
+            &vector[two_g, g],
+            &vector[scalar_from_u64(2), scalar_from_u64(5)]
+        );
+        assert!(msm == g2_scalar_mul(&g, &scalar_from_u64(9)), 5);
+    }
+
+    #[test]
+    fun test_hash_to() {
+        let p = g1_hash_to(b"APTOS_TEST_DST", b"hello");
+        assert!(p == g1_hash_to(b"APTOS_TEST_DST", b"hello"), 1);
+        assert!(p != g1_hash_to(b"APTOS_TEST_DST", b"world"), 2);
+        assert!(p != g1_hash_to(b"APTOS_OTHER_DST", b"hello"), 3);
+        assert!(g1_from_bytes(g1_to_bytes(&p)) == option::some(p), 4);
+
+        let q = g2_hash_to(b"APTOS_TEST_DST", b"hello");
+        assert!(g2_from_bytes(g2_to_bytes(&q)) == option::some(q), 5);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x010003)]
+    fun test_hash_to_empty_dst() {
+        g1_hash_to(b"", b"hello");
+    }
+
+    #[test]
+    fun test_pairing_bilinearity() {
+        let a = scalar_from_u64(5);
+        let b = scalar_from_u64(7);
+        let e = gt_generator();
+        assert!(e == pairing(&g1_generator(), &g2_generator()), 1);
+
+        // e(aP, bQ) = e(P, Q)^{ab}
+        let lhs = pairing(&g1_scalar_mul(&g1_generator(), &a), &g2_scalar_mul(&g2_generator(), &b));
+        assert!(lhs == gt_pow(&e, &scalar_mul(&a, &b)), 2);
+
+        assert!(gt_mul(&e, &gt_inv(&e)) == gt_identity(), 3);
+        assert!(pairing(&g1_identity(), &g2_generator()) == gt_identity(), 4);
+        assert!(gt_from_bytes(gt_to_bytes(&lhs)) == option::some(lhs), 5);
+        assert!(option::is_none(&gt_from_bytes(x"01")), 6);
+    }
+
+    #[test]
+    fun test_multi_pairing() {
+        let p = g1_generator();
+        let q = g2_generator();
+
+        // e(P, 2Q) * e(-2P, Q) = 1
+        let result = multi_pairing(
+            &vector[p, g1_scalar_mul(&g1_neg(&p), &scalar_from_u64(2))],
+            &vector[g2_add(&q, &q), q]
+        );
+        assert!(result == gt_identity(), 1);
+
+        let e = multi_pairing(&vector[p, p], &vector[q, q]);
+        assert!(e == gt_pow(&gt_generator(), &scalar_from_u64(2)), 2);
+    }
+}
@@ -0,0 +1,129 @@
+spec aptos_std::bls12381_algebra {
+    spec scalar_is_canonical_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec scalar_add_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec scalar_sub_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec scalar_mul_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec scalar_neg_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec scalar_inv_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g1_identity_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g1_generator_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g1_deserialize_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g1_compress_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g1_add_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g1_neg_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g1_scalar_mul_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g1_multi_scalar_mul_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g1_hash_to_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g2_identity_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g2_generator_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g2_deserialize_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g2_compress_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g2_add_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g2_neg_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g2_scalar_mul_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g2_multi_scalar_mul_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec g2_hash_to_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec gt_identity_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec gt_generator_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec gt_deserialize_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec gt_mul_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec gt_inv_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec gt_pow_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec pairing_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec multi_pairing_internal { // TODO: temporary mockup.
+        pragma opaque;
+    }
+}
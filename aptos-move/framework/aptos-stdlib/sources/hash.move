@@ -1,5 +1,12 @@
 /// Cryptographic hashes:
 /// - Keccak-256: see https://keccak.team/keccak.html
+/// - SHA2-512 and SHA3-512: see https://csrc.nist.gov/publications/detail/fips/180/4/final and
+///   https://csrc.nist.gov/publications/detail/fips/202/final
+/// - RIPEMD-160: see https://homes.esat.kuleuven.be/~bosselae/ripemd160.html
+/// - BLAKE2b-256: see https://www.rfc-editor.org/rfc/rfc7693
+///
+/// SHA2-512, SHA3-512, RIPEMD-160 and BLAKE2b-256 are gated behind feature flags in `std::features`, and abort until
+/// those are enabled.
 ///
 /// In addition, SHA2-256 and SHA3-256 are available in `std::hash`. Note that SHA3-256 is a variant of Keccak: it is
 /// NOT the same as Keccak-256.
@@ -8,6 +15,22 @@
 /// - SipHash: an add-rotate-xor (ARX) based family of pseudorandom functions created by Jean-Philippe Aumasson and Daniel J. Bernstein in 2012
 module aptos_std::aptos_hash {
     use std::bcs;
+    use std::error;
+    use std::features;
+
+    #[test_only]
+    use std::vector;
+
+    //
+    // Constants
+    //
+
+    /// A newly-added native function is not yet enabled.
+    const E_NATIVE_FUN_NOT_AVAILABLE: u64 = 1;
+
+    //
+    // Functions
+    //
 
     native public fun sip_hash(bytes: vector<u8>): u64;
 
@@ -23,6 +46,57 @@ module aptos_std::aptos_hash {
 
     native public fun keccak256(bytes: vector<u8>): vector<u8>;
 
+    /// Returns the SHA2-512 hash of `bytes`.
+    public fun sha2_512(bytes: vector<u8>): vector<u8> {
+        if(!features::sha_512_and_ripemd_160_enabled()) {
+            abort(error::invalid_state(E_NATIVE_FUN_NOT_AVAILABLE))
+        };
+
+        sha2_512_internal(bytes)
+    }
+
+    /// Returns the SHA3-512 hash of `bytes`.
+    public fun sha3_512(bytes: vector<u8>): vector<u8> {
+        if(!features::sha_512_and_ripemd_160_enabled()) {
+            abort(error::invalid_state(E_NATIVE_FUN_NOT_AVAILABLE))
+        };
+
+        sha3_512_internal(bytes)
+    }
+
+    /// Returns the RIPEMD-160 hash of `bytes`.
+    ///
+    /// WARNING: Only 80-bit security is provided by this function. This means an adversary who can compute roughly 2^80
+    /// hashes will, with high probability, find a collision x_1 != x_2 such that RIPEMD-160(x_1) = RIPEMD-160(x_2).
+    public fun ripemd160(bytes: vector<u8>): vector<u8> {
+        if(!features::sha_512_and_ripemd_160_enabled()) {
+            abort(error::invalid_state(E_NATIVE_FUN_NOT_AVAILABLE))
+        };
+
+        ripemd160_internal(bytes)
+    }
+
+    /// Returns the BLAKE2B-256 hash of `bytes`.
+    public fun blake2b_256(bytes: vector<u8>): vector<u8> {
+        if(!features::blake2b_256_enabled()) {
+            abort(error::invalid_state(E_NATIVE_FUN_NOT_AVAILABLE))
+        };
+
+        blake2b_256_internal(bytes)
+    }
+
+    //
+    // Private native functions
+    //
+
+    native fun sha2_512_internal(bytes: vector<u8>): vector<u8>;
+
+    native fun sha3_512_internal(bytes: vector<u8>): vector<u8>;
+
+    native fun ripemd160_internal(bytes: vector<u8>): vector<u8>;
+
+    native fun blake2b_256_internal(bytes: vector<u8>): vector<u8>;
+
     //
     // Testing
     //
@@ -50,4 +124,118 @@ module aptos_std::aptos_hash {
             i = i + 1;
         };
     }
+
+    #[test(fx = @aptos_std)]
+    fun sha2_512_test(fx: signer) {
+        // We need to enable the feature in order for the native call to be allowed.
+        features::change_feature_flags(&fx, vector[features::get_sha_512_and_ripemd_160_feature()], vector[]);
+
+        let inputs = vector[
+            b"testing",
+            b"",
+        ];
+
+        let outputs = vector[
+            x"521b9ccefbcd14d179e7a1bb877752870a6d620938b28a66a107eac6e6805b9d0989f45b5730508041aa5e710847d439ea74cd312c9355f1f2dae08d40e41d50",
+            x"cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e",
+        ];
+
+        let i = 0;
+        while (i < vector::length(&inputs)) {
+            let input = *vector::borrow(&inputs, i);
+            let hash_expected = *vector::borrow(&outputs, i);
+            let hash = sha2_512(input);
+
+            assert!(hash_expected == hash, 1);
+
+            i = i + 1;
+        };
+    }
+
+    #[test(fx = @aptos_std)]
+    fun sha3_512_test(fx: signer) {
+        // We need to enable the feature in order for the native call to be allowed.
+        features::change_feature_flags(&fx, vector[features::get_sha_512_and_ripemd_160_feature()], vector[]);
+
+        let inputs = vector[
+            b"testing",
+            b"",
+        ];
+
+        let outputs = vector[
+            x"881c7d6ba98678bcd96e253086c4048c3ea15306d0d13ff48341c6285ee71102a47b6f16e20e4d65c0c3d677be689dfda6d326695609cbadfafa1800e9eb7fc1",
+            x"a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26",
+        ];
+
+        let i = 0;
+        while (i < vector::length(&inputs)) {
+            let input = *vector::borrow(&inputs, i);
+            let hash_expected = *vector::borrow(&outputs, i);
+            let hash = sha3_512(input);
+
+            assert!(hash_expected == hash, 1);
+
+            i = i + 1;
+        };
+    }
+
+    #[test(fx = @aptos_std)]
+    fun ripemd160_test(fx: signer) {
+        // We need to enable the feature in order for the native call to be allowed.
+        features::change_feature_flags(&fx, vector[features::get_sha_512_and_ripemd_160_feature()], vector[]);
+
+        let inputs = vector[
+            b"testing",
+            b"",
+        ];
+
+        let outputs = vector[
+            x"b89ba156b40bed29a5965684b7d244c49a3a769b",
+            x"9c1185a5c5e9fc54612808977ee8f548b2258d31",
+        ];
+
+        let i = 0;
+        while (i < vector::length(&inputs)) {
+            let input = *vector::borrow(&inputs, i);
+            let hash_expected = *vector::borrow(&outputs, i);
+            let hash = ripemd160(input);
+
+            assert!(hash_expected == hash, 1);
+
+            i = i + 1;
+        };
+    }
+
+    #[test(fx = @aptos_std)]
+    fun blake2b_256_test(fx: signer) {
+        // We need to enable the feature in order for the native call to be allowed.
+        features::change_feature_flags(&fx, vector[features::get_blake2b_256_feature()], vector[]);
+
+        let inputs = vector[
+            b"testing",
+            b"",
+        ];
+
+        let outputs = vector[
+            x"99397ff32ae348b8b6536d5c213f343d7e9fdeaa10e8a23a9f90ab21a1658565",
+            x"0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8",
+        ];
+
+        let i = 0;
+        while (i < vector::length(&inputs)) {
+            let input = *vector::borrow(&inputs, i);
+            let hash_expected = *vector::borrow(&outputs, i);
+            let hash = blake2b_256(input);
+
+            assert!(hash_expected == hash, 1);
+
+            i = i + 1;
+        };
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x30001)]
+    fun sha2_512_disabled_test() {
+        sha2_512(b"testing");
+    }
 }
@@ -8,4 +8,24 @@ spec aptos_std::aptos_hash {
         // TODO: temporary mockup.
         pragma opaque;
     }
+
+    spec sha2_512_internal {
+        // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec sha3_512_internal {
+        // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec ripemd160_internal {
+        // TODO: temporary mockup.
+        pragma opaque;
+    }
+
+    spec blake2b_256_internal {
+        // TODO: temporary mockup.
+        pragma opaque;
+    }
 }
@@ -0,0 +1,157 @@
+/// Defines feature flags for Aptos. Those are used in Aptos specific implementations of features in
+/// the Move stdlib, the Aptos stdlib, and the Aptos framework, so that new functionality can be rolled out
+/// safely: it is shipped disabled and switched on through governance once the network is ready for it.
+///
+/// Feature flags are identified by a number. Once a feature flag has been assigned, its number must never be
+/// reused for a different feature.
+module std::features {
+    use std::error;
+    use std::signer;
+    use std::vector;
+
+    // --------------------------------------------------------------------------------------------
+    // Feature Flag Definitions
+
+    /// Whether the new SHA2-512, SHA3-512 and RIPEMD-160 hash function natives are enabled.
+    /// This is needed because of the introduction of new native functions.
+    /// Lifetime: transient
+    const SHA_512_AND_RIPEMD_160_NATIVES: u64 = 1;
+    public fun get_sha_512_and_ripemd_160_feature(): u64 { SHA_512_AND_RIPEMD_160_NATIVES }
+    public fun sha_512_and_ripemd_160_enabled(): bool acquires Features {
+        is_enabled(SHA_512_AND_RIPEMD_160_NATIVES)
+    }
+
+    /// Whether the new BLAKE2B-256 hash function native is enabled.
+    /// This is needed because of the introduction of new native function(s).
+    /// Lifetime: transient
+    const BLAKE2B_256_NATIVE: u64 = 2;
+    public fun get_blake2b_256_feature(): u64 { BLAKE2B_256_NATIVE }
+    public fun blake2b_256_enabled(): bool acquires Features {
+        is_enabled(BLAKE2B_256_NATIVE)
+    }
+
+    /// Whether transactions can be authenticated with Secp256r1 ECDSA keys, either directly or
+    /// through WebAuthn assertions. This is checked by the VM before running the prologue.
+    /// Lifetime: transient
+    const SECP256R1_ECDSA_AUTHENTICATOR: u64 = 3;
+    public fun get_secp256r1_ecdsa_authenticator_feature(): u64 { SECP256R1_ECDSA_AUTHENTICATOR }
+    public fun secp256r1_ecdsa_authenticator_enabled(): bool acquires Features {
+        is_enabled(SECP256R1_ECDSA_AUTHENTICATOR)
+    }
+
+    /// Whether sponsored transactions are enabled, where a fee payer other than the sender is
+    /// charged for gas. This is checked by the VM before running the prologue.
+    /// Lifetime: transient
+    const FEE_PAYER_ENABLED: u64 = 4;
+    public fun get_fee_payer_feature(): u64 { FEE_PAYER_ENABLED }
+    public fun fee_payer_enabled(): bool acquires Features {
+        is_enabled(FEE_PAYER_ENABLED)
+    }
+
+    /// Whether on-chain multisig accounts can be created and their approved transactions executed
+    /// through the multisig transaction payload.
+    /// Lifetime: transient
+    const MULTISIG_ACCOUNTS: u64 = 5;
+    public fun get_multisig_accounts_feature(): u64 { MULTISIG_ACCOUNTS }
+    public fun multisig_accounts_enabled(): bool acquires Features {
+        is_enabled(MULTISIG_ACCOUNTS)
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Feature Flag Implementation
+
+    /// The provided signer has not a framework address.
+    const EFRAMEWORK_SIGNER_NEEDED: u64 = 1;
+
+    /// The enabled features, represented by a bitset stored on chain.
+    struct Features has key {
+        features: vector<u8>,
+    }
+
+    /// Function to enable and disable features. Can only be called by a signer of @std.
+    public fun change_feature_flags(framework: &signer, enable: vector<u64>, disable: vector<u64>)
+    acquires Features {
+        assert!(signer::address_of(framework) == @std, error::permission_denied(EFRAMEWORK_SIGNER_NEEDED));
+        if (!exists<Features>(@std)) {
+            move_to<Features>(framework, Features { features: vector[] })
+        };
+        let features = &mut borrow_global_mut<Features>(@std).features;
+        let i = 0;
+        let n = vector::length(&enable);
+        while (i < n) {
+            set(features, *vector::borrow(&enable, i), true);
+            i = i + 1
+        };
+        let i = 0;
+        let n = vector::length(&disable);
+        while (i < n) {
+            set(features, *vector::borrow(&disable, i), false);
+            i = i + 1
+        };
+    }
+
+    /// Check whether the feature is enabled.
+    public fun is_enabled(feature: u64): bool acquires Features {
+        exists<Features>(@std) && contains(&borrow_global<Features>(@std).features, feature)
+    }
+
+    /// Helper to include or exclude a feature flag.
+    fun set(features: &mut vector<u8>, feature: u64, include: bool) {
+        let byte_index = feature / 8;
+        let bit_mask = 1 << ((feature % 8) as u8);
+        while (vector::length(features) <= byte_index) {
+            vector::push_back(features, 0)
+        };
+        let entry = vector::borrow_mut(features, byte_index);
+        if (include)
+            *entry = *entry | bit_mask
+        else
+            *entry = *entry & (0xff ^ bit_mask)
+    }
+
+    /// Helper to check whether a feature flag is enabled.
+    fun contains(features: &vector<u8>, feature: u64): bool {
+        let byte_index = feature / 8;
+        let bit_mask = 1 << ((feature % 8) as u8);
+        byte_index < vector::length(features) && (*vector::borrow(features, byte_index) & bit_mask) != 0
+    }
+
+    #[test]
+    fun test_feature_sets() {
+        let features = vector[];
+        set(&mut features, 1, true);
+        set(&mut features, 5, true);
+        set(&mut features, 17, true);
+        set(&mut features, 23, true);
+        assert!(contains(&features, 1), 0);
+        assert!(contains(&features, 5), 1);
+        assert!(contains(&features, 17), 2);
+        assert!(contains(&features, 23), 3);
+        set(&mut features, 5, false);
+        set(&mut features, 17, false);
+        assert!(contains(&features, 1), 0);
+        assert!(!contains(&features, 5), 1);
+        assert!(!contains(&features, 17), 2);
+        assert!(contains(&features, 23), 3);
+    }
+
+    #[test(fx = @std)]
+    fun test_change_feature_txn(fx: signer) acquires Features {
+        assert!(!is_enabled(1), 0);
+        change_feature_flags(&fx, vector[1, 9, 23], vector[]);
+        assert!(is_enabled(1), 1);
+        assert!(is_enabled(9), 2);
+        assert!(is_enabled(23), 3);
+        change_feature_flags(&fx, vector[17], vector[9]);
+        assert!(is_enabled(1), 1);
+        assert!(!is_enabled(9), 2);
+        assert!(is_enabled(17), 3);
+        assert!(is_enabled(23), 4);
+    }
+
+    #[test(not_fx = @0x123)]
+    #[expected_failure(abort_code = 0x50001)]
+    fun test_change_feature_txn_requires_framework(not_fx: signer) acquires Features {
+        change_feature_flags(&not_fx, vector[1], vector[]);
+    }
+}
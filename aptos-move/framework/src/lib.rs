@@ -11,6 +11,9 @@ use std::io::{Read, Write};
 mod built_package;
 pub use built_package::*;
 
+mod module_compatibility;
+pub use module_compatibility::*;
+
 mod module_metadata;
 pub use module_metadata::*;
 
@@ -0,0 +1,431 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use move_deps::move_binary_format::{
+    compatibility::Compatibility,
+    file_format::{Ability, AbilitySet, Visibility},
+    normalized::{Function, Module, Struct, Type},
+    CompiledModule,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The kind of module member an incompatibility refers to.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberKind {
+    Module,
+    Struct,
+    Function,
+}
+
+/// A change in a module which prevents it from being published as a `compatible` upgrade of
+/// the currently deployed module.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Incompatibility {
+    pub kind: MemberKind,
+    pub name: String,
+    pub reason: String,
+}
+
+impl Incompatibility {
+    pub fn new(kind: MemberKind, name: impl ToString, reason: String) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            MemberKind::Module => "module",
+            MemberKind::Struct => "struct",
+            MemberKind::Function => "function",
+        };
+        write!(f, "{} `{}`: {}", kind, self.name, self.reason)
+    }
+}
+
+/// Checks whether `new` is a compatible upgrade of `old`, as decided by the same
+/// [`Compatibility`] check the Move VM applies when publishing a module under the `compatible`
+/// upgrade policy, and explains what makes it incompatible:
+///
+/// - structs can't be removed, lose abilities, or change their fields;
+/// - type parameters can't be added, removed or given new constraints, and phantom type parameters
+///   of structs must stay phantom;
+/// - exposed (public, friend or entry) functions can't be removed, or change their signature;
+/// - friend declarations can't be removed.
+///
+/// Returns the list of changes which make the upgrade incompatible, which is empty if the
+/// upgrade is compatible.
+pub fn check_module_compatibility(
+    old: &CompiledModule,
+    new: &CompiledModule,
+) -> Vec<Incompatibility> {
+    let old = Module::new(old);
+    let new = Module::new(new);
+    if Compatibility::check(&old, &new).is_fully_compatible() {
+        return vec![];
+    }
+    let mut result = explain_incompatibilities(&old, &new);
+    if result.is_empty() {
+        // The VM rejects the upgrade for a reason the rules above don't cover
+        result.push(Incompatibility::new(
+            MemberKind::Module,
+            &new.name,
+            "module is incompatible with the deployed one".to_owned(),
+        ));
+    }
+    result
+}
+
+/// Lists the changes from `old` to `new` which break the rules of compatible upgrades.
+fn explain_incompatibilities(old: &Module, new: &Module) -> Vec<Incompatibility> {
+    let mut result = vec![];
+
+    for (name, old_struct) in &old.structs {
+        match new.structs.get(name) {
+            Some(new_struct) => check_struct(name.as_str(), old_struct, new_struct, &mut result),
+            None => result.push(Incompatibility::new(
+                MemberKind::Struct,
+                name,
+                "struct was removed".to_owned(),
+            )),
+        }
+    }
+
+    for (name, old_function) in &old.exposed_functions {
+        match new.exposed_functions.get(name) {
+            Some(new_function) => {
+                check_function(name.as_str(), old_function, new_function, &mut result)
+            }
+            None => result.push(Incompatibility::new(
+                MemberKind::Function,
+                name,
+                format!(
+                    "{} function was removed or made private",
+                    visibility_str(old_function.visibility)
+                ),
+            )),
+        }
+    }
+
+    for friend in &old.friends {
+        if !new.friends.contains(friend) {
+            result.push(Incompatibility::new(
+                MemberKind::Module,
+                &new.name,
+                format!("friend declaration of `{}` was removed", friend),
+            ));
+        }
+    }
+
+    result
+}
+
+fn check_struct(name: &str, old: &Struct, new: &Struct, result: &mut Vec<Incompatibility>) {
+    if !old.abilities.is_subset(new.abilities) {
+        result.push(Incompatibility::new(
+            MemberKind::Struct,
+            name,
+            format!(
+                "abilities changed from `{}` to `{}`",
+                abilities_str(old.abilities),
+                abilities_str(new.abilities)
+            ),
+        ));
+    }
+    let type_parameters_compatible = old.type_parameters.len() == new.type_parameters.len()
+        && old
+            .type_parameters
+            .iter()
+            .zip(&new.type_parameters)
+            .all(|(old, new)| {
+                // A type parameter can be made phantom, but a phantom one can't stop being so.
+                new.constraints.is_subset(old.constraints) && (!old.is_phantom || new.is_phantom)
+            });
+    if !type_parameters_compatible {
+        result.push(Incompatibility::new(
+            MemberKind::Struct,
+            name,
+            "type parameters or their constraints changed".to_owned(),
+        ));
+    }
+    if old.fields != new.fields {
+        result.push(Incompatibility::new(
+            MemberKind::Struct,
+            name,
+            format!(
+                "fields changed from `{{ {} }}` to `{{ {} }}`, which changes the layout of stored values",
+                fields_str(old),
+                fields_str(new)
+            ),
+        ));
+    }
+}
+
+fn check_function(name: &str, old: &Function, new: &Function, result: &mut Vec<Incompatibility>) {
+    let visibility_compatible = match (old.visibility, new.visibility) {
+        (Visibility::Public, Visibility::Public) => true,
+        (Visibility::Public, _) => false,
+        (Visibility::Friend, Visibility::Public) | (Visibility::Friend, Visibility::Friend) => true,
+        (Visibility::Friend, _) => false,
+        (Visibility::Private, _) => true,
+    };
+    if !visibility_compatible {
+        result.push(Incompatibility::new(
+            MemberKind::Function,
+            name,
+            format!(
+                "visibility changed from {} to {}",
+                visibility_str(old.visibility),
+                visibility_str(new.visibility)
+            ),
+        ));
+    }
+    if old.is_entry && !new.is_entry {
+        result.push(Incompatibility::new(
+            MemberKind::Function,
+            name,
+            "is no longer an entry function".to_owned(),
+        ));
+    }
+    if old.parameters != new.parameters {
+        result.push(Incompatibility::new(
+            MemberKind::Function,
+            name,
+            format!(
+                "parameters changed from `({})` to `({})`",
+                types_str(&old.parameters),
+                types_str(&new.parameters)
+            ),
+        ));
+    }
+    if old.return_ != new.return_ {
+        result.push(Incompatibility::new(
+            MemberKind::Function,
+            name,
+            format!(
+                "return types changed from `({})` to `({})`",
+                types_str(&old.return_),
+                types_str(&new.return_)
+            ),
+        ));
+    }
+    let type_parameters_compatible = old.type_parameters.len() == new.type_parameters.len()
+        && old
+            .type_parameters
+            .iter()
+            .zip(&new.type_parameters)
+            .all(|(old, new)| new.is_subset(*old));
+    if !type_parameters_compatible {
+        result.push(Incompatibility::new(
+            MemberKind::Function,
+            name,
+            "type parameters or their constraints changed".to_owned(),
+        ));
+    }
+}
+
+fn visibility_str(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "public",
+        Visibility::Friend => "friend",
+        Visibility::Private => "private",
+    }
+}
+
+fn abilities_str(abilities: AbilitySet) -> String {
+    abilities
+        .into_iter()
+        .map(|ability| match ability {
+            Ability::Copy => "copy",
+            Ability::Drop => "drop",
+            Ability::Store => "store",
+            Ability::Key => "key",
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn fields_str(s: &Struct) -> String {
+    s.fields
+        .iter()
+        .map(|field| format!("{}: {}", field.name, field.type_))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn types_str(types: &[Type]) -> String {
+    types
+        .iter()
+        .map(|ty| ty.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_deps::move_ir_compiler::Compiler;
+
+    fn compile(body: &str) -> CompiledModule {
+        Compiler { deps: vec![] }
+            .into_compiled_module(&format!("module 0x1.M {{ {} }}", body))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_check_module_compatibility() {
+        // (rule, old module body, new module body, whether the upgrade is compatible)
+        let cases = [
+            (
+                "unchanged module",
+                "struct T { f: u64 } public f() { label b0: return; }",
+                "struct T { f: u64 } public f() { label b0: return; }",
+                true,
+            ),
+            ("struct added", "", "struct T { f: u64 }", true),
+            ("struct removed", "struct T { f: u64 }", "", false),
+            (
+                "ability added",
+                "struct T { f: u64 }",
+                "struct T has copy { f: u64 }",
+                true,
+            ),
+            (
+                "ability removed",
+                "struct T has copy { f: u64 }",
+                "struct T { f: u64 }",
+                false,
+            ),
+            (
+                "field added",
+                "struct T { f: u64 }",
+                "struct T { f: u64, g: bool }",
+                false,
+            ),
+            (
+                "field type changed",
+                "struct T { f: u64 }",
+                "struct T { f: bool }",
+                false,
+            ),
+            (
+                "struct type parameter added",
+                "struct T { f: u64 }",
+                "struct T<A> { f: u64 }",
+                false,
+            ),
+            (
+                "struct type parameter made phantom",
+                "struct T<A> { f: u64 }",
+                "struct T<phantom A> { f: u64 }",
+                true,
+            ),
+            (
+                "phantom struct type parameter made non-phantom",
+                "struct T<phantom A> { f: u64 }",
+                "struct T<A> { f: u64 }",
+                false,
+            ),
+            (
+                "struct type parameter constraint removed",
+                "struct T<A: copy> { f: u64 }",
+                "struct T<A> { f: u64 }",
+                true,
+            ),
+            (
+                "struct type parameter constraint added",
+                "struct T<A> { f: u64 }",
+                "struct T<A: copy> { f: u64 }",
+                false,
+            ),
+            (
+                "public function added",
+                "",
+                "public f() { label b0: return; }",
+                true,
+            ),
+            (
+                "public function removed",
+                "public f() { label b0: return; }",
+                "",
+                false,
+            ),
+            (
+                "private function removed",
+                "f() { label b0: return; }",
+                "",
+                true,
+            ),
+            (
+                "public function made friend",
+                "public f() { label b0: return; }",
+                "public(friend) f() { label b0: return; }",
+                false,
+            ),
+            (
+                "friend function made public",
+                "public(friend) f() { label b0: return; }",
+                "public f() { label b0: return; }",
+                true,
+            ),
+            (
+                "parameter changed",
+                "public f(_a: u64) { label b0: return; }",
+                "public f(_a: bool) { label b0: return; }",
+                false,
+            ),
+            (
+                "return type changed",
+                "public f(): u64 { label b0: return 0; }",
+                "public f(): bool { label b0: return false; }",
+                false,
+            ),
+            (
+                "function type parameter constraint removed",
+                "public f<A: copy>() { label b0: return; }",
+                "public f<A>() { label b0: return; }",
+                true,
+            ),
+            (
+                "function type parameter constraint added",
+                "public f<A>() { label b0: return; }",
+                "public f<A: copy>() { label b0: return; }",
+                false,
+            ),
+            ("friend added", "", "friend 0x1.N;", true),
+            ("friend removed", "friend 0x1.N;", "", false),
+        ];
+        for (rule, old, new, compatible) in cases {
+            let (old, new) = (compile(old), compile(new));
+            assert_eq!(
+                check_module_compatibility(&old, &new).is_empty(),
+                compatible,
+                "{}",
+                rule
+            );
+
+            // The explanation has to agree with the check of the VM on its own
+            let (old, new) = (Module::new(&old), Module::new(&new));
+            assert_eq!(
+                Compatibility::check(&old, &new).is_fully_compatible(),
+                compatible,
+                "{}: disagrees with the VM",
+                rule
+            );
+            let incompatibilities = explain_incompatibilities(&old, &new);
+            assert_eq!(
+                incompatibilities.is_empty(),
+                compatible,
+                "{}: {:?}",
+                rule,
+                incompatibilities
+            );
+        }
+    }
+}
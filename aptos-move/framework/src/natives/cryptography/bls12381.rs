@@ -21,7 +21,7 @@ use std::{collections::VecDeque, convert::TryFrom};
 
 /// Pops a Vec<T> off the argument stack and converts it to a Vec<Vec<u8>> by reading the first
 /// field of T, which is a Vec<u8> field named `bytes`.
-fn pop_vec_of_vec_u8(arguments: &mut VecDeque<Value>) -> PartialVMResult<Vec<Vec<u8>>> {
+pub(crate) fn pop_vec_of_vec_u8(arguments: &mut VecDeque<Value>) -> PartialVMResult<Vec<Vec<u8>>> {
     let structs = pop_vec_arg!(arguments, Struct);
     let mut v = Vec::with_capacity(structs.len());
 
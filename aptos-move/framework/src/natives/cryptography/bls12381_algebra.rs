@@ -0,0 +1,799 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Natives for generic arithmetic over the BLS12-381 pairing groups G1, G2 and Gt, and over its
+//! scalar field, backed by arkworks.
+//!
+//! Group elements and scalars are passed to and from Move as byte vectors: points use their
+//! uncompressed serialization, Gt elements the serialization of their underlying Fq12 element and
+//! scalars their canonical 32-byte little-endian encoding. These encodings are unique, so Move can
+//! compare elements by comparing their bytes. Since the Move structs wrapping them can only be
+//! constructed by `aptos_std::bls12381_algebra`, the natives trust them and skip validity checks
+//! when parsing them back.
+
+use crate::natives::cryptography::bls12381::pop_vec_of_vec_u8;
+use crate::natives::util::make_native_from_func;
+use ark_bls12_381::{Bls12_381, Fr, G1Projective, G2Projective};
+use ark_ec::{
+    hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve},
+    pairing::{Pairing, PairingOutput},
+    CurveGroup, Group, VariableBaseMSM,
+};
+use ark_ff::{field_hashers::DefaultFieldHasher, Field, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use move_deps::move_binary_format::errors::{PartialVMError, PartialVMResult};
+use move_deps::move_core_types::gas_algebra::{
+    InternalGasPerArg, InternalGasPerByte, NumArgs, NumBytes,
+};
+use move_deps::move_core_types::vm_status::StatusCode;
+use move_deps::{
+    move_vm_runtime::native_functions::{NativeContext, NativeFunction},
+    move_vm_types::{
+        loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+    },
+};
+use sha2_0_10_6::Sha256;
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+type Gt = PairingOutput<Bls12_381>;
+
+/// The size of a canonically-encoded scalar.
+const SCALAR_NUM_BYTES: usize = 32;
+
+/// Abort code when the domain separation tag of a hash-to-curve is rejected (0x01 == INVALID_ARGUMENT)
+const EINVALID_DST: u64 = 0x01_0001;
+
+#[derive(Debug, Clone)]
+pub struct ScalarGasParameters {
+    pub deserialize: InternalGasPerArg,
+    pub add: InternalGasPerArg,
+    pub sub: InternalGasPerArg,
+    pub mul: InternalGasPerArg,
+    pub neg: InternalGasPerArg,
+    pub inv: InternalGasPerArg,
+}
+
+/// Gas parameters shared by the operations on G1 and G2.
+#[derive(Debug, Clone)]
+pub struct GroupGasParameters {
+    pub constant: InternalGasPerArg,
+    pub deserialize: InternalGasPerArg, // includes the prime-order subgroup check
+    pub compress: InternalGasPerArg,
+    pub add: InternalGasPerArg,
+    pub neg: InternalGasPerArg,
+    pub scalar_mul: InternalGasPerArg,
+    pub hash_to_base: InternalGasPerArg,
+    pub hash_to_per_byte: InternalGasPerByte,
+}
+
+#[derive(Debug, Clone)]
+pub struct GtGasParameters {
+    pub constant: InternalGasPerArg,
+    pub deserialize: InternalGasPerArg, // includes the prime-order subgroup check
+    pub mul: InternalGasPerArg,
+    pub inv: InternalGasPerArg,
+    pub pow: InternalGasPerArg,
+}
+
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub scalar: ScalarGasParameters,
+    pub g1: GroupGasParameters,
+    pub g2: GroupGasParameters,
+    pub gt: GtGasParameters,
+
+    // A size-n multi-pairing requires n Miller loops and a single final exponentiation.
+    pub per_miller_loop: InternalGasPerArg,
+    pub final_exponentiation: InternalGasPerArg,
+}
+
+/// Abstracts over G1 and G2, so their natives can be shared.
+trait PairingGroup: CurveGroup<ScalarField = Fr> {
+    fn gas_params(gas_params: &GasParameters) -> &GroupGasParameters;
+
+    /// Hashes `msg` to the group, as specified by the `BLS12381G1_XMD:SHA-256_SSWU_RO_` and
+    /// `BLS12381G2_XMD:SHA-256_SSWU_RO_` suites of RFC 9380, with the given domain separation tag.
+    fn hash_to_curve(dst: &[u8], msg: &[u8]) -> Option<Self::Affine>;
+}
+
+impl PairingGroup for G1Projective {
+    fn gas_params(gas_params: &GasParameters) -> &GroupGasParameters {
+        &gas_params.g1
+    }
+
+    fn hash_to_curve(dst: &[u8], msg: &[u8]) -> Option<Self::Affine> {
+        MapToCurveBasedHasher::<
+            Self,
+            DefaultFieldHasher<Sha256, 128>,
+            WBMap<ark_bls12_381::g1::Config>,
+        >::new(dst)
+        .and_then(|hasher| hasher.hash(msg))
+        .ok()
+    }
+}
+
+impl PairingGroup for G2Projective {
+    fn gas_params(gas_params: &GasParameters) -> &GroupGasParameters {
+        &gas_params.g2
+    }
+
+    fn hash_to_curve(dst: &[u8], msg: &[u8]) -> Option<Self::Affine> {
+        MapToCurveBasedHasher::<
+            Self,
+            DefaultFieldHasher<Sha256, 128>,
+            WBMap<ark_bls12_381::g2::Config>,
+        >::new(dst)
+        .and_then(|hasher| hasher.hash(msg))
+        .ok()
+    }
+}
+
+/***************************************************************************************************
+ * Helpers
+ *
+ **************************************************************************************************/
+
+/// Serializes a value into the encoding that is stored in its Move struct.
+fn to_move_bytes<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(value.uncompressed_size());
+    value
+        .serialize_uncompressed(&mut bytes)
+        .expect("serializing into a vector never fails");
+    bytes
+}
+
+/// Deserializes a value from the encoding that is stored in its Move struct.
+fn from_move_bytes<T: CanonicalDeserialize>(bytes: &[u8]) -> PartialVMResult<T> {
+    T::deserialize_uncompressed_unchecked(bytes)
+        .map_err(|_| PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR))
+}
+
+fn pop_scalar(arguments: &mut VecDeque<Value>) -> PartialVMResult<Fr> {
+    let bytes = pop_arg!(arguments, Vec<u8>);
+    if bytes.len() != SCALAR_NUM_BYTES {
+        return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR));
+    }
+    from_move_bytes(&bytes)
+}
+
+fn pop_element<T: CanonicalDeserialize>(arguments: &mut VecDeque<Value>) -> PartialVMResult<T> {
+    from_move_bytes(&pop_arg!(arguments, Vec<u8>))
+}
+
+fn element_result<T: CanonicalSerialize>(
+    cost: InternalGasPerArg,
+    value: &T,
+) -> PartialVMResult<NativeResult> {
+    Ok(NativeResult::ok(
+        cost * NumArgs::one(),
+        smallvec![Value::vector_u8(to_move_bytes(value))],
+    ))
+}
+
+/// Returns `(bytes, true)` for a valid element and `(vector[], false)` otherwise, which Move turns
+/// into an `Option`.
+fn optional_element_result<T: CanonicalSerialize>(
+    cost: InternalGasPerArg,
+    value: Option<T>,
+) -> PartialVMResult<NativeResult> {
+    let (bytes, valid) = match value {
+        Some(value) => (to_move_bytes(&value), true),
+        None => (vec![], false),
+    };
+
+    Ok(NativeResult::ok(
+        cost * NumArgs::one(),
+        smallvec![Value::vector_u8(bytes), Value::bool(valid)],
+    ))
+}
+
+/// The number of scalar multiplications a size-n multi-scalar multiplication is charged for.
+///
+/// NOTE: arkworks uses Pippenger's algorithm, which roughly requires O(n / log_2 n) scalar
+/// multiplications.
+fn msm_num_scalar_muls(n: usize) -> u64 {
+    if n <= 1 {
+        n as u64
+    } else {
+        (n as f64 / f64::log2(n as f64)).ceil() as u64
+    }
+}
+
+/***************************************************************************************************
+ * Scalars
+ *
+ **************************************************************************************************/
+
+fn native_scalar_is_canonical(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let cost = gas_params.scalar.deserialize * NumArgs::one();
+    let bytes = pop_arg!(arguments, Vec<u8>);
+
+    // Deserialization fails on encodings of integers that are not reduced modulo the field order.
+    let is_canonical =
+        bytes.len() == SCALAR_NUM_BYTES && Fr::deserialize_compressed(bytes.as_slice()).is_ok();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(is_canonical)]))
+}
+
+fn native_scalar_add(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_scalar(&mut arguments)?;
+    let a = pop_scalar(&mut arguments)?;
+
+    element_result(gas_params.scalar.add, &(a + b))
+}
+
+fn native_scalar_sub(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_scalar(&mut arguments)?;
+    let a = pop_scalar(&mut arguments)?;
+
+    element_result(gas_params.scalar.sub, &(a - b))
+}
+
+fn native_scalar_mul(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_scalar(&mut arguments)?;
+    let a = pop_scalar(&mut arguments)?;
+
+    element_result(gas_params.scalar.mul, &(a * b))
+}
+
+fn native_scalar_neg(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let a = pop_scalar(&mut arguments)?;
+
+    element_result(gas_params.scalar.neg, &(-a))
+}
+
+fn native_scalar_inv(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let a = pop_scalar(&mut arguments)?;
+
+    // Zero has no inverse.
+    optional_element_result(gas_params.scalar.inv, a.inverse())
+}
+
+/***************************************************************************************************
+ * G1 and G2
+ *
+ **************************************************************************************************/
+
+fn native_identity<G: PairingGroup>(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    _arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(_arguments.is_empty());
+
+    element_result(G::gas_params(gas_params).constant, &G::zero().into_affine())
+}
+
+fn native_generator<G: PairingGroup>(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    _arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(_arguments.is_empty());
+
+    element_result(
+        G::gas_params(gas_params).constant,
+        &G::generator().into_affine(),
+    )
+}
+
+/// Deserializes a point from either its compressed or its uncompressed encoding, rejecting points
+/// that are not on the curve or not in the prime-order subgroup.
+fn native_deserialize<G: PairingGroup>(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let bytes = pop_arg!(arguments, Vec<u8>);
+    let point = G::zero().into_affine();
+    let point = if bytes.len() == point.compressed_size() {
+        G::Affine::deserialize_compressed(bytes.as_slice()).ok()
+    } else if bytes.len() == point.uncompressed_size() {
+        G::Affine::deserialize_uncompressed(bytes.as_slice()).ok()
+    } else {
+        None
+    };
+
+    optional_element_result(G::gas_params(gas_params).deserialize, point)
+}
+
+fn native_compress<G: PairingGroup>(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let point = pop_element::<G::Affine>(&mut arguments)?;
+    let mut bytes = Vec::with_capacity(point.compressed_size());
+    point
+        .serialize_compressed(&mut bytes)
+        .expect("serializing into a vector never fails");
+
+    Ok(NativeResult::ok(
+        G::gas_params(gas_params).compress * NumArgs::one(),
+        smallvec![Value::vector_u8(bytes)],
+    ))
+}
+
+fn native_add<G: PairingGroup>(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_element::<G::Affine>(&mut arguments)?;
+    let a = pop_element::<G::Affine>(&mut arguments)?;
+
+    element_result(G::gas_params(gas_params).add, &(a + b).into_affine())
+}
+
+fn native_neg<G: PairingGroup>(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let a = pop_element::<G::Affine>(&mut arguments)?;
+
+    element_result(G::gas_params(gas_params).neg, &(-a))
+}
+
+fn native_scalar_mul_point<G: PairingGroup>(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let scalar = pop_scalar(&mut arguments)?;
+    let point = pop_element::<G::Affine>(&mut arguments)?;
+
+    element_result(
+        G::gas_params(gas_params).scalar_mul,
+        &(point * scalar).into_affine(),
+    )
+}
+
+/***************************************************************************************************
+ * native fun g1_multi_scalar_mul_internal / g2_multi_scalar_mul_internal
+ *
+ *   gas cost: scalar_mul * ceil(n / log_2 n) + add * n
+ *
+ * where n is the number of points, which the caller guarantees matches the number of scalars
+ **************************************************************************************************/
+fn native_multi_scalar_mul<G: PairingGroup>(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let scalars = pop_vec_of_vec_u8(&mut arguments)?
+        .iter()
+        .map(|bytes| from_move_bytes::<Fr>(bytes))
+        .collect::<PartialVMResult<Vec<_>>>()?;
+    let points = pop_vec_of_vec_u8(&mut arguments)?
+        .iter()
+        .map(|bytes| from_move_bytes::<G::Affine>(bytes))
+        .collect::<PartialVMResult<Vec<_>>>()?;
+
+    let result = G::msm(&points, &scalars)
+        .map_err(|_| PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR))?
+        .into_affine();
+
+    let params = G::gas_params(gas_params);
+    let cost = params.scalar_mul * NumArgs::new(msm_num_scalar_muls(points.len()))
+        + params.add * NumArgs::new(points.len() as u64);
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(to_move_bytes(&result))],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun g1_hash_to_internal / g2_hash_to_internal
+ *
+ *   gas cost: hash_to_base + hash_to_per_byte * (|dst| + |msg|)
+ *
+ * The caller is responsible for checking that the domain separation tag is between 1 and 255 bytes.
+ **************************************************************************************************/
+fn native_hash_to<G: PairingGroup>(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let msg = pop_arg!(arguments, Vec<u8>);
+    let dst = pop_arg!(arguments, Vec<u8>);
+
+    let params = G::gas_params(gas_params);
+    let cost = params.hash_to_base * NumArgs::one()
+        + params.hash_to_per_byte * NumBytes::new((dst.len() + msg.len()) as u64);
+
+    match G::hash_to_curve(&dst, &msg) {
+        Some(point) => Ok(NativeResult::ok(
+            cost,
+            smallvec![Value::vector_u8(to_move_bytes(&point))],
+        )),
+        None => Ok(NativeResult::err(cost, EINVALID_DST)),
+    }
+}
+
+/***************************************************************************************************
+ * Gt
+ *
+ **************************************************************************************************/
+
+fn native_gt_identity(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    _arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(_arguments.is_empty());
+
+    element_result(gas_params.gt.constant, &Gt::zero())
+}
+
+fn native_gt_generator(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    _arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(_arguments.is_empty());
+
+    element_result(gas_params.gt.constant, &Gt::generator())
+}
+
+/// Deserializes a Gt element, rejecting elements that are not in the prime-order subgroup of
+/// Fq12.
+fn native_gt_deserialize(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let bytes = pop_arg!(arguments, Vec<u8>);
+    let element = if bytes.len() == Gt::zero().uncompressed_size() {
+        Gt::deserialize_uncompressed(bytes.as_slice()).ok()
+    } else {
+        None
+    };
+
+    optional_element_result(gas_params.gt.deserialize, element)
+}
+
+fn native_gt_mul(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_element::<Gt>(&mut arguments)?;
+    let a = pop_element::<Gt>(&mut arguments)?;
+
+    // NOTE: arkworks writes the group operation of Gt, i.e., multiplication in Fq12, additively.
+    element_result(gas_params.gt.mul, &(a + b))
+}
+
+fn native_gt_inv(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let a = pop_element::<Gt>(&mut arguments)?;
+
+    element_result(gas_params.gt.inv, &(-a))
+}
+
+fn native_gt_pow(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let scalar = pop_scalar(&mut arguments)?;
+    let a = pop_element::<Gt>(&mut arguments)?;
+
+    element_result(gas_params.gt.pow, &(a * scalar))
+}
+
+/***************************************************************************************************
+ * Pairings
+ *
+ **************************************************************************************************/
+
+fn native_pairing(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let g2 = pop_element::<<Bls12_381 as Pairing>::G2Affine>(&mut arguments)?;
+    let g1 = pop_element::<<Bls12_381 as Pairing>::G1Affine>(&mut arguments)?;
+
+    let cost = gas_params.per_miller_loop * NumArgs::one()
+        + gas_params.final_exponentiation * NumArgs::one();
+    let result = Bls12_381::pairing(g1, g2);
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(to_move_bytes(&result))],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun multi_pairing_internal
+ *
+ *   gas cost: per_miller_loop * n + final_exponentiation
+ *
+ * where n is the number of G1 elements, which the caller guarantees matches the number of G2
+ * elements
+ **************************************************************************************************/
+fn native_multi_pairing(
+    gas_params: &GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let g2s = pop_vec_of_vec_u8(&mut arguments)?
+        .iter()
+        .map(|bytes| from_move_bytes::<<Bls12_381 as Pairing>::G2Affine>(bytes))
+        .collect::<PartialVMResult<Vec<_>>>()?;
+    let g1s = pop_vec_of_vec_u8(&mut arguments)?
+        .iter()
+        .map(|bytes| from_move_bytes::<<Bls12_381 as Pairing>::G1Affine>(bytes))
+        .collect::<PartialVMResult<Vec<_>>>()?;
+    if g1s.len() != g2s.len() {
+        return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR));
+    }
+
+    let cost = gas_params.per_miller_loop * NumArgs::new(g1s.len() as u64)
+        + gas_params.final_exponentiation * NumArgs::one();
+    let result = Bls12_381::multi_pairing(g1s, g2s);
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(to_move_bytes(&result))],
+    ))
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [
+        // Scalars
+        (
+            "scalar_is_canonical_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_is_canonical),
+        ),
+        (
+            "scalar_add_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_add),
+        ),
+        (
+            "scalar_sub_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_sub),
+        ),
+        (
+            "scalar_mul_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_mul),
+        ),
+        (
+            "scalar_neg_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_neg),
+        ),
+        (
+            "scalar_inv_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_inv),
+        ),
+        // G1
+        (
+            "g1_identity_internal",
+            make_native_from_func(gas_params.clone(), native_identity::<G1Projective>),
+        ),
+        (
+            "g1_generator_internal",
+            make_native_from_func(gas_params.clone(), native_generator::<G1Projective>),
+        ),
+        (
+            "g1_deserialize_internal",
+            make_native_from_func(gas_params.clone(), native_deserialize::<G1Projective>),
+        ),
+        (
+            "g1_compress_internal",
+            make_native_from_func(gas_params.clone(), native_compress::<G1Projective>),
+        ),
+        (
+            "g1_add_internal",
+            make_native_from_func(gas_params.clone(), native_add::<G1Projective>),
+        ),
+        (
+            "g1_neg_internal",
+            make_native_from_func(gas_params.clone(), native_neg::<G1Projective>),
+        ),
+        (
+            "g1_scalar_mul_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_mul_point::<G1Projective>),
+        ),
+        (
+            "g1_multi_scalar_mul_internal",
+            make_native_from_func(gas_params.clone(), native_multi_scalar_mul::<G1Projective>),
+        ),
+        (
+            "g1_hash_to_internal",
+            make_native_from_func(gas_params.clone(), native_hash_to::<G1Projective>),
+        ),
+        // G2
+        (
+            "g2_identity_internal",
+            make_native_from_func(gas_params.clone(), native_identity::<G2Projective>),
+        ),
+        (
+            "g2_generator_internal",
+            make_native_from_func(gas_params.clone(), native_generator::<G2Projective>),
+        ),
+        (
+            "g2_deserialize_internal",
+            make_native_from_func(gas_params.clone(), native_deserialize::<G2Projective>),
+        ),
+        (
+            "g2_compress_internal",
+            make_native_from_func(gas_params.clone(), native_compress::<G2Projective>),
+        ),
+        (
+            "g2_add_internal",
+            make_native_from_func(gas_params.clone(), native_add::<G2Projective>),
+        ),
+        (
+            "g2_neg_internal",
+            make_native_from_func(gas_params.clone(), native_neg::<G2Projective>),
+        ),
+        (
+            "g2_scalar_mul_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_mul_point::<G2Projective>),
+        ),
+        (
+            "g2_multi_scalar_mul_internal",
+            make_native_from_func(gas_params.clone(), native_multi_scalar_mul::<G2Projective>),
+        ),
+        (
+            "g2_hash_to_internal",
+            make_native_from_func(gas_params.clone(), native_hash_to::<G2Projective>),
+        ),
+        // Gt
+        (
+            "gt_identity_internal",
+            make_native_from_func(gas_params.clone(), native_gt_identity),
+        ),
+        (
+            "gt_generator_internal",
+            make_native_from_func(gas_params.clone(), native_gt_generator),
+        ),
+        (
+            "gt_deserialize_internal",
+            make_native_from_func(gas_params.clone(), native_gt_deserialize),
+        ),
+        (
+            "gt_mul_internal",
+            make_native_from_func(gas_params.clone(), native_gt_mul),
+        ),
+        (
+            "gt_inv_internal",
+            make_native_from_func(gas_params.clone(), native_gt_inv),
+        ),
+        (
+            "gt_pow_internal",
+            make_native_from_func(gas_params.clone(), native_gt_pow),
+        ),
+        // Pairings
+        (
+            "pairing_internal",
+            make_native_from_func(gas_params.clone(), native_pairing),
+        ),
+        (
+            "multi_pairing_internal",
+            make_native_from_func(gas_params, native_multi_pairing),
+        ),
+    ];
+
+    crate::natives::helpers::make_module_natives(natives)
+}
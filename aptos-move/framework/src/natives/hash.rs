@@ -10,9 +10,11 @@ use move_deps::{
         loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
     },
 };
+use ripemd::Ripemd160;
+use sha2_0_10_6::{Digest, Sha512};
 use smallvec::smallvec;
 use std::{collections::VecDeque, hash::Hasher};
-use tiny_keccak::{Hasher as KeccakHasher, Keccak};
+use tiny_keccak::{Hasher as KeccakHasher, Keccak, Sha3};
 
 /***************************************************************************************************
  * native fun sip_hash
@@ -75,6 +77,135 @@ fn native_keccak256(
     Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(output)]))
 }
 
+/***************************************************************************************************
+ * native fun sha2_512_internal
+ *
+ *   gas cost: base_cost + unit_cost * data_length
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct Sha2_512HashGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_sha2_512(
+    gas_params: &Sha2_512HashGasParameters,
+    _context: &mut NativeContext,
+    mut _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let bytes = pop_arg!(args, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(bytes.len() as u64);
+
+    let mut hasher = Sha512::new();
+    hasher.update(&bytes);
+    let output = hasher.finalize().to_vec();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(output)]))
+}
+
+/***************************************************************************************************
+ * native fun sha3_512_internal
+ *
+ *   gas cost: base_cost + unit_cost * data_length
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct Sha3_512HashGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_sha3_512(
+    gas_params: &Sha3_512HashGasParameters,
+    _context: &mut NativeContext,
+    mut _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let bytes = pop_arg!(args, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(bytes.len() as u64);
+
+    let mut hasher = Sha3::v512();
+    hasher.update(&bytes);
+    let mut output = [0u8; 64];
+    hasher.finalize(&mut output);
+
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(output)]))
+}
+
+/***************************************************************************************************
+ * native fun ripemd160_internal
+ *
+ *   gas cost: base_cost + unit_cost * data_length
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct Ripemd160HashGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_ripemd160(
+    gas_params: &Ripemd160HashGasParameters,
+    _context: &mut NativeContext,
+    mut _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let bytes = pop_arg!(args, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(bytes.len() as u64);
+
+    let mut hasher = Ripemd160::new();
+    hasher.update(&bytes);
+    let output = hasher.finalize().to_vec();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(output)]))
+}
+
+/***************************************************************************************************
+ * native fun blake2b_256_internal
+ *
+ *   gas cost: base_cost + unit_cost * data_length
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct Blake2b256HashGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_blake2b_256(
+    gas_params: &Blake2b256HashGasParameters,
+    _context: &mut NativeContext,
+    mut _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let bytes = pop_arg!(args, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(bytes.len() as u64);
+
+    let output = blake2_rfc::blake2b::blake2b(32, &[], &bytes)
+        .as_bytes()
+        .to_vec();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(output)]))
+}
+
 /***************************************************************************************************
  * module
  *
@@ -83,6 +214,10 @@ fn native_keccak256(
 pub struct GasParameters {
     pub sip_hash: SipHashGasParameters,
     pub keccak256: Keccak256HashGasParameters,
+    pub sha2_512: Sha2_512HashGasParameters,
+    pub sha3_512: Sha3_512HashGasParameters,
+    pub ripemd160: Ripemd160HashGasParameters,
+    pub blake2b_256: Blake2b256HashGasParameters,
 }
 
 pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
@@ -95,6 +230,22 @@ pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, Nati
             "keccak256",
             make_native_from_func(gas_params.keccak256, native_keccak256),
         ),
+        (
+            "sha2_512_internal",
+            make_native_from_func(gas_params.sha2_512, native_sha2_512),
+        ),
+        (
+            "sha3_512_internal",
+            make_native_from_func(gas_params.sha3_512, native_sha3_512),
+        ),
+        (
+            "ripemd160_internal",
+            make_native_from_func(gas_params.ripemd160, native_ripemd160),
+        ),
+        (
+            "blake2b_256_internal",
+            make_native_from_func(gas_params.blake2b_256, native_blake2b_256),
+        ),
     ];
 
     crate::natives::helpers::make_module_natives(natives)
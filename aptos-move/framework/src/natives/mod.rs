@@ -36,6 +36,7 @@ pub struct GasParameters {
     pub account: account::GasParameters,
     pub ed25519: ed25519::GasParameters,
     pub bls12381: cryptography::bls12381::GasParameters,
+    pub bls12381_algebra: cryptography::bls12381_algebra::GasParameters,
     pub secp256k1: cryptography::secp256k1::GasParameters,
     pub ristretto255: cryptography::ristretto255::GasParameters,
     pub hash: hash::GasParameters,
@@ -70,6 +71,45 @@ impl GasParameters {
                 per_msg_hashing: 0.into(),
                 per_byte_hashing: 0.into(),
             },
+            bls12381_algebra: cryptography::bls12381_algebra::GasParameters {
+                scalar: cryptography::bls12381_algebra::ScalarGasParameters {
+                    deserialize: 0.into(),
+                    add: 0.into(),
+                    sub: 0.into(),
+                    mul: 0.into(),
+                    neg: 0.into(),
+                    inv: 0.into(),
+                },
+                g1: cryptography::bls12381_algebra::GroupGasParameters {
+                    constant: 0.into(),
+                    deserialize: 0.into(),
+                    compress: 0.into(),
+                    add: 0.into(),
+                    neg: 0.into(),
+                    scalar_mul: 0.into(),
+                    hash_to_base: 0.into(),
+                    hash_to_per_byte: 0.into(),
+                },
+                g2: cryptography::bls12381_algebra::GroupGasParameters {
+                    constant: 0.into(),
+                    deserialize: 0.into(),
+                    compress: 0.into(),
+                    add: 0.into(),
+                    neg: 0.into(),
+                    scalar_mul: 0.into(),
+                    hash_to_base: 0.into(),
+                    hash_to_per_byte: 0.into(),
+                },
+                gt: cryptography::bls12381_algebra::GtGasParameters {
+                    constant: 0.into(),
+                    deserialize: 0.into(),
+                    mul: 0.into(),
+                    inv: 0.into(),
+                    pow: 0.into(),
+                },
+                per_miller_loop: 0.into(),
+                final_exponentiation: 0.into(),
+            },
             ed25519: cryptography::ed25519::GasParameters {
                 base: 0.into(),
                 per_pubkey_deserialize: 0.into(),
@@ -119,6 +159,22 @@ impl GasParameters {
                     base: 0.into(),
                     per_byte: 0.into(),
                 },
+                sha2_512: hash::Sha2_512HashGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                sha3_512: hash::Sha3_512HashGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                ripemd160: hash::Ripemd160HashGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                blake2b_256: hash::Blake2b256HashGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
             },
             type_info: type_info::GasParameters {
                 type_of: type_info::TypeOfGasParameters {
@@ -191,6 +247,10 @@ pub fn all_natives(
         "bls12381",
         cryptography::bls12381::make_all(gas_params.bls12381)
     );
+    add_natives_from_module!(
+        "bls12381_algebra",
+        cryptography::bls12381_algebra::make_all(gas_params.bls12381_algebra)
+    );
     add_natives_from_module!(
         "secp256k1",
         cryptography::secp256k1::make_all(gas_params.secp256k1)
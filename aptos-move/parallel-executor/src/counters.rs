@@ -0,0 +1,38 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_metrics_core::{
+    register_int_counter, register_int_counter_vec, IntCounter, IntCounterVec,
+};
+use once_cell::sync::Lazy;
+
+/// Count of transaction incarnations aborted after a failed validation, each of which is
+/// re-executed
+pub static PARALLEL_EXECUTION_ABORTS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_parallel_executor_aborts",
+        "Number of transaction incarnations aborted after a failed validation"
+    )
+    .unwrap()
+});
+
+/// Count of transactions whose first execution was deferred due to a predicted dependency
+pub static HINTED_EXECUTION_DEFERRALS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_parallel_executor_hinted_execution_deferrals",
+        "Number of first executions deferred until a predicted dependency was executed"
+    )
+    .unwrap()
+});
+
+/// Count of dependency predictions by outcome, compared against the actual dependencies
+/// (i.e. reads of values written by prior transactions in the block) of committed transactions.
+/// Outcomes are 'hit', 'false_positive', 'miss', 'true_negative' and 'unknown' (no prediction).
+pub static DEPENDENCY_HINT_OUTCOMES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_parallel_executor_dependency_hint_outcomes",
+        "Number of predicted dependencies by outcome, compared to the actual dependencies",
+        &["outcome"]
+    )
+    .unwrap()
+});
@@ -0,0 +1,82 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{scheduler::TxnIndex, task::Accesses};
+use std::{collections::HashMap, hash::Hash};
+
+/// The dependency predicted for a transaction before the block is executed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DependencyHint {
+    /// The accesses of the transaction could not be predicted.
+    Unknown,
+    /// The transaction is not expected to read anything written by a prior transaction.
+    Independent,
+    /// The transaction is expected to read a value written by the given (prior) transaction.
+    /// If multiple prior transactions qualify, the one with the highest index is used, as it
+    /// is the last one to be executed (in the preset order).
+    DependsOn(TxnIndex),
+}
+
+/// Dependency hints for all transactions in a block, derived from the predicted
+/// accesses of the transactions. The hints are only used to order the first
+/// execution of transactions, so they need not be sound: a missing dependency is
+/// detected (and handled) by validation as usual, and an unnecessary one only
+/// delays the execution of a transaction.
+#[derive(Clone, Debug)]
+pub struct DependencyHints {
+    hints: Vec<DependencyHint>,
+}
+
+impl DependencyHints {
+    /// Creates hints that don't predict anything, i.e. all transactions are
+    /// scheduled optimistically.
+    pub fn unknown(num_txns: usize) -> Self {
+        Self {
+            hints: vec![DependencyHint::Unknown; num_txns],
+        }
+    }
+
+    /// Creates the hints from the predicted accesses of each transaction (if any).
+    /// A transaction depends on the last prior transaction that is predicted to
+    /// write a key that the transaction is predicted to read or write.
+    pub fn from_accesses<K: Clone + Eq + Hash>(accesses: Vec<Option<Accesses<K>>>) -> Self {
+        let mut last_writers: HashMap<K, TxnIndex> = HashMap::new();
+        let hints = accesses
+            .into_iter()
+            .enumerate()
+            .map(|(txn_idx, accesses)| match accesses {
+                None => DependencyHint::Unknown,
+                Some(accesses) => {
+                    let dependency = accesses
+                        .keys_read
+                        .iter()
+                        .chain(accesses.keys_written.iter())
+                        .filter_map(|key| last_writers.get(key).copied())
+                        .max();
+                    for key in accesses.keys_written {
+                        last_writers.insert(key, txn_idx);
+                    }
+                    dependency.map_or(DependencyHint::Independent, DependencyHint::DependsOn)
+                }
+            })
+            .collect();
+
+        Self { hints }
+    }
+
+    /// Returns the hint of the given transaction.
+    pub fn hint(&self, txn_idx: TxnIndex) -> DependencyHint {
+        self.hints
+            .get(txn_idx)
+            .copied()
+            .unwrap_or(DependencyHint::Unknown)
+    }
+
+    /// Returns the predicted dependency of the given transaction (if any).
+    pub fn dependency(&self, txn_idx: TxnIndex) -> Option<TxnIndex> {
+        match self.hint(txn_idx) {
+            DependencyHint::DependsOn(dep_txn_idx) => Some(dep_txn_idx),
+            _ => None,
+        }
+    }
+}
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    counters,
+    dependency_hints::{DependencyHint, DependencyHints},
     errors::*,
     output_delta_resolver::OutputDeltaResolver,
     scheduler::{Scheduler, SchedulerTask, TaskGuard, TxnIndex, Version},
@@ -14,6 +16,7 @@ use aptos_types::write_set::TransactionWrite;
 use mvhashmap::{MVHashMap, MVHashMapError, MVHashMapOutput};
 use num_cpus;
 use once_cell::sync::Lazy;
+use rayon::prelude::*;
 use std::{hash::Hash, marker::PhantomData, sync::Arc, thread::spawn};
 
 static RAYON_EXEC_POOL: Lazy<rayon::ThreadPool> = Lazy::new(|| {
@@ -291,6 +294,8 @@ where
         let aborted = !valid && scheduler.try_abort(idx_to_validate, incarnation);
 
         if aborted {
+            counters::PARALLEL_EXECUTION_ABORTS.inc();
+
             // Not valid and successfully aborted, mark the latest write/delta sets as estimates.
             for k in last_input_output.modified_keys(idx_to_validate) {
                 versioned_data_cache.mark_estimate(&k, idx_to_validate);
@@ -372,7 +377,16 @@ where
 
         let num_txns = signature_verified_block.len();
         let last_input_output = TxnLastInputOutput::new(num_txns);
-        let scheduler = Scheduler::new(num_txns);
+
+        // Predict the accesses of all transactions to order likely conflicts up front.
+        let predicted_accesses = RAYON_EXEC_POOL.install(|| {
+            signature_verified_block
+                .par_iter()
+                .map(|txn| E::predict_accesses(executor_initial_arguments, txn))
+                .collect()
+        });
+        let dependency_hints = DependencyHints::from_accesses(predicted_accesses);
+        let scheduler = Scheduler::new_with_dependency_hints(num_txns, dependency_hints.clone());
 
         RAYON_EXEC_POOL.scope(|s| {
             for _ in 0..self.concurrency_level {
@@ -411,6 +425,13 @@ where
             }
             ret
         };
+        if maybe_err.is_none() {
+            update_dependency_hint_counters(
+                &dependency_hints,
+                &last_input_output,
+                final_results.len(),
+            );
+        }
 
         spawn(move || {
             // Explicit async drops.
@@ -431,3 +452,38 @@ where
         }
     }
 }
+
+/// Compares the predicted dependencies of the committed transactions with their actual
+/// dependencies (i.e. whether they read a value written by a prior transaction in the block).
+fn update_dependency_hint_counters<K: ModulePath, T: TransactionOutput, E: Send + Clone>(
+    dependency_hints: &DependencyHints,
+    last_input_output: &TxnLastInputOutput<K, T, E>,
+    num_committed_txns: usize,
+) {
+    let (mut hits, mut false_positives, mut misses, mut true_negatives, mut unknowns) =
+        (0, 0, 0, 0, 0);
+    for txn_idx in 0..num_committed_txns {
+        let has_dependency = last_input_output.read_set(txn_idx).map_or(false, |reads| {
+            reads.iter().any(|r| r.written_by().is_some())
+        });
+        match (dependency_hints.hint(txn_idx), has_dependency) {
+            (DependencyHint::Unknown, _) => unknowns += 1,
+            (DependencyHint::DependsOn(_), true) => hits += 1,
+            (DependencyHint::DependsOn(_), false) => false_positives += 1,
+            (DependencyHint::Independent, true) => misses += 1,
+            (DependencyHint::Independent, false) => true_negatives += 1,
+        }
+    }
+
+    for (outcome, count) in [
+        ("hit", hits),
+        ("false_positive", false_positives),
+        ("miss", misses),
+        ("true_negative", true_negatives),
+        ("unknown", unknowns),
+    ] {
+        counters::DEPENDENCY_HINT_OUTCOMES
+            .with_label_values(&[outcome])
+            .inc_by(count);
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     errors::{Error, Result},
     executor::{MVHashMapView, ReadResult},
     task::{
-        ExecutionStatus, ExecutorTask, ModulePath, Transaction as TransactionType,
+        Accesses, ExecutionStatus, ExecutorTask, ModulePath, Transaction as TransactionType,
         TransactionOutput,
     },
 };
@@ -353,6 +353,26 @@ where
         Self::new()
     }
 
+    // Predict the accesses of the first incarnation, so that all tests also exercise
+    // the scheduling based on dependency hints.
+    fn predict_accesses(_argument: Self::Argument, txn: &Self::T) -> Option<Accesses<K>> {
+        match txn {
+            Transaction::Write {
+                reads,
+                writes_and_deltas,
+                ..
+            } => Some(Accesses {
+                keys_read: reads[0].clone(),
+                keys_written: writes_and_deltas[0]
+                    .0
+                    .iter()
+                    .map(|(k, _)| k.clone())
+                    .collect(),
+            }),
+            Transaction::SkipRest | Transaction::Abort => None,
+        }
+    }
+
     fn execute_transaction(
         &self,
         view: &MVHashMapView<K, V>,
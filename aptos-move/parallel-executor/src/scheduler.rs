@@ -1,6 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::{counters, dependency_hints::DependencyHints};
 use aptos_infallible::Mutex;
 use crossbeam::utils::CachePadded;
 use std::{
@@ -135,11 +136,25 @@ pub struct Scheduler {
     txn_dependency: Vec<CachePadded<Mutex<Vec<TxnIndex>>>>,
     /// An index i maps to the most up-to-date status of transaction i.
     txn_status: Vec<CachePadded<Mutex<TransactionStatus>>>,
+
+    /// Dependencies predicted before execution. The first incarnation of a transaction with
+    /// a predicted dependency isn't executed until the dependency has been executed.
+    dependency_hints: DependencyHints,
+    /// An index i maps to indices of other transactions whose first execution was deferred
+    /// because they are predicted to depend on transaction i. They are scheduled for execution
+    /// again once transaction i finishes its execution.
+    txn_hinted_dependents: Vec<CachePadded<Mutex<Vec<TxnIndex>>>>,
 }
 
 /// Public Interfaces for the Scheduler
 impl Scheduler {
     pub fn new(num_txns: usize) -> Self {
+        Self::new_with_dependency_hints(num_txns, DependencyHints::unknown(num_txns))
+    }
+
+    /// Creates a scheduler that uses the given hints to order the first executions of
+    /// transactions that are predicted to conflict.
+    pub fn new_with_dependency_hints(num_txns: usize, dependency_hints: DependencyHints) -> Self {
         Self {
             num_txns,
             execution_idx: AtomicUsize::new(0),
@@ -153,6 +168,10 @@ impl Scheduler {
             txn_status: (0..num_txns)
                 .map(|_| CachePadded::new(Mutex::new(TransactionStatus::ReadyToExecute(0, None))))
                 .collect(),
+            dependency_hints,
+            txn_hinted_dependents: (0..num_txns)
+                .map(|_| CachePadded::new(Mutex::new(Vec::new())))
+                .collect(),
         }
     }
 
@@ -227,8 +246,9 @@ impl Scheduler {
                 // not add a (stale) dependency.
 
                 // Note: acquires (a different, status) mutex, while holding (dependency) mutex.
-                // Only place in scheduler where a thread may hold >1 mutexes, hence, such
-                // acquisitions always happens in the same order (this function), may not deadlock.
+                // Only places in scheduler where a thread may hold >1 mutexes (together with
+                // 'try_defer_to_hinted_dependency'), and the status mutex is always acquired
+                // last, hence, such acquisitions may not deadlock.
 
                 return None;
             }
@@ -261,6 +281,10 @@ impl Scheduler {
             // Holding the lock, take dependency vector.
             std::mem::take(&mut stored_deps)
         };
+        let hinted_deps: Vec<TxnIndex> = {
+            let mut stored_deps = self.txn_hinted_dependents[txn_idx].lock();
+            std::mem::take(&mut stored_deps)
+        };
 
         // Mark dependencies as resolved and find the minimum index among them.
         let min_dep = txn_deps
@@ -272,6 +296,7 @@ impl Scheduler {
 
                 dep
             })
+            .chain(hinted_deps)
             .min();
         if let Some(execution_target_idx) = min_dep {
             // Decrease the execution index as necessary to ensure resolved dependencies
@@ -437,6 +462,12 @@ impl Scheduler {
 
         let idx_to_execute = self.execution_idx.fetch_add(1, Ordering::SeqCst);
 
+        // If the transaction is predicted to depend on a transaction that hasn't been
+        // executed yet, defer its execution instead of (likely) having to abort it.
+        if self.try_defer_to_hinted_dependency(idx_to_execute) {
+            return None;
+        }
+
         // If successfully incarnated (changed status from ready to executing),
         // return version and guard for execution task, otherwise None.
         self.try_incarnate(idx_to_execute)
@@ -445,6 +476,36 @@ impl Scheduler {
             })
     }
 
+    /// Defers the first execution of a transaction if it has a predicted dependency that
+    /// hasn't been executed yet. Returns true if the execution was deferred, in which case
+    /// Scheduler guarantees that the transaction will be considered for execution again once
+    /// the dependency finishes its execution. Later incarnations are never deferred, as
+    /// their actual dependencies are already handled by validation and 'wait_for_dependency'.
+    fn try_defer_to_hinted_dependency(&self, txn_idx: TxnIndex) -> bool {
+        let dep_txn_idx = match self.dependency_hints.dependency(txn_idx) {
+            Some(dep_txn_idx) if txn_idx < self.num_txns => dep_txn_idx,
+            _ => return false,
+        };
+
+        // Only the first incarnation (that isn't resuming from a dependency) is deferred.
+        if !matches!(
+            &*self.txn_status[txn_idx].lock(),
+            TransactionStatus::ReadyToExecute(0, None)
+        ) {
+            return false;
+        }
+
+        let mut stored_deps = self.txn_hinted_dependents[dep_txn_idx].lock();
+        // Same as in 'wait_for_dependency': if the dependency got executed in the meantime,
+        // the deferral isn't needed (and wouldn't be resolved).
+        if self.is_executed(dep_txn_idx).is_some() {
+            return false;
+        }
+        stored_deps.push(txn_idx);
+        counters::HINTED_EXECUTION_DEFERRALS.inc();
+        true
+    }
+
     /// Put a transaction in a suspended state, with a condition variable that can be
     /// used to wake it up after the dependency is resolved.
     fn suspend(&self, txn_idx: TxnIndex, dep_condvar: DependencyCondvar) {
@@ -68,6 +68,16 @@ pub trait ExecutorTask: Sync {
     /// Create an instance of the transaction executor.
     fn init(args: Self::Argument) -> Self;
 
+    /// Predict the keys a transaction will read and write, before the block is executed. The
+    /// prediction is only used as a scheduling hint and needn't be sound, returns None if the
+    /// accesses of the transaction can't be predicted.
+    fn predict_accesses(
+        _args: Self::Argument,
+        _txn: &Self::T,
+    ) -> Option<Accesses<<Self::T as Transaction>::Key>> {
+        None
+    }
+
     /// Execute one single transaction given the view of the current state.
     fn execute_transaction(
         &self,
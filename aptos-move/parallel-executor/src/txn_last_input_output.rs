@@ -91,6 +91,15 @@ impl<K: ModulePath> ReadDescriptor<K> {
         &self.access_path
     }
 
+    // The index of the transaction that wrote the value returned by the read, if the value
+    // was written by a (prior) transaction in the block.
+    pub fn written_by(&self) -> Option<TxnIndex> {
+        match self.kind {
+            ReadKind::Version(txn_idx, _) => Some(txn_idx),
+            _ => None,
+        }
+    }
+
     // Does the read descriptor describe a read from MVHashMap w. a specified version.
     pub fn validate_version(&self, version: Version) -> bool {
         let (txn_idx, incarnation) = version;
@@ -2,13 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    dependency_hints::{DependencyHint, DependencyHints},
     executor::ParallelTransactionExecutor,
+    output_delta_resolver::OutputDeltaResolver,
     proptest_types::types::{ExpectedOutput, KeyType, Task, Transaction, ValueType},
     scheduler::{Scheduler, SchedulerTask, TaskGuard},
-    task::ModulePath,
+    task::{Accesses, ModulePath},
 };
-use aptos_aggregator::delta_change_set::{delta_add, delta_sub, DeltaOp, DeltaUpdate};
-use aptos_types::write_set::TransactionWrite;
+use aptos_aggregator::delta_change_set::{delta_add, delta_sub, serialize, DeltaOp, DeltaUpdate};
+use aptos_types::write_set::{TransactionWrite, WriteOp};
+use mvhashmap::MVHashMap;
 use rand::random;
 use std::{
     fmt::Debug,
@@ -155,6 +158,43 @@ fn delta_chains() {
 const TOTAL_KEY_NUM: u64 = 50;
 const WRITES_PER_KEY: u64 = 100;
 
+#[test]
+fn resolve_deltas() {
+    // Mirrors per-block fee collection: the first transaction drains the aggregator with a
+    // write, and every following transaction adds its fee with a delta.
+    let (drained, untouched) = (
+        KeyType(random::<[u8; 32]>(), false),
+        KeyType(random::<[u8; 32]>(), false),
+    );
+    let versioned_outputs: MVHashMap<_, ValueType<Vec<u8>>> = MVHashMap::new();
+    versioned_outputs.add_write(&drained, (0, 0), ValueType(serialize(&0), true));
+    for idx in 1..10 {
+        versioned_outputs.add_delta(&drained, idx, delta_add(idx as u128, u128::MAX));
+        versioned_outputs.add_delta(&untouched, idx, delta_add(1, u128::MAX));
+    }
+
+    let resolved = OutputDeltaResolver::new(versioned_outputs).resolve(
+        vec![
+            (drained, Ok(Some(serialize(&100)))),
+            (untouched, Ok(Some(serialize(&100)))),
+        ],
+        10,
+    );
+
+    // The write itself isn't part of the materialized deltas.
+    assert!(resolved[0].is_empty());
+    let mut drained_value = 0;
+    for (idx, writes) in resolved.iter().enumerate().skip(1) {
+        drained_value += idx as u128;
+        assert_eq!(writes.len(), 2);
+        assert!(writes.contains(&(drained, WriteOp::Modification(serialize(&drained_value)))));
+        assert!(writes.contains(&(
+            untouched,
+            WriteOp::Modification(serialize(&(100 + idx as u128)))
+        )));
+    }
+}
+
 #[test]
 fn cycle_transactions() {
     let mut transactions = vec![];
@@ -415,6 +455,82 @@ fn scheduler_dependency() {
     ));
 }
 
+#[test]
+fn dependency_hints() {
+    let hints = DependencyHints::from_accesses(vec![
+        Some(Accesses {
+            keys_read: vec![],
+            keys_written: vec![1, 2],
+        }),
+        Some(Accesses {
+            keys_read: vec![3],
+            keys_written: vec![2],
+        }),
+        Some(Accesses {
+            keys_read: vec![1],
+            keys_written: vec![],
+        }),
+        None,
+        Some(Accesses {
+            keys_read: vec![2],
+            keys_written: vec![],
+        }),
+    ]);
+
+    assert_eq!(hints.hint(0), DependencyHint::Independent);
+    // Write-write conflicts are also hinted.
+    assert_eq!(hints.hint(1), DependencyHint::DependsOn(0));
+    assert_eq!(hints.hint(2), DependencyHint::DependsOn(0));
+    assert_eq!(hints.hint(3), DependencyHint::Unknown);
+    // The last writer is the dependency.
+    assert_eq!(hints.hint(4), DependencyHint::DependsOn(1));
+    assert_eq!(hints.dependency(4), Some(1));
+    assert_eq!(hints.dependency(5), None);
+}
+
+#[test]
+fn scheduler_dependency_hints() {
+    let hints = DependencyHints::from_accesses(vec![
+        Some(Accesses {
+            keys_read: vec![],
+            keys_written: vec![1],
+        }),
+        Some(Accesses {
+            keys_read: vec![1],
+            keys_written: vec![],
+        }),
+        Some(Accesses {
+            keys_read: vec![2],
+            keys_written: vec![],
+        }),
+    ]);
+    let s = Scheduler::new_with_dependency_hints(3, hints);
+    let fake_counter = AtomicUsize::new(0);
+
+    // Keep the task (and its guard) alive, as txn 0 is being executed.
+    let task_0 = s.next_task();
+    assert!(matches!(
+        task_0,
+        SchedulerTask::ExecutionTask((0, 0), None, _)
+    ));
+    // txn 1 is deferred as it is predicted to depend on txn 0.
+    assert!(matches!(
+        s.next_task(),
+        SchedulerTask::ExecutionTask((2, 0), None, _)
+    ));
+
+    // Finishing txn 0 makes txn 1 executable again.
+    assert!(matches!(
+        s.finish_execution(0, 0, false, TaskGuard::new(&fake_counter)),
+        SchedulerTask::ValidationTask((0, 0), _)
+    ));
+    assert!(matches!(
+        s.next_task(),
+        SchedulerTask::ExecutionTask((1, 0), None, _)
+    ));
+    drop(task_0);
+}
+
 #[test]
 fn scheduler_incarnation() {
     let s = Scheduler::new(5);
@@ -20,7 +20,9 @@ use aptos_types::{
     account_config::{self, events::NewEpochEvent, CORE_CODE_ADDRESS},
     chain_id::ChainId,
     contract_event::ContractEvent,
-    on_chain_config::{ConsensusConfigV1, OnChainConsensusConfig, APTOS_MAX_KNOWN_VERSION},
+    on_chain_config::{
+        ConsensusConfigV1, GasSchedule, OnChainConsensusConfig, APTOS_MAX_KNOWN_VERSION,
+    },
     transaction::{authenticator::AuthenticationKey, ChangeSet, Transaction, WriteSetPayload},
 };
 use aptos_vm::{
@@ -49,6 +51,7 @@ const GENESIS_MODULE_NAME: &str = "genesis";
 const GOVERNANCE_MODULE_NAME: &str = "aptos_governance";
 const CODE_MODULE_NAME: &str = "code";
 const VERSION_MODULE_NAME: &str = "version";
+const TRANSACTION_FEE_MODULE_NAME: &str = "transaction_fee";
 
 const NUM_SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
 const MICRO_SECONDS_PER_SECOND: u64 = 1_000_000;
@@ -65,6 +68,9 @@ pub struct GenesisConfiguration {
     pub recurring_lockup_duration_secs: u64,
     pub required_proposer_stake: u64,
     pub rewards_apy_percentage: u64,
+    // If set, transaction fees are collected and distributed to block proposers, with the given
+    // percentage of fees being burnt. Otherwise, all transaction fees are burnt.
+    pub transaction_fee_burn_percentage: Option<u8>,
     pub voting_duration_secs: u64,
     pub voting_power_increase_limit: u64,
 }
@@ -87,19 +93,30 @@ pub fn encode_genesis_transaction(
 
     Transaction::GenesisTransaction(WriteSetPayload::Direct(encode_genesis_change_set(
         &aptos_root_key,
+        &[],
         validators,
         framework,
         consensus_config,
+        &default_gas_schedule(),
         chain_id,
         &genesis_config,
     )))
 }
 
+/// The gas schedule used at genesis, unless another one is given
+pub fn default_gas_schedule() -> GasSchedule {
+    GasSchedule {
+        entries: AptosGasParameters::initial().to_on_chain_gas_schedule(),
+    }
+}
+
 pub fn encode_genesis_change_set(
     core_resources_key: &Ed25519PublicKey,
+    accounts: &[AccountBalance],
     validators: &[Validator],
     framework: &ReleaseBundle,
     consensus_config: OnChainConsensusConfig,
+    gas_schedule: &GasSchedule,
     chain_id: ChainId,
     genesis_config: &GenesisConfiguration,
 ) -> ChangeSet {
@@ -120,13 +137,23 @@ pub fn encode_genesis_change_set(
     let mut session = move_vm.new_session(&data_cache, SessionId::genesis(id1));
 
     // On-chain genesis process.
-    initialize(&mut session, consensus_config, chain_id, genesis_config);
+    initialize(
+        &mut session,
+        consensus_config,
+        gas_schedule,
+        chain_id,
+        genesis_config,
+    );
     if genesis_config.is_test {
         initialize_core_resources_and_aptos_coin(&mut session, core_resources_key);
     } else {
         initialize_aptos_coin(&mut session);
     }
     initialize_on_chain_governance(&mut session, genesis_config);
+    if let Some(burn_percentage) = genesis_config.transaction_fee_burn_percentage {
+        initialize_fee_collection_and_distribution(&mut session, burn_percentage);
+    }
+    create_accounts(&mut session, accounts);
     create_and_initialize_validators(&mut session, validators);
     if genesis_config.is_test {
         allow_core_resources_to_set_version(&mut session);
@@ -209,6 +236,12 @@ fn validate_genesis_config(genesis_config: &GenesisConfiguration) {
             && genesis_config.voting_power_increase_limit <= 50,
         "voting_power_increase_limit must be > 0 and <= 50"
     );
+    if let Some(burn_percentage) = genesis_config.transaction_fee_burn_percentage {
+        assert!(
+            burn_percentage <= 100,
+            "Transaction fee burn percentage must be <= 100"
+        );
+    }
 }
 
 fn exec_function(
@@ -242,12 +275,12 @@ fn exec_function(
 fn initialize(
     session: &mut SessionExt<impl MoveResolver>,
     consensus_config: OnChainConsensusConfig,
+    gas_schedule: &GasSchedule,
     chain_id: ChainId,
     genesis_config: &GenesisConfiguration,
 ) {
-    let genesis_gas_params = AptosGasParameters::initial();
-    let gas_schedule_blob = bcs::to_bytes(&genesis_gas_params.to_on_chain_gas_schedule())
-        .expect("Failure serializing genesis gas schedule");
+    let gas_schedule_blob =
+        bcs::to_bytes(gas_schedule).expect("Failure serializing genesis gas schedule");
 
     let consensus_config_bytes =
         bcs::to_bytes(&consensus_config).expect("Failure serializing genesis consensus config");
@@ -296,6 +329,22 @@ fn initialize_aptos_coin(session: &mut SessionExt<impl MoveResolver>) {
     );
 }
 
+fn initialize_fee_collection_and_distribution(
+    session: &mut SessionExt<impl MoveResolver>,
+    burn_percentage: u8,
+) {
+    exec_function(
+        session,
+        TRANSACTION_FEE_MODULE_NAME,
+        "initialize_fee_collection_and_distribution",
+        vec![],
+        serialize_values(&vec![
+            MoveValue::Signer(CORE_CODE_ADDRESS),
+            MoveValue::U8(burn_percentage),
+        ]),
+    );
+}
+
 fn set_genesis_end(session: &mut SessionExt<impl MoveResolver>) {
     exec_function(
         session,
@@ -342,6 +391,21 @@ fn initialize_on_chain_governance(
     );
 }
 
+/// Creates each account and mints it its initial balance. This has to happen before the validators
+/// are initialized, as that destroys the mint capability.
+fn create_accounts(session: &mut SessionExt<impl MoveResolver>, accounts: &[AccountBalance]) {
+    let accounts_bytes = bcs::to_bytes(accounts).expect("Accounts can be serialized");
+    let mut serialized_values = serialize_values(&vec![MoveValue::Signer(CORE_CODE_ADDRESS)]);
+    serialized_values.push(accounts_bytes);
+    exec_function(
+        session,
+        GENESIS_MODULE_NAME,
+        "create_accounts",
+        vec![],
+        serialized_values,
+    );
+}
+
 /// Creates and initializes each validator owner and validator operator. This method creates all
 /// the required accounts, sets the validator operators for each validator owner, and sets the
 /// validator config on-chain.
@@ -485,6 +549,14 @@ pub fn test_genesis_change_set_and_validators(
     generate_test_genesis(cached_packages::head_release_bundle(), count)
 }
 
+/// An account to create at genesis, along with the amount of coins minted to it.
+/// Serialized as the `AccountMap` struct in `genesis.move`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountBalance {
+    pub account_address: AccountAddress,
+    pub balance: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Validator {
     /// The Aptos account address of the validator.
@@ -565,9 +637,11 @@ pub fn generate_test_genesis(
 
     let genesis = encode_genesis_change_set(
         &GENESIS_KEYPAIR.1,
+        &[],
         validators,
         framework,
         OnChainConsensusConfig::default(),
+        &default_gas_schedule(),
         ChainId::test(),
         &GenesisConfiguration {
             allow_new_validators: true,
@@ -580,6 +654,7 @@ pub fn generate_test_genesis(
             recurring_lockup_duration_secs: 7200,
             required_proposer_stake: 0,
             rewards_apy_percentage: 10,
+            transaction_fee_burn_percentage: None,
             voting_duration_secs: 3600,
             voting_power_increase_limit: 50,
         },
@@ -598,9 +673,11 @@ pub fn generate_mainnet_genesis(
 
     let genesis = encode_genesis_change_set(
         &GENESIS_KEYPAIR.1,
+        &[],
         validators,
         framework,
         OnChainConsensusConfig::default(),
+        &default_gas_schedule(),
         ChainId::test(),
         // TODO: Update once mainnet numbers are decided. These numbers are just placeholders.
         &GenesisConfiguration {
@@ -614,6 +691,7 @@ pub fn generate_mainnet_genesis(
             recurring_lockup_duration_secs: 30 * 24 * 3600,         // 1 month
             required_proposer_stake: 1_000_000 * APTOS_COINS_BASE_WITH_DECIMALS, // 1M APT
             rewards_apy_percentage: 10,
+            transaction_fee_burn_percentage: None,
             voting_duration_secs: 7 * 24 * 3600, // 7 days
             voting_power_increase_limit: 30,
         },
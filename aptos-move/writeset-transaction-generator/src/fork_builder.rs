@@ -0,0 +1,186 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Builds the writeset which forks an existing chain: the validator set is handed over to a set of
+//! local validators, so that a copy of the chain's state can be run as a separate local network.
+
+use crate::writeset_builder::build_reconfiguration_changeset;
+use anyhow::{ensure, format_err, Result};
+use aptos_state_view::{
+    account_with_state_view::AsAccountWithStateView, overlay_state_view::OverlayStateView,
+    StateView,
+};
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_config::{reserved_vm_address, ChainIdResource, CORE_CODE_ADDRESS},
+    account_view::AccountView,
+    chain_id::ChainId,
+    on_chain_config::{access_path_for_config, OnChainConfig, ValidatorSet},
+    stake_pool::StakePool,
+    state_store::state_key::StateKey,
+    timestamp::TimestampResource,
+    transaction::{authenticator::AuthenticationKey, ChangeSet},
+    write_set::WriteOp,
+};
+use move_deps::move_core_types::{
+    move_resource::MoveResource,
+    value::{serialize_values, MoveValue},
+};
+
+/// A local validator which takes over one of the forked chain's stake pools
+#[derive(Clone, Debug)]
+pub struct ForkValidator {
+    /// bls12381 public key used to sign consensus messages.
+    pub consensus_pubkey: Vec<u8>,
+    /// Proof of Possession of the consensus pubkey.
+    pub proof_of_possession: Vec<u8>,
+    /// `NetworkAddress` for the validator.
+    pub network_addresses: Vec<u8>,
+    /// `NetworkAddress` for the validator's full node.
+    pub full_node_network_addresses: Vec<u8>,
+    /// If set, the pool's delegated voter account is rotated to this authentication key, so that
+    /// governance proposals can be voted on from the fork.
+    pub voter_authentication_key: Option<AuthenticationKey>,
+}
+
+/// Builds the fork `ChangeSet` on top of `state_view`.
+///
+/// The stake pools with the most voting power are taken over by `validators`, by rotating their
+/// consensus keys and network addresses.  All other validators are removed from the validator set,
+/// and a new epoch is started so the new set takes effect right away.  The fork also gets its own
+/// `chain_id`, so transactions signed for the original chain can't be replayed on it.
+///
+/// Returns the change set along with the addresses of the stake pools taken over, in the same order
+/// as `validators`.
+pub fn build_fork_changeset<S: StateView>(
+    state_view: &S,
+    chain_id: ChainId,
+    validators: &[ForkValidator],
+) -> Result<(ChangeSet, Vec<AccountAddress>)> {
+    ensure!(
+        !validators.is_empty(),
+        "At least one validator is needed to fork the network"
+    );
+    let framework_account = state_view.as_account_with_state_view(&CORE_CODE_ADDRESS);
+    let mut validator_set = framework_account
+        .get_validator_set()?
+        .ok_or_else(|| format_err!("ValidatorSet not found, is the state empty?"))?;
+    let now_microseconds = framework_account
+        .get_move_resource::<TimestampResource>()?
+        .ok_or_else(|| format_err!("Timestamp not found, is the state empty?"))?
+        .timestamp
+        .microseconds;
+
+    // Keep the pools with the most voting power, so the fork's stake distribution stays close to
+    // the original one
+    let mut active_validators = std::mem::take(&mut validator_set.active_validators);
+    ensure!(
+        active_validators.len() >= validators.len(),
+        "Can't fork with {} validators, there are only {} active validators",
+        validators.len(),
+        active_validators.len()
+    );
+    active_validators.sort_by_key(|info| std::cmp::Reverse(info.consensus_voting_power()));
+    let removed_validators = active_validators.split_off(validators.len());
+
+    // Everyone else leaves the set at the next epoch, and nobody else joins it
+    validator_set.active_validators = active_validators;
+    validator_set.pending_inactive.extend(removed_validators);
+    validator_set.pending_active.clear();
+    validator_set.total_joining_power = 0;
+
+    let mut pools = Vec::new();
+    for info in &validator_set.active_validators {
+        let stake_pool = state_view
+            .as_account_with_state_view(&info.account_address)
+            .get_move_resource::<StakePool>()?
+            .ok_or_else(|| format_err!("StakePool not found for {}", info.account_address))?;
+        pools.push((
+            info.account_address,
+            stake_pool.operator_address,
+            stake_pool.delegated_voter,
+        ));
+    }
+
+    let validator_set_key = StateKey::AccessPath(access_path_for_config(ValidatorSet::CONFIG_ID));
+    let chain_id_key = StateKey::AccessPath(AccessPath::new(
+        CORE_CODE_ADDRESS,
+        ChainIdResource::resource_path(),
+    ));
+    let mut fork_state_view = OverlayStateView::new(state_view);
+    fork_state_view.set(validator_set_key, Some(bcs::to_bytes(&validator_set)?));
+    fork_state_view.set(chain_id_key, Some(bcs::to_bytes(&chain_id.id())?));
+
+    let change_set = build_reconfiguration_changeset(&fork_state_view, |session| {
+        // Time has to move forward for the reconfiguration to happen, in case the state was taken
+        // right at an epoch change
+        session.exec_func(
+            "timestamp",
+            "update_global_time",
+            vec![],
+            serialize_values(&vec![
+                MoveValue::Signer(reserved_vm_address()),
+                MoveValue::Address(pools[0].0),
+                MoveValue::U64(now_microseconds + 1),
+            ]),
+        );
+
+        for ((pool_address, operator_address, voter_address), validator) in
+            pools.iter().zip(validators)
+        {
+            session.exec_func(
+                "stake",
+                "rotate_consensus_key",
+                vec![],
+                serialize_values(&vec![
+                    MoveValue::Signer(*operator_address),
+                    MoveValue::Address(*pool_address),
+                    MoveValue::vector_u8(validator.consensus_pubkey.clone()),
+                    MoveValue::vector_u8(validator.proof_of_possession.clone()),
+                ]),
+            );
+            session.exec_func(
+                "stake",
+                "update_network_and_fullnode_addresses",
+                vec![],
+                serialize_values(&vec![
+                    MoveValue::Signer(*operator_address),
+                    MoveValue::Address(*pool_address),
+                    MoveValue::vector_u8(validator.network_addresses.clone()),
+                    MoveValue::vector_u8(validator.full_node_network_addresses.clone()),
+                ]),
+            );
+            if let Some(authentication_key) = &validator.voter_authentication_key {
+                session.exec_func(
+                    "account",
+                    "rotate_authentication_key_internal",
+                    vec![],
+                    serialize_values(&vec![
+                        MoveValue::Signer(*voter_address),
+                        MoveValue::vector_u8(authentication_key.to_vec()),
+                    ]),
+                );
+            }
+        }
+
+        session.exec_func("reconfiguration", "reconfigure", vec![], vec![]);
+    });
+
+    // The overridden validator set is always rewritten by the new epoch, but the chain id isn't,
+    // so make sure every override lands in the fork
+    let (write_set, events) = change_set.into_inner();
+    let mut write_set = write_set.into_mut();
+    for (state_key, value) in fork_state_view.into_overrides() {
+        if write_set.get(&state_key).is_none() {
+            write_set.insert((
+                state_key,
+                value.map_or(WriteOp::Deletion, WriteOp::Modification),
+            ));
+        }
+    }
+    let change_set = ChangeSet::new(write_set.freeze()?, events);
+
+    let pool_addresses = pools.into_iter().map(|(pool, _, _)| pool).collect();
+    Ok((change_set, pool_addresses))
+}
@@ -3,8 +3,12 @@
 
 mod admin_script_builder;
 
+mod fork_builder;
+
 mod writeset_builder;
 
 pub use admin_script_builder::{custom_script, halt_network_payload, remove_validators_payload};
 
-pub use writeset_builder::{build_changeset, GenesisSession};
+pub use fork_builder::{build_fork_changeset, ForkValidator};
+
+pub use writeset_builder::{build_changeset, build_reconfiguration_changeset, GenesisSession};
@@ -104,6 +104,26 @@ impl<'r, 'l, S: MoveResolverExt> GenesisSession<'r, 'l, S> {
 }
 
 pub fn build_changeset<S: StateView, F>(state_view: &S, procedure: F) -> ChangeSet
+where
+    F: FnOnce(&mut GenesisSession<StorageAdapter<S>>),
+{
+    build_changeset_impl(state_view, true, procedure)
+}
+
+/// Same as `build_changeset`, except reconfiguration is left enabled so `procedure` can start a
+/// new epoch
+pub fn build_reconfiguration_changeset<S: StateView, F>(state_view: &S, procedure: F) -> ChangeSet
+where
+    F: FnOnce(&mut GenesisSession<StorageAdapter<S>>),
+{
+    build_changeset_impl(state_view, false, procedure)
+}
+
+fn build_changeset_impl<S: StateView, F>(
+    state_view: &S,
+    disable_reconfiguration: bool,
+    procedure: F,
+) -> ChangeSet
 where
     F: FnOnce(&mut GenesisSession<StorageAdapter<S>>),
 {
@@ -119,9 +139,13 @@ where
         let mut session = GenesisSession(
             move_vm.new_session(&state_view_storage, SessionId::genesis(genesis_id)),
         );
-        session.disable_reconfiguration();
+        if disable_reconfiguration {
+            session.disable_reconfiguration();
+        }
         procedure(&mut session);
-        session.enable_reconfiguration();
+        if disable_reconfiguration {
+            session.enable_reconfiguration();
+        }
         session
             .0
             .finish()
@@ -11,13 +11,15 @@ use aptos_build_info::build_information;
 use aptos_config::{
     config::{
         AptosDataClientConfig, BaseConfig, DataStreamingServiceConfig, NetworkConfig, NodeConfig,
-        PersistableConfig, StorageServiceConfig,
+        PeerMonitoringServiceConfig, PersistableConfig, StorageServiceConfig,
     },
     network_id::NetworkId,
     utils::get_genesis_txn,
 };
 use aptos_data_client::aptosnet::AptosNetDataClient;
 use aptos_fh_stream::runtime::bootstrap as bootstrap_fh_stream;
+#[cfg(feature = "indexer")]
+use aptos_indexer::runtime::bootstrap as bootstrap_indexer;
 use aptos_infallible::RwLock;
 use aptos_logger::telemetry_log_writer::TelemetryLog;
 use aptos_logger::{prelude::*, Level};
@@ -46,6 +48,13 @@ use log_build_information::log_build_information;
 use mempool_notifications::MempoolNotificationSender;
 use network::application::storage::PeerMetadataStorage;
 use network_builder::builder::NetworkBuilder;
+use peer_monitoring_service_client::{
+    PeerMonitor, PeerMonitoringServiceClient, PeerMonitoringServiceMultiSender,
+    PeerMonitoringServiceNetworkSender,
+};
+use peer_monitoring_service_server::{
+    network::PeerMonitoringServiceNetworkEvents, PeerMonitoringServiceServer,
+};
 use rand::{rngs::StdRng, SeedableRng};
 use state_sync_driver::{
     driver_factory::{DriverFactory, StateSyncRuntimes},
@@ -111,6 +120,17 @@ pub struct AptosNodeArgs {
     /// only commit a block when there is user transaction in mempool.
     #[clap(long, requires("test"))]
     lazy: bool,
+
+    /// Fork the network restored into this db directory, instead of starting a new one
+    ///
+    /// The restored validator set is replaced with local validators, which all run in this
+    /// process.  Useful to rehearse upgrades against a copy of a real network's state.
+    #[clap(long, parse(from_os_str), requires("test"))]
+    fork_db: Option<PathBuf>,
+
+    /// Number of local validators to run when forking a network
+    #[clap(long, default_value = "1", requires("fork-db"))]
+    num_validators: usize,
 }
 
 impl AptosNodeArgs {
@@ -121,6 +141,17 @@ impl AptosNodeArgs {
                 .seed
                 .map(StdRng::from_seed)
                 .unwrap_or_else(StdRng::from_entropy);
+            if let Some(fork_db) = self.fork_db {
+                load_fork_environment(
+                    fork_db,
+                    self.test_dir,
+                    self.num_validators,
+                    self.random_ports,
+                    rng,
+                )
+                .expect("Fork mode should start correctly");
+                return;
+            }
             let genesis_framework = if let Some(path) = self.genesis_framework {
                 ReleaseBundle::read(path).unwrap()
             } else {
@@ -169,14 +200,25 @@ pub struct AptosHandle {
     _mempool: Runtime,
     _network_runtimes: Vec<Runtime>,
     _fh_stream: Option<Runtime>,
+    _indexer_runtime: Option<Runtime>,
+    _peer_monitoring_service: Runtime,
     _state_sync_runtimes: StateSyncRuntimes,
     _telemetry_runtime: Option<Runtime>,
 }
 
 /// Start an aptos node
 pub fn start(config: NodeConfig, log_file: Option<PathBuf>) -> anyhow::Result<()> {
+    start_nodes(vec![config], log_file)
+}
+
+/// Start several aptos nodes in the same process, the logger is set up from the first config
+pub fn start_nodes(configs: Vec<NodeConfig>, log_file: Option<PathBuf>) -> anyhow::Result<()> {
     crash_handler::setup_panic_handler();
 
+    let config = configs
+        .first()
+        .ok_or_else(|| anyhow!("At least one node config is needed"))?;
+
     let mut logger = aptos_logger::Logger::new();
     logger
         .channel_size(config.logger.chan_size)
@@ -217,7 +259,10 @@ pub fn start(config: NodeConfig, log_file: Option<PathBuf>) -> anyhow::Result<()
         warn!("failpoints is set in config, but the binary doesn't compile with this feature");
     }
 
-    let _node_handle = setup_environment(config, remote_log_rx)?;
+    let _node_handles = configs
+        .into_iter()
+        .map(|config| setup_environment(config, remote_log_rx.take()))
+        .collect::<anyhow::Result<Vec<_>>>()?;
 
     let term = Arc::new(AtomicBool::new(false));
 
@@ -335,6 +380,89 @@ where
     start(config, Some(log_file))
 }
 
+/// Forks the network restored into `db_dir`, and runs all of its validators locally
+pub fn load_fork_environment<R>(
+    db_dir: PathBuf,
+    test_dir: Option<PathBuf>,
+    num_validators: usize,
+    random_ports: bool,
+    rng: R,
+) -> anyhow::Result<()>
+where
+    R: ::rand::RngCore + ::rand::CryptoRng,
+{
+    // If there wasn't a testnet directory given, create a temp one
+    let test_dir = if let Some(test_dir) = test_dir {
+        test_dir
+    } else {
+        aptos_temppath::TempPath::new().as_ref().to_path_buf()
+    };
+    std::fs::DirBuilder::new()
+        .recursive(true)
+        .create(&test_dir)?;
+    let test_dir = test_dir.canonicalize()?;
+
+    // If the fork was already built, start up from its previous state
+    let mut configs = Vec::new();
+    for index in 0.. {
+        let config_path = test_dir.join(index.to_string()).join("node.yaml");
+        if !config_path.exists() {
+            break;
+        }
+        configs.push(
+            NodeConfig::load(&config_path)
+                .map_err(|err| anyhow!("Unable to load config: {}", err))?,
+        );
+    }
+
+    if configs.is_empty() {
+        let num_validators = std::num::NonZeroUsize::new(num_validators)
+            .ok_or_else(|| anyhow!("At least one validator is needed to fork the network"))?;
+        let (_fork, waypoint, validators) = aptos_genesis::builder::Builder::new(
+            &test_dir,
+            cached_packages::head_release_bundle().clone(),
+        )?
+        .with_num_validators(num_validators)
+        .with_randomize_first_validator_ports(random_ports)
+        .build_fork(&db_dir, ChainId::test(), rng)?;
+
+        // Build a waypoint file so that clients / docker can grab it easily
+        let waypoint_file_path = test_dir.join("waypoint.txt");
+        std::io::Write::write_all(
+            &mut std::fs::File::create(&waypoint_file_path)?,
+            waypoint.to_string().as_bytes(),
+        )?;
+
+        configs = validators
+            .into_iter()
+            .map(|validator| validator.config)
+            .collect();
+    }
+
+    // Prepare log file since we cannot automatically route logs to stderr
+    let log_file = test_dir.join("validator.log");
+
+    println!("Completed generating fork configuration:");
+    println!("\tForked db: {:?}", db_dir);
+    println!("\tChainId: {}", ChainId::test());
+    println!("\tLog file: {:?}", log_file);
+    println!("\tTest dir: {:?}", test_dir);
+    println!(
+        "\tWaypoint: {}",
+        configs[0].base.waypoint.genesis_waypoint()
+    );
+    for (index, config) in configs.iter().enumerate() {
+        println!(
+            "\tValidator {} REST API endpoint: http://{}",
+            index, &config.api.address
+        );
+    }
+
+    println!("\nAptos fork is running, press ctrl-c to exit\n");
+
+    start_nodes(configs, Some(log_file))
+}
+
 // Fetch chain ID from on-chain resource
 fn fetch_chain_id(db: &DbReaderWriter) -> anyhow::Result<ChainId> {
     let db_state_view = db
@@ -496,14 +624,81 @@ fn setup_state_sync_storage_service(
     Ok(storage_service_runtime)
 }
 
+fn setup_peer_monitoring_service(
+    config: PeerMonitoringServiceConfig,
+    server_network_handles: Vec<PeerMonitoringServiceNetworkEvents>,
+    client_network_handles: HashMap<NetworkId, PeerMonitoringServiceNetworkSender>,
+    peer_metadata_storage: Arc<PeerMetadataStorage>,
+    db_rw: &DbReaderWriter,
+) -> anyhow::Result<Runtime> {
+    // Create a new peer monitoring service runtime
+    let peer_monitoring_service_runtime = Builder::new_multi_thread()
+        .thread_name("peer-monitoring-service")
+        .enable_all()
+        .build()
+        .map_err(|err| anyhow!("Failed to start peer monitoring service {}", err))?;
+
+    // Spawn all peer monitoring service servers on the same runtime
+    for events in server_network_handles {
+        let service = PeerMonitoringServiceServer::new(
+            config.clone(),
+            peer_monitoring_service_runtime.handle().clone(),
+            events,
+            peer_metadata_storage.clone(),
+            Arc::clone(&db_rw.reader),
+        );
+        peer_monitoring_service_runtime.spawn(service.start());
+    }
+
+    // Spawn the peer monitor that periodically monitors all connected peers
+    if config.enable_peer_monitoring_client {
+        let monitoring_client = PeerMonitoringServiceClient::new(
+            PeerMonitoringServiceMultiSender::new(client_network_handles),
+            peer_metadata_storage,
+        );
+        let peer_monitor = PeerMonitor::new(config, monitoring_client, TimeService::real());
+        peer_monitoring_service_runtime.spawn(peer_monitor.start());
+    }
+
+    Ok(peer_monitoring_service_runtime)
+}
+
 pub fn setup_environment(
     node_config: NodeConfig,
     remote_log_rx: Option<mpsc::Receiver<TelemetryLog>>,
 ) -> anyhow::Result<AptosHandle> {
+    // Gather all network configs into a single vector.
+    let mut network_configs: Vec<&NetworkConfig> = node_config.full_node_networks.iter().collect();
+    if let Some(network_config) = node_config.validator_network.as_ref() {
+        network_configs.push(network_config);
+    }
+
+    // Collect the ids of all networks (each network may only be configured once)
+    let mut network_ids = HashSet::new();
+    network_configs.iter().for_each(|config| {
+        let network_id = config.network_id;
+        // Guarantee there is only one of this network
+        if network_ids.contains(&network_id) {
+            panic!(
+                "Duplicate NetworkId: '{}'.  Can't start node with duplicate networks",
+                network_id
+            );
+        }
+        network_ids.insert(network_id);
+    });
+    let network_ids: Vec<_> = network_ids.into_iter().collect();
+
+    // Create the peer metadata storage shared by all networks and components
+    let peer_metadata_storage = PeerMetadataStorage::new(&network_ids);
+
     // Start the node inspection service
     let node_config_clone = node_config.clone();
+    let peer_metadata_storage_clone = peer_metadata_storage.clone();
     thread::spawn(move || {
-        inspection_service::inspection_service::start_inspection_service(node_config_clone)
+        inspection_service::inspection_service::start_inspection_service(
+            node_config_clone,
+            peer_metadata_storage_clone,
+        )
     });
 
     // Open the database
@@ -549,6 +744,8 @@ pub fn setup_environment(
     let mut consensus_network_handles = None;
     let mut storage_service_server_network_handles = vec![];
     let mut storage_service_client_network_handles = HashMap::new();
+    let mut peer_monitoring_service_server_network_handles = vec![];
+    let mut peer_monitoring_service_client_network_handles = HashMap::new();
 
     // Create an event subscription service so that components can be notified of events and reconfigs
     let mut event_subscription_service = EventSubscriptionService::new(
@@ -565,28 +762,7 @@ pub fn setup_environment(
         None
     };
 
-    // Gather all network configs into a single vector.
-    let mut network_configs: Vec<&NetworkConfig> = node_config.full_node_networks.iter().collect();
-    if let Some(network_config) = node_config.validator_network.as_ref() {
-        network_configs.push(network_config);
-    }
-
     // Instantiate every network and collect the requisite endpoints for state_sync, mempool, and consensus.
-    let mut network_ids = HashSet::new();
-    network_configs.iter().for_each(|config| {
-        let network_id = config.network_id;
-        // Guarantee there is only one of this network
-        if network_ids.contains(&network_id) {
-            panic!(
-                "Duplicate NetworkId: '{}'.  Can't start node with duplicate networks",
-                network_id
-            );
-        }
-        network_ids.insert(network_id);
-    });
-    let network_ids: Vec<_> = network_ids.into_iter().collect();
-
-    let peer_metadata_storage = PeerMetadataStorage::new(&network_ids);
     for network_config in network_configs.into_iter() {
         debug!("Creating runtime for {}", network_config.network_id);
         let mut runtime_builder = Builder::new_multi_thread();
@@ -633,6 +809,25 @@ pub fn setup_environment(
             network_builder.add_client(&storage_service_client::network_endpoint_config());
         storage_service_client_network_handles.insert(network_id, storage_service_sender);
 
+        // Register the peer monitoring service (both client and server) with Network. Peers on
+        // public networks are untrusted, so they aren't served unless explicitly enabled.
+        let peer_monitoring_config = &node_config.peer_monitoring_service;
+        if peer_monitoring_config.enable_peer_monitoring_server
+            && (!network_id.is_public_network()
+                || peer_monitoring_config.enable_public_network_server)
+        {
+            let peer_monitoring_service_events = network_builder.add_service(
+                &peer_monitoring_service_server::network::network_endpoint_config(
+                    peer_monitoring_config.clone(),
+                ),
+            );
+            peer_monitoring_service_server_network_handles.push(peer_monitoring_service_events);
+        }
+        let peer_monitoring_service_sender =
+            network_builder.add_client(&peer_monitoring_service_client::network_endpoint_config());
+        peer_monitoring_service_client_network_handles
+            .insert(network_id, peer_monitoring_service_sender);
+
         // Create the endpoints to connect the Network to mempool.
         let (mempool_sender, mempool_events) = network_builder.add_p2p_service(
             &aptos_mempool::network::network_endpoint_config(MEMPOOL_NETWORK_CHANNEL_BUFFER_SIZE),
@@ -663,6 +858,15 @@ pub fn setup_environment(
     // TODO set up on-chain discovery network based on UpstreamConfig.fallback_network
     // and pass network handles to mempool/state sync
 
+    // Start the peer monitoring service
+    let peer_monitoring_service_runtime = setup_peer_monitoring_service(
+        node_config.peer_monitoring_service.clone(),
+        peer_monitoring_service_server_network_handles,
+        peer_monitoring_service_client_network_handles,
+        peer_metadata_storage.clone(),
+        &db_rw,
+    )?;
+
     // For state sync to send notifications to mempool and receive notifications from consensus.
     let (mempool_notifier, mempool_listener) =
         mempool_notifications::new_mempool_notifier_listener_pair();
@@ -695,10 +899,29 @@ pub fn setup_environment(
         aptos_db.clone(),
         mp_client_sender.clone(),
     )?;
-    let sf_runtime = match bootstrap_fh_stream(&node_config, chain_id, aptos_db, mp_client_sender) {
+    let sf_runtime = match bootstrap_fh_stream(
+        &node_config,
+        chain_id,
+        aptos_db.clone(),
+        mp_client_sender.clone(),
+    ) {
         None => None,
         Some(res) => Some(res?),
     };
+    #[cfg(feature = "indexer")]
+    let indexer_runtime =
+        match bootstrap_indexer(&node_config, chain_id, aptos_db, mp_client_sender) {
+            None => None,
+            Some(res) => Some(res?),
+        };
+    #[cfg(not(feature = "indexer"))]
+    let indexer_runtime: Option<Runtime> = if node_config.indexer.enabled {
+        return Err(anyhow!(
+            "The indexer is enabled in the config, but aptos-node was built without the `indexer` feature"
+        ));
+    } else {
+        None
+    };
 
     let mut consensus_runtime = None;
     let (consensus_to_mempool_sender, consensus_to_mempool_receiver) =
@@ -771,6 +994,8 @@ pub fn setup_environment(
         _mempool: mempool,
         _network_runtimes: network_runtimes,
         _fh_stream: sf_runtime,
+        _indexer_runtime: indexer_runtime,
+        _peer_monitoring_service: peer_monitoring_service_runtime,
         _state_sync_runtimes: state_sync_runtimes,
         _telemetry_runtime: telemetry_runtime,
     })
@@ -36,6 +36,8 @@ mod state_sync_config;
 pub use state_sync_config::*;
 mod firehose_streamer_config;
 pub use firehose_streamer_config::*;
+mod indexer_config;
+pub use indexer_config::*;
 mod storage_config;
 pub use storage_config::*;
 mod safety_rules_config;
@@ -84,6 +86,8 @@ pub struct NodeConfig {
     #[serde(default)]
     pub firehose_stream: FirehoseStreamerConfig,
     #[serde(default)]
+    pub indexer: IndexerConfig,
+    #[serde(default)]
     pub storage: StorageConfig,
     #[serde(default)]
     pub test: Option<TestConfig>,
@@ -11,7 +11,7 @@ use aptos_crypto::{x25519, Uniform};
 use aptos_secure_storage::{CryptoStorage, KVStorage, Storage};
 use aptos_types::{
     account_address::from_identity_public_key, network_address::NetworkAddress,
-    transaction::authenticator::AuthenticationKey, PeerId,
+    transaction::authenticator::AuthenticationKey, waypoint::Waypoint, PeerId,
 };
 use rand::{
     rngs::{OsRng, StdRng},
@@ -89,6 +89,9 @@ pub struct NetworkConfig {
     pub max_frame_size: usize,
     // Enables proxy protocol on incoming connections to get original source addresses
     pub enable_proxy_protocol: bool,
+    // Advertises the upgraded messaging protocol, which rejects rate limited rpcs with an error.
+    // Nodes running older releases can't parse it, so only enable once all peers have upgraded.
+    pub enable_upgraded_messaging_protocol: bool,
     // Interval to send healthcheck pings to peers
     pub ping_interval_ms: u64,
     // Timeout until a healthcheck ping is rejected
@@ -103,6 +106,12 @@ pub struct NetworkConfig {
     pub inbound_rate_limit_config: Option<RateLimitConfig>,
     // Outbound rate limiting configuration, if not specified, no rate limiting
     pub outbound_rate_limit_config: Option<RateLimitConfig>,
+    // Per connection inbound rate limits of individual protocols, keyed by protocol name
+    // (e.g. `StorageServiceRpc`). Messages exceeding the limit are dropped.
+    pub inbound_protocol_rate_limit_configs: HashMap<String, RateLimitConfig>,
+    // Per connection outbound rate limits of individual protocols, keyed by protocol name.
+    // Messages exceeding the limit are delayed, without delaying other protocols.
+    pub outbound_protocol_rate_limit_configs: HashMap<String, RateLimitConfig>,
     // The maximum size of an inbound or outbound message (it may be divided into multiple frame)
     pub max_message_size: usize,
 }
@@ -127,6 +136,7 @@ impl NetworkConfig {
             seeds: PeerSet::default(),
             max_frame_size: MAX_FRAME_SIZE,
             enable_proxy_protocol: false,
+            enable_upgraded_messaging_protocol: false,
             max_connection_delay_ms: MAX_CONNECTION_DELAY_MS,
             connectivity_check_interval_ms: CONNECTIVITY_CHECK_INTERVAL_MS,
             network_channel_size: NETWORK_CHANNEL_SIZE,
@@ -139,6 +149,8 @@ impl NetworkConfig {
             max_inbound_connections: MAX_INBOUND_CONNECTIONS,
             inbound_rate_limit_config: None,
             outbound_rate_limit_config: None,
+            inbound_protocol_rate_limit_configs: HashMap::new(),
+            outbound_protocol_rate_limit_configs: HashMap::new(),
             max_message_size: MAX_MESSAGE_SIZE,
         };
         config.prepare_identity();
@@ -318,15 +330,27 @@ impl NetworkConfig {
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct PeerMonitoringServiceConfig {
-    pub max_concurrent_requests: u64, // Max num of concurrent server tasks
-    pub max_network_channel_size: u64, // Max num of pending network messages
+    pub enable_peer_monitoring_client: bool, // Whether to periodically monitor all connected peers
+    pub enable_peer_monitoring_server: bool, // Whether to serve monitoring requests from peers
+    pub enable_public_network_server: bool,  // Whether to also serve requests on public networks
+    pub max_concurrent_requests: u64,        // Max num of concurrent server tasks
+    pub max_network_channel_size: u64,       // Max num of pending network messages
+    pub max_num_latency_pings_to_retain: u64, // Max num of recent pings to average the latency over
+    pub peer_monitor_interval_ms: u64,       // Interval (ms) between monitoring rounds
+    pub request_timeout_ms: u64,             // Timeout (ms) for each monitoring request
 }
 
 impl Default for PeerMonitoringServiceConfig {
     fn default() -> Self {
         Self {
+            enable_peer_monitoring_client: true,
+            enable_peer_monitoring_server: true,
+            enable_public_network_server: false,
             max_concurrent_requests: 1000,
             max_network_channel_size: 1000,
+            max_num_latency_pings_to_retain: 10,
+            peer_monitor_interval_ms: 5000,
+            request_timeout_ms: 5000,
         }
     }
 }
@@ -336,9 +360,69 @@ impl Default for PeerMonitoringServiceConfig {
 pub enum DiscoveryMethod {
     Onchain,
     File(PathBuf, Duration),
+    PeerExchange(PeerExchangeConfig),
+    Rest(RestDiscovery),
     None,
 }
 
+/// Configuration for discovering peers by periodically reading the `ValidatorSet` from the REST
+/// API of a trusted node. Useful for fullnodes that are not yet synced enough to use `Onchain`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RestDiscovery {
+    /// Base url of the trusted REST endpoint, e.g. `https://fullnode.mainnet.aptoslabs.com/v1`
+    pub url: url::Url,
+    /// Interval between two reads of the validator set
+    pub interval_secs: u64,
+    /// If set, responses from an endpoint whose ledger is behind or doesn't match this waypoint
+    /// are rejected. The endpoint must keep the state at the waypoint version.
+    #[serde(default)]
+    pub waypoint: Option<Waypoint>,
+}
+
+/// Configuration for discovering peers through the addresses shared by connected peers. Only
+/// intended for public networks, where peers are not mutually authenticated.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PeerExchangeConfig {
+    /// Interval between two rounds of peer exchange requests
+    pub interval_ms: u64,
+    /// Timeout of a single peer exchange request
+    pub request_timeout_ms: u64,
+    /// Number of connected peers queried in each round
+    pub peers_per_round: usize,
+    /// Maximum number of peers accepted from a single response
+    pub max_peers_per_response: usize,
+    /// Maximum number of candidate peers tracked across all responses
+    pub max_candidate_peers: usize,
+    /// Maximum number of candidate peers supported only by a single reporter, so one peer
+    /// can't fill the candidate set on its own
+    pub max_candidates_per_source: usize,
+    /// Maximum number of peers handed to the ConnectivityManager
+    pub max_discovered_peers: usize,
+    /// Maximum number of discovered peers in the same /24 (IPv4) or /48 (IPv6) subnet, or under
+    /// the same DNS domain
+    pub max_peers_per_subnet: usize,
+    /// File where peers we successfully connected to are kept across restarts
+    pub peer_file: Option<PathBuf>,
+}
+
+impl Default for PeerExchangeConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 60_000,
+            request_timeout_ms: 10_000,
+            peers_per_round: 3,
+            max_peers_per_response: 32,
+            max_candidate_peers: 500,
+            max_candidates_per_source: 64,
+            max_discovered_peers: 100,
+            max_peers_per_subnet: 2,
+            peer_file: None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum Identity {
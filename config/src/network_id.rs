@@ -163,6 +163,10 @@ impl NetworkId {
         self == &NetworkId::Validator
     }
 
+    pub fn is_public_network(&self) -> bool {
+        self == &NetworkId::Public
+    }
+
     /// Roles for a prioritization of relative upstreams
     pub fn upstream_roles(&self, role: &RoleType) -> &'static [PeerRole] {
         match self {
@@ -0,0 +1,10 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use consensus::consensusdb_cli::Opt;
+use structopt::StructOpt;
+
+fn main() -> Result<()> {
+    Opt::from_args().run()
+}
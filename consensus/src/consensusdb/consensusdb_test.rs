@@ -2,8 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use crate::consensusdb::inspector::{plan_prune, verify_chain};
 use aptos_temppath::TempPath;
-use consensus_types::block::block_test_utils::certificate_for_genesis;
+use aptos_types::{block_info::BlockInfo, validator_signer::ValidatorSigner};
+use consensus_types::{
+    block::block_test_utils::{
+        certificate_for_genesis, placeholder_certificate_for_block, placeholder_ledger_info,
+    },
+    common::Payload,
+    safety_data::SafetyData,
+    vote::Vote,
+    vote_data::VoteData,
+};
 
 #[test]
 fn test_put_get() {
@@ -69,3 +79,91 @@ fn test_delete_block_and_qc() {
     assert_eq!(db.get_blocks().unwrap().len(), 0);
     assert_eq!(db.get_quorum_certificates().unwrap().len(), 0);
 }
+
+fn proposal(signer: &ValidatorSigner, round: u64, parent_qc: QuorumCert) -> Block {
+    Block::new_proposal(
+        Payload::empty(),
+        round,
+        aptos_infallible::duration_since_epoch().as_micros() as u64,
+        parent_qc,
+        signer,
+        Vec::new(),
+    )
+}
+
+fn vote_for(signer: &ValidatorSigner, block: &Block) -> Vote {
+    let proposed = BlockInfo::new(
+        block.epoch(),
+        block.round(),
+        block.id(),
+        HashValue::zero(),
+        0,
+        block.timestamp_usecs(),
+        None,
+    );
+    let vote_data = VoteData::new(proposed, block.quorum_cert().certified_block().clone());
+    Vote::new(
+        vote_data,
+        signer.author(),
+        placeholder_ledger_info(),
+        signer,
+    )
+}
+
+#[test]
+fn test_verify_chain_and_prune_orphans() {
+    let signer = ValidatorSigner::random(None);
+    let genesis = Block::make_genesis_block();
+    let child = proposal(&signer, 1, certificate_for_genesis());
+    let orphan_qc = placeholder_certificate_for_block(
+        &[signer.clone()],
+        HashValue::random(),
+        4,
+        HashValue::random(),
+        3,
+    );
+    let orphan = proposal(&signer, 5, orphan_qc);
+    let blocks = vec![orphan.clone(), child.clone(), genesis.clone()];
+
+    let report = verify_chain(&blocks, &[certificate_for_genesis()], None).unwrap();
+    assert!(report.is_consistent());
+    assert_eq!(report.root, Some(genesis.id()));
+    assert_eq!(report.chain, vec![genesis.id(), child.id()]);
+    assert_eq!(report.orphan_blocks, vec![orphan.id()]);
+
+    let safety_data = SafetyData::new(genesis.epoch(), 1, 0, 0, None);
+    assert_eq!(
+        plan_prune(&report, None, &safety_data, &[]).unwrap(),
+        vec![orphan.id()]
+    );
+    // blocks descending from the root are never pruned
+    assert!(plan_prune(&report, None, &safety_data, &[child.id()]).is_err());
+    assert!(plan_prune(&report, None, &safety_data, &[genesis.id()]).is_err());
+    // neither is the block that was last voted for
+    let vote = vote_for(&signer, &orphan);
+    let safety_data = SafetyData::new(genesis.epoch(), 5, 0, 0, Some(vote.clone()));
+    assert!(plan_prune(&report, Some(&vote), &safety_data, &[]).is_err());
+}
+
+#[test]
+fn test_prune_refused_when_inconsistent() {
+    let signer = ValidatorSigner::random(None);
+    let genesis = Block::make_genesis_block();
+    let child = proposal(&signer, 1, certificate_for_genesis());
+    let blocks = vec![genesis.clone(), child.clone()];
+    let safety_data = SafetyData::new(genesis.epoch(), 0, 0, 0, None);
+
+    // the root must be certified
+    let report = verify_chain(&blocks, &[], None).unwrap();
+    assert!(!report.is_consistent());
+    assert!(plan_prune(&report, None, &safety_data, &[]).is_err());
+
+    // safety rules storage lagging behind the persisted vote
+    let report = verify_chain(&blocks, &[certificate_for_genesis()], None).unwrap();
+    assert!(report.is_consistent());
+    let vote = vote_for(&signer, &child);
+    assert!(plan_prune(&report, Some(&vote), &safety_data, &[]).is_err());
+
+    // an unknown root is rejected
+    assert!(verify_chain(&blocks, &[], Some(HashValue::random())).is_err());
+}
@@ -0,0 +1,181 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, Result};
+use aptos_crypto::HashValue;
+use consensus_types::{block::Block, quorum_cert::QuorumCert, safety_data::SafetyData, vote::Vote};
+use std::collections::{HashMap, HashSet};
+
+/// The result of checking that the blocks persisted in the ConsensusDB form a valid tree
+/// rooted at a single block.
+#[derive(Debug, Default)]
+pub struct ChainReport {
+    /// The block every other valid block descends from.
+    pub root: Option<HashValue>,
+    /// The root and all of its descendants, sorted by (epoch, round).
+    pub chain: Vec<HashValue>,
+    /// Blocks that don't have the root as ancestor, these are the ones recovery would prune.
+    pub orphan_blocks: Vec<HashValue>,
+    /// QCs certifying a block that is not stored in the db.
+    pub dangling_quorum_certs: Vec<HashValue>,
+    /// Invariant violations found while walking the chain.
+    pub errors: Vec<String>,
+}
+
+impl ChainReport {
+    pub fn is_consistent(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Checks that `blocks` form a tree starting at `root_id` (or the lowest (epoch, round) block if
+/// no root is given), that parents precede children and that the root is certified.
+pub fn verify_chain(
+    blocks: &[Block],
+    quorum_certs: &[QuorumCert],
+    root_id: Option<HashValue>,
+) -> Result<ChainReport> {
+    let mut sorted_blocks: Vec<&Block> = blocks.iter().collect();
+    // sort by (epoch, round) to guarantee the topological order of parent <- child
+    sorted_blocks.sort_by_key(|b| (b.epoch(), b.round()));
+
+    let mut report = ChainReport::default();
+    let root_id = match root_id {
+        Some(id) => {
+            ensure!(
+                sorted_blocks.iter().any(|b| b.id() == id),
+                "Root block {} not found in ConsensusDB",
+                id
+            );
+            id
+        }
+        None => match sorted_blocks.first() {
+            Some(block) => block.id(),
+            None => return Ok(report),
+        },
+    };
+    report.root = Some(root_id);
+
+    let blocks_by_id: HashMap<HashValue, &Block> =
+        sorted_blocks.iter().map(|b| (b.id(), *b)).collect();
+    let certified: HashSet<HashValue> = quorum_certs
+        .iter()
+        .map(|qc| qc.certified_block().id())
+        .collect();
+
+    let mut tree = HashSet::new();
+    for block in sorted_blocks {
+        if block.id() == root_id {
+            tree.insert(block.id());
+            report.chain.push(block.id());
+            continue;
+        }
+        if !tree.contains(&block.parent_id()) {
+            report.orphan_blocks.push(block.id());
+            continue;
+        }
+        let parent = blocks_by_id[&block.parent_id()];
+        if block.round() <= parent.round() {
+            report.errors.push(format!(
+                "Block {} at round {} does not have a higher round than its parent {} at round {}",
+                block.id(),
+                block.round(),
+                parent.id(),
+                parent.round()
+            ));
+        }
+        if block.epoch() != parent.epoch() {
+            report.errors.push(format!(
+                "Block {} at epoch {} has parent {} from a different epoch {}",
+                block.id(),
+                block.epoch(),
+                parent.id(),
+                parent.epoch()
+            ));
+        }
+        if let Err(e) = block.verify_well_formed() {
+            report
+                .errors
+                .push(format!("Block {} is not well formed: {}", block.id(), e));
+        }
+        tree.insert(block.id());
+        report.chain.push(block.id());
+    }
+
+    if !certified.contains(&root_id) {
+        report
+            .errors
+            .push(format!("No QC found for root block {}", root_id));
+    }
+    report.dangling_quorum_certs = quorum_certs
+        .iter()
+        .map(|qc| qc.certified_block().id())
+        .filter(|id| !blocks_by_id.contains_key(id))
+        .collect();
+
+    Ok(report)
+}
+
+/// Returns the block ids that are safe to delete from the ConsensusDB.
+///
+/// If `requested` is empty all orphan blocks are selected. The plan is refused if it touches the
+/// root or any of its descendants, the block the validator last voted for, or if the safety rules
+/// storage lags behind the vote persisted in the ConsensusDB: in any of these cases a restart could
+/// lead the validator to vote inconsistently.
+pub fn plan_prune(
+    report: &ChainReport,
+    last_vote: Option<&Vote>,
+    safety_data: &SafetyData,
+    requested: &[HashValue],
+) -> Result<Vec<HashValue>> {
+    if !report.is_consistent() {
+        bail!(
+            "Refusing to prune an inconsistent ConsensusDB:\n\t{}",
+            report.errors.join("\n\t")
+        );
+    }
+    if let Some(vote) = last_vote {
+        let round = vote.vote_data().proposed().round();
+        if vote.epoch() > safety_data.epoch
+            || (vote.epoch() == safety_data.epoch && round > safety_data.last_voted_round)
+        {
+            bail!(
+                "Safety rules storage ({}) is behind the last vote in ConsensusDB (epoch {}, round {})",
+                safety_data,
+                vote.epoch(),
+                round
+            );
+        }
+    }
+
+    let protected: HashSet<HashValue> = report
+        .chain
+        .iter()
+        .copied()
+        .chain(last_vote.map(|v| v.vote_data().proposed().id()))
+        .chain(
+            safety_data
+                .last_vote
+                .as_ref()
+                .map(|v| v.vote_data().proposed().id()),
+        )
+        .collect();
+
+    let to_prune = if requested.is_empty() {
+        report.orphan_blocks.clone()
+    } else {
+        requested.to_vec()
+    };
+    for id in &to_prune {
+        if protected.contains(id) {
+            bail!(
+                "Refusing to prune block {}: it is the root, descends from the root or was voted for",
+                id
+            );
+        }
+        if !report.orphan_blocks.contains(id) {
+            bail!("Block {} not found in ConsensusDB", id);
+        }
+    }
+    Ok(to_prune)
+}
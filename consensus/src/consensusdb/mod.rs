@@ -3,6 +3,7 @@
 
 #[cfg(test)]
 mod consensusdb_test;
+pub mod inspector;
 mod schema;
 
 use crate::{
@@ -0,0 +1,192 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::consensusdb::{
+    inspector::{plan_prune, verify_chain, ChainReport},
+    ConsensusDB, CONSENSUS_DB_NAME,
+};
+use anyhow::{ensure, format_err, Context, Result};
+use aptos_config::config::NodeConfig;
+use aptos_crypto::HashValue;
+use aptos_secure_storage::{KVStorage, Storage};
+use consensus_types::{
+    block::Block, quorum_cert::QuorumCert, safety_data::SafetyData,
+    timeout_2chain::TwoChainTimeoutCertificate, vote::Vote,
+};
+use safety_rules::PersistentSafetyStorage;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Command line options of the `consensusdb-cli` tool. The node must be stopped while the tool
+/// runs, as the ConsensusDB can only be opened by a single process.
+#[derive(StructOpt)]
+#[structopt(
+    name = "consensusdb-cli",
+    about = "Inspect, verify and repair the ConsensusDB and safety rules storage of a stopped node."
+)]
+pub struct Opt {
+    /// Node config used to locate the ConsensusDB and the safety rules storage.
+    #[structopt(long, parse(from_os_str))]
+    node_config: PathBuf,
+
+    #[structopt(subcommand)]
+    cmd: Command,
+}
+
+#[derive(StructOpt)]
+enum Command {
+    /// Print the blocks, quorum certs, last vote, timeout certificate and safety data.
+    Dump,
+    /// Verify that the persisted blocks form a valid chain from the root.
+    Verify {
+        /// The root block, defaults to the block with the lowest (epoch, round).
+        #[structopt(long)]
+        root_id: Option<HashValue>,
+    },
+    /// Delete blocks (and their QCs) that don't descend from the root.
+    Prune {
+        /// The root block, defaults to the block with the lowest (epoch, round).
+        #[structopt(long)]
+        root_id: Option<HashValue>,
+        /// Blocks to delete, defaults to all blocks not descending from the root.
+        #[structopt(long = "block-id")]
+        block_ids: Vec<HashValue>,
+        /// Without this flag the blocks that would be deleted are only printed.
+        #[structopt(long)]
+        commit: bool,
+    },
+}
+
+struct ConsensusData {
+    last_vote: Option<Vote>,
+    highest_timeout_cert: Option<TwoChainTimeoutCertificate>,
+    blocks: Vec<Block>,
+    quorum_certs: Vec<QuorumCert>,
+}
+
+impl Opt {
+    /// Executes the selected subcommand against the node's ConsensusDB.
+    pub fn run(self) -> Result<()> {
+        let config = NodeConfig::load(&self.node_config)
+            .with_context(|| format_err!("Failed to load node config."))?;
+        let db = open_consensus_db(&config)?;
+        let data = read_consensus_data(&db)?;
+
+        match self.cmd {
+            Command::Dump => {
+                dump(&data);
+                match read_safety_data(&config) {
+                    Ok(safety_data) => println!("{}", safety_data),
+                    Err(e) => println!("Safety data unavailable: {}", e),
+                }
+            }
+            Command::Verify { root_id } => {
+                let report = verify_chain(&data.blocks, &data.quorum_certs, root_id)?;
+                print_report(&report);
+                ensure!(report.is_consistent(), "ConsensusDB verification failed.");
+            }
+            Command::Prune {
+                root_id,
+                block_ids,
+                commit,
+            } => {
+                let report = verify_chain(&data.blocks, &data.quorum_certs, root_id)?;
+                let safety_data = read_safety_data(&config)?;
+                let to_prune =
+                    plan_prune(&report, data.last_vote.as_ref(), &safety_data, &block_ids)?;
+                if to_prune.is_empty() {
+                    println!("Nothing to prune.");
+                    return Ok(());
+                }
+                println!("Blocks to prune:");
+                to_prune.iter().for_each(|id| println!("\t{}", id));
+                if commit {
+                    db.delete_blocks_and_quorum_certificates(to_prune)?;
+                    println!("Successfully pruned ConsensusDB.");
+                } else {
+                    println!("Dry run, pass --commit to delete.");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn open_consensus_db(config: &NodeConfig) -> Result<ConsensusDB> {
+    let db_dir = config.storage.dir();
+    ensure!(
+        db_dir.join(CONSENSUS_DB_NAME).exists(),
+        "No ConsensusDB found under {:?}",
+        db_dir
+    );
+    Ok(ConsensusDB::new(db_dir))
+}
+
+fn read_consensus_data(db: &ConsensusDB) -> Result<ConsensusData> {
+    let (last_vote, highest_timeout_cert, mut blocks, mut quorum_certs) = db.get_data()?;
+    blocks.sort_by_key(|b| (b.epoch(), b.round()));
+    quorum_certs.sort_by_key(|qc| (qc.certified_block().epoch(), qc.certified_block().round()));
+    Ok(ConsensusData {
+        last_vote: last_vote
+            .map(|bytes| bcs::from_bytes(&bytes[..]))
+            .transpose()
+            .with_context(|| format_err!("Failed to deserialize last vote."))?,
+        highest_timeout_cert: highest_timeout_cert
+            .map(|bytes| bcs::from_bytes(&bytes[..]))
+            .transpose()
+            .with_context(|| format_err!("Failed to deserialize highest timeout certificate."))?,
+        blocks,
+        quorum_certs,
+    })
+}
+
+/// Reads the safety data without going through the safety rules service. The storage is never
+/// written to by this tool.
+fn read_safety_data(config: &NodeConfig) -> Result<SafetyData> {
+    let internal_storage = Storage::from(&config.consensus.safety_rules.backend);
+    internal_storage
+        .available()
+        .map_err(|e| format_err!("Safety rules storage is not available: {:?}", e))?;
+    Ok(PersistentSafetyStorage::new(internal_storage, false).safety_data()?)
+}
+
+fn dump(data: &ConsensusData) {
+    println!("Blocks ({}):", data.blocks.len());
+    for block in &data.blocks {
+        println!(
+            "\t{} parent: {}, payload size: {}",
+            block,
+            block.parent_id(),
+            block.payload().map_or(0, |p| p.len())
+        );
+    }
+    println!("Quorum certs ({}):", data.quorum_certs.len());
+    for qc in &data.quorum_certs {
+        println!("\t{}", qc);
+    }
+    match &data.last_vote {
+        Some(vote) => println!("Last vote: {}", vote),
+        None => println!("Last vote: None"),
+    }
+    match &data.highest_timeout_cert {
+        Some(tc) => println!("Highest timeout certificate: {}", tc),
+        None => println!("Highest timeout certificate: None"),
+    }
+}
+
+fn print_report(report: &ChainReport) {
+    match report.root {
+        Some(root) => println!("Root: {}", root),
+        None => println!("ConsensusDB has no blocks."),
+    }
+    println!("Blocks descending from root: {}", report.chain.len());
+    report
+        .orphan_blocks
+        .iter()
+        .for_each(|id| println!("Orphan block: {}", id));
+    report
+        .dangling_quorum_certs
+        .iter()
+        .for_each(|id| println!("QC for missing block: {}", id));
+    report.errors.iter().for_each(|e| println!("Error: {}", e));
+}
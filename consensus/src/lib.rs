@@ -40,6 +40,8 @@ mod util;
 
 /// AptosBFT implementation
 pub mod consensus_provider;
+/// Offline inspection and repair of the ConsensusDB.
+pub mod consensusdb_cli;
 /// Required by the telemetry service
 pub mod counters;
 /// AptosNet interface.
@@ -13,6 +13,7 @@ pub mod hash;
 pub mod hkdf;
 pub mod multi_ed25519;
 pub mod noise;
+pub mod secp256r1_ecdsa;
 pub mod test_utils;
 pub mod traits;
 pub mod validatable;
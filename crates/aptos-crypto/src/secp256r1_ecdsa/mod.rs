@@ -0,0 +1,53 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides an API for the ECDSA signature scheme over the NIST P-256 curve (a.k.a.
+//! secp256r1), as used by passkeys, WebAuthn authenticators and secure enclaves.
+//!
+//! Messages are hashed with SHA2-256 before being signed, as specified for `ES256` in
+//! [RFC7518](https://tools.ietf.org/html/rfc7518#section-3.4).
+//!
+//! Signature verification also checks and rejects non-canonical (high-S) signatures.
+//!
+//! # Examples
+//!
+//! ```
+//! use aptos_crypto_derive::{CryptoHasher, BCSCryptoHash};
+//! use aptos_crypto::{
+//!     secp256r1_ecdsa::*,
+//!     traits::{Signature, SigningKey, Uniform},
+//!     test_utils::KeyPair
+//! };
+//! use rand_core::OsRng;
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize, CryptoHasher, BCSCryptoHash)]
+//! pub struct TestCryptoDocTest(String);
+//! let message = TestCryptoDocTest("Test message".to_string());
+//!
+//! let mut rng = OsRng;
+//! let kp = KeyPair::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>::generate(&mut rng);
+//!
+//! let signature = kp.private_key.sign(&message);
+//! assert!(signature.verify(&message, &kp.public_key).is_ok());
+//! ```
+
+/// The length of the Secp256r1EcdsaPrivateKey
+pub const SECP256R1_ECDSA_PRIVATE_KEY_LENGTH: usize = 32;
+/// The length of the Secp256r1EcdsaPublicKey, in uncompressed SEC1 form
+pub const SECP256R1_ECDSA_PUBLIC_KEY_LENGTH: usize = 65;
+/// The length of the Secp256r1EcdsaSignature, as the concatenation `r || s`
+pub const SECP256R1_ECDSA_SIGNATURE_LENGTH: usize = 64;
+
+pub mod secp256r1_ecdsa_keys;
+pub mod secp256r1_ecdsa_sigs;
+
+#[cfg(any(test, feature = "fuzzing"))]
+pub use secp256r1_ecdsa_keys::keypair_strategy;
+
+pub use secp256r1_ecdsa_keys::{Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey};
+pub use secp256r1_ecdsa_sigs::Secp256r1EcdsaSignature;
+
+pub use secp256r1_ecdsa_keys::Secp256r1EcdsaPrivateKey as PrivateKey;
+pub use secp256r1_ecdsa_keys::Secp256r1EcdsaPublicKey as PublicKey;
+pub use secp256r1_ecdsa_sigs::Secp256r1EcdsaSignature as Signature;
@@ -0,0 +1,265 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file implements traits for ECDSA P-256 private keys and public keys.
+
+use crate::{
+    hash::CryptoHash,
+    secp256r1_ecdsa::{
+        Secp256r1EcdsaSignature, SECP256R1_ECDSA_PRIVATE_KEY_LENGTH,
+        SECP256R1_ECDSA_PUBLIC_KEY_LENGTH,
+    },
+    traits::*,
+};
+use aptos_crypto_derive::{DeserializeKey, SerializeKey, SilentDebug, SilentDisplay};
+use core::convert::TryFrom;
+use p256::ecdsa::signature::Signer as _;
+use serde::Serialize;
+use std::fmt;
+
+#[cfg(any(test, feature = "fuzzing"))]
+use crate::test_utils::{self, KeyPair};
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest::prelude::*;
+
+/// An ECDSA P-256 private key
+#[derive(DeserializeKey, SerializeKey, SilentDebug, SilentDisplay)]
+pub struct Secp256r1EcdsaPrivateKey(pub(crate) p256::ecdsa::SigningKey);
+
+#[cfg(feature = "assert-private-keys-not-cloneable")]
+static_assertions::assert_not_impl_any!(Secp256r1EcdsaPrivateKey: Clone);
+
+#[cfg(any(test, feature = "cloneable-private-keys"))]
+impl Clone for Secp256r1EcdsaPrivateKey {
+    fn clone(&self) -> Self {
+        let serialized: &[u8] = &(self.to_bytes());
+        Secp256r1EcdsaPrivateKey::try_from(serialized).unwrap()
+    }
+}
+
+/// An ECDSA P-256 public key
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct Secp256r1EcdsaPublicKey(pub(crate) p256::ecdsa::VerifyingKey);
+
+impl Secp256r1EcdsaPrivateKey {
+    /// The length of the Secp256r1EcdsaPrivateKey
+    pub const LENGTH: usize = SECP256R1_ECDSA_PRIVATE_KEY_LENGTH;
+
+    /// Serialize a Secp256r1EcdsaPrivateKey.
+    pub fn to_bytes(&self) -> [u8; SECP256R1_ECDSA_PRIVATE_KEY_LENGTH] {
+        self.0.to_bytes().into()
+    }
+
+    /// Signs the data of a WebAuthn assertion, i.e., the authenticator data followed by the
+    /// SHA2-256 hash of the client data. Unlike `SigningKey::sign`, the message is not prefixed
+    /// with a domain separator, since its format is defined by the WebAuthn specification.
+    pub fn sign_webauthn_assertion(&self, verification_data: &[u8]) -> Secp256r1EcdsaSignature {
+        self.sign_arbitrary_message(verification_data)
+    }
+
+    /// Private function aimed at minimizing code duplication between sign
+    /// methods of the SigningKey implementation. This should remain private.
+    ///
+    /// The message is hashed with SHA2-256 and signed deterministically as per
+    /// [RFC6979](https://tools.ietf.org/html/rfc6979). The resulting signature is normalized to
+    /// its low-S form, which is the only form accepted by verification.
+    fn sign_arbitrary_message(&self, message: &[u8]) -> Secp256r1EcdsaSignature {
+        let sig: p256::ecdsa::Signature = self.0.sign(message);
+        Secp256r1EcdsaSignature(sig.normalize_s().unwrap_or(sig))
+    }
+}
+
+impl Secp256r1EcdsaPublicKey {
+    /// Serialize a Secp256r1EcdsaPublicKey in uncompressed SEC1 form.
+    pub fn to_bytes(&self) -> [u8; SECP256R1_ECDSA_PUBLIC_KEY_LENGTH] {
+        let mut bytes = [0u8; SECP256R1_ECDSA_PUBLIC_KEY_LENGTH];
+        bytes.copy_from_slice(self.0.to_encoded_point(false).as_bytes());
+        bytes
+    }
+}
+
+///////////////////////
+// PrivateKey Traits //
+///////////////////////
+
+impl PrivateKey for Secp256r1EcdsaPrivateKey {
+    type PublicKeyMaterial = Secp256r1EcdsaPublicKey;
+}
+
+impl SigningKey for Secp256r1EcdsaPrivateKey {
+    type VerifyingKeyMaterial = Secp256r1EcdsaPublicKey;
+    type SignatureMaterial = Secp256r1EcdsaSignature;
+
+    fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> Secp256r1EcdsaSignature {
+        Secp256r1EcdsaPrivateKey::sign_arbitrary_message(self, signing_message(message).as_ref())
+    }
+
+    #[cfg(any(test, feature = "fuzzing"))]
+    fn sign_arbitrary_message(&self, message: &[u8]) -> Secp256r1EcdsaSignature {
+        Secp256r1EcdsaPrivateKey::sign_arbitrary_message(self, message)
+    }
+}
+
+impl Uniform for Secp256r1EcdsaPrivateKey {
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: ::rand::RngCore + ::rand::CryptoRng + ::rand_core::CryptoRng + ::rand_core::RngCore,
+    {
+        // p256 depends on a newer version of rand_core than this crate, so we sample the scalar
+        // ourselves. Out of range values (zero or larger than the group order) are rejected,
+        // which happens with negligible probability.
+        loop {
+            let mut bytes = [0u8; SECP256R1_ECDSA_PRIVATE_KEY_LENGTH];
+            rng.fill_bytes(&mut bytes);
+            if let Ok(key) = Secp256r1EcdsaPrivateKey::try_from(&bytes[..]) {
+                return key;
+            }
+        }
+    }
+}
+
+impl PartialEq<Self> for Secp256r1EcdsaPrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for Secp256r1EcdsaPrivateKey {}
+
+impl TryFrom<&[u8]> for Secp256r1EcdsaPrivateKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a Secp256r1EcdsaPrivateKey. This method will check for private key validity:
+    /// i.e., correct key length and a non-zero scalar smaller than the group order.
+    fn try_from(
+        bytes: &[u8],
+    ) -> std::result::Result<Secp256r1EcdsaPrivateKey, CryptoMaterialError> {
+        if bytes.len() != SECP256R1_ECDSA_PRIVATE_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        p256::ecdsa::SigningKey::from_bytes(bytes)
+            .map(Secp256r1EcdsaPrivateKey)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+}
+
+impl Length for Secp256r1EcdsaPrivateKey {
+    fn length(&self) -> usize {
+        Self::LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for Secp256r1EcdsaPrivateKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl Genesis for Secp256r1EcdsaPrivateKey {
+    fn genesis() -> Self {
+        let mut buf = [0u8; SECP256R1_ECDSA_PRIVATE_KEY_LENGTH];
+        buf[SECP256R1_ECDSA_PRIVATE_KEY_LENGTH - 1] = 1;
+        Self::try_from(buf.as_ref()).unwrap()
+    }
+}
+
+//////////////////////
+// PublicKey Traits //
+//////////////////////
+
+// Implementing From<&PrivateKey<...>> allows to derive a public key in a more elegant fashion
+impl From<&Secp256r1EcdsaPrivateKey> for Secp256r1EcdsaPublicKey {
+    fn from(private_key: &Secp256r1EcdsaPrivateKey) -> Self {
+        Secp256r1EcdsaPublicKey(private_key.0.verifying_key())
+    }
+}
+
+// We deduce PublicKey from this
+impl PublicKey for Secp256r1EcdsaPublicKey {
+    type PrivateKeyMaterial = Secp256r1EcdsaPrivateKey;
+}
+
+impl std::hash::Hash for Secp256r1EcdsaPublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_pubkey = self.to_bytes();
+        state.write(&encoded_pubkey);
+    }
+}
+
+// Those are required by the implementation of hash above
+impl PartialEq for Secp256r1EcdsaPublicKey {
+    fn eq(&self, other: &Secp256r1EcdsaPublicKey) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for Secp256r1EcdsaPublicKey {}
+
+// We deduce VerifyingKey from pointing to the signature material
+// we get the ability to do `pubkey.validate(msg, signature)`
+impl VerifyingKey for Secp256r1EcdsaPublicKey {
+    type SigningKeyMaterial = Secp256r1EcdsaPrivateKey;
+    type SignatureMaterial = Secp256r1EcdsaSignature;
+}
+
+impl fmt::Display for Secp256r1EcdsaPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()[..]))
+    }
+}
+
+impl fmt::Debug for Secp256r1EcdsaPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secp256r1EcdsaPublicKey({})", self)
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256r1EcdsaPublicKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a Secp256r1EcdsaPublicKey from its uncompressed SEC1 form. This method checks
+    /// that the bytes encode a point on the curve other than the identity; since P-256 has a
+    /// prime order, no further subgroup check is needed.
+    fn try_from(bytes: &[u8]) -> std::result::Result<Secp256r1EcdsaPublicKey, CryptoMaterialError> {
+        if bytes.len() != SECP256R1_ECDSA_PUBLIC_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        p256::ecdsa::VerifyingKey::from_sec1_bytes(bytes)
+            .map(Secp256r1EcdsaPublicKey)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+}
+
+impl Length for Secp256r1EcdsaPublicKey {
+    fn length(&self) -> usize {
+        SECP256R1_ECDSA_PUBLIC_KEY_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for Secp256r1EcdsaPublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+/////////////
+// Fuzzing //
+/////////////
+
+/// Produces a uniformly random ECDSA P-256 keypair from a seed
+#[cfg(any(test, feature = "fuzzing"))]
+pub fn keypair_strategy(
+) -> impl Strategy<Value = KeyPair<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>> {
+    test_utils::uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>()
+}
+
+/// Produces a uniformly random ECDSA P-256 public key
+#[cfg(any(test, feature = "fuzzing"))]
+impl proptest::arbitrary::Arbitrary for Secp256r1EcdsaPublicKey {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        keypair_strategy().prop_map(|v| v.public_key).boxed()
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file implements traits for ECDSA P-256 signatures.
+
+use crate::{
+    hash::CryptoHash,
+    secp256r1_ecdsa::{
+        Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey, SECP256R1_ECDSA_SIGNATURE_LENGTH,
+    },
+    traits::*,
+};
+use anyhow::{anyhow, Result};
+use aptos_crypto_derive::{DeserializeKey, SerializeKey};
+use core::convert::TryFrom;
+use p256::ecdsa::signature::Verifier as _;
+use serde::Serialize;
+use std::fmt;
+
+/// An ECDSA P-256 signature
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct Secp256r1EcdsaSignature(pub(crate) p256::ecdsa::Signature);
+
+impl Secp256r1EcdsaSignature {
+    /// The length of the Secp256r1EcdsaSignature
+    pub const LENGTH: usize = SECP256R1_ECDSA_SIGNATURE_LENGTH;
+
+    /// Serialize a Secp256r1EcdsaSignature as `r || s`.
+    pub fn to_bytes(&self) -> [u8; SECP256R1_ECDSA_SIGNATURE_LENGTH] {
+        let mut bytes = [0u8; SECP256R1_ECDSA_SIGNATURE_LENGTH];
+        bytes.copy_from_slice(self.0.as_ref());
+        bytes
+    }
+
+    /// Deserialize a Secp256r1EcdsaSignature without any validation checks (malleability)
+    /// apart from expected signature size and non-zero components.
+    pub(crate) fn from_bytes_unchecked(
+        bytes: &[u8],
+    ) -> std::result::Result<Secp256r1EcdsaSignature, CryptoMaterialError> {
+        match p256::ecdsa::Signature::try_from(bytes) {
+            Ok(signature) => Ok(Secp256r1EcdsaSignature(signature)),
+            Err(_) => Err(CryptoMaterialError::DeserializationError),
+        }
+    }
+
+    /// return a signature with both components set to one (for test only)
+    #[cfg(any(test, feature = "fuzzing"))]
+    pub fn dummy_signature() -> Self {
+        let mut bytes = [0u8; Self::LENGTH];
+        bytes[Self::LENGTH / 2 - 1] = 1;
+        bytes[Self::LENGTH - 1] = 1;
+        Self::from_bytes_unchecked(&bytes).unwrap()
+    }
+
+    /// Check for correct size and third-party based signature malleability issues.
+    ///
+    /// For any valid ECDSA signature (r, s), the signature (r, n - s), where n is the order of the
+    /// curve group, is valid too. To prevent signature malleability, we only accept the signature
+    /// whose s-component is in the lower half of the range, i.e., s <= n / 2.
+    pub fn check_s_malleability(bytes: &[u8]) -> std::result::Result<(), CryptoMaterialError> {
+        if bytes.len() != SECP256R1_ECDSA_SIGNATURE_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        let signature = Secp256r1EcdsaSignature::from_bytes_unchecked(bytes)?;
+        // `normalize_s` returns a new signature only if s is in the upper half of the range.
+        if signature.0.normalize_s().is_some() {
+            return Err(CryptoMaterialError::CanonicalRepresentationError);
+        }
+        Ok(())
+    }
+}
+
+//////////////////////
+// Signature Traits //
+//////////////////////
+
+impl Signature for Secp256r1EcdsaSignature {
+    type VerifyingKeyMaterial = Secp256r1EcdsaPublicKey;
+    type SigningKeyMaterial = Secp256r1EcdsaPrivateKey;
+
+    /// Verifies that the provided signature is valid for the provided message, rejecting
+    /// signatures with a high s-component
+    /// (see [Secp256r1EcdsaSignature::check_s_malleability][Secp256r1EcdsaSignature::check_s_malleability]).
+    fn verify<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_key: &Secp256r1EcdsaPublicKey,
+    ) -> Result<()> {
+        Self::verify_arbitrary_msg(self, &signing_message(message), public_key)
+    }
+
+    /// Checks that `self` is valid for an arbitrary &[u8] `message` using `public_key`. The
+    /// message is hashed with SHA2-256 as part of the verification.
+    fn verify_arbitrary_msg(
+        &self,
+        message: &[u8],
+        public_key: &Secp256r1EcdsaPublicKey,
+    ) -> Result<()> {
+        Secp256r1EcdsaSignature::check_s_malleability(&self.to_bytes())?;
+
+        public_key
+            .0
+            .verify(message, &self.0)
+            .map_err(|e| anyhow!("{}", e))
+            .and(Ok(()))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl Length for Secp256r1EcdsaSignature {
+    fn length(&self) -> usize {
+        SECP256R1_ECDSA_SIGNATURE_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for Secp256r1EcdsaSignature {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl std::hash::Hash for Secp256r1EcdsaSignature {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_signature = self.to_bytes();
+        state.write(&encoded_signature);
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256r1EcdsaSignature {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<Secp256r1EcdsaSignature, CryptoMaterialError> {
+        Secp256r1EcdsaSignature::check_s_malleability(bytes)?;
+        Secp256r1EcdsaSignature::from_bytes_unchecked(bytes)
+    }
+}
+
+// Those are required by the implementation of hash above
+impl PartialEq for Secp256r1EcdsaSignature {
+    fn eq(&self, other: &Secp256r1EcdsaSignature) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for Secp256r1EcdsaSignature {}
+
+impl fmt::Display for Secp256r1EcdsaSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()[..]))
+    }
+}
+
+impl fmt::Debug for Secp256r1EcdsaSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secp256r1EcdsaSignature({})", self)
+    }
+}
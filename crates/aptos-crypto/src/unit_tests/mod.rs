@@ -11,3 +11,4 @@ mod hash_test;
 mod hkdf_test;
 mod multi_ed25519_test;
 mod noise_test;
+mod secp256r1_ecdsa_test;
@@ -0,0 +1,129 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    secp256r1_ecdsa::{
+        Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey, Secp256r1EcdsaSignature,
+        SECP256R1_ECDSA_PRIVATE_KEY_LENGTH, SECP256R1_ECDSA_PUBLIC_KEY_LENGTH,
+        SECP256R1_ECDSA_SIGNATURE_LENGTH,
+    },
+    test_utils::{random_serializable_struct, uniform_keypair_strategy},
+    traits::*,
+};
+
+use core::convert::TryFrom;
+use p256::ecdsa::signature::Verifier as _;
+use proptest::{collection::vec, prelude::*};
+
+proptest! {
+    #[test]
+    fn test_keys_encode(keypair in uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>()) {
+        {
+            let encoded = keypair.private_key.to_encoded_string().unwrap();
+            prop_assert_eq!(2 + 2 * SECP256R1_ECDSA_PRIVATE_KEY_LENGTH, encoded.len());
+            let decoded = Secp256r1EcdsaPrivateKey::from_encoded_string(&encoded);
+            prop_assert_eq!(Some(keypair.private_key), decoded.ok());
+        }
+        {
+            let encoded = keypair.public_key.to_encoded_string().unwrap();
+            prop_assert_eq!(2 + 2 * SECP256R1_ECDSA_PUBLIC_KEY_LENGTH, encoded.len());
+            let decoded = Secp256r1EcdsaPublicKey::from_encoded_string(&encoded);
+            prop_assert_eq!(Some(keypair.public_key), decoded.ok());
+        }
+    }
+
+    #[test]
+    fn test_keys_custom_serialisation(
+        keypair in uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>()
+    ) {
+        {
+            let serialized: &[u8] = &(keypair.private_key.to_bytes());
+            prop_assert_eq!(SECP256R1_ECDSA_PRIVATE_KEY_LENGTH, serialized.len());
+            let deserialized = Secp256r1EcdsaPrivateKey::try_from(serialized);
+            prop_assert_eq!(Some(keypair.private_key), deserialized.ok());
+        }
+        {
+            let serialized: &[u8] = &(keypair.public_key.to_bytes());
+            prop_assert_eq!(SECP256R1_ECDSA_PUBLIC_KEY_LENGTH, serialized.len());
+            let deserialized = Secp256r1EcdsaPublicKey::try_from(serialized);
+            prop_assert_eq!(Some(keypair.public_key), deserialized.ok());
+        }
+    }
+
+    #[test]
+    fn test_signature_verification_custom_serialisation(
+        message in random_serializable_struct(),
+        keypair in uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>()
+    ) {
+        let signature = keypair.private_key.sign(&message);
+        let serialized: &[u8] = &(signature.to_bytes());
+        prop_assert_eq!(SECP256R1_ECDSA_SIGNATURE_LENGTH, serialized.len());
+        let deserialized = Secp256r1EcdsaSignature::try_from(serialized).unwrap();
+        prop_assert!(deserialized.verify(&message, &keypair.public_key).is_ok());
+    }
+
+    #[test]
+    fn test_signature_verification_from_arbitrary(
+        msg in vec(proptest::num::u8::ANY, 1..128),
+        keypair in uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>()
+    ) {
+        let signature = keypair.private_key.sign_arbitrary_message(&msg);
+        prop_assert!(signature.verify_arbitrary_msg(&msg, &keypair.public_key).is_ok());
+        // Signatures produced by this crate are interoperable with plain ES256 verification.
+        prop_assert!(keypair.public_key.0.verify(&msg, &signature.0).is_ok());
+
+        let mut other_msg = msg.clone();
+        other_msg[0] ^= 1;
+        prop_assert!(signature.verify_arbitrary_msg(&other_msg, &keypair.public_key).is_err());
+    }
+
+    // Check for low S.
+    #[test]
+    fn test_signature_malleability(
+        message in random_serializable_struct(),
+        keypair in uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>()
+    ) {
+        let signature = keypair.private_key.sign(&message);
+        prop_assert!(signature.0.normalize_s().is_none());
+
+        // (r, n - s) is a valid ECDSA signature, but must be rejected as non-canonical.
+        let (r, s) = signature.0.split_scalars();
+        let mauled = p256::ecdsa::Signature::from_scalars(*r, -*s).unwrap();
+        prop_assert!(keypair.public_key.0.verify(
+            &signing_message(&message),
+            &mauled,
+        ).is_ok());
+
+        let mauled_bytes: &[u8] = mauled.as_ref();
+        prop_assert_eq!(
+            Secp256r1EcdsaSignature::try_from(mauled_bytes),
+            Err(CryptoMaterialError::CanonicalRepresentationError)
+        );
+        let mauled = Secp256r1EcdsaSignature(mauled);
+        prop_assert!(mauled.verify(&message, &keypair.public_key).is_err());
+    }
+}
+
+#[test]
+fn test_invalid_public_key_encodings() {
+    // Wrong length.
+    assert_eq!(
+        Secp256r1EcdsaPublicKey::try_from(&[4u8; 33][..]),
+        Err(CryptoMaterialError::WrongLengthError)
+    );
+    // Not a point on the curve.
+    let mut bytes = [0u8; SECP256R1_ECDSA_PUBLIC_KEY_LENGTH];
+    bytes[0] = 4;
+    bytes[SECP256R1_ECDSA_PUBLIC_KEY_LENGTH - 1] = 1;
+    assert_eq!(
+        Secp256r1EcdsaPublicKey::try_from(&bytes[..]),
+        Err(CryptoMaterialError::DeserializationError)
+    );
+}
+
+#[test]
+fn test_invalid_private_key_encodings() {
+    assert!(Secp256r1EcdsaPrivateKey::try_from(&[0u8; 32][..]).is_err());
+    assert!(Secp256r1EcdsaPrivateKey::try_from(&[0xffu8; 32][..]).is_err());
+    assert!(Secp256r1EcdsaPrivateKey::try_from(&[1u8; 31][..]).is_err());
+}
@@ -8,7 +8,10 @@ use crate::{
     GenesisInfo,
 };
 use anyhow::ensure;
-use aptos_config::config::RocksDbStorageConfig;
+use aptos_config::config::{
+    RocksDbStorageConfig, RocksdbConfigs, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    NO_OP_STORAGE_PRUNER_CONFIG, TARGET_SNAPSHOT_SIZE,
+};
 use aptos_config::keys::ConfigKey;
 use aptos_config::{
     config::{
@@ -25,7 +28,15 @@ use aptos_crypto::{
 };
 use aptos_keygen::KeyGen;
 use aptos_logger::prelude::*;
-use aptos_types::{chain_id::ChainId, transaction::Transaction, waypoint::Waypoint};
+use aptos_types::{
+    account_address::AccountAddress,
+    chain_id::ChainId,
+    transaction::{authenticator::AuthenticationKey, Transaction, WriteSetPayload},
+    waypoint::Waypoint,
+};
+use aptos_vm::AptosVM;
+use aptos_writeset_generator::{build_fork_changeset, ForkValidator};
+use aptosdb::AptosDB;
 use framework::ReleaseBundle;
 use rand::Rng;
 use serde::{de::DeserializeOwned, Serialize};
@@ -37,6 +48,8 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
+use storage_interface::{state_view::DbStateViewAtVersion, DbReader, DbReaderWriter};
+use vm_genesis::Validator;
 
 const VALIDATOR_IDENTITY: &str = "validator-identity.yaml";
 const VFN_IDENTITY: &str = "vfn-identity.yaml";
@@ -138,6 +151,18 @@ impl ValidatorNodeConfig {
         }
     }
 
+    /// Takes over the identity of `account_address`, e.g. the stake pool this validator runs in a
+    /// forked network
+    fn set_account_address(&mut self, account_address: AccountAddress) -> anyhow::Result<()> {
+        for identity_file in [VALIDATOR_IDENTITY, VFN_IDENTITY] {
+            let identity_file = self.dir.join(identity_file);
+            let mut identity: IdentityBlob = read_yaml(identity_file.as_path())?;
+            identity.account_address = Some(account_address);
+            write_yaml(identity_file.as_path(), &identity)?;
+        }
+        Ok(())
+    }
+
     fn insert_genesis(&mut self, genesis: &Transaction) {
         self.config.execution.genesis = Some(genesis.clone());
         self.config.execution.genesis_file_location = self.dir.join(GENESIS_BLOB)
@@ -376,6 +401,19 @@ fn write_yaml<T: Serialize>(path: &Path, object: &T) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Opens the db in `db_dir` for writing, without the pruner or the indexer
+fn open_db(db_dir: &Path) -> anyhow::Result<AptosDB> {
+    AptosDB::open(
+        db_dir,
+        false, /* readonly */
+        NO_OP_STORAGE_PRUNER_CONFIG,
+        RocksdbConfigs::default(),
+        false, /* indexer */
+        TARGET_SNAPSHOT_SIZE,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    )
+}
+
 const ONE_DAY: u64 = 86400;
 
 #[derive(Clone)]
@@ -389,6 +427,7 @@ pub struct GenesisConfiguration {
     pub recurring_lockup_duration_secs: u64,
     pub required_proposer_stake: u64,
     pub rewards_apy_percentage: u64,
+    pub transaction_fee_burn_percentage: Option<u8>,
     pub voting_duration_secs: u64,
     pub voting_power_increase_limit: u64,
 }
@@ -486,6 +525,72 @@ impl Builder {
         Ok((root_key, genesis, waypoint, validators))
     }
 
+    /// Build validators which take over the chain restored in `db_dir`, and save their configs
+    ///
+    /// Rather than a genesis transaction, the validators get a fork transaction which hands the
+    /// validator set of the restored chain over to them, and switches it to `chain_id`.  Each
+    /// validator runs on its own checkpoint of the restored db, on top of which the fork
+    /// transaction is applied when the node starts.
+    pub fn build_fork<R>(
+        mut self,
+        db_dir: &Path,
+        chain_id: ChainId,
+        mut rng: R,
+    ) -> anyhow::Result<(Transaction, Waypoint, Vec<ValidatorNodeConfig>)>
+    where
+        R: rand::RngCore + rand::CryptoRng,
+    {
+        info!(
+            "Forking {:?} with {:?} validators. Directory of output: {:?}",
+            db_dir,
+            self.num_validators.get(),
+            self.config_dir
+        );
+
+        // Generate validator configs
+        let template = NodeConfig::default_for_validator();
+        let mut validators: Vec<ValidatorNodeConfig> = (0..self.num_validators.get())
+            .map(|i| self.generate_validator_config(i, &mut rng, &template))
+            .collect::<anyhow::Result<Vec<ValidatorNodeConfig>>>()?;
+        let mut fork_validators = Vec::new();
+        for validator in validators.iter() {
+            let configuration: ValidatorConfiguration = validator.try_into()?;
+            let voter_authentication_key =
+                AuthenticationKey::ed25519(&configuration.voter_account_public_key);
+            let validator = Validator::try_from(configuration)?;
+            fork_validators.push(ForkValidator {
+                consensus_pubkey: validator.consensus_pubkey,
+                proof_of_possession: validator.proof_of_possession,
+                network_addresses: validator.network_addresses,
+                full_node_network_addresses: validator.full_node_network_addresses,
+                voter_authentication_key: Some(voter_authentication_key),
+            });
+        }
+
+        // Build the fork on top of the latest state of the restored chain
+        let db = Arc::new(open_db(db_dir)?);
+        let reader: Arc<dyn DbReader> = db.clone();
+        let version = reader.get_latest_version()?;
+        let state_view = reader.state_view_at_version(Some(version))?;
+        let (change_set, pool_addresses) =
+            build_fork_changeset(&state_view, chain_id, &fork_validators)?;
+        let fork = Transaction::GenesisTransaction(WriteSetPayload::Direct(change_set));
+        let waypoint = executor::db_bootstrapper::generate_waypoint::<AptosVM>(
+            &DbReaderWriter::from_arc(db.clone()),
+            &fork,
+        )?;
+
+        for (validator, pool_address) in validators.iter_mut().zip(pool_addresses) {
+            validator.set_account_address(pool_address)?;
+            db.create_checkpoint(validator.config.storage.dir())?;
+            validator.insert_waypoint(&waypoint);
+            validator.insert_genesis(&fork);
+            validator.save_config()?;
+        }
+
+        Ok((fork, waypoint, validators))
+    }
+
     /// Generate a configuration for a single validator
     fn generate_validator_config<R>(
         &mut self,
@@ -581,6 +686,7 @@ impl Builder {
             recurring_lockup_duration_secs: ONE_DAY,
             required_proposer_stake: 0,
             rewards_apy_percentage: 10,
+            transaction_fee_burn_percentage: None,
             voting_duration_secs: ONE_DAY / 24,
             voting_power_increase_limit: 50,
         };
@@ -609,3 +715,107 @@ impl Builder {
         Ok((genesis.clone(), waypoint))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_state_view::account_with_state_view::AsAccountWithStateView;
+    use aptos_temppath::TempPath;
+    use aptos_types::{account_config::CORE_CODE_ADDRESS, account_view::AccountView};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn epoch_at_version(reader: &Arc<dyn DbReader>, version: u64) -> u64 {
+        reader
+            .state_view_at_version(Some(version))
+            .unwrap()
+            .as_account_with_state_view(&CORE_CODE_ADDRESS)
+            .get_configuration_resource()
+            .unwrap()
+            .unwrap()
+            .epoch()
+    }
+
+    fn temp_dir() -> TempPath {
+        let dir = TempPath::new();
+        dir.create_as_dir().unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_build_fork() {
+        let framework = cached_packages::head_release_bundle().clone();
+        let mut rng = StdRng::from_seed([0u8; 32]);
+
+        // Start a chain with more validators than the fork will have
+        let genesis_dir = temp_dir();
+        let (_, genesis, waypoint, _) = Builder::new(genesis_dir.path(), framework.clone())
+            .unwrap()
+            .with_num_validators(NonZeroUsize::new(3).unwrap())
+            .build(&mut rng)
+            .unwrap();
+        let db_dir = temp_dir();
+        let genesis_epoch = {
+            let db = DbReaderWriter::new(open_db(db_dir.path()).unwrap());
+            assert!(
+                executor::db_bootstrapper::maybe_bootstrap::<AptosVM>(&db, &genesis, waypoint)
+                    .unwrap()
+            );
+            epoch_at_version(&db.reader, waypoint.version())
+        };
+
+        // Building the fork generates its waypoint on top of the chain
+        let fork_dir = temp_dir();
+        let chain_id = ChainId::new(42);
+        let (fork, fork_waypoint, validators) = Builder::new(fork_dir.path(), framework)
+            .unwrap()
+            .with_num_validators(NonZeroUsize::new(2).unwrap())
+            .build_fork(db_dir.path(), chain_id, &mut rng)
+            .unwrap();
+        assert_eq!(2, validators.len());
+        assert_eq!(waypoint.version() + 1, fork_waypoint.version());
+
+        // Every validator can start from the fork on its own checkpoint
+        for validator in &validators {
+            let db = DbReaderWriter::new(open_db(&validator.config.storage.dir()).unwrap());
+            assert!(executor::db_bootstrapper::maybe_bootstrap::<AptosVM>(
+                &db,
+                &fork,
+                fork_waypoint
+            )
+            .unwrap());
+        }
+
+        let reader: Arc<dyn DbReader> =
+            Arc::new(open_db(&validators[0].config.storage.dir()).unwrap());
+        let state_view = reader
+            .state_view_at_version(Some(fork_waypoint.version()))
+            .unwrap();
+        let framework_account = state_view.as_account_with_state_view(&CORE_CODE_ADDRESS);
+
+        // A new epoch started with only the fork's validators, using their consensus keys
+        assert_eq!(
+            genesis_epoch + 1,
+            epoch_at_version(&reader, fork_waypoint.version())
+        );
+        let validator_set = framework_account.get_validator_set().unwrap().unwrap();
+        let consensus_keys: Vec<_> = validator_set
+            .payload()
+            .map(|info| info.consensus_public_key().clone())
+            .collect();
+        assert_eq!(validators.len(), consensus_keys.len());
+        for validator in &validators {
+            let configuration = ValidatorConfiguration::try_from(validator).unwrap();
+            assert!(consensus_keys.contains(&configuration.consensus_public_key));
+        }
+
+        // The fork runs as its own chain
+        assert_eq!(
+            chain_id,
+            framework_account
+                .get_chain_id_resource()
+                .unwrap()
+                .unwrap()
+                .chain_id()
+        );
+    }
+}
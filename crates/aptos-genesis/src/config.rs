@@ -49,6 +49,10 @@ pub struct Layout {
     pub required_proposer_stake: u64,
     /// Percentage of stake given out as rewards a year (0-100%).
     pub rewards_apy_percentage: u64,
+    /// Percentage of collected transaction fees to burn. If not set, transaction fees are
+    /// not distributed to block proposers and are burnt entirely.
+    #[serde(default)]
+    pub transaction_fee_burn_percentage: Option<u8>,
     /// Voting duration for a proposal in seconds.
     pub voting_duration_secs: u64,
     /// % of current epoch's total voting power that can be added in this epoch.
@@ -85,6 +89,7 @@ impl Default for Layout {
             recurring_lockup_duration_secs: 86_400,
             required_proposer_stake: 100_000_000_000_000,
             rewards_apy_percentage: 10,
+            transaction_fee_burn_percentage: None,
             voting_duration_secs: 43_200,
             voting_power_increase_limit: 20,
         }
@@ -127,13 +132,10 @@ impl TryFrom<ValidatorConfiguration> for Validator {
     fn try_from(config: ValidatorConfiguration) -> Result<Self, Self::Error> {
         let validator_addresses = vec![config
             .validator_host
-            .as_network_address(config.validator_network_public_key)
-            .unwrap()];
+            .as_network_address(config.validator_network_public_key)?];
         let full_node_addresses = if let Some(full_node_host) = config.full_node_host {
             if let Some(full_node_network_key) = config.full_node_network_public_key {
-                vec![full_node_host
-                    .as_network_address(full_node_network_key)
-                    .unwrap()]
+                vec![full_node_host.as_network_address(full_node_network_key)?]
             } else {
                 return Err(anyhow::Error::msg(
                     "Full node host specified, but not full node network key",
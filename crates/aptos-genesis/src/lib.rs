@@ -6,6 +6,7 @@
 pub mod builder;
 pub mod config;
 pub mod keys;
+pub mod spec;
 
 #[cfg(any(test, feature = "testing"))]
 pub mod test_utils;
@@ -17,13 +18,18 @@ use aptos_config::config::{
 };
 use aptos_crypto::ed25519::Ed25519PublicKey;
 use aptos_temppath::TempPath;
-use aptos_types::{chain_id::ChainId, transaction::Transaction, waypoint::Waypoint};
+use aptos_types::{
+    chain_id::ChainId,
+    on_chain_config::{GasSchedule, OnChainConsensusConfig},
+    transaction::{Transaction, WriteSetPayload},
+    waypoint::Waypoint,
+};
 use aptos_vm::AptosVM;
 use aptosdb::AptosDB;
 use framework::ReleaseBundle;
 use std::convert::TryInto;
 use storage_interface::DbReaderWriter;
-use vm_genesis::Validator;
+use vm_genesis::{AccountBalance, Validator};
 
 /// Holder object for all pieces needed to generate a genesis transaction
 #[derive(Clone)]
@@ -34,6 +40,12 @@ pub struct GenesisInfo {
     root_key: Ed25519PublicKey,
     /// Set of configurations for validators on the network
     validators: Vec<Validator>,
+    /// Accounts to create at genesis, with their initial balances
+    pub accounts: Vec<AccountBalance>,
+    /// On-chain consensus config at genesis
+    pub consensus_config: OnChainConsensusConfig,
+    /// On-chain gas schedule at genesis
+    pub gas_schedule: GasSchedule,
     /// Released framework packages
     framework: ReleaseBundle,
     /// The genesis transaction, once it's been generated
@@ -56,6 +68,8 @@ pub struct GenesisInfo {
     pub required_proposer_stake: u64,
     /// Percentage of stake given out as rewards a year (0-100%).
     pub rewards_apy_percentage: u64,
+    /// Percentage of collected transaction fees to burn, if fees are distributed to block proposers.
+    pub transaction_fee_burn_percentage: Option<u8>,
     /// Voting duration for a proposal in seconds.
     pub voting_duration_secs: u64,
     /// Percent of current epoch's total voting power that can be added in this epoch.
@@ -80,6 +94,9 @@ impl GenesisInfo {
             chain_id,
             root_key,
             validators,
+            accounts: vec![],
+            consensus_config: OnChainConsensusConfig::default(),
+            gas_schedule: vm_genesis::default_gas_schedule(),
             framework,
             genesis: None,
             allow_new_validators: genesis_config.allow_new_validators,
@@ -91,6 +108,7 @@ impl GenesisInfo {
             recurring_lockup_duration_secs: genesis_config.recurring_lockup_duration_secs,
             required_proposer_stake: genesis_config.required_proposer_stake,
             rewards_apy_percentage: genesis_config.rewards_apy_percentage,
+            transaction_fee_burn_percentage: genesis_config.transaction_fee_burn_percentage,
             voting_duration_secs: genesis_config.voting_duration_secs,
             voting_power_increase_limit: genesis_config.voting_power_increase_limit,
         })
@@ -106,12 +124,15 @@ impl GenesisInfo {
     }
 
     fn generate_genesis_txn(&self) -> Transaction {
-        vm_genesis::encode_genesis_transaction(
-            self.root_key.clone(),
+        let change_set = vm_genesis::encode_genesis_change_set(
+            &self.root_key,
+            &self.accounts,
             &self.validators,
             &self.framework,
+            self.consensus_config.clone(),
+            &self.gas_schedule,
             self.chain_id,
-            vm_genesis::GenesisConfiguration {
+            &vm_genesis::GenesisConfiguration {
                 allow_new_validators: self.allow_new_validators,
                 epoch_duration_secs: self.epoch_duration_secs,
                 is_test: true,
@@ -121,10 +142,12 @@ impl GenesisInfo {
                 recurring_lockup_duration_secs: self.recurring_lockup_duration_secs,
                 required_proposer_stake: self.required_proposer_stake,
                 rewards_apy_percentage: self.rewards_apy_percentage,
+                transaction_fee_burn_percentage: self.transaction_fee_burn_percentage,
                 voting_duration_secs: self.voting_duration_secs,
                 voting_power_increase_limit: self.voting_power_increase_limit,
             },
-        )
+        );
+        Transaction::GenesisTransaction(WriteSetPayload::Direct(change_set))
     }
 
     pub fn generate_waypoint(&mut self) -> anyhow::Result<Waypoint> {
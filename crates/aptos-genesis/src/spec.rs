@@ -0,0 +1,357 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A single declarative file describing everything that goes into genesis, so a network can be
+//! created without going through the git based genesis ceremony.
+
+use crate::{builder::GenesisConfiguration, config::ValidatorConfiguration, GenesisInfo};
+use aptos_crypto::ed25519::Ed25519PublicKey;
+use aptos_gas::{AptosGasParameters, FromOnChainGasSchedule};
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::aptos_test_root_address,
+    chain_id::ChainId,
+    on_chain_config::{GasSchedule, OnChainConsensusConfig},
+};
+use framework::ReleaseBundle;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    convert::TryFrom,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+};
+use vm_genesis::{AccountBalance, Validator};
+
+/// Highest address reserved for the framework, e.g. `0x1` for `aptos_framework`
+const MAX_RESERVED_ADDRESS: u8 = 0xa;
+
+/// Everything needed to build a genesis transaction, usually read from a YAML file
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisSpec {
+    /// ChainId for the target network
+    pub chain_id: ChainId,
+    /// Root key for the blockchain
+    /// TODO: In the future, we won't need a root key
+    pub root_key: Ed25519PublicKey,
+    /// Path to the framework release bundle, relative to the spec file.  If not set, the
+    /// framework built into this binary is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framework: Option<PathBuf>,
+    /// Whether to allow new validators to join the set after genesis
+    #[serde(default)]
+    pub allow_new_validators: bool,
+    /// Duration of an epoch
+    pub epoch_duration_secs: u64,
+    /// Minimum stake to be in the validator set
+    pub min_stake: u64,
+    /// Minimum number of votes to consider a proposal valid.
+    pub min_voting_threshold: u128,
+    /// Maximum stake to be in the validator set
+    pub max_stake: u64,
+    /// Minimum number of seconds to lockup staked coins
+    pub recurring_lockup_duration_secs: u64,
+    /// Required amount of stake to create proposals.
+    pub required_proposer_stake: u64,
+    /// Percentage of stake given out as rewards a year (0-100%).
+    pub rewards_apy_percentage: u64,
+    /// Percentage of collected transaction fees to burn. If not set, transaction fees are
+    /// not distributed to block proposers and are burnt entirely.
+    #[serde(default)]
+    pub transaction_fee_burn_percentage: Option<u8>,
+    /// Voting duration for a proposal in seconds.
+    pub voting_duration_secs: u64,
+    /// % of current epoch's total voting power that can be added in this epoch.
+    pub voting_power_increase_limit: u64,
+    /// Accounts to create at genesis, along with their initial balances
+    #[serde(default)]
+    pub accounts: Vec<AccountBalance>,
+    /// Initial validator set, each of which is minted and stakes its `stake_amount`
+    pub validators: Vec<ValidatorConfiguration>,
+    /// On-chain consensus config, defaults to `OnChainConsensusConfig::default()`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consensus_config: Option<OnChainConsensusConfig>,
+    /// On-chain gas schedule, defaults to the initial gas schedule of this binary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_schedule: Option<GasSchedule>,
+}
+
+impl GenesisSpec {
+    /// Read the spec from a YAML file on disk
+    pub fn from_disk(path: &Path) -> anyhow::Result<Self> {
+        let mut file = File::open(&path).map_err(|e| {
+            anyhow::Error::msg(format!("Failed to open file {}, {}", path.display(), e))
+        })?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).map_err(|e| {
+            anyhow::Error::msg(format!("Failed to read file {}, {}", path.display(), e))
+        })?;
+
+        Ok(serde_yaml::from_str(&contents)?)
+    }
+
+    /// Loads the framework release bundle, resolving its path relative to `base_dir`
+    pub fn load_framework(&self, base_dir: &Path) -> anyhow::Result<ReleaseBundle> {
+        if let Some(ref path) = self.framework {
+            ReleaseBundle::read(base_dir.join(path))
+        } else {
+            Ok(cached_packages::head_release_bundle().clone())
+        }
+    }
+
+    pub fn genesis_config(&self) -> GenesisConfiguration {
+        GenesisConfiguration {
+            allow_new_validators: self.allow_new_validators,
+            epoch_duration_secs: self.epoch_duration_secs,
+            is_test: true,
+            min_stake: self.min_stake,
+            min_voting_threshold: self.min_voting_threshold,
+            max_stake: self.max_stake,
+            recurring_lockup_duration_secs: self.recurring_lockup_duration_secs,
+            required_proposer_stake: self.required_proposer_stake,
+            rewards_apy_percentage: self.rewards_apy_percentage,
+            transaction_fee_burn_percentage: self.transaction_fee_burn_percentage,
+            voting_duration_secs: self.voting_duration_secs,
+            voting_power_increase_limit: self.voting_power_increase_limit,
+        }
+    }
+
+    /// Checks the whole spec, returning every problem found rather than stopping at the first.
+    /// Genesis is only guaranteed to succeed if this returns no errors.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = self.validate_config();
+        errors.append(&mut self.validate_validators());
+        errors.append(&mut self.validate_accounts());
+        if let Some(ref gas_schedule) = self.gas_schedule {
+            errors.append(&mut validate_gas_schedule(gas_schedule));
+        }
+        errors
+    }
+
+    /// Mirrors the checks `vm_genesis` makes on the `GenesisConfiguration`
+    fn validate_config(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.min_stake > self.max_stake {
+            errors.push(format!(
+                "min_stake {} must be smaller than or equal to max_stake {}",
+                self.min_stake, self.max_stake
+            ));
+        }
+        if self.epoch_duration_secs == 0 {
+            errors.push("epoch_duration_secs must be > 0".to_string());
+        }
+        if self.recurring_lockup_duration_secs < self.epoch_duration_secs {
+            errors.push(format!(
+                "recurring_lockup_duration_secs {} must be at least as long as epoch_duration_secs {}",
+                self.recurring_lockup_duration_secs, self.epoch_duration_secs
+            ));
+        }
+        if self.rewards_apy_percentage == 0 || self.rewards_apy_percentage >= 100 {
+            errors.push(format!(
+                "rewards_apy_percentage {} must be > 0 and < 100",
+                self.rewards_apy_percentage
+            ));
+        }
+        if self.voting_duration_secs == 0
+            || self.voting_duration_secs >= self.recurring_lockup_duration_secs
+        {
+            errors.push(format!(
+                "voting_duration_secs {} must be > 0 and strictly smaller than recurring_lockup_duration_secs {}",
+                self.voting_duration_secs, self.recurring_lockup_duration_secs
+            ));
+        }
+        if self.voting_power_increase_limit == 0 || self.voting_power_increase_limit > 50 {
+            errors.push(format!(
+                "voting_power_increase_limit {} must be > 0 and <= 50",
+                self.voting_power_increase_limit
+            ));
+        }
+        if let Some(burn_percentage) = self.transaction_fee_burn_percentage {
+            if burn_percentage > 100 {
+                errors.push(format!(
+                    "transaction_fee_burn_percentage {} must be <= 100",
+                    burn_percentage
+                ));
+            }
+        }
+        errors
+    }
+
+    fn validate_validators(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.validators.is_empty() {
+            errors.push("At least one validator is required".to_string());
+        }
+
+        let mut accounts = HashSet::new();
+        let mut consensus_keys = HashSet::new();
+        let mut network_keys = HashSet::new();
+        let mut hosts = HashSet::new();
+        for (i, validator) in self.validators.iter().enumerate() {
+            let name = format!("validators[{}]", i);
+
+            if validator.stake_amount < self.min_stake || validator.stake_amount > self.max_stake {
+                errors.push(format!(
+                    "{}: stake_amount {} must be between min_stake {} and max_stake {}",
+                    name, validator.stake_amount, self.min_stake, self.max_stake
+                ));
+            }
+
+            // Owner, operator and voter may be the same account, but each account is created
+            // once so it can't be shared between validators
+            let validator_accounts: HashSet<_> = [
+                validator.owner_account_address,
+                validator.operator_account_address,
+                validator.voter_account_address,
+            ]
+            .iter()
+            .copied()
+            .collect();
+            for address in validator_accounts {
+                if is_reserved_address(&address) {
+                    errors.push(format!("{}: account {} is reserved", name, address));
+                }
+                if !accounts.insert(address) {
+                    errors.push(format!(
+                        "{}: account {} is used by another validator",
+                        name, address
+                    ));
+                }
+            }
+
+            if !consensus_keys.insert(validator.consensus_public_key.to_bytes().to_vec()) {
+                errors.push(format!(
+                    "{}: consensus_public_key is used by another validator",
+                    name
+                ));
+            }
+            if let Err(err) = validator
+                .proof_of_possession
+                .verify(&validator.consensus_public_key)
+            {
+                errors.push(format!(
+                    "{}: proof_of_possession does not match consensus_public_key: {}",
+                    name, err
+                ));
+            }
+
+            if !network_keys.insert(validator.validator_network_public_key.as_slice().to_vec()) {
+                errors.push(format!(
+                    "{}: validator_network_public_key is used by another node",
+                    name
+                ));
+            }
+            if let Some(ref key) = validator.full_node_network_public_key {
+                if !network_keys.insert(key.as_slice().to_vec()) {
+                    errors.push(format!(
+                        "{}: full_node_network_public_key is used by another node",
+                        name
+                    ));
+                }
+            }
+
+            let validator_host = format!(
+                "{}:{}",
+                validator.validator_host.host, validator.validator_host.port
+            );
+            if !hosts.insert(validator_host.clone()) {
+                errors.push(format!(
+                    "{}: validator_host {} is used by another node",
+                    name, validator_host
+                ));
+            }
+            if let Some(ref full_node_host) = validator.full_node_host {
+                let full_node_host = format!("{}:{}", full_node_host.host, full_node_host.port);
+                if !hosts.insert(full_node_host.clone()) {
+                    errors.push(format!(
+                        "{}: full_node_host {} is used by another node",
+                        name, full_node_host
+                    ));
+                }
+            }
+
+            // Checks the account keys match their addresses, and builds the network addresses
+            if let Err(err) = Validator::try_from(validator.clone()) {
+                errors.push(format!("{}: {}", name, err));
+            }
+        }
+        errors
+    }
+
+    fn validate_accounts(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let validator_accounts: HashSet<_> = self
+            .validators
+            .iter()
+            .flat_map(|validator| {
+                [
+                    validator.owner_account_address,
+                    validator.operator_account_address,
+                    validator.voter_account_address,
+                ]
+            })
+            .collect();
+
+        let mut accounts = HashSet::new();
+        for (i, account) in self.accounts.iter().enumerate() {
+            let name = format!("accounts[{}]", i);
+            let address = account.account_address;
+            if is_reserved_address(&address) || address == aptos_test_root_address() {
+                errors.push(format!("{}: account {} is reserved", name, address));
+            }
+            if validator_accounts.contains(&address) {
+                errors.push(format!(
+                    "{}: account {} is already created for a validator, its coins should be part of the validator's stake_amount",
+                    name, address
+                ));
+            }
+            if !accounts.insert(address) {
+                errors.push(format!("{}: account {} is listed twice", name, address));
+            }
+        }
+        errors
+    }
+
+    /// Builds the `GenesisInfo` for this spec.  The spec should be validated first.
+    pub fn into_genesis_info(self, framework: ReleaseBundle) -> anyhow::Result<GenesisInfo> {
+        let genesis_config = self.genesis_config();
+        let mut genesis_info = GenesisInfo::new(
+            self.chain_id,
+            self.root_key,
+            self.validators,
+            framework,
+            &genesis_config,
+        )?;
+        genesis_info.accounts = self.accounts;
+        if let Some(consensus_config) = self.consensus_config {
+            genesis_info.consensus_config = consensus_config;
+        }
+        if let Some(gas_schedule) = self.gas_schedule {
+            genesis_info.gas_schedule = gas_schedule;
+        }
+        Ok(genesis_info)
+    }
+}
+
+fn validate_gas_schedule(gas_schedule: &GasSchedule) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut entries = BTreeMap::new();
+    for (name, value) in &gas_schedule.entries {
+        if entries.insert(name.clone(), *value).is_some() {
+            errors.push(format!("gas_schedule: entry {} is listed twice", name));
+        }
+    }
+    if AptosGasParameters::from_on_chain_gas_schedule(&entries).is_none() {
+        errors.push("gas_schedule: is missing required entries".to_string());
+    }
+    errors
+}
+
+/// Whether the address belongs to the framework, which creates these accounts itself
+fn is_reserved_address(address: &AccountAddress) -> bool {
+    let bytes = address.to_vec();
+    let (last, rest) = bytes.split_last().unwrap();
+    rest.iter().all(|byte| *byte == 0) && *last <= MAX_RESERVED_ADDRESS
+}
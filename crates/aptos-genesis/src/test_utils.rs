@@ -1,17 +1,25 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::builder::InitGenesisConfigFn;
 use aptos_config::config::{IdentityBlob, NodeConfig};
 use aptos_crypto::ed25519::Ed25519PrivateKey;
 use aptos_temppath::TempPath;
 use rand::{rngs::StdRng, SeedableRng};
 
 pub fn test_config() -> (NodeConfig, Ed25519PrivateKey) {
+    test_config_with_genesis_config(None)
+}
+
+pub fn test_config_with_genesis_config(
+    init_genesis_config: Option<InitGenesisConfigFn>,
+) -> (NodeConfig, Ed25519PrivateKey) {
     let path = TempPath::new();
     path.create_as_dir().unwrap();
     let (root_key, _genesis, _genesis_waypoint, validators) =
         crate::builder::Builder::new(path.path(), cached_packages::head_release_bundle().clone())
             .unwrap()
+            .with_init_genesis_config(init_genesis_config)
             .build(StdRng::from_seed([0; 32]))
             .unwrap();
     let (
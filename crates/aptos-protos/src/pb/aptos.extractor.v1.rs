@@ -370,7 +370,7 @@ pub struct WriteTableItem {
 pub struct TransactionPayload {
     #[prost(enumeration = "transaction_payload::Type", tag = "1")]
     pub r#type: i32,
-    #[prost(oneof = "transaction_payload::Payload", tags = "2, 3, 4, 5")]
+    #[prost(oneof = "transaction_payload::Payload", tags = "2, 3, 4, 5, 6")]
     pub payload: ::core::option::Option<transaction_payload::Payload>,
 }
 /// Nested message and enum types in `TransactionPayload`.
@@ -381,6 +381,7 @@ pub mod transaction_payload {
         EntryFunctionPayload = 0,
         ScriptPayload = 1,
         ModuleBundlePayload = 2,
+        MultisigPayload = 3,
     }
     impl Type {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -392,6 +393,7 @@ pub mod transaction_payload {
                 Type::EntryFunctionPayload => "ENTRY_FUNCTION_PAYLOAD",
                 Type::ScriptPayload => "SCRIPT_PAYLOAD",
                 Type::ModuleBundlePayload => "MODULE_BUNDLE_PAYLOAD",
+                Type::MultisigPayload => "MULTISIG_PAYLOAD",
             }
         }
     }
@@ -405,6 +407,8 @@ pub mod transaction_payload {
         ModuleBundlePayload(super::ModuleBundlePayload),
         #[prost(message, tag = "5")]
         WriteSetPayload(super::WriteSetPayload),
+        #[prost(message, tag = "6")]
+        MultisigPayload(super::MultisigPayload),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -438,6 +442,44 @@ pub struct ModuleBundlePayload {
     pub modules: ::prost::alloc::vec::Vec<MoveModuleBytecode>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultisigPayload {
+    #[prost(string, tag = "1")]
+    pub multisig_address: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub transaction_payload: ::core::option::Option<MultisigTransactionPayload>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultisigTransactionPayload {
+    #[prost(enumeration = "multisig_transaction_payload::Type", tag = "1")]
+    pub r#type: i32,
+    #[prost(oneof = "multisig_transaction_payload::Payload", tags = "2")]
+    pub payload: ::core::option::Option<multisig_transaction_payload::Payload>,
+}
+/// Nested message and enum types in `MultisigTransactionPayload`.
+pub mod multisig_transaction_payload {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Type {
+        EntryFunctionPayload = 0,
+    }
+    impl Type {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Type::EntryFunctionPayload => "ENTRY_FUNCTION_PAYLOAD",
+            }
+        }
+    }
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Payload {
+        #[prost(message, tag = "2")]
+        EntryFunctionPayload(super::EntryFunctionPayload),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MoveModuleBytecode {
     #[prost(bytes = "vec", tag = "1")]
     pub bytecode: ::prost::alloc::vec::Vec<u8>,
@@ -591,7 +633,7 @@ pub struct MoveStructTag {
 pub struct Signature {
     #[prost(enumeration = "signature::Type", tag = "1")]
     pub r#type: i32,
-    #[prost(oneof = "signature::Signature", tags = "2, 3, 4")]
+    #[prost(oneof = "signature::Signature", tags = "2, 3, 4, 5, 6")]
     pub signature: ::core::option::Option<signature::Signature>,
 }
 /// Nested message and enum types in `Signature`.
@@ -602,6 +644,8 @@ pub mod signature {
         Ed25519 = 0,
         MultiEd25519 = 1,
         MultiAgent = 2,
+        SingleSender = 3,
+        FeePayer = 4,
     }
     impl Type {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -613,6 +657,8 @@ pub mod signature {
                 Type::Ed25519 => "ED25519",
                 Type::MultiEd25519 => "MULTI_ED25519",
                 Type::MultiAgent => "MULTI_AGENT",
+                Type::SingleSender => "SINGLE_SENDER",
+                Type::FeePayer => "FEE_PAYER",
             }
         }
     }
@@ -624,6 +670,10 @@ pub mod signature {
         MultiEd25519(super::MultiEd25519Signature),
         #[prost(message, tag = "4")]
         MultiAgent(super::MultiAgentSignature),
+        #[prost(message, tag = "5")]
+        SingleSender(super::SingleSenderSignature),
+        #[prost(message, tag = "6")]
+        FeePayer(super::FeePayerSignature),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -654,10 +704,46 @@ pub struct MultiAgentSignature {
     pub secondary_signers: ::prost::alloc::vec::Vec<AccountSignature>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SingleSenderSignature {
+    #[prost(message, optional, tag = "1")]
+    pub sender: ::core::option::Option<AccountSignature>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeePayerSignature {
+    #[prost(message, optional, tag = "1")]
+    pub sender: ::core::option::Option<AccountSignature>,
+    #[prost(string, repeated, tag = "2")]
+    pub secondary_signer_addresses: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "3")]
+    pub secondary_signers: ::prost::alloc::vec::Vec<AccountSignature>,
+    #[prost(string, tag = "4")]
+    pub fee_payer_address: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "5")]
+    pub fee_payer_signer: ::core::option::Option<AccountSignature>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Secp256r1EcdsaSignature {
+    #[prost(bytes = "vec", tag = "1")]
+    pub public_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebAuthnSignature {
+    #[prost(bytes = "vec", tag = "1")]
+    pub public_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub authenticator_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub client_data_json: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AccountSignature {
     #[prost(enumeration = "account_signature::Type", tag = "1")]
     pub r#type: i32,
-    #[prost(oneof = "account_signature::Signature", tags = "2, 3")]
+    #[prost(oneof = "account_signature::Signature", tags = "2, 3, 4, 5")]
     pub signature: ::core::option::Option<account_signature::Signature>,
 }
 /// Nested message and enum types in `AccountSignature`.
@@ -667,6 +753,8 @@ pub mod account_signature {
     pub enum Type {
         Ed25519 = 0,
         MultiEd25519 = 1,
+        Secp256r1Ecdsa = 2,
+        Webauthn = 3,
     }
     impl Type {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -677,6 +765,8 @@ pub mod account_signature {
             match self {
                 Type::Ed25519 => "ED25519",
                 Type::MultiEd25519 => "MULTI_ED25519",
+                Type::Secp256r1Ecdsa => "SECP256R1_ECDSA",
+                Type::Webauthn => "WEBAUTHN",
             }
         }
     }
@@ -686,6 +776,10 @@ pub mod account_signature {
         Ed25519(super::Ed25519Signature),
         #[prost(message, tag = "3")]
         MultiEd25519(super::MultiEd25519Signature),
+        #[prost(message, tag = "4")]
+        Secp256r1Ecdsa(super::Secp256r1EcdsaSignature),
+        #[prost(message, tag = "5")]
+        Webauthn(super::WebAuthnSignature),
     }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
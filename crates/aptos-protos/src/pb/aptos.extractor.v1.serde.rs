@@ -32,6 +32,12 @@ impl serde::Serialize for AccountSignature {
                 account_signature::Signature::MultiEd25519(v) => {
                     struct_ser.serialize_field("multiEd25519", v)?;
                 }
+                account_signature::Signature::Secp256r1Ecdsa(v) => {
+                    struct_ser.serialize_field("secp256r1Ecdsa", v)?;
+                }
+                account_signature::Signature::Webauthn(v) => {
+                    struct_ser.serialize_field("webauthn", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -43,13 +49,21 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["type", "ed25519", "multiEd25519"];
+        const FIELDS: &[&str] = &[
+            "type",
+            "ed25519",
+            "multiEd25519",
+            "secp256r1Ecdsa",
+            "webauthn",
+        ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             Type,
             Ed25519,
             MultiEd25519,
+            Secp256r1Ecdsa,
+            Webauthn,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -77,6 +91,8 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
                             "type" => Ok(GeneratedField::Type),
                             "ed25519" => Ok(GeneratedField::Ed25519),
                             "multiEd25519" => Ok(GeneratedField::MultiEd25519),
+                            "secp256r1Ecdsa" => Ok(GeneratedField::Secp256r1Ecdsa),
+                            "webauthn" => Ok(GeneratedField::Webauthn),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -121,6 +137,21 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
                                 map.next_value()?,
                             ));
                         }
+                        GeneratedField::Secp256r1Ecdsa => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secp256r1Ecdsa"));
+                            }
+                            signature__ = Some(account_signature::Signature::Secp256r1Ecdsa(
+                                map.next_value()?,
+                            ));
+                        }
+                        GeneratedField::Webauthn => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("webauthn"));
+                            }
+                            signature__ =
+                                Some(account_signature::Signature::Webauthn(map.next_value()?));
+                        }
                     }
                 }
                 Ok(AccountSignature {
@@ -145,6 +176,8 @@ impl serde::Serialize for account_signature::Type {
         let variant = match self {
             Self::Ed25519 => "ED25519",
             Self::MultiEd25519 => "MULTI_ED25519",
+            Self::Secp256r1Ecdsa => "SECP256R1_ECDSA",
+            Self::Webauthn => "WEBAUTHN",
         };
         serializer.serialize_str(variant)
     }
@@ -155,7 +188,7 @@ impl<'de> serde::Deserialize<'de> for account_signature::Type {
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["ED25519", "MULTI_ED25519"];
+        const FIELDS: &[&str] = &["ED25519", "MULTI_ED25519", "SECP256R1_ECDSA", "WEBAUTHN"];
 
         struct GeneratedVisitor;
 
@@ -199,6 +232,8 @@ impl<'de> serde::Deserialize<'de> for account_signature::Type {
                 match value {
                     "ED25519" => Ok(account_signature::Type::Ed25519),
                     "MULTI_ED25519" => Ok(account_signature::Type::MultiEd25519),
+                    "SECP256R1_ECDSA" => Ok(account_signature::Type::Secp256r1Ecdsa),
+                    "WEBAUTHN" => Ok(account_signature::Type::Webauthn),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -1860,6 +1895,178 @@ impl<'de> serde::Deserialize<'de> for EventKey {
         deserializer.deserialize_struct("aptos.extractor.v1.EventKey", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for FeePayerSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.sender.is_some() {
+            len += 1;
+        }
+        if !self.secondary_signer_addresses.is_empty() {
+            len += 1;
+        }
+        if !self.secondary_signers.is_empty() {
+            len += 1;
+        }
+        if !self.fee_payer_address.is_empty() {
+            len += 1;
+        }
+        if self.fee_payer_signer.is_some() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.extractor.v1.FeePayerSignature", len)?;
+        if let Some(v) = self.sender.as_ref() {
+            struct_ser.serialize_field("sender", v)?;
+        }
+        if !self.secondary_signer_addresses.is_empty() {
+            struct_ser
+                .serialize_field("secondarySignerAddresses", &self.secondary_signer_addresses)?;
+        }
+        if !self.secondary_signers.is_empty() {
+            struct_ser.serialize_field("secondarySigners", &self.secondary_signers)?;
+        }
+        if !self.fee_payer_address.is_empty() {
+            struct_ser.serialize_field("feePayerAddress", &self.fee_payer_address)?;
+        }
+        if let Some(v) = self.fee_payer_signer.as_ref() {
+            struct_ser.serialize_field("feePayerSigner", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for FeePayerSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "sender",
+            "secondarySignerAddresses",
+            "secondarySigners",
+            "feePayerAddress",
+            "feePayerSigner",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Sender,
+            SecondarySignerAddresses,
+            SecondarySigners,
+            FeePayerAddress,
+            FeePayerSigner,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "sender" => Ok(GeneratedField::Sender),
+                            "secondarySignerAddresses" => {
+                                Ok(GeneratedField::SecondarySignerAddresses)
+                            }
+                            "secondarySigners" => Ok(GeneratedField::SecondarySigners),
+                            "feePayerAddress" => Ok(GeneratedField::FeePayerAddress),
+                            "feePayerSigner" => Ok(GeneratedField::FeePayerSigner),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = FeePayerSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.extractor.v1.FeePayerSignature")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<FeePayerSignature, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut sender__ = None;
+                let mut secondary_signer_addresses__ = None;
+                let mut secondary_signers__ = None;
+                let mut fee_payer_address__ = None;
+                let mut fee_payer_signer__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Sender => {
+                            if sender__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sender"));
+                            }
+                            sender__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::SecondarySignerAddresses => {
+                            if secondary_signer_addresses__.is_some() {
+                                return Err(serde::de::Error::duplicate_field(
+                                    "secondarySignerAddresses",
+                                ));
+                            }
+                            secondary_signer_addresses__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::SecondarySigners => {
+                            if secondary_signers__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secondarySigners"));
+                            }
+                            secondary_signers__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::FeePayerAddress => {
+                            if fee_payer_address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("feePayerAddress"));
+                            }
+                            fee_payer_address__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::FeePayerSigner => {
+                            if fee_payer_signer__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("feePayerSigner"));
+                            }
+                            fee_payer_signer__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(FeePayerSignature {
+                    sender: sender__,
+                    secondary_signer_addresses: secondary_signer_addresses__.unwrap_or_default(),
+                    secondary_signers: secondary_signers__.unwrap_or_default(),
+                    fee_payer_address: fee_payer_address__.unwrap_or_default(),
+                    fee_payer_signer: fee_payer_signer__,
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.extractor.v1.FeePayerSignature",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
 impl serde::Serialize for GenesisTransaction {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -4280,7 +4487,7 @@ impl<'de> serde::Deserialize<'de> for MultiEd25519Signature {
         )
     }
 }
-impl serde::Serialize for ScriptPayload {
+impl serde::Serialize for MultisigPayload {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -4288,42 +4495,35 @@ impl serde::Serialize for ScriptPayload {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if self.code.is_some() {
-            len += 1;
-        }
-        if !self.type_arguments.is_empty() {
+        if !self.multisig_address.is_empty() {
             len += 1;
         }
-        if !self.arguments.is_empty() {
+        if self.transaction_payload.is_some() {
             len += 1;
         }
         let mut struct_ser =
-            serializer.serialize_struct("aptos.extractor.v1.ScriptPayload", len)?;
-        if let Some(v) = self.code.as_ref() {
-            struct_ser.serialize_field("code", v)?;
-        }
-        if !self.type_arguments.is_empty() {
-            struct_ser.serialize_field("typeArguments", &self.type_arguments)?;
+            serializer.serialize_struct("aptos.extractor.v1.MultisigPayload", len)?;
+        if !self.multisig_address.is_empty() {
+            struct_ser.serialize_field("multisigAddress", &self.multisig_address)?;
         }
-        if !self.arguments.is_empty() {
-            struct_ser.serialize_field("arguments", &self.arguments)?;
+        if let Some(v) = self.transaction_payload.as_ref() {
+            struct_ser.serialize_field("transactionPayload", v)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for ScriptPayload {
+impl<'de> serde::Deserialize<'de> for MultisigPayload {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["code", "typeArguments", "arguments"];
+        const FIELDS: &[&str] = &["multisigAddress", "transactionPayload"];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Code,
-            TypeArguments,
-            Arguments,
+            MultisigAddress,
+            TransactionPayload,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -4348,9 +4548,8 @@ impl<'de> serde::Deserialize<'de> for ScriptPayload {
                         E: serde::de::Error,
                     {
                         match value {
-                            "code" => Ok(GeneratedField::Code),
-                            "typeArguments" => Ok(GeneratedField::TypeArguments),
-                            "arguments" => Ok(GeneratedField::Arguments),
+                            "multisigAddress" => Ok(GeneratedField::MultisigAddress),
+                            "transactionPayload" => Ok(GeneratedField::TransactionPayload),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -4360,56 +4559,50 @@ impl<'de> serde::Deserialize<'de> for ScriptPayload {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = ScriptPayload;
+            type Value = MultisigPayload;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct aptos.extractor.v1.ScriptPayload")
+                formatter.write_str("struct aptos.extractor.v1.MultisigPayload")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<ScriptPayload, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<MultisigPayload, V::Error>
             where
                 V: serde::de::MapAccess<'de>,
             {
-                let mut code__ = None;
-                let mut type_arguments__ = None;
-                let mut arguments__ = None;
+                let mut multisig_address__ = None;
+                let mut transaction_payload__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Code => {
-                            if code__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("code"));
-                            }
-                            code__ = Some(map.next_value()?);
-                        }
-                        GeneratedField::TypeArguments => {
-                            if type_arguments__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("typeArguments"));
+                        GeneratedField::MultisigAddress => {
+                            if multisig_address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("multisigAddress"));
                             }
-                            type_arguments__ = Some(map.next_value()?);
+                            multisig_address__ = Some(map.next_value()?);
                         }
-                        GeneratedField::Arguments => {
-                            if arguments__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("arguments"));
+                        GeneratedField::TransactionPayload => {
+                            if transaction_payload__.is_some() {
+                                return Err(serde::de::Error::duplicate_field(
+                                    "transactionPayload",
+                                ));
                             }
-                            arguments__ = Some(map.next_value()?);
+                            transaction_payload__ = Some(map.next_value()?);
                         }
                     }
                 }
-                Ok(ScriptPayload {
-                    code: code__,
-                    type_arguments: type_arguments__.unwrap_or_default(),
-                    arguments: arguments__.unwrap_or_default(),
+                Ok(MultisigPayload {
+                    multisig_address: multisig_address__.unwrap_or_default(),
+                    transaction_payload: transaction_payload__,
                 })
             }
         }
         deserializer.deserialize_struct(
-            "aptos.extractor.v1.ScriptPayload",
+            "aptos.extractor.v1.MultisigPayload",
             FIELDS,
             GeneratedVisitor,
         )
     }
 }
-impl serde::Serialize for ScriptWriteSet {
+impl serde::Serialize for MultisigTransactionPayload {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -4417,35 +4610,42 @@ impl serde::Serialize for ScriptWriteSet {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.execute_as.is_empty() {
+        if self.r#type != 0 {
             len += 1;
         }
-        if self.script.is_some() {
+        if self.payload.is_some() {
             len += 1;
         }
         let mut struct_ser =
-            serializer.serialize_struct("aptos.extractor.v1.ScriptWriteSet", len)?;
-        if !self.execute_as.is_empty() {
-            struct_ser.serialize_field("executeAs", &self.execute_as)?;
+            serializer.serialize_struct("aptos.extractor.v1.MultisigTransactionPayload", len)?;
+        if self.r#type != 0 {
+            let v = multisig_transaction_payload::Type::from_i32(self.r#type).ok_or_else(|| {
+                serde::ser::Error::custom(format!("Invalid variant {}", self.r#type))
+            })?;
+            struct_ser.serialize_field("type", &v)?;
         }
-        if let Some(v) = self.script.as_ref() {
-            struct_ser.serialize_field("script", v)?;
+        if let Some(v) = self.payload.as_ref() {
+            match v {
+                multisig_transaction_payload::Payload::EntryFunctionPayload(v) => {
+                    struct_ser.serialize_field("entryFunctionPayload", v)?;
+                }
+            }
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for ScriptWriteSet {
+impl<'de> serde::Deserialize<'de> for MultisigTransactionPayload {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["executeAs", "script"];
+        const FIELDS: &[&str] = &["type", "entryFunctionPayload"];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            ExecuteAs,
-            Script,
+            Type,
+            EntryFunctionPayload,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -4470,8 +4670,8 @@ impl<'de> serde::Deserialize<'de> for ScriptWriteSet {
                         E: serde::de::Error,
                     {
                         match value {
-                            "executeAs" => Ok(GeneratedField::ExecuteAs),
-                            "script" => Ok(GeneratedField::Script),
+                            "type" => Ok(GeneratedField::Type),
+                            "entryFunctionPayload" => Ok(GeneratedField::EntryFunctionPayload),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -4481,42 +4681,492 @@ impl<'de> serde::Deserialize<'de> for ScriptWriteSet {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = ScriptWriteSet;
+            type Value = MultisigTransactionPayload;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct aptos.extractor.v1.ScriptWriteSet")
+                formatter.write_str("struct aptos.extractor.v1.MultisigTransactionPayload")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<ScriptWriteSet, V::Error>
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<MultisigTransactionPayload, V::Error>
             where
                 V: serde::de::MapAccess<'de>,
             {
-                let mut execute_as__ = None;
-                let mut script__ = None;
+                let mut r#type__ = None;
+                let mut payload__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::ExecuteAs => {
-                            if execute_as__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("executeAs"));
+                        GeneratedField::Type => {
+                            if r#type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("type"));
                             }
-                            execute_as__ = Some(map.next_value()?);
+                            r#type__ = Some(
+                                map.next_value::<multisig_transaction_payload::Type>()? as i32,
+                            );
                         }
-                        GeneratedField::Script => {
-                            if script__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("script"));
+                        GeneratedField::EntryFunctionPayload => {
+                            if payload__.is_some() {
+                                return Err(serde::de::Error::duplicate_field(
+                                    "entryFunctionPayload",
+                                ));
+                            }
+                            payload__ =
+                                Some(multisig_transaction_payload::Payload::EntryFunctionPayload(
+                                    map.next_value()?,
+                                ));
+                        }
+                    }
+                }
+                Ok(MultisigTransactionPayload {
+                    r#type: r#type__.unwrap_or_default(),
+                    payload: payload__,
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.extractor.v1.MultisigTransactionPayload",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for multisig_transaction_payload::Type {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::EntryFunctionPayload => "ENTRY_FUNCTION_PAYLOAD",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for multisig_transaction_payload::Type {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["ENTRY_FUNCTION_PAYLOAD"];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = multisig_transaction_payload::Type;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(multisig_transaction_payload::Type::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(multisig_transaction_payload::Type::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "ENTRY_FUNCTION_PAYLOAD" => {
+                        Ok(multisig_transaction_payload::Type::EntryFunctionPayload)
+                    }
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ScriptPayload {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.code.is_some() {
+            len += 1;
+        }
+        if !self.type_arguments.is_empty() {
+            len += 1;
+        }
+        if !self.arguments.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.extractor.v1.ScriptPayload", len)?;
+        if let Some(v) = self.code.as_ref() {
+            struct_ser.serialize_field("code", v)?;
+        }
+        if !self.type_arguments.is_empty() {
+            struct_ser.serialize_field("typeArguments", &self.type_arguments)?;
+        }
+        if !self.arguments.is_empty() {
+            struct_ser.serialize_field("arguments", &self.arguments)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ScriptPayload {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["code", "typeArguments", "arguments"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Code,
+            TypeArguments,
+            Arguments,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "code" => Ok(GeneratedField::Code),
+                            "typeArguments" => Ok(GeneratedField::TypeArguments),
+                            "arguments" => Ok(GeneratedField::Arguments),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ScriptPayload;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.extractor.v1.ScriptPayload")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ScriptPayload, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut code__ = None;
+                let mut type_arguments__ = None;
+                let mut arguments__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Code => {
+                            if code__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("code"));
+                            }
+                            code__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::TypeArguments => {
+                            if type_arguments__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("typeArguments"));
+                            }
+                            type_arguments__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Arguments => {
+                            if arguments__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("arguments"));
+                            }
+                            arguments__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ScriptPayload {
+                    code: code__,
+                    type_arguments: type_arguments__.unwrap_or_default(),
+                    arguments: arguments__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.extractor.v1.ScriptPayload",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for ScriptWriteSet {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.execute_as.is_empty() {
+            len += 1;
+        }
+        if self.script.is_some() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.extractor.v1.ScriptWriteSet", len)?;
+        if !self.execute_as.is_empty() {
+            struct_ser.serialize_field("executeAs", &self.execute_as)?;
+        }
+        if let Some(v) = self.script.as_ref() {
+            struct_ser.serialize_field("script", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ScriptWriteSet {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["executeAs", "script"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            ExecuteAs,
+            Script,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "executeAs" => Ok(GeneratedField::ExecuteAs),
+                            "script" => Ok(GeneratedField::Script),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ScriptWriteSet;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.extractor.v1.ScriptWriteSet")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ScriptWriteSet, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut execute_as__ = None;
+                let mut script__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::ExecuteAs => {
+                            if execute_as__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("executeAs"));
+                            }
+                            execute_as__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Script => {
+                            if script__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("script"));
                             }
                             script__ = Some(map.next_value()?);
                         }
                     }
                 }
-                Ok(ScriptWriteSet {
-                    execute_as: execute_as__.unwrap_or_default(),
-                    script: script__,
+                Ok(ScriptWriteSet {
+                    execute_as: execute_as__.unwrap_or_default(),
+                    script: script__,
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.extractor.v1.ScriptWriteSet",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for Secp256r1EcdsaSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.public_key.is_empty() {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.extractor.v1.Secp256r1EcdsaSignature", len)?;
+        if !self.public_key.is_empty() {
+            struct_ser.serialize_field(
+                "publicKey",
+                pbjson::private::base64::encode(&self.public_key).as_str(),
+            )?;
+        }
+        if !self.signature.is_empty() {
+            struct_ser.serialize_field(
+                "signature",
+                pbjson::private::base64::encode(&self.signature).as_str(),
+            )?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Secp256r1EcdsaSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["publicKey", "signature"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PublicKey,
+            Signature,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "publicKey" => Ok(GeneratedField::PublicKey),
+                            "signature" => Ok(GeneratedField::Signature),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Secp256r1EcdsaSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.extractor.v1.Secp256r1EcdsaSignature")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<Secp256r1EcdsaSignature, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut public_key__ = None;
+                let mut signature__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::PublicKey => {
+                            if public_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKey"));
+                            }
+                            public_key__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                    }
+                }
+                Ok(Secp256r1EcdsaSignature {
+                    public_key: public_key__.unwrap_or_default(),
+                    signature: signature__.unwrap_or_default(),
                 })
             }
         }
         deserializer.deserialize_struct(
-            "aptos.extractor.v1.ScriptWriteSet",
+            "aptos.extractor.v1.Secp256r1EcdsaSignature",
             FIELDS,
             GeneratedVisitor,
         )
@@ -4554,6 +5204,12 @@ impl serde::Serialize for Signature {
                 signature::Signature::MultiAgent(v) => {
                     struct_ser.serialize_field("multiAgent", v)?;
                 }
+                signature::Signature::SingleSender(v) => {
+                    struct_ser.serialize_field("singleSender", v)?;
+                }
+                signature::Signature::FeePayer(v) => {
+                    struct_ser.serialize_field("feePayer", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -4565,7 +5221,14 @@ impl<'de> serde::Deserialize<'de> for Signature {
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["type", "ed25519", "multiEd25519", "multiAgent"];
+        const FIELDS: &[&str] = &[
+            "type",
+            "ed25519",
+            "multiEd25519",
+            "multiAgent",
+            "singleSender",
+            "feePayer",
+        ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
@@ -4573,6 +5236,8 @@ impl<'de> serde::Deserialize<'de> for Signature {
             Ed25519,
             MultiEd25519,
             MultiAgent,
+            SingleSender,
+            FeePayer,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -4601,6 +5266,8 @@ impl<'de> serde::Deserialize<'de> for Signature {
                             "ed25519" => Ok(GeneratedField::Ed25519),
                             "multiEd25519" => Ok(GeneratedField::MultiEd25519),
                             "multiAgent" => Ok(GeneratedField::MultiAgent),
+                            "singleSender" => Ok(GeneratedField::SingleSender),
+                            "feePayer" => Ok(GeneratedField::FeePayer),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -4649,6 +5316,19 @@ impl<'de> serde::Deserialize<'de> for Signature {
                             }
                             signature__ = Some(signature::Signature::MultiAgent(map.next_value()?));
                         }
+                        GeneratedField::SingleSender => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("singleSender"));
+                            }
+                            signature__ =
+                                Some(signature::Signature::SingleSender(map.next_value()?));
+                        }
+                        GeneratedField::FeePayer => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("feePayer"));
+                            }
+                            signature__ = Some(signature::Signature::FeePayer(map.next_value()?));
+                        }
                     }
                 }
                 Ok(Signature {
@@ -4670,6 +5350,8 @@ impl serde::Serialize for signature::Type {
             Self::Ed25519 => "ED25519",
             Self::MultiEd25519 => "MULTI_ED25519",
             Self::MultiAgent => "MULTI_AGENT",
+            Self::SingleSender => "SINGLE_SENDER",
+            Self::FeePayer => "FEE_PAYER",
         };
         serializer.serialize_str(variant)
     }
@@ -4680,7 +5362,13 @@ impl<'de> serde::Deserialize<'de> for signature::Type {
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["ED25519", "MULTI_ED25519", "MULTI_AGENT"];
+        const FIELDS: &[&str] = &[
+            "ED25519",
+            "MULTI_ED25519",
+            "MULTI_AGENT",
+            "SINGLE_SENDER",
+            "FEE_PAYER",
+        ];
 
         struct GeneratedVisitor;
 
@@ -4725,6 +5413,8 @@ impl<'de> serde::Deserialize<'de> for signature::Type {
                     "ED25519" => Ok(signature::Type::Ed25519),
                     "MULTI_ED25519" => Ok(signature::Type::MultiEd25519),
                     "MULTI_AGENT" => Ok(signature::Type::MultiAgent),
+                    "SINGLE_SENDER" => Ok(signature::Type::SingleSender),
+                    "FEE_PAYER" => Ok(signature::Type::FeePayer),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -4732,6 +5422,104 @@ impl<'de> serde::Deserialize<'de> for signature::Type {
         deserializer.deserialize_any(GeneratedVisitor)
     }
 }
+impl serde::Serialize for SingleSenderSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.sender.is_some() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.extractor.v1.SingleSenderSignature", len)?;
+        if let Some(v) = self.sender.as_ref() {
+            struct_ser.serialize_field("sender", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SingleSenderSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["sender"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Sender,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "sender" => Ok(GeneratedField::Sender),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SingleSenderSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.extractor.v1.SingleSenderSignature")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<SingleSenderSignature, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut sender__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Sender => {
+                            if sender__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sender"));
+                            }
+                            sender__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(SingleSenderSignature { sender: sender__ })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.extractor.v1.SingleSenderSignature",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
 impl serde::Serialize for StateCheckpointTransaction {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -5445,6 +6233,9 @@ impl serde::Serialize for TransactionPayload {
                 transaction_payload::Payload::WriteSetPayload(v) => {
                     struct_ser.serialize_field("writeSetPayload", v)?;
                 }
+                transaction_payload::Payload::MultisigPayload(v) => {
+                    struct_ser.serialize_field("multisigPayload", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -5462,6 +6253,7 @@ impl<'de> serde::Deserialize<'de> for TransactionPayload {
             "scriptPayload",
             "moduleBundlePayload",
             "writeSetPayload",
+            "multisigPayload",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -5471,6 +6263,7 @@ impl<'de> serde::Deserialize<'de> for TransactionPayload {
             ScriptPayload,
             ModuleBundlePayload,
             WriteSetPayload,
+            MultisigPayload,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -5500,6 +6293,7 @@ impl<'de> serde::Deserialize<'de> for TransactionPayload {
                             "scriptPayload" => Ok(GeneratedField::ScriptPayload),
                             "moduleBundlePayload" => Ok(GeneratedField::ModuleBundlePayload),
                             "writeSetPayload" => Ok(GeneratedField::WriteSetPayload),
+                            "multisigPayload" => Ok(GeneratedField::MultisigPayload),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -5565,6 +6359,14 @@ impl<'de> serde::Deserialize<'de> for TransactionPayload {
                                 map.next_value()?,
                             ));
                         }
+                        GeneratedField::MultisigPayload => {
+                            if payload__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("multisigPayload"));
+                            }
+                            payload__ = Some(transaction_payload::Payload::MultisigPayload(
+                                map.next_value()?,
+                            ));
+                        }
                     }
                 }
                 Ok(TransactionPayload {
@@ -5590,6 +6392,7 @@ impl serde::Serialize for transaction_payload::Type {
             Self::EntryFunctionPayload => "ENTRY_FUNCTION_PAYLOAD",
             Self::ScriptPayload => "SCRIPT_PAYLOAD",
             Self::ModuleBundlePayload => "MODULE_BUNDLE_PAYLOAD",
+            Self::MultisigPayload => "MULTISIG_PAYLOAD",
         };
         serializer.serialize_str(variant)
     }
@@ -5604,6 +6407,7 @@ impl<'de> serde::Deserialize<'de> for transaction_payload::Type {
             "ENTRY_FUNCTION_PAYLOAD",
             "SCRIPT_PAYLOAD",
             "MODULE_BUNDLE_PAYLOAD",
+            "MULTISIG_PAYLOAD",
         ];
 
         struct GeneratedVisitor;
@@ -5649,6 +6453,7 @@ impl<'de> serde::Deserialize<'de> for transaction_payload::Type {
                     "ENTRY_FUNCTION_PAYLOAD" => Ok(transaction_payload::Type::EntryFunctionPayload),
                     "SCRIPT_PAYLOAD" => Ok(transaction_payload::Type::ScriptPayload),
                     "MODULE_BUNDLE_PAYLOAD" => Ok(transaction_payload::Type::ModuleBundlePayload),
+                    "MULTISIG_PAYLOAD" => Ok(transaction_payload::Type::MultisigPayload),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -6111,6 +6916,180 @@ impl<'de> serde::Deserialize<'de> for UserTransactionRequest {
         )
     }
 }
+impl serde::Serialize for WebAuthnSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.public_key.is_empty() {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        if !self.authenticator_data.is_empty() {
+            len += 1;
+        }
+        if !self.client_data_json.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.extractor.v1.WebAuthnSignature", len)?;
+        if !self.public_key.is_empty() {
+            struct_ser.serialize_field(
+                "publicKey",
+                pbjson::private::base64::encode(&self.public_key).as_str(),
+            )?;
+        }
+        if !self.signature.is_empty() {
+            struct_ser.serialize_field(
+                "signature",
+                pbjson::private::base64::encode(&self.signature).as_str(),
+            )?;
+        }
+        if !self.authenticator_data.is_empty() {
+            struct_ser.serialize_field(
+                "authenticatorData",
+                pbjson::private::base64::encode(&self.authenticator_data).as_str(),
+            )?;
+        }
+        if !self.client_data_json.is_empty() {
+            struct_ser.serialize_field(
+                "clientDataJson",
+                pbjson::private::base64::encode(&self.client_data_json).as_str(),
+            )?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for WebAuthnSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "publicKey",
+            "signature",
+            "authenticatorData",
+            "clientDataJson",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PublicKey,
+            Signature,
+            AuthenticatorData,
+            ClientDataJson,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "publicKey" => Ok(GeneratedField::PublicKey),
+                            "signature" => Ok(GeneratedField::Signature),
+                            "authenticatorData" => Ok(GeneratedField::AuthenticatorData),
+                            "clientDataJson" => Ok(GeneratedField::ClientDataJson),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = WebAuthnSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.extractor.v1.WebAuthnSignature")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<WebAuthnSignature, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut public_key__ = None;
+                let mut signature__ = None;
+                let mut authenticator_data__ = None;
+                let mut client_data_json__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::PublicKey => {
+                            if public_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKey"));
+                            }
+                            public_key__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::AuthenticatorData => {
+                            if authenticator_data__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("authenticatorData"));
+                            }
+                            authenticator_data__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::ClientDataJson => {
+                            if client_data_json__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("clientDataJson"));
+                            }
+                            client_data_json__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                    }
+                }
+                Ok(WebAuthnSignature {
+                    public_key: public_key__.unwrap_or_default(),
+                    signature: signature__.unwrap_or_default(),
+                    authenticator_data: authenticator_data__.unwrap_or_default(),
+                    client_data_json: client_data_json__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.extractor.v1.WebAuthnSignature",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
 impl serde::Serialize for WriteModule {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -731,6 +731,20 @@ impl Client {
         self.json(response).await
     }
 
+    pub async fn get_account_resource_at_version_bytes(
+        &self,
+        address: AccountAddress,
+        resource_type: &str,
+        version: u64,
+    ) -> AptosResult<Response<Vec<u8>>> {
+        let url = self.build_path(&format!(
+            "accounts/{}/resource/{}?ledger_version={}",
+            address, resource_type, version
+        ))?;
+        let response = self.get_bcs(url).await?;
+        Ok(response.map(|inner| inner.to_vec()))
+    }
+
     pub async fn get_account_modules(
         &self,
         address: AccountAddress,
@@ -768,6 +782,19 @@ impl Client {
         self.get_bcs(url).await
     }
 
+    pub async fn get_account_module_bcs_at_version(
+        &self,
+        address: AccountAddress,
+        module_name: &str,
+        version: u64,
+    ) -> AptosResult<Response<bytes::Bytes>> {
+        let url = self.build_path(&format!(
+            "accounts/{}/module/{}?ledger_version={}",
+            address, module_name, version
+        ))?;
+        self.get_bcs(url).await
+    }
+
     pub async fn get_account_events(
         &self,
         address: AccountAddress,
@@ -899,6 +926,31 @@ impl Client {
         self.json(response).await
     }
 
+    pub async fn get_raw_table_item(
+        &self,
+        table_handle: AccountAddress,
+        key: &[u8],
+        version: u64,
+    ) -> AptosResult<Response<Vec<u8>>> {
+        let url = self.build_path(&format!(
+            "tables/{}/raw_item?ledger_version={}",
+            table_handle, version
+        ))?;
+        let data = json!({
+            "key": format!("0x{}", hex::encode(key)),
+        });
+
+        let response = self
+            .inner
+            .post(url)
+            .header(ACCEPT, BCS)
+            .json(&data)
+            .send()
+            .await?;
+        let response = self.check_and_parse_bcs_response(response).await?;
+        Ok(response.map(|inner| inner.to_vec()))
+    }
+
     pub async fn get_account(&self, address: AccountAddress) -> AptosResult<Response<Account>> {
         let url = self.build_path(&format!("accounts/{}", address))?;
         let response = self.inner.get(url).send().await?;
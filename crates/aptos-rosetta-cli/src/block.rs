@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::common::{format_output, BlockArgs, NetworkArgs, UrlArgs};
-use aptos_rosetta::types::{BlockRequest, BlockResponse};
+use aptos_rosetta::types::{
+    BlockIdentifier, BlockRequest, BlockResponse, BlockTransactionRequest,
+    BlockTransactionResponse, TransactionIdentifier,
+};
 use clap::{Parser, Subcommand};
 
 /// Block APIs
@@ -13,12 +16,14 @@ use clap::{Parser, Subcommand};
 #[derive(Debug, Subcommand)]
 pub enum BlockCommand {
     Get(GetBlockCommand),
+    Transaction(GetBlockTransactionCommand),
 }
 
 impl BlockCommand {
     pub async fn execute(self) -> anyhow::Result<String> {
         match self {
             BlockCommand::Get(inner) => format_output(inner.execute().await),
+            BlockCommand::Transaction(inner) => format_output(inner.execute().await),
         }
     }
 }
@@ -45,3 +50,39 @@ impl GetBlockCommand {
         self.url_args.client().block(&request).await
     }
 }
+
+/// Get a single transaction in a block
+///
+/// [API Spec](https://www.rosetta-api.org/docs/BlockApi.html#blocktransaction)
+#[derive(Debug, Parser)]
+pub struct GetBlockTransactionCommand {
+    /// The index of the block containing the transaction
+    #[clap(long)]
+    block_index: u64,
+    /// The hash of the block containing the transaction
+    #[clap(long)]
+    block_hash: String,
+    /// The hash of the transaction
+    #[clap(long)]
+    txn_hash: String,
+    #[clap(flatten)]
+    network_args: NetworkArgs,
+    #[clap(flatten)]
+    url_args: UrlArgs,
+}
+
+impl GetBlockTransactionCommand {
+    pub async fn execute(self) -> anyhow::Result<BlockTransactionResponse> {
+        let request = BlockTransactionRequest {
+            network_identifier: self.network_args.network_identifier(),
+            block_identifier: BlockIdentifier {
+                index: self.block_index,
+                hash: self.block_hash,
+            },
+            transaction_identifier: TransactionIdentifier {
+                hash: self.txn_hash,
+            },
+        };
+        self.url_args.client().block_transaction(&request).await
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{account, block, construction, network};
+use crate::{account, block, construction, events, mempool, network, search};
 use aptos_rosetta::{
     client::RosettaClient,
     types::{NetworkIdentifier, NetworkRequest, PartialBlockIdentifier},
@@ -23,7 +23,13 @@ pub enum RosettaCliArgs {
     #[clap(subcommand)]
     Construction(construction::ConstructionCommand),
     #[clap(subcommand)]
+    Events(events::EventsCommand),
+    #[clap(subcommand)]
+    Mempool(mempool::MempoolCommand),
+    #[clap(subcommand)]
     Network(network::NetworkCommand),
+    #[clap(subcommand)]
+    Search(search::SearchCommand),
 }
 
 impl RosettaCliArgs {
@@ -33,7 +39,10 @@ impl RosettaCliArgs {
             Account(inner) => inner.execute().await,
             Block(inner) => inner.execute().await,
             Construction(inner) => inner.execute().await,
+            Events(inner) => inner.execute().await,
+            Mempool(inner) => inner.execute().await,
             Network(inner) => inner.execute().await,
+            Search(inner) => inner.execute().await,
         }
     }
 }
@@ -105,3 +114,50 @@ impl From<BlockArgs> for Option<PartialBlockIdentifier> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_data_api_commands() {
+        let hash = format!("0x{}", "ab".repeat(32));
+        let commands: Vec<Vec<&str>> = vec![
+            vec![
+                "block",
+                "transaction",
+                "--block-index",
+                "5",
+                "--block-hash",
+                "4-5",
+                "--txn-hash",
+                &hash,
+            ],
+            vec!["mempool", "list"],
+            vec!["mempool", "transaction", "--txn-hash", &hash],
+            vec![
+                "search",
+                "transactions",
+                "--account",
+                "0x1",
+                "--limit",
+                "10",
+            ],
+            vec![
+                "search",
+                "transactions",
+                "--txn-hash",
+                &hash,
+                "--success",
+                "true",
+            ],
+            vec!["events", "blocks", "--offset", "0", "--limit", "10"],
+        ];
+
+        for command in commands {
+            let args = std::iter::once("aptos-rosetta-cli").chain(command.iter().copied());
+            RosettaCliArgs::try_parse_from(args)
+                .unwrap_or_else(|err| panic!("Failed to parse {:?}: {}", command, err));
+        }
+    }
+}
@@ -4,7 +4,10 @@
 use crate::common::{format_output, NetworkArgs, UrlArgs};
 use aptos::common::types::{EncodingOptions, PrivateKeyInputOptions, ProfileOptions};
 use aptos_logger::info;
-use aptos_rosetta::types::TransactionIdentifier;
+use aptos_rosetta::{
+    common::native_coin,
+    types::{Currency, TransactionIdentifier},
+};
 use aptos_types::account_address::AccountAddress;
 use clap::{Parser, Subcommand};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -112,7 +115,7 @@ impl CreateAccountCommand {
 
 /// Transfer coins via Rosetta
 ///
-/// Only the native coin is allowed for now
+/// Defaults to the native coin, other coins must be supported by the Rosetta server
 #[derive(Debug, Parser)]
 pub struct TransferCommand {
     #[clap(flatten)]
@@ -137,6 +140,12 @@ pub struct TransferCommand {
     /// The amount of coins to send
     #[clap(long)]
     amount: u64,
+    /// The currency to send as JSON e.g.
+    /// `{"symbol": "APT", "decimals": 8, "metadata": {"move_type": "0x1::aptos_coin::AptosCoin"}}`
+    ///
+    /// Defaults to the native coin
+    #[clap(long, parse(try_from_str=serde_json::from_str))]
+    currency: Option<Currency>,
 }
 
 impl TransferCommand {
@@ -155,6 +164,7 @@ impl TransferCommand {
                 &private_key,
                 self.receiver,
                 self.amount,
+                self.currency.unwrap_or_else(native_coin),
                 self.txn_args.expiry_time()?,
                 self.txn_args.sequence_number,
                 self.txn_args.max_gas,
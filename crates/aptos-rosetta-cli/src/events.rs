@@ -0,0 +1,52 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{format_output, NetworkArgs, UrlArgs};
+use aptos_rosetta::types::{EventsBlocksRequest, EventsBlocksResponse};
+use clap::{Parser, Subcommand};
+
+/// Events APIs
+///
+/// Used for following blocks being added to the blockchain
+///
+/// [API Spec](https://www.rosetta-api.org/docs/EventsApi.html)
+#[derive(Debug, Subcommand)]
+pub enum EventsCommand {
+    Blocks(EventsBlocksCommand),
+}
+
+impl EventsCommand {
+    pub async fn execute(self) -> anyhow::Result<String> {
+        match self {
+            EventsCommand::Blocks(inner) => format_output(inner.execute().await),
+        }
+    }
+}
+
+/// Get a range of block events
+///
+/// [API Spec](https://www.rosetta-api.org/docs/EventsApi.html#eventsblocks)
+#[derive(Debug, Parser)]
+pub struct EventsBlocksCommand {
+    /// Sequence number of the first event, defaults to the oldest available block
+    #[clap(long)]
+    offset: Option<u64>,
+    /// Maximum number of events to get
+    #[clap(long)]
+    limit: Option<u64>,
+    #[clap(flatten)]
+    network_args: NetworkArgs,
+    #[clap(flatten)]
+    url_args: UrlArgs,
+}
+
+impl EventsBlocksCommand {
+    pub async fn execute(self) -> anyhow::Result<EventsBlocksResponse> {
+        let request = EventsBlocksRequest {
+            network_identifier: self.network_args.network_identifier(),
+            offset: self.offset,
+            limit: self.limit,
+        };
+        self.url_args.client().events_blocks(&request).await
+    }
+}
@@ -21,7 +21,10 @@ mod account;
 mod block;
 mod common;
 mod construction;
+mod events;
+mod mempool;
 mod network;
+mod search;
 
 use crate::common::{ErrorWrapper, RosettaCliArgs};
 use aptos_logger::Level;
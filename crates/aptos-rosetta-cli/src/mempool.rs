@@ -0,0 +1,75 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{format_output, NetworkArgs, UrlArgs};
+use aptos_rosetta::types::{
+    MempoolRequest, MempoolResponse, MempoolTransactionRequest, MempoolTransactionResponse,
+    TransactionIdentifier,
+};
+use clap::{Parser, Subcommand};
+
+/// Mempool APIs
+///
+/// Used for looking up transactions that haven't been committed yet
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html)
+#[derive(Debug, Subcommand)]
+pub enum MempoolCommand {
+    List(MempoolListCommand),
+    Transaction(MempoolTransactionCommand),
+}
+
+impl MempoolCommand {
+    pub async fn execute(self) -> anyhow::Result<String> {
+        match self {
+            MempoolCommand::List(inner) => format_output(inner.execute().await),
+            MempoolCommand::Transaction(inner) => format_output(inner.execute().await),
+        }
+    }
+}
+
+/// List transactions submitted through the server that are still in mempool
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempool)
+#[derive(Debug, Parser)]
+pub struct MempoolListCommand {
+    #[clap(flatten)]
+    network_args: NetworkArgs,
+    #[clap(flatten)]
+    url_args: UrlArgs,
+}
+
+impl MempoolListCommand {
+    pub async fn execute(self) -> anyhow::Result<MempoolResponse> {
+        let request = MempoolRequest {
+            network_identifier: self.network_args.network_identifier(),
+        };
+        self.url_args.client().mempool(&request).await
+    }
+}
+
+/// Get a transaction in mempool by hash
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempooltransaction)
+#[derive(Debug, Parser)]
+pub struct MempoolTransactionCommand {
+    /// The hash of the transaction
+    #[clap(long)]
+    txn_hash: String,
+    #[clap(flatten)]
+    network_args: NetworkArgs,
+    #[clap(flatten)]
+    url_args: UrlArgs,
+}
+
+impl MempoolTransactionCommand {
+    pub async fn execute(self) -> anyhow::Result<MempoolTransactionResponse> {
+        let request = MempoolTransactionRequest {
+            network_identifier: self.network_args.network_identifier(),
+            transaction_identifier: TransactionIdentifier {
+                hash: self.txn_hash,
+            },
+        };
+        self.url_args.client().mempool_transaction(&request).await
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{format_output, NetworkArgs, UrlArgs};
+use aptos_rosetta::types::{
+    SearchTransactionsRequest, SearchTransactionsResponse, TransactionIdentifier,
+};
+use aptos_types::account_address::AccountAddress;
+use clap::{Parser, Subcommand};
+
+/// Search APIs
+///
+/// Used for finding transactions by hash or by sender
+///
+/// [API Spec](https://www.rosetta-api.org/docs/SearchApi.html)
+#[derive(Debug, Subcommand)]
+pub enum SearchCommand {
+    Transactions(SearchTransactionsCommand),
+}
+
+impl SearchCommand {
+    pub async fn execute(self) -> anyhow::Result<String> {
+        match self {
+            SearchCommand::Transactions(inner) => format_output(inner.execute().await),
+        }
+    }
+}
+
+/// Search for committed transactions
+///
+/// [API Spec](https://www.rosetta-api.org/docs/SearchApi.html#searchtransactions)
+#[derive(Debug, Parser)]
+pub struct SearchTransactionsCommand {
+    /// The hash of the transaction to find
+    #[clap(long)]
+    txn_hash: Option<String>,
+    /// The sender of the transactions to find
+    #[clap(long, parse(try_from_str=aptos::common::types::load_account_arg))]
+    account: Option<AccountAddress>,
+    /// Only find transactions in or before this block
+    #[clap(long)]
+    max_block: Option<u64>,
+    /// Sequence number of the account to start from
+    #[clap(long)]
+    offset: Option<u64>,
+    /// Maximum number of transactions to find
+    #[clap(long)]
+    limit: Option<u64>,
+    /// Only find transactions that succeeded or failed
+    #[clap(long)]
+    success: Option<bool>,
+    #[clap(flatten)]
+    network_args: NetworkArgs,
+    #[clap(flatten)]
+    url_args: UrlArgs,
+}
+
+impl SearchTransactionsCommand {
+    pub async fn execute(self) -> anyhow::Result<SearchTransactionsResponse> {
+        let request = SearchTransactionsRequest {
+            network_identifier: self.network_args.network_identifier(),
+            operator: None,
+            max_block: self.max_block,
+            offset: self.offset,
+            limit: self.limit,
+            transaction_identifier: self.txn_hash.map(|hash| TransactionIdentifier { hash }),
+            account_identifier: self.account.map(|account| account.into()),
+            success: self.success,
+        };
+        self.url_args.client().search_transactions(&request).await
+    }
+}
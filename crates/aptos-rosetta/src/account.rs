@@ -8,12 +8,13 @@
 
 use crate::types::{
     account_module_identifier, account_resource_identifier, coin_module_identifier,
-    AccountBalanceMetadata,
+    stake_module_identifier, stake_pool_resource_identifier, AccountBalanceMetadata,
+    StakeSubAccount,
 };
 use crate::{
     common::{
-        check_network, get_block_index_from_request, handle_request, native_coin, native_coin_tag,
-        with_context,
+        check_network, check_supported_currency, find_coin_currency, get_block_index_from_request,
+        handle_request, native_coin, native_coin_tag, with_context,
     },
     error::{ApiError, ApiResult},
     types::{
@@ -34,7 +35,7 @@ use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
-    sync::{Arc, RwLock},
+    sync::RwLock,
 };
 use warp::Filter;
 
@@ -84,18 +85,13 @@ async fn account_balance(
     let (sequence_number, balances) = get_balances(
         &rest_client,
         request.account_identifier.account_address()?,
+        request.account_identifier.stake_sub_account()?,
         balance_version,
     )
     .await?;
 
-    let amounts = convert_balances_to_amounts(
-        &rest_client,
-        server_context.coin_cache.clone(),
-        request.currencies,
-        balances,
-        balance_version,
-    )
-    .await?;
+    let amounts =
+        convert_balances_to_amounts(&server_context.currencies, request.currencies, balances)?;
 
     Ok(AccountBalanceResponse {
         block_identifier: block_info.block_id,
@@ -107,21 +103,18 @@ async fn account_balance(
 }
 
 /// Lookup currencies and convert them to Rosetta types
-async fn convert_balances_to_amounts(
-    rest_client: &aptos_rest_client::Client,
-    coin_cache: Arc<CoinCache>,
+///
+/// Only coins of the currencies supported by the server are included
+fn convert_balances_to_amounts(
+    supported_currencies: &HashSet<Currency>,
     maybe_filter_currencies: Option<Vec<Currency>>,
     balances: HashMap<TypeTag, Balance>,
-    balance_version: u64,
 ) -> ApiResult<Vec<Amount>> {
     let mut amounts = Vec::new();
 
     // Lookup coins, and fill in currency codes
     for (coin, balance) in balances {
-        if let Some(currency) = coin_cache
-            .get_currency(rest_client, coin, Some(balance_version))
-            .await?
-        {
+        if let Some(currency) = find_coin_currency(supported_currencies, &coin) {
             amounts.push(Amount {
                 value: balance.coin.value.0.to_string(),
                 currency,
@@ -131,6 +124,9 @@ async fn convert_balances_to_amounts(
 
     // Filter based on requested currencies
     if let Some(currencies) = maybe_filter_currencies {
+        for currency in currencies.iter() {
+            check_supported_currency(supported_currencies, currency)?;
+        }
         let mut currencies: HashSet<Currency> = currencies.into_iter().collect();
         // Remove extra currencies not requested
         amounts = amounts
@@ -154,9 +150,12 @@ async fn convert_balances_to_amounts(
 }
 
 /// Retrieve the balances for an account
+///
+/// If a stake sub account is given, the balance is the stake in the account's stake pool instead
 async fn get_balances(
     rest_client: &aptos_rest_client::Client,
     address: AccountAddress,
+    maybe_stake_sub_account: Option<StakeSubAccount>,
     version: u64,
 ) -> ApiResult<(u64, HashMap<TypeTag, Balance>)> {
     if let Ok(response) = rest_client
@@ -190,6 +189,38 @@ async fn get_balances(
             )));
         };
 
+        if let Some(stake_sub_account) = maybe_stake_sub_account {
+            let stake = response
+                .iter()
+                .find(|resource| {
+                    resource.resource_type.address == AccountAddress::ONE
+                        && resource.resource_type.module == stake_module_identifier()
+                        && resource.resource_type.name == stake_pool_resource_identifier()
+                })
+                .map(|resource| {
+                    serde_json::from_value::<StakePool>(resource.data.clone())
+                        .map(|stake_pool| stake_pool.stake(stake_sub_account))
+                        .map_err(|_| {
+                            ApiError::DeserializationFailed(Some(format!(
+                                "StakePool failed to deserialize for {}",
+                                address
+                            )))
+                        })
+                })
+                .transpose()?
+                // Accounts without a stake pool have no stake
+                .unwrap_or_default();
+
+            let mut currency_map = HashMap::new();
+            currency_map.insert(
+                native_coin_tag(),
+                Balance {
+                    coin: AptosCoin { value: U64(stake) },
+                },
+            );
+            return Ok((sequence_number, currency_map));
+        }
+
         let balances = response
             .iter()
             .filter(|resource| {
@@ -225,6 +256,33 @@ async fn get_balances(
     }
 }
 
+/// Type for deserializing the coins held in a `0x1::stake::StakePool`
+#[derive(Debug, Deserialize)]
+struct StakePool {
+    active: AptosCoin,
+    inactive: AptosCoin,
+    pending_active: AptosCoin,
+    pending_inactive: AptosCoin,
+}
+
+impl StakePool {
+    /// Stake in the pool for the given sub account
+    fn stake(&self, stake_sub_account: StakeSubAccount) -> u64 {
+        match stake_sub_account {
+            StakeSubAccount::Total => {
+                self.active.value.0
+                    + self.inactive.value.0
+                    + self.pending_active.value.0
+                    + self.pending_inactive.value.0
+            }
+            StakeSubAccount::Active => self.active.value.0,
+            StakeSubAccount::PendingActive => self.pending_active.value.0,
+            StakeSubAccount::Inactive => self.inactive.value.0,
+            StakeSubAccount::PendingInactive => self.pending_inactive.value.0,
+        }
+    }
+}
+
 /// A cache for currencies, so we don't have to keep looking up the status of it
 #[derive(Debug)]
 pub struct CoinCache {
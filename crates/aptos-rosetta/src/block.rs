@@ -6,13 +6,16 @@ use crate::{
     common::{
         check_network, get_block_index_from_request, get_timestamp, handle_request, with_context,
     },
-    error::ApiResult,
-    types::{Block, BlockIdentifier, BlockRequest, BlockResponse, Transaction},
+    error::{ApiError, ApiResult},
+    types::{
+        Block, BlockIdentifier, BlockRequest, BlockResponse, BlockTransactionRequest,
+        BlockTransactionResponse, Currency, Transaction,
+    },
     RosettaContext,
 };
 use aptos_logger::{debug, trace};
 use aptos_types::chain_id::ChainId;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 use warp::Filter;
 
 pub fn block_route(
@@ -25,6 +28,16 @@ pub fn block_route(
         .and_then(handle_request(block))
 }
 
+pub fn block_transaction_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("block" / "transaction")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(block_transaction))
+}
+
 /// Retrieves a block (in this case a single transaction) given it's identifier.
 ///
 /// Our implementation allows for by `index`, which is the ledger `version` or by
@@ -52,13 +65,72 @@ async fn block(request: BlockRequest, server_context: RosettaContext) -> ApiResu
     )
     .await?;
 
-    let block = build_block(parent_transaction, block, server_context.chain_id).await?;
+    let block = build_block(
+        &server_context.currencies,
+        parent_transaction,
+        block,
+        server_context.chain_id,
+    )
+    .await?;
 
     Ok(BlockResponse { block })
 }
 
+/// Retrieves a single transaction in a block given the block and transaction identifiers
+///
+/// [API Spec](https://www.rosetta-api.org/docs/BlockApi.html#blocktransaction)
+async fn block_transaction(
+    request: BlockTransactionRequest,
+    server_context: RosettaContext,
+) -> ApiResult<BlockTransactionResponse> {
+    debug!("/block/transaction");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/block/transaction",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+
+    let block_identifier = request.block_identifier;
+    let hash = request.transaction_identifier.hash_value()?;
+    let block = server_context
+        .block_cache()?
+        .get_block_by_height(block_identifier.index, true)
+        .await?;
+
+    // Block hashes are derived from the height, so the hash must match the one for the index
+    if BlockIdentifier::from_block(&block, server_context.chain_id) != block_identifier {
+        return Err(ApiError::BlockNotFound(Some(format!(
+            "Block hash {} doesn't match block {}",
+            block_identifier.hash, block_identifier.index
+        ))));
+    }
+
+    let txn = block
+        .transactions
+        .unwrap_or_default()
+        .into_iter()
+        .find(|txn| {
+            txn.transaction_info()
+                .map(|info| aptos_crypto::HashValue::from(info.hash) == hash)
+                .unwrap_or(false)
+        })
+        .ok_or_else(|| {
+            ApiError::TransactionNotFound(Some(format!(
+                "Transaction {} is not in block {}",
+                hash, block_identifier.index
+            )))
+        })?;
+
+    Ok(BlockTransactionResponse {
+        transaction: Transaction::from_transaction(&server_context.currencies, txn).await?,
+    })
+}
+
 /// Build up the transaction, which should contain the `operations` as the change set
 async fn build_block(
+    currencies: &HashSet<Currency>,
     parent_block_identifier: BlockIdentifier,
     block: aptos_rest_client::aptos_api_types::Block,
     chain_id: ChainId,
@@ -71,7 +143,7 @@ async fn build_block(
     let mut transactions: Vec<Transaction> = Vec::new();
     if let Some(txns) = block.transactions {
         for txn in txns {
-            transactions.push(Transaction::from_transaction(txn).await?)
+            transactions.push(Transaction::from_transaction(currencies, txn).await?)
         }
     }
 
@@ -4,14 +4,17 @@
 use crate::common::native_coin;
 use crate::types::{
     AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, BlockRequest, BlockResponse,
-    ConstructionCombineRequest, ConstructionCombineResponse, ConstructionDeriveRequest,
-    ConstructionDeriveResponse, ConstructionHashRequest, ConstructionMetadata,
-    ConstructionMetadataRequest, ConstructionMetadataResponse, ConstructionParseRequest,
-    ConstructionParseResponse, ConstructionPayloadsRequest, ConstructionPayloadsResponse,
-    ConstructionPreprocessRequest, ConstructionPreprocessResponse, ConstructionSubmitRequest,
-    ConstructionSubmitResponse, Error, MetadataRequest, NetworkIdentifier, NetworkListResponse,
-    NetworkOptionsResponse, NetworkRequest, NetworkStatusResponse, Operation, PreprocessMetadata,
-    PublicKey, Signature, SignatureType, TransactionIdentifier, TransactionIdentifierResponse,
+    BlockTransactionRequest, BlockTransactionResponse, ConstructionCombineRequest,
+    ConstructionCombineResponse, ConstructionDeriveRequest, ConstructionDeriveResponse,
+    ConstructionHashRequest, ConstructionMetadata, ConstructionMetadataRequest,
+    ConstructionMetadataResponse, ConstructionParseRequest, ConstructionParseResponse,
+    ConstructionPayloadsRequest, ConstructionPayloadsResponse, ConstructionPreprocessRequest,
+    ConstructionPreprocessResponse, ConstructionSubmitRequest, ConstructionSubmitResponse,
+    Currency, Error, EventsBlocksRequest, EventsBlocksResponse, MempoolRequest, MempoolResponse,
+    MempoolTransactionRequest, MempoolTransactionResponse, MetadataRequest, NetworkIdentifier,
+    NetworkListResponse, NetworkOptionsResponse, NetworkRequest, NetworkStatusResponse, Operation,
+    PreprocessMetadata, PublicKey, SearchTransactionsRequest, SearchTransactionsResponse,
+    Signature, SignatureType, TransactionIdentifier, TransactionIdentifierResponse,
 };
 use anyhow::anyhow;
 use aptos_crypto::ed25519::Ed25519PrivateKey;
@@ -54,6 +57,13 @@ impl RosettaClient {
         self.make_call("block", request).await
     }
 
+    pub async fn block_transaction(
+        &self,
+        request: &BlockTransactionRequest,
+    ) -> anyhow::Result<BlockTransactionResponse> {
+        self.make_call("block/transaction", request).await
+    }
+
     pub async fn combine(
         &self,
         request: &ConstructionCombineRequest,
@@ -110,6 +120,24 @@ impl RosettaClient {
         self.make_call("construction/submit", request).await
     }
 
+    pub async fn events_blocks(
+        &self,
+        request: &EventsBlocksRequest,
+    ) -> anyhow::Result<EventsBlocksResponse> {
+        self.make_call("events/blocks", request).await
+    }
+
+    pub async fn mempool(&self, request: &MempoolRequest) -> anyhow::Result<MempoolResponse> {
+        self.make_call("mempool", request).await
+    }
+
+    pub async fn mempool_transaction(
+        &self,
+        request: &MempoolTransactionRequest,
+    ) -> anyhow::Result<MempoolTransactionResponse> {
+        self.make_call("mempool/transaction", request).await
+    }
+
     pub async fn network_list(&self) -> anyhow::Result<NetworkListResponse> {
         self.make_call("network/list", &MetadataRequest {}).await
     }
@@ -128,6 +156,13 @@ impl RosettaClient {
         self.make_call("network/status", request).await
     }
 
+    pub async fn search_transactions(
+        &self,
+        request: &SearchTransactionsRequest,
+    ) -> anyhow::Result<SearchTransactionsResponse> {
+        self.make_call("search/transactions", request).await
+    }
+
     async fn make_call<'a, I: Serialize + Debug, O: DeserializeOwned>(
         &'a self,
         path: &'static str,
@@ -186,6 +221,7 @@ impl RosettaClient {
         private_key: &Ed25519PrivateKey,
         receiver: AccountAddress,
         amount: u64,
+        currency: Currency,
         expiry_time_secs: u64,
         sequence_number: Option<u64>,
         max_gas: Option<u64>,
@@ -199,8 +235,8 @@ impl RosettaClient {
 
         // A transfer operation is made up of a withdraw and a deposit
         let operations = vec![
-            Operation::withdraw(0, None, sender, native_coin(), amount),
-            Operation::deposit(1, None, receiver, native_coin(), amount),
+            Operation::withdraw(0, None, sender, currency.clone(), amount),
+            Operation::deposit(1, None, receiver, currency, amount),
         ];
 
         self.submit_operations(
@@ -248,6 +284,146 @@ impl RosettaClient {
         .await
     }
 
+    pub async fn set_delegated_voter(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        new_voter: AccountAddress,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+        max_gas: Option<u64>,
+        gas_unit_price: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        self.submit_stake_operation(
+            network_identifier,
+            private_key,
+            |owner| vec![Operation::set_delegated_voter(0, None, owner, new_voter)],
+            expiry_time_secs,
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+    }
+
+    pub async fn add_stake(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        amount: u64,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+        max_gas: Option<u64>,
+        gas_unit_price: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        self.submit_stake_operation(
+            network_identifier,
+            private_key,
+            |owner| vec![Operation::add_stake(0, None, owner, amount)],
+            expiry_time_secs,
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+    }
+
+    pub async fn unlock_stake(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        amount: u64,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+        max_gas: Option<u64>,
+        gas_unit_price: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        self.submit_stake_operation(
+            network_identifier,
+            private_key,
+            |owner| Operation::unlock_stake(0, None, owner, amount),
+            expiry_time_secs,
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+    }
+
+    pub async fn withdraw_stake(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        amount: u64,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+        max_gas: Option<u64>,
+        gas_unit_price: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        self.submit_stake_operation(
+            network_identifier,
+            private_key,
+            |owner| vec![Operation::withdraw_stake(0, None, owner, amount)],
+            expiry_time_secs,
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+    }
+
+    pub async fn reset_lockup(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+        max_gas: Option<u64>,
+        gas_unit_price: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        self.submit_stake_operation(
+            network_identifier,
+            private_key,
+            |owner| vec![Operation::reset_lockup(0, None, owner)],
+            expiry_time_secs,
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+    }
+
+    /// Submits the operations of a single staking action on the stake pool owned by the key's
+    /// account
+    async fn submit_stake_operation<F: FnOnce(AccountAddress) -> Vec<Operation>>(
+        &self,
+        network_identifier: &NetworkIdentifier,
+        private_key: &Ed25519PrivateKey,
+        operations: F,
+        expiry_time_secs: u64,
+        sequence_number: Option<u64>,
+        max_gas: Option<u64>,
+        gas_unit_price: Option<u64>,
+    ) -> anyhow::Result<TransactionIdentifier> {
+        let sender = self
+            .get_account_address(network_identifier.clone(), private_key)
+            .await?;
+        let mut keys = HashMap::new();
+        keys.insert(sender, private_key);
+
+        self.submit_operations(
+            sender,
+            network_identifier.clone(),
+            &keys,
+            operations(sender),
+            expiry_time_secs,
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+    }
+
     /// Retrieves the account address from the derivation path if there isn't an overriding account specified
     async fn get_account_address(
         &self,
@@ -16,7 +16,7 @@ use aptos_sdk::move_types::language_storage::{StructTag, TypeTag};
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use futures::future::BoxFuture;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{convert::Infallible, fmt::LowerHex, future::Future, str::FromStr};
+use std::{collections::HashSet, convert::Infallible, fmt::LowerHex, future::Future, str::FromStr};
 use warp::Filter;
 
 /// The year 2000 in milliseconds, as this is the lower limit for Rosetta API implementations
@@ -158,14 +158,35 @@ pub fn native_coin_tag() -> TypeTag {
     })
 }
 
-pub fn is_native_coin(currency: &Currency) -> ApiResult<()> {
-    if currency == &native_coin() {
+pub fn is_native_coin(currency: &Currency) -> bool {
+    currency == &native_coin()
+}
+
+/// Checks that the currency is one of the currencies served by this server
+pub fn check_supported_currency(
+    currencies: &HashSet<Currency>,
+    currency: &Currency,
+) -> ApiResult<()> {
+    if currencies.contains(currency) {
         Ok(())
     } else {
         Err(ApiError::UnsupportedCurrency(Some(currency.symbol.clone())))
     }
 }
 
+/// Finds the supported currency for a coin type, if there is one
+pub fn find_coin_currency(currencies: &HashSet<Currency>, coin_type: &TypeTag) -> Option<Currency> {
+    currencies
+        .iter()
+        .find(|currency| {
+            currency
+                .coin_type()
+                .map(|tag| &tag == coin_type)
+                .unwrap_or(false)
+        })
+        .cloned()
+}
+
 /// Determines which block to pull for the request
 pub async fn get_block_index_from_request(
     server_context: &RosettaContext,
@@ -282,9 +303,16 @@ pub fn to_hex_lower<T: LowerHex>(obj: &T) -> String {
 
 #[cfg(test)]
 mod test {
-    use crate::common::BlockHash;
-    use aptos_types::chain_id::{ChainId, NamedChain};
-    use std::str::FromStr;
+    use crate::common::{
+        check_supported_currency, find_coin_currency, native_coin, native_coin_tag, BlockHash,
+    };
+    use crate::types::{Currency, CurrencyMetadata};
+    use aptos_sdk::move_types::language_storage::{StructTag, TypeTag};
+    use aptos_types::{
+        account_address::AccountAddress,
+        chain_id::{ChainId, NamedChain},
+    };
+    use std::{collections::HashSet, str::FromStr};
 
     #[test]
     pub fn chain_id_height_check() {
@@ -319,6 +347,43 @@ mod test {
         }
     }
 
+    #[test]
+    pub fn supported_currencies() {
+        let usdc_tag = TypeTag::Struct(StructTag {
+            address: AccountAddress::from_hex_literal("0x1234").unwrap(),
+            module: "usdc".parse().unwrap(),
+            name: "USDC".parse().unwrap(),
+            type_params: vec![],
+        });
+        let usdc = Currency {
+            symbol: "USDC".to_string(),
+            decimals: 6,
+            metadata: Some(CurrencyMetadata {
+                move_type: "0x1234::usdc::USDC".to_string(),
+            }),
+        };
+        let currencies: HashSet<Currency> = vec![native_coin(), usdc.clone()].into_iter().collect();
+
+        assert_eq!(
+            find_coin_currency(&currencies, &native_coin_tag()),
+            Some(native_coin())
+        );
+        assert_eq!(
+            find_coin_currency(&currencies, &usdc_tag),
+            Some(usdc.clone())
+        );
+        assert_eq!(find_coin_currency(&currencies, &TypeTag::U64), None);
+        check_supported_currency(&currencies, &usdc).expect("USDC should be supported");
+
+        // A currency must match exactly, including its decimals
+        let wrong_decimals = Currency {
+            decimals: 8,
+            ..usdc
+        };
+        check_supported_currency(&currencies, &wrong_decimals)
+            .expect_err("Mismatched currency should not be supported");
+    }
+
     #[test]
     pub fn invalid_block_hashes() {
         let invalid_block_hashes: Vec<&str> =
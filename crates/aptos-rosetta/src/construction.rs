@@ -26,8 +26,8 @@
 
 use crate::{
     common::{
-        check_network, decode_bcs, decode_key, encode_bcs, get_account, handle_request,
-        native_coin, with_context,
+        check_network, decode_bcs, decode_key, encode_bcs, find_coin_currency, get_account,
+        handle_request, is_native_coin, native_coin, with_context,
     },
     error::{ApiError, ApiResult},
     types::{InternalOperation, *},
@@ -39,10 +39,7 @@ use aptos_crypto::{
 };
 use aptos_logger::debug;
 use aptos_sdk::{
-    move_types::{
-        identifier::Identifier,
-        language_storage::{StructTag, TypeTag},
-    },
+    move_types::{identifier::Identifier, language_storage::TypeTag},
     transaction_builder::TransactionFactory,
 };
 use aptos_types::{
@@ -51,6 +48,7 @@ use aptos_types::{
         authenticator::AuthenticationKey, RawTransaction, SignedTransaction, TransactionPayload,
     },
 };
+use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::time::{SystemTime, UNIX_EPOCH};
 use warp::Filter;
@@ -222,6 +220,9 @@ async fn construction_hash(
 
 const MAX_GAS_UNITS_PER_REQUEST: u64 = 1_000_000;
 
+/// Maximum number of submitted transactions tracked for the mempool API
+const MAX_SUBMITTED_TRANSACTIONS: usize = 10_000;
+
 /// Construction metadata command
 ///
 /// Retrieve sequence number for submitting transactions
@@ -271,23 +272,18 @@ async fn construction_metadata(
             .map_err(|err| ApiError::GasEstimationFailed(Some(err.to_string())))?
             .into_inner();
 
-        let maximum_possible_gas =
-            if let InternalOperation::Transfer(ref transfer) = request.options.internal_operation {
-                std::cmp::min(
-                    (account_balance
-                        .coin
-                        .value
-                        .0
-                        .saturating_sub(transfer.amount.0))
-                        / gas_price_per_unit,
-                    MAX_GAS_UNITS_PER_REQUEST,
-                )
-            } else {
-                std::cmp::min(
-                    account_balance.coin.value.0 / gas_price_per_unit,
-                    MAX_GAS_UNITS_PER_REQUEST,
-                )
-            };
+        // Coins leaving the account can't be used to pay for gas
+        let amount_spent = match request.options.internal_operation {
+            InternalOperation::Transfer(ref transfer) if is_native_coin(&transfer.currency) => {
+                transfer.amount.0
+            }
+            InternalOperation::AddStake(ref add_stake) => add_stake.amount.0,
+            _ => 0,
+        };
+        let maximum_possible_gas = std::cmp::min(
+            account_balance.coin.value.0.saturating_sub(amount_spent) / gas_price_per_unit,
+            MAX_GAS_UNITS_PER_REQUEST,
+        );
 
         let transaction_factory = TransactionFactory::new(server_context.chain_id)
             .with_gas_unit_price(gas_price_per_unit)
@@ -396,7 +392,7 @@ async fn construction_parse(
                 && coin_module_identifier() == module_name
                 && transfer_function_identifier() == function_name
             {
-                parse_transfer_operation(sender, &type_args, &args)?
+                parse_transfer_operation(&server_context.currencies, sender, &type_args, &args)?
             } else if AccountAddress::ONE == *module.address()
                 && aptos_account_module_identifier() == module_name
                 && transfer_function_identifier() == function_name
@@ -412,6 +408,34 @@ async fn construction_parse(
                 && set_operator_function_identifier() == function_name
             {
                 parse_set_operator_operation(sender, &type_args, &args)?
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && set_delegated_voter_function_identifier() == function_name
+            {
+                parse_set_delegated_voter_operation(sender, &type_args, &args)?
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && add_stake_function_identifier() == function_name
+            {
+                let amount = parse_stake_amount("Add stake", &type_args, &args)?;
+                vec![Operation::add_stake(0, None, sender, amount)]
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && unlock_function_identifier() == function_name
+            {
+                let amount = parse_stake_amount("Unlock stake", &type_args, &args)?;
+                Operation::unlock_stake(0, None, sender, amount)
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && withdraw_function_identifier() == function_name
+            {
+                let amount = parse_stake_amount("Withdraw stake", &type_args, &args)?;
+                vec![Operation::withdraw_stake(0, None, sender, amount)]
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && increase_lockup_function_identifier() == function_name
+            {
+                parse_reset_lockup_operation(sender, &type_args, &args)?
             } else {
                 return Err(ApiError::TransactionParseError(Some(format!(
                     "Unsupported entry function type {:x}::{}::{}",
@@ -466,31 +490,21 @@ fn parse_create_account_operation(
 }
 
 fn parse_transfer_operation(
+    currencies: &HashSet<Currency>,
     sender: AccountAddress,
     type_args: &[TypeTag],
     args: &[Vec<u8>],
 ) -> ApiResult<Vec<Operation>> {
     let mut operations = Vec::new();
 
-    // Check coin is the native coin
-    if let Some(TypeTag::Struct(StructTag {
-        address,
-        module,
-        name,
-        type_params,
-    })) = type_args.first()
-    {
-        // Currency must be the native coin for now
-        if *address != AccountAddress::ONE
-            || *module != aptos_coin_module_identifier()
-            || *name != aptos_coin_resource_identifier()
-            || !type_params.is_empty()
-        {
-            return Err(ApiError::TransactionParseError(Some(format!(
-                "Invalid coin for transfer {:x}::{}::{}",
-                address, module, name
-            ))));
-        }
+    // Check coin is one of the supported currencies
+    let currency = if let Some(coin_type) = type_args.first() {
+        find_coin_currency(currencies, coin_type).ok_or_else(|| {
+            ApiError::TransactionParseError(Some(format!(
+                "Invalid coin for transfer {}",
+                coin_type
+            )))
+        })?
     } else {
         return Err(ApiError::TransactionParseError(Some(
             "No coin type in transfer".to_string(),
@@ -514,8 +528,14 @@ fn parse_transfer_operation(
         )));
     };
 
-    operations.push(Operation::withdraw(0, None, sender, native_coin(), amount));
-    operations.push(Operation::deposit(1, None, receiver, native_coin(), amount));
+    operations.push(Operation::withdraw(
+        0,
+        None,
+        sender,
+        currency.clone(),
+        amount,
+    ));
+    operations.push(Operation::deposit(1, None, receiver, currency, amount));
     Ok(operations)
 }
 
@@ -580,6 +600,71 @@ fn parse_set_operator_operation(
     }
 }
 
+fn parse_set_delegated_voter_operation(
+    sender: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    // There are no typeargs for set delegated voter
+    if !type_args.is_empty() {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "Set delegated voter should not have type arguments: {:?}",
+            type_args
+        ))));
+    }
+
+    // Set delegated voter
+    if let Some(encoded_voter) = args.first() {
+        let voter: AccountAddress = bcs::from_bytes(encoded_voter)?;
+
+        Ok(vec![Operation::set_delegated_voter(0, None, sender, voter)])
+    } else {
+        Err(ApiError::InvalidOperations(Some(
+            "Set delegated voter doesn't have an address argument".to_string(),
+        )))
+    }
+}
+
+/// Parses the amount argument of `add_stake`, `unlock` and `withdraw`
+fn parse_stake_amount(
+    operation_name: &str,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<u64> {
+    // There are no typeargs for staking operations
+    if !type_args.is_empty() {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "{} should not have type arguments: {:?}",
+            operation_name, type_args
+        ))));
+    }
+
+    if let Some(encoded_amount) = args.first() {
+        Ok(bcs::from_bytes(encoded_amount)?)
+    } else {
+        Err(ApiError::InvalidOperations(Some(format!(
+            "{} doesn't have an amount argument",
+            operation_name
+        ))))
+    }
+}
+
+fn parse_reset_lockup_operation(
+    sender: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    // There are no typeargs or args for reset lockup
+    if !type_args.is_empty() || !args.is_empty() {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "Reset lockup should not have arguments: {:?} {:?}",
+            type_args, args
+        ))));
+    }
+
+    Ok(vec![Operation::reset_lockup(0, None, sender)])
+}
+
 /// Construction payloads command (OFFLINE)
 ///
 /// Constructs payloads for given known operations
@@ -593,7 +678,7 @@ async fn construction_payloads(
     check_network(request.network_identifier, &server_context)?;
 
     // Retrieve the real operation we're doing
-    let operation = InternalOperation::extract(&request.operations)?;
+    let operation = InternalOperation::extract(&server_context.currencies, &request.operations)?;
     let metadata = if let Some(ref metadata) = request.metadata {
         metadata
     } else {
@@ -645,7 +730,8 @@ async fn construction_preprocess(
     debug!("/construction/preprocess {:?}", request);
     check_network(request.network_identifier, &server_context)?;
 
-    let internal_operation = InternalOperation::extract(&request.operations)?;
+    let internal_operation =
+        InternalOperation::extract(&server_context.currencies, &request.operations)?;
     let required_public_keys = vec![internal_operation.sender().into()];
 
     if let Some(gas_price) = request.metadata.as_ref().and_then(|inner| inner.gas_price) {
@@ -745,7 +831,30 @@ async fn construction_submit(
 
     let txn: SignedTransaction = decode_bcs(&request.signed_transaction, "SignedTransaction")?;
     let response = rest_client.submit(&txn).await?;
+    let hash = response.into_inner().hash;
+
+    // Keep track of the transaction, so it can be listed while it's in mempool.  Transactions
+    // can't be in mempool past their expiration, so those are dropped along the way.
+    let now_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| {
+            ApiError::InternalError(Some(format!("Failed to get current time {}", err)))
+        })?
+        .as_secs();
+    let mut submitted_transactions = server_context.submitted_transactions.lock().await;
+    submitted_transactions.retain(|_, expiration_secs| *expiration_secs > now_secs);
+    if submitted_transactions.len() >= MAX_SUBMITTED_TRANSACTIONS {
+        // Forget the transaction closest to expiring, it'll leave mempool soonest anyways
+        if let Some(hash) = submitted_transactions
+            .iter()
+            .min_by_key(|(_, expiration_secs)| **expiration_secs)
+            .map(|(hash, _)| *hash)
+        {
+            submitted_transactions.remove(&hash);
+        }
+    }
+    submitted_transactions.insert(hash.into(), txn.expiration_timestamp_secs());
     Ok(ConstructionSubmitResponse {
-        transaction_identifier: response.inner().hash.into(),
+        transaction_identifier: hash.into(),
     })
 }
@@ -0,0 +1,83 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rosetta Events API
+//!
+//! Aptos has instant finality, so blocks are never removed, and every block has exactly one
+//! `block_added` event, with the block height as its sequence.
+//!
+//! See: [Events API Spec](https://www.rosetta-api.org/docs/EventsApi.html)
+
+use crate::{
+    common::{check_network, handle_request, with_context},
+    error::{ApiError, ApiResult},
+    types::{BlockEvent, BlockEventType, EventsBlocksRequest, EventsBlocksResponse},
+    RosettaContext,
+};
+use aptos_logger::{debug, trace};
+use warp::Filter;
+
+/// Maximum number of block events returned in a single request
+const MAX_BLOCK_EVENTS: u64 = 100;
+
+pub fn events_blocks_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("events" / "blocks")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(events_blocks))
+}
+
+/// Retrieves block events starting from the given offset
+///
+/// [API Spec](https://www.rosetta-api.org/docs/EventsApi.html#eventsblocks)
+async fn events_blocks(
+    request: EventsBlocksRequest,
+    server_context: RosettaContext,
+) -> ApiResult<EventsBlocksResponse> {
+    debug!("/events/blocks");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/events/blocks",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    let chain_id = server_context.chain_id;
+    let rest_client = server_context.rest_client()?;
+    let block_cache = server_context.block_cache()?;
+
+    let response = rest_client.get_ledger_information().await?;
+    let state = response.state();
+    let max_sequence = state.block_height;
+
+    let offset = request.offset.unwrap_or(state.oldest_block_height);
+    if offset < state.oldest_block_height {
+        return Err(ApiError::BlockPruned(Some(format!(
+            "Block {} is pruned, oldest block is {}",
+            offset, state.oldest_block_height
+        ))));
+    }
+    let limit = std::cmp::min(request.limit.unwrap_or(MAX_BLOCK_EVENTS), MAX_BLOCK_EVENTS);
+    let end = std::cmp::min(offset.saturating_add(limit), max_sequence.saturating_add(1));
+
+    let mut events = Vec::new();
+    for height in offset..end {
+        let block_identifier = block_cache
+            .get_block_info_by_height(height, chain_id)
+            .await?
+            .block_id;
+        events.push(BlockEvent {
+            sequence: height,
+            block_identifier,
+            event_type: BlockEventType::BlockAdded,
+        });
+    }
+
+    Ok(EventsBlocksResponse {
+        max_sequence,
+        events,
+    })
+}
@@ -8,16 +8,18 @@
 use crate::{
     account::CoinCache,
     block::BlockRetriever,
-    common::{handle_request, with_context},
+    common::{handle_request, native_coin, with_context},
     error::{ApiError, ApiResult},
+    types::Currency,
 };
 use aptos_config::config::ApiConfig;
+use aptos_crypto::HashValue;
 use aptos_logger::debug;
 use aptos_types::account_address::AccountAddress;
 use aptos_types::chain_id::ChainId;
 use aptos_warp_webserver::WebServer;
 use aptos_warp_webserver::{logger, Error};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::{convert::Infallible, sync::Arc};
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
@@ -30,7 +32,10 @@ use warp::{
 mod account;
 mod block;
 mod construction;
+mod events;
+mod mempool;
 mod network;
+mod search;
 
 pub mod client;
 pub mod common;
@@ -51,9 +56,14 @@ pub struct RosettaContext {
     pub chain_id: ChainId,
     /// Coin cache for looking up Currency details
     pub coin_cache: Arc<CoinCache>,
+    /// Currencies supported by this server, which always includes the native coin
+    pub currencies: Arc<HashSet<Currency>>,
     /// Block index cache
     pub block_cache: Option<Arc<BlockRetriever>>,
     pub accounts: Arc<Mutex<BTreeMap<AccountAddress, SequenceNumber>>>,
+    /// Transactions submitted through this server, which may still be in mempool, along with
+    /// their expiration timestamp in seconds
+    pub submitted_transactions: Arc<Mutex<BTreeMap<HashValue, u64>>>,
 }
 
 impl RosettaContext {
@@ -79,6 +89,7 @@ pub fn bootstrap(
     chain_id: ChainId,
     api_config: ApiConfig,
     rest_client: Option<aptos_rest_client::Client>,
+    supported_currencies: HashSet<Currency>,
 ) -> anyhow::Result<tokio::runtime::Runtime> {
     let runtime = tokio::runtime::Builder::new_multi_thread()
         .thread_name("rosetta")
@@ -88,7 +99,12 @@ pub fn bootstrap(
 
     debug!("Starting up Rosetta server with {:?}", api_config);
 
-    runtime.spawn(bootstrap_async(chain_id, api_config, rest_client));
+    runtime.spawn(bootstrap_async(
+        chain_id,
+        api_config,
+        rest_client,
+        supported_currencies,
+    ));
     Ok(runtime)
 }
 
@@ -97,6 +113,7 @@ pub async fn bootstrap_async(
     chain_id: ChainId,
     api_config: ApiConfig,
     rest_client: Option<aptos_rest_client::Client>,
+    supported_currencies: HashSet<Currency>,
 ) -> anyhow::Result<JoinHandle<()>> {
     debug!("Starting up Rosetta server with {:?}", api_config);
 
@@ -113,6 +130,41 @@ pub async fn bootstrap_async(
         );
     }
 
+    let coin_cache = Arc::new(CoinCache::new());
+    let mut currencies = HashSet::new();
+    currencies.insert(native_coin());
+    for currency in supported_currencies {
+        let coin_type = currency
+            .coin_type()
+            .map_err(|_| anyhow::anyhow!("Currency {:?} has an invalid coin type", currency))?;
+
+        // Ensure the configured currency matches the on-chain `CoinInfo`
+        if let Some(ref client) = rest_client {
+            let on_chain_currency = coin_cache
+                .get_currency(client, coin_type, None)
+                .await
+                .map_err(|err| anyhow::anyhow!("Failed to look up {:?}: {:?}", currency, err))?;
+            match on_chain_currency {
+                Some(on_chain_currency)
+                    if on_chain_currency.symbol == currency.symbol
+                        && on_chain_currency.decimals == currency.decimals =>
+                {
+                    // Use the on-chain currency, so the move type is in its canonical form
+                    currencies.insert(on_chain_currency);
+                }
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "Currency {:?} doesn't match the on-chain coin info {:?}",
+                        currency,
+                        on_chain_currency
+                    ))
+                }
+            }
+        } else {
+            currencies.insert(currency);
+        }
+    }
+
     let api = WebServer::from(api_config);
     let handle = tokio::spawn(async move {
         // If it's Online mode, add the block cache
@@ -124,9 +176,11 @@ pub async fn bootstrap_async(
         let context = RosettaContext {
             rest_client: rest_client.clone(),
             chain_id,
-            coin_cache: Arc::new(CoinCache::new()),
+            coin_cache,
+            currencies: Arc::new(currencies),
             block_cache,
             accounts: Arc::new(Mutex::new(BTreeMap::new())),
+            submitted_transactions: Arc::new(Mutex::new(BTreeMap::new())),
         };
         api.serve(routes(context)).await;
     });
@@ -139,6 +193,7 @@ pub fn routes(
 ) -> impl Filter<Extract = impl Reply, Error = Infallible> + Clone {
     account::routes(context.clone())
         .or(block::block_route(context.clone()))
+        .or(block::block_transaction_route(context.clone()))
         .or(construction::combine_route(context.clone()))
         .or(construction::derive_route(context.clone()))
         .or(construction::hash_route(context.clone()))
@@ -147,9 +202,13 @@ pub fn routes(
         .or(construction::payloads_route(context.clone()))
         .or(construction::preprocess_route(context.clone()))
         .or(construction::submit_route(context.clone()))
+        .or(events::events_blocks_route(context.clone()))
+        .or(mempool::mempool_route(context.clone()))
+        .or(mempool::mempool_transaction_route(context.clone()))
         .or(network::list_route(context.clone()))
         .or(network::options_route(context.clone()))
         .or(network::status_route(context.clone()))
+        .or(search::search_transactions_route(context.clone()))
         .or(health_check_route(context))
         .with(
             warp::cors()
@@ -5,11 +5,14 @@
 
 use aptos_config::config::ApiConfig;
 use aptos_node::AptosNodeArgs;
-use aptos_rosetta::bootstrap;
+use aptos_rosetta::{bootstrap, types::Currency};
 use aptos_types::chain_id::ChainId;
 use clap::Parser;
 use std::{
+    collections::HashSet,
+    fs::File,
     net::SocketAddr,
+    path::PathBuf,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -78,8 +81,16 @@ async fn main() {
 
     println!("aptos-rosetta: Starting rosetta");
     // Ensure runtime for Rosetta is up and running
-    let _rosetta = bootstrap(args.chain_id(), args.api_config(), args.rest_client())
-        .expect("aptos-rosetta: Should bootstrap rosetta server");
+    let supported_currencies = args
+        .supported_currencies()
+        .expect("aptos-rosetta: Should load currency config file");
+    let _rosetta = bootstrap(
+        args.chain_id(),
+        args.api_config(),
+        args.rest_client(),
+        supported_currencies,
+    )
+    .expect("aptos-rosetta: Should bootstrap rosetta server");
 
     println!("aptos-rosetta: Rosetta started");
     // Run until there is an interrupt
@@ -99,6 +110,9 @@ trait ServerArgs {
 
     /// Retrieve the chain id
     fn chain_id(&self) -> ChainId;
+
+    /// Retrieve the currencies to support in addition to the native coin
+    fn supported_currencies(&self) -> anyhow::Result<HashSet<Currency>>;
 }
 
 /// Aptos Rosetta API Server
@@ -139,6 +153,14 @@ impl ServerArgs for CommandArgs {
             CommandArgs::Online(args) => args.chain_id(),
         }
     }
+
+    fn supported_currencies(&self) -> anyhow::Result<HashSet<Currency>> {
+        match self {
+            CommandArgs::OnlineRemote(args) => args.supported_currencies(),
+            CommandArgs::Offline(args) => args.supported_currencies(),
+            CommandArgs::Online(args) => args.supported_currencies(),
+        }
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -158,6 +180,12 @@ pub struct OfflineArgs {
     /// ChainId to be used for the server e.g. TESTNET
     #[clap(long, default_value = "TESTING")]
     chain_id: ChainId,
+    /// Path to a JSON file with a list of additional currencies to support
+    ///
+    /// Each currency must match the on-chain `CoinInfo` of its coin type e.g.
+    /// `[{"symbol": "USDC", "decimals": 6, "metadata": {"move_type": "0x1234::usdc::USDC"}}]`
+    #[clap(long)]
+    currency_config_file: Option<PathBuf>,
 }
 
 impl ServerArgs for OfflineArgs {
@@ -179,6 +207,15 @@ impl ServerArgs for OfflineArgs {
     fn chain_id(&self) -> ChainId {
         self.chain_id
     }
+
+    fn supported_currencies(&self) -> anyhow::Result<HashSet<Currency>> {
+        if let Some(ref path) = self.currency_config_file {
+            let file = File::open(path)?;
+            Ok(serde_json::from_reader(file)?)
+        } else {
+            Ok(HashSet::new())
+        }
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -202,6 +239,10 @@ impl ServerArgs for OnlineRemoteArgs {
     fn chain_id(&self) -> ChainId {
         self.offline_args.chain_id
     }
+
+    fn supported_currencies(&self) -> anyhow::Result<HashSet<Currency>> {
+        self.offline_args.supported_currencies()
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -226,4 +267,8 @@ impl ServerArgs for OnlineLocalArgs {
     fn chain_id(&self) -> ChainId {
         self.online_args.offline_args.chain_id
     }
+
+    fn supported_currencies(&self) -> anyhow::Result<HashSet<Currency>> {
+        self.online_args.offline_args.supported_currencies()
+    }
 }
@@ -0,0 +1,142 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rosetta Mempool API
+//!
+//! The REST API doesn't expose the contents of mempool, so only transactions submitted through
+//! this server can be listed.  Any pending transaction can still be looked up by hash.
+//!
+//! See: [Mempool API Spec](https://www.rosetta-api.org/docs/MempoolApi.html)
+
+use crate::{
+    common::{check_network, handle_request, with_context},
+    error::{ApiError, ApiResult},
+    types::{
+        MempoolRequest, MempoolResponse, MempoolTransactionRequest, MempoolTransactionResponse,
+        Transaction,
+    },
+    RosettaContext,
+};
+use aptos_crypto::HashValue;
+use aptos_logger::{debug, trace};
+use aptos_rest_client::aptos_api_types::PendingTransaction;
+use warp::Filter;
+
+pub fn mempool_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("mempool")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(mempool))
+}
+
+pub fn mempool_transaction_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("mempool" / "transaction")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(mempool_transaction))
+}
+
+/// Lists the transactions submitted through this server that are still in mempool
+///
+/// Transactions that have been committed, or have expired, are no longer tracked afterwards.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempool)
+async fn mempool(
+    request: MempoolRequest,
+    server_context: RosettaContext,
+) -> ApiResult<MempoolResponse> {
+    debug!("/mempool");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/mempool",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    let rest_client = server_context.rest_client()?;
+
+    // Don't hold the lock across calls to the REST API, which would block submissions
+    let submitted_hashes: Vec<HashValue> = server_context
+        .submitted_transactions
+        .lock()
+        .await
+        .keys()
+        .copied()
+        .collect();
+    let mut transaction_identifiers = Vec::new();
+    let mut finished_transactions = Vec::new();
+    for hash in submitted_hashes {
+        if get_pending_transaction(&rest_client, hash).await?.is_some() {
+            transaction_identifiers.push(hash.into());
+        } else {
+            finished_transactions.push(hash);
+        }
+    }
+    if !finished_transactions.is_empty() {
+        let mut submitted_transactions = server_context.submitted_transactions.lock().await;
+        for hash in finished_transactions {
+            submitted_transactions.remove(&hash);
+        }
+    }
+
+    Ok(MempoolResponse {
+        transaction_identifiers,
+    })
+}
+
+/// Retrieves a transaction that is still in mempool by its hash
+///
+/// Operations are parsed from the transaction's payload, and have no status until the
+/// transaction is committed.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempooltransaction)
+async fn mempool_transaction(
+    request: MempoolTransactionRequest,
+    server_context: RosettaContext,
+) -> ApiResult<MempoolTransactionResponse> {
+    debug!("/mempool/transaction");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/mempool/transaction",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    let rest_client = server_context.rest_client()?;
+
+    let hash = request.transaction_identifier.hash_value()?;
+    if let Some(txn) = get_pending_transaction(&rest_client, hash).await? {
+        Ok(MempoolTransactionResponse {
+            transaction: Transaction::from_pending_transaction(&server_context.currencies, txn),
+        })
+    } else {
+        Err(ApiError::TransactionNotFound(Some(format!(
+            "Transaction {} is not in mempool",
+            hash
+        ))))
+    }
+}
+
+/// Looks up a transaction by hash, only returning it if it's still pending
+async fn get_pending_transaction(
+    rest_client: &aptos_rest_client::Client,
+    hash: HashValue,
+) -> ApiResult<Option<PendingTransaction>> {
+    match rest_client.get_transaction_by_hash(hash).await {
+        Ok(response) => match response.into_inner() {
+            aptos_rest_client::Transaction::PendingTransaction(txn) => Ok(Some(txn)),
+            _ => Ok(None),
+        },
+        Err(err) => match ApiError::from(err) {
+            // Transactions that expired or were dropped from mempool can't be found
+            ApiError::TransactionNotFound(_) => Ok(None),
+            err => Err(err),
+        },
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rosetta Search API
+//!
+//! Transactions can be searched by hash, or by the account that sent them.  Searching by account
+//! pages through the account's transactions by sequence number.
+//!
+//! See: [Search API Spec](https://www.rosetta-api.org/docs/SearchApi.html)
+
+use crate::{
+    common::{check_network, get_account, handle_request, with_context},
+    error::{ApiError, ApiResult},
+    types::{
+        BlockIdentifier, BlockTransaction, OperatorType, SearchTransactionsRequest,
+        SearchTransactionsResponse, Transaction,
+    },
+    RosettaContext,
+};
+use aptos_logger::{debug, trace};
+use warp::Filter;
+
+/// Maximum number of transactions returned in a single search
+const MAX_SEARCH_RESULTS: u64 = 100;
+
+pub fn search_transactions_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("search" / "transactions")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(search_transactions))
+}
+
+/// Searches for committed transactions by hash or by sender
+///
+/// All given conditions must match.  Pending transactions are never returned, as they aren't in
+/// a block yet.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/SearchApi.html#searchtransactions)
+async fn search_transactions(
+    request: SearchTransactionsRequest,
+    server_context: RosettaContext,
+) -> ApiResult<SearchTransactionsResponse> {
+    debug!("/search/transactions");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/search/transactions",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    if request.operator == Some(OperatorType::Or) {
+        return Err(ApiError::InvalidInput(Some(
+            "Only the `and` operator is supported".to_string(),
+        )));
+    }
+
+    let chain_id = server_context.chain_id;
+    let rest_client = server_context.rest_client()?;
+    let maybe_sender = request
+        .account_identifier
+        .as_ref()
+        .map(|account| account.account_address())
+        .transpose()?;
+
+    let (txns, maybe_total_count, mut next_offset) =
+        if let Some(ref transaction_identifier) = request.transaction_identifier {
+            let hash = transaction_identifier.hash_value()?;
+            let txns = match rest_client.get_transaction_by_hash(hash).await {
+                Ok(response) => vec![response.into_inner()],
+                Err(err) => match ApiError::from(err) {
+                    ApiError::TransactionNotFound(_) => vec![],
+                    err => return Err(err),
+                },
+            };
+            (txns, None, None)
+        } else if let Some(sender) = maybe_sender {
+            let offset = request.offset.unwrap_or(0);
+            let limit = std::cmp::min(
+                request.limit.unwrap_or(MAX_SEARCH_RESULTS),
+                MAX_SEARCH_RESULTS,
+            );
+            let total_count = get_account(&rest_client, sender)
+                .await?
+                .into_inner()
+                .sequence_number;
+            let txns = rest_client
+                .get_account_transactions(sender, Some(offset), Some(limit))
+                .await?
+                .into_inner();
+            let next_offset = if offset.saturating_add(limit) < total_count {
+                Some(offset + limit)
+            } else {
+                None
+            };
+            (txns, Some(total_count), next_offset)
+        } else {
+            return Err(ApiError::InvalidInput(Some(
+                "Search requires a transaction_identifier or an account_identifier".to_string(),
+            )));
+        };
+
+    let mut transactions = Vec::new();
+    for txn in txns {
+        // Pending transactions aren't in a block yet
+        let version = if let Some(version) = txn.version() {
+            version
+        } else {
+            continue;
+        };
+
+        if let Some(sender) = maybe_sender {
+            match txn {
+                aptos_rest_client::Transaction::UserTransaction(ref user_txn)
+                    if *user_txn.request.sender.inner() == sender => {}
+                _ => continue,
+            }
+        }
+        if let Some(success) = request.success {
+            if txn.success() != success {
+                continue;
+            }
+        }
+
+        let block = rest_client
+            .get_block_by_version(version, false)
+            .await?
+            .into_inner();
+        let block_identifier = BlockIdentifier::from_block(&block, chain_id);
+        if let Some(max_block) = request.max_block {
+            // Transactions are in order, so no later transaction can match either
+            if block_identifier.index > max_block {
+                next_offset = None;
+                break;
+            }
+        }
+
+        transactions.push(BlockTransaction {
+            block_identifier,
+            transaction: Transaction::from_transaction(&server_context.currencies, txn).await?,
+        });
+    }
+
+    Ok(SearchTransactionsResponse {
+        total_count: maybe_total_count.unwrap_or(transactions.len() as u64),
+        transactions,
+        next_offset,
+    })
+}
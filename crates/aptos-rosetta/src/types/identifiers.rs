@@ -15,6 +15,7 @@ use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use serde::{Deserialize, Serialize};
 use std::{
     convert::{TryFrom, TryInto},
+    fmt::{Display, Formatter},
     str::FromStr,
 };
 
@@ -25,6 +26,9 @@ use std::{
 pub struct AccountIdentifier {
     /// Hex encoded AccountAddress beginning with 0x
     pub address: String,
+    /// Sub account of the account, used to track the stake of the account's stake pool
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_account: Option<SubAccountIdentifier>,
 }
 
 impl AccountIdentifier {
@@ -32,6 +36,24 @@ impl AccountIdentifier {
     pub fn account_address(&self) -> ApiResult<AccountAddress> {
         self.try_into()
     }
+
+    /// Account identifier of the stake of `address` in the given state
+    pub fn stake_account(address: AccountAddress, stake_sub_account: StakeSubAccount) -> Self {
+        AccountIdentifier {
+            address: to_hex_lower(&address),
+            sub_account: Some(SubAccountIdentifier {
+                address: stake_sub_account.to_string(),
+            }),
+        }
+    }
+
+    /// The stake sub account this identifies, if any
+    pub fn stake_sub_account(&self) -> ApiResult<Option<StakeSubAccount>> {
+        self.sub_account
+            .as_ref()
+            .map(|sub_account| StakeSubAccount::from_str(&sub_account.address))
+            .transpose()
+    }
 }
 
 impl TryFrom<&AccountIdentifier> for AccountAddress {
@@ -52,10 +74,70 @@ impl From<AccountAddress> for AccountIdentifier {
     fn from(address: AccountAddress) -> Self {
         AccountIdentifier {
             address: to_hex_lower(&address),
+            sub_account: None,
         }
     }
 }
 
+/// Identifier of a sub account of an [`AccountIdentifier`]
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/SubAccountIdentifier.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SubAccountIdentifier {
+    /// Name of the sub account e.g. `active_stake`
+    pub address: String,
+}
+
+/// The stake sub accounts of an account, which track the stake in its stake pool by state
+///
+/// The balance of the `stake` sub account is the total of all states.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StakeSubAccount {
+    Total,
+    Active,
+    PendingActive,
+    Inactive,
+    PendingInactive,
+}
+
+impl StakeSubAccount {
+    const TOTAL: &'static str = "stake";
+    const ACTIVE: &'static str = "active_stake";
+    const PENDING_ACTIVE: &'static str = "pending_active_stake";
+    const INACTIVE: &'static str = "inactive_stake";
+    const PENDING_INACTIVE: &'static str = "pending_inactive_stake";
+}
+
+impl FromStr for StakeSubAccount {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().trim() {
+            Self::TOTAL => Ok(StakeSubAccount::Total),
+            Self::ACTIVE => Ok(StakeSubAccount::Active),
+            Self::PENDING_ACTIVE => Ok(StakeSubAccount::PendingActive),
+            Self::INACTIVE => Ok(StakeSubAccount::Inactive),
+            Self::PENDING_INACTIVE => Ok(StakeSubAccount::PendingInactive),
+            _ => Err(ApiError::InvalidInput(Some(format!(
+                "Invalid sub account: {}",
+                s
+            )))),
+        }
+    }
+}
+
+impl Display for StakeSubAccount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            StakeSubAccount::Total => Self::TOTAL,
+            StakeSubAccount::Active => Self::ACTIVE,
+            StakeSubAccount::PendingActive => Self::PENDING_ACTIVE,
+            StakeSubAccount::Inactive => Self::INACTIVE,
+            StakeSubAccount::PendingInactive => Self::PENDING_INACTIVE,
+        })
+    }
+}
+
 /// Identifier for a "block".  In aptos, we use a transaction model, so the index
 /// represents multiple transactions in a "block" grouping of transactions
 ///
@@ -172,6 +254,20 @@ pub struct TransactionIdentifier {
     pub hash: String,
 }
 
+impl TransactionIdentifier {
+    /// Parses the hash of the transaction
+    pub fn hash_value(&self) -> ApiResult<aptos_crypto::HashValue> {
+        HashValue::from_str(&self.hash)
+            .map(|hash| hash.into())
+            .map_err(|err| {
+                ApiError::InvalidInput(Some(format!(
+                    "Invalid transaction hash {}: {}",
+                    self.hash, err
+                )))
+            })
+    }
+}
+
 impl From<&TransactionInfo> for TransactionIdentifier {
     fn from(txn: &TransactionInfo) -> Self {
         TransactionIdentifier {
@@ -41,6 +41,16 @@ pub struct OperationStatus {
     pub successful: bool,
 }
 
+/// How conditions of a search are combined
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/Operator.html)
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperatorType {
+    Or,
+    And,
+}
+
 /// Represents a Peer, used for discovery
 ///
 /// [API Spec](https://www.rosetta-api.org/docs/models/Peer.html)
@@ -83,6 +93,12 @@ pub enum OperationType {
     Withdraw,
     Deposit,
     SetOperator,
+    SetDelegatedVoter,
+    AddStake,
+    UnlockStake,
+    WithdrawStake,
+    ResetLockup,
+    StakingReward,
     // Fee must always be last for ordering
     Fee,
 }
@@ -93,6 +109,12 @@ impl OperationType {
     const WITHDRAW: &'static str = "withdraw";
     const FEE: &'static str = "fee";
     const SET_OPERATOR: &'static str = "set_operator";
+    const SET_DELEGATED_VOTER: &'static str = "set_delegated_voter";
+    const ADD_STAKE: &'static str = "add_stake";
+    const UNLOCK_STAKE: &'static str = "unlock_stake";
+    const WITHDRAW_STAKE: &'static str = "withdraw_stake";
+    const RESET_LOCKUP: &'static str = "reset_lockup";
+    const STAKING_REWARD: &'static str = "staking_reward";
 
     pub fn all() -> Vec<OperationType> {
         vec![
@@ -101,6 +123,12 @@ impl OperationType {
             OperationType::Deposit,
             OperationType::Fee,
             OperationType::SetOperator,
+            OperationType::SetDelegatedVoter,
+            OperationType::AddStake,
+            OperationType::UnlockStake,
+            OperationType::WithdrawStake,
+            OperationType::ResetLockup,
+            OperationType::StakingReward,
         ]
     }
 }
@@ -115,6 +143,12 @@ impl FromStr for OperationType {
             Self::WITHDRAW => Ok(OperationType::Withdraw),
             Self::FEE => Ok(OperationType::Fee),
             Self::SET_OPERATOR => Ok(OperationType::SetOperator),
+            Self::SET_DELEGATED_VOTER => Ok(OperationType::SetDelegatedVoter),
+            Self::ADD_STAKE => Ok(OperationType::AddStake),
+            Self::UNLOCK_STAKE => Ok(OperationType::UnlockStake),
+            Self::WITHDRAW_STAKE => Ok(OperationType::WithdrawStake),
+            Self::RESET_LOCKUP => Ok(OperationType::ResetLockup),
+            Self::STAKING_REWARD => Ok(OperationType::StakingReward),
             _ => Err(ApiError::DeserializationFailed(Some(format!(
                 "Invalid OperationType: {}",
                 s
@@ -130,6 +164,12 @@ impl Display for OperationType {
             OperationType::Deposit => Self::DEPOSIT,
             OperationType::Withdraw => Self::WITHDRAW,
             OperationType::SetOperator => Self::SET_OPERATOR,
+            OperationType::SetDelegatedVoter => Self::SET_DELEGATED_VOTER,
+            OperationType::AddStake => Self::ADD_STAKE,
+            OperationType::UnlockStake => Self::UNLOCK_STAKE,
+            OperationType::WithdrawStake => Self::WITHDRAW_STAKE,
+            OperationType::ResetLockup => Self::RESET_LOCKUP,
+            OperationType::StakingReward => Self::STAKING_REWARD,
             OperationType::Fee => Self::FEE,
         })
     }
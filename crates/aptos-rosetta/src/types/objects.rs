@@ -5,21 +5,24 @@
 //!
 //! [Spec](https://www.rosetta-api.org/docs/api_objects.html)
 
-use crate::common::native_coin_tag;
 use crate::types::{
-    account_module_identifier, aptos_coin_module_identifier, aptos_coin_resource_identifier,
+    account_module_identifier, add_stake_events_field_identifier, add_stake_function_identifier,
     coin_module_identifier, create_account_function_identifier,
+    distribute_rewards_events_field_identifier, increase_lockup_events_field_identifier,
+    increase_lockup_function_identifier, set_delegated_voter_function_identifier,
     set_operator_events_field_identifier, set_operator_function_identifier,
     stake_module_identifier, stake_pool_resource_identifier, transfer_function_identifier,
+    unlock_function_identifier, unlock_stake_events_field_identifier, withdraw_function_identifier,
+    withdraw_stake_events_field_identifier,
 };
 use crate::{
-    common::{is_native_coin, native_coin},
+    common::{check_supported_currency, find_coin_currency, is_native_coin, native_coin},
     error::ApiResult,
     types::{
         account_resource_identifier, coin_store_resource_identifier,
         deposit_events_field_identifier, sequence_number_field_identifier,
         withdraw_events_field_identifier, AccountIdentifier, BlockIdentifier, Error,
-        OperationIdentifier, OperationStatus, OperationStatusType, OperationType,
+        OperationIdentifier, OperationStatus, OperationStatusType, OperationType, StakeSubAccount,
         TransactionIdentifier,
     },
     ApiError,
@@ -27,8 +30,8 @@ use crate::{
 use anyhow::anyhow;
 use aptos_crypto::{ed25519::Ed25519PublicKey, ValidCryptoMaterialStringExt};
 use aptos_rest_client::aptos_api_types::{
-    Address, Event, MoveStructTag, MoveType, TransactionPayload, UserTransactionRequest,
-    WriteResource,
+    Address, EntryFunctionPayload, Event, MoveStructTag, MoveType, TransactionPayload,
+    UserTransactionRequest, WriteResource,
 };
 use aptos_rest_client::{
     aptos::Balance,
@@ -36,10 +39,11 @@ use aptos_rest_client::{
 };
 use aptos_types::{account_address::AccountAddress, event::EventKey};
 use cached_packages::aptos_stdlib;
-use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize};
+use move_deps::move_core_types::{language_storage::TypeTag, parser::parse_type_tag};
+use serde::{de::DeserializeOwned, de::Error as SerdeError, Deserialize, Deserializer, Serialize};
 use std::cmp::Ordering;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     convert::{TryFrom, TryInto},
     fmt::{Display, Formatter},
     hash::Hash,
@@ -121,9 +125,35 @@ pub struct Block {
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct BlockTransaction {
     /// Block associated with transaction
-    block_identifier: BlockIdentifier,
+    pub block_identifier: BlockIdentifier,
     /// Transaction associated with block
-    transaction: Transaction,
+    pub transaction: Transaction,
+}
+
+/// An event of a block being added to or removed from the canonical chain
+///
+/// Aptos has instant finality, so blocks are only ever added, and the sequence of an event is
+/// always the height of its block
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/BlockEvent.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockEvent {
+    /// Sequence number of the event
+    pub sequence: u64,
+    /// Block added or removed
+    pub block_identifier: BlockIdentifier,
+    #[serde(rename = "type")]
+    pub event_type: BlockEventType,
+}
+
+/// Type of a [`BlockEvent`]
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/BlockEventType.html)
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockEventType {
+    BlockAdded,
+    BlockRemoved,
 }
 
 /// Currency represented as atomic units including decimals
@@ -139,6 +169,19 @@ pub struct Currency {
     pub metadata: Option<CurrencyMetadata>,
 }
 
+impl Currency {
+    /// The Move coin type of the currency, e.g. `0x1::aptos_coin::AptosCoin`
+    pub fn coin_type(&self) -> ApiResult<TypeTag> {
+        let move_type = self
+            .metadata
+            .as_ref()
+            .map(|metadata| metadata.move_type.as_str())
+            .ok_or_else(|| ApiError::UnsupportedCurrency(Some(self.symbol.clone())))?;
+        parse_type_tag(move_type)
+            .map_err(|_| ApiError::UnsupportedCurrency(Some(self.symbol.clone())))
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct CurrencyMetadata {
     pub move_type: String,
@@ -200,6 +243,34 @@ impl Operation {
         }
     }
 
+    /// A change in the native coin balance of one of the stake sub-accounts of `owner`
+    ///
+    /// Only the total `stake` sub-account reconciles with these operations, as stake also moves
+    /// between the other sub-accounts at epoch boundaries without any events
+    fn stake_balance_change(
+        operation_type: OperationType,
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        owner: AccountAddress,
+        stake_sub_account: StakeSubAccount,
+        value: String,
+    ) -> Operation {
+        Operation {
+            account: Some(AccountIdentifier::stake_account(owner, stake_sub_account)),
+            ..Operation::new(
+                operation_type,
+                operation_index,
+                status,
+                owner,
+                Some(Amount {
+                    value,
+                    currency: native_coin(),
+                }),
+                None,
+            )
+        }
+    }
+
     pub fn create_account(
         operation_index: u64,
         status: Option<OperationStatusType>,
@@ -290,6 +361,117 @@ impl Operation {
             Some(OperationMetadata::set_operator(operator)),
         )
     }
+
+    pub fn set_delegated_voter(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        owner: AccountAddress,
+        voter: AccountAddress,
+    ) -> Operation {
+        Operation::new(
+            OperationType::SetDelegatedVoter,
+            operation_index,
+            status,
+            owner,
+            None,
+            Some(OperationMetadata::set_delegated_voter(voter)),
+        )
+    }
+
+    /// Stake added to the owner's stake pool, the coins leaving the owner's account are
+    /// already accounted for by a withdraw operation
+    pub fn add_stake(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        owner: AccountAddress,
+        amount: u64,
+    ) -> Operation {
+        Operation::stake_balance_change(
+            OperationType::AddStake,
+            operation_index,
+            status,
+            owner,
+            StakeSubAccount::Total,
+            amount.to_string(),
+        )
+    }
+
+    /// Unlocking moves stake from active to pending inactive, so it's a pair of operations
+    pub fn unlock_stake(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        owner: AccountAddress,
+        amount: u64,
+    ) -> Vec<Operation> {
+        vec![
+            Operation::stake_balance_change(
+                OperationType::UnlockStake,
+                operation_index,
+                status,
+                owner,
+                StakeSubAccount::Active,
+                format!("-{}", amount),
+            ),
+            Operation::stake_balance_change(
+                OperationType::UnlockStake,
+                operation_index + 1,
+                status,
+                owner,
+                StakeSubAccount::PendingInactive,
+                amount.to_string(),
+            ),
+        ]
+    }
+
+    /// Stake withdrawn from the owner's stake pool, the coins arriving in the owner's account
+    /// are already accounted for by a deposit operation
+    pub fn withdraw_stake(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        owner: AccountAddress,
+        amount: u64,
+    ) -> Operation {
+        Operation::stake_balance_change(
+            OperationType::WithdrawStake,
+            operation_index,
+            status,
+            owner,
+            StakeSubAccount::Total,
+            format!("-{}", amount),
+        )
+    }
+
+    /// Rewards distributed to the stake pool at the end of an epoch
+    pub fn staking_reward(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        owner: AccountAddress,
+        amount: u64,
+    ) -> Operation {
+        Operation::stake_balance_change(
+            OperationType::StakingReward,
+            operation_index,
+            status,
+            owner,
+            StakeSubAccount::Total,
+            amount.to_string(),
+        )
+    }
+
+    pub fn reset_lockup(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        owner: AccountAddress,
+    ) -> Operation {
+        Operation::new(
+            OperationType::ResetLockup,
+            operation_index,
+            status,
+            owner,
+            None,
+            None,
+        )
+    }
 }
 
 impl std::cmp::PartialOrd for Operation {
@@ -322,6 +504,8 @@ pub struct OperationMetadata {
     pub sender: Option<AccountIdentifier>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub operator: Option<AccountIdentifier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voter: Option<AccountIdentifier>,
 }
 
 impl OperationMetadata {
@@ -338,6 +522,13 @@ impl OperationMetadata {
             ..Default::default()
         }
     }
+
+    pub fn set_delegated_voter(voter: AccountAddress) -> Self {
+        OperationMetadata {
+            voter: Some(voter.into()),
+            ..Default::default()
+        }
+    }
 }
 
 /// Public key used for the rosetta implementation.  All private keys will never be handled
@@ -430,7 +621,9 @@ pub struct Transaction {
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct TransactionMetadata {
     pub transaction_type: TransactionType,
-    pub version: U64,
+    /// Version of the transaction, which isn't known until it's committed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<U64>,
     pub failed: bool,
     pub vm_status: String,
 }
@@ -456,7 +649,10 @@ impl Display for TransactionType {
 }
 
 impl Transaction {
-    pub async fn from_transaction(txn: aptos_rest_client::Transaction) -> ApiResult<Transaction> {
+    pub async fn from_transaction(
+        currencies: &HashSet<Currency>,
+        txn: aptos_rest_client::Transaction,
+    ) -> ApiResult<Transaction> {
         use aptos_rest_client::Transaction::*;
         let (txn_type, maybe_user_transaction_request, txn_info, events) = match txn {
             // Pending transactions aren't supported by Rosetta (for now)
@@ -484,6 +680,7 @@ impl Transaction {
             // Parse all operations from the writeset changes in a success
             for change in &txn_info.changes {
                 let mut ops = parse_operations_from_write_set(
+                    currencies,
                     change,
                     &events,
                     &maybe_user_transaction_request,
@@ -492,11 +689,29 @@ impl Transaction {
                 operation_index += ops.len() as u64;
                 operations.append(&mut ops);
             }
+
+            // Setting the delegated voter doesn't emit an event, so it has to come from the payload
+            if let Some(ref request) = maybe_user_transaction_request {
+                if let TransactionPayload::EntryFunctionPayload(ref inner) = request.payload {
+                    if set_delegated_voter_function_identifier() == inner.function.name.0 {
+                        let mut ops = parse_stake_operations_from_txn_payload(
+                            operation_index,
+                            Some(OperationStatusType::Success),
+                            *request.sender.inner(),
+                            inner,
+                        );
+                        operation_index += ops.len() as u64;
+                        operations.append(&mut ops);
+                    }
+                }
+            }
         } else {
             // Parse all failed operations from the payload
             if let Some(ref request) = maybe_user_transaction_request {
                 let mut ops = parse_operations_from_txn_payload(
+                    currencies,
                     operation_index,
+                    Some(OperationStatusType::Failure),
                     *request.sender.inner(),
                     &request.payload,
                 );
@@ -527,20 +742,54 @@ impl Transaction {
             operations,
             metadata: TransactionMetadata {
                 transaction_type: txn_type,
-                version: txn_info.version,
+                version: Some(txn_info.version),
                 failed: !txn_info.success,
                 vm_status: txn_info.vm_status,
             },
         })
     }
+
+    /// Builds a transaction that is still in mempool
+    ///
+    /// Operations are parsed from the payload and have no status, as the transaction hasn't
+    /// been executed yet
+    pub fn from_pending_transaction(
+        currencies: &HashSet<Currency>,
+        txn: aptos_rest_client::aptos_api_types::PendingTransaction,
+    ) -> Transaction {
+        let request = txn.request;
+        let operations = parse_operations_from_txn_payload(
+            currencies,
+            0,
+            None,
+            *request.sender.inner(),
+            &request.payload,
+        );
+
+        Transaction {
+            transaction_identifier: txn.hash.into(),
+            operations,
+            metadata: TransactionMetadata {
+                transaction_type: TransactionType::User,
+                version: None,
+                failed: false,
+                vm_status: PENDING_VM_STATUS.to_string(),
+            },
+        }
+    }
 }
 
+/// VM status of transactions that haven't been executed yet
+const PENDING_VM_STATUS: &str = "Pending";
+
 /// Parses operations from the transaction payload
 ///
-/// This case only occurs if the transaction failed, and that's because it's less accurate
-/// than just following the state changes
+/// This case only occurs if the transaction failed or is still pending, and that's because it's
+/// less accurate than just following the state changes
 fn parse_operations_from_txn_payload(
+    currencies: &HashSet<Currency>,
     operation_index: u64,
+    status: Option<OperationStatusType>,
     sender: AccountAddress,
     payload: &TransactionPayload,
 ) -> Vec<Operation> {
@@ -550,40 +799,32 @@ fn parse_operations_from_txn_payload(
             && coin_module_identifier() == inner.function.module.name.0
             && transfer_function_identifier() == inner.function.name.0
         {
-            if let Some(MoveType::Struct(MoveStructTag {
-                address,
-                module,
-                name,
-                ..
-            })) = inner.type_arguments.first()
+            if let Some(currency) = inner
+                .type_arguments
+                .first()
+                .and_then(|coin_type| find_move_type_currency(currencies, coin_type))
             {
-                if *address.inner() == AccountAddress::ONE
-                    && module.0 == aptos_coin_module_identifier()
-                    && name.0 == aptos_coin_resource_identifier()
-                {
-                    let receiver = serde_json::from_value::<Address>(
-                        inner.arguments.first().cloned().unwrap(),
-                    )
-                    .unwrap();
-                    let amount =
-                        serde_json::from_value::<U64>(inner.arguments.get(1).cloned().unwrap())
-                            .unwrap()
-                            .0;
-                    operations.push(Operation::withdraw(
-                        operation_index,
-                        Some(OperationStatusType::Failure),
-                        sender,
-                        native_coin(),
-                        amount,
-                    ));
-                    operations.push(Operation::deposit(
-                        operation_index + 1,
-                        Some(OperationStatusType::Failure),
-                        receiver.into(),
-                        native_coin(),
-                        amount,
-                    ));
-                }
+                let receiver =
+                    serde_json::from_value::<Address>(inner.arguments.first().cloned().unwrap())
+                        .unwrap();
+                let amount =
+                    serde_json::from_value::<U64>(inner.arguments.get(1).cloned().unwrap())
+                        .unwrap()
+                        .0;
+                operations.push(Operation::withdraw(
+                    operation_index,
+                    status,
+                    sender,
+                    currency.clone(),
+                    amount,
+                ));
+                operations.push(Operation::deposit(
+                    operation_index + 1,
+                    status,
+                    receiver.into(),
+                    currency,
+                    amount,
+                ));
             }
         } else if AccountAddress::ONE == *inner.function.module.address.inner()
             && account_module_identifier() == inner.function.module.name.0
@@ -594,33 +835,84 @@ fn parse_operations_from_txn_payload(
                     .unwrap();
             operations.push(Operation::create_account(
                 operation_index,
-                Some(OperationStatusType::Failure),
+                status,
                 address.into(),
                 sender,
             ));
-        } else if AccountAddress::ONE == *inner.function.module.address.inner()
-            && stake_module_identifier() == inner.function.module.name.0
-            && set_operator_function_identifier() == inner.function.name.0
-        {
-            let operator =
-                serde_json::from_value::<Address>(inner.arguments.first().cloned().unwrap())
-                    .unwrap();
-            operations.push(Operation::set_operator(
+        } else {
+            operations.append(&mut parse_stake_operations_from_txn_payload(
                 operation_index,
-                Some(OperationStatusType::Failure),
-                operator.into(),
+                status,
                 sender,
+                inner,
             ));
         }
     }
     operations
 }
 
+/// Parses the staking operations from a call to an entry function of the `stake` module
+fn parse_stake_operations_from_txn_payload(
+    operation_index: u64,
+    status: Option<OperationStatusType>,
+    owner: AccountAddress,
+    payload: &EntryFunctionPayload,
+) -> Vec<Operation> {
+    if AccountAddress::ONE != *payload.function.module.address.inner()
+        || stake_module_identifier() != payload.function.module.name.0
+    {
+        return vec![];
+    }
+
+    let function_name = &payload.function.name.0;
+    let address_arg = || {
+        serde_json::from_value::<Address>(payload.arguments.first().cloned()?)
+            .ok()
+            .map(|address| *address.inner())
+    };
+    let amount_arg = || {
+        serde_json::from_value::<U64>(payload.arguments.first().cloned()?)
+            .ok()
+            .map(|amount| amount.0)
+    };
+
+    if set_operator_function_identifier() == *function_name {
+        address_arg()
+            .map(|operator| Operation::set_operator(operation_index, status, owner, operator))
+            .into_iter()
+            .collect()
+    } else if set_delegated_voter_function_identifier() == *function_name {
+        address_arg()
+            .map(|voter| Operation::set_delegated_voter(operation_index, status, owner, voter))
+            .into_iter()
+            .collect()
+    } else if add_stake_function_identifier() == *function_name {
+        amount_arg()
+            .map(|amount| Operation::add_stake(operation_index, status, owner, amount))
+            .into_iter()
+            .collect()
+    } else if unlock_function_identifier() == *function_name {
+        amount_arg()
+            .map(|amount| Operation::unlock_stake(operation_index, status, owner, amount))
+            .unwrap_or_default()
+    } else if withdraw_function_identifier() == *function_name {
+        amount_arg()
+            .map(|amount| Operation::withdraw_stake(operation_index, status, owner, amount))
+            .into_iter()
+            .collect()
+    } else if increase_lockup_function_identifier() == *function_name {
+        vec![Operation::reset_lockup(operation_index, status, owner)]
+    } else {
+        vec![]
+    }
+}
+
 /// Parses operations from the write set
 ///
 /// This can only be done during a successful transaction because there are actual state changes.
 /// It is more accurate because untracked scripts are included in balance operations
 fn parse_operations_from_write_set(
+    currencies: &HashSet<Currency>,
     change: &WriteSetChange,
     events: &[Event],
     maybe_request: &Option<UserTransactionRequest>,
@@ -636,13 +928,6 @@ fn parse_operations_from_write_set(
             account_resource_identifier().into(),
             vec![],
         );
-        let coin_store_tag = MoveStructTag::new(
-            AccountAddress::ONE.into(),
-            coin_module_identifier().into(),
-            coin_store_resource_identifier().into(),
-            vec![native_coin_tag().into()],
-        );
-
         let stake_pool_tag = MoveStructTag::new(
             AccountAddress::ONE.into(),
             stake_module_identifier().into(),
@@ -670,28 +955,85 @@ fn parse_operations_from_write_set(
                 }
             }
         } else if data.typ == stake_pool_tag {
-            // Find set operator events
+            // Find staking events
+            let status = Some(OperationStatusType::Success);
             for (id, value) in data.data.0.iter() {
-                if id.0 == set_operator_events_field_identifier() {
-                    serde_json::from_value::<EventId>(value.clone()).unwrap();
-                    if let Ok(event) = serde_json::from_value::<EventId>(value.clone()) {
-                        let set_operator_event =
-                            EventKey::new(event.guid.id.creation_num.0, event.guid.id.addr);
-                        if let Some(operator) =
-                            get_set_operator_from_event(events, set_operator_event)
-                        {
-                            operations.push(Operation::set_operator(
+                let event_key = if let Ok(event) = serde_json::from_value::<EventId>(value.clone())
+                {
+                    EventKey::new(event.guid.id.creation_num.0, event.guid.id.addr)
+                } else {
+                    // Not an event handle
+                    continue;
+                };
+
+                let mut ops = if id.0 == set_operator_events_field_identifier() {
+                    get_set_operator_from_event(events, event_key)
+                        .map(|operator| {
+                            Operation::set_operator(operation_index, status, address, operator)
+                        })
+                        .into_iter()
+                        .collect()
+                } else if id.0 == add_stake_events_field_identifier() {
+                    get_event_data::<AddStakeEvent>(events, event_key)
+                        .map(|event| {
+                            Operation::add_stake(
                                 operation_index,
-                                Some(OperationStatusType::Success),
+                                status,
                                 address,
-                                operator,
-                            ));
-                            operation_index += 1;
-                        }
-                    }
-                }
+                                event.amount_added.0,
+                            )
+                        })
+                        .into_iter()
+                        .collect()
+                } else if id.0 == unlock_stake_events_field_identifier() {
+                    get_event_data::<UnlockStakeEvent>(events, event_key)
+                        .map(|event| {
+                            Operation::unlock_stake(
+                                operation_index,
+                                status,
+                                address,
+                                event.amount_unlocked.0,
+                            )
+                        })
+                        .unwrap_or_default()
+                } else if id.0 == withdraw_stake_events_field_identifier() {
+                    get_event_data::<WithdrawStakeEvent>(events, event_key)
+                        .map(|event| {
+                            Operation::withdraw_stake(
+                                operation_index,
+                                status,
+                                address,
+                                event.amount_withdrawn.0,
+                            )
+                        })
+                        .into_iter()
+                        .collect()
+                } else if id.0 == distribute_rewards_events_field_identifier() {
+                    get_event_data::<DistributeRewardsEvent>(events, event_key)
+                        .map(|event| {
+                            Operation::staking_reward(
+                                operation_index,
+                                status,
+                                address,
+                                event.rewards_amount.0,
+                            )
+                        })
+                        .into_iter()
+                        .collect()
+                } else if id.0 == increase_lockup_events_field_identifier() {
+                    // Only the presence of the event matters, the new lockup isn't an operation field
+                    get_event_data::<serde_json::Value>(events, event_key)
+                        .map(|_| Operation::reset_lockup(operation_index, status, address))
+                        .into_iter()
+                        .collect()
+                } else {
+                    vec![]
+                };
+
+                operation_index += ops.len() as u64;
+                operations.append(&mut ops);
             }
-        } else if data.typ == coin_store_tag {
+        } else if let Some(currency) = coin_store_currency(currencies, &data.typ) {
             // Account balance change
             for (id, value) in data.data.0.iter() {
                 if id.0 == withdraw_events_field_identifier() {
@@ -704,7 +1046,7 @@ fn parse_operations_from_write_set(
                                 operation_index,
                                 Some(OperationStatusType::Success),
                                 address,
-                                native_coin(),
+                                currency.clone(),
                                 amount,
                             ));
                             operation_index += 1;
@@ -720,7 +1062,7 @@ fn parse_operations_from_write_set(
                                 operation_index,
                                 Some(OperationStatusType::Success),
                                 address,
-                                native_coin(),
+                                currency.clone(),
                                 amount,
                             ));
                             operation_index += 1;
@@ -734,6 +1076,27 @@ fn parse_operations_from_write_set(
     operations
 }
 
+/// Finds the supported currency of a `0x1::coin::CoinStore<T>` resource
+fn coin_store_currency(currencies: &HashSet<Currency>, tag: &MoveStructTag) -> Option<Currency> {
+    if *tag.address.inner() == AccountAddress::ONE
+        && tag.module.0 == coin_module_identifier()
+        && tag.name.0 == coin_store_resource_identifier()
+    {
+        find_move_type_currency(currencies, tag.generic_type_params.first()?)
+    } else {
+        None
+    }
+}
+
+/// Finds the supported currency of a coin type
+fn find_move_type_currency(
+    currencies: &HashSet<Currency>,
+    coin_type: &MoveType,
+) -> Option<Currency> {
+    let coin_type = TypeTag::try_from(coin_type.clone()).ok()?;
+    find_coin_currency(currencies, &coin_type)
+}
+
 /// Pulls the balance change from a withdraw or deposit event
 fn get_amount_from_event(events: &[Event], event_key: EventKey) -> Option<u64> {
     if let Some(event) = events
@@ -763,6 +1126,14 @@ fn get_set_operator_from_event(events: &[Event], event_key: EventKey) -> Option<
     None
 }
 
+/// Pulls the data of the event with the given key out of the transaction's events
+fn get_event_data<T: DeserializeOwned>(events: &[Event], event_key: EventKey) -> Option<T> {
+    events
+        .iter()
+        .find(|event| EventKey::from(event.key) == event_key)
+        .and_then(|event| serde_json::from_value::<T>(event.data.clone()).ok())
+}
+
 /// An enum for processing which operation is in a transaction
 pub enum OperationDetails {
     CreateAccount,
@@ -780,11 +1151,19 @@ pub enum InternalOperation {
     CreateAccount(CreateAccount),
     Transfer(Transfer),
     SetOperator(SetOperator),
+    SetDelegatedVoter(SetDelegatedVoter),
+    AddStake(AddStake),
+    UnlockStake(UnlockStake),
+    WithdrawStake(WithdrawStake),
+    ResetLockup(ResetLockup),
 }
 
 impl InternalOperation {
     /// Pulls the [`InternalOperation`] from the set of [`Operation`]
-    pub fn extract(operations: &Vec<Operation>) -> ApiResult<InternalOperation> {
+    pub fn extract(
+        currencies: &HashSet<Currency>,
+        operations: &Vec<Operation>,
+    ) -> ApiResult<InternalOperation> {
         match operations.len() {
             1 => {
                 if let Some(operation) = operations.first() {
@@ -819,6 +1198,44 @@ impl InternalOperation {
                                 }));
                             }
                         }
+                        Ok(OperationType::SetDelegatedVoter) => {
+                            if let (
+                                Some(OperationMetadata {
+                                    voter: Some(voter), ..
+                                }),
+                                Some(account),
+                            ) = (&operation.metadata, &operation.account)
+                            {
+                                return Ok(Self::SetDelegatedVoter(SetDelegatedVoter {
+                                    owner: account.account_address()?,
+                                    voter: voter.account_address()?,
+                                }));
+                            }
+                        }
+                        Ok(OperationType::ResetLockup) => {
+                            if let Some(account) = &operation.account {
+                                return Ok(Self::ResetLockup(ResetLockup {
+                                    owner: account.account_address()?,
+                                }));
+                            }
+                        }
+                        Ok(
+                            operation_type @ (OperationType::AddStake
+                            | OperationType::WithdrawStake),
+                        ) => {
+                            if let (Some(amount), Some(account)) =
+                                (&operation.amount, &operation.account)
+                            {
+                                let owner = account.account_address()?;
+                                let amount = stake_amount(amount)?;
+                                return Ok(match operation_type {
+                                    OperationType::AddStake => {
+                                        Self::AddStake(AddStake { owner, amount })
+                                    }
+                                    _ => Self::WithdrawStake(WithdrawStake { owner, amount }),
+                                });
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -829,7 +1246,21 @@ impl InternalOperation {
                     operations
                 ))))
             }
-            2 => Ok(Self::Transfer(Transfer::extract_transfer(operations)?)),
+            2 => {
+                // Unlocking stake is a pair of operations moving stake between sub accounts
+                if operations.iter().all(|operation| {
+                    OperationType::from_str(&operation.operation_type)
+                        .map_or(false, |op| op == OperationType::UnlockStake)
+                }) {
+                    Ok(Self::UnlockStake(UnlockStake::extract_unlock_stake(
+                        operations,
+                    )?))
+                } else {
+                    Ok(Self::Transfer(Transfer::extract_transfer(
+                        currencies, operations,
+                    )?))
+                }
+            }
             _ => Err(ApiError::InvalidOperations(Some(format!(
                 "Unrecognized operation combination {:?}",
                 operations
@@ -843,6 +1274,11 @@ impl InternalOperation {
             Self::CreateAccount(inner) => inner.sender,
             Self::Transfer(inner) => inner.sender,
             Self::SetOperator(inner) => inner.owner,
+            Self::SetDelegatedVoter(inner) => inner.owner,
+            Self::AddStake(inner) => inner.owner,
+            Self::UnlockStake(inner) => inner.owner,
+            Self::WithdrawStake(inner) => inner.owner,
+            Self::ResetLockup(inner) => inner.owner,
         }
     }
 
@@ -855,16 +1291,41 @@ impl InternalOperation {
                 create_account.sender,
             ),
             InternalOperation::Transfer(transfer) => {
-                is_native_coin(&transfer.currency)?;
-                (
-                    aptos_stdlib::aptos_account_transfer(transfer.receiver, transfer.amount.0),
-                    transfer.sender,
-                )
+                // The native coin goes through `aptos_account` so the receiver is created if needed
+                let payload = if is_native_coin(&transfer.currency) {
+                    aptos_stdlib::aptos_account_transfer(transfer.receiver, transfer.amount.0)
+                } else {
+                    aptos_stdlib::coin_transfer(
+                        transfer.currency.coin_type()?,
+                        transfer.receiver,
+                        transfer.amount.0,
+                    )
+                };
+                (payload, transfer.sender)
             }
             InternalOperation::SetOperator(set_operator) => (
                 aptos_stdlib::stake_set_operator(set_operator.operator),
                 set_operator.owner,
             ),
+            InternalOperation::SetDelegatedVoter(set_delegated_voter) => (
+                aptos_stdlib::stake_set_delegated_voter(set_delegated_voter.voter),
+                set_delegated_voter.owner,
+            ),
+            InternalOperation::AddStake(add_stake) => (
+                aptos_stdlib::stake_add_stake(add_stake.amount.0),
+                add_stake.owner,
+            ),
+            InternalOperation::UnlockStake(unlock_stake) => (
+                aptos_stdlib::stake_unlock(unlock_stake.amount.0),
+                unlock_stake.owner,
+            ),
+            InternalOperation::WithdrawStake(withdraw_stake) => (
+                aptos_stdlib::stake_withdraw(withdraw_stake.amount.0),
+                withdraw_stake.owner,
+            ),
+            InternalOperation::ResetLockup(reset_lockup) => {
+                (aptos_stdlib::stake_increase_lockup(), reset_lockup.owner)
+            }
         })
     }
 }
@@ -886,7 +1347,10 @@ pub struct Transfer {
 }
 
 impl Transfer {
-    pub fn extract_transfer(operations: &Vec<Operation>) -> ApiResult<Transfer> {
+    pub fn extract_transfer(
+        currencies: &HashSet<Currency>,
+        operations: &Vec<Operation>,
+    ) -> ApiResult<Transfer> {
         // Only support 1:1 P2P transfer
         // This is composed of a Deposit and a Withdraw operation
         if operations.len() != 2 {
@@ -943,8 +1407,7 @@ impl Transfer {
                 }
 
                 // Check that the currency is supported
-                // TODO: in future use currency, since there's more than just 1
-                is_native_coin(&withdraw_amount.currency)?;
+                check_supported_currency(currencies, &withdraw_amount.currency)?;
 
                 let withdraw_value = i64::from_str(&withdraw_amount.value).map_err(|_| {
                     ApiError::InvalidTransferOperations(Some("Withdraw amount is invalid"))
@@ -983,6 +1446,89 @@ pub struct SetOperator {
     pub operator: AccountAddress,
 }
 
+/// Set delegated voter
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SetDelegatedVoter {
+    pub owner: AccountAddress,
+    pub voter: AccountAddress,
+}
+
+/// Add coins to the owner's stake pool
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AddStake {
+    pub owner: AccountAddress,
+    pub amount: U64,
+}
+
+/// Unlock stake in the owner's stake pool, so it can be withdrawn after the lockup expires
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct UnlockStake {
+    pub owner: AccountAddress,
+    pub amount: U64,
+}
+
+impl UnlockStake {
+    /// Extracts the unlock from the pair of operations moving stake out of the active sub
+    /// account and into the pending inactive sub account of the same owner
+    pub fn extract_unlock_stake(operations: &Vec<Operation>) -> ApiResult<UnlockStake> {
+        let mut active = None;
+        let mut pending_inactive = None;
+        for operation in operations {
+            if let (Some(account), Some(amount)) = (&operation.account, &operation.amount) {
+                match account.stake_sub_account()? {
+                    Some(StakeSubAccount::Active) if amount.value.starts_with('-') => {
+                        active = Some((account.account_address()?, stake_amount(amount)?))
+                    }
+                    Some(StakeSubAccount::PendingInactive) if !amount.value.starts_with('-') => {
+                        pending_inactive = Some((account.account_address()?, stake_amount(amount)?))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        match (active, pending_inactive) {
+            (Some((owner, amount)), Some(pending_inactive))
+                if (owner, amount) == pending_inactive =>
+            {
+                Ok(UnlockStake { owner, amount })
+            }
+            _ => Err(ApiError::InvalidOperations(Some(format!(
+                "Unlock stake must move the same amount from active to pending inactive stake of \
+                 the same owner {:?}",
+                operations
+            )))),
+        }
+    }
+}
+
+/// The absolute amount of native coin in a staking operation
+fn stake_amount(amount: &Amount) -> ApiResult<U64> {
+    if !is_native_coin(&amount.currency) {
+        return Err(ApiError::InvalidOperations(Some(format!(
+            "Staking operations only support the native coin {:?}",
+            amount.currency
+        ))));
+    }
+
+    u64::from_str(amount.value.trim_start_matches('-'))
+        .map(U64::from)
+        .map_err(|_| ApiError::InvalidOperations(Some(format!("Invalid amount {:?}", amount))))
+}
+
+/// Withdraw unlocked stake from the owner's stake pool back to the owner's account
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WithdrawStake {
+    pub owner: AccountAddress,
+    pub amount: U64,
+}
+
+/// Reset the lockup of the owner's stake pool to the full lockup duration
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ResetLockup {
+    pub owner: AccountAddress,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct CoinEvent {
     amount: U64,
@@ -993,6 +1539,26 @@ pub struct SetOperatorEvent {
     new_operator: Address,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+pub struct AddStakeEvent {
+    amount_added: U64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct UnlockStakeEvent {
+    amount_unlocked: U64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct WithdrawStakeEvent {
+    amount_withdrawn: U64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DistributeRewardsEvent {
+    rewards_amount: U64,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct EventId {
     guid: Id,
@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::types::{
-    AccountIdentifier, Allow, Amount, Block, BlockIdentifier, Currency, InternalOperation,
-    NetworkIdentifier, Operation, PartialBlockIdentifier, Peer, PublicKey, Signature,
-    SigningPayload, SyncStatus, Transaction, TransactionIdentifier, Version,
+    AccountIdentifier, Allow, Amount, Block, BlockEvent, BlockIdentifier, BlockTransaction,
+    Currency, InternalOperation, NetworkIdentifier, Operation, OperatorType,
+    PartialBlockIdentifier, Peer, PublicKey, Signature, SigningPayload, SyncStatus, Transaction,
+    TransactionIdentifier, Version,
 };
 use aptos_rest_client::aptos_api_types::U64;
 use aptos_types::chain_id::ChainId;
@@ -89,6 +90,28 @@ pub struct BlockResponse {
     pub block: Block,
 }
 
+/// Request for a single transaction in a block
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/BlockTransactionRequest.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockTransactionRequest {
+    /// Network identifier describing the blockchain and the chain id
+    pub network_identifier: NetworkIdentifier,
+    /// Block containing the transaction
+    pub block_identifier: BlockIdentifier,
+    /// Hash of the transaction in the block
+    pub transaction_identifier: TransactionIdentifier,
+}
+
+/// Response with the requested transaction
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/BlockTransactionResponse.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockTransactionResponse {
+    /// The transaction requested
+    pub transaction: Transaction,
+}
+
 /// Request to combine signatures and an unsigned transaction for submission as a
 /// [`aptos_types::transaction::SignedTransaction`]
 ///
@@ -340,6 +363,32 @@ pub struct ConstructionSubmitResponse {
     pub transaction_identifier: TransactionIdentifier,
 }
 
+/// Request for a range of block events
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/EventsBlocksRequest.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct EventsBlocksRequest {
+    /// Network identifier describing the blockchain and the chain id
+    pub network_identifier: NetworkIdentifier,
+    /// Sequence number of the first event to return, defaults to the oldest available block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// Maximum number of events to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+/// Response with block events in order of sequence number
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/EventsBlocksResponse.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct EventsBlocksResponse {
+    /// Sequence number of the latest block event
+    pub max_sequence: u64,
+    /// Block events in the requested range
+    pub events: Vec<BlockEvent>,
+}
+
 /// Request for all transactions in mempool
 ///
 /// [API Spec](https://www.rosetta-api.org/docs/models/MempoolRequest.html)
@@ -432,6 +481,53 @@ pub struct NetworkStatusResponse {
     pub peers: Vec<Peer>,
 }
 
+/// Request to search for transactions matching the given conditions
+///
+/// Either a `transaction_identifier` or an `account_identifier` is required.  Searching by account
+/// only returns transactions sent by the account.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/SearchTransactionsRequest.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SearchTransactionsRequest {
+    /// Network identifier describing the blockchain and the chain id
+    pub network_identifier: NetworkIdentifier,
+    /// How the conditions are combined, only `and` is supported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operator: Option<OperatorType>,
+    /// Only return transactions in or before this block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_block: Option<u64>,
+    /// Offset into the results, for an account this is the sequence number to start from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// Maximum number of transactions to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    /// Hash of the transaction to find
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_identifier: Option<TransactionIdentifier>,
+    /// Sender of the transactions to find
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_identifier: Option<AccountIdentifier>,
+    /// Whether the transactions to find succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+}
+
+/// Response with the transactions found and the offset to continue the search from
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/SearchTransactionsResponse.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SearchTransactionsResponse {
+    /// Transactions found, along with their blocks
+    pub transactions: Vec<BlockTransaction>,
+    /// Total number of transactions that could match the search
+    pub total_count: u64,
+    /// Offset to use for the next page of results, if there are more
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
+}
+
 /// Response with a transaction that was hashed or submitted
 ///
 /// [API Spec](https://www.rosetta-api.org/docs/models/TransactionIdentifierResponse.html)
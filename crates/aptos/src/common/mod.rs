@@ -2,5 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod init;
+pub mod rest_state_view;
 pub mod types;
 pub mod utils;
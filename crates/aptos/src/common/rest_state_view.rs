@@ -0,0 +1,84 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A [`StateView`] reading the state of a network through its REST API, which allows executing
+//! transactions locally, e.g., to profile their gas.
+
+use anyhow::{bail, Result};
+use aptos_rest_client::{error::RestError, Client, Response};
+use aptos_state_view::StateView;
+use aptos_types::{
+    access_path::Path, state_store::state_key::StateKey,
+    state_store::state_storage_usage::StateStorageUsage, transaction::Version,
+};
+use reqwest::StatusCode;
+use std::future::Future;
+use tokio::runtime::Handle;
+
+/// A state view backed by a REST client, pinned at a ledger version.
+///
+/// The state view is synchronous, so it must be used from a thread where blocking is allowed,
+/// e.g., in `tokio::task::spawn_blocking`.
+pub struct RestStateView {
+    client: Client,
+    version: Version,
+    runtime: Handle,
+}
+
+impl RestStateView {
+    /// Creates a state view reading the state at `version`. Must be called from within a
+    /// tokio runtime, which is used to drive the requests.
+    pub fn new(client: Client, version: Version) -> Self {
+        Self {
+            client,
+            version,
+            runtime: Handle::current(),
+        }
+    }
+
+    fn fetch<T>(
+        &self,
+        request: impl Future<Output = Result<Response<T>, RestError>>,
+    ) -> Result<Option<T>> {
+        match self.runtime.block_on(request) {
+            Ok(response) => Ok(Some(response.into_inner())),
+            Err(RestError::Api(err)) if err.status_code == StatusCode::NOT_FOUND => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+impl StateView for RestStateView {
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        match state_key {
+            StateKey::AccessPath(access_path) => match access_path.get_path() {
+                Path::Code(module_id) => Ok(self
+                    .fetch(self.client.get_account_module_bcs_at_version(
+                        access_path.address,
+                        module_id.name().as_str(),
+                        self.version,
+                    ))?
+                    .map(|bytes| bytes.to_vec())),
+                Path::Resource(struct_tag) => {
+                    self.fetch(self.client.get_account_resource_at_version_bytes(
+                        access_path.address,
+                        &struct_tag.to_string(),
+                        self.version,
+                    ))
+                }
+            },
+            StateKey::TableItem { handle, key } => {
+                self.fetch(self.client.get_raw_table_item(handle.0, key, self.version))
+            }
+            StateKey::Raw(_) => bail!("Raw state keys can't be read through the REST API"),
+        }
+    }
+
+    fn is_genesis(&self) -> bool {
+        false
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        Ok(StateStorageUsage::new_untracked())
+    }
+}
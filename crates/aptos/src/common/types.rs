@@ -5,6 +5,7 @@ use crate::common::utils::prompt_yes_with_override;
 use crate::{
     common::{
         init::{DEFAULT_FAUCET_URL, DEFAULT_REST_URL},
+        rest_state_view::RestStateView,
         utils::{
             chain_id, check_if_file_exists, create_dir_if_not_exist, dir_default_to_current,
             get_sequence_number, read_from_file, start_logger, to_common_result,
@@ -26,8 +27,10 @@ use aptos_rest_client::error::RestError;
 use aptos_rest_client::{Client, Transaction};
 use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
 use aptos_types::transaction::{
-    authenticator::AuthenticationKey, SignedTransaction, TransactionPayload,
+    authenticator::AuthenticationKey, ExecutionStatus, SignedTransaction, TransactionPayload,
+    TransactionStatus,
 };
+use aptos_vm::AptosVM;
 use async_trait::async_trait;
 use clap::{ArgEnum, Parser};
 use hex::FromHexError;
@@ -77,6 +80,8 @@ pub enum CliError {
     MoveTestError,
     #[error("Move Prover failed: {0}")]
     MoveProverError(String),
+    #[error("Simulation failed: {0}")]
+    SimulationError(String),
     #[error("Unable to parse '{0}': error: {1}")]
     UnableToParse(&'static str, String),
     #[error("Unable to read file '{0}', error: {1}")]
@@ -98,6 +103,7 @@ impl CliError {
             CliError::MoveCompilationError(_) => "MoveCompilationError",
             CliError::MoveTestError => "MoveTestError",
             CliError::MoveProverError(_) => "MoveProverError",
+            CliError::SimulationError(_) => "SimulationError",
             CliError::UnableToParse(_, _) => "UnableToParse",
             CliError::UnableToReadFile(_, _) => "UnableToReadFile",
             CliError::UnexpectedError(_) => "UnexpectedError",
@@ -918,6 +924,12 @@ pub struct TransactionSummary {
     pub version: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vm_status: Option<String>,
+    /// Storage fee deposited by a simulated transaction, in Octas
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_fee: Option<u64>,
+    /// Storage fee refunded to a simulated transaction, in Octas
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_fee_refund: Option<u64>,
 }
 
 impl From<Transaction> for TransactionSummary {
@@ -939,6 +951,8 @@ impl From<&Transaction> for TransactionSummary {
                 version: None,
                 vm_status: None,
                 timestamp_us: None,
+                storage_fee: None,
+                storage_fee_refund: None,
             },
             Transaction::UserTransaction(txn) => TransactionSummary {
                 transaction_hash: txn.info.hash,
@@ -951,6 +965,8 @@ impl From<&Transaction> for TransactionSummary {
                 sequence_number: Some(txn.request.sequence_number.0),
                 timestamp_us: Some(txn.timestamp.0),
                 pending: None,
+                storage_fee: None,
+                storage_fee_refund: None,
             },
             Transaction::GenesisTransaction(txn) => TransactionSummary {
                 transaction_hash: txn.info.hash,
@@ -962,6 +978,8 @@ impl From<&Transaction> for TransactionSummary {
                 gas_unit_price: None,
                 pending: None,
                 sequence_number: None,
+                storage_fee: None,
+                storage_fee_refund: None,
                 timestamp_us: None,
             },
             Transaction::BlockMetadataTransaction(txn) => TransactionSummary {
@@ -975,6 +993,8 @@ impl From<&Transaction> for TransactionSummary {
                 gas_unit_price: None,
                 pending: None,
                 sequence_number: None,
+                storage_fee: None,
+                storage_fee_refund: None,
             },
             Transaction::StateCheckpointTransaction(txn) => TransactionSummary {
                 transaction_hash: txn.info.hash,
@@ -987,6 +1007,8 @@ impl From<&Transaction> for TransactionSummary {
                 gas_unit_price: None,
                 pending: None,
                 sequence_number: None,
+                storage_fee: None,
+                storage_fee_refund: None,
             },
         }
     }
@@ -1183,8 +1205,90 @@ impl TransactionOptions {
         gas_price: Option<u64>,
         amount_transfer: Option<u64>,
     ) -> CliTypedResult<UserTransaction> {
-        let sender_key = self.private_key()?;
         let client = self.rest_client()?;
+        let signed_transaction = self
+            .build_simulated_transaction(&client, payload, gas_price, amount_transfer)
+            .await?;
+        let txns = client.simulate(&signed_transaction).await?.into_inner();
+        Ok(txns.first().unwrap().clone())
+    }
+
+    /// Executes a transaction locally against the latest state of the network instead of
+    /// submitting it, and writes a profile of the gas it was charged to `output_dir`: a
+    /// `<name>.folded` file of folded stacks for flamegraph tools, and a `<name>.txt` summary.
+    pub async fn profile_gas(
+        &self,
+        payload: TransactionPayload,
+        output_dir: &Path,
+        name: &str,
+    ) -> CliTypedResult<TransactionSummary> {
+        let client = self.rest_client()?;
+        let signed_transaction = self
+            .build_simulated_transaction(&client, payload, self.gas_options.gas_unit_price, None)
+            .await?;
+        let version = client.get_ledger_information().await?.into_inner().version;
+        let state_view = RestStateView::new(client, version);
+
+        let txn = signed_transaction.clone();
+        let (vm_status, output, profile) = tokio::task::spawn_blocking(move || {
+            AptosVM::simulate_signed_transaction_with_gas_profiling(&txn, &state_view)
+        })
+        .await
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        let profile = profile.ok_or_else(|| {
+            CliError::ApiError(format!(
+                "Simulated transaction was discarded with status {:?}",
+                vm_status
+            ))
+        })?;
+
+        create_dir_if_not_exist(output_dir)?;
+        write_to_file(
+            &output_dir.join(format!("{}.folded", name)),
+            "Gas profile folded stacks",
+            profile.to_folded_stacks().as_bytes(),
+        )?;
+        let summary = profile.summary_table();
+        write_to_file(
+            &output_dir.join(format!("{}.txt", name)),
+            "Gas profile summary",
+            summary.as_bytes(),
+        )?;
+        eprintln!("{}", summary);
+
+        let output = output.txn_output();
+        Ok(TransactionSummary {
+            transaction_hash: signed_transaction.clone().committed_hash().into(),
+            gas_used: Some(output.gas_used()),
+            gas_unit_price: Some(signed_transaction.gas_unit_price()),
+            pending: None,
+            sender: Some(signed_transaction.sender()),
+            sequence_number: Some(signed_transaction.sequence_number()),
+            success: Some(matches!(
+                output.status(),
+                TransactionStatus::Keep(ExecutionStatus::Success)
+            )),
+            timestamp_us: None,
+            version: Some(version),
+            vm_status: Some(format!("{:?}", vm_status)),
+            storage_fee: profile
+                .storage_fee()
+                .map(|storage_fee| storage_fee.charge.into()),
+            storage_fee_refund: profile
+                .storage_fee()
+                .map(|storage_fee| storage_fee.refund.into()),
+        })
+    }
+
+    /// Builds a transaction that can be simulated, i.e., that doesn't carry a valid signature.
+    async fn build_simulated_transaction(
+        &self,
+        client: &Client,
+        payload: TransactionPayload,
+        gas_price: Option<u64>,
+        amount_transfer: Option<u64>,
+    ) -> CliTypedResult<SignedTransaction> {
+        let sender_key = self.private_key()?;
 
         // Get sender address
         let sender_address = self.sender_address()?;
@@ -1234,13 +1338,11 @@ impl TransactionOptions {
             .sequence_number(sequence_number)
             .build();
 
-        let signed_transaction = SignedTransaction::new(
+        Ok(SignedTransaction::new(
             unsigned_transaction,
             sender_key.public_key(),
             Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
-        );
-        let txns = client.simulate(&signed_transaction).await?.into_inner();
-        Ok(txns.first().unwrap().clone())
+        ))
     }
 
     pub async fn estimate_gas_price(&self) -> CliTypedResult<u64> {
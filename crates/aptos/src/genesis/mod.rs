@@ -21,6 +21,7 @@ use aptos_genesis::builder::GenesisConfiguration;
 use aptos_genesis::config::{StringOperatorConfiguration, StringOwnerConfiguration};
 use aptos_genesis::{
     config::{Layout, ValidatorConfiguration},
+    spec::GenesisSpec,
     GenesisInfo,
 };
 use aptos_types::account_address::AccountAddress;
@@ -39,6 +40,7 @@ const GENESIS_FILE: &str = "genesis.blob";
 #[derive(Parser)]
 pub enum GenesisTool {
     GenerateGenesis(GenerateGenesis),
+    GenerateGenesisFromSpec(GenerateGenesisFromSpec),
     GenerateKeys(keys::GenerateKeys),
     GenerateLayoutTemplate(keys::GenerateLayoutTemplate),
     SetupGit(git::SetupGit),
@@ -49,6 +51,7 @@ impl GenesisTool {
     pub async fn execute(self) -> CliResult {
         match self {
             GenesisTool::GenerateGenesis(tool) => tool.execute_serialized().await,
+            GenesisTool::GenerateGenesisFromSpec(tool) => tool.execute_serialized().await,
             GenesisTool::GenerateKeys(tool) => tool.execute_serialized().await,
             GenesisTool::GenerateLayoutTemplate(tool) => tool.execute_serialized_success().await,
             GenesisTool::SetupGit(tool) => tool.execute_serialized_success().await,
@@ -103,6 +106,84 @@ impl CliCommand<Vec<PathBuf>> for GenerateGenesis {
     }
 }
 
+/// Generate genesis from a single genesis spec file
+///
+/// The spec declares the chain id, framework, accounts and their balances, validators and their
+/// stake, as well as the on-chain configs.  It is fully validated first, and every problem found
+/// is reported before anything is written.
+#[derive(Parser)]
+pub struct GenerateGenesisFromSpec {
+    /// Path to the YAML genesis spec
+    #[clap(long, parse(from_os_str))]
+    spec_file: PathBuf,
+    /// Output directory for Genesis file and waypoint
+    #[clap(long, parse(from_os_str))]
+    output_dir: Option<PathBuf>,
+    /// Only validate the spec, without generating genesis
+    #[clap(long)]
+    validate_only: bool,
+
+    #[clap(flatten)]
+    prompt_options: PromptOptions,
+}
+
+#[async_trait]
+impl CliCommand<Vec<PathBuf>> for GenerateGenesisFromSpec {
+    fn command_name(&self) -> &'static str {
+        "GenerateGenesisFromSpec"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<PathBuf>> {
+        let spec = GenesisSpec::from_disk(&self.spec_file)
+            .map_err(|err| CliError::UnableToParse("spec-file", err.to_string()))?;
+
+        // Collect errors, and report all of them at once
+        let errors = spec.validate();
+        if !errors.is_empty() {
+            return Err(CliError::CommandArgumentError(format!(
+                "Genesis spec {} has {} error(s):\n{}",
+                self.spec_file.display(),
+                errors.len(),
+                errors.join("\n")
+            )));
+        }
+        if self.validate_only {
+            return Ok(vec![]);
+        }
+
+        let output_dir = dir_default_to_current(self.output_dir.clone())?;
+        let genesis_file = output_dir.join(GENESIS_FILE);
+        let waypoint_file = output_dir.join(WAYPOINT_FILE);
+        check_if_file_exists(genesis_file.as_path(), self.prompt_options)?;
+        check_if_file_exists(waypoint_file.as_path(), self.prompt_options)?;
+
+        let spec_dir = self
+            .spec_file
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let framework = spec.load_framework(&spec_dir)?;
+        let mut genesis_info = spec.into_genesis_info(framework)?;
+
+        // Generate genesis file
+        let genesis = genesis_info.get_genesis();
+        write_to_file(
+            genesis_file.as_path(),
+            GENESIS_FILE,
+            &bcs::to_bytes(genesis).map_err(|e| CliError::BCS(GENESIS_FILE, e))?,
+        )?;
+
+        // Generate waypoint file
+        let waypoint = genesis_info.generate_waypoint()?;
+        write_to_file(
+            waypoint_file.as_path(),
+            WAYPOINT_FILE,
+            waypoint.to_string().as_bytes(),
+        )?;
+        Ok(vec![genesis_file, waypoint_file])
+    }
+}
+
 /// Retrieves all information for genesis from the Git repository
 pub fn fetch_genesis_info(git_options: GitOptions) -> CliTypedResult<GenesisInfo> {
     let client = git_options.get_client()?;
@@ -161,6 +242,7 @@ pub fn fetch_genesis_info(git_options: GitOptions) -> CliTypedResult<GenesisInfo
             recurring_lockup_duration_secs: layout.recurring_lockup_duration_secs,
             required_proposer_stake: layout.required_proposer_stake,
             rewards_apy_percentage: layout.rewards_apy_percentage,
+            transaction_fee_burn_percentage: layout.transaction_fee_burn_percentage,
             voting_duration_secs: layout.voting_duration_secs,
             voting_power_increase_limit: layout.voting_power_increase_limit,
         },
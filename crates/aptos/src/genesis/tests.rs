@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::common::types::OptionalPoolAddressArgs;
+use crate::common::types::{CliTypedResult, OptionalPoolAddressArgs};
 use crate::common::utils::read_from_file;
 use crate::genesis::git::from_yaml;
 use crate::genesis::git::FRAMEWORK_NAME;
@@ -14,7 +14,7 @@ use crate::{
     genesis::{
         git::{GitOptions, SetupGit},
         keys::{GenerateKeys, SetValidatorConfiguration},
-        GenerateGenesis,
+        GenerateGenesis, GenerateGenesisFromSpec,
     },
     CliCommand,
 };
@@ -22,15 +22,20 @@ use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
     PrivateKey,
 };
-use aptos_genesis::config::{HostAndPort, Layout};
+use aptos_genesis::{
+    config::{HostAndPort, Layout, ValidatorConfiguration},
+    keys::generate_key_objects,
+    spec::GenesisSpec,
+};
 use aptos_keygen::KeyGen;
 use aptos_temppath::TempPath;
-use aptos_types::chain_id::ChainId;
+use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     str::FromStr,
 };
+use vm_genesis::AccountBalance;
 
 /// Test the E2E genesis flow since it doesn't require a node to run
 #[tokio::test]
@@ -74,6 +79,133 @@ async fn test_genesis_e2e_flow() {
     assert!(genesis_file.exists());
 }
 
+/// Test generating genesis from a single spec file, and that an invalid spec is rejected
+#[tokio::test]
+async fn test_genesis_from_spec() {
+    let dir = TempPath::new();
+    dir.create_as_dir().unwrap();
+    let spec_file = dir.path().join("genesis.yaml");
+
+    let mut keygen = KeyGen::from_seed([7; 32]);
+    let root_private_key = keygen.generate_ed25519_private_key();
+    let validators = (0..2)
+        .map(|i| validator_configuration(&mut keygen, 6180 + i))
+        .collect();
+    let mut spec = GenesisSpec {
+        chain_id: ChainId::test(),
+        root_key: root_private_key.public_key(),
+        framework: None,
+        allow_new_validators: true,
+        epoch_duration_secs: 7_200,
+        min_stake: 100_000_000,
+        min_voting_threshold: 100_000_000,
+        max_stake: 100_000_000_000_000,
+        recurring_lockup_duration_secs: 86_400,
+        required_proposer_stake: 100_000_000,
+        rewards_apy_percentage: 10,
+        transaction_fee_burn_percentage: None,
+        voting_duration_secs: 43_200,
+        voting_power_increase_limit: 20,
+        accounts: vec![AccountBalance {
+            account_address: AccountAddress::random(),
+            balance: 1_000_000,
+        }],
+        validators,
+        consensus_config: None,
+        gas_schedule: None,
+    };
+    assert!(spec.validate().is_empty());
+    write_spec(&spec_file, &spec);
+
+    let output_dir = PathBuf::from(dir.path()).join("output");
+    std::fs::create_dir(&output_dir).unwrap();
+    let files = generate_genesis_from_spec(spec_file.clone(), output_dir.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        files,
+        vec![
+            output_dir.join("genesis.blob"),
+            output_dir.join("waypoint.txt")
+        ]
+    );
+    assert!(files.iter().all(|file| file.exists()));
+
+    // Break the spec in several ways, all of which should be reported together
+    let duplicate = spec.validators[0].clone();
+    spec.validators.push(duplicate);
+    spec.validators[1].stake_amount = spec.max_stake + 1;
+    spec.accounts.push(AccountBalance {
+        account_address: spec.validators[1].owner_account_address,
+        balance: 1,
+    });
+    spec.voting_duration_secs = spec.recurring_lockup_duration_secs;
+    let errors = spec.validate();
+    assert_eq!(errors.len(), 7, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .any(|error| error.starts_with("validators[1]: stake_amount")));
+    assert!(errors
+        .iter()
+        .any(|error| error.starts_with("validators[2]: consensus_public_key")));
+    assert!(errors
+        .iter()
+        .any(|error| error.starts_with("accounts[1]: account")));
+
+    // The errors are part of the command's error, rather than only printed
+    write_spec(&spec_file, &spec);
+    let message = generate_genesis_from_spec(spec_file, output_dir)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(message.contains("7 error(s)"), "{}", message);
+    assert!(errors.iter().all(|error| message.contains(error.as_str())));
+}
+
+/// Builds the configuration of a validator that is its own operator and voter
+fn validator_configuration(keygen: &mut KeyGen, port: u16) -> ValidatorConfiguration {
+    let (_, _, _, public_identity) = generate_key_objects(keygen).unwrap();
+    ValidatorConfiguration {
+        owner_account_address: public_identity.account_address,
+        owner_account_public_key: public_identity.account_public_key.clone(),
+        operator_account_address: public_identity.account_address,
+        operator_account_public_key: public_identity.account_public_key.clone(),
+        voter_account_address: public_identity.account_address,
+        voter_account_public_key: public_identity.account_public_key,
+        consensus_public_key: public_identity.consensus_public_key.unwrap(),
+        proof_of_possession: public_identity.consensus_proof_of_possession.unwrap(),
+        validator_network_public_key: public_identity.validator_network_public_key.unwrap(),
+        validator_host: HostAndPort::from_str(&format!("localhost:{}", port)).unwrap(),
+        full_node_network_public_key: None,
+        full_node_host: None,
+        stake_amount: 100_000_000_000,
+    }
+}
+
+fn write_spec(file: &Path, spec: &GenesisSpec) {
+    write_to_file(
+        file,
+        "Genesis spec",
+        serde_yaml::to_string(spec).unwrap().as_bytes(),
+    )
+    .unwrap();
+}
+
+/// Generate genesis and waypoint from a spec file
+async fn generate_genesis_from_spec(
+    spec_file: PathBuf,
+    output_dir: PathBuf,
+) -> CliTypedResult<Vec<PathBuf>> {
+    GenerateGenesisFromSpec {
+        spec_file,
+        output_dir: Some(output_dir),
+        validate_only: false,
+        prompt_options: PromptOptions::yes(),
+    }
+    .execute()
+    .await
+}
+
 /// Generate genesis and waypoint
 async fn generate_genesis(git_options: GitOptions, output_dir: PathBuf) {
     let command = GenerateGenesis {
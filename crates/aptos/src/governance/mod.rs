@@ -1,9 +1,13 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(test)]
+mod tests;
+
+use crate::common::rest_state_view::RestStateView;
 use crate::common::types::{
-    CliError, CliTypedResult, MovePackageDir, PoolAddressArgs, PromptOptions, TransactionOptions,
-    TransactionSummary,
+    CliError, CliTypedResult, MovePackageDir, PoolAddressArgs, ProfileOptions, PromptOptions,
+    RestOptions, TransactionOptions, TransactionSummary,
 };
 use crate::common::utils::prompt_yes_with_override;
 #[cfg(feature = "no-upload-proposal")]
@@ -12,22 +16,38 @@ use crate::move_tool::{init_move_dir, IncludedArtifacts};
 use crate::{CliCommand, CliResult};
 use aptos_crypto::HashValue;
 use aptos_logger::warn;
-use aptos_rest_client::aptos_api_types::U64;
+use aptos_rest_client::aptos_api_types::{MoveResource, MoveValue, U64};
 use aptos_rest_client::Transaction;
+use aptos_state_view::{overlay_state_view::OverlayStateView, StateView};
 use aptos_types::{
+    access_path::{AccessPath, Path as AccessPathPath},
     account_address::AccountAddress,
-    transaction::{Script, TransactionPayload},
+    account_config::CORE_CODE_ADDRESS,
+    state_store::state_key::StateKey,
+    transaction::{Script, TransactionPayload, Version},
+    write_set::WriteOp,
 };
+use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
 use async_trait::async_trait;
 use cached_packages::aptos_stdlib;
 use clap::Parser;
 use framework::{BuildOptions, BuiltPackage, ReleasePackage};
-use move_deps::move_core_types::transaction_argument::TransactionArgument;
+use move_deps::{
+    move_binary_format::{
+        access::ModuleAccess,
+        file_format::{Bytecode, FunctionHandleIndex},
+        CompiledModule,
+    },
+    move_core_types::{
+        ident_str, language_storage::ModuleId, transaction_argument::TransactionArgument,
+    },
+    move_resource_viewer::MoveValueAnnotator,
+};
 use reqwest::Url;
 use serde::Deserialize;
 use serde::Serialize;
 use std::path::Path;
-use std::{collections::BTreeMap, fmt::Formatter, fs, path::PathBuf};
+use std::{collections::BTreeMap, convert::TryFrom, fmt::Formatter, fs, path::PathBuf};
 use tempfile::TempDir;
 
 /// Tool for on-chain governance
@@ -41,6 +61,7 @@ pub enum GovernanceTool {
     Vote(SubmitVote),
     ExecuteProposal(ExecuteProposal),
     GenerateUpgradeProposal(GenerateUpgradeProposal),
+    SimulateProposal(SimulateProposal),
 }
 
 impl GovernanceTool {
@@ -51,6 +72,7 @@ impl GovernanceTool {
             Vote(tool) => tool.execute_serialized().await,
             ExecuteProposal(tool) => tool.execute_serialized().await,
             GenerateUpgradeProposal(tool) => tool.execute_serialized_success().await,
+            SimulateProposal(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -391,6 +413,283 @@ impl CliCommand<TransactionSummary> for ExecuteProposal {
     }
 }
 
+/// Simulate a proposal against the current state of the network, as if it had passed voting
+///
+/// The proposal script is executed locally, with `aptos_governance::resolve` mocked to approve
+/// the proposal right away, and the changes it would make to on-chain configs and code, along
+/// with the events it would emit, are shown.  Nothing is submitted to the network.
+#[derive(Parser)]
+pub struct SimulateProposal {
+    /// Proposal Id passed to the proposal script
+    ///
+    /// Voting is skipped, so this doesn't need to be the id of an existing proposal
+    #[clap(long, default_value_t = 0)]
+    pub(crate) proposal_id: u64,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) compile_proposal_args: CompileProposalArgs,
+}
+
+#[async_trait]
+impl CliCommand<ProposalSimulationSummary> for SimulateProposal {
+    fn command_name(&self) -> &'static str {
+        "SimulateProposal"
+    }
+
+    async fn execute(self) -> CliTypedResult<ProposalSimulationSummary> {
+        let (bytecode, script_hash) = self.compile_proposal_args.compile()?;
+        eprintln!("Simulating proposal with script hash {}", script_hash);
+
+        let client = self.rest_options.client(&self.profile_options.profile)?;
+        let version = client.get_ledger_information().await?.into_inner().version;
+        let state_view = RestStateView::new(client, version);
+        let script = Script::new(
+            bytecode,
+            vec![],
+            vec![TransactionArgument::U64(self.proposal_id)],
+        );
+
+        tokio::task::spawn_blocking(move || simulate_proposal(state_view, version, &script))
+            .await
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+    }
+}
+
+/// Outcome of simulating a proposal
+#[derive(Debug, Serialize)]
+pub struct ProposalSimulationSummary {
+    /// Ledger version the proposal was simulated against
+    version: Version,
+    gas_used: u64,
+    changes: Vec<SimulatedChange>,
+    events: Vec<SimulatedEvent>,
+}
+
+/// A change the proposal would make to on-chain state
+#[derive(Debug, Serialize)]
+pub struct SimulatedChange {
+    /// e.g. `write_module` or `delete_resource`
+    #[serde(rename = "type")]
+    change_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<AccountAddress>,
+    /// Name of the module, type of the resource, or table handle and key changed
+    key: String,
+    /// New value, decoded when its type is known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<serde_json::Value>,
+}
+
+/// An event the proposal would emit
+#[derive(Debug, Serialize)]
+pub struct SimulatedEvent {
+    #[serde(rename = "type")]
+    typ: String,
+    data: serde_json::Value,
+}
+
+/// Functions of `aptos_governance` handing out the governance signer once a proposal has passed
+const RESOLVE_FUNCTIONS: &[&str] = &["resolve", "resolve_multi_step_proposal"];
+
+/// Executes the proposal `script` on top of `state_view`, with voting mocked out
+fn simulate_proposal<S: StateView>(
+    state_view: S,
+    version: Version,
+    script: &Script,
+) -> CliTypedResult<ProposalSimulationSummary> {
+    let governance_key = StateKey::AccessPath(AccessPath::code_access_path(ModuleId::new(
+        CORE_CODE_ADDRESS,
+        ident_str!("aptos_governance").to_owned(),
+    )));
+    let governance_module = state_view
+        .get_state_value(&governance_key)?
+        .ok_or_else(|| {
+            CliError::UnexpectedError("aptos_governance not found on chain".to_string())
+        })?;
+    let mut state_view = OverlayStateView::new(state_view);
+    state_view.set(
+        governance_key,
+        Some(mock_governance_resolution(&governance_module)?),
+    );
+
+    let (change_set, gas_used) =
+        AptosVM::execute_governance_script(script, &state_view).map_err(|vm_status| {
+            CliError::SimulationError(format!("Proposal script failed with {:?}", vm_status))
+        })?;
+    let (deltas, change_set) = change_set.into_inner();
+    let (write_set, events) = change_set.into_inner();
+
+    // Decode values against the state after the proposal, so new and upgraded types are known
+    for (state_key, write_op) in write_set.iter() {
+        let value = match write_op {
+            WriteOp::Creation(value) | WriteOp::Modification(value) => Some(value.clone()),
+            WriteOp::Deletion => None,
+        };
+        state_view.set(state_key.clone(), value);
+    }
+    let resolver = state_view.as_move_resolver();
+    let annotator = MoveValueAnnotator::new(&resolver);
+
+    let mut changes = Vec::new();
+    for (state_key, write_op) in write_set.iter() {
+        let value = match write_op {
+            WriteOp::Creation(value) | WriteOp::Modification(value) => Some(value),
+            WriteOp::Deletion => None,
+        };
+        let change = match state_key {
+            StateKey::AccessPath(access_path) => match access_path.get_path() {
+                AccessPathPath::Code(module_id) => SimulatedChange {
+                    change_type: if value.is_some() {
+                        "write_module"
+                    } else {
+                        "delete_module"
+                    },
+                    address: Some(access_path.address),
+                    key: module_id.name().to_string(),
+                    data: None,
+                },
+                AccessPathPath::Resource(struct_tag) => SimulatedChange {
+                    change_type: if value.is_some() {
+                        "write_resource"
+                    } else {
+                        "delete_resource"
+                    },
+                    address: Some(access_path.address),
+                    data: value.map(|value| {
+                        annotator
+                            .view_resource(&struct_tag, value)
+                            .and_then(MoveResource::try_from)
+                            .and_then(|resource| Ok(serde_json::to_value(resource.data)?))
+                            .unwrap_or_else(|_| serde_json::Value::String(hex::encode(value)))
+                    }),
+                    key: struct_tag.to_string(),
+                },
+            },
+            StateKey::TableItem { handle, key } => SimulatedChange {
+                change_type: if value.is_some() {
+                    "write_table_item"
+                } else {
+                    "delete_table_item"
+                },
+                address: None,
+                key: format!("{}/{}", handle.0, hex::encode(key)),
+                data: value.map(|value| serde_json::Value::String(hex::encode(value))),
+            },
+            StateKey::Raw(key) => SimulatedChange {
+                change_type: if value.is_some() {
+                    "write_raw"
+                } else {
+                    "delete_raw"
+                },
+                address: None,
+                key: hex::encode(key),
+                data: value.map(|value| serde_json::Value::String(hex::encode(value))),
+            },
+        };
+        changes.push(change);
+    }
+    for (state_key, delta) in deltas.iter() {
+        changes.push(SimulatedChange {
+            change_type: "apply_delta",
+            address: None,
+            key: format!("{:?}", state_key),
+            data: Some(serde_json::Value::String(format!("{:?}", delta))),
+        });
+    }
+
+    let events = events
+        .iter()
+        .map(|event| SimulatedEvent {
+            typ: event.type_tag().to_string(),
+            data: annotator
+                .view_value(event.type_tag(), event.event_data())
+                .and_then(MoveValue::try_from)
+                .and_then(|value| value.json())
+                .unwrap_or_else(|_| serde_json::Value::String(hex::encode(event.event_data()))),
+        })
+        .collect();
+
+    Ok(ProposalSimulationSummary {
+        version,
+        gas_used,
+        changes,
+        events,
+    })
+}
+
+/// Replaces the functions of `aptos_governance` resolving a proposal, so that they hand out the
+/// governance signer right away, as if the proposal had been approved
+pub(crate) fn mock_governance_resolution(module_bytes: &[u8]) -> CliTypedResult<Vec<u8>> {
+    let mut module = CompiledModule::deserialize(module_bytes).map_err(|err| {
+        CliError::UnexpectedError(format!("Unable to deserialize aptos_governance: {:?}", err))
+    })?;
+    let function_handle = |module_name: &str, function_name: &str| {
+        module
+            .function_handles()
+            .iter()
+            .position(|handle| {
+                module.identifier_at(handle.name).as_str() == function_name
+                    && module
+                        .identifier_at(module.module_handle_at(handle.module).name)
+                        .as_str()
+                        == module_name
+            })
+            .map(|idx| FunctionHandleIndex(idx as u16))
+            .ok_or_else(|| {
+                CliError::UnexpectedError(format!(
+                    "{}::{} isn't used by aptos_governance, unable to mock voting",
+                    module_name, function_name
+                ))
+            })
+    };
+    let create_proposal = function_handle("governance_proposal", "create_proposal")?;
+    let get_signer = function_handle("aptos_governance", "get_signer")?;
+    let get_signer_acquires = module
+        .function_defs()
+        .iter()
+        .find(|def| def.function == get_signer)
+        .map(|def| def.acquires_global_resources.clone())
+        .unwrap_or_default();
+    let resolve_functions: Vec<usize> = module
+        .function_defs()
+        .iter()
+        .enumerate()
+        .filter(|(_, def)| {
+            let name = module.identifier_at(module.function_handle_at(def.function).name);
+            RESOLVE_FUNCTIONS.contains(&name.as_str())
+        })
+        .map(|(idx, _)| idx)
+        .collect();
+    if resolve_functions.is_empty() {
+        return Err(CliError::UnexpectedError(
+            "aptos_governance::resolve not found, unable to mock voting".to_string(),
+        ));
+    }
+
+    // `resolve(proposal_id, signer_address)` becomes
+    // `get_signer(governance_proposal::create_proposal(), signer_address)`
+    for idx in resolve_functions {
+        let def = &mut module.function_defs[idx];
+        def.acquires_global_resources = get_signer_acquires.clone();
+        if let Some(code) = def.code.as_mut() {
+            code.code = vec![
+                Bytecode::Call(create_proposal),
+                Bytecode::MoveLoc(1),
+                Bytecode::Call(get_signer),
+                Bytecode::Ret,
+            ];
+        }
+    }
+
+    let mut bytes = vec![];
+    module.serialize(&mut bytes)?;
+    Ok(bytes)
+}
+
 /// Execute a proposal that has passed voting requirements
 #[derive(Parser)]
 pub struct CompileProposalArgs {
@@ -0,0 +1,107 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::types::{CliError, PromptOptions};
+use crate::governance::{compile_script, mock_governance_resolution, simulate_proposal};
+use crate::move_tool::init_move_dir;
+use aptos_types::transaction::Script;
+use language_e2e_tests::data_store::{FakeDataStore, GENESIS_CHANGE_SET};
+use move_deps::{
+    move_binary_format::{access::ModuleAccess, CompiledModule},
+    move_bytecode_verifier::verify_module,
+    move_core_types::transaction_argument::TransactionArgument,
+};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+use tempfile::TempDir;
+
+#[test]
+fn test_mock_governance_resolution_verifies() {
+    let (governance_bytes, _) = cached_packages::head_release_bundle()
+        .code_and_compiled_modules()
+        .into_iter()
+        .find(|(_, module)| module.self_id().name().as_str() == "aptos_governance")
+        .expect("aptos_governance should be in the framework");
+
+    let mocked = mock_governance_resolution(governance_bytes).unwrap();
+    let mocked = CompiledModule::deserialize(&mocked).unwrap();
+    verify_module(&mocked).unwrap();
+}
+
+/// Compiles `source` into a proposal script against the local framework
+fn compile_proposal(source: &str) -> Script {
+    let temp_dir = TempDir::new().unwrap();
+    let package_dir = temp_dir.path();
+    let framework_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../aptos-move/framework/aptos-framework");
+    init_move_dir(
+        package_dir,
+        "Proposal",
+        None,
+        BTreeMap::new(),
+        PromptOptions::yes(),
+        Some(framework_dir),
+    )
+    .unwrap();
+    fs::write(package_dir.join("sources").join("proposal.move"), source).unwrap();
+
+    let (bytecode, _) = compile_script(package_dir).unwrap();
+    Script::new(bytecode, vec![], vec![TransactionArgument::U64(0)])
+}
+
+fn genesis_state() -> FakeDataStore {
+    let mut state_view = FakeDataStore::default();
+    state_view.add_write_set(GENESIS_CHANGE_SET.write_set());
+    state_view
+}
+
+#[test]
+fn test_simulate_proposal() {
+    let script = compile_proposal(
+        r#"
+        script {
+            use aptos_framework::aptos_governance;
+
+            fun main(proposal_id: u64) {
+                let framework_signer = aptos_governance::resolve(proposal_id, @aptos_framework);
+                aptos_governance::update_governance_config(&framework_signer, 1, 2, 3);
+            }
+        }
+        "#,
+    );
+
+    // The proposal doesn't exist, so it only runs because voting is mocked out
+    let summary = simulate_proposal(genesis_state(), 0, &script).unwrap();
+    assert!(summary.gas_used > 0);
+    assert!(summary
+        .changes
+        .iter()
+        .any(|change| change.change_type == "write_resource"
+            && change.key.ends_with("::aptos_governance::GovernanceConfig")));
+    let event = summary
+        .events
+        .iter()
+        .find(|event| event.typ.ends_with("::aptos_governance::UpdateConfigEvent"))
+        .expect("Updating the governance config should emit an event");
+    assert_eq!("3", event.data["voting_duration_secs"]);
+}
+
+#[test]
+fn test_simulate_failing_proposal() {
+    // Only the framework's signer can be handed out by governance
+    let script = compile_proposal(
+        r#"
+        script {
+            use aptos_framework::aptos_governance;
+
+            fun main(proposal_id: u64) {
+                aptos_governance::resolve(proposal_id, @0xcafe);
+            }
+        }
+        "#,
+    );
+
+    assert!(matches!(
+        simulate_proposal(genesis_state(), 0, &script),
+        Err(CliError::SimulationError(_))
+    ));
+}
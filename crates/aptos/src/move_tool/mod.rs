@@ -7,6 +7,7 @@ pub mod package_hooks;
 pub use package_hooks::*;
 pub mod stored_package;
 mod transactional_tests_runner;
+mod unit_test_profiling;
 
 pub use stored_package::*;
 
@@ -30,18 +31,21 @@ use crate::{
 };
 use aptos_gas::{AbstractValueSizeGasParameters, NativeGasParameters};
 use aptos_module_verifier::module_init::verify_module_init_function;
-use aptos_rest_client::aptos_api_types::MoveType;
+use aptos_rest_client::{aptos_api_types::MoveType, error::RestError, Client};
 use aptos_transactional_test_harness::run_aptos_test;
 use aptos_types::account_address::AccountAddress;
 use aptos_types::transaction::{EntryFunction, ModuleBundle, TransactionPayload};
 use async_trait::async_trait;
 use clap::{ArgEnum, Parser, Subcommand};
 use framework::natives::code::UpgradePolicy;
-use framework::{BuildOptions, BuiltPackage};
+use framework::{
+    check_module_compatibility, BuildOptions, BuiltPackage, Incompatibility, MemberKind,
+};
 use itertools::Itertools;
 use move_deps::move_cli::base::test::UnitTestResult;
 use move_deps::move_command_line_common::env::MOVE_HOME;
 use move_deps::{
+    move_binary_format::CompiledModule,
     move_cli,
     move_core_types::{
         identifier::Identifier,
@@ -51,6 +55,8 @@ use move_deps::{
     move_prover, move_prover_boogie_backend,
     move_unit_test::UnitTestingConfig,
 };
+use reqwest::StatusCode;
+use serde::Serialize;
 use std::fmt::{Display, Formatter};
 use std::{
     collections::BTreeMap,
@@ -72,6 +78,7 @@ pub enum MoveTool {
     Init(InitPackage),
     Publish(PublishPackage),
     Download(DownloadPackage),
+    CheckUpgrade(CheckUpgrade),
     List(ListPackage),
     Clean(CleanPackage),
     Run(RunFunction),
@@ -87,6 +94,7 @@ impl MoveTool {
             MoveTool::Init(tool) => tool.execute_serialized_success().await,
             MoveTool::Publish(tool) => tool.execute_serialized().await,
             MoveTool::Download(tool) => tool.execute_serialized().await,
+            MoveTool::CheckUpgrade(tool) => tool.execute_serialized().await,
             MoveTool::List(tool) => tool.execute_serialized().await,
             MoveTool::Clean(tool) => tool.execute_serialized().await,
             MoveTool::Run(tool) => tool.execute_serialized().await,
@@ -275,6 +283,18 @@ pub struct TestPackage {
         long = "instructions"
     )]
     pub instruction_execution_bound: u64,
+
+    /// Profile the gas of the tests after running them
+    ///
+    /// Each test is executed again with the Aptos gas schedule, and the gas it's charged per
+    /// function, native function and storage operation is written to `--profile-output-dir`, as
+    /// folded stacks for flamegraph tools and as a summary table.
+    #[clap(long)]
+    pub(crate) profile_gas: bool,
+
+    /// Directory where the gas profiles are written when `--profile-gas` is set
+    #[clap(long, parse(from_os_str), default_value = "gas-profiling")]
+    pub(crate) profile_output_dir: PathBuf,
 }
 
 #[async_trait]
@@ -290,11 +310,12 @@ impl CliCommand<&'static str> for TestPackage {
             install_dir: self.move_options.output_dir.clone(),
             ..Default::default()
         };
+        let package_path = self.move_options.get_package_path()?;
         let result = move_cli::base::test::run_move_unit_tests(
-            self.move_options.get_package_path()?.as_path(),
-            config,
+            package_path.as_path(),
+            config.clone(),
             UnitTestingConfig {
-                filter: self.filter,
+                filter: self.filter.clone(),
                 instruction_execution_bound: Some(self.instruction_execution_bound),
                 ..UnitTestingConfig::default_with_bound(None)
             },
@@ -308,6 +329,15 @@ impl CliCommand<&'static str> for TestPackage {
         )
         .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
 
+        if self.profile_gas {
+            unit_test_profiling::profile_unit_tests(
+                package_path.as_path(),
+                config,
+                self.filter.as_deref(),
+                &self.profile_output_dir,
+            )?;
+        }
+
         match result {
             UnitTestResult::Success => Ok("Success"),
             UnitTestResult::Failure => Err(CliError::MoveTestError),
@@ -599,6 +629,145 @@ impl CliCommand<&'static str> for DownloadPackage {
     }
 }
 
+/// Checks whether a package can be published as an upgrade of a package on-chain
+///
+/// This fetches the package with the same name from the given account, and runs the same
+/// upgrade checks locally which are performed when the package is published. Any changes
+/// which make the upgrade incompatible are listed per module.
+#[derive(Parser)]
+pub struct CheckUpgrade {
+    /// Address of the account containing the deployed package
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub(crate) account: AccountAddress,
+
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// The result of checking a package upgrade
+#[derive(Debug, Serialize)]
+pub struct CheckUpgradeSummary {
+    pub package: String,
+    /// Whether a package with the same name is deployed at the account
+    pub deployed: bool,
+    pub compatible: bool,
+    /// Errors which prevent upgrading the package as a whole
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub package_errors: Vec<String>,
+    /// Incompatible changes, by module name
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub incompatibilities: BTreeMap<String, Vec<Incompatibility>>,
+}
+
+#[async_trait]
+impl CliCommand<CheckUpgradeSummary> for CheckUpgrade {
+    fn command_name(&self) -> &'static str {
+        "CheckUpgrade"
+    }
+
+    async fn execute(self) -> CliTypedResult<CheckUpgradeSummary> {
+        let package_path = self.move_options.get_package_path()?;
+        let build_options =
+            IncludedArtifacts::Sparse.build_options(self.move_options.named_addresses());
+        let package = BuiltPackage::build(package_path, build_options)
+            .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
+        let new_metadata = package.extract_metadata()?;
+
+        let url = self.rest_options.url(&self.profile_options.profile)?;
+        let mut summary = CheckUpgradeSummary {
+            package: package.name().to_string(),
+            deployed: false,
+            compatible: true,
+            package_errors: vec![],
+            incompatibilities: BTreeMap::new(),
+        };
+        // An account which never published a package has no registry.
+        let registry = match CachedPackageRegistry::create(url.clone(), self.account).await {
+            Ok(registry) => registry,
+            Err(err) if is_not_found(&err) => return Ok(summary),
+            Err(err) => return Err(err.into()),
+        };
+        // The package will be published fresh, there is nothing to be compatible with.
+        if !registry.package_names().contains(&package.name()) {
+            return Ok(summary);
+        }
+        let old_package = registry.get_package(package.name()).await?;
+        summary.deployed = true;
+
+        let old_policy = old_package.upgrade_policy();
+        if old_policy == UpgradePolicy::immutable() {
+            summary
+                .package_errors
+                .push("the deployed package is immutable and cannot be upgraded".to_owned());
+        }
+        if new_metadata.upgrade_policy.policy < old_policy.policy {
+            summary.package_errors.push(format!(
+                "upgrade policy cannot be weakened from `{}` to `{}`",
+                old_policy, new_metadata.upgrade_policy
+            ));
+        }
+
+        let client = Client::new(url);
+        let new_modules: BTreeMap<String, &CompiledModule> = package
+            .modules()
+            .map(|module| (module.self_id().name().to_string(), module))
+            .collect();
+        for name in old_package.module_names() {
+            let new_module = match new_modules.get(name) {
+                Some(new_module) => new_module,
+                None => {
+                    summary.incompatibilities.insert(
+                        name.to_string(),
+                        vec![Incompatibility::new(
+                            MemberKind::Module,
+                            name,
+                            "module was removed from the package".to_owned(),
+                        )],
+                    );
+                    continue;
+                }
+            };
+            // Modules of `arbitrary` packages are not checked for compatibility when upgraded.
+            if old_policy == UpgradePolicy::arbitrary() {
+                continue;
+            }
+            let bytes = client
+                .get_account_module_bcs(self.account, name)
+                .await?
+                .into_inner();
+            let old_module = CompiledModule::deserialize(&bytes).map_err(|e| {
+                CliError::UnexpectedError(format!(
+                    "Failed to deserialize deployed module `{}`: {}",
+                    name, e
+                ))
+            })?;
+            let incompatibilities = check_module_compatibility(&old_module, new_module);
+            if !incompatibilities.is_empty() {
+                summary
+                    .incompatibilities
+                    .insert(name.to_string(), incompatibilities);
+            }
+        }
+
+        summary.compatible =
+            summary.package_errors.is_empty() && summary.incompatibilities.is_empty();
+        Ok(summary)
+    }
+}
+
+/// Whether the request failed because the resource doesn't exist.
+fn is_not_found(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<RestError>() {
+        Some(RestError::Api(err)) => err.status_code == StatusCode::NOT_FOUND,
+        Some(RestError::Http(status_code)) => *status_code == StatusCode::NOT_FOUND,
+        _ => false,
+    }
+}
+
 /// Lists information about packages and modules on-chain
 #[derive(Parser)]
 pub struct ListPackage {
@@ -702,9 +871,9 @@ impl CliCommand<&'static str> for CleanPackage {
     }
 }
 
-/// Run a Move function
+/// Arguments identifying an entry function and the arguments to call it with
 #[derive(Parser)]
-pub struct RunFunction {
+pub struct EntryFunctionArguments {
     /// Function name as `<ADDRESS>::<MODULE_ID>::<FUNCTION_NAME>`
     ///
     /// Example: `0x842ed41fad9640a2ad08fdd7d3e4f7f505319aac7d67e1c0dd6a7cce8732c7e3::message::set_message`
@@ -724,18 +893,20 @@ pub struct RunFunction {
     /// Example: `u8 u64 u128 bool address vector signer`
     #[clap(long, multiple_values = true)]
     pub(crate) type_args: Vec<MoveType>,
-
-    #[clap(flatten)]
-    pub(crate) txn_options: TransactionOptions,
 }
 
-#[async_trait]
-impl CliCommand<TransactionSummary> for RunFunction {
-    fn command_name(&self) -> &'static str {
-        "RunFunction"
+impl EntryFunctionArguments {
+    /// Name used to identify the function, e.g. in gas profiles
+    pub(crate) fn profile_name(&self) -> String {
+        format!(
+            "{}-{}-{}",
+            self.function_id.module_id.address().short_str_lossless(),
+            self.function_id.module_id.name(),
+            self.function_id.member_id
+        )
     }
 
-    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+    pub(crate) fn create_entry_function(self) -> CliTypedResult<EntryFunction> {
         let args: Vec<Vec<u8>> = self
             .args
             .into_iter()
@@ -750,18 +921,58 @@ impl CliCommand<TransactionSummary> for RunFunction {
             type_args.push(type_tag)
         }
 
-        self.txn_options
-            .submit_transaction(
-                TransactionPayload::EntryFunction(EntryFunction::new(
-                    self.function_id.module_id,
-                    self.function_id.member_id,
-                    type_args,
-                    args,
-                )),
-                None,
-            )
-            .await
-            .map(TransactionSummary::from)
+        Ok(EntryFunction::new(
+            self.function_id.module_id,
+            self.function_id.member_id,
+            type_args,
+            args,
+        ))
+    }
+}
+
+/// Run a Move function
+#[derive(Parser)]
+pub struct RunFunction {
+    #[clap(flatten)]
+    pub(crate) entry_function_args: EntryFunctionArguments,
+
+    /// Profile the gas of the function instead of submitting a transaction
+    ///
+    /// The transaction is executed locally against the latest state of the network, and the gas
+    /// it's charged per function, native function and storage operation is written to
+    /// `--profile-output-dir`, as folded stacks for flamegraph tools and as a summary table.
+    #[clap(long)]
+    pub(crate) profile_gas: bool,
+
+    /// Directory where the gas profile is written when `--profile-gas` is set
+    #[clap(long, parse(from_os_str), default_value = "gas-profiling")]
+    pub(crate) profile_output_dir: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for RunFunction {
+    fn command_name(&self) -> &'static str {
+        "RunFunction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let profile_name = self.entry_function_args.profile_name();
+        let payload =
+            TransactionPayload::EntryFunction(self.entry_function_args.create_entry_function()?);
+
+        if self.profile_gas {
+            self.txn_options
+                .profile_gas(payload, &self.profile_output_dir, &profile_name)
+                .await
+        } else {
+            self.txn_options
+                .submit_transaction(payload, None)
+                .await
+                .map(TransactionSummary::from)
+        }
     }
 }
 
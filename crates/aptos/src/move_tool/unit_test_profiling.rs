@@ -0,0 +1,165 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas profiling of Move unit tests.
+//!
+//! The unit test runner of the Move CLI meters tests by instruction count and doesn't let us plug
+//! in another gas meter, so to profile them against the Aptos gas schedule, the tests of the
+//! package are executed here on the Move VM, with a [`GasProfiler`] wrapping an [`AptosGasMeter`].
+
+use crate::{
+    common::{
+        types::{CliError, CliTypedResult},
+        utils::{create_dir_if_not_exist, write_to_file},
+    },
+    move_tool::aptos_debug_natives::aptos_debug_natives,
+};
+use aptos_gas::{AptosGasMeter, AptosGasParameters, GasProfiler, InitialGasSchedule};
+use aptos_vm::natives;
+use move_deps::{
+    move_command_line_common::{address::NumericalAddress, parser::NumberFormat},
+    move_core_types::{identifier::IdentStr, value::serialize_values},
+    move_package::BuildConfig,
+    move_table_extension::NativeTableContext,
+    move_unit_test::{test_reporter::TestPlan, UnitTestingConfig},
+    move_vm_runtime::{move_vm::MoveVM, native_extensions::NativeContextExtensions},
+    move_vm_test_utils::InMemoryStorage,
+};
+use std::path::Path;
+
+/// Runs the unit tests of the package at `package_path` whose name contains `filter`, and writes
+/// a profile of the gas each of them was charged to `output_dir`: a `<test>.folded` file of folded
+/// stacks for flamegraph tools, and a `<test>.txt` summary.
+///
+/// Tests can use as much gas as the largest transaction. Whether they pass isn't checked here, as
+/// tests that are expected to abort are profiled up to the abort.
+pub fn profile_unit_tests(
+    package_path: &Path,
+    build_config: BuildConfig,
+    filter: Option<&str>,
+    output_dir: &Path,
+) -> CliTypedResult<()> {
+    let test_plan = build_test_plan(package_path, build_config)?;
+
+    let mut storage = InMemoryStorage::new();
+    for info in test_plan.module_info.values() {
+        let mut bytes = vec![];
+        info.module
+            .serialize(&mut bytes)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        storage.publish_or_overwrite_module(info.module.self_id(), bytes);
+    }
+
+    let gas_params = AptosGasParameters::initial();
+    let vm = MoveVM::new(aptos_debug_natives(
+        gas_params.natives.clone(),
+        gas_params.misc.abs_val.clone(),
+    ))
+    .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+
+    create_dir_if_not_exist(output_dir)?;
+    for (module_id, module_tests) in &test_plan.module_tests {
+        for (test_name, test) in &module_tests.tests {
+            let name = format!(
+                "{}::{}::{}",
+                module_id.address().short_str_lossless(),
+                module_id.name(),
+                test_name
+            );
+            if !filter.map_or(true, |filter| name.contains(filter)) {
+                continue;
+            }
+
+            let mut extensions = NativeContextExtensions::default();
+            extensions.add(NativeTableContext::new([0; 32], &storage));
+            natives::unit_test_extensions_hook(&mut extensions);
+            let mut session = vm.new_session_with_extensions(&storage, extensions);
+
+            let mut gas_meter = GasProfiler::new_function(
+                AptosGasMeter::new(
+                    gas_params.clone(),
+                    gas_params.txn.maximum_number_of_gas_units,
+                ),
+                module_id,
+                test_name,
+            );
+            let function_name = IdentStr::new(test_name)
+                .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+            let result = session.execute_function_bypass_visibility(
+                module_id,
+                function_name,
+                vec![],
+                serialize_values(&test.arguments),
+                &mut gas_meter,
+            );
+            let (profile, _) = gas_meter.finish();
+
+            let profile_name = name.replace("::", "-");
+            write_to_file(
+                &output_dir.join(format!("{}.folded", profile_name)),
+                "Gas profile folded stacks",
+                profile.to_folded_stacks().as_bytes(),
+            )?;
+            write_to_file(
+                &output_dir.join(format!("{}.txt", profile_name)),
+                "Gas profile summary",
+                profile.summary_table().as_bytes(),
+            )?;
+            eprintln!(
+                "{}: {} internal gas units ({})",
+                name,
+                u64::from(profile.total()),
+                match result {
+                    Ok(_) => "returned".to_string(),
+                    Err(err) => format!("aborted with {:?}", err.major_status()),
+                }
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Compiles the package in test mode, along with its dependencies, and collects its unit tests.
+fn build_test_plan(package_path: &Path, build_config: BuildConfig) -> CliTypedResult<TestPlan> {
+    let build_config = BuildConfig {
+        dev_mode: true,
+        test_mode: true,
+        ..build_config
+    };
+    let resolution_graph = build_config
+        .resolution_graph_for_package(package_path, &mut Vec::new())
+        .map_err(|err| CliError::MoveCompilationError(err.to_string()))?;
+
+    let root_package = resolution_graph.root_package.package.name;
+    let mut source_files = vec![];
+    let mut dep_files = vec![];
+    for (package_name, package) in &resolution_graph.package_table {
+        let sources = package
+            .get_sources(&resolution_graph.build_options)
+            .map_err(|err| CliError::MoveCompilationError(err.to_string()))?
+            .into_iter()
+            .map(|file| file.to_string());
+        if *package_name == root_package {
+            source_files.extend(sources);
+        } else {
+            dep_files.extend(sources);
+        }
+    }
+
+    UnitTestingConfig {
+        source_files,
+        dep_files,
+        named_address_values: resolution_graph
+            .extract_named_address_mapping()
+            .map(|(name, address)| {
+                (
+                    name.to_string(),
+                    NumericalAddress::new(address.into_bytes(), NumberFormat::Hex),
+                )
+            })
+            .collect(),
+        ..UnitTestingConfig::default_with_bound(None)
+    }
+    .build_test_plan()
+    .ok_or_else(|| CliError::MoveCompilationError("Failed to build the unit test plan".to_string()))
+}
@@ -0,0 +1,292 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::types::{
+    CliCommand, CliResult, CliTypedResult, TransactionOptions, TransactionSummary,
+};
+use crate::move_tool::EntryFunctionArguments;
+use aptos_rest_client::{
+    aptos_api_types::{WriteResource, WriteSetChange},
+    Transaction,
+};
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{Multisig, MultisigTransactionPayload, TransactionPayload},
+};
+use async_trait::async_trait;
+use cached_packages::aptos_stdlib;
+use clap::Parser;
+use serde::Serialize;
+
+/// Tool for interacting with multisig accounts
+///
+/// Owners of a multisig account propose transactions on-chain, other owners approve or
+/// reject them, and once enough owners have approved, any owner can execute the transaction
+/// as the multisig account.
+#[derive(Parser)]
+pub enum MultisigTool {
+    Create(CreateMultisig),
+    Propose(ProposeTransaction),
+    Approve(ApproveTransaction),
+    Reject(RejectTransaction),
+    Execute(ExecuteTransaction),
+    ExecuteRejected(ExecuteRejectedTransaction),
+}
+
+impl MultisigTool {
+    pub async fn execute(self) -> CliResult {
+        use MultisigTool::*;
+        match self {
+            Create(tool) => tool.execute_serialized().await,
+            Propose(tool) => tool.execute_serialized().await,
+            Approve(tool) => tool.execute_serialized().await,
+            Reject(tool) => tool.execute_serialized().await,
+            Execute(tool) => tool.execute_serialized().await,
+            ExecuteRejected(tool) => tool.execute_serialized().await,
+        }
+    }
+}
+
+/// Create a new multisig account
+///
+/// The sender is always an owner of the new multisig account. The address of the multisig
+/// account is derived from the sender's address and sequence number.
+#[derive(Parser)]
+pub struct CreateMultisig {
+    /// Addresses of the owners of the multisig account, other than the sender
+    #[clap(long, multiple_values = true, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub additional_owners: Vec<AccountAddress>,
+
+    /// Number of owner approvals required to execute a transaction
+    #[clap(long)]
+    pub num_signatures_required: u64,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+/// A shortened create multisig account output
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateMultisigSummary {
+    pub multisig_account: Option<AccountAddress>,
+    #[serde(flatten)]
+    pub transaction_summary: TransactionSummary,
+}
+
+impl From<Transaction> for CreateMultisigSummary {
+    fn from(transaction: Transaction) -> Self {
+        let transaction_summary = TransactionSummary::from(&transaction);
+
+        let mut summary = CreateMultisigSummary {
+            transaction_summary,
+            multisig_account: None,
+        };
+
+        if let Transaction::UserTransaction(txn) = transaction {
+            summary.multisig_account = txn.info.changes.iter().find_map(|change| match change {
+                WriteSetChange::WriteResource(WriteResource { address, data, .. }) => {
+                    if data.typ.name.as_str() == "MultisigAccount" {
+                        Some(*address.inner())
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            });
+        }
+
+        summary
+    }
+}
+
+#[async_trait]
+impl CliCommand<CreateMultisigSummary> for CreateMultisig {
+    fn command_name(&self) -> &'static str {
+        "CreateMultisig"
+    }
+
+    async fn execute(self) -> CliTypedResult<CreateMultisigSummary> {
+        self.txn_options
+            .submit_transaction(
+                aptos_stdlib::multisig_account_create(
+                    self.additional_owners,
+                    self.num_signatures_required,
+                ),
+                None,
+            )
+            .await
+            .map(CreateMultisigSummary::from)
+    }
+}
+
+/// Propose a new transaction for a multisig account
+///
+/// The proposed entry function is stored on-chain and will be executed as the multisig account
+/// once enough owners have approved it. The proposer implicitly approves the transaction.
+#[derive(Parser)]
+pub struct ProposeTransaction {
+    /// Address of the multisig account
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub multisig_address: AccountAddress,
+
+    #[clap(flatten)]
+    pub(crate) entry_function_args: EntryFunctionArguments,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for ProposeTransaction {
+    fn command_name(&self) -> &'static str {
+        "ProposeMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let payload = MultisigTransactionPayload::EntryFunction(
+            self.entry_function_args.create_entry_function()?,
+        );
+        self.txn_options
+            .submit_transaction(
+                aptos_stdlib::multisig_account_create_transaction(
+                    self.multisig_address,
+                    bcs::to_bytes(&payload)?,
+                ),
+                None,
+            )
+            .await
+            .map(TransactionSummary::from)
+    }
+}
+
+/// Approve a pending transaction of a multisig account
+#[derive(Parser)]
+pub struct ApproveTransaction {
+    /// Address of the multisig account
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub multisig_address: AccountAddress,
+
+    /// Sequence number of the multisig transaction to approve
+    #[clap(long)]
+    pub sequence_number: u64,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for ApproveTransaction {
+    fn command_name(&self) -> &'static str {
+        "ApproveMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        self.txn_options
+            .submit_transaction(
+                aptos_stdlib::multisig_account_approve_transaction(
+                    self.multisig_address,
+                    self.sequence_number,
+                ),
+                None,
+            )
+            .await
+            .map(TransactionSummary::from)
+    }
+}
+
+/// Reject a pending transaction of a multisig account
+#[derive(Parser)]
+pub struct RejectTransaction {
+    /// Address of the multisig account
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub multisig_address: AccountAddress,
+
+    /// Sequence number of the multisig transaction to reject
+    #[clap(long)]
+    pub sequence_number: u64,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for RejectTransaction {
+    fn command_name(&self) -> &'static str {
+        "RejectMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        self.txn_options
+            .submit_transaction(
+                aptos_stdlib::multisig_account_reject_transaction(
+                    self.multisig_address,
+                    self.sequence_number,
+                ),
+                None,
+            )
+            .await
+            .map(TransactionSummary::from)
+    }
+}
+
+/// Execute the next approved transaction of a multisig account
+///
+/// The transaction payload stored on-chain is executed as the multisig account. Transactions
+/// are executed in order of their sequence numbers.
+#[derive(Parser)]
+pub struct ExecuteTransaction {
+    /// Address of the multisig account
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub multisig_address: AccountAddress,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for ExecuteTransaction {
+    fn command_name(&self) -> &'static str {
+        "ExecuteMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        self.txn_options
+            .submit_transaction(
+                TransactionPayload::Multisig(Multisig {
+                    multisig_address: self.multisig_address,
+                    transaction_payload: None,
+                }),
+                None,
+            )
+            .await
+            .map(TransactionSummary::from)
+    }
+}
+
+/// Remove the next transaction of a multisig account once enough owners have rejected it
+#[derive(Parser)]
+pub struct ExecuteRejectedTransaction {
+    /// Address of the multisig account
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub multisig_address: AccountAddress,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for ExecuteRejectedTransaction {
+    fn command_name(&self) -> &'static str {
+        "ExecuteRejectedMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        self.txn_options
+            .submit_transaction(
+                aptos_stdlib::multisig_account_execute_rejected_transaction(self.multisig_address),
+                None,
+            )
+            .await
+            .map(TransactionSummary::from)
+    }
+}
@@ -16,8 +16,8 @@ use crate::common::types::{
 };
 use crate::common::utils::write_to_file;
 use crate::move_tool::{
-    ArgWithType, CompilePackage, DownloadPackage, IncludedArtifacts, InitPackage, MemberId,
-    PublishPackage, RunFunction, TestPackage,
+    ArgWithType, CompilePackage, DownloadPackage, EntryFunctionArguments, IncludedArtifacts,
+    InitPackage, MemberId, PublishPackage, RunFunction, TestPackage,
 };
 use crate::node::{
     AnalyzeMode, AnalyzeValidatorPerformance, InitializeValidator, JoinValidatorSet,
@@ -665,6 +665,8 @@ impl CliTestFramework {
             instruction_execution_bound: 100_000,
             move_options: self.move_options(account_strs),
             filter: filter.map(|str| str.to_string()),
+            profile_gas: false,
+            profile_output_dir: PathBuf::from("gas-profiling"),
         }
         .execute()
         .await
@@ -731,9 +733,13 @@ impl CliTestFramework {
 
         RunFunction {
             txn_options: self.transaction_options(index, gas_options),
-            function_id,
-            args: parsed_args,
-            type_args: parsed_type_args,
+            entry_function_args: EntryFunctionArguments {
+                function_id,
+                args: parsed_args,
+                type_args: parsed_type_args,
+            },
+            profile_gas: false,
+            profile_output_dir: PathBuf::from("gas-profiling"),
         }
         .execute()
         .await
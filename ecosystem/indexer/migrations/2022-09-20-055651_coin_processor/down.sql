@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+drop table if exists coin_balances;
+drop table if exists current_coin_balances;
+drop table if exists coin_activities;
+drop table if exists coin_infos;
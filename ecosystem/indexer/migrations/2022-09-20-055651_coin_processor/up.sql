@@ -0,0 +1,71 @@
+-- Your SQL goes here
+-- Balance of every CoinStore<T> after each transaction which changed it
+CREATE TABLE coin_balances
+(
+    transaction_version uint_64,
+    owner_address VARCHAR NOT NULL,
+    coin_type VARCHAR NOT NULL,
+    amount uint_64,
+    transaction_timestamp TIMESTAMP NOT NULL,
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (transaction_version, owner_address, coin_type)
+);
+CREATE INDEX cb_owner_coin_type_index ON coin_balances (owner_address, coin_type);
+
+-- Latest balance of every CoinStore<T>
+CREATE TABLE current_coin_balances
+(
+    owner_address VARCHAR NOT NULL,
+    coin_type VARCHAR NOT NULL,
+    amount uint_64,
+    last_transaction_version uint_64,
+    last_transaction_timestamp TIMESTAMP NOT NULL,
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (owner_address, coin_type)
+);
+
+-- Deposits, withdrawals and gas fees of every account
+CREATE TABLE coin_activities
+(
+    transaction_version uint_64,
+    event_account_address VARCHAR NOT NULL,
+    -- Gas fees don't have an event, and use -1 for both
+    event_creation_number BIGINT NOT NULL,
+    event_sequence_number BIGINT NOT NULL,
+    owner_address VARCHAR NOT NULL,
+    coin_type VARCHAR NOT NULL,
+    amount uint_64,
+    activity_type VARCHAR NOT NULL,
+    is_gas_fee BOOLEAN NOT NULL,
+    is_transaction_success BOOLEAN NOT NULL,
+    entry_function_id_str VARCHAR,
+    transaction_timestamp TIMESTAMP NOT NULL,
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (
+        transaction_version,
+        event_account_address,
+        event_creation_number,
+        event_sequence_number
+    )
+);
+CREATE INDEX ca_owner_coin_type_index ON coin_activities (owner_address, coin_type);
+
+-- Latest CoinInfo<T> of every coin type
+CREATE TABLE coin_infos
+(
+    coin_type VARCHAR NOT NULL,
+    transaction_version_created uint_64,
+    creator_address VARCHAR NOT NULL,
+    name VARCHAR NOT NULL,
+    symbol VARCHAR NOT NULL,
+    decimals INT NOT NULL,
+    -- Only known when the supply isn't tracked by a parallelizable aggregator
+    supply NUMERIC,
+    last_transaction_version uint_64,
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (coin_type)
+);
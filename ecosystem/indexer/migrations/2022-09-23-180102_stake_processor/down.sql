@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+drop table if exists stake_pool_activities;
+drop table if exists stake_rewards;
+drop table if exists governance_proposals;
+drop table if exists governance_votes;
@@ -0,0 +1,74 @@
+-- Your SQL goes here
+-- Every 0x1::stake event, one row per event
+CREATE TABLE stake_pool_activities
+(
+    transaction_version uint_64,
+    event_account_address VARCHAR NOT NULL,
+    event_creation_number BIGINT NOT NULL,
+    event_sequence_number BIGINT NOT NULL,
+    pool_address VARCHAR NOT NULL,
+    activity_type VARCHAR NOT NULL,
+    -- Set for events which move stake, e.g. AddStakeEvent or DistributeRewardsEvent
+    amount NUMERIC,
+    -- Only set for SetOperatorEvent
+    old_operator_address VARCHAR,
+    new_operator_address VARCHAR,
+    -- Only set for IncreaseLockupEvent
+    old_locked_until_secs NUMERIC,
+    new_locked_until_secs NUMERIC,
+    transaction_timestamp TIMESTAMP NOT NULL,
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (
+        transaction_version,
+        event_account_address,
+        event_creation_number,
+        event_sequence_number
+    )
+);
+CREATE INDEX spa_pool_address_index ON stake_pool_activities (pool_address);
+
+-- Rewards distributed to each stake pool at the end of each epoch
+CREATE TABLE stake_rewards
+(
+    transaction_version uint_64,
+    pool_address VARCHAR NOT NULL,
+    epoch uint_64,
+    rewards_amount uint_64,
+    transaction_timestamp TIMESTAMP NOT NULL,
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (transaction_version, pool_address)
+);
+CREATE INDEX sr_pool_address_epoch_index ON stake_rewards (pool_address, epoch);
+
+-- Proposals created through 0x1::aptos_governance
+CREATE TABLE governance_proposals
+(
+    proposal_id uint_64,
+    proposer_address VARCHAR NOT NULL,
+    stake_pool_address VARCHAR NOT NULL,
+    execution_hash VARCHAR NOT NULL,
+    proposal_metadata JSONB NOT NULL,
+    transaction_version uint_64,
+    transaction_timestamp TIMESTAMP NOT NULL,
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (proposal_id)
+);
+
+-- Votes cast through 0x1::aptos_governance
+CREATE TABLE governance_votes
+(
+    transaction_version uint_64,
+    proposal_id uint_64,
+    voter_address VARCHAR NOT NULL,
+    stake_pool_address VARCHAR NOT NULL,
+    num_votes uint_64,
+    should_pass BOOLEAN NOT NULL,
+    transaction_timestamp TIMESTAMP NOT NULL,
+    inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (transaction_version, proposal_id, stake_pool_address)
+);
+CREATE INDEX gv_proposal_id_index ON governance_votes (proposal_id);
@@ -0,0 +1,208 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    database::{
+        execute_upsert_with_better_error, execute_with_better_error, get_chunks, PgDbPool,
+        PgPoolConnection,
+    },
+    indexer::{
+        errors::TransactionProcessingError, processing_result::ProcessingResult,
+        transaction_processor::TransactionProcessor,
+    },
+    models::coin::{CoinActivity, CoinBalance, CoinInfo, CoinModels, CurrentCoinBalance},
+    schema,
+};
+use aptos_rest_client::Transaction;
+use async_trait::async_trait;
+use diesel::{pg::upsert::excluded, ExpressionMethods};
+use field_count::FieldCount;
+use std::fmt::Debug;
+
+pub struct CoinTransactionProcessor {
+    connection_pool: PgDbPool,
+}
+
+impl CoinTransactionProcessor {
+    pub fn new(connection_pool: PgDbPool) -> Self {
+        Self { connection_pool }
+    }
+}
+
+impl Debug for CoinTransactionProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "CoinTransactionProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+fn insert_coin_balances(conn: &PgPoolConnection, coin_balances: &[CoinBalance]) {
+    let chunks = get_chunks(coin_balances.len(), CoinBalance::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::coin_balances::table)
+                .values(&coin_balances[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into coin_balances");
+    }
+}
+
+fn insert_coin_activities(conn: &PgPoolConnection, coin_activities: &[CoinActivity]) {
+    let chunks = get_chunks(coin_activities.len(), CoinActivity::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::coin_activities::table)
+                .values(&coin_activities[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into coin_activities");
+    }
+}
+
+/// Upserts the latest balances, skipping any that are older than what's already stored.
+/// This keeps the current balances correct when older versions are reprocessed, or processed
+/// concurrently by a parallel backfill.
+fn insert_current_coin_balances(conn: &PgPoolConnection, items_to_insert: &[CurrentCoinBalance]) {
+    use schema::current_coin_balances::dsl::*;
+
+    if items_to_insert.is_empty() {
+        return;
+    }
+    let chunks = get_chunks(items_to_insert.len(), CurrentCoinBalance::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_upsert_with_better_error(
+            conn,
+            diesel::insert_into(schema::current_coin_balances::table)
+                .values(&items_to_insert[start_ind..end_ind])
+                .on_conflict((owner_address, coin_type))
+                .do_update()
+                .set((
+                    amount.eq(excluded(amount)),
+                    last_transaction_version.eq(excluded(last_transaction_version)),
+                    last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+                    inserted_at.eq(excluded(inserted_at)),
+                )),
+            "excluded.last_transaction_version >= current_coin_balances.last_transaction_version",
+        )
+        .expect("Error inserting row into current_coin_balances");
+    }
+}
+
+/// Upserts the latest coin infos, skipping any that are older than what's already stored
+fn insert_coin_infos(conn: &PgPoolConnection, items_to_insert: &[CoinInfo]) {
+    use schema::coin_infos::dsl::*;
+
+    if items_to_insert.is_empty() {
+        return;
+    }
+    let chunks = get_chunks(items_to_insert.len(), CoinInfo::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_upsert_with_better_error(
+            conn,
+            diesel::insert_into(schema::coin_infos::table)
+                .values(&items_to_insert[start_ind..end_ind])
+                .on_conflict(coin_type)
+                .do_update()
+                // The creator never changes, and the creation version is only ever lowered below
+                .set((
+                    name.eq(excluded(name)),
+                    symbol.eq(excluded(symbol)),
+                    decimals.eq(excluded(decimals)),
+                    supply.eq(excluded(supply)),
+                    last_transaction_version.eq(excluded(last_transaction_version)),
+                    inserted_at.eq(excluded(inserted_at)),
+                )),
+            "excluded.last_transaction_version >= coin_infos.last_transaction_version",
+        )
+        .expect("Error inserting row into coin_infos");
+        // Batches can be processed out of order, e.g. during a parallel backfill, so a batch
+        // older than the row may still hold an earlier creation version.
+        execute_upsert_with_better_error(
+            conn,
+            diesel::insert_into(schema::coin_infos::table)
+                .values(&items_to_insert[start_ind..end_ind])
+                .on_conflict(coin_type)
+                .do_update()
+                .set(transaction_version_created.eq(excluded(transaction_version_created))),
+            "excluded.transaction_version_created < coin_infos.transaction_version_created",
+        )
+        .expect("Error updating the creation version of coin_infos");
+    }
+}
+
+fn insert_to_db(
+    conn: &PgPoolConnection,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    coin_models: CoinModels,
+) -> Result<(), diesel::result::Error> {
+    aptos_logger::trace!(
+        "[{}] inserting versions {} to {}",
+        name,
+        start_version,
+        end_version
+    );
+    let CoinModels {
+        coin_balances,
+        current_coin_balances,
+        coin_activities,
+        coin_infos,
+    } = coin_models;
+    conn.build_transaction()
+        .read_write()
+        .run::<_, diesel::result::Error, _>(|| {
+            insert_coin_balances(conn, &coin_balances);
+            insert_coin_activities(conn, &coin_activities);
+            insert_current_coin_balances(conn, &current_coin_balances);
+            insert_coin_infos(conn, &coin_infos);
+            Ok(())
+        })
+}
+
+#[async_trait]
+impl TransactionProcessor for CoinTransactionProcessor {
+    fn name(&self) -> &'static str {
+        "coin_processor"
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+    ) -> Result<ProcessingResult, TransactionProcessingError> {
+        let coin_models = CoinModels::from_transactions(&transactions);
+
+        let conn = self.get_conn();
+        let tx_result = insert_to_db(&conn, self.name(), start_version, end_version, coin_models);
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::new(
+                self.name(),
+                start_version,
+                end_version,
+            )),
+            Err(err) => Err(TransactionProcessingError::TransactionCommitError((
+                anyhow::Error::from(err),
+                start_version,
+                end_version,
+                self.name(),
+            ))),
+        }
+    }
+
+    fn connection_pool(&self) -> &PgDbPool {
+        &self.connection_pool
+    }
+
+    fn supports_parallel_backfill(&self) -> bool {
+        true
+    }
+}
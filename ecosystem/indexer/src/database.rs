@@ -6,9 +6,10 @@
 use std::{cmp::min, sync::Arc};
 
 use diesel::{
-    pg::PgConnection,
+    pg::{Pg, PgConnection},
+    query_builder::{AstPass, QueryFragment, QueryId},
     r2d2::{ConnectionManager, PoolError, PooledConnection},
-    RunQueryDsl,
+    QueryResult, RunQueryDsl,
 };
 
 pub type PgPool = diesel::r2d2::Pool<ConnectionManager<PgConnection>>;
@@ -59,6 +60,58 @@ where
     res
 }
 
+/// An upsert with a `WHERE` clause on its `DO UPDATE`, which diesel 1.4 can't express.
+/// This is how current values are only overwritten by ones from a later transaction, atomically.
+struct UpsertWithWhereClause<T> {
+    query: T,
+    where_clause: &'static str,
+}
+
+impl<T: QueryFragment<Pg>> QueryFragment<Pg> for UpsertWithWhereClause<T> {
+    fn walk_ast(&self, mut out: AstPass<Pg>) -> QueryResult<()> {
+        self.query.walk_ast(out.reborrow())?;
+        out.push_sql(" WHERE ");
+        out.push_sql(self.where_clause);
+        Ok(())
+    }
+}
+
+impl<T> QueryId for UpsertWithWhereClause<T> {
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<T> RunQueryDsl<PgConnection> for UpsertWithWhereClause<T> {}
+
+/// Same as `execute_with_better_error`, for an `ON CONFLICT ... DO UPDATE` which should only update
+/// the rows matching `where_clause`, e.g.
+/// `excluded.last_transaction_version >= table.last_transaction_version`.
+/// The query must insert at least one row, otherwise diesel doesn't generate an upsert at all.
+pub fn execute_upsert_with_better_error<
+    T: diesel::Table + diesel::QuerySource,
+    U: diesel::query_builder::QueryFragment<diesel::pg::Pg>
+        + diesel::insertable::CanInsertInSingleQuery<diesel::pg::Pg>,
+>(
+    conn: &PgPoolConnection,
+    query: diesel::query_builder::InsertStatement<T, U>,
+    where_clause: &'static str,
+) -> diesel::QueryResult<usize>
+where
+    <T as diesel::QuerySource>::FromClause: diesel::query_builder::QueryFragment<diesel::pg::Pg>,
+{
+    let query = UpsertWithWhereClause {
+        query,
+        where_clause,
+    };
+    let debug = diesel::debug_query::<diesel::pg::Pg, _>(&query).to_string();
+    aptos_logger::debug!("Executing query: {:?}", debug);
+    let res = query.execute(conn);
+    if let Err(ref e) = res {
+        aptos_logger::warn!("Error running query: {:?}\n{}", e, debug);
+    }
+    res
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
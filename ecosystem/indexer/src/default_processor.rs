@@ -182,4 +182,8 @@ impl TransactionProcessor for DefaultTransactionProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn supports_parallel_backfill(&self) -> bool {
+        true
+    }
 }
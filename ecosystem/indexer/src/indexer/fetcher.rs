@@ -7,7 +7,7 @@ use aptos_rest_client::{retriable, retriable_with_404, Client as RestClient, Sta
 use futures::channel::mpsc;
 use futures::{SinkExt, StreamExt};
 use serde_json::Value;
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 use tokio::task::JoinHandle;
 use url::Url;
 
@@ -205,6 +205,12 @@ impl TransactionFetcherTrait for TransactionFetcher {
         }
     }
 
+    fn range_fetcher(&self) -> Arc<dyn TransactionRangeFetcherTrait> {
+        Arc::new(RestRangeFetcher {
+            client: self.client.clone(),
+        })
+    }
+
     async fn fetch_ledger_info(&mut self) -> State {
         let res = RestClient::try_until_ok(Some(MAX_RETRY_TIME), None, retriable, || {
             self.client.get_ledger_information()
@@ -242,6 +248,43 @@ impl TransactionFetcherTrait for TransactionFetcher {
     }
 }
 
+/// Fetches ranges of versions from the REST API. Every backfill worker gets its own copy.
+pub struct RestRangeFetcher {
+    client: RestClient,
+}
+
+#[async_trait::async_trait]
+impl TransactionRangeFetcherTrait for RestRangeFetcher {
+    /// fetches up to `limit` versions starting at `start_version`; this is used for backfills
+    /// In the event it can't, it will keep retrying every RETRY_TIME_MILLIS ms
+    async fn fetch_range(&self, start_version: u64, limit: u16) -> Vec<Transaction> {
+        loop {
+            let res = RestClient::try_until_ok(None, None, retriable_with_404, || {
+                self.client
+                    .get_transactions(Some(start_version), Some(limit))
+            })
+            .await;
+            match res {
+                Ok(response) => {
+                    FETCHED_TRANSACTION.inc();
+                    return remove_null_bytes_from_txns(response.into_inner());
+                }
+                Err(err) => {
+                    UNABLE_TO_FETCH_TRANSACTION.inc();
+                    aptos_logger::error!(
+                        "Could not fetch {} versions starting at {}, will retry in {}ms. Err: {:?}",
+                        limit,
+                        start_version,
+                        RETRY_TIME_MILLIS,
+                        err
+                    );
+                    tokio::time::sleep(STARTING_RETRY_TIME).await;
+                }
+            };
+        }
+    }
+}
+
 pub fn string_null_byte_replacement(value: &mut str) -> String {
     value.replace('\u{0000}', "").replace("\\u0000", "")
 }
@@ -285,9 +328,19 @@ pub trait TransactionFetcherTrait: Send + Sync {
 
     async fn fetch_version(&self, version: u64) -> Transaction;
 
+    /// Returns a fetcher for arbitrary ranges of versions, which can be used without holding the
+    /// lock on this fetcher, e.g. by concurrent backfill workers
+    fn range_fetcher(&self) -> Arc<dyn TransactionRangeFetcherTrait>;
+
     async fn fetch_ledger_info(&mut self) -> State;
 
     async fn set_version(&mut self, version: u64);
 
     async fn start(&mut self);
 }
+
+/// Fetches arbitrary ranges of versions, e.g. for backfills and repairs
+#[async_trait::async_trait]
+pub trait TransactionRangeFetcherTrait: Send + Sync {
+    async fn fetch_range(&self, start_version: u64, limit: u16) -> Vec<Transaction>;
+}
@@ -4,6 +4,7 @@
 pub mod errors;
 pub mod fetcher;
 pub mod metadata_fetcher;
+pub mod node_fetcher;
 pub mod processing_result;
 pub mod tailer;
 pub mod transaction_processor;
@@ -0,0 +1,317 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fetches transactions straight from the node's storage, for when the indexer runs inside the
+//! node. This skips the REST API entirely: transactions are read from the `DbReader` and converted
+//! to API `Transaction`s in-process.
+
+use crate::{
+    counters::{FETCHED_TRANSACTION, UNABLE_TO_FETCH_TRANSACTION},
+    indexer::fetcher::{
+        remove_null_bytes_from_txns, TransactionFetcherTrait, TransactionRangeFetcherTrait,
+    },
+};
+use aptos_api::context::Context;
+use aptos_logger::prelude::*;
+use aptos_rest_client::{aptos_api_types::AsConverter, State, Transaction};
+use futures::{channel::mpsc, SinkExt, StreamExt};
+use std::{sync::Arc, time::Duration};
+use tokio::task::JoinHandle;
+
+const RETRY_TIME_MILLIS: u64 = 300;
+const TRANSACTION_FETCH_BATCH_SIZE: u16 = 500;
+const TRANSACTION_CHANNEL_SIZE: usize = 35;
+const MAX_TASKS: usize = 10;
+static RETRY_TIME: Duration = Duration::from_millis(RETRY_TIME_MILLIS);
+
+/// Reads up to `limit` transactions starting at `start_version` from storage, and converts them
+/// to their API representation
+fn fetch_transactions(
+    context: &Context,
+    start_version: u64,
+    limit: u16,
+) -> anyhow::Result<Vec<Transaction>> {
+    let ledger_version = context.get_latest_ledger_info_wrapped()?.version();
+    anyhow::ensure!(
+        start_version <= ledger_version,
+        "Version {} is ahead of the latest ledger version {}",
+        start_version,
+        ledger_version
+    );
+    let limit = std::cmp::min(limit as u64, ledger_version - start_version + 1) as u16;
+    let data = context.get_transactions(start_version, limit, ledger_version)?;
+
+    let mut timestamp = context.db.get_block_timestamp(start_version)?;
+    let resolver = context.move_resolver()?;
+    let converter = resolver.as_converter(context.db.clone());
+    let txns = data
+        .into_iter()
+        .map(|txn| {
+            // Update the timestamp if the next block occurs
+            if let aptos_types::transaction::Transaction::BlockMetadata(ref block_metadata) =
+                txn.transaction
+            {
+                timestamp = block_metadata.timestamp_usecs();
+            }
+            converter.try_into_onchain_transaction(timestamp, txn)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(remove_null_bytes_from_txns(txns))
+}
+
+/// Same as `fetch_transactions`, but keeps retrying every RETRY_TIME_MILLIS ms until it succeeds
+async fn fetch_transactions_until_ok(
+    context: Arc<Context>,
+    start_version: u64,
+    limit: u16,
+) -> Vec<Transaction> {
+    loop {
+        let context2 = context.clone();
+        let res = tokio::task::spawn_blocking(move || {
+            fetch_transactions(&context2, start_version, limit)
+        })
+        .await
+        .expect("Fetching transactions from storage panicked");
+        match res {
+            Ok(txns) => {
+                FETCHED_TRANSACTION.inc();
+                return txns;
+            }
+            Err(err) => {
+                UNABLE_TO_FETCH_TRANSACTION.inc();
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(10)),
+                    error!(
+                        "Could not fetch {} versions starting at {} from storage, will retry in {}ms. Err: {:?}",
+                        limit,
+                        start_version,
+                        RETRY_TIME_MILLIS,
+                        err
+                    )
+                );
+                tokio::time::sleep(RETRY_TIME).await;
+            }
+        }
+    }
+}
+
+pub struct NodeFetcher {
+    context: Arc<Context>,
+    current_version: u64,
+    transactions_sender: mpsc::Sender<Vec<Transaction>>,
+}
+
+impl NodeFetcher {
+    pub fn new(
+        context: Arc<Context>,
+        current_version: u64,
+        transactions_sender: mpsc::Sender<Vec<Transaction>>,
+    ) -> Self {
+        Self {
+            context,
+            current_version,
+            transactions_sender,
+        }
+    }
+
+    pub async fn run(&mut self) {
+        loop {
+            let ledger_version = match self.context.get_latest_ledger_info_wrapped() {
+                Ok(ledger_info) => ledger_info.version(),
+                Err(err) => {
+                    error!("Failed to get the latest ledger info. Err: {:?}", err);
+                    tokio::time::sleep(RETRY_TIME).await;
+                    continue;
+                }
+            };
+            if self.current_version > ledger_version {
+                tokio::time::sleep(RETRY_TIME).await;
+                continue;
+            }
+
+            let num_missing = ledger_version - self.current_version + 1;
+            let num_batches = std::cmp::min(
+                (num_missing as f64 / TRANSACTION_FETCH_BATCH_SIZE as f64).ceil() as u64,
+                MAX_TASKS as u64,
+            );
+            let mut futures = vec![];
+            for i in 0..num_batches {
+                futures.push(fetch_transactions_until_ok(
+                    self.context.clone(),
+                    self.current_version + i * TRANSACTION_FETCH_BATCH_SIZE as u64,
+                    TRANSACTION_FETCH_BATCH_SIZE,
+                ));
+            }
+            // `join_all` keeps the order of the futures, so the batches are already sorted
+            let res: Vec<Vec<Transaction>> = futures::future::join_all(futures).await;
+
+            for batch in res {
+                if let Some(last_version) = batch.last().and_then(|txn| txn.version()) {
+                    self.current_version = last_version + 1;
+                    self.transactions_sender
+                        .send(batch)
+                        .await
+                        .expect("Should be able to send transaction on channel");
+                }
+            }
+        }
+    }
+}
+
+/// A `TransactionFetcherTrait` which reads transactions from the node's storage
+pub struct NodeTransactionFetcher {
+    starting_version: u64,
+    context: Arc<Context>,
+    fetcher_handle: Option<JoinHandle<()>>,
+    transactions_sender: Option<mpsc::Sender<Vec<Transaction>>>,
+    transaction_receiver: mpsc::Receiver<Vec<Transaction>>,
+}
+
+impl NodeTransactionFetcher {
+    pub fn new(context: Arc<Context>, starting_version: Option<u64>) -> Self {
+        let (transactions_sender, transaction_receiver) =
+            mpsc::channel::<Vec<Transaction>>(TRANSACTION_CHANNEL_SIZE);
+
+        Self {
+            starting_version: starting_version.unwrap_or(0),
+            context,
+            fetcher_handle: None,
+            transactions_sender: Some(transactions_sender),
+            transaction_receiver,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TransactionFetcherTrait for NodeTransactionFetcher {
+    /// Fetches the next batch based on its internal version counter
+    async fn fetch_next_batch(&mut self) -> Vec<Transaction> {
+        self.transaction_receiver
+            .next()
+            .await
+            .expect("No transactions, producer of batches died")
+    }
+
+    /// fetches one version; this used for error checking/repair/etc
+    async fn fetch_version(&self, version: u64) -> Transaction {
+        fetch_transactions_until_ok(self.context.clone(), version, 1)
+            .await
+            .pop()
+            .expect("Fetching a single version should return one transaction")
+    }
+
+    fn range_fetcher(&self) -> Arc<dyn TransactionRangeFetcherTrait> {
+        Arc::new(NodeRangeFetcher {
+            context: self.context.clone(),
+        })
+    }
+
+    async fn fetch_ledger_info(&mut self) -> State {
+        let ledger_info = self
+            .context
+            .get_latest_ledger_info_wrapped()
+            .expect("Failed to get the latest ledger info from storage");
+        State {
+            chain_id: ledger_info.chain_id,
+            epoch: ledger_info.epoch.0,
+            version: ledger_info.ledger_version.0,
+            timestamp_usecs: ledger_info.ledger_timestamp.0,
+            oldest_ledger_version: ledger_info.oldest_ledger_version.0,
+            oldest_block_height: ledger_info.oldest_block_height.0,
+            block_height: ledger_info.block_height.0,
+        }
+    }
+
+    async fn set_version(&mut self, version: u64) {
+        if self.fetcher_handle.is_some() {
+            panic!("NodeTransactionFetcher already started!");
+        }
+        self.starting_version = version;
+    }
+
+    async fn start(&mut self) {
+        if self.fetcher_handle.is_some() {
+            panic!("NodeTransactionFetcher already started!");
+        }
+        let context = self.context.clone();
+        let transactions_sender = self.transactions_sender.take().unwrap();
+        let starting_version = self.starting_version;
+        let fetcher_handle = tokio::spawn(async move {
+            let mut fetcher = NodeFetcher::new(context, starting_version, transactions_sender);
+            fetcher.run().await;
+        });
+        self.fetcher_handle = Some(fetcher_handle);
+    }
+}
+
+/// Fetches ranges of versions from the node's storage. Every backfill worker gets its own copy.
+pub struct NodeRangeFetcher {
+    context: Arc<Context>,
+}
+
+#[async_trait::async_trait]
+impl TransactionRangeFetcherTrait for NodeRangeFetcher {
+    /// fetches up to `limit` versions starting at `start_version`; this is used for backfills
+    async fn fetch_range(&self, start_version: u64, limit: u16) -> Vec<Transaction> {
+        fetch_transactions_until_ok(self.context.clone(), start_version, limit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_api_test_context::{new_test_context, TestContext};
+    use aptos_types::chain_id::ChainId;
+
+    /// Commits a few blocks on top of genesis
+    async fn new_test_context_with_blocks(test_name: &str) -> TestContext {
+        let mut test_context = new_test_context(test_name.to_string(), false);
+        for _ in 0..3 {
+            let account = test_context.gen_account();
+            let txn = test_context.create_user_account(&account);
+            test_context.commit_block(&[txn]).await;
+        }
+        test_context
+    }
+
+    fn versions(txns: &[Transaction]) -> Vec<u64> {
+        txns.iter().map(|txn| txn.version().unwrap()).collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_fetch_range_is_clamped_to_ledger_version() {
+        let test_context = new_test_context_with_blocks("test_fetch_range").await;
+        let ledger_version = test_context.get_latest_ledger_info().version();
+        let fetcher = NodeTransactionFetcher::new(Arc::new(test_context.context.clone()), None);
+        let range_fetcher = fetcher.range_fetcher();
+
+        let txns = range_fetcher.fetch_range(0, 1000).await;
+        assert_eq!(versions(&txns), (0..=ledger_version).collect::<Vec<_>>());
+
+        let txns = range_fetcher.fetch_range(ledger_version - 1, 1000).await;
+        assert_eq!(versions(&txns), vec![ledger_version - 1, ledger_version]);
+
+        let txns = range_fetcher.fetch_range(2, 3).await;
+        assert_eq!(versions(&txns), vec![2, 3, 4]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_fetch_next_batch_in_order() {
+        let test_context = new_test_context_with_blocks("test_fetch_next_batch").await;
+        let ledger_version = test_context.get_latest_ledger_info().version();
+        let mut fetcher = NodeTransactionFetcher::new(Arc::new(test_context.context.clone()), None);
+
+        let state = fetcher.fetch_ledger_info().await;
+        assert_eq!(state.chain_id, ChainId::test().id());
+        assert_eq!(state.version, ledger_version);
+        assert_eq!(fetcher.fetch_version(3).await.version(), Some(3),);
+
+        fetcher.set_version(1).await;
+        fetcher.start().await;
+        let mut fetched = vec![];
+        while fetched.last() != Some(&ledger_version) {
+            fetched.extend(versions(&fetcher.fetch_next_batch().await));
+        }
+        assert_eq!(fetched, (1..=ledger_version).collect::<Vec<_>>());
+    }
+}
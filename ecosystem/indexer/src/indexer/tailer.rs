@@ -1,10 +1,11 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
+    counters::PROCESSOR_MISSING_VERSIONS,
     database::{execute_with_better_error, PgDbPool},
     indexer::{
         errors::TransactionProcessingError,
-        fetcher::{TransactionFetcher, TransactionFetcherTrait},
+        fetcher::{TransactionFetcher, TransactionFetcherTrait, TransactionRangeFetcherTrait},
         processing_result::ProcessingResult,
         transaction_processor::TransactionProcessor,
     },
@@ -21,6 +22,9 @@ use url::{ParseError, Url};
 
 diesel_migrations::embed_migrations!();
 
+/// How many versions to fetch and process at a time when repairing errored versions and gaps
+const REPAIR_BATCH_SIZE: u16 = 100;
+
 #[derive(Clone)]
 pub struct Tailer {
     pub transaction_fetcher: Arc<Mutex<dyn TransactionFetcherTrait>>,
@@ -39,6 +43,19 @@ impl Tailer {
         })
     }
 
+    /// Creates a `Tailer` which gets its transactions from `transaction_fetcher`, e.g. straight
+    /// from storage when running inside the node
+    pub fn new_with_fetcher(
+        transaction_fetcher: Arc<Mutex<dyn TransactionFetcherTrait>>,
+        connection_pool: PgDbPool,
+    ) -> Tailer {
+        Self {
+            transaction_fetcher,
+            processors: vec![],
+            connection_pool,
+        }
+    }
+
     pub fn run_migrations(&self) {
         info!("Running migrations...");
         embedded_migrations::run_with_output(
@@ -98,47 +115,217 @@ impl Tailer {
         self.processors.push(processor);
     }
 
-    /// For all versions which have an `success=false` in the `processor_status` table, re-run them
-    /// TODO: also handle gaps in sequence numbers (pg query for this is super easy)
+    /// For all versions which have an `success=false` in the `processor_status` table, or which
+    /// have no status at all (gaps), re-run them
     pub async fn handle_previous_errors(&self) {
-        info!("Checking for previously errored versions...");
+        info!("Checking for previously errored versions and gaps...");
         let mut tasks = vec![];
         for processor in &self.processors {
             let processor2 = processor.clone();
             let self2 = self.clone();
             let task = tokio::task::spawn(async move {
-                let errored_versions = processor2.get_error_versions();
-                let err_count = errored_versions.len();
+                let mut errored_versions = processor2.get_error_versions();
+                errored_versions.sort_unstable();
+                let gaps = processor2.get_version_gaps();
+                let num_gap_versions: u64 = gaps.iter().map(|(start, end)| end - start + 1).sum();
+                PROCESSOR_MISSING_VERSIONS
+                    .with_label_values(&[processor2.name(), "error"])
+                    .set(errored_versions.len() as i64);
+                PROCESSOR_MISSING_VERSIONS
+                    .with_label_values(&[processor2.name(), "gap"])
+                    .set(num_gap_versions as i64);
                 info!(
-                    "Found {} previously errored versions for {}",
-                    err_count,
+                    "Found {} previously errored versions and {} gaps ({} versions) for {}",
+                    errored_versions.len(),
+                    gaps.len(),
+                    num_gap_versions,
                     processor2.name(),
                 );
-                if err_count == 0 {
+                for (start_version, end_version) in &gaps {
+                    info!(
+                        "Gap for {} from version {} to {}",
+                        processor2.name(),
+                        start_version,
+                        end_version
+                    );
+                }
+
+                let mut ranges = versions_to_ranges(&errored_versions);
+                ranges.extend(gaps);
+                if ranges.is_empty() {
                     return;
                 }
-                let mut fixed = 0;
-                for version in errored_versions {
-                    let txn = self2.get_txn(version).await;
-                    if processor2
-                        .process_transactions_with_status(vec![txn])
-                        .await
-                        .is_ok()
-                    {
-                        fixed += 1;
-                    };
+                let num_ranges = ranges.len();
+                let mut failed_batches = 0;
+                for (start_version, end_version) in ranges {
+                    failed_batches += self2
+                        .process_range(
+                            &[processor2.clone()],
+                            start_version,
+                            end_version,
+                            REPAIR_BATCH_SIZE,
+                        )
+                        .await;
                 }
                 info!(
-                    "Fixed {}/{} previously errored versions for {}",
-                    fixed,
-                    err_count,
+                    "Re-ran {} ranges of errored versions and gaps for {}, {} batches failed",
+                    num_ranges,
                     processor2.name(),
+                    failed_batches,
                 );
             });
             tasks.push(task);
         }
         await_tasks(tasks).await;
-        info!("Fixing previously errored versions complete!");
+        info!("Fixing previously errored versions and gaps complete!");
+    }
+
+    /// Processes every version in `[start_version, end_version]` with `num_workers` workers, each
+    /// working on its own disjoint range concurrently. If `processor_name` is set only that
+    /// processor is run, which allows re-running a single processor over an arbitrary range.
+    ///
+    /// Only processors which support parallel backfills are split across workers. The others
+    /// process the whole range in order in a single worker, and refuse to backfill versions they
+    /// have already processed since they would apply them twice.
+    ///
+    /// Progress is recorded in the `processor_statuses` table after every batch, so an interrupted
+    /// backfill shows up as gaps which `handle_previous_errors` will resume from.
+    /// Returns the number of batches which failed.
+    pub async fn backfill(
+        &self,
+        processor_name: Option<&str>,
+        start_version: u64,
+        end_version: u64,
+        batch_size: u16,
+        num_workers: usize,
+    ) -> anyhow::Result<usize> {
+        ensure!(
+            start_version <= end_version,
+            "Start version {} must not be after end version {}",
+            start_version,
+            end_version
+        );
+        ensure!(batch_size > 0, "Batch size must be greater than 0");
+        ensure!(num_workers > 0, "Number of workers must be greater than 0");
+        let (parallel_processors, sequential_processors): (
+            Vec<Arc<dyn TransactionProcessor>>,
+            Vec<Arc<dyn TransactionProcessor>>,
+        ) = self
+            .processors
+            .iter()
+            .filter(|processor| processor_name.map_or(true, |name| processor.name() == name))
+            .cloned()
+            .partition(|processor| processor.supports_parallel_backfill());
+        ensure!(
+            !parallel_processors.is_empty() || !sequential_processors.is_empty(),
+            "No processor named {} was added to the indexer",
+            processor_name.unwrap_or_default()
+        );
+        for processor in &sequential_processors {
+            let num_processed_versions =
+                processor.get_num_processed_versions(start_version, end_version);
+            ensure!(
+                num_processed_versions == 0,
+                "{} already processed {} versions between {} and {}, and can't process them again",
+                processor.name(),
+                num_processed_versions,
+                start_version,
+                end_version
+            );
+        }
+
+        info!(
+            "Backfilling versions {} to {} with {} workers",
+            start_version, end_version, num_workers
+        );
+        let mut tasks = vec![];
+        if !parallel_processors.is_empty() {
+            for (worker_start, worker_end) in split_range(start_version, end_version, num_workers) {
+                let self2 = self.clone();
+                let processors2 = parallel_processors.clone();
+                let task = tokio::task::spawn(async move {
+                    self2
+                        .process_range(&processors2, worker_start, worker_end, batch_size)
+                        .await
+                });
+                tasks.push(task);
+            }
+        }
+        if !sequential_processors.is_empty() {
+            info!(
+                "Backfilling {:?} in a single worker, as they must process versions in order",
+                sequential_processors
+                    .iter()
+                    .map(|processor| processor.name())
+                    .collect::<Vec<_>>()
+            );
+            let self2 = self.clone();
+            let task = tokio::task::spawn(async move {
+                self2
+                    .process_range(
+                        &sequential_processors,
+                        start_version,
+                        end_version,
+                        batch_size,
+                    )
+                    .await
+            });
+            tasks.push(task);
+        }
+        let failed_batches = await_tasks(tasks).await.into_iter().sum();
+        info!(
+            "Backfill of versions {} to {} complete, {} batches failed",
+            start_version, end_version, failed_batches
+        );
+        Ok(failed_batches)
+    }
+
+    /// Runs `processors` over every version in `[start_version, end_version]`, `batch_size`
+    /// versions at a time. Returns the number of batches which failed for any of the processors.
+    async fn process_range(
+        &self,
+        processors: &[Arc<dyn TransactionProcessor>],
+        start_version: u64,
+        end_version: u64,
+        batch_size: u16,
+    ) -> usize {
+        // Only hold the lock long enough to get a range fetcher, so that concurrent workers
+        // fetch concurrently too
+        let range_fetcher = self.transaction_fetcher.lock().await.range_fetcher();
+        let mut failed_batches = 0;
+        let mut version = start_version;
+        while version <= end_version {
+            let limit = std::cmp::min(batch_size as u64, end_version - version + 1) as u16;
+            let txns = range_fetcher.fetch_range(version, limit).await;
+            let last_version = match txns.last().and_then(|txn| txn.version()) {
+                Some(last_version) => last_version,
+                None => {
+                    aptos_logger::warn!(
+                        "No transactions found at version {}, stopping before version {}",
+                        version,
+                        end_version
+                    );
+                    break;
+                }
+            };
+
+            let mut tasks = vec![];
+            for processor in processors {
+                let processor2 = processor.clone();
+                let txns2 = txns.clone();
+                let task = tokio::task::spawn(async move {
+                    processor2.process_transactions_with_status(txns2).await
+                });
+                tasks.push(task);
+            }
+            failed_batches += await_tasks(tasks)
+                .await
+                .iter()
+                .filter(|result| result.is_err())
+                .count();
+            version = last_version + 1;
+        }
+        failed_batches
     }
 
     /// Sets the version of the fetcher to the lowest version among all processors
@@ -215,6 +402,38 @@ impl Tailer {
         (num_txns, results)
     }
 
+    /// Starts the fetcher and processes `batch_size` versions at a time forever, logging the
+    /// overall progress every `emit_every` versions (or never if it's 0)
+    pub async fn run(&self, starting_version: u64, batch_size: u8, emit_every: usize) {
+        info!("Starting fetcher...");
+        self.transaction_fetcher.lock().await.start().await;
+
+        let start = chrono::Utc::now().naive_utc();
+
+        info!("Indexing loop started!");
+        let mut version_processed: usize = starting_version as usize;
+        let mut total_processed: usize = 0;
+        let mut base: usize = 0;
+        loop {
+            let (num_res, _) = self.process_next_batch(batch_size).await;
+            total_processed += num_res as usize;
+            version_processed += num_res as usize;
+            if emit_every != 0 {
+                let new_base: usize = version_processed / emit_every;
+                if base != new_base {
+                    base = new_base;
+                    let num_millis =
+                        (chrono::Utc::now().naive_utc() - start).num_milliseconds() as f64 / 1000.0;
+                    let tps = (total_processed as f64 / num_millis) as u64;
+                    info!(
+                        "Indexer has processed version {}. Overall average TPS: {}",
+                        version_processed, tps
+                    );
+                }
+            }
+        }
+    }
+
     pub async fn process_transactions(
         &self,
         txns: Vec<Transaction>,
@@ -241,6 +460,33 @@ impl Tailer {
     }
 }
 
+/// Collapses sorted versions into ranges of consecutive versions, inclusive on both ends
+fn versions_to_ranges(versions: &[u64]) -> Vec<(u64, u64)> {
+    let mut ranges: Vec<(u64, u64)> = vec![];
+    for version in versions {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == *version => *end = *version,
+            _ => ranges.push((*version, *version)),
+        }
+    }
+    ranges
+}
+
+/// Splits `[start_version, end_version]` into at most `num_parts` disjoint ranges of roughly
+/// equal size, inclusive on both ends
+fn split_range(start_version: u64, end_version: u64, num_parts: usize) -> Vec<(u64, u64)> {
+    let num_versions = end_version - start_version + 1;
+    let part_size = (num_versions + num_parts as u64 - 1) / num_parts as u64;
+    let mut ranges = vec![];
+    let mut part_start = start_version;
+    while part_start <= end_version {
+        let part_end = std::cmp::min(part_start + part_size - 1, end_version);
+        ranges.push((part_start, part_end));
+        part_start = part_end + 1;
+    }
+    ranges
+}
+
 pub async fn await_tasks<T: Debug>(tasks: Vec<JoinHandle<T>>) -> Vec<T> {
     let mut results = vec![];
     for task in tasks {
@@ -261,7 +507,7 @@ mod test {
     use crate::{
         database::{new_db_pool, PgPoolConnection},
         default_processor::DefaultTransactionProcessor,
-        models::transactions::TransactionModel,
+        models::{processor_statuses::ProcessorStatusModel, transactions::TransactionModel},
         token_processor::TokenTransactionProcessor,
     };
     use aptos_rest_client::State;
@@ -292,6 +538,10 @@ mod test {
             unimplemented!();
         }
 
+        fn range_fetcher(&self) -> Arc<dyn TransactionRangeFetcherTrait> {
+            Arc::new(FakeRangeFetcher)
+        }
+
         async fn fetch_ledger_info(&mut self) -> State {
             State {
                 chain_id: self.chain_id,
@@ -315,8 +565,47 @@ mod test {
         }
     }
 
+    /// Serves state checkpoint transactions for versions up to `FAKE_LEDGER_VERSION`
+    struct FakeRangeFetcher;
+
+    const FAKE_LEDGER_VERSION: u64 = 99;
+
+    #[async_trait::async_trait]
+    impl TransactionRangeFetcherTrait for FakeRangeFetcher {
+        async fn fetch_range(&self, start_version: u64, limit: u16) -> Vec<Transaction> {
+            (start_version..start_version + limit as u64)
+                .take_while(|version| *version <= FAKE_LEDGER_VERSION)
+                .map(|version| {
+                    serde_json::from_value(json!({
+                        "type": "state_checkpoint_transaction",
+                        "version": version.to_string(),
+                        "hash": format!("0x{:064x}", version),
+                        "state_change_hash": format!("0x{:064x}", 0),
+                        "event_root_hash": format!("0x{:064x}", 0),
+                        "state_checkpoint_hash": format!("0x{:064x}", 0),
+                        "gas_used": "0",
+                        "success": true,
+                        "vm_status": "Executed successfully",
+                        "accumulator_root_hash": format!("0x{:064x}", 0),
+                        "changes": [],
+                        "timestamp": "1651789018411640",
+                    }))
+                    .unwrap()
+                })
+                .collect()
+        }
+    }
+
     pub fn wipe_database(conn: &PgPoolConnection) {
         for table in [
+            "coin_activities",
+            "coin_balances",
+            "coin_infos",
+            "current_coin_balances",
+            "governance_proposals",
+            "governance_votes",
+            "stake_pool_activities",
+            "stake_rewards",
             "metadatas",
             "token_activities",
             "token_datas",
@@ -849,4 +1138,110 @@ mod test {
         tailer.set_fetcher_version(4).await;
         assert!(tailer.check_or_update_chain_id().await.is_ok());
     }
+
+    #[test]
+    fn test_versions_to_ranges() {
+        assert_eq!(versions_to_ranges(&[]), Vec::<(u64, u64)>::new());
+        assert_eq!(
+            versions_to_ranges(&[1, 2, 3, 5, 7, 8]),
+            vec![(1, 3), (5, 5), (7, 8)]
+        );
+    }
+
+    #[test]
+    fn test_split_range() {
+        assert_eq!(split_range(0, 9, 3), vec![(0, 3), (4, 7), (8, 9)]);
+        assert_eq!(split_range(5, 6, 4), vec![(5, 5), (6, 6)]);
+        assert_eq!(split_range(7, 7, 1), vec![(7, 7)]);
+    }
+
+    #[tokio::test]
+    async fn test_version_gaps() {
+        if crate::should_skip_pg_tests() {
+            return;
+        }
+        let (conn_pool, _tailer) = setup_indexer().unwrap();
+        let processor = DefaultTransactionProcessor::new(conn_pool);
+        for (start_version, end_version) in [(0, 2), (5, 5), (9, 10)] {
+            processor.apply_processor_status(&ProcessorStatusModel::from_versions(
+                processor.name(),
+                start_version,
+                end_version,
+                true,
+                None,
+            ));
+        }
+        assert_eq!(processor.get_version_gaps(), vec![(3, 4), (6, 8)]);
+        assert_eq!(processor.get_max_version(), Some(10));
+    }
+
+    #[tokio::test]
+    async fn test_backfill_and_resume() {
+        if crate::should_skip_pg_tests() {
+            return;
+        }
+        let (conn_pool, tailer) = setup_indexer().unwrap();
+        let default_processor = DefaultTransactionProcessor::new(conn_pool.clone());
+        let token_processor = TokenTransactionProcessor::new(conn_pool.clone(), false);
+
+        // The default processor only appends rows, so it's split across workers
+        assert_eq!(
+            tailer
+                .backfill(Some(default_processor.name()), 0, 49, 10, 4)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(default_processor.get_num_processed_versions(0, 49), 50);
+        assert!(default_processor.get_version_gaps().is_empty());
+
+        // An interrupted backfill leaves gaps behind, which are resumed from
+        tailer
+            .backfill(Some(default_processor.name()), 60, 69, 10, 2)
+            .await
+            .unwrap();
+        tailer
+            .backfill(Some(default_processor.name()), 80, 99, 10, 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            default_processor.get_version_gaps(),
+            vec![(50, 59), (70, 79)]
+        );
+        tailer.handle_previous_errors().await;
+        assert!(default_processor.get_version_gaps().is_empty());
+        assert_eq!(default_processor.get_num_processed_versions(0, 99), 100);
+        assert_eq!(
+            default_processor.get_max_version(),
+            Some(FAKE_LEDGER_VERSION)
+        );
+        let num_txns: i64 = crate::schema::transactions::table
+            .count()
+            .get_result(&conn_pool.get().unwrap())
+            .unwrap();
+        assert_eq!(num_txns, 100);
+
+        // Backfilling the same versions again is fine for the default processor
+        assert_eq!(
+            tailer
+                .backfill(Some(default_processor.name()), 0, 99, 10, 4)
+                .await
+                .unwrap(),
+            0
+        );
+
+        // The token processor runs in order, once per version
+        assert_eq!(
+            tailer
+                .backfill(Some(token_processor.name()), 0, 9, 5, 4)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(token_processor.get_num_processed_versions(0, 9), 10);
+        assert!(tailer
+            .backfill(Some(token_processor.name()), 5, 14, 5, 4)
+            .await
+            .is_err());
+    }
 }
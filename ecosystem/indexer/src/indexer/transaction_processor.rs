@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::database::get_chunks;
-use crate::util::bigdecimal_to_u64;
+use crate::util::{bigdecimal_to_u64, u64_to_bigdecimal};
 use crate::{
     counters::{
         GOT_CONNECTION, PROCESSOR_ERRORS, PROCESSOR_INVOCATIONS, PROCESSOR_SUCCESSES,
@@ -10,7 +10,7 @@ use crate::{
     },
     database::{execute_with_better_error, PgDbPool, PgPoolConnection},
     indexer::{errors::TransactionProcessingError, processing_result::ProcessingResult},
-    models::processor_statuses::ProcessorStatusModel,
+    models::processor_statuses::{ProcessorStatusModel, VersionGap},
     schema,
 };
 use aptos_rest_client::Transaction;
@@ -21,6 +21,18 @@ use field_count::FieldCount;
 use schema::processor_statuses::{self, dsl};
 use std::fmt::Debug;
 
+/// Finds every run of versions missing between two versions which have a status.
+/// Versions before the first or after the last status aren't considered gaps.
+const VERSION_GAPS_QUERY: &str = "
+    SELECT version + 1 AS gap_start, next_version - 1 AS gap_end
+    FROM (
+        SELECT version, LEAD(version) OVER (ORDER BY version) AS next_version
+        FROM processor_statuses
+        WHERE name = $1
+    ) AS statuses
+    WHERE next_version > version + 1
+    ORDER BY gap_start";
+
 /// The `TransactionProcessor` is used by an instance of a `Tailer` to process transactions
 #[async_trait]
 pub trait TransactionProcessor: Send + Sync + Debug {
@@ -41,6 +53,13 @@ pub trait TransactionProcessor: Send + Sync + Debug {
     /// This is used by the `get_conn()` helper below
     fn connection_pool(&self) -> &PgDbPool;
 
+    /// Whether disjoint ranges of versions can be processed concurrently, and processed again,
+    /// e.g. because the processor only appends rows keyed by version. Processors which apply
+    /// deltas to current values must process every version once and in order.
+    fn supports_parallel_backfill(&self) -> bool {
+        false
+    }
+
     //* Below are helper methods that don't need to be implemented *//
 
     /// Gets the connection.
@@ -183,6 +202,45 @@ pub trait TransactionProcessor: Send + Sync + Debug {
             .collect()
     }
 
+    /// Gets all ranges of versions, inclusive on both ends, which were never processed for this
+    /// `TransactionProcessor`, e.g. because the indexer stopped in the middle of a parallel backfill
+    fn get_version_gaps(&self) -> Vec<(u64, u64)> {
+        let conn = self.get_conn();
+
+        diesel::sql_query(VERSION_GAPS_QUERY)
+            .bind::<diesel::sql_types::Text, _>(self.name())
+            .load::<VersionGap>(&conn)
+            .expect("Error loading the version gaps query")
+            .iter()
+            .map(|gap| {
+                (
+                    bigdecimal_to_u64(&gap.gap_start),
+                    bigdecimal_to_u64(&gap.gap_end),
+                )
+            })
+            .collect()
+    }
+
+    /// Counts the versions in `[start_version, end_version]` which were already processed
+    /// successfully for this `TransactionProcessor`
+    fn get_num_processed_versions(&self, start_version: u64, end_version: u64) -> i64 {
+        let conn = self.get_conn();
+
+        dsl::processor_statuses
+            .filter(
+                dsl::name
+                    .eq(self.name().to_string())
+                    .and(dsl::success.eq(true))
+                    .and(dsl::version.between(
+                        u64_to_bigdecimal(start_version),
+                        u64_to_bigdecimal(end_version),
+                    )),
+            )
+            .count()
+            .get_result::<i64>(&conn)
+            .expect("Error loading the processed versions count query")
+    }
+
     /// Gets the highest version for this `TransactionProcessor` from the DB
     /// This is so we know where to resume from on restarts
     fn get_max_version(&self) -> Option<u64> {
@@ -12,8 +12,9 @@ use clap::Parser;
 use std::sync::Arc;
 
 use aptos_indexer::{
-    database::new_db_pool, default_processor::DefaultTransactionProcessor, indexer::tailer::Tailer,
-    token_processor::TokenTransactionProcessor,
+    coin_processor::CoinTransactionProcessor, database::new_db_pool,
+    default_processor::DefaultTransactionProcessor, indexer::tailer::Tailer,
+    stake_processor::StakeTransactionProcessor, token_processor::TokenTransactionProcessor,
 };
 
 #[derive(Debug, Parser)]
@@ -54,6 +55,28 @@ struct IndexerArgs {
     #[clap(long, default_value_t = 1000)]
     emit_every: usize,
 
+    /// If set, will process every version from `--backfill-start-version` up to and including this
+    /// version in parallel, and then exit instead of starting indexing loop.
+    /// Interrupted backfills are resumed on the next start, unless `--skip-previous-errors` is set.
+    #[clap(long)]
+    backfill_end_version: Option<u64>,
+
+    /// First version of the backfill
+    #[clap(long, default_value_t = 0)]
+    backfill_start_version: u64,
+
+    /// If set, only this processor is run by the backfill, ex: "coin_processor"
+    #[clap(long)]
+    backfill_processor: Option<String>,
+
+    /// How many workers to backfill with, each working on its own range of versions
+    #[clap(long, default_value_t = 4)]
+    backfill_workers: usize,
+
+    /// How many versions each backfill worker fetches and processes at a time
+    #[clap(long, default_value_t = 100)]
+    backfill_batch_size: u16,
+
     /// Turn on the indexer to collect token, ownership, collection and metadata and store them
     /// in the postgres DB tables.
     #[clap(long)]
@@ -62,6 +85,16 @@ struct IndexerArgs {
     /// turn on the token URI fetcher
     #[clap(long)]
     index_token_uri_data: bool,
+
+    /// Turn on the indexer to collect coin balances, coin activities and coin infos and store
+    /// them in the postgres DB tables.
+    #[clap(long)]
+    index_coin_data: bool,
+
+    /// Turn on the indexer to collect stake pool activities, rewards, and governance proposals
+    /// and votes and store them in the postgres DB tables.
+    #[clap(long)]
+    index_stake_data: bool,
 }
 
 #[tokio::main]
@@ -92,6 +125,14 @@ async fn main() -> std::io::Result<()> {
             TokenTransactionProcessor::new(conn_pool.clone(), args.index_token_uri_data);
         tailer.add_processor(Arc::new(token_transaction_processor));
     }
+    if args.index_coin_data {
+        let coin_transaction_processor = CoinTransactionProcessor::new(conn_pool.clone());
+        tailer.add_processor(Arc::new(coin_transaction_processor));
+    }
+    if args.index_stake_data {
+        let stake_transaction_processor = StakeTransactionProcessor::new(conn_pool.clone());
+        tailer.add_processor(Arc::new(stake_transaction_processor));
+    }
 
     let starting_version = match args.start_from_version {
         None => tailer.set_fetcher_to_lowest_processor_version().await,
@@ -102,37 +143,31 @@ async fn main() -> std::io::Result<()> {
         tailer.handle_previous_errors().await;
     }
 
+    if let Some(backfill_end_version) = args.backfill_end_version {
+        let failed_batches = tailer
+            .backfill(
+                args.backfill_processor.as_deref(),
+                args.backfill_start_version,
+                backfill_end_version,
+                args.backfill_batch_size,
+                args.backfill_workers,
+            )
+            .await
+            .expect("Failed to backfill");
+        info!(
+            "Backfill complete with {} failed batches, exiting!",
+            failed_batches
+        );
+        return Ok(());
+    }
+
     if args.dont_index {
         info!("All pre-index tasks complete, exiting!");
         return Ok(());
     }
 
-    info!("Starting fetcher...");
-    tailer.transaction_fetcher.lock().await.start().await;
-
-    let start = chrono::Utc::now().naive_utc();
-
-    info!("Indexing loop started!");
-    let mut version_processed: usize = starting_version as usize;
-    let mut total_processed: usize = 0;
-    let mut base: usize = 0;
-    loop {
-        let (num_res, _) = tailer.process_next_batch(args.batch_size).await;
-        total_processed += num_res as usize;
-        version_processed += num_res as usize;
-        if args.emit_every != 0 {
-            let new_base: usize = version_processed / args.emit_every;
-            if base != new_base {
-                base = new_base;
-                let num_millis =
-                    (chrono::Utc::now().naive_utc() - start).num_milliseconds() as f64 / 1000.0;
-                let tps = (total_processed as f64 / num_millis) as u64;
-                aptos_logger::info!(
-                    "Indexer has processed version {}. Overall average TPS: {}",
-                    version_processed,
-                    tps
-                );
-            }
-        }
-    }
+    tailer
+        .run(starting_version, args.batch_size, args.emit_every)
+        .await;
+    Ok(())
 }
@@ -0,0 +1,493 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use crate::{
+    models::transactions::parse_timestamp,
+    schema::{coin_activities, coin_balances, coin_infos, current_coin_balances},
+    util::u64_to_bigdecimal,
+};
+use aptos_rest_client::{
+    aptos_api_types::{
+        Address, Event as APIEvent, MoveStructTag, Transaction as APITransaction,
+        TransactionPayload, WriteResource, WriteSetChange as APIWriteSetChange,
+    },
+    types,
+};
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, str::FromStr};
+
+const COIN_ADDRESS: &str = "0x1";
+const COIN_MODULE: &str = "coin";
+const COIN_STORE_RESOURCE: &str = "CoinStore";
+const COIN_INFO_RESOURCE: &str = "CoinInfo";
+const DEPOSIT_EVENT_TYPE: &str = "0x1::coin::DepositEvent";
+const WITHDRAW_EVENT_TYPE: &str = "0x1::coin::WithdrawEvent";
+const APTOS_COIN_TYPE: &str = "0x1::aptos_coin::AptosCoin";
+const GAS_FEE_ACTIVITY_TYPE: &str = "0x1::aptos_coin::GasFeeEvent";
+/// Gas fees don't have an event, so this is used for their event creation and sequence numbers
+const GAS_FEE_EVENT_NUMBER: i64 = -1;
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[table_name = "coin_balances"]
+#[primary_key(transaction_version, owner_address, coin_type)]
+pub struct CoinBalance {
+    pub transaction_version: bigdecimal::BigDecimal,
+    pub owner_address: String,
+    pub coin_type: String,
+    pub amount: bigdecimal::BigDecimal,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[table_name = "current_coin_balances"]
+#[primary_key(owner_address, coin_type)]
+pub struct CurrentCoinBalance {
+    pub owner_address: String,
+    pub coin_type: String,
+    pub amount: bigdecimal::BigDecimal,
+    pub last_transaction_version: bigdecimal::BigDecimal,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[table_name = "coin_activities"]
+#[primary_key(
+    transaction_version,
+    event_account_address,
+    event_creation_number,
+    event_sequence_number
+)]
+pub struct CoinActivity {
+    pub transaction_version: bigdecimal::BigDecimal,
+    pub event_account_address: String,
+    pub event_creation_number: i64,
+    pub event_sequence_number: i64,
+    pub owner_address: String,
+    pub coin_type: String,
+    pub amount: bigdecimal::BigDecimal,
+    pub activity_type: String,
+    pub is_gas_fee: bool,
+    pub is_transaction_success: bool,
+    pub entry_function_id_str: Option<String>,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[table_name = "coin_infos"]
+#[primary_key(coin_type)]
+pub struct CoinInfo {
+    pub coin_type: String,
+    pub transaction_version_created: bigdecimal::BigDecimal,
+    pub creator_address: String,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: i32,
+    pub supply: Option<bigdecimal::BigDecimal>,
+    pub last_transaction_version: bigdecimal::BigDecimal,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+/// `0x1::coin::CoinStore<T>` as it's returned by the API
+#[derive(Deserialize, Debug, Clone)]
+struct CoinStoreResource {
+    coin: CoinResource,
+    deposit_events: EventHandleResource,
+    withdraw_events: EventHandleResource,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct CoinResource {
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    value: bigdecimal::BigDecimal,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct EventHandleResource {
+    guid: EventGuidResource,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct EventGuidResource {
+    id: EventGuidIdResource,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct EventGuidIdResource {
+    addr: Address,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    creation_num: u64,
+}
+
+/// `0x1::coin::CoinInfo<T>` as it's returned by the API
+#[derive(Deserialize, Debug, Clone)]
+struct CoinInfoResource {
+    name: String,
+    symbol: String,
+    decimals: i32,
+    supply: serde_json::Value,
+}
+
+impl CoinInfoResource {
+    /// The supply is an `Option<OptionalAggregator>`, and is only readable from the resource when
+    /// it's tracked as an integer.  Aggregator values are stored in a table instead.
+    fn supply(&self) -> Option<bigdecimal::BigDecimal> {
+        let value = self.supply["vec"][0]["integer"]["vec"][0]["value"].as_str()?;
+        BigDecimal::from_str(value).ok()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct CoinEventData {
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    amount: bigdecimal::BigDecimal,
+}
+
+/// Everything the coin processor extracts from a batch of transactions
+#[derive(Debug, Default)]
+pub struct CoinModels {
+    pub coin_balances: Vec<CoinBalance>,
+    pub current_coin_balances: Vec<CurrentCoinBalance>,
+    pub coin_activities: Vec<CoinActivity>,
+    pub coin_infos: Vec<CoinInfo>,
+}
+
+impl CoinModels {
+    pub fn from_transactions(transactions: &[APITransaction]) -> Self {
+        let mut models = CoinModels::default();
+        // Only the latest state within the batch is kept for the current tables
+        let mut current_coin_balances: HashMap<(String, String), CurrentCoinBalance> =
+            HashMap::new();
+        let mut coin_infos: HashMap<String, CoinInfo> = HashMap::new();
+
+        for transaction in transactions {
+            let (info, events, timestamp, maybe_user_request) = match transaction {
+                APITransaction::UserTransaction(txn) => (
+                    &txn.info,
+                    &txn.events,
+                    parse_timestamp(txn.timestamp, txn.info.version),
+                    Some(&txn.request),
+                ),
+                APITransaction::GenesisTransaction(txn) => (
+                    &txn.info,
+                    &txn.events,
+                    chrono::NaiveDateTime::from_timestamp(0, 0),
+                    None,
+                ),
+                APITransaction::BlockMetadataTransaction(txn) => (
+                    &txn.info,
+                    &txn.events,
+                    parse_timestamp(txn.timestamp, txn.info.version),
+                    None,
+                ),
+                _ => continue,
+            };
+            let version = u64_to_bigdecimal(info.version.0);
+            let entry_function_id_str = maybe_user_request.and_then(|request| {
+                if let TransactionPayload::EntryFunctionPayload(ref payload) = request.payload {
+                    Some(payload.function.to_string())
+                } else {
+                    None
+                }
+            });
+
+            // Event handles of each coin store, to find out the coin type of its events
+            let mut event_to_coin_store: HashMap<(Address, u64), (String, String)> = HashMap::new();
+            for change in &info.changes {
+                let (address, resource) = match change {
+                    APIWriteSetChange::WriteResource(WriteResource { address, data, .. }) => {
+                        (address, data)
+                    }
+                    _ => continue,
+                };
+                let owner_address = address.to_string();
+
+                if let Some(coin_type) = get_coin_type(&resource.typ, COIN_STORE_RESOURCE) {
+                    let coin_store = match parse_resource::<CoinStoreResource>(&resource.data) {
+                        Some(coin_store) => coin_store,
+                        None => continue,
+                    };
+                    for handle in [&coin_store.deposit_events, &coin_store.withdraw_events] {
+                        event_to_coin_store.insert(
+                            (handle.guid.id.addr, handle.guid.id.creation_num),
+                            (owner_address.clone(), coin_type.clone()),
+                        );
+                    }
+                    models.coin_balances.push(CoinBalance {
+                        transaction_version: version.clone(),
+                        owner_address: owner_address.clone(),
+                        coin_type: coin_type.clone(),
+                        amount: coin_store.coin.value.clone(),
+                        transaction_timestamp: timestamp,
+                        inserted_at: chrono::Utc::now().naive_utc(),
+                    });
+                    current_coin_balances.insert(
+                        (owner_address.clone(), coin_type.clone()),
+                        CurrentCoinBalance {
+                            owner_address,
+                            coin_type,
+                            amount: coin_store.coin.value,
+                            last_transaction_version: version.clone(),
+                            last_transaction_timestamp: timestamp,
+                            inserted_at: chrono::Utc::now().naive_utc(),
+                        },
+                    );
+                } else if let Some(coin_type) = get_coin_type(&resource.typ, COIN_INFO_RESOURCE) {
+                    let coin_info = match parse_resource::<CoinInfoResource>(&resource.data) {
+                        Some(coin_info) => coin_info,
+                        None => continue,
+                    };
+                    let transaction_version_created = coin_infos
+                        .get(&coin_type)
+                        .map(|existing| existing.transaction_version_created.clone())
+                        .unwrap_or_else(|| version.clone());
+                    coin_infos.insert(
+                        coin_type.clone(),
+                        CoinInfo {
+                            coin_type,
+                            transaction_version_created,
+                            creator_address: owner_address,
+                            supply: coin_info.supply(),
+                            name: coin_info.name,
+                            symbol: coin_info.symbol,
+                            decimals: coin_info.decimals,
+                            last_transaction_version: version.clone(),
+                            inserted_at: chrono::Utc::now().naive_utc(),
+                        },
+                    );
+                }
+            }
+
+            for event in events {
+                if let Some(activity) = CoinActivity::from_event(
+                    event,
+                    &event_to_coin_store,
+                    &version,
+                    info.success,
+                    &entry_function_id_str,
+                    timestamp,
+                ) {
+                    models.coin_activities.push(activity);
+                }
+            }
+
+            // Every user transaction pays for gas, even when it fails
+            if let Some(request) = maybe_user_request {
+                let sender = request.sender.to_string();
+                models.coin_activities.push(CoinActivity {
+                    transaction_version: version.clone(),
+                    event_account_address: sender.clone(),
+                    event_creation_number: GAS_FEE_EVENT_NUMBER,
+                    event_sequence_number: GAS_FEE_EVENT_NUMBER,
+                    owner_address: sender,
+                    coin_type: APTOS_COIN_TYPE.to_string(),
+                    amount: u64_to_bigdecimal(info.gas_used.0)
+                        * u64_to_bigdecimal(request.gas_unit_price.0),
+                    activity_type: GAS_FEE_ACTIVITY_TYPE.to_string(),
+                    is_gas_fee: true,
+                    is_transaction_success: info.success,
+                    entry_function_id_str: entry_function_id_str.clone(),
+                    transaction_timestamp: timestamp,
+                    inserted_at: chrono::Utc::now().naive_utc(),
+                });
+            }
+        }
+
+        models.current_coin_balances = current_coin_balances.into_values().collect();
+        models.coin_infos = coin_infos.into_values().collect();
+        models
+    }
+}
+
+impl CoinActivity {
+    fn from_event(
+        event: &APIEvent,
+        event_to_coin_store: &HashMap<(Address, u64), (String, String)>,
+        version: &bigdecimal::BigDecimal,
+        is_transaction_success: bool,
+        entry_function_id_str: &Option<String>,
+        timestamp: chrono::NaiveDateTime,
+    ) -> Option<Self> {
+        let event_type = event.typ.to_string();
+        if event_type != DEPOSIT_EVENT_TYPE && event_type != WITHDRAW_EVENT_TYPE {
+            return None;
+        }
+        let event_address = Address::from(event.key.0.get_creator_address());
+        let event_creation_number = event.key.0.get_creation_number();
+        let (owner_address, coin_type) =
+            event_to_coin_store.get(&(event_address, event_creation_number))?;
+        let data = serde_json::from_value::<CoinEventData>(event.data.clone()).ok()?;
+
+        Some(Self {
+            transaction_version: version.clone(),
+            event_account_address: event_address.to_string(),
+            event_creation_number: event_creation_number as i64,
+            event_sequence_number: event.sequence_number.0 as i64,
+            owner_address: owner_address.clone(),
+            coin_type: coin_type.clone(),
+            amount: data.amount,
+            activity_type: event_type,
+            is_gas_fee: false,
+            is_transaction_success,
+            entry_function_id_str: entry_function_id_str.clone(),
+            transaction_timestamp: timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+        })
+    }
+}
+
+/// Returns `T` if the resource is `0x1::coin::<resource_name><T>`
+fn get_coin_type(tag: &MoveStructTag, resource_name: &str) -> Option<String> {
+    if tag.address.to_string() == COIN_ADDRESS
+        && tag.module.0.as_str() == COIN_MODULE
+        && tag.name.0.as_str() == resource_name
+    {
+        tag.generic_type_params
+            .first()
+            .map(|coin_type| coin_type.to_string())
+    } else {
+        None
+    }
+}
+
+fn parse_resource<T: serde::de::DeserializeOwned>(data: &impl Serialize) -> Option<T> {
+    serde_json::to_value(data)
+        .ok()
+        .and_then(|value| serde_json::from_value(value).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_coin_models_from_transfer() {
+        let transaction: APITransaction = serde_json::from_value(json!({
+            "type": "user_transaction",
+            "version": "100",
+            "hash": "0x2b7c58ed8524d228f9d0543a82e2793d04e8871df322f976b0e7bb8c5ced4ff5",
+            "state_change_hash": "0x3ead9eb40582fbc7df5e02f72280931dc3e6f1aae45dc832966b4cd972dac4b8",
+            "event_root_hash": "0x2e481956dea9c59b6fc9f823fe5f4c45efce173e42c551c1fe073b5d76a65504",
+            "gas_used": "10",
+            "success": true,
+            "vm_status": "Executed successfully",
+            "accumulator_root_hash": "0xb0ad602f805eb20c398f0f29a3504a9ef38bcc52c9c451deb9ec4a2d18807b49",
+            "sender": "0xa",
+            "sequence_number": "0",
+            "max_gas_amount": "1000",
+            "gas_unit_price": "2",
+            "expiration_timestamp_secs": "1649395500",
+            "payload": {
+                "type": "entry_function_payload",
+                "function": "0x1::coin::transfer",
+                "type_arguments": ["0x1::aptos_coin::AptosCoin"],
+                "arguments": ["0xb", "5"]
+            },
+            "timestamp": "1649395495746947",
+            "events": [
+                {
+                    "key": "0x0300000000000000000000000000000000000000000000000000000000000000000000000000000a",
+                    "sequence_number": "0",
+                    "type": "0x1::coin::WithdrawEvent",
+                    "data": { "amount": "5" }
+                },
+                {
+                    "key": "0x0200000000000000000000000000000000000000000000000000000000000000000000000000000b",
+                    "sequence_number": "1",
+                    "type": "0x1::coin::DepositEvent",
+                    "data": { "amount": "5" }
+                }
+            ],
+            "changes": [
+                {
+                    "type": "write_resource",
+                    "address": "0xa",
+                    "state_key_hash": "0x1",
+                    "data": {
+                        "type": "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+                        "data": {
+                            "coin": { "value": "75" },
+                            "deposit_events": {
+                                "counter": "0",
+                                "guid": { "id": { "addr": "0xa", "creation_num": "2" } }
+                            },
+                            "frozen": false,
+                            "withdraw_events": {
+                                "counter": "1",
+                                "guid": { "id": { "addr": "0xa", "creation_num": "3" } }
+                            }
+                        }
+                    }
+                },
+                {
+                    "type": "write_resource",
+                    "address": "0xb",
+                    "state_key_hash": "0x2",
+                    "data": {
+                        "type": "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+                        "data": {
+                            "coin": { "value": "105" },
+                            "deposit_events": {
+                                "counter": "2",
+                                "guid": { "id": { "addr": "0xb", "creation_num": "2" } }
+                            },
+                            "frozen": false,
+                            "withdraw_events": {
+                                "counter": "0",
+                                "guid": { "id": { "addr": "0xb", "creation_num": "3" } }
+                            }
+                        }
+                    }
+                }
+            ]
+        }))
+        .unwrap();
+
+        let models = CoinModels::from_transactions(&[transaction]);
+
+        assert_eq!(models.coin_balances.len(), 2);
+        assert_eq!(models.current_coin_balances.len(), 2);
+        let sender_balance = models
+            .current_coin_balances
+            .iter()
+            .find(|balance| balance.owner_address == "0xa")
+            .unwrap();
+        assert_eq!(sender_balance.coin_type, APTOS_COIN_TYPE);
+        assert_eq!(sender_balance.amount, u64_to_bigdecimal(75));
+
+        assert_eq!(models.coin_activities.len(), 3);
+        let withdraw = models
+            .coin_activities
+            .iter()
+            .find(|activity| activity.activity_type == WITHDRAW_EVENT_TYPE)
+            .unwrap();
+        assert_eq!(withdraw.owner_address, "0xa");
+        assert_eq!(withdraw.amount, u64_to_bigdecimal(5));
+        let deposit = models
+            .coin_activities
+            .iter()
+            .find(|activity| activity.activity_type == DEPOSIT_EVENT_TYPE)
+            .unwrap();
+        assert_eq!(deposit.owner_address, "0xb");
+        assert_eq!(
+            deposit.entry_function_id_str.as_deref(),
+            Some("0x1::coin::transfer")
+        );
+        let gas_fee = models
+            .coin_activities
+            .iter()
+            .find(|activity| activity.is_gas_fee)
+            .unwrap();
+        assert_eq!(gas_fee.owner_address, "0xa");
+        assert_eq!(gas_fee.amount, u64_to_bigdecimal(20));
+    }
+}
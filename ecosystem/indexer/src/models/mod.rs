@@ -1,12 +1,14 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod coin;
 pub mod collection;
 pub mod events;
 pub mod ledger_info;
 pub mod metadata;
 pub mod ownership;
 pub mod processor_statuses;
+pub mod stake;
 pub mod token;
 pub mod token_property;
 pub mod transactions;
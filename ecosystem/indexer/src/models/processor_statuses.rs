@@ -49,12 +49,21 @@ impl ProcessorStatus {
         details: Option<String>,
     ) -> Vec<Self> {
         let mut status: Vec<Self> = vec![Self::new(name, start_version, success, details.clone())];
-        for version in start_version + 1..end_version {
+        for version in start_version + 1..=end_version {
             status.push(Self::new(name, version, success, details.clone()));
         }
         status
     }
 }
 
+/// A range of versions, inclusive on both ends, which have no status for a processor
+#[derive(Debug, QueryableByName)]
+pub struct VersionGap {
+    #[sql_type = "diesel::sql_types::Numeric"]
+    pub gap_start: bigdecimal::BigDecimal,
+    #[sql_type = "diesel::sql_types::Numeric"]
+    pub gap_end: bigdecimal::BigDecimal,
+}
+
 // Prevent conflicts with other things named `ProcessorStatus`
 pub type ProcessorStatusModel = ProcessorStatus;
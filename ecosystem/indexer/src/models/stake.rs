@@ -0,0 +1,454 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use crate::{
+    models::transactions::parse_timestamp,
+    schema::{governance_proposals, governance_votes, stake_pool_activities, stake_rewards},
+    util::u64_to_bigdecimal,
+};
+use aptos_rest_client::{
+    aptos_api_types::{Address, Event as APIEvent, Transaction as APITransaction},
+    types,
+};
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+const STAKE_EVENT_PREFIX: &str = "0x1::stake::";
+const DISTRIBUTE_REWARDS_EVENT_TYPE: &str = "0x1::stake::DistributeRewardsEvent";
+const CREATE_PROPOSAL_EVENT_TYPE: &str = "0x1::aptos_governance::CreateProposalEvent";
+const VOTE_EVENT_TYPE: &str = "0x1::aptos_governance::VoteEvent";
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[table_name = "stake_pool_activities"]
+#[primary_key(
+    transaction_version,
+    event_account_address,
+    event_creation_number,
+    event_sequence_number
+)]
+pub struct StakePoolActivity {
+    pub transaction_version: bigdecimal::BigDecimal,
+    pub event_account_address: String,
+    pub event_creation_number: i64,
+    pub event_sequence_number: i64,
+    pub pool_address: String,
+    pub activity_type: String,
+    pub amount: Option<bigdecimal::BigDecimal>,
+    pub old_operator_address: Option<String>,
+    pub new_operator_address: Option<String>,
+    pub old_locked_until_secs: Option<bigdecimal::BigDecimal>,
+    pub new_locked_until_secs: Option<bigdecimal::BigDecimal>,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[table_name = "stake_rewards"]
+#[primary_key(transaction_version, pool_address)]
+pub struct StakeReward {
+    pub transaction_version: bigdecimal::BigDecimal,
+    pub pool_address: String,
+    pub epoch: bigdecimal::BigDecimal,
+    pub rewards_amount: bigdecimal::BigDecimal,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[table_name = "governance_proposals"]
+#[primary_key(proposal_id)]
+pub struct GovernanceProposal {
+    pub proposal_id: bigdecimal::BigDecimal,
+    pub proposer_address: String,
+    pub stake_pool_address: String,
+    pub execution_hash: String,
+    pub proposal_metadata: serde_json::Value,
+    pub transaction_version: bigdecimal::BigDecimal,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, FieldCount, Identifiable, Insertable, Queryable, Serialize, Clone)]
+#[table_name = "governance_votes"]
+#[primary_key(transaction_version, proposal_id, stake_pool_address)]
+pub struct GovernanceVote {
+    pub transaction_version: bigdecimal::BigDecimal,
+    pub proposal_id: bigdecimal::BigDecimal,
+    pub voter_address: String,
+    pub stake_pool_address: String,
+    pub num_votes: bigdecimal::BigDecimal,
+    pub should_pass: bool,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+/// Every `0x1::stake` event has the pool address, this covers the ones which have nothing else
+/// worth indexing, e.g. `JoinValidatorSetEvent` or `RotateConsensusKeyEvent`
+#[derive(Deserialize, Debug, Clone)]
+struct StakePoolEventData {
+    pool_address: Address,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct AddStakeEventData {
+    pool_address: Address,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    amount_added: bigdecimal::BigDecimal,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ReactivateStakeEventData {
+    pool_address: Address,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    amount: bigdecimal::BigDecimal,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct DistributeRewardsEventData {
+    pool_address: Address,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    rewards_amount: bigdecimal::BigDecimal,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct UnlockStakeEventData {
+    pool_address: Address,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    amount_unlocked: bigdecimal::BigDecimal,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct WithdrawStakeEventData {
+    pool_address: Address,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    amount_withdrawn: bigdecimal::BigDecimal,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct SetOperatorEventData {
+    pool_address: Address,
+    old_operator: Address,
+    new_operator: Address,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct IncreaseLockupEventData {
+    pool_address: Address,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    old_locked_until_secs: bigdecimal::BigDecimal,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    new_locked_until_secs: bigdecimal::BigDecimal,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct CreateProposalEventData {
+    proposer: Address,
+    stake_pool: Address,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    proposal_id: bigdecimal::BigDecimal,
+    execution_hash: String,
+    proposal_metadata: serde_json::Value,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct VoteEventData {
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    proposal_id: bigdecimal::BigDecimal,
+    voter: Address,
+    stake_pool: Address,
+    #[serde(deserialize_with = "types::deserialize_from_string")]
+    num_votes: bigdecimal::BigDecimal,
+    should_pass: bool,
+}
+
+/// Everything the stake processor extracts from a batch of transactions
+#[derive(Debug, Default)]
+pub struct StakeModels {
+    pub stake_pool_activities: Vec<StakePoolActivity>,
+    pub stake_rewards: Vec<StakeReward>,
+    pub governance_proposals: Vec<GovernanceProposal>,
+    pub governance_votes: Vec<GovernanceVote>,
+}
+
+impl StakeModels {
+    pub fn from_transactions(transactions: &[APITransaction]) -> Self {
+        let mut models = StakeModels::default();
+
+        for transaction in transactions {
+            // Rewards are only distributed on epoch change, in the block prologue
+            let (version, events, timestamp, maybe_epoch) = match transaction {
+                APITransaction::UserTransaction(txn) => (
+                    txn.info.version.0,
+                    &txn.events,
+                    parse_timestamp(txn.timestamp, txn.info.version),
+                    None,
+                ),
+                APITransaction::GenesisTransaction(txn) => (
+                    txn.info.version.0,
+                    &txn.events,
+                    chrono::NaiveDateTime::from_timestamp(0, 0),
+                    None,
+                ),
+                APITransaction::BlockMetadataTransaction(txn) => (
+                    txn.info.version.0,
+                    &txn.events,
+                    parse_timestamp(txn.timestamp, txn.info.version),
+                    Some(txn.epoch.0),
+                ),
+                _ => continue,
+            };
+            let version = u64_to_bigdecimal(version);
+
+            for event in events {
+                let event_type = event.typ.to_string();
+                if event_type.starts_with(STAKE_EVENT_PREFIX) {
+                    if let Some(activity) =
+                        StakePoolActivity::from_event(event, event_type, &version, timestamp)
+                    {
+                        if let (Some(epoch), Some(amount)) = (maybe_epoch, &activity.amount) {
+                            if activity.activity_type == DISTRIBUTE_REWARDS_EVENT_TYPE {
+                                models.stake_rewards.push(StakeReward {
+                                    transaction_version: version.clone(),
+                                    pool_address: activity.pool_address.clone(),
+                                    epoch: u64_to_bigdecimal(epoch),
+                                    rewards_amount: amount.clone(),
+                                    transaction_timestamp: timestamp,
+                                    inserted_at: chrono::Utc::now().naive_utc(),
+                                });
+                            }
+                        }
+                        models.stake_pool_activities.push(activity);
+                    }
+                } else if event_type == CREATE_PROPOSAL_EVENT_TYPE {
+                    if let Ok(data) =
+                        serde_json::from_value::<CreateProposalEventData>(event.data.clone())
+                    {
+                        models.governance_proposals.push(GovernanceProposal {
+                            proposal_id: data.proposal_id,
+                            proposer_address: data.proposer.to_string(),
+                            stake_pool_address: data.stake_pool.to_string(),
+                            execution_hash: data.execution_hash,
+                            proposal_metadata: data.proposal_metadata,
+                            transaction_version: version.clone(),
+                            transaction_timestamp: timestamp,
+                            inserted_at: chrono::Utc::now().naive_utc(),
+                        });
+                    }
+                } else if event_type == VOTE_EVENT_TYPE {
+                    if let Ok(data) = serde_json::from_value::<VoteEventData>(event.data.clone()) {
+                        models.governance_votes.push(GovernanceVote {
+                            transaction_version: version.clone(),
+                            proposal_id: data.proposal_id,
+                            voter_address: data.voter.to_string(),
+                            stake_pool_address: data.stake_pool.to_string(),
+                            num_votes: data.num_votes,
+                            should_pass: data.should_pass,
+                            transaction_timestamp: timestamp,
+                            inserted_at: chrono::Utc::now().naive_utc(),
+                        });
+                    }
+                }
+            }
+        }
+        models
+    }
+}
+
+impl StakePoolActivity {
+    fn from_event(
+        event: &APIEvent,
+        event_type: String,
+        version: &bigdecimal::BigDecimal,
+        timestamp: chrono::NaiveDateTime,
+    ) -> Option<Self> {
+        let data = event.data.clone();
+        let mut amount = None;
+        let mut old_operator_address = None;
+        let mut new_operator_address = None;
+        let mut old_locked_until_secs = None;
+        let mut new_locked_until_secs = None;
+        let pool_address = match &event_type[STAKE_EVENT_PREFIX.len()..] {
+            "AddStakeEvent" => {
+                let inner = serde_json::from_value::<AddStakeEventData>(data).ok()?;
+                amount = Some(inner.amount_added);
+                inner.pool_address
+            }
+            "ReactivateStakeEvent" => {
+                let inner = serde_json::from_value::<ReactivateStakeEventData>(data).ok()?;
+                amount = Some(inner.amount);
+                inner.pool_address
+            }
+            "DistributeRewardsEvent" => {
+                let inner = serde_json::from_value::<DistributeRewardsEventData>(data).ok()?;
+                amount = Some(inner.rewards_amount);
+                inner.pool_address
+            }
+            "UnlockStakeEvent" => {
+                let inner = serde_json::from_value::<UnlockStakeEventData>(data).ok()?;
+                amount = Some(inner.amount_unlocked);
+                inner.pool_address
+            }
+            "WithdrawStakeEvent" => {
+                let inner = serde_json::from_value::<WithdrawStakeEventData>(data).ok()?;
+                amount = Some(inner.amount_withdrawn);
+                inner.pool_address
+            }
+            "SetOperatorEvent" => {
+                let inner = serde_json::from_value::<SetOperatorEventData>(data).ok()?;
+                old_operator_address = Some(inner.old_operator.to_string());
+                new_operator_address = Some(inner.new_operator.to_string());
+                inner.pool_address
+            }
+            "IncreaseLockupEvent" => {
+                let inner = serde_json::from_value::<IncreaseLockupEventData>(data).ok()?;
+                old_locked_until_secs = Some(inner.old_locked_until_secs);
+                new_locked_until_secs = Some(inner.new_locked_until_secs);
+                inner.pool_address
+            }
+            _ => {
+                serde_json::from_value::<StakePoolEventData>(data)
+                    .ok()?
+                    .pool_address
+            }
+        };
+
+        Some(Self {
+            transaction_version: version.clone(),
+            event_account_address: Address::from(event.key.0.get_creator_address()).to_string(),
+            event_creation_number: event.key.0.get_creation_number() as i64,
+            event_sequence_number: event.sequence_number.0 as i64,
+            pool_address: pool_address.to_string(),
+            activity_type: event_type,
+            amount,
+            old_operator_address,
+            new_operator_address,
+            old_locked_until_secs,
+            new_locked_until_secs,
+            transaction_timestamp: timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_stake_models_from_block_metadata_and_vote() {
+        let block_metadata: APITransaction = serde_json::from_value(json!({
+            "type": "block_metadata_transaction",
+            "version": "200",
+            "hash": "0x2b7c58ed8524d228f9d0543a82e2793d04e8871df322f976b0e7bb8c5ced4ff5",
+            "state_change_hash": "0x3ead9eb40582fbc7df5e02f72280931dc3e6f1aae45dc832966b4cd972dac4b8",
+            "event_root_hash": "0x2e481956dea9c59b6fc9f823fe5f4c45efce173e42c551c1fe073b5d76a65504",
+            "gas_used": "0",
+            "success": true,
+            "vm_status": "Executed successfully",
+            "accumulator_root_hash": "0xb0ad602f805eb20c398f0f29a3504a9ef38bcc52c9c451deb9ec4a2d18807b49",
+            "changes": [],
+            "id": "0x2b7c58ed8524d228f9d0543a82e2793d04e8871df322f976b0e7bb8c5ced4ff5",
+            "epoch": "7",
+            "round": "12",
+            "events": [
+                {
+                    "key": "0x0900000000000000000000000000000000000000000000000000000000000000000000000000000c",
+                    "sequence_number": "6",
+                    "type": "0x1::stake::DistributeRewardsEvent",
+                    "data": { "pool_address": "0xc", "rewards_amount": "150" }
+                }
+            ],
+            "previous_block_votes_bitvec": [],
+            "proposer": "0xc",
+            "failed_proposer_indices": [],
+            "timestamp": "1649395495746947"
+        }))
+        .unwrap();
+        let vote: APITransaction = serde_json::from_value(json!({
+            "type": "user_transaction",
+            "version": "201",
+            "hash": "0x2b7c58ed8524d228f9d0543a82e2793d04e8871df322f976b0e7bb8c5ced4ff5",
+            "state_change_hash": "0x3ead9eb40582fbc7df5e02f72280931dc3e6f1aae45dc832966b4cd972dac4b8",
+            "event_root_hash": "0x2e481956dea9c59b6fc9f823fe5f4c45efce173e42c551c1fe073b5d76a65504",
+            "gas_used": "10",
+            "success": true,
+            "vm_status": "Executed successfully",
+            "accumulator_root_hash": "0xb0ad602f805eb20c398f0f29a3504a9ef38bcc52c9c451deb9ec4a2d18807b49",
+            "changes": [],
+            "sender": "0xd",
+            "sequence_number": "0",
+            "max_gas_amount": "1000",
+            "gas_unit_price": "1",
+            "expiration_timestamp_secs": "1649395500",
+            "payload": {
+                "type": "entry_function_payload",
+                "function": "0x1::aptos_governance::vote",
+                "type_arguments": [],
+                "arguments": ["0xc", "3", true]
+            },
+            "timestamp": "1649395495746947",
+            "events": [
+                {
+                    "key": "0x0400000000000000000000000000000000000000000000000000000000000000000000000000000c",
+                    "sequence_number": "2",
+                    "type": "0x1::stake::AddStakeEvent",
+                    "data": { "pool_address": "0xc", "amount_added": "1000" }
+                },
+                {
+                    "key": "0x0500000000000000000000000000000000000000000000000000000000000000000000000000000c",
+                    "sequence_number": "0",
+                    "type": "0x1::stake::SetOperatorEvent",
+                    "data": { "pool_address": "0xc", "old_operator": "0xc", "new_operator": "0xd" }
+                },
+                {
+                    "key": "0x03000000000000000000000000000000000000000000000000000000000000000000000000000001",
+                    "sequence_number": "4",
+                    "type": "0x1::aptos_governance::VoteEvent",
+                    "data": {
+                        "proposal_id": "3",
+                        "voter": "0xd",
+                        "stake_pool": "0xc",
+                        "num_votes": "1000",
+                        "should_pass": true
+                    }
+                }
+            ]
+        }))
+        .unwrap();
+
+        let models = StakeModels::from_transactions(&[block_metadata, vote]);
+
+        assert_eq!(models.stake_rewards.len(), 1);
+        let reward = &models.stake_rewards[0];
+        assert_eq!(reward.pool_address, "0xc");
+        assert_eq!(reward.epoch, u64_to_bigdecimal(7));
+        assert_eq!(reward.rewards_amount, u64_to_bigdecimal(150));
+
+        assert_eq!(models.stake_pool_activities.len(), 3);
+        let add_stake = models
+            .stake_pool_activities
+            .iter()
+            .find(|activity| activity.activity_type == "0x1::stake::AddStakeEvent")
+            .unwrap();
+        assert_eq!(add_stake.amount, Some(u64_to_bigdecimal(1000)));
+        let set_operator = models
+            .stake_pool_activities
+            .iter()
+            .find(|activity| activity.activity_type == "0x1::stake::SetOperatorEvent")
+            .unwrap();
+        assert_eq!(set_operator.amount, None);
+        assert_eq!(set_operator.new_operator_address.as_deref(), Some("0xd"));
+
+        assert!(models.governance_proposals.is_empty());
+        assert_eq!(models.governance_votes.len(), 1);
+        let vote = &models.governance_votes[0];
+        assert_eq!(vote.proposal_id, u64_to_bigdecimal(3));
+        assert_eq!(vote.stake_pool_address, "0xc");
+        assert!(vote.should_pass);
+    }
+}
@@ -421,7 +421,7 @@ impl BlockMetadataTransaction {
     }
 }
 
-fn parse_timestamp(ts: U64, version: U64) -> chrono::NaiveDateTime {
+pub fn parse_timestamp(ts: U64, version: U64) -> chrono::NaiveDateTime {
     chrono::NaiveDateTime::from_timestamp_opt((*ts.inner() / 1000000) as i64, 0)
         .unwrap_or_else(|| panic!("Could not parse timestamp {:?} for version {}", ts, version))
 }
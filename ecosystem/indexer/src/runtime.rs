@@ -0,0 +1,134 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runs the indexer inside the node, reading transactions straight from storage
+
+use crate::{
+    coin_processor::CoinTransactionProcessor,
+    database::{new_db_pool, PgDbPool},
+    default_processor::DefaultTransactionProcessor,
+    indexer::{node_fetcher::NodeTransactionFetcher, tailer::Tailer},
+    stake_processor::StakeTransactionProcessor,
+    token_processor::TokenTransactionProcessor,
+};
+use aptos_api::context::Context;
+use aptos_config::config::{IndexerConfig, NodeConfig};
+use aptos_logger::info;
+use aptos_mempool::MempoolClientSender;
+use aptos_types::chain_id::ChainId;
+use std::sync::Arc;
+use storage_interface::DbReader;
+use tokio::{
+    runtime::{Builder, Runtime},
+    sync::Mutex,
+};
+
+const DEFAULT_BATCH_SIZE: u8 = 10;
+const DEFAULT_EMIT_EVERY: usize = 1000;
+
+/// Creates a runtime which runs the indexer's processors on the transactions in `db`
+/// Returns corresponding Tokio runtime
+pub fn bootstrap(
+    config: &NodeConfig,
+    chain_id: ChainId,
+    db: Arc<dyn DbReader>,
+    mp_sender: MempoolClientSender,
+) -> Option<anyhow::Result<Runtime>> {
+    if !config.indexer.enabled {
+        return None;
+    }
+
+    // Let the config file take precedence over the env variable
+    let postgres_uri = match config
+        .indexer
+        .postgres_uri
+        .clone()
+        .or_else(|| std::env::var("INDEXER_DATABASE_URL").ok())
+    {
+        Some(postgres_uri) => postgres_uri,
+        None => {
+            return Some(Err(anyhow::anyhow!(
+                "postgres_uri or the INDEXER_DATABASE_URL env var must be set to run the indexer"
+            )))
+        }
+    };
+    let conn_pool = match new_db_pool(&postgres_uri) {
+        Ok(conn_pool) => conn_pool,
+        Err(err) => {
+            return Some(Err(anyhow::anyhow!(
+                "Failed to create the indexer connection pool: {}",
+                err
+            )))
+        }
+    };
+    info!("Created the connection pool... ");
+
+    let runtime = Builder::new_multi_thread()
+        .thread_name("indexer")
+        .enable_all()
+        .build()
+        .expect("[indexer] failed to create runtime");
+
+    let context = Arc::new(Context::new(chain_id, db, mp_sender, config.clone()));
+    let tailer = match runtime.block_on(setup_tailer(&config.indexer, context, conn_pool)) {
+        Ok(tailer) => tailer,
+        Err(err) => return Some(Err(err)),
+    };
+    runtime.spawn(run_forever(config.indexer.clone(), tailer));
+    Some(Ok(runtime))
+}
+
+/// Creates the tailer with all the processors enabled in `config`, and makes sure the database
+/// indexes the same chain as the node
+async fn setup_tailer(
+    config: &IndexerConfig,
+    context: Arc<Context>,
+    conn_pool: PgDbPool,
+) -> anyhow::Result<Tailer> {
+    info!("Starting indexer...");
+
+    let transaction_fetcher = NodeTransactionFetcher::new(context, None);
+    let mut tailer =
+        Tailer::new_with_fetcher(Arc::new(Mutex::new(transaction_fetcher)), conn_pool.clone());
+
+    if !config.skip_migrations {
+        tailer.run_migrations();
+    }
+
+    tailer.check_or_update_chain_id().await?;
+    let pg_transaction_processor = DefaultTransactionProcessor::new(conn_pool.clone());
+    tailer.add_processor(Arc::new(pg_transaction_processor));
+    if config.index_token_data {
+        let token_transaction_processor =
+            TokenTransactionProcessor::new(conn_pool.clone(), config.index_token_uri_data);
+        tailer.add_processor(Arc::new(token_transaction_processor));
+    }
+    if config.index_coin_data {
+        let coin_transaction_processor = CoinTransactionProcessor::new(conn_pool.clone());
+        tailer.add_processor(Arc::new(coin_transaction_processor));
+    }
+    if config.index_stake_data {
+        let stake_transaction_processor = StakeTransactionProcessor::new(conn_pool);
+        tailer.add_processor(Arc::new(stake_transaction_processor));
+    }
+    Ok(tailer)
+}
+
+async fn run_forever(config: IndexerConfig, tailer: Tailer) {
+    let starting_version = match config.starting_version {
+        None => tailer.set_fetcher_to_lowest_processor_version().await,
+        Some(version) => tailer.set_fetcher_version(version).await,
+    };
+
+    if !config.skip_previous_errors {
+        tailer.handle_previous_errors().await;
+    }
+
+    tailer
+        .run(
+            starting_version,
+            config.batch_size.unwrap_or(DEFAULT_BATCH_SIZE),
+            config.emit_every.unwrap_or(DEFAULT_EMIT_EVERY),
+        )
+        .await;
+}
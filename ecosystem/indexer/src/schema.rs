@@ -16,6 +16,49 @@ table! {
     }
 }
 
+table! {
+    coin_activities (transaction_version, event_account_address, event_creation_number, event_sequence_number) {
+        transaction_version -> Numeric,
+        event_account_address -> Varchar,
+        event_creation_number -> Int8,
+        event_sequence_number -> Int8,
+        owner_address -> Varchar,
+        coin_type -> Varchar,
+        amount -> Numeric,
+        activity_type -> Varchar,
+        is_gas_fee -> Bool,
+        is_transaction_success -> Bool,
+        entry_function_id_str -> Nullable<Varchar>,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+table! {
+    coin_balances (transaction_version, owner_address, coin_type) {
+        transaction_version -> Numeric,
+        owner_address -> Varchar,
+        coin_type -> Varchar,
+        amount -> Numeric,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+table! {
+    coin_infos (coin_type) {
+        coin_type -> Varchar,
+        transaction_version_created -> Numeric,
+        creator_address -> Varchar,
+        name -> Varchar,
+        symbol -> Varchar,
+        decimals -> Int4,
+        supply -> Nullable<Numeric>,
+        last_transaction_version -> Numeric,
+        inserted_at -> Timestamp,
+    }
+}
+
 table! {
     collections (collection_id) {
         collection_id -> Varchar,
@@ -29,6 +72,17 @@ table! {
     }
 }
 
+table! {
+    current_coin_balances (owner_address, coin_type) {
+        owner_address -> Varchar,
+        coin_type -> Varchar,
+        amount -> Numeric,
+        last_transaction_version -> Numeric,
+        last_transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 table! {
     events (key, sequence_number) {
         transaction_hash -> Varchar,
@@ -41,6 +95,32 @@ table! {
     }
 }
 
+table! {
+    governance_proposals (proposal_id) {
+        proposal_id -> Numeric,
+        proposer_address -> Varchar,
+        stake_pool_address -> Varchar,
+        execution_hash -> Varchar,
+        proposal_metadata -> Jsonb,
+        transaction_version -> Numeric,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+table! {
+    governance_votes (transaction_version, proposal_id, stake_pool_address) {
+        transaction_version -> Numeric,
+        proposal_id -> Numeric,
+        voter_address -> Varchar,
+        stake_pool_address -> Varchar,
+        num_votes -> Numeric,
+        should_pass -> Bool,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 table! {
     ledger_infos (chain_id) {
         chain_id -> Int8,
@@ -85,6 +165,35 @@ table! {
     }
 }
 
+table! {
+    stake_pool_activities (transaction_version, event_account_address, event_creation_number, event_sequence_number) {
+        transaction_version -> Numeric,
+        event_account_address -> Varchar,
+        event_creation_number -> Int8,
+        event_sequence_number -> Int8,
+        pool_address -> Varchar,
+        activity_type -> Varchar,
+        amount -> Nullable<Numeric>,
+        old_operator_address -> Nullable<Varchar>,
+        new_operator_address -> Nullable<Varchar>,
+        old_locked_until_secs -> Nullable<Numeric>,
+        new_locked_until_secs -> Nullable<Numeric>,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+table! {
+    stake_rewards (transaction_version, pool_address) {
+        transaction_version -> Numeric,
+        pool_address -> Varchar,
+        epoch -> Numeric,
+        rewards_amount -> Numeric,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 table! {
     token_activities (event_key, sequence_number) {
         event_key -> Varchar,
@@ -181,12 +290,20 @@ table! {
 
 allow_tables_to_appear_in_same_query!(
     block_metadata_transactions,
+    coin_activities,
+    coin_balances,
+    coin_infos,
     collections,
+    current_coin_balances,
     events,
+    governance_proposals,
+    governance_votes,
     ledger_infos,
     metadatas,
     ownerships,
     processor_statuses,
+    stake_pool_activities,
+    stake_rewards,
     token_activities,
     token_datas,
     token_propertys,
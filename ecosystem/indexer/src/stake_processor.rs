@@ -0,0 +1,168 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    database::{execute_with_better_error, get_chunks, PgDbPool, PgPoolConnection},
+    indexer::{
+        errors::TransactionProcessingError, processing_result::ProcessingResult,
+        transaction_processor::TransactionProcessor,
+    },
+    models::stake::{
+        GovernanceProposal, GovernanceVote, StakeModels, StakePoolActivity, StakeReward,
+    },
+    schema,
+};
+use aptos_rest_client::Transaction;
+use async_trait::async_trait;
+use diesel::RunQueryDsl;
+use field_count::FieldCount;
+use std::fmt::Debug;
+
+pub struct StakeTransactionProcessor {
+    connection_pool: PgDbPool,
+}
+
+impl StakeTransactionProcessor {
+    pub fn new(connection_pool: PgDbPool) -> Self {
+        Self { connection_pool }
+    }
+}
+
+impl Debug for StakeTransactionProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "StakeTransactionProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+fn insert_stake_pool_activities(
+    conn: &PgPoolConnection,
+    stake_pool_activities: &[StakePoolActivity],
+) {
+    let chunks = get_chunks(
+        stake_pool_activities.len(),
+        StakePoolActivity::field_count(),
+    );
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::stake_pool_activities::table)
+                .values(&stake_pool_activities[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into stake_pool_activities");
+    }
+}
+
+fn insert_stake_rewards(conn: &PgPoolConnection, stake_rewards: &[StakeReward]) {
+    let chunks = get_chunks(stake_rewards.len(), StakeReward::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::stake_rewards::table)
+                .values(&stake_rewards[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into stake_rewards");
+    }
+}
+
+fn insert_governance_proposals(
+    conn: &PgPoolConnection,
+    governance_proposals: &[GovernanceProposal],
+) {
+    let chunks = get_chunks(
+        governance_proposals.len(),
+        GovernanceProposal::field_count(),
+    );
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::governance_proposals::table)
+                .values(&governance_proposals[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into governance_proposals");
+    }
+}
+
+fn insert_governance_votes(conn: &PgPoolConnection, governance_votes: &[GovernanceVote]) {
+    let chunks = get_chunks(governance_votes.len(), GovernanceVote::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::governance_votes::table)
+                .values(&governance_votes[start_ind..end_ind])
+                .on_conflict_do_nothing(),
+        )
+        .expect("Error inserting row into governance_votes");
+    }
+}
+
+fn insert_to_db(
+    conn: &PgPoolConnection,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    stake_models: StakeModels,
+) -> Result<(), diesel::result::Error> {
+    aptos_logger::trace!(
+        "[{}] inserting versions {} to {}",
+        name,
+        start_version,
+        end_version
+    );
+    conn.build_transaction()
+        .read_write()
+        .run::<_, diesel::result::Error, _>(|| {
+            insert_stake_pool_activities(conn, &stake_models.stake_pool_activities);
+            insert_stake_rewards(conn, &stake_models.stake_rewards);
+            insert_governance_proposals(conn, &stake_models.governance_proposals);
+            insert_governance_votes(conn, &stake_models.governance_votes);
+            Ok(())
+        })
+}
+
+#[async_trait]
+impl TransactionProcessor for StakeTransactionProcessor {
+    fn name(&self) -> &'static str {
+        "stake_processor"
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+    ) -> Result<ProcessingResult, TransactionProcessingError> {
+        let stake_models = StakeModels::from_transactions(&transactions);
+
+        let conn = self.get_conn();
+        let tx_result = insert_to_db(&conn, self.name(), start_version, end_version, stake_models);
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::new(
+                self.name(),
+                start_version,
+                end_version,
+            )),
+            Err(err) => Err(TransactionProcessingError::TransactionCommitError((
+                anyhow::Error::from(err),
+                start_version,
+                end_version,
+                self.name(),
+            ))),
+        }
+    }
+
+    fn connection_pool(&self) -> &PgDbPool {
+        &self.connection_pool
+    }
+
+    fn supports_parallel_backfill(&self) -> bool {
+        true
+    }
+}
@@ -9,13 +9,13 @@ use aptos_protos::{
         UserTransactionOutput, WriteSetChangeOutput,
     },
     extractor::v1::{
-        account_signature::Signature as AccountSignature,
+        account_signature::{Signature as AccountSignature, Type as AccountSignatureType},
         signature::{Signature, Type as SignatureType},
         transaction::TransactionType,
         write_set_change::{Change as ChangeInput, Type as WriteSetChangeType},
-        BlockMetadataTransaction, Ed25519Signature, Event, GenesisTransaction, MultiAgentSignature,
-        MultiEd25519Signature, Transaction, TransactionInfo, UserTransaction,
-        UserTransactionRequest,
+        BlockMetadataTransaction, Ed25519Signature, Event, FeePayerSignature, GenesisTransaction,
+        MultiAgentSignature, MultiEd25519Signature, Secp256r1EcdsaSignature, SingleSenderSignature,
+        Transaction, TransactionInfo, UserTransaction, UserTransactionRequest, WebAuthnSignature,
     },
 };
 
@@ -152,6 +152,17 @@ pub fn get_signature_type(t: SignatureType) -> String {
         SignatureType::Ed25519 => String::from("ed25519_signature"),
         SignatureType::MultiEd25519 => String::from("multi_ed25519_signature"),
         SignatureType::MultiAgent => String::from("multi_agent_signature"),
+        SignatureType::SingleSender => String::from("single_sender_signature"),
+        SignatureType::FeePayer => String::from("fee_payer_signature"),
+    }
+}
+
+pub fn get_account_signature_type(t: AccountSignatureType) -> String {
+    match t {
+        AccountSignatureType::Ed25519 => String::from("ed25519_signature"),
+        AccountSignatureType::MultiEd25519 => String::from("multi_ed25519_signature"),
+        AccountSignatureType::Secp256r1Ecdsa => String::from("secp256r1_ecdsa_signature"),
+        AccountSignatureType::Webauthn => String::from("webauthn_signature"),
     }
 }
 
@@ -179,6 +190,8 @@ pub fn get_signature_outputs(
             Ok(parse_multi_signature(sig, request, info, true, 0, None))
         }
         Signature::MultiAgent(sig) => parse_multi_agent_signature(sig, request, info),
+        Signature::SingleSender(sig) => Ok(parse_single_sender_signature(sig, request, info)),
+        Signature::FeePayer(sig) => parse_fee_payer_signature(sig, request, info),
     }
 }
 
@@ -205,6 +218,52 @@ fn parse_single_signature(
     }
 }
 
+fn parse_secp256r1_ecdsa_signature(
+    s: &Secp256r1EcdsaSignature,
+    request: &UserTransactionRequest,
+    info: &TransactionInfoOutput,
+    is_sender_primary: bool,
+    multi_agent_index: u32,
+    override_address: Option<&String>,
+) -> SignatureOutput {
+    let signer = override_address.unwrap_or(&request.sender);
+    SignatureOutput {
+        version: info.version,
+        signer: signer.clone(),
+        is_sender_primary,
+        signature_type: get_account_signature_type(AccountSignatureType::Secp256r1Ecdsa),
+        public_key: s.public_key.clone(),
+        signature: s.signature.clone(),
+        threshold: 1,
+        public_key_indices: Vec::default(),
+        multi_agent_index,
+        multi_sig_index: 0,
+    }
+}
+
+fn parse_webauthn_signature(
+    s: &WebAuthnSignature,
+    request: &UserTransactionRequest,
+    info: &TransactionInfoOutput,
+    is_sender_primary: bool,
+    multi_agent_index: u32,
+    override_address: Option<&String>,
+) -> SignatureOutput {
+    let signer = override_address.unwrap_or(&request.sender);
+    SignatureOutput {
+        version: info.version,
+        signer: signer.clone(),
+        is_sender_primary,
+        signature_type: get_account_signature_type(AccountSignatureType::Webauthn),
+        public_key: s.public_key.clone(),
+        signature: s.signature.clone(),
+        threshold: 1,
+        public_key_indices: Vec::default(),
+        multi_agent_index,
+        multi_sig_index: 0,
+    }
+}
+
 fn parse_multi_signature(
     s: &MultiEd25519Signature,
     request: &UserTransactionRequest,
@@ -277,6 +336,55 @@ fn parse_multi_agent_signature(
     Ok(signatures)
 }
 
+fn parse_fee_payer_signature(
+    s: &FeePayerSignature,
+    request: &UserTransactionRequest,
+    info: &TransactionInfoOutput,
+) -> Result<Vec<SignatureOutput>> {
+    let mut signatures = parse_multi_agent_signature(
+        &MultiAgentSignature {
+            sender: s.sender.clone(),
+            secondary_signer_addresses: s.secondary_signer_addresses.clone(),
+            secondary_signers: s.secondary_signers.clone(),
+        },
+        request,
+        info,
+    )?;
+    // the fee payer comes after the secondary signers
+    if let Some(fee_payer_sig) = s
+        .fee_payer_signer
+        .as_ref()
+        .and_then(|fee_payer| fee_payer.signature.as_ref())
+    {
+        signatures.append(&mut parse_multi_agent_signature_helper(
+            fee_payer_sig,
+            request,
+            info,
+            false,
+            s.secondary_signer_addresses.len() as u32,
+            Some(&s.fee_payer_address),
+        ));
+    }
+    Ok(signatures)
+}
+
+fn parse_single_sender_signature(
+    s: &SingleSenderSignature,
+    request: &UserTransactionRequest,
+    info: &TransactionInfoOutput,
+) -> Vec<SignatureOutput> {
+    match s
+        .sender
+        .as_ref()
+        .and_then(|sender| sender.signature.as_ref())
+    {
+        None => vec![],
+        Some(sender_sig) => {
+            parse_multi_agent_signature_helper(sender_sig, request, info, true, 0, None)
+        }
+    }
+}
+
 fn parse_multi_agent_signature_helper(
     s: &AccountSignature,
     request: &UserTransactionRequest,
@@ -302,6 +410,22 @@ fn parse_multi_agent_signature_helper(
             multi_agent_index,
             override_address,
         ),
+        AccountSignature::Secp256r1Ecdsa(sig) => vec![parse_secp256r1_ecdsa_signature(
+            sig,
+            request,
+            info,
+            is_sender_primary,
+            multi_agent_index,
+            override_address,
+        )],
+        AccountSignature::Webauthn(sig) => vec![parse_webauthn_signature(
+            sig,
+            request,
+            info,
+            is_sender_primary,
+            multi_agent_index,
+            override_address,
+        )],
     }
 }
 
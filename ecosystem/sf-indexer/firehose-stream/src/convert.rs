@@ -2,12 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_api_types::{
-    AccountSignature, DeleteModule, DeleteResource, Ed25519Signature, EntryFunctionId, Event,
-    GenesisPayload, MoveAbility, MoveFunction, MoveFunctionGenericTypeParam,
-    MoveFunctionVisibility, MoveModule, MoveModuleBytecode, MoveModuleId, MoveScriptBytecode,
-    MoveStruct, MoveStructField, MoveStructTag, MoveType, MultiEd25519Signature, ScriptPayload,
-    Transaction, TransactionInfo, TransactionPayload, TransactionSignature, WriteSet,
-    WriteSetChange,
+    AccountSignature, DeleteModule, DeleteResource, Ed25519Signature, EntryFunctionId,
+    EntryFunctionPayload, Event, GenesisPayload, MoveAbility, MoveFunction,
+    MoveFunctionGenericTypeParam, MoveFunctionVisibility, MoveModule, MoveModuleBytecode,
+    MoveModuleId, MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag, MoveType,
+    MultiEd25519Signature, MultisigTransactionPayload, ScriptPayload, Secp256r1EcdsaSignature,
+    Transaction, TransactionInfo, TransactionPayload, TransactionSignature, WebAuthnSignature,
+    WriteSet, WriteSetChange,
 };
 use aptos_bitvec::BitVec;
 use aptos_logger::warn;
@@ -146,15 +147,7 @@ pub fn convert_transaction_payload(payload: &TransactionPayload) -> extractor::T
             r#type: extractor::transaction_payload::Type::EntryFunctionPayload as i32,
             payload: Some(
                 extractor::transaction_payload::Payload::EntryFunctionPayload(
-                    extractor::EntryFunctionPayload {
-                        function: Some(convert_entry_function_id(&sfp.function)),
-                        type_arguments: sfp.type_arguments.iter().map(convert_move_type).collect(),
-                        arguments: sfp
-                            .arguments
-                            .iter()
-                            .map(|move_value| move_value.to_string())
-                            .collect(),
-                    },
+                    convert_entry_function_payload(sfp),
                 ),
             ),
         },
@@ -178,6 +171,52 @@ pub fn convert_transaction_payload(payload: &TransactionPayload) -> extractor::T
                 ),
             ),
         },
+        TransactionPayload::MultisigPayload(mp) => extractor::TransactionPayload {
+            r#type: extractor::transaction_payload::Type::MultisigPayload as i32,
+            payload: Some(extractor::transaction_payload::Payload::MultisigPayload(
+                extractor::MultisigPayload {
+                    multisig_address: mp.multisig_address.to_string(),
+                    transaction_payload: mp
+                        .transaction_payload
+                        .as_ref()
+                        .map(convert_multisig_transaction_payload),
+                },
+            )),
+        },
+    }
+}
+
+pub fn convert_multisig_transaction_payload(
+    multisig_transaction_payload: &MultisigTransactionPayload,
+) -> extractor::MultisigTransactionPayload {
+    use extractor::multisig_transaction_payload::{Payload, Type};
+    match multisig_transaction_payload {
+        MultisigTransactionPayload::EntryFunctionPayload(sfp) => {
+            extractor::MultisigTransactionPayload {
+                r#type: Type::EntryFunctionPayload as i32,
+                payload: Some(Payload::EntryFunctionPayload(
+                    convert_entry_function_payload(sfp),
+                )),
+            }
+        }
+    }
+}
+
+pub fn convert_entry_function_payload(
+    entry_function_payload: &EntryFunctionPayload,
+) -> extractor::EntryFunctionPayload {
+    extractor::EntryFunctionPayload {
+        function: Some(convert_entry_function_id(&entry_function_payload.function)),
+        type_arguments: entry_function_payload
+            .type_arguments
+            .iter()
+            .map(convert_move_type)
+            .collect(),
+        arguments: entry_function_payload
+            .arguments
+            .iter()
+            .map(|move_value| move_value.to_string())
+            .collect(),
     }
 }
 
@@ -487,6 +526,24 @@ pub fn convert_ed25519_signature(sig: &Ed25519Signature) -> extractor::Ed25519Si
     }
 }
 
+pub fn convert_secp256r1_ecdsa_signature(
+    sig: &Secp256r1EcdsaSignature,
+) -> extractor::Secp256r1EcdsaSignature {
+    extractor::Secp256r1EcdsaSignature {
+        public_key: sig.public_key.0.clone(),
+        signature: sig.signature.0.clone(),
+    }
+}
+
+pub fn convert_webauthn_signature(sig: &WebAuthnSignature) -> extractor::WebAuthnSignature {
+    extractor::WebAuthnSignature {
+        public_key: sig.public_key.0.clone(),
+        signature: sig.signature.0.clone(),
+        authenticator_data: sig.authenticator_data.0.clone(),
+        client_data_json: sig.client_data_json.0.clone(),
+    }
+}
+
 pub fn convert_multi_ed25519_signature(
     sig: &MultiEd25519Signature,
 ) -> extractor::MultiEd25519Signature {
@@ -510,6 +567,10 @@ pub fn convert_account_signature(
         AccountSignature::MultiEd25519Signature(_) => {
             extractor::account_signature::Type::MultiEd25519
         }
+        AccountSignature::Secp256r1EcdsaSignature(_) => {
+            extractor::account_signature::Type::Secp256r1Ecdsa
+        }
+        AccountSignature::WebAuthnSignature(_) => extractor::account_signature::Type::Webauthn,
     };
     let signature = match account_signature {
         AccountSignature::Ed25519Signature(s) => {
@@ -520,6 +581,14 @@ pub fn convert_account_signature(
                 s,
             ))
         }
+        AccountSignature::Secp256r1EcdsaSignature(s) => {
+            extractor::account_signature::Signature::Secp256r1Ecdsa(
+                convert_secp256r1_ecdsa_signature(s),
+            )
+        }
+        AccountSignature::WebAuthnSignature(s) => {
+            extractor::account_signature::Signature::Webauthn(convert_webauthn_signature(s))
+        }
     };
     extractor::AccountSignature {
         r#type: r#type as i32,
@@ -538,6 +607,8 @@ pub fn convert_transaction_signature(
         TransactionSignature::Ed25519Signature(_) => extractor::signature::Type::Ed25519,
         TransactionSignature::MultiEd25519Signature(_) => extractor::signature::Type::MultiEd25519,
         TransactionSignature::MultiAgentSignature(_) => extractor::signature::Type::MultiAgent,
+        TransactionSignature::SingleSenderSignature(_) => extractor::signature::Type::SingleSender,
+        TransactionSignature::FeePayerSignature(_) => extractor::signature::Type::FeePayer,
     };
 
     let signature = match signature {
@@ -562,6 +633,28 @@ pub fn convert_transaction_signature(
                     .collect(),
             })
         }
+        TransactionSignature::SingleSenderSignature(s) => {
+            extractor::signature::Signature::SingleSender(extractor::SingleSenderSignature {
+                sender: Some(convert_account_signature(&s.sender)),
+            })
+        }
+        TransactionSignature::FeePayerSignature(s) => {
+            extractor::signature::Signature::FeePayer(extractor::FeePayerSignature {
+                sender: Some(convert_account_signature(&s.sender)),
+                secondary_signer_addresses: s
+                    .secondary_signer_addresses
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+                secondary_signers: s
+                    .secondary_signers
+                    .iter()
+                    .map(convert_account_signature)
+                    .collect(),
+                fee_payer_address: s.fee_payer_address.to_string(),
+                fee_payer_signer: Some(convert_account_signature(&s.fee_payer_signer)),
+            })
+        }
     };
 
     Some(extractor::Signature {
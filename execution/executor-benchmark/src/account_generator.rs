@@ -106,4 +106,16 @@ impl AccountCache {
 
         (sender, receiver)
     }
+
+    /// Picks `num_transfers` transfers no two of which involve the same account, returning the
+    /// index of each sender along with the receiver.
+    pub fn get_disjoint_transfers(&mut self, num_transfers: usize) -> Vec<(usize, AccountAddress)> {
+        let indices =
+            rand::seq::index::sample(&mut self.rng, self.accounts.len(), 2 * num_transfers);
+        indices
+            .into_vec()
+            .chunks(2)
+            .map(|pair| (pair[0], self.accounts[pair[1]].address()))
+            .collect()
+    }
 }
@@ -13,7 +13,7 @@ use aptos_config::config::{
 use aptos_vm::AptosVM;
 use aptosdb::AptosDB;
 use executor::db_bootstrapper::{generate_waypoint, maybe_bootstrap};
-use std::{fs, path::Path};
+use std::{fs, path::Path, sync::Arc};
 use storage_interface::DbReaderWriter;
 
 pub fn run(
@@ -23,6 +23,7 @@ pub fn run(
     db_dir: impl AsRef<Path>,
     storage_pruner_config: PrunerConfig,
     verify_sequence_numbers: bool,
+    transaction_fee_burn_percentage: Option<u8>,
 ) {
     println!("Initializing...");
 
@@ -32,7 +33,7 @@ pub fn run(
     // create if not exists
     fs::create_dir_all(db_dir.as_ref()).unwrap();
 
-    bootstrap_with_genesis(&db_dir);
+    bootstrap_with_genesis(&db_dir, transaction_fee_burn_percentage);
 
     println!(
         "Finished empty DB creation, DB dir: {}. Creating accounts now...",
@@ -50,8 +51,12 @@ pub fn run(
     );
 }
 
-fn bootstrap_with_genesis(db_dir: impl AsRef<Path>) {
-    let (config, _genesis_key) = aptos_genesis::test_utils::test_config();
+fn bootstrap_with_genesis(db_dir: impl AsRef<Path>, transaction_fee_burn_percentage: Option<u8>) {
+    let (config, _genesis_key) = aptos_genesis::test_utils::test_config_with_genesis_config(Some(
+        Arc::new(move |genesis_config| {
+            genesis_config.transaction_fee_burn_percentage = transaction_fee_burn_percentage;
+        }),
+    ));
     // Create executor.
     let mut rocksdb_configs = RocksdbConfigs::default();
     rocksdb_configs.state_merkle_db_config.max_open_files = -1;
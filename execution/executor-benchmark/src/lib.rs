@@ -20,6 +20,7 @@ use aptos_jellyfish_merkle::metrics::{
     APTOS_JELLYFISH_INTERNAL_ENCODED_BYTES, APTOS_JELLYFISH_LEAF_ENCODED_BYTES,
     APTOS_JELLYFISH_STORAGE_READS,
 };
+use aptos_parallel_executor::counters::PARALLEL_EXECUTION_ABORTS;
 use aptosdb::AptosDB;
 
 use crate::pipeline::Pipeline;
@@ -76,6 +77,7 @@ pub fn run_benchmark(
     checkpoint_dir: impl AsRef<Path>,
     verify_sequence_numbers: bool,
     pruner_config: PrunerConfig,
+    conflict_free_transfers: bool,
 ) {
     create_checkpoint(source_dir.as_ref(), checkpoint_dir.as_ref());
 
@@ -95,13 +97,23 @@ pub fn run_benchmark(
         source_dir,
         version,
     );
-    generator.run_transfer(block_size, num_transfer_blocks);
+    let aborts_before = PARALLEL_EXECUTION_ABORTS.get();
+    if conflict_free_transfers {
+        generator.run_conflict_free_transfer(block_size, num_transfer_blocks);
+    } else {
+        generator.run_transfer(block_size, num_transfer_blocks);
+    }
     generator.drop_sender();
     pipeline.join();
 
     if verify_sequence_numbers {
         generator.verify_sequence_numbers(db.reader);
     }
+
+    println!(
+        "Aborted transaction executions: {}",
+        PARALLEL_EXECUTION_ABORTS.get() - aborts_before
+    );
 }
 
 pub fn add_accounts(
@@ -210,6 +222,7 @@ mod tests {
             storage_dir.as_ref(),
             NO_OP_STORAGE_PRUNER_CONFIG, /* prune_window */
             true,
+            None,
         );
 
         super::run_benchmark(
@@ -219,6 +232,7 @@ mod tests {
             checkpoint_dir,
             true,
             NO_OP_STORAGE_PRUNER_CONFIG,
+            false,
         );
     }
 }
@@ -114,6 +114,12 @@ enum Command {
 
         #[structopt(long, default_value = "1000000")]
         init_account_balance: u64,
+
+        #[structopt(
+            long,
+            about = "Collect transaction fees and distribute them to block proposers, burning the given percentage"
+        )]
+        transaction_fee_burn_percentage: Option<u8>,
     },
     RunExecutor {
         #[structopt(
@@ -128,6 +134,12 @@ enum Command {
 
         #[structopt(long, parse(from_os_str))]
         checkpoint_dir: PathBuf,
+
+        #[structopt(
+            long,
+            about = "Only transfer between accounts not involved in any other transfer of the block"
+        )]
+        conflict_free: bool,
     },
     AddAccounts {
         #[structopt(long, parse(from_os_str))]
@@ -162,6 +174,7 @@ fn main() {
             data_dir,
             num_accounts,
             init_account_balance,
+            transaction_fee_burn_percentage,
         } => {
             executor_benchmark::db_generator::run(
                 num_accounts,
@@ -170,12 +183,14 @@ fn main() {
                 data_dir,
                 opt.pruner_opt.pruner_config(),
                 opt.verify_sequence_numbers,
+                transaction_fee_burn_percentage,
             );
         }
         Command::RunExecutor {
             blocks,
             data_dir,
             checkpoint_dir,
+            conflict_free,
         } => {
             executor_benchmark::run_benchmark(
                 opt.block_size,
@@ -184,6 +199,7 @@ fn main() {
                 checkpoint_dir,
                 opt.verify_sequence_numbers,
                 opt.pruner_opt.pruner_config(),
+                conflict_free,
             );
         }
         Command::AddAccounts {
@@ -239,6 +239,11 @@ impl TransactionGenerator {
         self.gen_transfer_transactions(block_size, num_transfer_blocks);
     }
 
+    pub fn run_conflict_free_transfer(&mut self, block_size: usize, num_transfer_blocks: usize) {
+        assert!(self.block_sender.is_some());
+        self.gen_conflict_free_transfer_transactions(block_size, num_transfer_blocks);
+    }
+
     pub fn create_seed_accounts(
         &mut self,
         reader: Arc<dyn DbReader>,
@@ -381,6 +386,43 @@ impl TransactionGenerator {
         txn_block
     }
 
+    /// Generates transactions for pairs of accounts, such that within a block no account is
+    /// involved in more than one transfer and the transactions don't conflict with each other.
+    pub fn gen_conflict_free_transfer_transactions(
+        &mut self,
+        block_size: usize,
+        num_blocks: usize,
+    ) -> Vec<Vec<Transaction>> {
+        let mut txn_block = vec![];
+
+        for _ in 0..num_blocks {
+            let transfers = self
+                .accounts_cache
+                .as_mut()
+                .unwrap()
+                .get_disjoint_transfers(block_size);
+            let transactions: Vec<_> = transfers
+                .into_iter()
+                .map(|(sender_idx, receiver)| {
+                    let sender = &mut self.accounts_cache.as_mut().unwrap().accounts[sender_idx];
+                    sender.sign_with_transaction_builder(
+                        self.transaction_factory.transfer(receiver, 1),
+                    )
+                })
+                .map(Transaction::UserTransaction)
+                .chain(once(Transaction::StateCheckpoint(HashValue::random())))
+                .collect();
+            self.version += transactions.len() as Version;
+
+            if let Some(sender) = &self.block_sender {
+                sender.send(transactions).unwrap();
+            } else {
+                txn_block.push(transactions);
+            }
+        }
+        txn_block
+    }
+
     /// Verifies the sequence numbers in storage match what we have locally.
     pub fn verify_sequence_numbers(&self, db: Arc<dyn DbReader>) {
         if self.accounts_cache.is_none() {
@@ -0,0 +1,55 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that collecting transaction fees doesn't introduce conflicts between the transactions
+//! of a block. This is a test binary of its own because it sets the VM concurrency level and
+//! reads the process wide abort counter of the parallel executor.
+
+use aptos_config::config::NO_OP_STORAGE_PRUNER_CONFIG;
+use aptos_parallel_executor::counters::PARALLEL_EXECUTION_ABORTS;
+use aptos_temppath::TempPath;
+use aptos_vm::AptosVM;
+
+/// Runs conflict free transfers and returns the number of aborted executions.
+fn count_aborts(transaction_fee_burn_percentage: Option<u8>) -> u64 {
+    let storage_dir = TempPath::new();
+    let checkpoint_dir = TempPath::new();
+
+    executor_benchmark::db_generator::run(
+        50,      /* num_accounts */
+        100_000, /* init_account_balance */
+        10,      /* block_size */
+        storage_dir.as_ref(),
+        NO_OP_STORAGE_PRUNER_CONFIG,
+        true,
+        transaction_fee_burn_percentage,
+    );
+
+    // Creating the accounts has real conflicts, only the transfers are measured.
+    let aborts_before = PARALLEL_EXECUTION_ABORTS.get();
+    executor_benchmark::run_benchmark(
+        20, /* block_size */
+        10, /* num_transfer_blocks */
+        storage_dir.as_ref(),
+        checkpoint_dir,
+        true,
+        NO_OP_STORAGE_PRUNER_CONFIG,
+        true,
+    );
+    PARALLEL_EXECUTION_ABORTS.get() - aborts_before
+}
+
+#[test]
+fn test_fee_collection_adds_no_conflicts() {
+    // With a single CPU blocks are executed sequentially and nothing is ever aborted.
+    AptosVM::set_concurrency_level_once(num_cpus::get());
+
+    // The transfers of a block don't share any account, so they are never re-executed.
+    let aborts_without_fee_collection = count_aborts(None);
+    assert_eq!(aborts_without_fee_collection, 0);
+
+    // Fees are collected through aggregator deltas, which don't conflict either. Collecting them
+    // into a regular resource would make every transaction depend on the previous one.
+    let aborts_with_fee_collection = count_aborts(Some(10));
+    assert_eq!(aborts_with_fee_collection, aborts_without_fee_collection);
+}
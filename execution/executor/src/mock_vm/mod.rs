@@ -392,5 +392,8 @@ fn decode_transaction(txn: &SignedTransaction) -> MockVMTransaction {
         TransactionPayload::ModuleBundle(_) => {
             unimplemented!("MockVM does not support Module transaction payload.")
         }
+        TransactionPayload::Multisig(_) => {
+            unimplemented!("MockVM does not support multisig transaction payload.")
+        }
     }
 }
@@ -123,6 +123,24 @@ impl Mempool {
         self.transactions.get_by_hash(hash)
     }
 
+    /// Checks that the fee payer of a sponsored transaction can afford its max fee on top of the
+    /// max fees of the other transactions it sponsors in mempool. Always true for transactions
+    /// which are paid for by their sender.
+    pub(crate) fn can_fee_payer_afford(
+        &self,
+        txn: &SignedTransaction,
+        fee_payer_balance: u64,
+    ) -> bool {
+        match txn.fee_payer() {
+            Some(fee_payer) => {
+                let reserved = self.transactions.get_fee_payer_reserved(&fee_payer, txn);
+                let max_fee = txn.max_gas_amount().saturating_mul(txn.gas_unit_price());
+                reserved.saturating_add(max_fee) <= fee_payer_balance
+            }
+            None => true,
+        }
+    }
+
     /// Used to add a transaction to the Mempool.
     /// Performs basic validation: checks account's sequence number.
     pub(crate) fn add_txn(
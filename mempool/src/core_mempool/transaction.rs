@@ -45,6 +45,14 @@ impl MempoolTransaction {
     pub(crate) fn get_gas_price(&self) -> u64 {
         self.txn.gas_unit_price()
     }
+    pub(crate) fn get_fee_payer(&self) -> Option<AccountAddress> {
+        self.txn.fee_payer()
+    }
+    pub(crate) fn get_max_fee(&self) -> u64 {
+        self.txn
+            .max_gas_amount()
+            .saturating_mul(self.txn.gas_unit_price())
+    }
     pub(crate) fn get_committed_hash(&self) -> HashValue {
         self.txn.clone().committed_hash()
     }
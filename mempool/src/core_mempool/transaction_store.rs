@@ -59,6 +59,11 @@ pub struct TransactionStore {
     // one valid hash.
     hash_index: HashMap<HashValue, (AccountAddress, u64)>,
 
+    // Index of the total max fee of the sponsored transactions in mempool, by fee payer.
+    // The fee payer is charged for gas but its sequence number is not used, so this is the
+    // only place where its pending spending is accounted for.
+    fee_payer_index: HashMap<AccountAddress, u64>,
+
     // estimated size in bytes
     size_bytes: usize,
 
@@ -84,6 +89,7 @@ impl TransactionStore {
             timeline_index: TimelineIndex::new(),
             parking_lot_index: ParkingLotIndex::new(),
             hash_index: HashMap::new(),
+            fee_payer_index: HashMap::new(),
 
             // estimated size in bytes
             size_bytes: 0,
@@ -131,6 +137,22 @@ impl TransactionStore {
             .cloned()
     }
 
+    /// Returns the total max fee of the transactions in mempool sponsored by `fee_payer`, not
+    /// counting the transaction that would be replaced by `txn`, if any.
+    pub(crate) fn get_fee_payer_reserved(
+        &self,
+        fee_payer: &AccountAddress,
+        txn: &SignedTransaction,
+    ) -> u64 {
+        let reserved = self.fee_payer_index.get(fee_payer).copied().unwrap_or(0);
+        match self.get_mempool_txn(&txn.sender(), txn.sequence_number()) {
+            Some(current_version) if current_version.get_fee_payer() == Some(*fee_payer) => {
+                reserved.saturating_sub(current_version.get_max_fee())
+            }
+            _ => reserved,
+        }
+    }
+
     /// Insert transaction into TransactionStore. Performs validation checks and updates indexes.
     pub(crate) fn insert(&mut self, txn: MempoolTransaction) -> MempoolStatus {
         let address = txn.get_sender();
@@ -221,6 +243,10 @@ impl TransactionStore {
                     sequence_number.transaction_sequence_number,
                 ),
             );
+            if let Some(fee_payer) = txn.get_fee_payer() {
+                let reserved = self.fee_payer_index.entry(fee_payer).or_insert(0);
+                *reserved = reserved.saturating_add(txn.get_max_fee());
+            }
             let txn_size_bytes = txn.get_estimated_bytes();
             txns.insert(sequence_number.transaction_sequence_number, txn);
             self.size_bytes += txn_size_bytes;
@@ -448,6 +474,14 @@ impl TransactionStore {
         self.timeline_index.remove(txn);
         self.parking_lot_index.remove(txn);
         self.hash_index.remove(&txn.get_committed_hash());
+        if let Some(fee_payer) = txn.get_fee_payer() {
+            if let Some(reserved) = self.fee_payer_index.get_mut(&fee_payer) {
+                *reserved = reserved.saturating_sub(txn.get_max_fee());
+                if *reserved == 0 {
+                    self.fee_payer_index.remove(&fee_payer);
+                }
+            }
+        }
         self.size_bytes -= txn.get_estimated_bytes();
         self.track_indices();
     }
@@ -38,7 +38,9 @@ use std::{
 };
 use storage_interface::state_view::LatestDbStateCheckpointView;
 use tokio::runtime::Handle;
-use vm_validator::vm_validator::{get_account_sequence_number, TransactionValidation};
+use vm_validator::vm_validator::{
+    get_account_balance, get_account_sequence_number, TransactionValidation,
+};
 
 // ============================== //
 //  broadcast_coordinator tasks  //
@@ -265,6 +267,23 @@ where
             })
             .collect::<Vec<_>>()
     });
+    // Sponsored transactions are charged to the fee payer, so its balance is needed as well
+    let fee_payer_balances = IO_POOL.install(|| {
+        transactions
+            .par_iter()
+            .map(|t| {
+                t.fee_payer()
+                    .map(|fee_payer| {
+                        get_account_balance(&state_view, fee_payer).map_err(|e| {
+                            error!(LogSchema::new(LogEntry::DBError).error(&e));
+                            counters::DB_ERROR.inc();
+                            e
+                        })
+                    })
+                    .transpose()
+            })
+            .collect::<Vec<_>>()
+    });
     // Track latency for storage read fetching sequence number
     let storage_read_latency = start_storage_read.elapsed();
     counters::PROCESS_TXN_BREAKDOWN_LATENCY
@@ -275,9 +294,12 @@ where
         .into_iter()
         .enumerate()
         .filter_map(|(idx, t)| {
-            if let Ok(crsn_or_seqno) = seq_numbers[idx] {
+            if let (Ok(crsn_or_seqno), Ok(fee_payer_balance)) =
+                (&seq_numbers[idx], &fee_payer_balances[idx])
+            {
+                let crsn_or_seqno = *crsn_or_seqno;
                 if t.sequence_number() >= crsn_or_seqno.min_seq() {
-                    return Some((t, crsn_or_seqno));
+                    return Some((t, crsn_or_seqno, *fee_payer_balance));
                 } else {
                     statuses.push((
                         t,
@@ -312,9 +334,23 @@ where
     vm_validation_timer.stop_and_record();
     {
         let mut mempool = smp.mempool.lock();
-        for (idx, (transaction, crsn_or_seqno)) in transactions.into_iter().enumerate() {
+        for (idx, (transaction, crsn_or_seqno, fee_payer_balance)) in
+            transactions.into_iter().enumerate()
+        {
             if let Ok(validation_result) = &validation_results[idx] {
                 match validation_result.status() {
+                    None if fee_payer_balance.map_or(false, |balance| {
+                        !mempool.can_fee_payer_afford(&transaction, balance)
+                    }) =>
+                    {
+                        statuses.push((
+                            transaction.clone(),
+                            (
+                                MempoolStatus::new(MempoolStatusCode::VmError),
+                                Some(DiscardedVMStatus::INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE),
+                            ),
+                        ));
+                    }
                     None => {
                         let ranking_score = validation_result.score();
                         let mempool_status = mempool.add_txn(
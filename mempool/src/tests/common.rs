@@ -104,6 +104,33 @@ impl TestTransaction {
             .into_inner()
     }
 
+    pub(crate) fn make_signed_fee_payer_transaction(&self, fee_payer: usize) -> SignedTransaction {
+        let raw_txn = RawTransaction::new_script(
+            TestTransaction::get_address(self.address),
+            self.sequence_number,
+            Script::new(vec![], vec![], vec![]),
+            100,
+            self.gas_price,
+            u64::MAX,
+            ChainId::test(),
+        );
+        let mut seed: [u8; 32] = [0u8; 32];
+        seed[..4].copy_from_slice(&[1, 2, 3, 4]);
+        let mut rng: StdRng = StdRng::from_seed(seed);
+        let privkey = Ed25519PrivateKey::generate(&mut rng);
+        let fee_payer_privkey = Ed25519PrivateKey::generate(&mut rng);
+        raw_txn
+            .sign_fee_payer(
+                &privkey,
+                vec![],
+                vec![],
+                TestTransaction::get_address(fee_payer),
+                &fee_payer_privkey,
+            )
+            .expect("Failed to sign raw transaction.")
+            .into_inner()
+    }
+
     pub(crate) fn get_address(address: usize) -> AccountAddress {
         ACCOUNTS[address]
     }
@@ -769,6 +769,46 @@ fn test_get_transaction_by_hash_after_the_txn_is_updated() {
     assert_eq!(txn_by_new_hash, Some(new_txn));
 }
 
+#[test]
+fn test_fee_payer_balance_accounting() {
+    let mut pool = setup_mempool().0;
+    // Every transaction has a max fee of 100 (max gas amount) * 1 (gas price), paid by account 2.
+    let fee_payer_balance = 250;
+    let txn_0 = TestTransaction::new(0, 0, 1).make_signed_fee_payer_transaction(2);
+    let txn_1 = TestTransaction::new(1, 0, 1).make_signed_fee_payer_transaction(2);
+    let txn_2 = TestTransaction::new(0, 1, 1).make_signed_fee_payer_transaction(2);
+    assert_eq!(txn_0.gas_payer(), TestTransaction::get_address(2));
+
+    assert!(pool.can_fee_payer_afford(&txn_0, fee_payer_balance));
+    add_signed_txn(&mut pool, txn_0).unwrap();
+    assert!(pool.can_fee_payer_afford(&txn_1, fee_payer_balance));
+    add_signed_txn(&mut pool, txn_1).unwrap();
+    // The fee payer can't cover a third sponsored transaction.
+    assert!(!pool.can_fee_payer_afford(&txn_2, fee_payer_balance));
+
+    // Replacing a sponsored transaction only accounts for the new max fee.
+    let txn_0_update = TestTransaction::new(0, 0, 2).make_signed_fee_payer_transaction(2);
+    assert!(!pool.can_fee_payer_afford(&txn_0_update, fee_payer_balance));
+    assert!(pool.can_fee_payer_afford(&txn_0_update, 300));
+
+    // Transactions paid for by their sender don't depend on the fee payer.
+    let own_txn = TestTransaction::new(2, 0, 1).make_signed_transaction();
+    assert!(pool.can_fee_payer_afford(&own_txn, 0));
+    add_signed_txn(&mut pool, own_txn).unwrap();
+
+    // Committing a sponsored transaction advances the sender's sequence number only, and
+    // releases the max fee reserved for it.
+    pool.remove_transaction(&TestTransaction::get_address(0), 0, false);
+    assert!(pool.can_fee_payer_afford(&txn_2, fee_payer_balance));
+    assert!(pool
+        .get_by_hash(
+            TestTransaction::new(2, 0, 1)
+                .make_signed_transaction()
+                .committed_hash()
+        )
+        .is_some());
+}
+
 #[test]
 fn test_bytes_limit() {
     let mut config = NodeConfig::random();
@@ -29,6 +29,7 @@ use network::{
     connectivity_manager::{builder::ConnectivityManagerBuilder, ConnectivityRequest},
     constants::MAX_MESSAGE_SIZE,
     logging::NetworkSchema,
+    peer::ProtocolRateLimits,
     peer_manager::{
         builder::{AuthenticationMode, PeerManagerBuilder},
         ConnectionRequestSender,
@@ -38,7 +39,7 @@ use network::{
         network::{AppConfig, NewNetworkEvents, NewNetworkSender},
     },
 };
-use network_discovery::DiscoveryChangeListener;
+use network_discovery::{peer_exchange, DiscoveryChangeListener};
 use std::{
     clone::Clone,
     collections::{HashMap, HashSet},
@@ -61,6 +62,7 @@ enum State {
 pub struct NetworkBuilder {
     state: State,
     executor: Option<Handle>,
+    chain_id: ChainId,
     time_service: TimeService,
     network_context: NetworkContext,
     discovery_listeners: Option<Vec<DiscoveryChangeListener>>,
@@ -85,11 +87,13 @@ impl NetworkBuilder {
         max_frame_size: usize,
         max_message_size: usize,
         enable_proxy_protocol: bool,
+        enable_upgraded_messaging_protocol: bool,
         network_channel_size: usize,
         max_concurrent_network_reqs: usize,
         inbound_connection_limit: usize,
         inbound_rate_limit_config: Option<RateLimitConfig>,
         outbound_rate_limit_config: Option<RateLimitConfig>,
+        protocol_rate_limits: ProtocolRateLimits,
     ) -> Self {
         // A network cannot exist without a PeerManager
         // TODO:  construct this in create and pass it to new() as a parameter. The complication is manual construction of NetworkBuilder in various tests.
@@ -106,14 +110,17 @@ impl NetworkBuilder {
             max_frame_size,
             max_message_size,
             enable_proxy_protocol,
+            enable_upgraded_messaging_protocol,
             inbound_connection_limit,
             inbound_rate_limit_config,
             outbound_rate_limit_config,
+            protocol_rate_limits,
         );
 
         NetworkBuilder {
             state: State::CREATED,
             executor: None,
+            chain_id,
             time_service,
             network_context,
             discovery_listeners: None,
@@ -147,11 +154,13 @@ impl NetworkBuilder {
             MAX_FRAME_SIZE,
             MAX_MESSAGE_SIZE,
             false, /* Disable proxy protocol */
+            false, /* Disable upgraded messaging protocol */
             NETWORK_CHANNEL_SIZE,
             MAX_CONCURRENT_NETWORK_REQS,
             MAX_INBOUND_CONNECTIONS,
             None,
             None,
+            ProtocolRateLimits::default(),
         );
 
         builder.add_connectivity_manager(
@@ -190,6 +199,11 @@ impl NetworkBuilder {
         let network_context = NetworkContext::new(role, config.network_id, peer_id);
 
         let trusted_peers = Arc::new(RwLock::new(HashMap::new()));
+        let protocol_rate_limits = ProtocolRateLimits::from_config(
+            &config.inbound_protocol_rate_limit_configs,
+            &config.outbound_protocol_rate_limit_configs,
+        )
+        .expect("Invalid protocol rate limit config!");
 
         let mut network_builder = NetworkBuilder::new(
             chain_id,
@@ -202,11 +216,13 @@ impl NetworkBuilder {
             config.max_frame_size,
             config.max_message_size,
             config.enable_proxy_protocol,
+            config.enable_upgraded_messaging_protocol,
             config.network_channel_size,
             config.max_concurrent_network_reqs,
             config.max_inbound_connections,
             config.inbound_rate_limit_config,
             config.outbound_rate_limit_config,
+            protocol_rate_limits,
         );
 
         network_builder.add_connection_monitoring(
@@ -399,6 +415,29 @@ impl NetworkBuilder {
                 *interval_duration,
                 self.time_service.clone(),
             ),
+            DiscoveryMethod::PeerExchange(config) => {
+                assert!(
+                    !self.network_context.network_id().is_validator_network(),
+                    "Peer exchange can't be used on the validator network!"
+                );
+                let (network_sender, network_events) =
+                    self.add_p2p_service(&peer_exchange::network_endpoint_config());
+                DiscoveryChangeListener::peer_exchange(
+                    self.network_context,
+                    conn_mgr_reqs_tx,
+                    config.clone(),
+                    self.time_service.clone(),
+                    network_sender,
+                    network_events,
+                )
+            }
+            DiscoveryMethod::Rest(config) => DiscoveryChangeListener::rest(
+                self.network_context,
+                conn_mgr_reqs_tx,
+                config,
+                self.chain_id,
+                self.time_service.clone(),
+            ),
             DiscoveryMethod::None => return,
         };
 
@@ -35,3 +35,21 @@ pub static NETWORK_KEY_MISMATCH: Lazy<IntGaugeVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static PEER_EXCHANGE_CANDIDATES: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_network_peer_exchange_candidates",
+        "Gauge of the number of candidate peers learned through peer exchange",
+        &["role_type", "network_id", "peer_id"]
+    )
+    .unwrap()
+});
+
+pub static PENDING_PEER_EXCHANGE_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_pending_peer_exchange_events",
+        "Number of pending peer exchange events by state",
+        &["state"]
+    )
+    .unwrap()
+});
@@ -1,11 +1,24 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{counters::DISCOVERY_COUNTS, file::FileStream, validator_set::ValidatorSetStream};
-use aptos_config::{config::PeerSet, network_id::NetworkContext};
+use crate::{
+    counters::DISCOVERY_COUNTS,
+    file::FileStream,
+    peer_exchange::{
+        PeerExchange, PeerExchangeNetworkEvents, PeerExchangeNetworkSender, PeerExchangeStream,
+    },
+    rest::RestStream,
+    validator_set::ValidatorSetStream,
+};
+use aptos_config::{
+    config::{PeerExchangeConfig, PeerSet, RestDiscovery},
+    network_id::NetworkContext,
+};
 use aptos_crypto::x25519;
 use aptos_logger::prelude::*;
+use aptos_rest_client::error::RestError;
 use aptos_time_service::TimeService;
+use aptos_types::chain_id::ChainId;
 use event_notifications::ReconfigNotificationListener;
 use futures::{Stream, StreamExt};
 use network::{
@@ -23,12 +36,16 @@ use tokio::runtime::Handle;
 
 mod counters;
 mod file;
+pub mod peer_exchange;
+mod rest;
 mod validator_set;
 
 #[derive(Debug)]
 pub enum DiscoveryError {
     IO(std::io::Error),
     Parsing(String),
+    Rest(RestError),
+    Verification(String),
 }
 
 /// A union type for all implementations of `DiscoveryChangeListenerTrait`
@@ -42,6 +59,8 @@ pub struct DiscoveryChangeListener {
 enum DiscoveryChangeStream {
     ValidatorSet(ValidatorSetStream),
     File(FileStream),
+    PeerExchange(PeerExchangeStream),
+    Rest(RestStream),
 }
 
 impl Stream for DiscoveryChangeStream {
@@ -51,6 +70,8 @@ impl Stream for DiscoveryChangeStream {
         match self.get_mut() {
             Self::ValidatorSet(stream) => Pin::new(stream).poll_next(cx),
             Self::File(stream) => Pin::new(stream).poll_next(cx),
+            Self::PeerExchange(stream) => Pin::new(stream).poll_next(cx),
+            Self::Rest(stream) => Pin::new(stream).poll_next(cx),
         }
     }
 }
@@ -95,7 +116,58 @@ impl DiscoveryChangeListener {
         }
     }
 
-    pub fn start(self, executor: &Handle) {
+    pub fn peer_exchange(
+        network_context: NetworkContext,
+        update_channel: channel::Sender<ConnectivityRequest>,
+        config: PeerExchangeConfig,
+        time_service: TimeService,
+        network_sender: PeerExchangeNetworkSender,
+        network_events: PeerExchangeNetworkEvents,
+    ) -> Self {
+        let source_stream = DiscoveryChangeStream::PeerExchange(PeerExchange::new(
+            network_context,
+            time_service,
+            config,
+            network_sender,
+            network_events,
+        ));
+        DiscoveryChangeListener {
+            discovery_source: DiscoverySource::PeerExchange,
+            network_context,
+            update_channel,
+            source_stream,
+        }
+    }
+
+    pub fn rest(
+        network_context: NetworkContext,
+        update_channel: channel::Sender<ConnectivityRequest>,
+        config: &RestDiscovery,
+        chain_id: ChainId,
+        time_service: TimeService,
+    ) -> Self {
+        let source_stream = DiscoveryChangeStream::Rest(RestStream::new(
+            network_context,
+            config.url.clone(),
+            chain_id,
+            config.waypoint,
+            Duration::from_secs(config.interval_secs),
+            time_service,
+        ));
+        DiscoveryChangeListener {
+            discovery_source: DiscoverySource::Rest,
+            network_context,
+            update_channel,
+            source_stream,
+        }
+    }
+
+    pub fn start(mut self, executor: &Handle) {
+        if let DiscoveryChangeStream::PeerExchange(stream) = &mut self.source_stream {
+            if let Some(peer_exchange) = stream.take_actor() {
+                spawn_named!("PeerExchange", executor, peer_exchange.start());
+            }
+        }
         spawn_named!("DiscoveryChangeListener", executor, Box::pin(self).run());
     }
 
@@ -0,0 +1,781 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Peer exchange discovery for public networks.
+//!
+//! Every `interval_ms` a few random connected peers are asked for the addresses of the peers
+//! they dialed themselves. Since a node only shares addresses it managed to connect to, and the
+//! noise handshake binds the address to the peer's key, shared addresses are known to have worked
+//! at least once for the reporter.
+//!
+//! Reported addresses are scored by the number of distinct peers reporting them, with a bonus for
+//! the ones we connected to ourselves. To limit eclipse attacks, each response is truncated,
+//! the number of tracked candidates is bounded (existing candidates are never evicted by new
+//! reports, they only expire), a single reporter can only add a limited number of candidates
+//! nobody else vouches for, and the peers handed to the `ConnectivityManager` are capped per
+//! subnet or DNS domain. Peers we connected to can be persisted to a file so they survive restarts.
+
+use crate::{
+    counters::{DISCOVERY_COUNTS, PEER_EXCHANGE_CANDIDATES, PENDING_PEER_EXCHANGE_NETWORK_EVENTS},
+    DiscoveryError,
+};
+use aptos_config::{
+    config::{Peer, PeerExchangeConfig, PeerRole, PeerSet},
+    network_id::NetworkContext,
+};
+use aptos_logger::prelude::*;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::{
+    network_address::{parse_dns_tcp, NetworkAddress},
+    PeerId,
+};
+use bytes::Bytes;
+use channel::{aptos_channel, message_queues::QueueStyle};
+use futures::{
+    channel::{mpsc, oneshot},
+    stream::{FuturesUnordered, StreamExt},
+    Stream,
+};
+use netcore::transport::ConnectionOrigin;
+use network::{
+    constants::NETWORK_CHANNEL_SIZE,
+    counters::inc_by_with_context,
+    logging::NetworkSchema,
+    protocols::network::{AppConfig, Event, NetworkEvents, NetworkSender, RpcError},
+    transport::ConnectionMetadata,
+    ProtocolId,
+};
+use rand::seq::IteratorRandom;
+use serde::{Deserialize, Serialize};
+use short_hex_str::AsShortHexStr;
+use std::{
+    cmp::{min, Reverse},
+    collections::{hash_map::Entry, HashMap, HashSet},
+    net::IpAddr,
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// Maximum number of distinct reporters counted towards a candidate's score, so colluding peers
+/// can't push an address arbitrarily high.
+const MAX_SCORED_SOURCES: usize = 8;
+/// Score bonus of candidates we connected to ourselves.
+const VERIFIED_BONUS: usize = 2 * MAX_SCORED_SOURCES;
+/// Number of rounds an unverified candidate is kept without being reported again.
+const CANDIDATE_TTL_ROUNDS: u32 = 10;
+/// Number of rounds a verified candidate is kept without being connected to or reported again.
+const VERIFIED_CANDIDATE_TTL_ROUNDS: u32 = 100;
+
+pub type PeerExchangeNetworkEvents = NetworkEvents<PeerExchangeMsg>;
+pub type PeerExchangeNetworkSender = NetworkSender<PeerExchangeMsg>;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum PeerExchangeMsg {
+    /// Asks for the addresses of the peers the remote dialed itself
+    GetPeers,
+    /// The addresses of the peers the sender is connected to through outbound connections
+    Peers(Vec<(PeerId, NetworkAddress)>),
+}
+
+/// Configuration for the network endpoints to support peer exchange.
+pub fn network_endpoint_config() -> AppConfig {
+    AppConfig::p2p(
+        [ProtocolId::PeerExchangeRpc],
+        aptos_channel::Config::new(NETWORK_CHANNEL_SIZE)
+            .queue_style(QueueStyle::LIFO)
+            .counters(&PENDING_PEER_EXCHANGE_NETWORK_EVENTS),
+    )
+}
+
+/// Returns true if the address can be dialed by any node on the internet: it must be a full
+/// AptosNet address (including the noise key) and must not point to a private or local IP.
+fn is_valid_public_addr(addr: &NetworkAddress) -> bool {
+    if !addr.is_aptosnet_addr() || addr.find_noise_proto().is_none() {
+        return false;
+    }
+    match addr.find_ip_addr() {
+        Some(IpAddr::V4(ip)) => {
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation())
+        }
+        Some(IpAddr::V6(ip)) => {
+            let first_segment = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                // Unique local (fc00::/7) and link local (fe80::/10) addresses
+                || (first_segment & 0xfe00) == 0xfc00
+                || (first_segment & 0xffc0) == 0xfe80)
+        }
+        // DNS addresses
+        None => true,
+    }
+}
+
+/// Groups addresses by /24 (IPv4) or /48 (IPv6) subnet. DNS addresses are grouped by their last
+/// two labels, so neither different ports and keys nor subdomains of the same domain escape the
+/// per subnet limit.
+fn subnet_of(addr: &NetworkAddress) -> String {
+    match addr.find_ip_addr() {
+        Some(IpAddr::V4(ip)) => {
+            let octets = ip.octets();
+            format!("{}.{}.{}", octets[0], octets[1], octets[2])
+        }
+        Some(IpAddr::V6(ip)) => {
+            let segments = ip.segments();
+            format!("{:x}:{:x}:{:x}", segments[0], segments[1], segments[2])
+        }
+        None => match parse_dns_tcp(addr.as_slice()) {
+            Some(((_, name, _), _)) => {
+                let name = name.as_ref().trim_end_matches('.').to_ascii_lowercase();
+                let labels: Vec<_> = name.rsplitn(3, '.').take(2).collect();
+                format!(
+                    "dns:{}",
+                    labels.into_iter().rev().collect::<Vec<_>>().join(".")
+                )
+            }
+            None => addr.to_string(),
+        },
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Candidate {
+    addr: NetworkAddress,
+    /// The connected peers that reported this address
+    sources: HashSet<PeerId>,
+    /// Whether we connected to this address ourselves
+    verified: bool,
+    last_seen: Instant,
+}
+
+impl Candidate {
+    fn new(addr: NetworkAddress, verified: bool, now: Instant) -> Self {
+        Self {
+            addr,
+            sources: HashSet::new(),
+            verified,
+            last_seen: now,
+        }
+    }
+
+    fn score(&self) -> usize {
+        min(self.sources.len(), MAX_SCORED_SOURCES) + if self.verified { VERIFIED_BONUS } else { 0 }
+    }
+}
+
+/// The scored set of addresses learned through peer exchange.
+pub(crate) struct AddressBook {
+    self_peer_id: PeerId,
+    config: PeerExchangeConfig,
+    candidates: HashMap<PeerId, Candidate>,
+}
+
+impl AddressBook {
+    pub(crate) fn new(self_peer_id: PeerId, config: PeerExchangeConfig) -> Self {
+        Self {
+            self_peer_id,
+            config,
+            candidates: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// Records the peers reported by `source`, returns the number of rejected entries.
+    pub(crate) fn add_reported(
+        &mut self,
+        source: PeerId,
+        peers: Vec<(PeerId, NetworkAddress)>,
+        now: Instant,
+    ) -> usize {
+        let num_reported = peers.len();
+        let mut rejected = num_reported.saturating_sub(self.config.max_peers_per_response);
+        let mut num_sole_sourced = self.num_sole_sourced(&source);
+        for (peer_id, addr) in peers.into_iter().take(self.config.max_peers_per_response) {
+            if peer_id == self.self_peer_id || peer_id == source || !is_valid_public_addr(&addr) {
+                rejected += 1;
+                continue;
+            }
+            let is_full = self.candidates.len() >= self.config.max_candidate_peers;
+            match self.candidates.entry(peer_id) {
+                Entry::Occupied(mut entry) => {
+                    let candidate = entry.get_mut();
+                    if candidate.addr == addr {
+                        candidate.sources.insert(source);
+                        candidate.last_seen = now;
+                    } else if !candidate.verified && candidate.sources.len() <= 1 {
+                        // Conflicting reports for a weakly supported peer, keep the latest one
+                        if !candidate.sources.contains(&source) {
+                            if num_sole_sourced >= self.config.max_candidates_per_source {
+                                rejected += 1;
+                                continue;
+                            }
+                            num_sole_sourced += 1;
+                        }
+                        let mut replacement = Candidate::new(addr, false, now);
+                        replacement.sources.insert(source);
+                        *candidate = replacement;
+                    } else {
+                        rejected += 1;
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    // Existing candidates are never evicted in favor of new reports, otherwise
+                    // a single malicious peer could flush the book with its own addresses.
+                    if is_full || num_sole_sourced >= self.config.max_candidates_per_source {
+                        rejected += 1;
+                        continue;
+                    }
+                    num_sole_sourced += 1;
+                    let mut candidate = Candidate::new(addr, false, now);
+                    candidate.sources.insert(source);
+                    entry.insert(candidate);
+                }
+            }
+        }
+        rejected
+    }
+
+    /// The number of unverified candidates only `source` vouches for.
+    fn num_sole_sourced(&self, source: &PeerId) -> usize {
+        self.candidates
+            .values()
+            .filter(|candidate| {
+                !candidate.verified
+                    && candidate.sources.len() == 1
+                    && candidate.sources.contains(source)
+            })
+            .count()
+    }
+
+    /// Records a successful outbound connection, which proves the address is reachable.
+    pub(crate) fn mark_connected(&mut self, peer_id: PeerId, addr: NetworkAddress, now: Instant) {
+        if !is_valid_public_addr(&addr) {
+            return;
+        }
+        let candidate = self
+            .candidates
+            .entry(peer_id)
+            .or_insert_with(|| Candidate::new(addr.clone(), true, now));
+        candidate.addr = addr;
+        candidate.verified = true;
+        candidate.last_seen = now;
+    }
+
+    /// Adds previously verified peers, e.g. loaded from the peer file.
+    pub(crate) fn add_verified(&mut self, peers: PeerSet, now: Instant) {
+        for (peer_id, peer) in peers {
+            if let Some(addr) = peer.addresses.into_iter().find(is_valid_public_addr) {
+                self.candidates
+                    .entry(peer_id)
+                    .or_insert_with(|| Candidate::new(addr, true, now));
+            }
+        }
+    }
+
+    /// Drops candidates that haven't been seen for too long.
+    pub(crate) fn expire(&mut self, now: Instant, interval: Duration) {
+        self.candidates.retain(|_, candidate| {
+            let ttl_rounds = if candidate.verified {
+                VERIFIED_CANDIDATE_TTL_ROUNDS
+            } else {
+                CANDIDATE_TTL_ROUNDS
+            };
+            now.saturating_duration_since(candidate.last_seen) < interval * ttl_rounds
+        });
+    }
+
+    /// The best scored candidates, limited in number and per subnet.
+    pub(crate) fn discovered_peers(&self) -> PeerSet {
+        let mut candidates: Vec<_> = self.candidates.iter().collect();
+        candidates.sort_by_key(|(peer_id, candidate)| (Reverse(candidate.score()), **peer_id));
+
+        let mut peers_per_subnet: HashMap<String, usize> = HashMap::new();
+        let mut discovered_peers = PeerSet::new();
+        for (peer_id, candidate) in candidates {
+            if discovered_peers.len() >= self.config.max_discovered_peers {
+                break;
+            }
+            let count = peers_per_subnet
+                .entry(subnet_of(&candidate.addr))
+                .or_default();
+            if *count >= self.config.max_peers_per_subnet {
+                continue;
+            }
+            *count += 1;
+            discovered_peers.insert(
+                *peer_id,
+                Peer::from_addrs(PeerRole::Upstream, vec![candidate.addr.clone()]),
+            );
+        }
+        discovered_peers
+    }
+
+    /// The candidates we connected to ourselves.
+    pub(crate) fn verified_peers(&self) -> PeerSet {
+        self.candidates
+            .iter()
+            .filter(|(_, candidate)| candidate.verified)
+            .map(|(peer_id, candidate)| {
+                (
+                    *peer_id,
+                    Peer::from_addrs(PeerRole::Upstream, vec![candidate.addr.clone()]),
+                )
+            })
+            .collect()
+    }
+}
+
+/// The stream of discovered peers consumed by the `DiscoveryChangeListener`. It owns the
+/// `PeerExchange` actor until the listener is started.
+pub struct PeerExchangeStream {
+    actor: Option<Box<PeerExchange>>,
+    updates_rx: mpsc::Receiver<PeerSet>,
+}
+
+impl PeerExchangeStream {
+    pub(crate) fn take_actor(&mut self) -> Option<Box<PeerExchange>> {
+        self.actor.take()
+    }
+}
+
+impl Stream for PeerExchangeStream {
+    type Item = Result<PeerSet, DiscoveryError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.updates_rx)
+            .poll_next(cx)
+            .map(|maybe_peers| maybe_peers.map(Ok))
+    }
+}
+
+/// The actor serving and sending peer exchange requests.
+pub struct PeerExchange {
+    network_context: NetworkContext,
+    time_service: TimeService,
+    config: PeerExchangeConfig,
+    network_sender: PeerExchangeNetworkSender,
+    network_events: PeerExchangeNetworkEvents,
+    connected: HashMap<PeerId, ConnectionMetadata>,
+    address_book: AddressBook,
+    updates_tx: mpsc::Sender<PeerSet>,
+}
+
+impl PeerExchange {
+    pub(crate) fn new(
+        network_context: NetworkContext,
+        time_service: TimeService,
+        config: PeerExchangeConfig,
+        network_sender: PeerExchangeNetworkSender,
+        network_events: PeerExchangeNetworkEvents,
+    ) -> PeerExchangeStream {
+        let (updates_tx, updates_rx) = mpsc::channel(1);
+        let actor = Self {
+            network_context,
+            time_service,
+            address_book: AddressBook::new(network_context.peer_id(), config.clone()),
+            config,
+            network_sender,
+            network_events,
+            connected: HashMap::new(),
+            updates_tx,
+        };
+        PeerExchangeStream {
+            actor: Some(Box::new(actor)),
+            updates_rx,
+        }
+    }
+
+    pub async fn start(mut self) {
+        info!(
+            NetworkSchema::new(&self.network_context),
+            "{} Peer exchange actor started", self.network_context
+        );
+        if let Some(path) = self.config.peer_file.clone() {
+            if path.exists() {
+                match load_peer_file(&path) {
+                    Ok(peers) => self
+                        .address_book
+                        .add_verified(peers, self.time_service.now()),
+                    Err(error) => warn!(
+                        NetworkSchema::new(&self.network_context),
+                        "{} Unable to load peer exchange file {:?}: {:?}",
+                        self.network_context,
+                        path,
+                        error
+                    ),
+                }
+                self.publish();
+            }
+        }
+
+        let interval = Duration::from_millis(self.config.interval_ms);
+        let ticker = self.time_service.interval(interval);
+        tokio::pin!(ticker);
+        let mut pending_requests = FuturesUnordered::new();
+
+        loop {
+            futures::select! {
+                maybe_event = self.network_events.next() => {
+                    // Shutdown when this network instance shuts down
+                    let event = match maybe_event {
+                        Some(event) => event,
+                        None => break,
+                    };
+                    self.handle_event(event);
+                }
+                _ = ticker.select_next_some() => {
+                    self.refresh_connected();
+                    self.address_book.expire(self.time_service.now(), interval);
+                    for peer_id in self.choose_peers_to_query() {
+                        inc_by_with_context(&DISCOVERY_COUNTS, &self.network_context, "peer_exchange_request", 1);
+                        pending_requests.push(Self::request_peers(
+                            self.network_sender.clone(),
+                            peer_id,
+                            Duration::from_millis(self.config.request_timeout_ms),
+                        ));
+                    }
+                    self.publish();
+                }
+                (peer_id, result) = pending_requests.select_next_some() => {
+                    self.handle_response(peer_id, result);
+                }
+            }
+        }
+        warn!(
+            NetworkSchema::new(&self.network_context),
+            "{} Peer exchange actor terminated", self.network_context
+        );
+    }
+
+    fn handle_event(&mut self, event: Event<PeerExchangeMsg>) {
+        match event {
+            Event::NewPeer(metadata) => {
+                if metadata.origin == ConnectionOrigin::Outbound {
+                    self.address_book.mark_connected(
+                        metadata.remote_peer_id,
+                        metadata.addr.clone(),
+                        self.time_service.now(),
+                    );
+                }
+                self.connected.insert(metadata.remote_peer_id, metadata);
+            }
+            Event::LostPeer(metadata) => {
+                self.connected.remove(&metadata.remote_peer_id);
+            }
+            Event::RpcRequest(peer_id, PeerExchangeMsg::GetPeers, protocol, res_tx) => {
+                self.handle_get_peers(peer_id, protocol, res_tx)
+            }
+            Event::RpcRequest(peer_id, msg, _, _) | Event::Message(peer_id, msg) => {
+                warn!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                    "{} Unexpected peer exchange message from {}: {:?}",
+                    self.network_context,
+                    peer_id.short_str(),
+                    msg
+                );
+            }
+        }
+    }
+
+    /// Answers with the outbound connections, whose addresses have been proven to work.
+    fn handle_get_peers(
+        &self,
+        peer_id: PeerId,
+        protocol: ProtocolId,
+        res_tx: oneshot::Sender<Result<Bytes, RpcError>>,
+    ) {
+        let peers: Vec<_> = self
+            .connected
+            .values()
+            .filter(|metadata| {
+                metadata.origin == ConnectionOrigin::Outbound
+                    && metadata.remote_peer_id != peer_id
+                    && is_valid_public_addr(&metadata.addr)
+            })
+            .map(|metadata| (metadata.remote_peer_id, metadata.addr.clone()))
+            .take(self.config.max_peers_per_response)
+            .collect();
+        match protocol.to_bytes(&PeerExchangeMsg::Peers(peers)) {
+            Ok(message) => {
+                let _ = res_tx.send(Ok(message.into()));
+            }
+            Err(error) => warn!(
+                NetworkSchema::new(&self.network_context),
+                "{} Unable to serialize peer exchange response: {:?}", self.network_context, error
+            ),
+        }
+    }
+
+    fn handle_response(&mut self, peer_id: PeerId, result: Result<PeerExchangeMsg, RpcError>) {
+        match result {
+            Ok(PeerExchangeMsg::Peers(peers)) => {
+                let rejected =
+                    self.address_book
+                        .add_reported(peer_id, peers, self.time_service.now());
+                inc_by_with_context(
+                    &DISCOVERY_COUNTS,
+                    &self.network_context,
+                    "peer_exchange_rejected_address",
+                    rejected as u64,
+                );
+            }
+            Ok(msg) => warn!(
+                NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                "{} Unexpected peer exchange response from {}: {:?}",
+                self.network_context,
+                peer_id.short_str(),
+                msg
+            ),
+            Err(error) => {
+                inc_by_with_context(
+                    &DISCOVERY_COUNTS,
+                    &self.network_context,
+                    "peer_exchange_request_failure",
+                    1,
+                );
+                debug!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                    "{} Peer exchange request to {} failed: {:?}",
+                    self.network_context,
+                    peer_id.short_str(),
+                    error
+                );
+            }
+        }
+    }
+
+    async fn request_peers(
+        network_sender: PeerExchangeNetworkSender,
+        peer_id: PeerId,
+        timeout: Duration,
+    ) -> (PeerId, Result<PeerExchangeMsg, RpcError>) {
+        let result = network_sender
+            .send_rpc(
+                peer_id,
+                ProtocolId::PeerExchangeRpc,
+                PeerExchangeMsg::GetPeers,
+                timeout,
+            )
+            .await;
+        (peer_id, result)
+    }
+
+    /// Keeps the peers we're still connected to from expiring.
+    fn refresh_connected(&mut self) {
+        let now = self.time_service.now();
+        for metadata in self.connected.values() {
+            if metadata.origin == ConnectionOrigin::Outbound {
+                self.address_book.mark_connected(
+                    metadata.remote_peer_id,
+                    metadata.addr.clone(),
+                    now,
+                );
+            }
+        }
+    }
+
+    fn choose_peers_to_query(&self) -> Vec<PeerId> {
+        self.connected
+            .values()
+            .filter(|metadata| {
+                metadata
+                    .application_protocols
+                    .contains(ProtocolId::PeerExchangeRpc)
+            })
+            .map(|metadata| metadata.remote_peer_id)
+            .choose_multiple(&mut rand::thread_rng(), self.config.peers_per_round)
+    }
+
+    /// Sends the current selection to the ConnectivityManager and persists the verified peers.
+    fn publish(&mut self) {
+        PEER_EXCHANGE_CANDIDATES
+            .with_label_values(&[
+                self.network_context.role().as_str(),
+                self.network_context.network_id().as_str(),
+                self.network_context.peer_id().short_str().as_str(),
+            ])
+            .set(self.address_book.len() as i64);
+
+        // If the listener is still busy with the previous update, it'll pick up the next one
+        let _ = self
+            .updates_tx
+            .try_send(self.address_book.discovered_peers());
+
+        if let Some(path) = &self.config.peer_file {
+            if let Err(error) = store_peer_file(path, &self.address_book.verified_peers()) {
+                warn!(
+                    NetworkSchema::new(&self.network_context),
+                    "{} Unable to store peer exchange file {:?}: {:?}",
+                    self.network_context,
+                    path,
+                    error
+                );
+            }
+        }
+    }
+}
+
+fn load_peer_file(path: &Path) -> Result<PeerSet, DiscoveryError> {
+    let contents = std::fs::read_to_string(path).map_err(DiscoveryError::IO)?;
+    serde_yaml::from_str(&contents).map_err(|err| DiscoveryError::Parsing(err.to_string()))
+}
+
+fn store_peer_file(path: &Path, peers: &PeerSet) -> Result<(), DiscoveryError> {
+    let contents =
+        serde_yaml::to_vec(peers).map_err(|err| DiscoveryError::Parsing(err.to_string()))?;
+    std::fs::write(path, contents).map_err(DiscoveryError::IO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn config() -> PeerExchangeConfig {
+        PeerExchangeConfig {
+            max_peers_per_response: 3,
+            max_candidate_peers: 4,
+            max_candidates_per_source: 3,
+            max_discovered_peers: 3,
+            max_peers_per_subnet: 1,
+            ..PeerExchangeConfig::default()
+        }
+    }
+
+    fn addr(ip: &str) -> NetworkAddress {
+        NetworkAddress::from_str(&format!("/ip4/{}/tcp/6182/noise-ik/080e287879c918794170e258bfaddd75acac5b3e350419044655e4983a487120/handshake/0", ip)).unwrap()
+    }
+
+    #[test]
+    fn test_public_addresses() {
+        assert!(is_valid_public_addr(&addr("1.2.3.4")));
+        assert!(!is_valid_public_addr(&addr("10.0.0.1")));
+        assert!(!is_valid_public_addr(&addr("127.0.0.1")));
+        assert!(!is_valid_public_addr(&addr("192.168.1.1")));
+        // Addresses without a noise key can't be verified
+        assert!(!is_valid_public_addr(
+            &NetworkAddress::from_str("/ip4/1.2.3.4/tcp/6182").unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_response_and_candidate_limits() {
+        let now = Instant::now();
+        let mut book = AddressBook::new(PeerId::random(), config());
+        let source = PeerId::random();
+
+        // Only the first `max_peers_per_response` entries are considered
+        let reported: Vec<_> = (1..=5)
+            .map(|i| (PeerId::random(), addr(&format!("1.1.{}.1", i))))
+            .collect();
+        assert_eq!(book.add_reported(source, reported, now), 2);
+        assert_eq!(book.len(), 3);
+
+        // Existing candidates aren't evicted once the book is full
+        let reported: Vec<_> = (1..=3)
+            .map(|i| (PeerId::random(), addr(&format!("2.2.{}.1", i))))
+            .collect();
+        assert_eq!(book.add_reported(PeerId::random(), reported, now), 2);
+        assert_eq!(book.len(), 4);
+
+        // Unverified candidates expire, verified ones are kept longer
+        let verified = PeerId::random();
+        book.mark_connected(verified, addr("3.3.3.3"), now);
+        let interval = Duration::from_secs(1);
+        book.expire(now + interval * CANDIDATE_TTL_ROUNDS, interval);
+        assert_eq!(book.len(), 1);
+        assert!(book.verified_peers().contains_key(&verified));
+    }
+
+    #[test]
+    fn test_candidates_per_source_limit() {
+        let now = Instant::now();
+        let mut book = AddressBook::new(PeerId::random(), config());
+        let source = PeerId::random();
+        let reported: Vec<_> = (1..=3)
+            .map(|i| (PeerId::random(), addr(&format!("1.1.{}.1", i))))
+            .collect();
+        assert_eq!(book.add_reported(source, reported, now), 0);
+
+        // The same reporter can't add more candidates nobody else vouches for
+        let other = PeerId::random();
+        assert_eq!(
+            book.add_reported(source, vec![(other, addr("2.2.2.2"))], now),
+            1
+        );
+        assert_eq!(book.len(), 3);
+
+        // Once another peer vouches for one of its candidates, there's room again
+        let (peer_id, peer_addr) = book
+            .candidates
+            .iter()
+            .map(|(peer_id, candidate)| (*peer_id, candidate.addr.clone()))
+            .next()
+            .unwrap();
+        book.add_reported(PeerId::random(), vec![(peer_id, peer_addr)], now);
+        assert_eq!(
+            book.add_reported(source, vec![(other, addr("2.2.2.2"))], now),
+            0
+        );
+        assert_eq!(book.len(), 4);
+    }
+
+    #[test]
+    fn test_dns_subnets() {
+        let dns_addr = |name: &str, key: &str| {
+            NetworkAddress::from_str(&format!(
+                "/dns/{}/tcp/6182/noise-ik/{}/handshake/0",
+                name, key
+            ))
+            .unwrap()
+        };
+        let key_1 = "080e287879c918794170e258bfaddd75acac5b3e350419044655e4983a487120";
+        let key_2 = "ca575a1cc3e1d9ba2f9a0d4ae6a9d7e5a1e0a8f6d2b5b7a1c3e2d4f6a8b0c2d4";
+        assert_eq!(
+            subnet_of(&dns_addr("node.example.com", key_1)),
+            subnet_of(&dns_addr("node.example.com", key_2))
+        );
+        assert_eq!(
+            subnet_of(&dns_addr("a.Example.com", key_1)),
+            subnet_of(&dns_addr("b.example.com.", key_1))
+        );
+        assert_ne!(
+            subnet_of(&dns_addr("node.example.com", key_1)),
+            subnet_of(&dns_addr("node.example.org", key_1))
+        );
+    }
+
+    #[test]
+    fn test_scoring_and_subnet_limit() {
+        let now = Instant::now();
+        let mut book = AddressBook::new(PeerId::random(), config());
+        let (popular, lonely, same_subnet, verified) = (
+            PeerId::random(),
+            PeerId::random(),
+            PeerId::random(),
+            PeerId::random(),
+        );
+        book.add_reported(PeerId::random(), vec![(popular, addr("1.1.1.1"))], now);
+        book.add_reported(PeerId::random(), vec![(popular, addr("1.1.1.1"))], now);
+        book.add_reported(PeerId::random(), vec![(same_subnet, addr("1.1.1.2"))], now);
+        book.add_reported(PeerId::random(), vec![(lonely, addr("4.4.4.4"))], now);
+        book.mark_connected(verified, addr("5.5.5.5"), now);
+
+        let discovered = book.discovered_peers();
+        assert_eq!(discovered.len(), 3);
+        assert!(discovered.contains_key(&verified));
+        assert!(discovered.contains_key(&popular));
+        assert!(discovered.contains_key(&lonely));
+        // Only one peer per subnet is allowed, and the better supported one wins
+        assert!(!discovered.contains_key(&same_subnet));
+    }
+}
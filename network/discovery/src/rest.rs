@@ -0,0 +1,382 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters::DISCOVERY_COUNTS, validator_set::extract_validator_set_updates, DiscoveryError,
+};
+use aptos_config::{config::PeerSet, network_id::NetworkContext};
+use aptos_crypto::HashValue;
+use aptos_rest_client::{
+    aptos_api_types::TransactionData, error::RestError, Client, Response, State,
+};
+use aptos_time_service::{Interval, TimeService, TimeServiceTrait};
+use aptos_types::{
+    account_config::CORE_CODE_ADDRESS,
+    block_info::BlockInfo,
+    chain_id::ChainId,
+    epoch_state::EpochState,
+    ledger_info::LedgerInfo,
+    on_chain_config::{ConfigurationResource, OnChainConfig, ValidatorSet},
+    timestamp::TimestampResource,
+    transaction::Version,
+    waypoint::Waypoint,
+};
+use futures::{future::BoxFuture, Future, Stream};
+use move_deps::move_core_types::move_resource::MoveStructType;
+use network::counters::inc_by_with_context;
+use serde::de::DeserializeOwned;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use url::Url;
+
+/// The latest validator set, along with the ledger info at the waypoint version if it still has
+/// to be checked against the waypoint.
+type ValidatorSetResponse = Result<(Option<LedgerInfo>, Response<ValidatorSet>), RestError>;
+
+/// Periodically reads the `ValidatorSet` resource from the REST API of a trusted node.
+///
+/// The REST API doesn't serve signed ledger infos, so the endpoint itself has to be trusted.
+/// Responses are still checked against what we know locally: they have to come from our chain,
+/// must not go back in time, and the ledger the endpoint serves must match the configured
+/// waypoint. The latter requires the endpoint to still have the state at the waypoint version.
+pub struct RestStream {
+    network_context: NetworkContext,
+    rest_client: Client,
+    chain_id: ChainId,
+    waypoint: Option<Waypoint>,
+    /// Whether the ledger served by the endpoint has been checked against the waypoint
+    waypoint_verified: bool,
+    interval: Pin<Box<Interval>>,
+    pending_request: Option<BoxFuture<'static, ValidatorSetResponse>>,
+    /// (epoch, version) of the latest accepted response
+    latest_state: Option<(u64, u64)>,
+}
+
+impl RestStream {
+    pub(crate) fn new(
+        network_context: NetworkContext,
+        url: Url,
+        chain_id: ChainId,
+        waypoint: Option<Waypoint>,
+        interval_duration: Duration,
+        time_service: TimeService,
+    ) -> Self {
+        RestStream {
+            network_context,
+            rest_client: Client::new(url),
+            chain_id,
+            waypoint,
+            waypoint_verified: false,
+            interval: Box::pin(time_service.interval(interval_duration)),
+            pending_request: None,
+            latest_state: None,
+        }
+    }
+
+    fn request_validator_set(&self) -> BoxFuture<'static, ValidatorSetResponse> {
+        let client = self.rest_client.clone();
+        let unverified_waypoint = self.waypoint.filter(|_| !self.waypoint_verified);
+        Box::pin(async move {
+            let waypoint_ledger_info = match unverified_waypoint {
+                Some(waypoint) => {
+                    Some(fetch_epoch_ending_ledger_info(&client, waypoint.version()).await?)
+                }
+                None => None,
+            };
+            let validator_set = client
+                .get_account_resource_bcs::<ValidatorSet>(
+                    CORE_CODE_ADDRESS,
+                    &validator_set_resource_type(),
+                )
+                .await?;
+            Ok((waypoint_ledger_info, validator_set))
+        })
+    }
+
+    /// Checks that the ledger served by the endpoint matches the configured waypoint. This only
+    /// has to succeed once, the ledger can't change history afterwards.
+    fn verify_waypoint(&mut self, ledger_info: Option<&LedgerInfo>) -> Result<(), DiscoveryError> {
+        let waypoint = match self.waypoint {
+            Some(waypoint) if !self.waypoint_verified => waypoint,
+            _ => return Ok(()),
+        };
+        let ledger_info = ledger_info.ok_or_else(|| {
+            DiscoveryError::Verification(format!(
+                "Missing ledger info to verify waypoint {}",
+                waypoint
+            ))
+        })?;
+        waypoint.verify(ledger_info).map_err(|error| {
+            DiscoveryError::Verification(format!(
+                "Ledger doesn't match waypoint {}: {}",
+                waypoint, error
+            ))
+        })?;
+        self.waypoint_verified = true;
+        Ok(())
+    }
+
+    /// Checks that the ledger the response was read from is one we can trust.
+    fn verify_state(&mut self, state: &State) -> Result<(), DiscoveryError> {
+        if state.chain_id != self.chain_id.id() {
+            return Err(DiscoveryError::Verification(format!(
+                "Chain id mismatch, expected: {}, received: {}",
+                self.chain_id, state.chain_id
+            )));
+        }
+        if let Some(waypoint) = self.waypoint {
+            if state.version < waypoint.version() {
+                return Err(DiscoveryError::Verification(format!(
+                    "Ledger version {} is behind waypoint {}",
+                    state.version, waypoint
+                )));
+            }
+        }
+        if let Some((epoch, version)) = self.latest_state {
+            if (state.epoch, state.version) < (epoch, version) {
+                return Err(DiscoveryError::Verification(format!(
+                    "Ledger state (epoch: {}, version: {}) is older than previous (epoch: {}, version: {})",
+                    state.epoch, state.version, epoch, version
+                )));
+            }
+        }
+        self.latest_state = Some((state.epoch, state.version));
+        Ok(())
+    }
+
+    fn extract_updates(
+        &mut self,
+        response: ValidatorSetResponse,
+    ) -> Result<PeerSet, DiscoveryError> {
+        let (waypoint_ledger_info, response) = response.map_err(DiscoveryError::Rest)?;
+        let (validator_set, state) = response.into_parts();
+        let verification = self
+            .verify_waypoint(waypoint_ledger_info.as_ref())
+            .and_then(|()| self.verify_state(&state));
+        verification.map_err(|error| {
+            inc_by_with_context(
+                &DISCOVERY_COUNTS,
+                &self.network_context,
+                "verification_failure",
+                1,
+            );
+            error
+        })?;
+
+        let peer_set = extract_validator_set_updates(self.network_context, validator_set);
+        inc_by_with_context(
+            &DISCOVERY_COUNTS,
+            &self.network_context,
+            "new_nodes",
+            peer_set.len() as u64,
+        );
+        Ok(peer_set)
+    }
+}
+
+/// Rebuilds the epoch ending ledger info at `version` from the state the endpoint serves at that
+/// version, so that it can be checked against a waypoint.
+async fn fetch_epoch_ending_ledger_info(
+    client: &Client,
+    version: Version,
+) -> Result<LedgerInfo, RestError> {
+    let accumulator_root_hash = match client
+        .get_transaction_by_version_bcs(version)
+        .await?
+        .into_inner()
+    {
+        TransactionData::OnChain(txn) => txn.accumulator_root_hash,
+        TransactionData::Pending(_) => {
+            return Err(RestError::Unknown(anyhow::anyhow!(
+                "Transaction at version {} is pending",
+                version
+            )))
+        }
+    };
+    let configuration: ConfigurationResource = get_resource_at_version(
+        client,
+        &ConfigurationResource::struct_tag().to_string(),
+        version,
+    )
+    .await?;
+    let timestamp: TimestampResource = get_resource_at_version(
+        client,
+        &TimestampResource::struct_tag().to_string(),
+        version,
+    )
+    .await?;
+    let validator_set: ValidatorSet =
+        get_resource_at_version(client, &validator_set_resource_type(), version).await?;
+    Ok(epoch_ending_ledger_info(
+        version,
+        accumulator_root_hash,
+        configuration.epoch(),
+        timestamp.timestamp.microseconds,
+        &validator_set,
+    ))
+}
+
+fn validator_set_resource_type() -> String {
+    format!(
+        "0x1::{}::{}",
+        ValidatorSet::MODULE_IDENTIFIER,
+        ValidatorSet::TYPE_IDENTIFIER
+    )
+}
+
+async fn get_resource_at_version<T: DeserializeOwned>(
+    client: &Client,
+    resource_type: &str,
+    version: Version,
+) -> Result<T, RestError> {
+    let bytes = client
+        .get_account_resource_at_version_bytes(CORE_CODE_ADDRESS, resource_type, version)
+        .await?
+        .into_inner();
+    Ok(bcs::from_bytes(&bytes)?)
+}
+
+/// Builds the ledger info ending the epoch before `next_epoch` at `version`. Only the fields
+/// covered by waypoints are filled in, the round and block id are left empty.
+fn epoch_ending_ledger_info(
+    version: Version,
+    accumulator_root_hash: HashValue,
+    next_epoch: u64,
+    timestamp_usecs: u64,
+    validator_set: &ValidatorSet,
+) -> LedgerInfo {
+    let block_info = BlockInfo::new(
+        next_epoch.saturating_sub(1),
+        0,
+        HashValue::zero(),
+        accumulator_root_hash,
+        version,
+        timestamp_usecs,
+        Some(EpochState {
+            epoch: next_epoch,
+            verifier: validator_set.into(),
+        }),
+    );
+    LedgerInfo::new(block_info, HashValue::zero())
+}
+
+impl Stream for RestStream {
+    type Item = Result<PeerSet, DiscoveryError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Wait for delay before sending the next request
+        if self.pending_request.is_none() {
+            futures::ready!(self.interval.as_mut().poll_next(cx));
+            self.pending_request = Some(self.request_validator_set());
+        }
+
+        let response = futures::ready!(self
+            .pending_request
+            .as_mut()
+            .expect("A request must be pending")
+            .as_mut()
+            .poll(cx));
+        self.pending_request = None;
+        Poll::Ready(Some(self.extract_updates(response)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(chain_id: u8, epoch: u64, version: u64) -> State {
+        State {
+            chain_id,
+            epoch,
+            version,
+            timestamp_usecs: 0,
+            oldest_ledger_version: 0,
+            oldest_block_height: 0,
+            block_height: 0,
+        }
+    }
+
+    fn rest_stream(waypoint: Option<Waypoint>) -> RestStream {
+        RestStream::new(
+            NetworkContext::mock(),
+            Url::parse("http://localhost:8080").unwrap(),
+            ChainId::test(),
+            waypoint,
+            Duration::from_secs(1),
+            TimeService::mock(),
+        )
+    }
+
+    #[test]
+    fn test_verify_chain_id() {
+        let mut stream = rest_stream(None);
+        let chain_id = ChainId::test().id();
+        assert!(stream.verify_state(&state(chain_id + 1, 1, 10)).is_err());
+        assert!(stream.verify_state(&state(chain_id, 1, 10)).is_ok());
+    }
+
+    #[test]
+    fn test_verify_waypoint() {
+        let block_info = BlockInfo::new(1, 0, HashValue::zero(), HashValue::zero(), 100, 0, None);
+        let waypoint = Waypoint::new_any(&LedgerInfo::new(block_info, HashValue::zero()));
+        let mut stream = rest_stream(Some(waypoint));
+        let chain_id = ChainId::test().id();
+        assert!(stream.verify_state(&state(chain_id, 1, 99)).is_err());
+        assert!(stream.verify_state(&state(chain_id, 2, 100)).is_ok());
+    }
+
+    #[test]
+    fn test_verify_no_rollback() {
+        let mut stream = rest_stream(None);
+        let chain_id = ChainId::test().id();
+        assert!(stream.verify_state(&state(chain_id, 2, 100)).is_ok());
+        assert!(stream.verify_state(&state(chain_id, 2, 100)).is_ok());
+        assert!(stream.verify_state(&state(chain_id, 2, 99)).is_err());
+        assert!(stream.verify_state(&state(chain_id, 1, 200)).is_err());
+        assert!(stream.verify_state(&state(chain_id, 3, 150)).is_ok());
+    }
+
+    #[test]
+    fn test_verify_waypoint_ledger_info() {
+        // The waypoint is generated from the full ledger info, including consensus fields which
+        // the rebuilt one doesn't have.
+        let validator_set = ValidatorSet::empty();
+        let root_hash = HashValue::random();
+        let block_info = BlockInfo::new(
+            0,
+            0,
+            HashValue::random(),
+            root_hash,
+            0,
+            0,
+            Some(EpochState {
+                epoch: 1,
+                verifier: (&validator_set).into(),
+            }),
+        );
+        let waypoint =
+            Waypoint::new_epoch_boundary(&LedgerInfo::new(block_info, HashValue::random()))
+                .unwrap();
+        let mut stream = rest_stream(Some(waypoint));
+
+        // The ledger info is required until the waypoint has been verified.
+        assert!(stream.verify_waypoint(None).is_err());
+
+        // A forged ledger info is rejected, and the waypoint still has to be verified.
+        let forged = epoch_ending_ledger_info(0, HashValue::random(), 1, 0, &validator_set);
+        assert!(stream.verify_waypoint(Some(&forged)).is_err());
+        let forged = epoch_ending_ledger_info(0, root_hash, 2, 0, &validator_set);
+        assert!(stream.verify_waypoint(Some(&forged)).is_err());
+        let forged = epoch_ending_ledger_info(0, root_hash, 1, 1, &validator_set);
+        assert!(stream.verify_waypoint(Some(&forged)).is_err());
+        assert!(!stream.waypoint_verified);
+
+        let genuine = epoch_ending_ledger_info(0, root_hash, 1, 0, &validator_set);
+        assert!(stream.verify_waypoint(Some(&genuine)).is_ok());
+        assert!(stream.verify_waypoint(None).is_ok());
+    }
+}
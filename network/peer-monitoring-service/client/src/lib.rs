@@ -24,6 +24,11 @@ use peer_monitoring_service_types::{
 use std::{sync::Arc, time::Duration};
 use thiserror::Error;
 
+mod metrics;
+mod peer_monitor;
+
+pub use peer_monitor::PeerMonitor;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Aptos network rpc error: {0}")]
@@ -31,6 +36,9 @@ pub enum Error {
 
     #[error("Error from remote monitoring service: {0}")]
     PeerMonitoringServiceError(#[from] PeerMonitoringServiceError),
+
+    #[error("Unexpected error encountered: {0}")]
+    UnexpectedError(String),
 }
 
 /// The interface for sending peer monitoring service requests and querying
@@ -0,0 +1,54 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::network_id::PeerNetworkId;
+use aptos_metrics_core::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge_vec, HistogramVec,
+    IntCounterVec, IntGaugeVec,
+};
+use once_cell::sync::Lazy;
+use std::time::Duration;
+
+/// Gauge for the number of peers currently being monitored
+pub static NUM_MONITORED_PEERS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_peer_monitoring_client_num_monitored_peers",
+        "Gauge for the number of peers monitored by the peer monitoring client",
+        &["network_id"]
+    )
+    .unwrap()
+});
+
+/// Histogram for the ping latencies (i.e., round trip times) of peers
+pub static PING_LATENCIES: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_peer_monitoring_client_ping_latencies",
+        "Histogram of the ping latencies measured by the peer monitoring client",
+        &["network_id"]
+    )
+    .unwrap()
+});
+
+/// Counter for the monitoring requests that failed
+pub static REQUEST_ERRORS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_peer_monitoring_client_request_errors",
+        "Counters related to the peer monitoring client request errors",
+        &["network_id", "request_type"]
+    )
+    .unwrap()
+});
+
+/// Increments the request error counter for the given peer and request type
+pub fn increment_request_error(peer_network_id: &PeerNetworkId, request_label: &str) {
+    REQUEST_ERRORS
+        .with_label_values(&[peer_network_id.network_id().as_str(), request_label])
+        .inc();
+}
+
+/// Observes the ping latency measured for the given peer
+pub fn observe_ping_latency(peer_network_id: &PeerNetworkId, latency: Duration) {
+    PING_LATENCIES
+        .with_label_values(&[peer_network_id.network_id().as_str()])
+        .observe(latency.as_secs_f64());
+}
@@ -0,0 +1,372 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{metrics, Error, PeerMonitoringServiceClient};
+use aptos_config::{
+    config::PeerMonitoringServiceConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
+use aptos_logger::prelude::*;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use futures::{future::join_all, StreamExt};
+use network::{
+    application::{interface::NetworkInterface, types::PeerMonitoringMetadata},
+    ProtocolId,
+};
+use peer_monitoring_service_types::{
+    NodeInformationResponse, PeerMonitoringServiceRequest, PingRequest, PingResponse,
+};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    convert::TryFrom,
+    time::{Duration, Instant},
+};
+
+// Useful monitoring constants
+const LOG_FREQUENCY_SECS: u64 = 10;
+
+/// The peer monitor periodically pings every connected peer (to measure
+/// round trip times) and fetches their node information (e.g., build
+/// version, sync height and number of connected peers). The results are
+/// stored in the shared `PeerMetadataStorage` so that other components
+/// (e.g., state sync and mempool) can use them.
+pub struct PeerMonitor {
+    config: PeerMonitoringServiceConfig, // The config of the peer monitoring service
+    monitoring_client: PeerMonitoringServiceClient, // The client used to send requests
+    peer_states: HashMap<PeerNetworkId, PeerState>, // The monitoring states of all peers
+    time_service: TimeService,           // The service to monitor elapsed time
+}
+
+impl PeerMonitor {
+    pub fn new(
+        config: PeerMonitoringServiceConfig,
+        monitoring_client: PeerMonitoringServiceClient,
+        time_service: TimeService,
+    ) -> Self {
+        Self {
+            config,
+            monitoring_client,
+            peer_states: HashMap::new(),
+            time_service,
+        }
+    }
+
+    /// Runs the monitor that continuously refreshes the state of all peers
+    pub async fn start(mut self) {
+        info!("Starting the peer monitor!");
+        let ticker = self
+            .time_service
+            .interval(Duration::from_millis(self.config.peer_monitor_interval_ms));
+        futures::pin_mut!(ticker);
+
+        loop {
+            // Wait for the next round before monitoring the peers
+            ticker.next().await;
+            self.monitor_peers().await;
+        }
+    }
+
+    /// Sends the monitoring requests to all connected peers and updates
+    /// the peer metadata storage with the results.
+    async fn monitor_peers(&mut self) {
+        // Forget the states of peers that are no longer connected
+        let monitored_peers = self.fetch_monitored_peers();
+        self.peer_states
+            .retain(|peer_network_id, _| monitored_peers.contains(peer_network_id));
+        update_monitored_peer_metrics(
+            self.monitoring_client.peer_metadata_storage().networks(),
+            &monitored_peers,
+        );
+
+        // Assign the next ping counter to each peer
+        let peers_and_counters: Vec<_> = monitored_peers
+            .into_iter()
+            .map(|peer_network_id| {
+                let peer_state = self.peer_states.entry(peer_network_id).or_default();
+                (peer_network_id, peer_state.next_ping_counter())
+            })
+            .collect();
+
+        // Send the requests to all peers concurrently
+        let monitoring_client = &self.monitoring_client;
+        let time_service = &self.time_service;
+        let request_timeout = Duration::from_millis(self.config.request_timeout_ms);
+        let responses = join_all(peers_and_counters.into_iter().map(
+            |(peer_network_id, ping_counter)| async move {
+                let (ping_latency, node_information) = futures::join!(
+                    send_ping(
+                        monitoring_client,
+                        time_service,
+                        peer_network_id,
+                        ping_counter,
+                        request_timeout
+                    ),
+                    fetch_node_information(monitoring_client, peer_network_id, request_timeout),
+                );
+                (peer_network_id, ping_latency, node_information)
+            },
+        ))
+        .await;
+
+        // Update the peer states and the peer metadata storage
+        let max_num_latency_pings = self.config.max_num_latency_pings_to_retain as usize;
+        for (peer_network_id, ping_latency, node_information) in responses {
+            let peer_state = self.peer_states.entry(peer_network_id).or_default();
+            match ping_latency {
+                Ok(ping_latency) => {
+                    metrics::observe_ping_latency(&peer_network_id, ping_latency);
+                    peer_state.record_ping_latency(ping_latency, max_num_latency_pings);
+                }
+                Err(error) => log_request_error(&peer_network_id, "ping", error),
+            }
+            match node_information {
+                Ok(node_information) => {
+                    peer_state.record_node_information(self.time_service.now(), node_information)
+                }
+                Err(error) => log_request_error(&peer_network_id, "get_node_information", error),
+            }
+
+            // The peer may have disconnected in the meantime, so errors are expected
+            let _ = self
+                .monitoring_client
+                .peer_metadata_storage()
+                .update_monitoring_metadata(peer_network_id, peer_state.monitoring_metadata());
+        }
+    }
+
+    /// Returns all connected peers that support the peer monitoring service
+    fn fetch_monitored_peers(&self) -> HashSet<PeerNetworkId> {
+        let peer_metadata = self.monitoring_client.peer_metadata_storage();
+        peer_metadata
+            .networks()
+            .flat_map(|network_id| {
+                peer_metadata
+                    .read_filtered(network_id, |(_, peer_info)| {
+                        peer_info.is_connected()
+                            && peer_info.supports_protocol(ProtocolId::PeerMonitoringServiceRpc)
+                    })
+                    .into_keys()
+            })
+            .collect()
+    }
+}
+
+/// Sends a ping to the given peer and returns the measured round trip time
+async fn send_ping(
+    monitoring_client: &PeerMonitoringServiceClient,
+    time_service: &TimeService,
+    peer_network_id: PeerNetworkId,
+    ping_counter: u64,
+    request_timeout: Duration,
+) -> Result<Duration, Error> {
+    let start_time = time_service.now();
+    let request = PeerMonitoringServiceRequest::Ping(PingRequest { ping_counter });
+    let response = monitoring_client
+        .send_request(peer_network_id, request, request_timeout)
+        .await?;
+    let ping_latency = time_service.now().duration_since(start_time);
+
+    // Verify the peer responded to the right ping
+    let response = PingResponse::try_from(response)
+        .map_err(|error| Error::UnexpectedError(error.to_string()))?;
+    if response.ping_counter != ping_counter {
+        return Err(Error::UnexpectedError(format!(
+            "Ping counter mismatch! Expected: {}, received: {}",
+            ping_counter, response.ping_counter
+        )));
+    }
+    Ok(ping_latency)
+}
+
+/// Fetches the node information of the given peer
+async fn fetch_node_information(
+    monitoring_client: &PeerMonitoringServiceClient,
+    peer_network_id: PeerNetworkId,
+    request_timeout: Duration,
+) -> Result<NodeInformationResponse, Error> {
+    let request = PeerMonitoringServiceRequest::GetNodeInformation;
+    let response = monitoring_client
+        .send_request(peer_network_id, request, request_timeout)
+        .await?;
+    NodeInformationResponse::try_from(response)
+        .map_err(|error| Error::UnexpectedError(error.to_string()))
+}
+
+/// Logs the given request error (based on the log frequency) and updates the metrics
+fn log_request_error(peer_network_id: &PeerNetworkId, request_label: &str, error: Error) {
+    metrics::increment_request_error(peer_network_id, request_label);
+    sample!(
+        SampleRate::Duration(Duration::from_secs(LOG_FREQUENCY_SECS)),
+        warn!(
+            peer = %peer_network_id,
+            request = request_label,
+            error = %error,
+            "Peer monitoring request failed!"
+        );
+    );
+}
+
+/// Updates the number of monitored peers for each network
+fn update_monitored_peer_metrics(
+    network_ids: impl Iterator<Item = NetworkId>,
+    monitored_peers: &HashSet<PeerNetworkId>,
+) {
+    for network_id in network_ids {
+        let num_peers = monitored_peers
+            .iter()
+            .filter(|peer_network_id| peer_network_id.network_id() == network_id)
+            .count();
+        metrics::NUM_MONITORED_PEERS
+            .with_label_values(&[network_id.as_str()])
+            .set(num_peers as i64);
+    }
+}
+
+/// The monitoring state of a single peer
+#[derive(Debug, Default)]
+struct PeerState {
+    ping_counter: u64,                         // The counter of the next ping to send
+    recent_ping_latencies: VecDeque<Duration>, // The latencies of the most recent pings
+    sync_throughput: Option<u64>,              // The versions per second synced by the peer
+
+    // The latest node information received from the peer (and when it was received)
+    latest_node_information: Option<(Instant, NodeInformationResponse)>,
+}
+
+impl PeerState {
+    /// Returns the counter to use for the next ping
+    fn next_ping_counter(&mut self) -> u64 {
+        let ping_counter = self.ping_counter;
+        self.ping_counter = self.ping_counter.wrapping_add(1);
+        ping_counter
+    }
+
+    /// Records a new ping latency, dropping the oldest ones
+    fn record_ping_latency(&mut self, ping_latency: Duration, max_num_latency_pings: usize) {
+        self.recent_ping_latencies.push_back(ping_latency);
+        while self.recent_ping_latencies.len() > max_num_latency_pings {
+            self.recent_ping_latencies.pop_front();
+        }
+    }
+
+    /// Records new node information and derives the sync throughput of
+    /// the peer from the previous node information (if any).
+    fn record_node_information(
+        &mut self,
+        received_at: Instant,
+        node_information: NodeInformationResponse,
+    ) {
+        if let Some((previous_received_at, previous_information)) = &self.latest_node_information {
+            let elapsed = received_at.saturating_duration_since(*previous_received_at);
+            if !elapsed.is_zero() {
+                let num_synced_versions = node_information
+                    .highest_synced_version
+                    .saturating_sub(previous_information.highest_synced_version);
+                self.sync_throughput =
+                    Some((num_synced_versions as f64 / elapsed.as_secs_f64()) as u64);
+            }
+        }
+        self.latest_node_information = Some((received_at, node_information));
+    }
+
+    /// Returns the metadata to publish in the peer metadata storage
+    fn monitoring_metadata(&self) -> PeerMonitoringMetadata {
+        let average_ping_latency = if self.recent_ping_latencies.is_empty() {
+            None
+        } else {
+            let total_latency: Duration = self.recent_ping_latencies.iter().sum();
+            Some(total_latency / self.recent_ping_latencies.len() as u32)
+        };
+
+        let node_information = self
+            .latest_node_information
+            .as_ref()
+            .map(|(_, node_information)| node_information);
+        let build_value =
+            |key: &str| node_information.and_then(|info| info.build_information.get(key).cloned());
+        PeerMonitoringMetadata {
+            average_ping_latency,
+            build_commit_hash: build_value(aptos_build_info::BUILD_COMMIT_HASH),
+            build_pkg_version: build_value(aptos_build_info::BUILD_PKG_VERSION),
+            highest_synced_epoch: node_information.map(|info| info.highest_synced_epoch),
+            highest_synced_version: node_information.map(|info| info.highest_synced_version),
+            num_connected_peers: node_information.map(|info| info.num_connected_peers),
+            sync_throughput: self.sync_throughput,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn node_information(highest_synced_version: u64) -> NodeInformationResponse {
+        let mut build_information = BTreeMap::new();
+        build_information.insert(
+            aptos_build_info::BUILD_COMMIT_HASH.to_string(),
+            "abcdef".to_string(),
+        );
+        NodeInformationResponse {
+            build_information,
+            highest_synced_epoch: 5,
+            highest_synced_version,
+            ledger_timestamp_usecs: 0,
+            num_connected_peers: 3,
+        }
+    }
+
+    #[test]
+    fn test_empty_peer_state() {
+        let peer_state = PeerState::default();
+        assert_eq!(
+            peer_state.monitoring_metadata(),
+            PeerMonitoringMetadata::default()
+        );
+    }
+
+    #[test]
+    fn test_ping_counters() {
+        let mut peer_state = PeerState::default();
+        for expected_counter in 0..5 {
+            assert_eq!(peer_state.next_ping_counter(), expected_counter);
+        }
+    }
+
+    #[test]
+    fn test_average_ping_latency() {
+        let mut peer_state = PeerState::default();
+        for latency_ms in [1000, 10, 20, 30] {
+            peer_state.record_ping_latency(Duration::from_millis(latency_ms), 3);
+        }
+
+        // Only the three most recent pings are averaged
+        assert_eq!(
+            peer_state.monitoring_metadata().average_ping_latency,
+            Some(Duration::from_millis(20))
+        );
+    }
+
+    #[test]
+    fn test_node_information() {
+        let mut peer_state = PeerState::default();
+        let start_time = Instant::now();
+
+        // The first response doesn't allow the throughput to be calculated
+        peer_state.record_node_information(start_time, node_information(100));
+        let metadata = peer_state.monitoring_metadata();
+        assert_eq!(metadata.build_commit_hash, Some("abcdef".to_string()));
+        assert_eq!(metadata.build_pkg_version, None);
+        assert_eq!(metadata.highest_synced_epoch, Some(5));
+        assert_eq!(metadata.highest_synced_version, Some(100));
+        assert_eq!(metadata.num_connected_peers, Some(3));
+        assert_eq!(metadata.sync_throughput, None);
+
+        // Subsequent responses update the throughput
+        peer_state
+            .record_node_information(start_time + Duration::from_secs(2), node_information(300));
+        let metadata = peer_state.monitoring_metadata();
+        assert_eq!(metadata.highest_synced_version, Some(300));
+        assert_eq!(metadata.sync_throughput, Some(100));
+    }
+}
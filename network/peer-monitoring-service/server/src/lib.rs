@@ -9,16 +9,18 @@ use crate::{
     network::PeerMonitoringServiceNetworkEvents,
 };
 use ::network::{application::storage::PeerMetadataStorage, ProtocolId};
-use aptos_config::config::PeerMonitoringServiceConfig;
+use aptos_config::{config::PeerMonitoringServiceConfig, network_id::PeerNetworkId};
 use aptos_logger::prelude::*;
 use bounded_executor::BoundedExecutor;
 use futures::stream::StreamExt;
 use peer_monitoring_service_types::{
-    ConnectedPeersResponse, PeerMonitoringServiceError, PeerMonitoringServiceRequest,
-    PeerMonitoringServiceResponse, Result, ServerProtocolVersionResponse,
+    ConnectedPeerInfo, ConnectedPeersResponse, NodeInformationResponse, PeerMonitoringServiceError,
+    PeerMonitoringServiceRequest, PeerMonitoringServiceResponse, PingRequest, PingResponse, Result,
+    ServerProtocolVersionResponse,
 };
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
+use storage_interface::DbReader;
 use thiserror::Error;
 use tokio::runtime::Handle;
 
@@ -55,6 +57,7 @@ pub struct PeerMonitoringServiceServer {
     bounded_executor: BoundedExecutor,
     network_requests: PeerMonitoringServiceNetworkEvents,
     peer_metadata: Arc<PeerMetadataStorage>,
+    storage: Arc<dyn DbReader>,
 }
 
 impl PeerMonitoringServiceServer {
@@ -63,6 +66,7 @@ impl PeerMonitoringServiceServer {
         executor: Handle,
         network_requests: PeerMonitoringServiceNetworkEvents,
         peer_metadata: Arc<PeerMetadataStorage>,
+        storage: Arc<dyn DbReader>,
     ) -> Self {
         let bounded_executor =
             BoundedExecutor::new(config.max_concurrent_requests as usize, executor);
@@ -71,6 +75,7 @@ impl PeerMonitoringServiceServer {
             bounded_executor,
             network_requests,
             peer_metadata,
+            storage,
         }
     }
 
@@ -90,9 +95,10 @@ impl PeerMonitoringServiceServer {
             // All handler methods are currently CPU-bound so we want
             // to spawn on the blocking thread pool.
             let peer_metadata = self.peer_metadata.clone();
+            let storage = self.storage.clone();
             self.bounded_executor
                 .spawn_blocking(move || {
-                    let response = Handler::new(peer_metadata).call(protocol, request);
+                    let response = Handler::new(peer_metadata, storage).call(protocol, request);
                     log_monitoring_service_response(&response);
                     response_sender.send(response);
                 })
@@ -107,11 +113,15 @@ impl PeerMonitoringServiceServer {
 #[derive(Clone)]
 pub struct Handler {
     peer_metadata: Arc<PeerMetadataStorage>,
+    storage: Arc<dyn DbReader>,
 }
 
 impl Handler {
-    pub fn new(peer_metadata: Arc<PeerMetadataStorage>) -> Self {
-        Self { peer_metadata }
+    pub fn new(peer_metadata: Arc<PeerMetadataStorage>, storage: Arc<dyn DbReader>) -> Self {
+        Self {
+            peer_metadata,
+            storage,
+        }
     }
 
     pub fn call(
@@ -140,11 +150,12 @@ impl Handler {
                 self.get_depth_from_validators()
             }
             PeerMonitoringServiceRequest::GetKnownPeers => self.get_known_peers(),
+            PeerMonitoringServiceRequest::GetNodeInformation => self.get_node_information(),
             PeerMonitoringServiceRequest::GetServerProtocolVersion => {
                 self.get_server_protocol_version()
             }
             PeerMonitoringServiceRequest::GetValidatorsAndVFNs => self.get_validators_and_vfns(),
-            PeerMonitoringServiceRequest::Ping => self.handle_ping(),
+            PeerMonitoringServiceRequest::Ping(request) => self.handle_ping(request),
         };
 
         // Process the response and handle any errors
@@ -181,28 +192,52 @@ impl Handler {
     }
 
     fn get_connected_peers(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        // Fetch all connected peers
+        Ok(PeerMonitoringServiceResponse::ConnectedPeers(
+            ConnectedPeersResponse {
+                connected_peers: self.fetch_connected_peers(),
+            },
+        ))
+    }
+
+    /// Returns all connected peers across all networks
+    fn fetch_connected_peers(&self) -> HashMap<PeerNetworkId, ConnectedPeerInfo> {
         let mut connected_peers = HashMap::new();
         for network in self.peer_metadata.networks() {
             for (peer_network_id, peer_info) in self.peer_metadata.read_all(network) {
                 if peer_info.is_connected() {
-                    connected_peers.insert(peer_network_id, peer_info);
+                    connected_peers.insert(peer_network_id, peer_info.into());
                 }
             }
         }
-
-        // Return the connected peers
-        Ok(PeerMonitoringServiceResponse::ConnectedPeers(
-            ConnectedPeersResponse { connected_peers },
-        ))
+        connected_peers
     }
 
     fn get_depth_from_validators(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        unimplemented!();
+        Err(unsupported_request("get_depth_from_validators"))
     }
 
     fn get_known_peers(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        unimplemented!();
+        Err(unsupported_request("get_known_peers"))
+    }
+
+    fn get_node_information(&self) -> Result<PeerMonitoringServiceResponse, Error> {
+        // Fetch the latest synced ledger info
+        let latest_ledger_info = self
+            .storage
+            .get_latest_ledger_info()
+            .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))?;
+        let ledger_info = latest_ledger_info.ledger_info();
+
+        // Return the node information
+        Ok(PeerMonitoringServiceResponse::NodeInformation(
+            NodeInformationResponse {
+                build_information: aptos_build_info::get_build_information(),
+                highest_synced_epoch: ledger_info.epoch(),
+                highest_synced_version: ledger_info.version(),
+                ledger_timestamp_usecs: ledger_info.timestamp_usecs(),
+                num_connected_peers: self.fetch_connected_peers().len() as u64,
+            },
+        ))
     }
 
     fn get_server_protocol_version(&self) -> Result<PeerMonitoringServiceResponse, Error> {
@@ -214,14 +249,22 @@ impl Handler {
     }
 
     fn get_validators_and_vfns(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        unimplemented!();
+        Err(unsupported_request("get_validators_and_vfns"))
     }
 
-    fn handle_ping(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        unimplemented!();
+    fn handle_ping(&self, request: &PingRequest) -> Result<PeerMonitoringServiceResponse, Error> {
+        Ok(PeerMonitoringServiceResponse::Ping(PingResponse {
+            ping_counter: request.ping_counter,
+        }))
     }
 }
 
+/// Returns the error for requests the server doesn't support yet. Any peer can send
+/// these, so they must be rejected rather than panic.
+fn unsupported_request(request: &str) -> Error {
+    Error::InvalidRequest(format!("Unsupported request: {}", request))
+}
+
 /// Logs the response sent by the monitoring service for a request
 fn log_monitoring_service_response(
     monitoring_service_response: &Result<PeerMonitoringServiceResponse, PeerMonitoringServiceError>,
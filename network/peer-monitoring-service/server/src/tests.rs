@@ -6,12 +6,20 @@
 use crate::{
     PeerMonitoringServiceNetworkEvents, PeerMonitoringServiceServer, PEER_MONITORING_SERVER_VERSION,
 };
+use anyhow::Result as AnyhowResult;
 use aptos_config::{
     config::{PeerMonitoringServiceConfig, PeerRole},
     network_id::{NetworkId, PeerNetworkId},
 };
+use aptos_crypto::HashValue;
 use aptos_logger::Level;
-use aptos_types::{network_address::NetworkAddress, PeerId};
+use aptos_types::{
+    aggregate_signature::AggregateSignature,
+    block_info::BlockInfo,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    network_address::NetworkAddress,
+    PeerId,
+};
 use channel::aptos_channel;
 use futures::channel::oneshot;
 use netcore::transport::ConnectionOrigin;
@@ -29,14 +37,21 @@ use network::{
     transport::{ConnectionId, ConnectionMetadata},
 };
 use peer_monitoring_service_types::{
-    ConnectedPeersResponse, PeerMonitoringServiceError, PeerMonitoringServiceMessage,
-    PeerMonitoringServiceRequest, PeerMonitoringServiceResponse, ServerProtocolVersionResponse,
+    ConnectedPeerInfo, ConnectedPeersResponse, NodeInformationResponse, PeerMonitoringServiceError,
+    PeerMonitoringServiceMessage, PeerMonitoringServiceRequest, PeerMonitoringServiceResponse,
+    PingRequest, PingResponse, ServerProtocolVersionResponse,
 };
 use std::{
     collections::{hash_map::Entry, HashMap},
     str::FromStr,
     sync::Arc,
 };
+use storage_interface::DbReader;
+
+// Useful test constants
+const TEST_EPOCH: u64 = 10;
+const TEST_TIMESTAMP_USECS: u64 = 1_000_000;
+const TEST_VERSION: u64 = 1000;
 
 #[tokio::test]
 async fn test_get_server_protocol_version() {
@@ -56,6 +71,75 @@ async fn test_get_server_protocol_version() {
     assert_eq!(response, expected_response);
 }
 
+#[tokio::test]
+async fn test_ping() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, _) = MockClient::new();
+    tokio::spawn(service.start());
+
+    // Send several pings and verify the counters are echoed back
+    for ping_counter in 0..10 {
+        let request = PeerMonitoringServiceRequest::Ping(PingRequest { ping_counter });
+        let response = mock_client.send_request(request).await.unwrap();
+        assert_eq!(
+            response,
+            PeerMonitoringServiceResponse::Ping(PingResponse { ping_counter })
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_unsupported_requests() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, _) = MockClient::new();
+    tokio::spawn(service.start());
+
+    // Unsupported requests are rejected, and the server keeps serving requests
+    for request in vec![
+        PeerMonitoringServiceRequest::GetDepthFromValidators,
+        PeerMonitoringServiceRequest::GetKnownPeers,
+        PeerMonitoringServiceRequest::GetValidatorsAndVFNs,
+    ] {
+        let response = mock_client.send_request(request).await;
+        assert!(matches!(
+            response,
+            Err(PeerMonitoringServiceError::InvalidRequest(_))
+        ));
+    }
+    let request = PeerMonitoringServiceRequest::Ping(PingRequest { ping_counter: 0 });
+    assert!(mock_client.send_request(request).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_get_node_information() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, peer_metadata_storage) = MockClient::new();
+    tokio::spawn(service.start());
+
+    // Connect a single peer
+    let peer_id = PeerId::random();
+    let peer_network_id = PeerNetworkId::new(NetworkId::Validator, peer_id);
+    peer_metadata_storage.insert(
+        peer_network_id,
+        PeerInfo::new(create_connection_metadata(peer_id)),
+    );
+
+    // Process a request to fetch the node information
+    let request = PeerMonitoringServiceRequest::GetNodeInformation;
+    let response = mock_client.send_request(request).await.unwrap();
+
+    // Verify the response is correct
+    let expected_response =
+        PeerMonitoringServiceResponse::NodeInformation(NodeInformationResponse {
+            build_information: aptos_build_info::get_build_information(),
+            highest_synced_epoch: TEST_EPOCH,
+            highest_synced_version: TEST_VERSION,
+            ledger_timestamp_usecs: TEST_TIMESTAMP_USECS,
+            num_connected_peers: 1,
+        });
+    assert_eq!(response, expected_response);
+}
+
 #[tokio::test]
 async fn test_get_connected_peers() {
     // Create the peer monitoring client and server
@@ -75,16 +159,7 @@ async fn test_get_connected_peers() {
     // Update the connected peers with a new peer
     let peer_id = PeerId::random();
     let peer_network_id = PeerNetworkId::new(NetworkId::Validator, peer_id);
-    let connection_metadata = ConnectionMetadata::new(
-        peer_id,
-        ConnectionId::default(),
-        NetworkAddress::from_str("/ip4/127.0.0.1/tcp/8081").unwrap(),
-        ConnectionOrigin::Inbound,
-        MessagingProtocolVersion::V1,
-        ProtocolIdSet::empty(),
-        PeerRole::Unknown,
-    );
-    let peer_info = PeerInfo::new(connection_metadata);
+    let peer_info = PeerInfo::new(create_connection_metadata(peer_id));
     peer_metadata_storage.insert(peer_network_id, peer_info);
 
     // Process a request to fetch the connected peers
@@ -95,7 +170,7 @@ async fn test_get_connected_peers() {
     let mut connected_peers = HashMap::new();
     connected_peers.insert(
         peer_network_id,
-        peer_metadata_storage.read(peer_network_id).unwrap(),
+        ConnectedPeerInfo::from(peer_metadata_storage.read(peer_network_id).unwrap()),
     );
     let expected_response =
         PeerMonitoringServiceResponse::ConnectedPeers(ConnectedPeersResponse { connected_peers });
@@ -156,6 +231,7 @@ impl MockClient {
             executor,
             network_request_stream,
             peer_metadata_storage.clone(),
+            Arc::new(MockDbReader),
         );
 
         // Create the mock client
@@ -203,6 +279,43 @@ impl MockClient {
     }
 }
 
+/// A mock storage reader that always returns the same ledger info
+struct MockDbReader;
+
+impl DbReader for MockDbReader {
+    fn get_latest_ledger_info_option(&self) -> AnyhowResult<Option<LedgerInfoWithSignatures>> {
+        let ledger_info = LedgerInfo::new(
+            BlockInfo::new(
+                TEST_EPOCH,
+                0,
+                HashValue::zero(),
+                HashValue::zero(),
+                TEST_VERSION,
+                TEST_TIMESTAMP_USECS,
+                None,
+            ),
+            HashValue::zero(),
+        );
+        Ok(Some(LedgerInfoWithSignatures::new(
+            ledger_info,
+            AggregateSignature::empty(),
+        )))
+    }
+}
+
+/// Creates connection metadata for the given peer
+fn create_connection_metadata(peer_id: PeerId) -> ConnectionMetadata {
+    ConnectionMetadata::new(
+        peer_id,
+        ConnectionId::default(),
+        NetworkAddress::from_str("/ip4/127.0.0.1/tcp/8081").unwrap(),
+        ConnectionOrigin::Inbound,
+        MessagingProtocolVersion::V1,
+        ProtocolIdSet::empty(),
+        PeerRole::Unknown,
+    )
+}
+
 /// Initializes the Aptos logger for tests
 pub fn initialize_logger() {
     aptos_logger::Logger::builder()
@@ -4,9 +4,15 @@
 #![forbid(unsafe_code)]
 
 use aptos_config::network_id::PeerNetworkId;
-use network::application::types::PeerInfo;
+use network::{
+    application::types::{PeerInfo, PeerState},
+    transport::ConnectionMetadata,
+};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, convert::TryFrom};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+};
 use thiserror::Error;
 
 pub type Result<T, E = PeerMonitoringServiceError> = ::std::result::Result<T, E>;
@@ -36,9 +42,10 @@ pub enum PeerMonitoringServiceRequest {
     GetConnectedPeers,        // Returns all connected peers
     GetDepthFromValidators,   // Returns the depth of the node from the validators
     GetKnownPeers,            // Returns all of the known peers in the network
+    GetNodeInformation,       // Returns the build, sync and connectivity state of the node
     GetServerProtocolVersion, // Fetches the protocol version run by the server
     GetValidatorsAndVFNs,     // Returns the current validators and VFNs
-    Ping, // A simple message used by the client to ensure liveness and measure latency
+    Ping(PingRequest), // A simple message used by the client to ensure liveness and measure latency
 }
 
 impl PeerMonitoringServiceRequest {
@@ -48,9 +55,10 @@ impl PeerMonitoringServiceRequest {
             Self::GetConnectedPeers => "get_connected_peers",
             Self::GetDepthFromValidators => "get_depth_from_validators",
             Self::GetKnownPeers => "get_known_peers",
+            Self::GetNodeInformation => "get_node_information",
             Self::GetServerProtocolVersion => "get_server_protocol_version",
             Self::GetValidatorsAndVFNs => "get_validators_and_vfns",
-            Self::Ping => "ping",
+            Self::Ping(_) => "ping",
         }
     }
 }
@@ -62,6 +70,7 @@ pub enum PeerMonitoringServiceResponse {
     ConnectedPeers(ConnectedPeersResponse), // Holds all currently connected peers
     DepthFromValidators(DepthFromValidatorsResponse), // Holds the min depth from the validators
     KnownPeers(KnownPeersResponse),         // Holds all currently known peers
+    NodeInformation(NodeInformationResponse), // Holds the build, sync and connectivity state of the node
     Ping(PingResponse), // A simple message to respond to liveness checks (i.e., pings)
     ServerProtocolVersion(ServerProtocolVersionResponse), // Returns the current server protocol version
     ValidatorsAndVFNs(ValidatorsAndVFNsResponse), // Holds the current validator set and VFNs
@@ -74,6 +83,7 @@ impl PeerMonitoringServiceResponse {
             Self::ConnectedPeers(_) => "connected_peers",
             Self::DepthFromValidators(_) => "depth_from_validators",
             Self::KnownPeers(_) => "known_peers",
+            Self::NodeInformation(_) => "node_information",
             Self::Ping(_) => "ping",
             Self::ServerProtocolVersion(_) => "server_protocol_version",
             Self::ValidatorsAndVFNs(_) => "validators_and_vfns",
@@ -84,7 +94,25 @@ impl PeerMonitoringServiceResponse {
 /// A response for the connected peers request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct ConnectedPeersResponse {
-    pub connected_peers: HashMap<PeerNetworkId, PeerInfo>,
+    pub connected_peers: HashMap<PeerNetworkId, ConnectedPeerInfo>,
+}
+
+/// A connected peer as reported to other peers. This omits the monitoring
+/// metadata gathered locally in [`PeerInfo`], so the wire format doesn't
+/// change when that metadata does.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ConnectedPeerInfo {
+    pub status: PeerState,
+    pub active_connection: ConnectionMetadata,
+}
+
+impl From<PeerInfo> for ConnectedPeerInfo {
+    fn from(peer_info: PeerInfo) -> Self {
+        ConnectedPeerInfo {
+            status: peer_info.status,
+            active_connection: peer_info.active_connection,
+        }
+    }
 }
 
 /// A response for the depth from validators request
@@ -99,10 +127,27 @@ pub struct KnownPeersResponse {
     pub todo: bool,
 }
 
+/// A response for the node information request
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NodeInformationResponse {
+    pub build_information: BTreeMap<String, String>, // The build information of the node
+    pub highest_synced_epoch: u64,                   // The epoch of the latest ledger info
+    pub highest_synced_version: u64,                 // The version of the latest ledger info
+    pub ledger_timestamp_usecs: u64,                 // The timestamp of the latest ledger info
+    pub num_connected_peers: u64,                    // The number of currently connected peers
+}
+
+/// A ping request. The counter is echoed back by the server so that the
+/// client can match responses to requests.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct PingRequest {
+    pub ping_counter: u64,
+}
+
 /// A response for the ping request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct PingResponse {
-    pub todo: bool,
+    pub ping_counter: u64,
 }
 
 /// A response for the server protocol version request
@@ -160,6 +205,19 @@ impl TryFrom<PeerMonitoringServiceResponse> for KnownPeersResponse {
     }
 }
 
+impl TryFrom<PeerMonitoringServiceResponse> for NodeInformationResponse {
+    type Error = UnexpectedResponseError;
+    fn try_from(response: PeerMonitoringServiceResponse) -> Result<Self, Self::Error> {
+        match response {
+            PeerMonitoringServiceResponse::NodeInformation(inner) => Ok(inner),
+            _ => Err(UnexpectedResponseError(format!(
+                "expected node_information_response, found {}",
+                response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<PeerMonitoringServiceResponse> for PingResponse {
     type Error = UnexpectedResponseError;
     fn try_from(response: PeerMonitoringServiceResponse) -> Result<Self, Self::Error> {
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    application::types::{PeerError, PeerInfo},
+    application::types::{PeerError, PeerInfo, PeerMonitoringMetadata},
     transport::ConnectionMetadata,
 };
 use aptos_config::network_id::{NetworkId, PeerNetworkId};
@@ -103,6 +103,21 @@ impl PeerMetadataStorage {
             .write(peer_network_id.peer_id(), modifier)
     }
 
+    /// Replace the monitoring metadata of a known peer
+    pub fn update_monitoring_metadata(
+        &self,
+        peer_network_id: PeerNetworkId,
+        monitoring_metadata: PeerMonitoringMetadata,
+    ) -> Result<(), PeerError> {
+        self.write(peer_network_id, |entry| match entry {
+            Entry::Vacant(..) => Err(PeerError::NotFound),
+            Entry::Occupied(inner) => {
+                inner.get_mut().monitoring_metadata = monitoring_metadata;
+                Ok(())
+            }
+        })
+    }
+
     /// Get the underlying `RwLock` of the map.  Usage is discouraged as it leads to the possiblity of
     /// leaving the lock held for a long period of time.  However, not everything fits into the `write`
     /// model.
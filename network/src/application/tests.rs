@@ -5,14 +5,14 @@ use crate::{
     application::{
         interface::NetworkInterface,
         storage::{LockingHashMap, PeerMetadataStorage},
-        types::{PeerError, PeerState},
+        types::{PeerError, PeerMonitoringMetadata, PeerState},
     },
     protocols::health_checker::HealthCheckerMsg,
     transport::ConnectionMetadata,
 };
 use aptos_config::network_id::{NetworkId, PeerNetworkId};
 use aptos_types::PeerId;
-use std::{collections::hash_map::Entry, sync::Arc};
+use std::{collections::hash_map::Entry, sync::Arc, time::Duration};
 
 #[derive(Clone)]
 struct DummySender {}
@@ -80,6 +80,46 @@ fn test_interface() {
     assert_eq!(0, interface.connected_peers(network_id).len());
 }
 
+#[test]
+fn test_monitoring_metadata() {
+    let network_id = NetworkId::Validator;
+    let peer_metadata_storage = PeerMetadataStorage::test();
+    let peer_network_id = PeerNetworkId::new(network_id, PeerId::random());
+    let monitoring_metadata = PeerMonitoringMetadata {
+        average_ping_latency: Some(Duration::from_millis(50)),
+        highest_synced_version: Some(100),
+        ..PeerMonitoringMetadata::default()
+    };
+
+    // Unknown peers can't be updated
+    assert_eq!(
+        Err(PeerError::NotFound),
+        peer_metadata_storage
+            .update_monitoring_metadata(peer_network_id, monitoring_metadata.clone())
+    );
+
+    // Updates are visible to readers
+    let connection = ConnectionMetadata::mock(peer_network_id.peer_id());
+    peer_metadata_storage.insert_connection(network_id, connection);
+    peer_metadata_storage
+        .update_monitoring_metadata(peer_network_id, monitoring_metadata.clone())
+        .unwrap();
+    let peer_info = peer_metadata_storage.read(peer_network_id).unwrap();
+    assert_eq!(monitoring_metadata, peer_info.monitoring_metadata);
+
+    // A new connection to the same peer keeps the monitoring metadata
+    peer_metadata_storage.insert_connection(
+        network_id,
+        ConnectionMetadata::mock(peer_network_id.peer_id()),
+    );
+    let peer_info = peer_metadata_storage.read(peer_network_id).unwrap();
+    assert_eq!(monitoring_metadata, peer_info.monitoring_metadata);
+
+    // Removing the peer drops the monitoring metadata
+    peer_metadata_storage.remove_connection(network_id, &peer_info.active_connection);
+    assert!(peer_metadata_storage.read(peer_network_id).is_none());
+}
+
 fn update_state(
     peer_metadata_storage: Arc<PeerMetadataStorage>,
     peer_network_id: PeerNetworkId,
@@ -3,6 +3,7 @@
 
 use crate::{protocols::wire::handshake::v1::ProtocolId, transport::ConnectionMetadata};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Errors related to the peer layer in the `NetworkInterface`
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -15,6 +16,7 @@ pub enum PeerError {
 pub struct PeerInfo {
     pub status: PeerState,
     pub active_connection: ConnectionMetadata,
+    pub monitoring_metadata: PeerMonitoringMetadata,
 }
 
 impl PeerInfo {
@@ -22,6 +24,7 @@ impl PeerInfo {
         PeerInfo {
             status: PeerState::Connected,
             active_connection: connection_metadata,
+            monitoring_metadata: PeerMonitoringMetadata::default(),
         }
     }
 
@@ -44,3 +47,17 @@ pub enum PeerState {
     Disconnecting,
     Disconnected,
 }
+
+/// Information about a peer gathered by the peer monitoring service. Each
+/// field is `None` until the peer has successfully responded to at least one
+/// of the relevant monitoring requests.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PeerMonitoringMetadata {
+    pub average_ping_latency: Option<Duration>, // The average round trip time of recent pings
+    pub build_commit_hash: Option<String>,      // The commit the peer was built from
+    pub build_pkg_version: Option<String>,      // The package version run by the peer
+    pub highest_synced_epoch: Option<u64>,      // The highest epoch synced by the peer
+    pub highest_synced_version: Option<u64>,    // The highest version synced by the peer
+    pub num_connected_peers: Option<u64>,       // The number of peers the peer is connected to
+    pub sync_throughput: Option<u64>, // The number of versions per second synced by the peer
+}
@@ -121,13 +121,15 @@ pub struct ConnectivityManager<TBackoff> {
 }
 
 /// Different sources for peer addresses, ordered by priority (Onchain=highest,
-/// Config=lowest).
+/// PeerExchange=lowest).
 #[repr(u8)]
 #[derive(Copy, Clone, Eq, Hash, PartialEq, Ord, PartialOrd, NumVariants, Serialize)]
 pub enum DiscoverySource {
     OnChainValidatorSet,
     File,
+    Rest,
     Config,
+    PeerExchange,
 }
 
 impl fmt::Debug for DiscoverySource {
@@ -144,7 +146,9 @@ impl fmt::Display for DiscoverySource {
             match self {
                 DiscoverySource::OnChainValidatorSet => "OnChainValidatorSet",
                 DiscoverySource::File => "File",
+                DiscoverySource::Rest => "Rest",
                 DiscoverySource::Config => "Config",
+                DiscoverySource::PeerExchange => "PeerExchange",
             }
         )
     }
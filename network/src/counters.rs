@@ -261,6 +261,52 @@ pub fn inbound_rpc_handler_latency(
     ])
 }
 
+pub static APTOS_NETWORK_OUTBOUND_QUEUE_DELAY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_network_outbound_queue_delay_seconds",
+        "Time outbound messages spend queued on a connection before being written, by protocol",
+        &["role_type", "network_id", "peer_id", "protocol_id"]
+    )
+    .unwrap()
+});
+
+/// Messages generated by the network layer itself (e.g. errors) don't belong to a protocol
+pub fn outbound_queue_delay(
+    network_context: &NetworkContext,
+    protocol_id: Option<ProtocolId>,
+) -> Histogram {
+    APTOS_NETWORK_OUTBOUND_QUEUE_DELAY.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+        protocol_id.map_or("network", ProtocolId::as_str),
+    ])
+}
+
+pub static APTOS_NETWORK_PROTOCOL_RATE_LIMITED: Lazy<IntCounterVec> =
+    Lazy::new(|| {
+        register_int_counter_vec!(
+        "aptos_network_protocol_rate_limited_messages",
+        "Number of messages delayed (outbound) or dropped (inbound) by per-protocol rate limits",
+        &["role_type", "network_id", "peer_id", "protocol_id", "direction"]
+    )
+        .unwrap()
+    });
+
+pub fn protocol_rate_limited(
+    network_context: &NetworkContext,
+    protocol_id: ProtocolId,
+    direction: &str,
+) -> IntCounter {
+    APTOS_NETWORK_PROTOCOL_RATE_LIMITED.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+        protocol_id.as_str(),
+        direction,
+    ])
+}
+
 pub static APTOS_NETWORK_DIRECT_SEND_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
         "aptos_network_direct_send_messages",
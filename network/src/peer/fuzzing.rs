@@ -3,7 +3,7 @@
 
 use crate::{
     constants,
-    peer::Peer,
+    peer::{Peer, ProtocolRateLimits},
     protocols::wire::{
         handshake::v1::{MessagingProtocolVersion, ProtocolIdSet},
         messaging::v1::{MultiplexMessage, MultiplexMessageSink},
@@ -109,6 +109,7 @@ pub fn fuzz(data: &[u8]) {
         constants::MAX_MESSAGE_SIZE,
         None,
         None,
+        ProtocolRateLimits::default(),
     );
     executor.spawn(peer.start());
 
@@ -25,9 +25,12 @@ use crate::{
         direct_send::Message,
         rpc::{InboundRpcRequest, InboundRpcs, OutboundRpcRequest, OutboundRpcs},
         stream::{InboundStreamBuffer, OutboundStream, StreamMessage},
-        wire::messaging::v1::{
-            DirectSendMsg, ErrorCode, MultiplexMessage, MultiplexMessageSink,
-            MultiplexMessageStream, NetworkMessage, Priority, ReadError, WriteError,
+        wire::{
+            handshake::v1::MessagingProtocolVersion,
+            messaging::v1::{
+                DirectSendMsg, ErrorCode, MultiplexMessage, MultiplexMessageSink,
+                MultiplexMessageStream, NetworkMessage, Priority, ReadError, WriteError,
+            },
         },
     },
     transport::{self, Connection, ConnectionMetadata},
@@ -43,25 +46,36 @@ use channel::aptos_channel;
 use futures::{
     self,
     channel::oneshot,
+    future,
     io::{AsyncRead, AsyncWrite},
     stream::StreamExt,
-    SinkExt,
+    FutureExt, SinkExt,
 };
 use futures_util::stream::select;
 use serde::Serialize;
 use short_hex_str::AsShortHexStr;
-use std::{fmt, panic, time::Duration};
+use std::{
+    fmt, panic,
+    time::{Duration, Instant},
+};
 use tokio::runtime::Handle;
 use tokio_util::compat::{
     FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
 };
 
+mod shaping;
 #[cfg(test)]
 mod test;
 
+pub use shaping::{OutboundMessage, ProtocolRateLimits};
+use shaping::{OutboundQueue, ProtocolRateLimiters};
+
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod fuzzing;
 
+/// Number of messages buffered by the task writing to the socket.
+const WRITER_BUFFER_SIZE: usize = 16;
+
 /// Requests [`Peer`] receives from the [`PeerManager`](crate::peer_manager::PeerManager).
 #[derive(Debug)]
 pub enum PeerRequest {
@@ -142,6 +156,10 @@ pub struct Peer<TSocket> {
     outbound_rate_limiter: Option<SharedBucket>,
     /// Inbound stream buffer
     inbound_stream: InboundStreamBuffer,
+    /// Per-protocol rate limits of this connection
+    protocol_rate_limits: ProtocolRateLimits,
+    /// Per-protocol inbound rate limiters, messages exceeding them are dropped
+    inbound_protocol_limiters: ProtocolRateLimiters,
 }
 
 impl<TSocket> Peer<TSocket>
@@ -164,12 +182,15 @@ where
         max_message_size: usize,
         inbound_rate_limiter: Option<SharedBucket>,
         outbound_rate_limiter: Option<SharedBucket>,
+        protocol_rate_limits: ProtocolRateLimits,
     ) -> Self {
         let Connection {
             metadata: connection_metadata,
             socket,
         } = connection;
         let remote_peer_id = connection_metadata.remote_peer_id;
+        let inbound_protocol_limiters =
+            protocol_rate_limits.inbound_limiters(network_context, remote_peer_id);
         Self {
             network_context,
             executor,
@@ -198,6 +219,8 @@ where
             inbound_rate_limiter,
             outbound_rate_limiter,
             inbound_stream: InboundStreamBuffer::new(),
+            protocol_rate_limits,
+            inbound_protocol_limiters,
         }
     }
 
@@ -243,6 +266,8 @@ where
             writer,
             self.max_frame_size,
             self.max_message_size,
+            self.protocol_rate_limits
+                .outbound_limiters(self.network_context, remote_peer_id),
         );
 
         // Start main Peer event loop.
@@ -313,6 +338,9 @@ where
     // 2. The second channel is used to instruct the task to close the connection and terminate.
     // If outbound messages are queued when the task receives a close instruction, it discards
     // them and immediately closes the connection.
+    // Outbound messages are written in order of their protocol's priority, subject to the
+    // per-protocol rate limits (see `shaping`).
+    #[allow(clippy::too_many_arguments)]
     fn start_writer_task(
         executor: &Handle,
         time_service: TimeService,
@@ -321,17 +349,22 @@ where
         mut writer: MultiplexMessageSink<impl AsyncWrite + Unpin + Send + 'static>,
         max_frame_size: usize,
         max_message_size: usize,
-    ) -> (channel::Sender<NetworkMessage>, oneshot::Sender<()>) {
+        outbound_protocol_limiters: ProtocolRateLimiters,
+    ) -> (channel::Sender<OutboundMessage>, oneshot::Sender<()>) {
         let remote_peer_id = connection_metadata.remote_peer_id;
-        let (write_reqs_tx, mut write_reqs_rx): (channel::Sender<NetworkMessage>, _) =
+        let (write_reqs_tx, mut write_reqs_rx): (channel::Sender<OutboundMessage>, _) =
             channel::new(1024, &counters::PENDING_WIRE_MESSAGES);
         let (close_tx, mut close_rx) = oneshot::channel();
 
-        let (mut msg_tx, msg_rx) = channel::new(1024, &counters::PENDING_MULTIPLEX_MESSAGE);
+        // Keep the writer's buffer small, so that messages wait in the outbound queue where they
+        // can still be overtaken by higher priority messages.
+        let (mut msg_tx, msg_rx) =
+            channel::new(WRITER_BUFFER_SIZE, &counters::PENDING_MULTIPLEX_MESSAGE);
         let (stream_msg_tx, stream_msg_rx) =
             channel::new(1024, &counters::PENDING_MULTIPLEX_STREAM);
 
         // this task ends when the multiplex task ends (by dropping the senders)
+        let writer_time_service = time_service.clone();
         let writer_task = async move {
             let mut stream = select(msg_rx, stream_msg_rx);
             let log_context =
@@ -358,7 +391,7 @@ where
                 writer.close().await?;
                 Ok(()) as Result<(), WriteError>
             };
-            match time_service
+            match writer_time_service
                 .timeout(transport::TRANSPORT_TIMEOUT, flush_and_close)
                 .await
             {
@@ -393,14 +426,37 @@ where
         let multiplex_task = async move {
             let mut outbound_stream =
                 OutboundStream::new(max_frame_size, max_message_size, stream_msg_tx);
+            let mut outbound_queue = OutboundQueue::new(
+                network_context,
+                time_service.clone(),
+                outbound_protocol_limiters,
+            );
+            let mut rx_closed = false;
             loop {
-                futures::select! {
-                    message = write_reqs_rx.select_next_some() => {
+                // Stop as soon as we're asked to close, even if messages are still queued
+                if !matches!(close_rx.try_recv(), Ok(None)) {
+                    break;
+                }
+
+                // Move all pending messages to the outbound queue, so they are sent by priority
+                while !rx_closed && !outbound_queue.is_full() {
+                    match write_reqs_rx.next().now_or_never() {
+                        Some(Some(message)) => outbound_queue.push(message),
+                        Some(None) => rx_closed = true,
+                        None => break,
+                    }
+                }
+
+                let retry_at = match outbound_queue.pop() {
+                    Ok(OutboundMessage { message, .. }) => {
                         // either channel full would block the other one
                         let result = if outbound_stream.should_stream(&message) {
                             outbound_stream.stream_message(message).await
                         } else {
-                            msg_tx.send(MultiplexMessage::Message(message)).await.map_err(|_| anyhow::anyhow!("Writer task ended"))
+                            msg_tx
+                                .send(MultiplexMessage::Message(message))
+                                .await
+                                .map_err(|_| anyhow::anyhow!("Writer task ended"))
                         };
                         if let Err(err) = result {
                             warn!(
@@ -410,7 +466,42 @@ where
                                 remote_peer_id.short_str(),
                             );
                         }
+                        continue;
+                    }
+                    Err(retry_at) => retry_at,
+                };
+
+                // Nothing can be sent right now, wait for a new message or for the rate limits.
+                // Rate limiter buckets are refilled based on the system clock.
+                let rate_limit_delay = async {
+                    match retry_at {
+                        Some(retry_at) => {
+                            time_service
+                                .sleep(retry_at.saturating_duration_since(Instant::now()))
+                                .await
+                        }
+                        None => future::pending().await,
+                    }
+                }
+                .fuse();
+                futures::pin_mut!(rate_limit_delay);
+                let accept_messages = !rx_closed && !outbound_queue.is_full();
+                let next_message = async {
+                    if accept_messages {
+                        write_reqs_rx.next().await
+                    } else {
+                        future::pending().await
+                    }
+                }
+                .fuse();
+                futures::pin_mut!(next_message);
+
+                futures::select! {
+                    message = next_message => match message {
+                        Some(message) => outbound_queue.push(message),
+                        None => rx_closed = true,
                     },
+                    _ = rate_limit_delay => {},
                     _ = close_rx => {
                         break;
                     }
@@ -425,9 +516,47 @@ where
     async fn handle_inbound_network_message(
         &mut self,
         message: NetworkMessage,
+        write_reqs_tx: &mut channel::Sender<OutboundMessage>,
     ) -> Result<(), PeerManagerError> {
+        if let Some(protocol_id) = message.protocol_id() {
+            if let Err(retry_at) = self
+                .inbound_protocol_limiters
+                .try_acquire(Some(protocol_id), message.data_len())
+            {
+                self.inbound_protocol_limiters
+                    .record_rate_limited(protocol_id);
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(1)),
+                    debug!(
+                        NetworkSchema::new(&self.network_context)
+                            .connection_metadata(&self.connection_metadata),
+                        "{} Dropping inbound {} message from peer {}, rate limited until {:?}",
+                        self.network_context,
+                        protocol_id,
+                        self.remote_peer_id().short_str(),
+                        retry_at
+                    )
+                );
+                // Direct send messages are dropped silently, but the requester of an rpc is told
+                // right away, so it doesn't wait for its request to time out. Peers on an older
+                // messaging protocol can't parse the error, so their requests still time out.
+                if let NetworkMessage::RpcRequest(request) = &message {
+                    if self.connection_metadata.messaging_protocol >= MessagingProtocolVersion::V2 {
+                        let error_code = ErrorCode::rate_limited(protocol_id, request.request_id);
+                        write_reqs_tx
+                            .send(OutboundMessage::network(NetworkMessage::Error(error_code)))
+                            .await?;
+                    }
+                }
+                return Ok(());
+            }
+        }
+
         match message {
             NetworkMessage::DirectSendMsg(message) => self.handle_inbound_direct_send(message),
+            NetworkMessage::Error(ErrorCode::RateLimited(rate_limited)) => self
+                .outbound_rpcs
+                .handle_rate_limited_request(rate_limited.request_id),
             NetworkMessage::Error(error_msg) => {
                 warn!(
                     NetworkSchema::new(&self.network_context)
@@ -464,6 +593,7 @@ where
     async fn handle_inbound_stream_message(
         &mut self,
         message: StreamMessage,
+        write_reqs_tx: &mut channel::Sender<OutboundMessage>,
     ) -> Result<(), PeerManagerError> {
         match message {
             StreamMessage::Header(header) => {
@@ -471,7 +601,8 @@ where
             }
             StreamMessage::Fragment(fragment) => {
                 if let Some(message) = self.inbound_stream.append_fragment(fragment)? {
-                    self.handle_inbound_network_message(message).await?;
+                    self.handle_inbound_network_message(message, write_reqs_tx)
+                        .await?;
                 }
             }
         }
@@ -481,7 +612,7 @@ where
     async fn handle_inbound_message(
         &mut self,
         message: Result<MultiplexMessage, ReadError>,
-        write_reqs_tx: &mut channel::Sender<NetworkMessage>,
+        write_reqs_tx: &mut channel::Sender<OutboundMessage>,
     ) -> Result<(), PeerManagerError> {
         trace!(
             NetworkSchema::new(&self.network_context)
@@ -503,7 +634,9 @@ where
                     let error_code = ErrorCode::parsing_error(*message_type, *protocol_id);
                     let message = NetworkMessage::Error(error_code);
 
-                    write_reqs_tx.send(message).await?;
+                    write_reqs_tx
+                        .send(OutboundMessage::network(message))
+                        .await?;
                     return Err(err.into());
                 }
                 ReadError::IoError(_) => {
@@ -516,9 +649,13 @@ where
 
         match message {
             MultiplexMessage::Message(message) => {
-                self.handle_inbound_network_message(message).await
+                self.handle_inbound_network_message(message, write_reqs_tx)
+                    .await
+            }
+            MultiplexMessage::Stream(message) => {
+                self.handle_inbound_stream_message(message, write_reqs_tx)
+                    .await
             }
-            MultiplexMessage::Stream(message) => self.handle_inbound_stream_message(message).await,
         }
     }
 
@@ -562,7 +699,7 @@ where
     async fn handle_outbound_request(
         &mut self,
         request: PeerRequest,
-        write_reqs_tx: &mut channel::Sender<NetworkMessage>,
+        write_reqs_tx: &mut channel::Sender<OutboundMessage>,
     ) {
         trace!(
             "Peer {} PeerRequest::{:?}",
@@ -586,7 +723,10 @@ where
                     raw_msg: Vec::from(message.mdata.as_ref()),
                 });
 
-                match write_reqs_tx.send(message).await {
+                match write_reqs_tx
+                    .send(OutboundMessage::new(protocol_id, message))
+                    .await
+                {
                     Ok(_) => {
                         counters::direct_send_messages(&self.network_context, SENT_LABEL).inc();
                         counters::direct_send_bytes(&self.network_context, SENT_LABEL)
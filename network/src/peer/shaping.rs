@@ -0,0 +1,425 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-protocol prioritization and bandwidth shaping of a single peer connection.
+//!
+//! All protocols share one connection to a peer. To keep a burst of e.g. state sync traffic
+//! from delaying consensus messages, outbound messages are queued per protocol and the
+//! [`OutboundQueue`] always hands out the oldest message of the highest priority protocol (see
+//! [`ProtocolId::priority`]) that isn't rate limited. Protocols with the same priority are
+//! served round-robin.
+//!
+//! On top of the per-IP limits applied to the whole connection, protocols can be given their own
+//! inbound and outbound byte rate limits through [`ProtocolRateLimits`].
+
+use crate::{
+    counters,
+    protocols::wire::{
+        handshake::v1::ProtocolId,
+        messaging::v1::{NetworkMessage, Priority},
+    },
+};
+use anyhow::{format_err, Result};
+use aptos_config::{config::RateLimitConfig, network_id::NetworkContext};
+use aptos_rate_limiter::rate_limit::Bucket;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::PeerId;
+use short_hex_str::AsShortHexStr;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap, VecDeque},
+    time::Instant,
+};
+
+/// Maximum number of messages queued on a connection before senders have to wait.
+pub const MAX_QUEUED_OUTBOUND_MESSAGES: usize = 1024;
+
+pub const INBOUND_LABEL: &str = "inbound";
+pub const OUTBOUND_LABEL: &str = "outbound";
+
+/// A message to be written to the remote peer, along with the protocol it belongs to.
+#[derive(Debug)]
+pub struct OutboundMessage {
+    /// `None` for messages generated by the network layer itself, e.g. errors.
+    pub protocol_id: Option<ProtocolId>,
+    pub message: NetworkMessage,
+}
+
+impl OutboundMessage {
+    pub fn new(protocol_id: ProtocolId, message: NetworkMessage) -> Self {
+        Self {
+            protocol_id: Some(protocol_id),
+            message,
+        }
+    }
+
+    /// A message that doesn't belong to any application protocol.
+    pub fn network(message: NetworkMessage) -> Self {
+        Self {
+            protocol_id: None,
+            message,
+        }
+    }
+
+    /// Network messages are never rate limited and go before any application message.
+    fn priority(&self) -> Priority {
+        self.protocol_id.map_or(Priority::MAX, ProtocolId::priority)
+    }
+}
+
+/// The per-protocol rate limits applied to each connection.
+#[derive(Clone, Debug, Default)]
+pub struct ProtocolRateLimits {
+    inbound: HashMap<ProtocolId, RateLimitConfig>,
+    outbound: HashMap<ProtocolId, RateLimitConfig>,
+}
+
+impl ProtocolRateLimits {
+    pub fn new(
+        inbound: HashMap<ProtocolId, RateLimitConfig>,
+        outbound: HashMap<ProtocolId, RateLimitConfig>,
+    ) -> Self {
+        Self { inbound, outbound }
+    }
+
+    /// Builds the limits from the network config, where protocols are referred to by name.
+    /// Disabled limits are ignored.
+    pub fn from_config(
+        inbound: &HashMap<String, RateLimitConfig>,
+        outbound: &HashMap<String, RateLimitConfig>,
+    ) -> Result<Self> {
+        Ok(Self::new(
+            parse_protocol_configs(inbound)?,
+            parse_protocol_configs(outbound)?,
+        ))
+    }
+
+    pub(crate) fn inbound_limiters(
+        &self,
+        network_context: NetworkContext,
+        remote_peer_id: PeerId,
+    ) -> ProtocolRateLimiters {
+        ProtocolRateLimiters::new(
+            network_context,
+            INBOUND_LABEL,
+            remote_peer_id,
+            &self.inbound,
+        )
+    }
+
+    pub(crate) fn outbound_limiters(
+        &self,
+        network_context: NetworkContext,
+        remote_peer_id: PeerId,
+    ) -> ProtocolRateLimiters {
+        ProtocolRateLimiters::new(
+            network_context,
+            OUTBOUND_LABEL,
+            remote_peer_id,
+            &self.outbound,
+        )
+    }
+}
+
+fn parse_protocol_configs(
+    configs: &HashMap<String, RateLimitConfig>,
+) -> Result<HashMap<ProtocolId, RateLimitConfig>> {
+    configs
+        .iter()
+        .filter(|(_, config)| config.enabled)
+        .map(|(name, config)| {
+            ProtocolId::from_name(name)
+                .map(|protocol_id| (protocol_id, *config))
+                .ok_or_else(|| format_err!("Unknown protocol in rate limit config: {}", name))
+        })
+        .collect()
+}
+
+/// The rate limiters of each protocol on a single connection, in one direction.
+pub(crate) struct ProtocolRateLimiters {
+    network_context: NetworkContext,
+    direction: &'static str,
+    buckets: HashMap<ProtocolId, Bucket>,
+}
+
+impl ProtocolRateLimiters {
+    fn new(
+        network_context: NetworkContext,
+        direction: &'static str,
+        remote_peer_id: PeerId,
+        configs: &HashMap<ProtocolId, RateLimitConfig>,
+    ) -> Self {
+        let buckets = configs
+            .iter()
+            .map(|(protocol_id, config)| {
+                let bucket = Bucket::new(
+                    format!("{}_{}", direction, protocol_id.as_str()),
+                    network_context.to_string(),
+                    remote_peer_id.short_str().to_string(),
+                    config
+                        .ip_byte_bucket_size
+                        .saturating_mul(config.initial_bucket_fill_percentage as usize)
+                        / 100,
+                    config.ip_byte_bucket_size,
+                    config.ip_byte_bucket_rate,
+                    None,
+                );
+                (*protocol_id, bucket)
+            })
+            .collect();
+        Self {
+            network_context,
+            direction,
+            buckets,
+        }
+    }
+
+    /// Takes `bytes` tokens from the protocol's bucket. If there aren't enough, returns the time
+    /// at which the message can be retried.
+    pub fn try_acquire(
+        &mut self,
+        protocol_id: Option<ProtocolId>,
+        bytes: usize,
+    ) -> Result<(), Instant> {
+        let bucket = match protocol_id.and_then(|protocol_id| self.buckets.get_mut(&protocol_id)) {
+            Some(bucket) => bucket,
+            None => return Ok(()),
+        };
+        match bucket.acquire_all_tokens(bytes) {
+            Ok(()) => Ok(()),
+            Err(Some(retry_at)) => Err(retry_at),
+            // The message is larger than the bucket and would never fit: let it through as soon
+            // as there are any tokens left, which empties the bucket.
+            Err(None) => bucket.acquire_tokens(bytes).map(|_| ()),
+        }
+    }
+
+    /// Records a message that was delayed or dropped because of its protocol's rate limit.
+    pub fn record_rate_limited(&self, protocol_id: ProtocolId) {
+        counters::protocol_rate_limited(&self.network_context, protocol_id, self.direction).inc();
+    }
+}
+
+struct QueuedMessage {
+    message: OutboundMessage,
+    enqueued_at: Instant,
+    /// Whether the message has already been counted as rate limited
+    rate_limited: bool,
+}
+
+/// Outbound messages waiting to be written on a connection, see the module documentation.
+pub(crate) struct OutboundQueue {
+    network_context: NetworkContext,
+    time_service: TimeService,
+    rate_limiters: ProtocolRateLimiters,
+    /// The queued messages of each protocol, oldest first
+    queues: HashMap<Option<ProtocolId>, VecDeque<QueuedMessage>>,
+    /// The protocols with queued messages grouped by priority, highest first
+    active_protocols: BTreeMap<Reverse<Priority>, VecDeque<Option<ProtocolId>>>,
+    len: usize,
+}
+
+impl OutboundQueue {
+    pub fn new(
+        network_context: NetworkContext,
+        time_service: TimeService,
+        rate_limiters: ProtocolRateLimiters,
+    ) -> Self {
+        Self {
+            network_context,
+            time_service,
+            rate_limiters,
+            queues: HashMap::new(),
+            active_protocols: BTreeMap::new(),
+            len: 0,
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len >= MAX_QUEUED_OUTBOUND_MESSAGES
+    }
+
+    pub fn push(&mut self, message: OutboundMessage) {
+        let protocol_id = message.protocol_id;
+        let priority = message.priority();
+        let queue = self.queues.entry(protocol_id).or_default();
+        if queue.is_empty() {
+            self.active_protocols
+                .entry(Reverse(priority))
+                .or_default()
+                .push_back(protocol_id);
+        }
+        queue.push_back(QueuedMessage {
+            message,
+            enqueued_at: self.time_service.now(),
+            rate_limited: false,
+        });
+        self.len += 1;
+    }
+
+    /// Returns the next message to write. An error of `None` means the queue is empty, otherwise
+    /// all queued messages are rate limited and the error is when to try again.
+    pub fn pop(&mut self) -> Result<OutboundMessage, Option<Instant>> {
+        let mut retry_at: Option<Instant> = None;
+        for protocols in self.active_protocols.values_mut() {
+            for _ in 0..protocols.len() {
+                let protocol_id = protocols
+                    .pop_front()
+                    .expect("Number of protocols was checked");
+                let queue = self
+                    .queues
+                    .get_mut(&protocol_id)
+                    .expect("Active protocols must have a queue");
+                let head = queue
+                    .front_mut()
+                    .expect("Active protocols must have queued messages");
+
+                match self
+                    .rate_limiters
+                    .try_acquire(protocol_id, head.message.message.data_len())
+                {
+                    Ok(()) => {
+                        let queued = queue.pop_front().expect("Head was checked");
+                        if !queue.is_empty() {
+                            protocols.push_back(protocol_id);
+                        }
+                        self.len -= 1;
+                        counters::outbound_queue_delay(&self.network_context, protocol_id).observe(
+                            self.time_service
+                                .now()
+                                .saturating_duration_since(queued.enqueued_at)
+                                .as_secs_f64(),
+                        );
+                        return Ok(queued.message);
+                    }
+                    Err(protocol_retry_at) => {
+                        if let (Some(protocol_id), false) = (protocol_id, head.rate_limited) {
+                            head.rate_limited = true;
+                            self.rate_limiters.record_rate_limited(protocol_id);
+                        }
+                        retry_at =
+                            Some(retry_at.map_or(protocol_retry_at, |t| t.min(protocol_retry_at)));
+                        protocols.push_back(protocol_id);
+                    }
+                }
+            }
+        }
+        Err(retry_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::wire::messaging::v1::{DirectSendMsg, ErrorCode};
+
+    fn direct_send(protocol_id: ProtocolId, len: usize) -> OutboundMessage {
+        OutboundMessage::new(
+            protocol_id,
+            NetworkMessage::DirectSendMsg(DirectSendMsg {
+                protocol_id,
+                priority: 0,
+                raw_msg: vec![0; len],
+            }),
+        )
+    }
+
+    fn outbound_queue(limits: HashMap<ProtocolId, RateLimitConfig>) -> OutboundQueue {
+        let network_context = NetworkContext::mock();
+        let limits = ProtocolRateLimits::new(HashMap::new(), limits);
+        OutboundQueue::new(
+            network_context,
+            TimeService::mock(),
+            limits.outbound_limiters(network_context, PeerId::random()),
+        )
+    }
+
+    fn pop_protocol(queue: &mut OutboundQueue) -> Option<ProtocolId> {
+        queue.pop().unwrap().protocol_id
+    }
+
+    #[test]
+    fn test_priority_order() {
+        let mut queue = outbound_queue(HashMap::new());
+        queue.push(direct_send(ProtocolId::StateSyncDirectSend, 10));
+        queue.push(direct_send(ProtocolId::MempoolDirectSend, 10));
+        queue.push(direct_send(ProtocolId::ConsensusDirectSendBcs, 10));
+        queue.push(OutboundMessage::network(NetworkMessage::Error(
+            ErrorCode::parsing_error(0, 0),
+        )));
+
+        assert_eq!(pop_protocol(&mut queue), None);
+        assert_eq!(
+            pop_protocol(&mut queue),
+            Some(ProtocolId::ConsensusDirectSendBcs)
+        );
+        assert_eq!(
+            pop_protocol(&mut queue),
+            Some(ProtocolId::MempoolDirectSend)
+        );
+        assert_eq!(
+            pop_protocol(&mut queue),
+            Some(ProtocolId::StateSyncDirectSend)
+        );
+        assert_eq!(queue.pop().unwrap_err(), None);
+    }
+
+    #[test]
+    fn test_round_robin_within_priority() {
+        let mut queue = outbound_queue(HashMap::new());
+        for _ in 0..2 {
+            queue.push(direct_send(ProtocolId::MempoolDirectSend, 10));
+        }
+        queue.push(direct_send(ProtocolId::HealthCheckerRpc, 10));
+
+        assert_eq!(
+            pop_protocol(&mut queue),
+            Some(ProtocolId::MempoolDirectSend)
+        );
+        assert_eq!(pop_protocol(&mut queue), Some(ProtocolId::HealthCheckerRpc));
+        assert_eq!(
+            pop_protocol(&mut queue),
+            Some(ProtocolId::MempoolDirectSend)
+        );
+    }
+
+    #[test]
+    fn test_rate_limited_protocol_does_not_block_others() {
+        let mut limits = HashMap::new();
+        limits.insert(
+            ProtocolId::ConsensusDirectSendBcs,
+            RateLimitConfig {
+                ip_byte_bucket_rate: 100,
+                ip_byte_bucket_size: 100,
+                initial_bucket_fill_percentage: 100,
+                enabled: true,
+            },
+        );
+        let mut queue = outbound_queue(limits);
+        queue.push(direct_send(ProtocolId::ConsensusDirectSendBcs, 80));
+        queue.push(direct_send(ProtocolId::ConsensusDirectSendBcs, 80));
+        queue.push(direct_send(ProtocolId::StateSyncDirectSend, 80));
+
+        assert_eq!(
+            pop_protocol(&mut queue),
+            Some(ProtocolId::ConsensusDirectSendBcs)
+        );
+        // The second consensus message has to wait for the bucket to refill
+        assert_eq!(
+            pop_protocol(&mut queue),
+            Some(ProtocolId::StateSyncDirectSend)
+        );
+        assert!(queue.pop().unwrap_err().is_some());
+    }
+
+    #[test]
+    fn test_unknown_protocol_in_config() {
+        let mut configs = HashMap::new();
+        configs.insert("NotAProtocol".to_string(), RateLimitConfig::default());
+        assert!(ProtocolRateLimits::from_config(&configs, &HashMap::new()).is_err());
+
+        let mut configs = HashMap::new();
+        configs.insert("StorageServiceRpc".to_string(), RateLimitConfig::default());
+        assert!(ProtocolRateLimits::from_config(&HashMap::new(), &configs).is_ok());
+    }
+}
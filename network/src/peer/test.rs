@@ -6,7 +6,7 @@ use crate::{
         INBOUND_RPC_TIMEOUT_MS, MAX_CONCURRENT_INBOUND_RPCS, MAX_CONCURRENT_OUTBOUND_RPCS,
         MAX_FRAME_SIZE, MAX_MESSAGE_SIZE, NETWORK_CHANNEL_SIZE,
     },
-    peer::{DisconnectReason, Peer, PeerNotification, PeerRequest},
+    peer::{DisconnectReason, Peer, PeerNotification, PeerRequest, ProtocolRateLimits},
     peer_manager::TransportNotification,
     protocols::{
         direct_send::Message,
@@ -14,15 +14,18 @@ use crate::{
         wire::{
             handshake::v1::{MessagingProtocolVersion, ProtocolIdSet},
             messaging::v1::{
-                DirectSendMsg, MultiplexMessage, MultiplexMessageSink, MultiplexMessageStream,
-                NetworkMessage, RpcRequest, RpcResponse,
+                DirectSendMsg, ErrorCode, MultiplexMessage, MultiplexMessageSink,
+                MultiplexMessageStream, NetworkMessage, RequestId, RpcRequest, RpcResponse,
             },
         },
     },
     transport::{Connection, ConnectionId, ConnectionMetadata},
     ProtocolId,
 };
-use aptos_config::{config::PeerRole, network_id::NetworkContext};
+use aptos_config::{
+    config::{PeerRole, RateLimitConfig},
+    network_id::NetworkContext,
+};
 use aptos_time_service::{MockTimeService, TimeService};
 use aptos_types::{network_address::NetworkAddress, PeerId};
 use bytes::Bytes;
@@ -36,7 +39,11 @@ use futures::{
 };
 use memsocket::MemorySocket;
 use netcore::transport::ConnectionOrigin;
-use std::{collections::HashSet, str::FromStr, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    time::Duration,
+};
 use tokio::runtime::{Handle, Runtime};
 use tokio_util::compat::{
     FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
@@ -54,6 +61,28 @@ fn build_test_peer(
     MemorySocket,
     channel::Receiver<TransportNotification<MemorySocket>>,
     aptos_channel::Receiver<ProtocolId, PeerNotification>,
+) {
+    build_rate_limited_test_peer(
+        executor,
+        time_service,
+        origin,
+        MessagingProtocolVersion::V1,
+        ProtocolRateLimits::default(),
+    )
+}
+
+fn build_rate_limited_test_peer(
+    executor: Handle,
+    time_service: TimeService,
+    origin: ConnectionOrigin,
+    messaging_protocol: MessagingProtocolVersion,
+    protocol_rate_limits: ProtocolRateLimits,
+) -> (
+    Peer<MemorySocket>,
+    PeerHandle,
+    MemorySocket,
+    channel::Receiver<TransportNotification<MemorySocket>>,
+    aptos_channel::Receiver<ProtocolId, PeerNotification>,
 ) {
     let (a, b) = MemorySocket::new_pair();
     let peer_id = PeerId::random();
@@ -63,7 +92,7 @@ fn build_test_peer(
             ConnectionId::default(),
             NetworkAddress::from_str("/ip4/127.0.0.1/tcp/8081").unwrap(),
             origin,
-            MessagingProtocolVersion::V1,
+            messaging_protocol,
             ProtocolIdSet::empty(),
             PeerRole::Unknown,
         ),
@@ -91,6 +120,7 @@ fn build_test_peer(
         MAX_MESSAGE_SIZE,
         None,
         None,
+        protocol_rate_limits,
     );
     let peer_handle = PeerHandle(peer_reqs_tx);
 
@@ -827,6 +857,165 @@ fn peer_send_rpc_timeout() {
     rt.block_on(future::join(peer.start(), test));
 }
 
+// Only a single "hello world" fits in the inbound bucket, and it takes seconds to refill.
+fn single_request_rate_limits() -> ProtocolRateLimits {
+    let mut inbound_limits = HashMap::new();
+    inbound_limits.insert(
+        PROTOCOL,
+        RateLimitConfig {
+            ip_byte_bucket_rate: 1,
+            ip_byte_bucket_size: 11,
+            initial_bucket_fill_percentage: 100,
+            enabled: true,
+        },
+    );
+    ProtocolRateLimits::new(inbound_limits, HashMap::new())
+}
+
+fn hello_world_rpc_request(request_id: RequestId) -> MultiplexMessage {
+    MultiplexMessage::Message(NetworkMessage::RpcRequest(RpcRequest {
+        request_id,
+        protocol_id: PROTOCOL,
+        priority: 0,
+        raw_request: Vec::from("hello world"),
+    }))
+}
+
+// Inbound rpc requests exceeding their protocol's rate limit are rejected with an error, while
+// direct send messages are dropped silently.
+#[test]
+fn peer_recv_rate_limited_rpc() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    let (peer, _peer_handle, mut connection, _connection_notifs_rx, mut peer_notifs_rx) =
+        build_rate_limited_test_peer(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+            MessagingProtocolVersion::V2,
+            single_request_rate_limits(),
+        );
+    let (mut client_sink, mut client_stream) = build_network_sink_stream(&mut connection);
+
+    let client = async move {
+        // The first request is within the limit and gets a response.
+        client_sink.send(&hello_world_rpc_request(1)).await.unwrap();
+        let received = client_stream.next().await.unwrap().unwrap();
+        let expected = MultiplexMessage::Message(NetworkMessage::RpcResponse(RpcResponse {
+            request_id: 1,
+            priority: 0,
+            raw_response: Vec::from("goodbye world"),
+        }));
+        assert_eq!(received, expected);
+
+        // A direct send message over the limit gets no reply at all.
+        let direct_send = MultiplexMessage::Message(NetworkMessage::DirectSendMsg(DirectSendMsg {
+            protocol_id: PROTOCOL,
+            priority: 0,
+            raw_msg: Vec::from("hello world"),
+        }));
+        client_sink.send(&direct_send).await.unwrap();
+
+        // A request over the limit is rejected right away.
+        client_sink.send(&hello_world_rpc_request(2)).await.unwrap();
+        let received = client_stream.next().await.unwrap().unwrap();
+        let expected =
+            MultiplexMessage::Message(NetworkMessage::Error(ErrorCode::rate_limited(PROTOCOL, 2)));
+        assert_eq!(received, expected);
+
+        client_sink.close().await.unwrap();
+    };
+    let server = async move {
+        match peer_notifs_rx.next().await.unwrap() {
+            PeerNotification::RecvRpc(req) => {
+                req.res_tx.send(Ok(Bytes::from("goodbye world"))).unwrap()
+            }
+            received => panic!("Unexpected PeerNotification: {:?}", received),
+        }
+        // Nothing over the limit reaches the application.
+        assert!(peer_notifs_rx.next().await.is_none());
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
+// Peers on the original messaging protocol can't parse the rate limited error, so their rpc
+// requests over the limit are dropped silently.
+#[test]
+fn peer_recv_rate_limited_rpc_v1() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    let (peer, _peer_handle, mut connection, _connection_notifs_rx, mut peer_notifs_rx) =
+        build_rate_limited_test_peer(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+            MessagingProtocolVersion::V1,
+            single_request_rate_limits(),
+        );
+    let (mut client_sink, mut client_stream) = build_network_sink_stream(&mut connection);
+
+    let client = async move {
+        client_sink.send(&hello_world_rpc_request(1)).await.unwrap();
+        let received = client_stream.next().await.unwrap().unwrap();
+        assert!(matches!(
+            received,
+            MultiplexMessage::Message(NetworkMessage::RpcResponse(_))
+        ));
+
+        // The request over the limit gets no reply before the connection is closed.
+        client_sink.send(&hello_world_rpc_request(2)).await.unwrap();
+        client_sink.close().await.unwrap();
+        assert!(client_stream.next().await.is_none());
+    };
+    let server = async move {
+        match peer_notifs_rx.next().await.unwrap() {
+            PeerNotification::RecvRpc(req) => {
+                req.res_tx.send(Ok(Bytes::from("goodbye world"))).unwrap()
+            }
+            received => panic!("Unexpected PeerNotification: {:?}", received),
+        }
+        assert!(peer_notifs_rx.next().await.is_none());
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
+// An outbound rpc request rate limited by the remote peer fails without waiting for its timeout.
+#[test]
+fn peer_send_rate_limited_rpc() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    let (peer, mut peer_handle, mut connection, _connection_notifs_rx, _peer_notifs_rx) =
+        build_test_peer(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+        );
+    let (mut server_sink, mut server_stream) = build_network_sink_stream(&mut connection);
+    let timeout = Duration::from_millis(10_000);
+
+    let client = async move {
+        let result = peer_handle
+            .send_rpc_request(PROTOCOL, Bytes::from(&b"hello world"[..]), timeout)
+            .await;
+        assert!(matches!(result, Err(RpcError::RateLimited)));
+    };
+    let server = async move {
+        let received = server_stream.next().await.unwrap().unwrap();
+        let received = match received {
+            MultiplexMessage::Message(NetworkMessage::RpcRequest(request)) => request,
+            _ => panic!("Expected RpcRequest; unexpected: {:?}", received),
+        };
+
+        let error = MultiplexMessage::Message(NetworkMessage::Error(ErrorCode::rate_limited(
+            received.protocol_id,
+            received.request_id,
+        )));
+        server_sink.send(&error).await.unwrap();
+        assert!(matches!(server_stream.next().await, None));
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
 // PeerManager can request a Peer to shutdown.
 #[test]
 fn peer_disconnect_request() {
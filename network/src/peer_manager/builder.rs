@@ -6,6 +6,7 @@ use crate::{
     counters,
     counters::NETWORK_RATE_LIMIT_METRICS,
     noise::{stream::NoiseStream, HandshakeAuthMode},
+    peer::ProtocolRateLimits,
     peer_manager::{
         conn_notifs_channel, ConnectionRequest, ConnectionRequestSender, PeerManager,
         PeerManagerNotification, PeerManagerRequest, PeerManagerRequestSender,
@@ -54,6 +55,7 @@ struct TransportContext {
     authentication_mode: AuthenticationMode,
     trusted_peers: Arc<RwLock<PeerSet>>,
     enable_proxy_protocol: bool,
+    enable_upgraded_messaging_protocol: bool,
 }
 
 impl TransportContext {
@@ -82,6 +84,7 @@ struct PeerManagerContext {
     inbound_connection_limit: usize,
     inbound_rate_limit_config: Option<RateLimitConfig>,
     outbound_rate_limit_config: Option<RateLimitConfig>,
+    protocol_rate_limits: ProtocolRateLimits,
 }
 
 impl PeerManagerContext {
@@ -107,6 +110,7 @@ impl PeerManagerContext {
         inbound_connection_limit: usize,
         inbound_rate_limit_config: Option<RateLimitConfig>,
         outbound_rate_limit_config: Option<RateLimitConfig>,
+        protocol_rate_limits: ProtocolRateLimits,
     ) -> Self {
         Self {
             pm_reqs_tx,
@@ -126,6 +130,7 @@ impl PeerManagerContext {
             inbound_connection_limit,
             inbound_rate_limit_config,
             outbound_rate_limit_config,
+            protocol_rate_limits,
         }
     }
 
@@ -183,9 +188,11 @@ impl PeerManagerBuilder {
         max_frame_size: usize,
         max_message_size: usize,
         enable_proxy_protocol: bool,
+        enable_upgraded_messaging_protocol: bool,
         inbound_connection_limit: usize,
         inbound_rate_limit_config: Option<RateLimitConfig>,
         outbound_rate_limit_config: Option<RateLimitConfig>,
+        protocol_rate_limits: ProtocolRateLimits,
     ) -> Self {
         // Setup channel to send requests to peer manager.
         let (pm_reqs_tx, pm_reqs_rx) = aptos_channel::new(
@@ -206,6 +213,7 @@ impl PeerManagerBuilder {
                 authentication_mode,
                 trusted_peers: trusted_peers.clone(),
                 enable_proxy_protocol,
+                enable_upgraded_messaging_protocol,
             }),
             peer_manager_context: Some(PeerManagerContext::new(
                 pm_reqs_tx,
@@ -223,6 +231,7 @@ impl PeerManagerBuilder {
                 inbound_connection_limit,
                 inbound_rate_limit_config,
                 outbound_rate_limit_config,
+                protocol_rate_limits,
             )),
             peer_manager: None,
             listen_address,
@@ -266,6 +275,8 @@ impl PeerManagerBuilder {
         let protos = transport_context.supported_protocols;
         let chain_id = transport_context.chain_id;
         let enable_proxy_protocol = transport_context.enable_proxy_protocol;
+        let enable_upgraded_messaging_protocol =
+            transport_context.enable_upgraded_messaging_protocol;
 
         let (key, auth_mode) = match transport_context.authentication_mode {
             AuthenticationMode::MaybeMutual(key) => (
@@ -291,6 +302,7 @@ impl PeerManagerBuilder {
                         chain_id,
                         protos,
                         enable_proxy_protocol,
+                        enable_upgraded_messaging_protocol,
                     ),
                     executor,
                 )))
@@ -307,6 +319,7 @@ impl PeerManagerBuilder {
                     chain_id,
                     protos,
                     enable_proxy_protocol,
+                    enable_upgraded_messaging_protocol,
                 ),
                 executor,
             ))),
@@ -366,6 +379,7 @@ impl PeerManagerBuilder {
             pm_context.inbound_connection_limit,
             inbound_rate_limiters,
             outbound_rate_limiters,
+            pm_context.protocol_rate_limits,
         );
 
         // PeerManager constructor appends a public key to the listen_address.
@@ -14,7 +14,7 @@ use crate::{
     constants,
     counters::{self},
     logging::*,
-    peer::{Peer, PeerNotification, PeerRequest},
+    peer::{Peer, PeerNotification, PeerRequest, ProtocolRateLimits},
     transport::{
         Connection, ConnectionId, ConnectionMetadata, TSocket as TransportTSocket,
         TRANSPORT_TIMEOUT,
@@ -128,6 +128,8 @@ where
     inbound_rate_limiters: IpAddrTokenBucketLimiter,
     /// Keyed storage of all outbound rate limiters
     outbound_rate_limiters: IpAddrTokenBucketLimiter,
+    /// Per-protocol rate limits applied to each connection
+    protocol_rate_limits: ProtocolRateLimits,
 }
 
 impl<TTransport, TSocket> PeerManager<TTransport, TSocket>
@@ -159,6 +161,7 @@ where
         inbound_connection_limit: usize,
         inbound_rate_limiters: IpAddrTokenBucketLimiter,
         outbound_rate_limiters: IpAddrTokenBucketLimiter,
+        protocol_rate_limits: ProtocolRateLimits,
     ) -> Self {
         let (transport_notifs_tx, transport_notifs_rx) = channel::new(
             channel_size,
@@ -204,6 +207,7 @@ where
             inbound_connection_limit,
             inbound_rate_limiters,
             outbound_rate_limiters,
+            protocol_rate_limits,
         }
     }
 
@@ -691,6 +695,7 @@ where
             self.max_message_size,
             Some(inbound_rate_limiter),
             Some(outbound_rate_limiter),
+            self.protocol_rate_limits.clone(),
         );
         self.executor.spawn(peer.start());
 
@@ -4,7 +4,7 @@
 use crate::{
     application::storage::PeerMetadataStorage,
     constants,
-    peer::DisconnectReason,
+    peer::{DisconnectReason, ProtocolRateLimits},
     peer_manager::{
         conn_notifs_channel, error::PeerManagerError, ConnectionNotification, ConnectionRequest,
         PeerManager, PeerManagerNotification, PeerManagerRequest, TransportNotification,
@@ -118,6 +118,7 @@ fn build_test_peer_manager(
         MAX_INBOUND_CONNECTIONS,
         TokenBucketRateLimiter::open("inbound"),
         TokenBucketRateLimiter::open("outbound"),
+        ProtocolRateLimits::default(),
     );
 
     (
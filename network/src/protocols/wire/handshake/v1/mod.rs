@@ -12,6 +12,7 @@
 //!
 //! [AptosNet Handshake v1 Specification]: https://github.com/aptos-labs/aptos-core/blob/main/specifications/network/handshake-v1.md
 
+use crate::protocols::wire::messaging::v1::Priority;
 use anyhow::anyhow;
 use aptos_config::network_id::NetworkId;
 use aptos_types::chain_id::ChainId;
@@ -56,6 +57,7 @@ pub enum ProtocolId {
     PeerMonitoringServiceRpc = 10,
     ConsensusRpcCompressed = 11,
     ConsensusDirectSendCompressed = 12,
+    PeerExchangeRpc = 13,
 }
 
 /// The encoding types for Protocols
@@ -82,6 +84,7 @@ impl ProtocolId {
             PeerMonitoringServiceRpc => "PeerMonitoringServiceRpc",
             ConsensusRpcCompressed => "ConsensusRpcCompressed",
             ConsensusDirectSendCompressed => "ConsensusDirectSendCompressed",
+            PeerExchangeRpc => "PeerExchangeRpc",
         }
     }
 
@@ -100,9 +103,40 @@ impl ProtocolId {
             ProtocolId::PeerMonitoringServiceRpc,
             ProtocolId::ConsensusRpcCompressed,
             ProtocolId::ConsensusDirectSendCompressed,
+            ProtocolId::PeerExchangeRpc,
         ]
     }
 
+    /// Looks up a `ProtocolId` by the name returned from [`ProtocolId::as_str`]
+    pub fn from_name(name: &str) -> Option<ProtocolId> {
+        ProtocolId::all()
+            .iter()
+            .find(|protocol_id| protocol_id.as_str() == name)
+            .copied()
+    }
+
+    /// The priority used to schedule outbound messages of this protocol on a connection,
+    /// higher priorities are always sent first. Consensus is latency critical, while state
+    /// sync transfers large amounts of data and can tolerate being delayed.
+    pub fn priority(self) -> Priority {
+        use ProtocolId::*;
+        match self {
+            ConsensusRpcBcs
+            | ConsensusDirectSendBcs
+            | ConsensusDirectSendJson
+            | ConsensusRpcJson
+            | ConsensusRpcCompressed
+            | ConsensusDirectSendCompressed => 2,
+            MempoolDirectSend
+            | DiscoveryDirectSend
+            | HealthCheckerRpc
+            | MempoolRpc
+            | PeerMonitoringServiceRpc
+            | PeerExchangeRpc => 1,
+            StateSyncDirectSend | StorageServiceRpc => 0,
+        }
+    }
+
     /// How to encode messages for a given `ProtocolId`
     fn encoding(self) -> Encoding {
         match self {
@@ -264,12 +298,17 @@ impl<'a> FromIterator<&'a ProtocolId> for ProtocolIdSet {
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 pub enum MessagingProtocolVersion {
     V1 = 0,
+    /// Adds [`ErrorCode::RateLimited`] to reject rate limited rpc requests.
+    ///
+    /// [`ErrorCode::RateLimited`]: crate::protocols::wire::messaging::v1::ErrorCode::RateLimited
+    V2 = 1,
 }
 
 impl MessagingProtocolVersion {
     fn as_str(&self) -> &str {
         match self {
             Self::V1 => "V1",
+            Self::V2 => "V2",
         }
     }
 }
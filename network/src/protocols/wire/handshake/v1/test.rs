@@ -117,6 +117,31 @@ fn common_protocols() {
     );
 }
 
+// Peers advertising the upgraded messaging protocol use it with each other, but still fall back
+// to the original version with peers that only support that one.
+#[test]
+fn upgraded_messaging_protocol() {
+    let protocols = ProtocolIdSet::from_iter([ProtocolId::ConsensusRpcBcs]);
+    let mut upgraded = HandshakeMsg::from_supported(protocols.clone());
+    upgraded
+        .supported_protocols
+        .insert(MessagingProtocolVersion::V2, protocols.clone());
+    let original = HandshakeMsg::from_supported(protocols.clone());
+
+    assert_eq!(
+        (MessagingProtocolVersion::V2, protocols.clone()),
+        upgraded.perform_handshake(&upgraded).unwrap()
+    );
+    assert_eq!(
+        (MessagingProtocolVersion::V1, protocols.clone()),
+        upgraded.perform_handshake(&original).unwrap()
+    );
+    assert_eq!(
+        (MessagingProtocolVersion::V1, protocols),
+        original.perform_handshake(&upgraded).unwrap()
+    );
+}
+
 #[test]
 fn is_empty() {
     assert!(ProtocolIdSet::empty().is_empty());
@@ -62,6 +62,15 @@ impl NetworkMessage {
             NetworkMessage::DirectSendMsg(message) => message.raw_msg.len(),
         }
     }
+
+    /// The protocol of requests and direct send messages. Responses and errors don't carry one.
+    pub fn protocol_id(&self) -> Option<ProtocolId> {
+        match self {
+            NetworkMessage::RpcRequest(request) => Some(request.protocol_id),
+            NetworkMessage::DirectSendMsg(message) => Some(message.protocol_id),
+            NetworkMessage::Error(_) | NetworkMessage::RpcResponse(_) => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
@@ -71,12 +80,22 @@ pub enum ErrorCode {
     ParsingError(ParsingErrorType),
     /// A message was received for a protocol that is not supported over this connection.
     NotSupported(NotSupportedType),
+    /// An rpc request was dropped because it exceeded the inbound rate limit of its protocol.
+    /// Only sent over messaging protocol V2 and later.
+    RateLimited(RateLimitedType),
 }
 
 impl ErrorCode {
     pub fn parsing_error(message: u8, protocol: u8) -> Self {
         ErrorCode::ParsingError(ParsingErrorType { message, protocol })
     }
+
+    pub fn rate_limited(protocol_id: ProtocolId, request_id: RequestId) -> Self {
+        ErrorCode::RateLimited(RateLimitedType {
+            protocol_id,
+            request_id,
+        })
+    }
 }
 
 /// Flags an invalid network message with as much header information as possible. This is a message
@@ -97,6 +116,14 @@ pub enum NotSupportedType {
     DirectSendMsg(ProtocolId),
 }
 
+/// Flags a rate limited rpc request, so the requester can fail it without waiting for a timeout.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct RateLimitedType {
+    pub protocol_id: ProtocolId,
+    pub request_id: RequestId,
+}
+
 /// Create alias RequestId for `u32`.
 pub type RequestId = u32;
 
@@ -41,10 +41,13 @@ mod test;
 /// A timeout for the connection to open and complete all of the upgrade steps.
 pub const TRANSPORT_TIMEOUT: Duration = Duration::from_secs(30);
 
-/// Currently supported messaging protocol version.
-/// TODO: Add ability to support more than one messaging protocol.
+/// Messaging protocol version supported by every node.
 pub const SUPPORTED_MESSAGING_PROTOCOL: MessagingProtocolVersion = MessagingProtocolVersion::V1;
 
+/// Newer messaging protocol version, only advertised when enabled since nodes running older
+/// releases fail to parse a handshake containing it.
+pub const UPGRADED_MESSAGING_PROTOCOL: MessagingProtocolVersion = MessagingProtocolVersion::V2;
+
 /// Global connection-id generator.
 static CONNECTION_ID_GENERATOR: ConnectionIdGenerator = ConnectionIdGenerator::new();
 
@@ -430,9 +433,13 @@ where
         chain_id: ChainId,
         application_protocols: ProtocolIdSet,
         enable_proxy_protocol: bool,
+        enable_upgraded_messaging_protocol: bool,
     ) -> Self {
         // build supported protocols
         let mut supported_protocols = BTreeMap::new();
+        if enable_upgraded_messaging_protocol {
+            supported_protocols.insert(UPGRADED_MESSAGING_PROTOCOL, application_protocols.clone());
+        }
         supported_protocols.insert(SUPPORTED_MESSAGING_PROTOCOL, application_protocols);
 
         let identity_pubkey = identity_key.public_key();
@@ -156,6 +156,7 @@ where
         chain_id,
         supported_protocols.clone(),
         false, /* Disable proxy protocol */
+        false, /* Disable upgraded messaging protocol */
     );
 
     let dialer_transport = AptosNetTransport::new(
@@ -168,6 +169,7 @@ where
         chain_id,
         supported_protocols.clone(),
         false, /* Disable proxy protocol */
+        false, /* Disable upgraded messaging protocol */
     );
 
     (
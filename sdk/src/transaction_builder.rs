@@ -5,7 +5,10 @@ use crate::{
     move_types::account_address::AccountAddress,
     types::{
         chain_id::ChainId,
-        transaction::{authenticator::AuthenticationKey, RawTransaction, TransactionPayload},
+        transaction::{
+            authenticator::AuthenticationKey, RawTransaction, RawTransactionWithData,
+            TransactionPayload,
+        },
     },
 };
 use aptos_crypto::ed25519::Ed25519PublicKey;
@@ -23,6 +26,8 @@ pub struct TransactionBuilder {
     gas_unit_price: u64,
     expiration_timestamp_secs: u64,
     chain_id: ChainId,
+    secondary_signers: Vec<AccountAddress>,
+    fee_payer: Option<AccountAddress>,
 }
 
 impl TransactionBuilder {
@@ -39,6 +44,8 @@ impl TransactionBuilder {
             gas_unit_price: 1,
             sender: None,
             sequence_number: None,
+            secondary_signers: Vec::new(),
+            fee_payer: None,
         }
     }
 
@@ -72,6 +79,17 @@ impl TransactionBuilder {
         self
     }
 
+    pub fn secondary_signers(mut self, secondary_signers: Vec<AccountAddress>) -> Self {
+        self.secondary_signers = secondary_signers;
+        self
+    }
+
+    /// Sets the account which pays for gas on behalf of the sender.
+    pub fn fee_payer(mut self, fee_payer: AccountAddress) -> Self {
+        self.fee_payer = Some(fee_payer);
+        self
+    }
+
     pub fn build(self) -> RawTransaction {
         RawTransaction::new(
             self.sender.expect("sender must have been set"),
@@ -84,6 +102,19 @@ impl TransactionBuilder {
             self.chain_id,
         )
     }
+
+    /// Builds the message of a sponsored transaction. The sender, every secondary signer and the
+    /// fee payer each sign this message, and the resulting authenticators are put together with
+    /// `SignedTransaction::new_fee_payer`.
+    pub fn build_fee_payer(self) -> RawTransactionWithData {
+        let secondary_signers = self.secondary_signers.clone();
+        let fee_payer = self.fee_payer.expect("fee payer must have been set");
+        RawTransactionWithData::new_multi_agent_with_fee_payer(
+            self.build(),
+            secondary_signers,
+            fee_payer,
+        )
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -183,6 +214,8 @@ impl TransactionFactory {
             gas_unit_price: self.gas_unit_price,
             expiration_timestamp_secs: self.expiration_timestamp(),
             chain_id: self.chain_id,
+            secondary_signers: Vec::new(),
+            fee_payer: None,
         }
     }
 
@@ -4,12 +4,17 @@
 use crate::{
     crypto::{
         ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
-        traits::Uniform,
+        secp256r1_ecdsa::{Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey},
+        traits::{SigningKey, Uniform},
     },
     transaction_builder::TransactionBuilder,
     types::{
         account_address::AccountAddress,
-        transaction::{authenticator::AuthenticationKey, RawTransaction, SignedTransaction},
+        transaction::{
+            authenticator::{AccountAuthenticator, AuthenticationKey},
+            webauthn::WebAuthnSignature,
+            RawTransaction, RawTransactionWithData, SignedTransaction,
+        },
     },
 };
 
@@ -101,6 +106,48 @@ impl LocalAccount {
             .into_inner()
     }
 
+    /// Builds and signs a sponsored transaction, where `fee_payer_signer` pays for gas on behalf
+    /// of this account. Only this account's sequence number is used and incremented.
+    pub fn sign_fee_payer_with_transaction_builder(
+        &mut self,
+        secondary_signers: Vec<&Self>,
+        fee_payer_signer: &Self,
+        builder: TransactionBuilder,
+    ) -> SignedTransaction {
+        let secondary_signer_addresses = secondary_signers
+            .iter()
+            .map(|signer| signer.address())
+            .collect();
+        let secondary_signer_privkeys = secondary_signers
+            .iter()
+            .map(|signer| signer.private_key())
+            .collect();
+        let raw_txn = builder
+            .sender(self.address())
+            .sequence_number(self.sequence_number())
+            .build();
+        *self.sequence_number_mut() += 1;
+        raw_txn
+            .sign_fee_payer(
+                self.private_key(),
+                secondary_signer_addresses,
+                secondary_signer_privkeys,
+                fee_payer_signer.address(),
+                fee_payer_signer.private_key(),
+            )
+            .expect("Signing fee payer txn failed")
+            .into_inner()
+    }
+
+    /// Co-signs a multi-agent or sponsored transaction built by someone else, e.g. with
+    /// `TransactionBuilder::build_fee_payer`, and returns this account's authenticator.
+    pub fn sign_raw_transaction_with_data(
+        &self,
+        message: &RawTransactionWithData,
+    ) -> AccountAuthenticator {
+        AccountAuthenticator::ed25519(self.public_key().clone(), self.private_key().sign(message))
+    }
+
     pub fn address(&self) -> AccountAddress {
         self.address
     }
@@ -183,3 +230,186 @@ impl From<Ed25519PrivateKey> for AccountKey {
         Self::from_private_key(private_key)
     }
 }
+
+/// Secp256r1LocalAccount represents an account on the Aptos blockchain whose authentication key is
+/// derived from a Secp256r1 (P-256) ECDSA key, e.g., a key held by a secure enclave. It can be
+/// used like a `LocalAccount` to sign transactions, which are submitted with a single-sender
+/// authenticator.
+#[derive(Debug)]
+pub struct Secp256r1LocalAccount {
+    /// Address of the account.
+    address: AccountAddress,
+    private_key: Secp256r1EcdsaPrivateKey,
+    public_key: Secp256r1EcdsaPublicKey,
+    /// Latest known sequence number of the account, it can be different from validator.
+    sequence_number: u64,
+}
+
+impl Secp256r1LocalAccount {
+    /// Create a new representation of an account locally. Note: This function
+    /// does not actually create an account on the Aptos blockchain, just a
+    /// local representation.
+    pub fn new(
+        address: AccountAddress,
+        private_key: Secp256r1EcdsaPrivateKey,
+        sequence_number: u64,
+    ) -> Self {
+        let public_key = Secp256r1EcdsaPublicKey::from(&private_key);
+        Self {
+            address,
+            private_key,
+            public_key,
+            sequence_number,
+        }
+    }
+
+    /// Generate a new account locally. Note: This function does not actually
+    /// create an account on the Aptos blockchain, it just generates a new
+    /// account locally.
+    pub fn generate<R>(rng: &mut R) -> Self
+    where
+        R: ::rand_core::RngCore + ::rand_core::CryptoRng,
+    {
+        let private_key = Secp256r1EcdsaPrivateKey::generate(rng);
+        let public_key = Secp256r1EcdsaPublicKey::from(&private_key);
+        let address = AuthenticationKey::secp256r1_ecdsa(&public_key).derived_address();
+
+        Self::new(address, private_key, 0)
+    }
+
+    pub fn sign_transaction(&self, txn: RawTransaction) -> SignedTransaction {
+        txn.sign_secp256r1_ecdsa(&self.private_key, self.public_key.clone())
+            .expect("Signing a txn can't fail")
+            .into_inner()
+    }
+
+    pub fn sign_with_transaction_builder(
+        &mut self,
+        builder: TransactionBuilder,
+    ) -> SignedTransaction {
+        let raw_txn = builder
+            .sender(self.address())
+            .sequence_number(self.sequence_number())
+            .build();
+        *self.sequence_number_mut() += 1;
+        self.sign_transaction(raw_txn)
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    pub fn private_key(&self) -> &Secp256r1EcdsaPrivateKey {
+        &self.private_key
+    }
+
+    pub fn public_key(&self) -> &Secp256r1EcdsaPublicKey {
+        &self.public_key
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::secp256r1_ecdsa(&self.public_key)
+    }
+
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    pub fn sequence_number_mut(&mut self) -> &mut u64 {
+        &mut self.sequence_number
+    }
+}
+
+/// WebAuthnLocalAccount represents an account on the Aptos blockchain whose Secp256r1 (P-256)
+/// ECDSA key is held by a WebAuthn authenticator, e.g., a passkey. It signs transactions the way
+/// such an authenticator does for the relying party `rp_id`, when used from `origin`, and is
+/// mostly useful to exercise passkey flows without a browser.
+#[derive(Debug)]
+pub struct WebAuthnLocalAccount {
+    account: Secp256r1LocalAccount,
+    rp_id: String,
+    origin: String,
+    /// Number of assertions produced by the authenticator.
+    sign_count: u32,
+}
+
+impl WebAuthnLocalAccount {
+    /// Create a new representation of an account locally. Note: This function
+    /// does not actually create an account on the Aptos blockchain, just a
+    /// local representation.
+    pub fn new(
+        address: AccountAddress,
+        private_key: Secp256r1EcdsaPrivateKey,
+        sequence_number: u64,
+        rp_id: String,
+        origin: String,
+    ) -> Self {
+        Self {
+            account: Secp256r1LocalAccount::new(address, private_key, sequence_number),
+            rp_id,
+            origin,
+            sign_count: 0,
+        }
+    }
+
+    /// Generate a new account locally. Note: This function does not actually
+    /// create an account on the Aptos blockchain, it just generates a new
+    /// account locally.
+    pub fn generate<R>(rng: &mut R, rp_id: String, origin: String) -> Self
+    where
+        R: ::rand_core::RngCore + ::rand_core::CryptoRng,
+    {
+        Self {
+            account: Secp256r1LocalAccount::generate(rng),
+            rp_id,
+            origin,
+            sign_count: 0,
+        }
+    }
+
+    pub fn sign_transaction(&mut self, txn: RawTransaction) -> SignedTransaction {
+        self.sign_count += 1;
+        let authenticator_data =
+            WebAuthnSignature::authenticator_data(&self.rp_id, self.sign_count);
+        txn.sign_webauthn(
+            self.account.private_key(),
+            self.account.public_key().clone(),
+            authenticator_data,
+            &self.origin,
+        )
+        .expect("Signing a txn can't fail")
+        .into_inner()
+    }
+
+    pub fn sign_with_transaction_builder(
+        &mut self,
+        builder: TransactionBuilder,
+    ) -> SignedTransaction {
+        let raw_txn = builder
+            .sender(self.address())
+            .sequence_number(self.sequence_number())
+            .build();
+        *self.sequence_number_mut() += 1;
+        self.sign_transaction(raw_txn)
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.account.address()
+    }
+
+    pub fn public_key(&self) -> &Secp256r1EcdsaPublicKey {
+        self.account.public_key()
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        self.account.authentication_key()
+    }
+
+    pub fn sequence_number(&self) -> u64 {
+        self.account.sequence_number()
+    }
+
+    pub fn sequence_number_mut(&mut self) -> &mut u64 {
+        self.account.sequence_number_mut()
+    }
+}
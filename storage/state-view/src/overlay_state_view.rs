@@ -0,0 +1,54 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{StateView, StateViewId};
+use anyhow::Result;
+use aptos_types::state_store::{state_key::StateKey, state_storage_usage::StateStorageUsage};
+use std::collections::HashMap;
+
+/// A [`StateView`] with some of the values of another one replaced, e.g. to execute against
+/// changes which haven't been committed
+pub struct OverlayStateView<S> {
+    base: S,
+    overrides: HashMap<StateKey, Option<Vec<u8>>>,
+}
+
+impl<S> OverlayStateView<S> {
+    pub fn new(base: S) -> Self {
+        Self {
+            base,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Replaces the value of `state_key`, or deletes it if `value` is `None`
+    pub fn set(&mut self, state_key: StateKey, value: Option<Vec<u8>>) {
+        self.overrides.insert(state_key, value);
+    }
+
+    /// The values replaced so far
+    pub fn into_overrides(self) -> HashMap<StateKey, Option<Vec<u8>>> {
+        self.overrides
+    }
+}
+
+impl<S: StateView> StateView for OverlayStateView<S> {
+    fn id(&self) -> StateViewId {
+        self.base.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        match self.overrides.get(state_key) {
+            Some(value) => Ok(value.clone()),
+            None => self.base.get_state_value(state_key),
+        }
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base.is_genesis()
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.base.get_usage()
+    }
+}
@@ -5,6 +5,7 @@ use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
     hash::{CryptoHasher as _, TestOnlyHasher},
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    secp256r1_ecdsa::{Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey},
     traits::{SigningKey, Uniform},
 };
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
@@ -43,6 +44,12 @@ fn trace_crypto_values(tracer: &mut Tracer, samples: &mut Samples) -> Result<()>
     tracer.trace_value::<MultiEd25519PublicKey>(samples, &public_key.into())?;
     tracer.trace_value(samples, &signature)?;
     tracer.trace_value::<MultiEd25519Signature>(samples, &signature.into())?;
+
+    let secp256r1_private_key = Secp256r1EcdsaPrivateKey::generate(&mut rng);
+    let secp256r1_public_key: Secp256r1EcdsaPublicKey = (&secp256r1_private_key).into();
+    let secp256r1_signature = secp256r1_private_key.sign(&message);
+    tracer.trace_value(samples, &secp256r1_public_key)?;
+    tracer.trace_value(samples, &secp256r1_signature)?;
     Ok(())
 }
 
@@ -5,6 +5,7 @@ use aptos_crypto::{
     bls12381,
     ed25519::Ed25519PrivateKey,
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    secp256r1_ecdsa::Secp256r1EcdsaPrivateKey,
     traits::{SigningKey, Uniform},
     PrivateKey,
 };
@@ -36,12 +37,18 @@ fn trace_crypto_values(tracer: &mut Tracer, samples: &mut Samples) -> Result<()>
     let public_key = private_key.public_key();
     let signature = private_key.sign(&message);
 
+    let secp256r1_private_key = Secp256r1EcdsaPrivateKey::generate(&mut rng);
+    let secp256r1_public_key = secp256r1_private_key.public_key();
+    let secp256r1_signature = secp256r1_private_key.sign(&message);
+
     let bls_private_key = bls12381::PrivateKey::generate(&mut rng);
     let bls_public_key = bls_private_key.public_key();
     let bls_signature = bls_private_key.sign(&message);
 
     tracer.trace_value(samples, &public_key)?;
     tracer.trace_value(samples, &signature)?;
+    tracer.trace_value(samples, &secp256r1_public_key)?;
+    tracer.trace_value(samples, &secp256r1_signature)?;
     tracer.trace_value(samples, &bls_public_key)?;
     tracer.trace_value(samples, &bls_signature)?;
     tracer.trace_value::<MultiEd25519PublicKey>(samples, &public_key.into())?;
@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::smoke_test_environment::SwarmBuilder;
+use crate::{smoke_test_environment::SwarmBuilder, test_utils::aptos_framework_dir};
 use aptos::move_tool::MemberId;
 use aptos::test::CliTestFramework;
 use aptos_logger::info;
@@ -9,21 +9,11 @@ use framework::{BuildOptions, BuiltPackage};
 use move_deps::move_core_types::account_address::AccountAddress;
 use move_deps::move_package::source_package::manifest_parser::parse_move_manifest_from_file;
 use std::collections::BTreeMap;
-use std::path::PathBuf;
 use std::str::FromStr;
 
 const PACKAGE_NAME: &str = "AwesomePackage";
 const HELLO_BLOCKCHAIN: &str = "hello_blockchain";
 
-fn aptos_framework_dir() -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("..")
-        .join("..")
-        .join("aptos-move")
-        .join("framework")
-        .join("aptos-framework")
-}
-
 #[tokio::test]
 async fn test_move_compile_flow() {
     let mut cli = CliTestFramework::local_new(1);
@@ -1,28 +1,31 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::smoke_test_environment::SwarmBuilder;
+use crate::{smoke_test_environment::SwarmBuilder, test_utils::aptos_framework_dir};
 use anyhow::anyhow;
 use aptos::common::types::GasOptions;
+use aptos::move_tool::MemberId;
 use aptos::test::INVALID_ACCOUNT;
 use aptos::{account::create::DEFAULT_FUNDED_COINS, test::CliTestFramework};
 use aptos_config::config::PersistableConfig;
 use aptos_config::{config::ApiConfig, utils::get_available_port};
 use aptos_crypto::ed25519::{Ed25519PrivateKey, Ed25519Signature};
 use aptos_crypto::{HashValue, PrivateKey};
-use aptos_rest_client::aptos_api_types::{TransactionPayload, UserTransaction};
+use aptos_rest_client::aptos_api_types::{MoveType, TransactionPayload, UserTransaction};
 use aptos_rest_client::Transaction;
 use aptos_rosetta::common::BlockHash;
 use aptos_rosetta::types::{
-    AccountIdentifier, BlockResponse, Operation, OperationStatusType, OperationType,
-    TransactionType,
+    AccountIdentifier, BlockEvent, BlockEventType, BlockResponse, BlockTransactionRequest,
+    Currency, CurrencyMetadata, EventsBlocksRequest, MempoolRequest, MempoolTransactionRequest,
+    Operation, OperationStatusType, OperationType, SearchTransactionsRequest,
+    TransactionIdentifier, TransactionType,
 };
 use aptos_rosetta::{
     client::RosettaClient,
     common::{native_coin, BLOCKCHAIN, Y2K_MS},
     types::{
         AccountBalanceRequest, AccountBalanceResponse, BlockIdentifier, BlockRequest,
-        NetworkIdentifier, NetworkRequest, PartialBlockIdentifier,
+        NetworkIdentifier, NetworkRequest, PartialBlockIdentifier, StakeSubAccount,
     },
     ROSETTA_VERSION,
 };
@@ -31,13 +34,18 @@ use aptos_types::transaction::SignedTransaction;
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use cached_packages::aptos_stdlib;
 use forge::{LocalSwarm, Node, NodeExt, Swarm};
-use std::collections::BTreeMap;
+use move_deps::move_core_types::{
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+};
+use std::collections::{BTreeMap, HashSet};
 use std::convert::TryFrom;
 use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::{future::Future, time::Duration};
 use tokio::{task::JoinHandle, time::Instant};
 
+const MOON_COIN_ADDRESS: &str = "MoonCoinType";
 const DEFAULT_MAX_WAIT_MS: u64 = 5000;
 const DEFAULT_INTERVAL_MS: u64 = 100;
 static DEFAULT_MAX_WAIT_DURATION: Duration = Duration::from_millis(DEFAULT_MAX_WAIT_MS);
@@ -51,6 +59,13 @@ pub async fn setup_test(
         .with_aptos()
         .build_with_cli(num_accounts)
         .await;
+    let rosetta_client = start_rosetta(&swarm, HashSet::new()).await;
+
+    (swarm, cli, faucet, rosetta_client)
+}
+
+/// Starts a Rosetta server against the swarm, supporting the native coin and `currencies`
+async fn start_rosetta(swarm: &LocalSwarm, currencies: HashSet<Currency>) -> RosettaClient {
     let validator = swarm.validators().next().unwrap();
 
     // And the client
@@ -76,6 +91,7 @@ pub async fn setup_test(
         Some(aptos_rest_client::Client::new(
             validator.rest_api_endpoint(),
         )),
+        currencies,
     )
     .await
     .unwrap();
@@ -85,7 +101,7 @@ pub async fn setup_test(
         .await
         .unwrap();
 
-    (swarm, cli, faucet, rosetta_client)
+    rosetta_client
 }
 
 #[tokio::test]
@@ -327,6 +343,7 @@ async fn test_transfer() {
             sender_private_key,
             receiver,
             sender_balance,
+            native_coin(),
             expiry_time(Duration::from_secs(5)).as_secs(),
             None,
             None,
@@ -342,6 +359,7 @@ async fn test_transfer() {
             sender_private_key,
             receiver,
             sender_balance + 200,
+            native_coin(),
             expiry_time(Duration::from_secs(5)).as_secs(),
             None,
             None,
@@ -380,6 +398,7 @@ async fn test_transfer() {
             sender_private_key,
             receiver,
             sender_balance - gas_usage + 1,
+            native_coin(),
             expiry_time(Duration::from_secs(5)).as_secs(),
             None,
             None,
@@ -431,6 +450,229 @@ async fn test_transfer() {
     );
 }
 
+#[tokio::test]
+async fn test_non_native_coin() {
+    let (swarm, mut cli, _faucet) = SwarmBuilder::new_local(1)
+        .with_aptos()
+        .build_with_cli(2)
+        .await;
+    let chain_id = swarm.chain_id();
+    let network = NetworkIdentifier::from(chain_id);
+    let rest_client = swarm.validators().next().unwrap().rest_client();
+    let issuer = cli.account_id(0);
+    let receiver = cli.account_id(1);
+
+    // Publish a coin type, and mint some coins to the issuer
+    cli.init_move_dir();
+    let mut package_addresses = BTreeMap::new();
+    package_addresses.insert(MOON_COIN_ADDRESS, "_");
+    cli.init_package(
+        "MoonCoin".to_string(),
+        package_addresses,
+        Some(aptos_framework_dir()),
+    )
+    .await
+    .unwrap();
+    let sources_dir = cli.move_dir().join("sources");
+    std::fs::create_dir_all(&sources_dir).unwrap();
+    std::fs::write(
+        sources_dir.join("MoonCoinType.move"),
+        include_str!("../../../aptos-move/move-examples/moon_coin/sources/MoonCoinType.move"),
+    )
+    .unwrap();
+    let issuer_str = issuer.to_hex_literal();
+    let mut named_addresses = BTreeMap::new();
+    named_addresses.insert(MOON_COIN_ADDRESS, issuer_str.as_str());
+    cli.publish_package(0, None, named_addresses, false)
+        .await
+        .unwrap();
+
+    let coin_tag = StructTag {
+        address: issuer,
+        module: Identifier::new("moon_coin").unwrap(),
+        name: Identifier::new("MoonCoin").unwrap(),
+        type_params: vec![],
+    };
+    let coin_type = coin_tag.to_string();
+    let managed_coin =
+        |function: &str| MemberId::from_str(&format!("0x1::managed_coin::{}", function)).unwrap();
+    cli.run_function(
+        0,
+        None,
+        managed_coin("initialize"),
+        vec!["string:Moon Coin", "string:MOON", "u8:6", "bool:false"],
+        vec![&coin_type],
+    )
+    .await
+    .unwrap();
+    cli.run_function(0, None, managed_coin("register"), vec![], vec![&coin_type])
+        .await
+        .unwrap();
+    cli.run_function(1, None, managed_coin("register"), vec![], vec![&coin_type])
+        .await
+        .unwrap();
+    let mint_to = format!("address:{}", issuer_str);
+    cli.run_function(
+        0,
+        None,
+        managed_coin("mint"),
+        vec![&mint_to, "u64:1000000"],
+        vec![&coin_type],
+    )
+    .await
+    .unwrap();
+
+    // The currency is served once it's configured and matches the on-chain `CoinInfo`
+    let moon_coin = Currency {
+        symbol: "MOON".to_string(),
+        decimals: 6,
+        metadata: Some(CurrencyMetadata {
+            move_type: coin_type.clone(),
+        }),
+    };
+    let rosetta_client = start_rosetta(&swarm, vec![moon_coin.clone()].into_iter().collect()).await;
+
+    // Balances are looked up in the `CoinStore` of the coin type
+    coin_has_balance(&rosetta_client, chain_id, issuer, &moon_coin, 1_000_000)
+        .await
+        .unwrap();
+    coin_has_balance(&rosetta_client, chain_id, receiver, &moon_coin, 0)
+        .await
+        .unwrap();
+
+    // Transfers are built with `coin::transfer` of the coin type
+    const TRANSFER_AMOUNT: u64 = 1000;
+    let expiry_time = expiry_time(Duration::from_secs(5));
+    let txn_hash = rosetta_client
+        .transfer(
+            &network,
+            cli.private_key(0),
+            receiver,
+            TRANSFER_AMOUNT,
+            moon_coin.clone(),
+            expiry_time.as_secs(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .hash;
+    let txn = wait_for_transaction(&rest_client, expiry_time, txn_hash)
+        .await
+        .expect("Transfer should succeed");
+    if let TransactionPayload::EntryFunctionPayload(ref payload) = txn.request.payload {
+        assert_eq!("0x1::coin::transfer", payload.function.to_string());
+        assert_eq!(
+            vec![MoveType::from(TypeTag::Struct(coin_tag))],
+            payload.type_arguments
+        );
+    } else {
+        panic!("Transfer should be an entry function");
+    }
+
+    coin_has_balance(
+        &rosetta_client,
+        chain_id,
+        issuer,
+        &moon_coin,
+        1_000_000 - TRANSFER_AMOUNT,
+    )
+    .await
+    .unwrap();
+    coin_has_balance(
+        &rosetta_client,
+        chain_id,
+        receiver,
+        &moon_coin,
+        TRANSFER_AMOUNT,
+    )
+    .await
+    .unwrap();
+
+    // The withdraw and deposit events of the coin are parsed as operations in the coin
+    let rosetta_txn = try_until_ok_default(|| {
+        rosetta_client.search_transactions(&SearchTransactionsRequest {
+            network_identifier: network.clone(),
+            operator: None,
+            max_block: None,
+            offset: None,
+            limit: None,
+            transaction_identifier: Some(TransactionIdentifier::from(txn.info.hash)),
+            account_identifier: None,
+            success: None,
+        })
+    })
+    .await
+    .unwrap()
+    .transactions
+    .pop()
+    .expect("Transfer should be found")
+    .transaction;
+    let coin_operations: Vec<_> = rosetta_txn
+        .operations
+        .iter()
+        .filter(|operation| {
+            operation.amount.as_ref().map(|amount| &amount.currency) == Some(&moon_coin)
+        })
+        .collect();
+    assert_eq!(coin_operations.len(), 2);
+    for operation in coin_operations {
+        let (expected_account, expected_amount) =
+            match OperationType::from_str(&operation.operation_type).unwrap() {
+                OperationType::Withdraw => (issuer, format!("-{}", TRANSFER_AMOUNT)),
+                OperationType::Deposit => (receiver, TRANSFER_AMOUNT.to_string()),
+                _ => panic!("Unexpected operation {:?}", operation),
+            };
+        assert_eq!(
+            &AccountIdentifier::from(expected_account),
+            operation.account.as_ref().unwrap()
+        );
+        assert_eq!(expected_amount, operation.amount.as_ref().unwrap().value);
+        assert_eq!(
+            OperationStatusType::Success.to_string(),
+            *operation.status.as_ref().unwrap()
+        );
+    }
+
+    // Gas is still paid in the native coin
+    assert!(rosetta_txn.operations.iter().any(|operation| {
+        operation.operation_type == OperationType::Fee.to_string()
+            && operation.amount.as_ref().unwrap().currency == native_coin()
+    }));
+}
+
+async fn coin_has_balance(
+    rosetta_client: &RosettaClient,
+    chain_id: ChainId,
+    account: AccountAddress,
+    currency: &Currency,
+    expected_balance: u64,
+) -> anyhow::Result<()> {
+    let request = AccountBalanceRequest {
+        network_identifier: chain_id.into(),
+        account_identifier: account.into(),
+        block_identifier: None,
+        currencies: Some(vec![currency.clone()]),
+    };
+    try_until_ok_default(|| async {
+        let response = rosetta_client.account_balance(&request).await?;
+        if response.balances.iter().any(|amount| {
+            &amount.currency == currency && amount.value == expected_balance.to_string()
+        }) {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Failed to find account with {} {:?}, received {:?}",
+                expected_balance,
+                currency,
+                response
+            ))
+        }
+    })
+    .await
+}
+
 /// This test tests all of Rosetta's functionality from the read side in one go.  Since
 /// it's block based and it needs time to run, we do all the checks in a single test.
 #[tokio::test]
@@ -442,6 +684,7 @@ async fn test_block() {
 
     // Mapping of account to block and balance mappings
     let mut balances = BTreeMap::<AccountAddress, BTreeMap<u64, i128>>::new();
+    let mut stake_balances = BTreeMap::<AccountAddress, BTreeMap<u64, i128>>::new();
 
     // Wait until the Rosetta service is ready
     let request = NetworkRequest {
@@ -635,6 +878,108 @@ async fn test_block() {
     // Also fail to set an operator
     cli.set_operator(1, 3).await.unwrap_err();
 
+    // Add stake and delegate voting, which should show up in the stake sub account
+    add_stake_and_wait(
+        &rosetta_client,
+        &rest_client,
+        &network_identifier,
+        private_key_3,
+        1_000,
+        Duration::from_secs(5),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Add stake should work!");
+    set_delegated_voter_and_wait(
+        &rosetta_client,
+        &rest_client,
+        &network_identifier,
+        private_key_3,
+        account_id_1,
+        Duration::from_secs(5),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Set delegated voter should work!");
+    let stake_balance = get_stake_balance(&rosetta_client, chain_id, account_id_3).await;
+    assert_eq!(1_001_000, stake_balance);
+
+    // Unlock and withdraw some stake, the pool isn't in the validator set and has no lockup
+    unlock_stake_and_wait(
+        &rosetta_client,
+        &rest_client,
+        &network_identifier,
+        private_key_3,
+        1_000,
+        Duration::from_secs(5),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Unlock stake should work!");
+    withdraw_stake_and_wait(
+        &rosetta_client,
+        &rest_client,
+        &network_identifier,
+        private_key_3,
+        1_000,
+        Duration::from_secs(5),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Withdraw stake should work!");
+    let stake_balance = get_stake_balance(&rosetta_client, chain_id, account_id_3).await;
+    assert_eq!(1_000_000, stake_balance);
+
+    // After resetting the lockup, unlocked stake can't be withdrawn anymore
+    reset_lockup_and_wait(
+        &rosetta_client,
+        &rest_client,
+        &network_identifier,
+        private_key_3,
+        Duration::from_secs(5),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Reset lockup should work!");
+    unlock_stake_and_wait(
+        &rosetta_client,
+        &rest_client,
+        &network_identifier,
+        private_key_3,
+        1_000,
+        Duration::from_secs(5),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Unlock stake should work!");
+    withdraw_stake_and_wait(
+        &rosetta_client,
+        &rest_client,
+        &network_identifier,
+        private_key_3,
+        1_000,
+        Duration::from_secs(5),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect_err("Withdraw stake should fail while locked up");
+    let stake_balance = get_stake_balance(&rosetta_client, chain_id, account_id_3).await;
+    assert_eq!(1_000_000, stake_balance);
+
     // This one will fail
     let maybe_final_txn = transfer_and_wait(
         &rosetta_client,
@@ -744,7 +1089,14 @@ async fn test_block() {
             .transactions
             .as_ref()
             .expect("Every actual block should have transactions");
-        parse_block_transactions(&block, &mut balances, actual_txns, &mut current_version).await;
+        parse_block_transactions(
+            &block,
+            &mut balances,
+            &mut stake_balances,
+            actual_txns,
+            &mut current_version,
+        )
+        .await;
 
         // The full block must have been processed
         assert_eq!(current_version - 1, actual_block.last_version.0);
@@ -754,13 +1106,194 @@ async fn test_block() {
     }
 
     // Reconcile and ensure all balances are calculated correctly
-    check_balances(&rosetta_client, chain_id, balances).await;
+    check_balances(&rosetta_client, chain_id, balances, AccountIdentifier::from).await;
+    check_balances(&rosetta_client, chain_id, stake_balances, |address| {
+        AccountIdentifier::stake_account(address, StakeSubAccount::Total)
+    })
+    .await;
+
+    // Check that the final transaction can be found through the other data APIs
+    check_transaction_lookups(&rosetta_client, chain_id, &final_txn).await;
+}
+
+/// Looks up a committed transaction through the block transaction, search, and events APIs
+async fn check_transaction_lookups(
+    rosetta_client: &RosettaClient,
+    chain_id: ChainId,
+    txn: &UserTransaction,
+) {
+    let network_identifier = NetworkIdentifier::from(chain_id);
+    let transaction_identifier = TransactionIdentifier::from(txn.info.hash);
+    let sender = *txn.request.sender.inner();
+
+    // Find the block through search by hash
+    let search_response = rosetta_client
+        .search_transactions(&SearchTransactionsRequest {
+            network_identifier: network_identifier.clone(),
+            operator: None,
+            max_block: None,
+            offset: None,
+            limit: None,
+            transaction_identifier: Some(transaction_identifier.clone()),
+            account_identifier: None,
+            success: None,
+        })
+        .await
+        .expect("Should be able to search by transaction hash");
+    assert_eq!(1, search_response.transactions.len());
+    let block_transaction = search_response.transactions.first().unwrap();
+    assert_eq!(
+        transaction_identifier,
+        block_transaction.transaction.transaction_identifier
+    );
+    let block_identifier = block_transaction.block_identifier.clone();
+
+    // The same transaction should be returned from the block
+    let block_transaction_response = rosetta_client
+        .block_transaction(&BlockTransactionRequest {
+            network_identifier: network_identifier.clone(),
+            block_identifier: block_identifier.clone(),
+            transaction_identifier: transaction_identifier.clone(),
+        })
+        .await
+        .expect("Should be able to get a transaction in a block");
+    assert_eq!(
+        block_transaction.transaction,
+        block_transaction_response.transaction
+    );
+
+    // The sender's history should include it, and only failed transactions when filtered
+    let search_response = rosetta_client
+        .search_transactions(&SearchTransactionsRequest {
+            network_identifier: network_identifier.clone(),
+            operator: None,
+            max_block: None,
+            offset: None,
+            limit: None,
+            transaction_identifier: None,
+            account_identifier: Some(sender.into()),
+            success: Some(false),
+        })
+        .await
+        .expect("Should be able to search by account");
+    assert!(search_response
+        .transactions
+        .iter()
+        .all(|block_txn| block_txn.transaction.metadata.failed));
+    assert!(search_response
+        .transactions
+        .iter()
+        .any(|block_txn| block_txn.transaction.transaction_identifier == transaction_identifier));
+
+    // Every block up to the transaction's block has been added
+    let events_response = rosetta_client
+        .events_blocks(&EventsBlocksRequest {
+            network_identifier: network_identifier.clone(),
+            offset: Some(block_identifier.index),
+            limit: Some(1),
+        })
+        .await
+        .expect("Should be able to get block events");
+    assert!(events_response.max_sequence >= block_identifier.index);
+    assert_eq!(
+        vec![BlockEvent {
+            sequence: block_identifier.index,
+            block_identifier: block_identifier.clone(),
+            event_type: BlockEventType::BlockAdded,
+        }],
+        events_response.events
+    );
+
+    // A committed transaction is no longer in mempool
+    rosetta_client
+        .mempool_transaction(&MempoolTransactionRequest {
+            network_identifier: network_identifier.clone(),
+            transaction_identifier: transaction_identifier.clone(),
+        })
+        .await
+        .expect_err("Committed transaction shouldn't be in mempool");
+    let mempool_response = rosetta_client
+        .mempool(&MempoolRequest {
+            network_identifier: network_identifier.clone(),
+        })
+        .await
+        .expect("Should be able to list mempool");
+    assert!(!mempool_response
+        .transaction_identifiers
+        .contains(&transaction_identifier));
+
+    // A transaction with a sequence number gap stays pending in mempool
+    let account_id_2 = cli.account_id(2);
+    let sequence_number = rest_client
+        .get_account(account_id_2)
+        .await
+        .unwrap()
+        .into_inner()
+        .sequence_number;
+    let pending_identifier = rosetta_client
+        .transfer(
+            &network_identifier,
+            private_key_2,
+            account_id_0,
+            20,
+            native_coin(),
+            expiry_time(Duration::from_secs(30)).as_secs(),
+            Some(sequence_number + 1),
+            Some(1000),
+            None,
+        )
+        .await
+        .expect("Should be able to submit a transaction with a sequence number gap");
+    let mempool_transaction = rosetta_client
+        .mempool_transaction(&MempoolTransactionRequest {
+            network_identifier: network_identifier.clone(),
+            transaction_identifier: pending_identifier.clone(),
+        })
+        .await
+        .expect("Pending transaction should be in mempool")
+        .transaction;
+    assert_eq!(
+        pending_identifier,
+        mempool_transaction.transaction_identifier
+    );
+    assert!(!mempool_transaction.metadata.failed);
+    assert_eq!(None, mempool_transaction.metadata.version);
+    let operation_types: Vec<_> = mempool_transaction
+        .operations
+        .iter()
+        .map(|operation| {
+            (
+                OperationType::from_str(&operation.operation_type).unwrap(),
+                operation
+                    .account
+                    .as_ref()
+                    .unwrap()
+                    .account_address()
+                    .unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            (OperationType::Withdraw, account_id_2),
+            (OperationType::Deposit, account_id_0)
+        ],
+        operation_types
+    );
+    let mempool_response = rosetta_client
+        .mempool(&MempoolRequest { network_identifier })
+        .await
+        .expect("Should be able to list mempool");
+    assert!(mempool_response
+        .transaction_identifiers
+        .contains(&pending_identifier));
 }
 
 /// Parse the transactions in each block
 async fn parse_block_transactions(
     block: &aptos_rosetta::types::Block,
     balances: &mut BTreeMap<AccountAddress, BTreeMap<u64, i128>>,
+    stake_balances: &mut BTreeMap<AccountAddress, BTreeMap<u64, i128>>,
     actual_txns: &[Transaction],
     current_version: &mut u64,
 ) {
@@ -775,7 +1308,8 @@ async fn parse_block_transactions(
 
         // Ensure transaction identifier is correct
         assert_eq!(
-            *current_version, txn_metadata.version.0,
+            *current_version,
+            txn_metadata.version.unwrap().0,
             "There should be no gaps in transaction versions"
         );
         assert_eq!(
@@ -805,7 +1339,10 @@ async fn parse_block_transactions(
                     actual_txn,
                     Transaction::BlockMetadataTransaction(_)
                 ));
-                assert!(transaction.operations.is_empty());
+                // Only rewards are distributed at the start of an epoch
+                assert!(transaction.operations.iter().all(|operation| {
+                    operation.operation_type == OperationType::StakingReward.to_string()
+                }));
             }
             TransactionType::StateCheckpoint => {
                 assert!(matches!(
@@ -819,12 +1356,13 @@ async fn parse_block_transactions(
         parse_operations(
             block.block_identifier.index,
             balances,
+            stake_balances,
             transaction,
             actual_txn,
         )
         .await;
 
-        for (_, account_balance) in balances.iter() {
+        for (_, account_balance) in balances.iter().chain(stake_balances.iter()) {
             if let Some(amount) = account_balance.get(current_version) {
                 assert!(*amount >= 0, "Amount shouldn't be negative!")
             }
@@ -839,6 +1377,7 @@ async fn parse_block_transactions(
 async fn parse_operations(
     block_height: u64,
     balances: &mut BTreeMap<AccountAddress, BTreeMap<u64, i128>>,
+    stake_balances: &mut BTreeMap<AccountAddress, BTreeMap<u64, i128>>,
     transaction: &aptos_rosetta::types::Transaction,
     actual_txn: &Transaction,
 ) {
@@ -1009,6 +1548,107 @@ async fn parse_operations(
                     );
                 }
             }
+            OperationType::SetDelegatedVoter => {
+                if actual_txn.success() {
+                    assert_eq!(
+                        OperationStatusType::Success,
+                        status,
+                        "Successful transaction should have successful set delegated voter operation"
+                    );
+                    // Check that the voter was set the same
+                    if let Transaction::UserTransaction(txn) = actual_txn {
+                        if let TransactionPayload::EntryFunctionPayload(ref payload) =
+                            txn.request.payload
+                        {
+                            let actual_voter_address: AccountAddress =
+                                serde_json::from_value(payload.arguments.first().unwrap().clone())
+                                    .unwrap();
+                            let voter = operation
+                                .metadata
+                                .as_ref()
+                                .unwrap()
+                                .voter
+                                .as_ref()
+                                .unwrap()
+                                .account_address()
+                                .unwrap();
+                            assert_eq!(actual_voter_address, voter)
+                        } else {
+                            panic!("Not an entry function");
+                        }
+                    } else {
+                        panic!("Not a user transaction");
+                    }
+                } else {
+                    assert_eq!(
+                        OperationStatusType::Failure,
+                        status,
+                        "Failed transaction should have failed set delegated voter operation"
+                    );
+                }
+            }
+            OperationType::AddStake
+            | OperationType::UnlockStake
+            | OperationType::WithdrawStake
+            | OperationType::StakingReward
+            | OperationType::ResetLockup => {
+                if actual_txn.success() {
+                    let account = operation
+                        .account
+                        .as_ref()
+                        .expect("There should be an account in a staking operation");
+                    let stake_sub_account = account
+                        .stake_sub_account()
+                        .expect("Stake sub account should be parsable");
+                    match (operation_type, operation.amount.as_ref()) {
+                        (OperationType::ResetLockup, amount) => {
+                            assert_eq!(None, stake_sub_account);
+                            assert!(amount.is_none(), "Reset lockup doesn't move stake");
+                        }
+                        (OperationType::UnlockStake, Some(amount)) => {
+                            // Stake moves between sub accounts, so the total doesn't change
+                            assert!(matches!(
+                                stake_sub_account,
+                                Some(StakeSubAccount::Active | StakeSubAccount::PendingInactive)
+                            ));
+                            assert_eq!(amount.currency, native_coin());
+                        }
+                        (_, Some(amount)) => {
+                            assert_eq!(Some(StakeSubAccount::Total), stake_sub_account);
+                            assert_eq!(
+                                amount.currency,
+                                native_coin(),
+                                "Stake should be the native coin"
+                            );
+                            let delta = i128::from_str(&amount.value)
+                                .expect("Should be able to parse amount value");
+                            let account_balances = stake_balances
+                                .entry(account.account_address().unwrap())
+                                .or_insert_with(|| {
+                                    let mut map = BTreeMap::new();
+                                    map.insert(block_height, 0);
+                                    map
+                                });
+                            let (_, latest_balance) = account_balances.iter().last().unwrap();
+                            let new_balance = *latest_balance + delta;
+                            account_balances.insert(block_height, new_balance);
+                        }
+                        (_, None) => panic!("Stake balance changes should have an amount"),
+                    }
+
+                    assert_eq!(
+                        OperationStatusType::Success,
+                        status,
+                        "Successful transaction should have successful staking operation"
+                    );
+                } else {
+                    assert_eq!(
+                        OperationStatusType::Failure,
+                        status,
+                        "Failed transaction should have failed staking operation"
+                    );
+                }
+            }
             OperationType::Fee => {
                 has_gas_op = true;
                 assert_eq!(OperationStatusType::Success, status);
@@ -1068,8 +1708,9 @@ async fn parse_operations(
     assert!(
         has_gas_op
             || transaction.metadata.transaction_type == TransactionType::Genesis
+            || transaction.metadata.transaction_type == TransactionType::BlockMetadata
             || transaction.operations.is_empty(),
-        "Must have a gas operation at least in a transaction except for Genesis",
+        "Must have a gas operation at least in a user transaction",
     );
 }
 
@@ -1078,6 +1719,7 @@ async fn check_balances(
     rosetta_client: &RosettaClient,
     chain_id: ChainId,
     balances: BTreeMap<AccountAddress, BTreeMap<u64, i128>>,
+    account_identifier: impl Fn(AccountAddress) -> AccountIdentifier,
 ) {
     // TODO: Check some random times that arent on changes?
     for (account, account_balances) in balances {
@@ -1086,7 +1728,7 @@ async fn check_balances(
             let response = rosetta_client
                 .account_balance(&AccountBalanceRequest {
                     network_identifier: NetworkIdentifier::from(chain_id),
-                    account_identifier: account.into(),
+                    account_identifier: account_identifier(account),
                     block_identifier: Some(PartialBlockIdentifier {
                         index: Some(block_height),
                         hash: None,
@@ -1197,7 +1839,10 @@ fn assert_transfer_transaction(
 
     let rosetta_txn_metadata = &rosetta_txn.metadata;
     assert_eq!(TransactionType::User, rosetta_txn_metadata.transaction_type);
-    assert_eq!(actual_txn.info.version.0, rosetta_txn_metadata.version.0);
+    assert_eq!(
+        actual_txn.info.version.0,
+        rosetta_txn_metadata.version.unwrap().0
+    );
     assert_eq!(rosetta_txn.operations.len(), 3);
 
     // Check the operations
@@ -1392,6 +2037,7 @@ async fn transfer_and_wait(
             sender_key,
             receiver,
             amount,
+            native_coin(),
             expiry_time.as_secs(),
             sequence_number,
             max_gas,
@@ -1435,6 +2081,172 @@ async fn set_operator_and_wait(
         .map_err(ErrorWrapper::AfterSubmission)
 }
 
+async fn add_stake_and_wait(
+    rosetta_client: &RosettaClient,
+    rest_client: &aptos_rest_client::Client,
+    network_identifier: &NetworkIdentifier,
+    sender_key: &Ed25519PrivateKey,
+    amount: u64,
+    txn_expiry_duration: Duration,
+    sequence_number: Option<u64>,
+    max_gas: Option<u64>,
+    gas_unit_price: Option<u64>,
+) -> Result<Box<UserTransaction>, ErrorWrapper> {
+    let expiry_time = expiry_time(txn_expiry_duration);
+    let txn_hash = rosetta_client
+        .add_stake(
+            network_identifier,
+            sender_key,
+            amount,
+            expiry_time.as_secs(),
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+        .map_err(ErrorWrapper::BeforeSubmission)?
+        .hash;
+    wait_for_transaction(rest_client, expiry_time, txn_hash)
+        .await
+        .map_err(ErrorWrapper::AfterSubmission)
+}
+
+async fn unlock_stake_and_wait(
+    rosetta_client: &RosettaClient,
+    rest_client: &aptos_rest_client::Client,
+    network_identifier: &NetworkIdentifier,
+    sender_key: &Ed25519PrivateKey,
+    amount: u64,
+    txn_expiry_duration: Duration,
+    sequence_number: Option<u64>,
+    max_gas: Option<u64>,
+    gas_unit_price: Option<u64>,
+) -> Result<Box<UserTransaction>, ErrorWrapper> {
+    let expiry_time = expiry_time(txn_expiry_duration);
+    let txn_hash = rosetta_client
+        .unlock_stake(
+            network_identifier,
+            sender_key,
+            amount,
+            expiry_time.as_secs(),
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+        .map_err(ErrorWrapper::BeforeSubmission)?
+        .hash;
+    wait_for_transaction(rest_client, expiry_time, txn_hash)
+        .await
+        .map_err(ErrorWrapper::AfterSubmission)
+}
+
+async fn withdraw_stake_and_wait(
+    rosetta_client: &RosettaClient,
+    rest_client: &aptos_rest_client::Client,
+    network_identifier: &NetworkIdentifier,
+    sender_key: &Ed25519PrivateKey,
+    amount: u64,
+    txn_expiry_duration: Duration,
+    sequence_number: Option<u64>,
+    max_gas: Option<u64>,
+    gas_unit_price: Option<u64>,
+) -> Result<Box<UserTransaction>, ErrorWrapper> {
+    let expiry_time = expiry_time(txn_expiry_duration);
+    let txn_hash = rosetta_client
+        .withdraw_stake(
+            network_identifier,
+            sender_key,
+            amount,
+            expiry_time.as_secs(),
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+        .map_err(ErrorWrapper::BeforeSubmission)?
+        .hash;
+    wait_for_transaction(rest_client, expiry_time, txn_hash)
+        .await
+        .map_err(ErrorWrapper::AfterSubmission)
+}
+
+async fn reset_lockup_and_wait(
+    rosetta_client: &RosettaClient,
+    rest_client: &aptos_rest_client::Client,
+    network_identifier: &NetworkIdentifier,
+    sender_key: &Ed25519PrivateKey,
+    txn_expiry_duration: Duration,
+    sequence_number: Option<u64>,
+    max_gas: Option<u64>,
+    gas_unit_price: Option<u64>,
+) -> Result<Box<UserTransaction>, ErrorWrapper> {
+    let expiry_time = expiry_time(txn_expiry_duration);
+    let txn_hash = rosetta_client
+        .reset_lockup(
+            network_identifier,
+            sender_key,
+            expiry_time.as_secs(),
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+        .map_err(ErrorWrapper::BeforeSubmission)?
+        .hash;
+    wait_for_transaction(rest_client, expiry_time, txn_hash)
+        .await
+        .map_err(ErrorWrapper::AfterSubmission)
+}
+
+async fn set_delegated_voter_and_wait(
+    rosetta_client: &RosettaClient,
+    rest_client: &aptos_rest_client::Client,
+    network_identifier: &NetworkIdentifier,
+    sender_key: &Ed25519PrivateKey,
+    new_voter: AccountAddress,
+    txn_expiry_duration: Duration,
+    sequence_number: Option<u64>,
+    max_gas: Option<u64>,
+    gas_unit_price: Option<u64>,
+) -> Result<Box<UserTransaction>, ErrorWrapper> {
+    let expiry_time = expiry_time(txn_expiry_duration);
+    let txn_hash = rosetta_client
+        .set_delegated_voter(
+            network_identifier,
+            sender_key,
+            new_voter,
+            expiry_time.as_secs(),
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+        .map_err(ErrorWrapper::BeforeSubmission)?
+        .hash;
+    wait_for_transaction(rest_client, expiry_time, txn_hash)
+        .await
+        .map_err(ErrorWrapper::AfterSubmission)
+}
+
+/// Total stake in the account's stake pool at the latest block
+async fn get_stake_balance(
+    rosetta_client: &RosettaClient,
+    chain_id: ChainId,
+    account: AccountAddress,
+) -> u64 {
+    let request = AccountBalanceRequest {
+        network_identifier: chain_id.into(),
+        account_identifier: AccountIdentifier::stake_account(account, StakeSubAccount::Total),
+        block_identifier: None,
+        currencies: Some(vec![native_coin()]),
+    };
+    let response = try_until_ok_default(|| rosetta_client.account_balance(&request))
+        .await
+        .unwrap();
+    u64::from_str(&response.balances.first().unwrap().value).unwrap()
+}
+
 async fn wait_for_transaction(
     rest_client: &aptos_rest_client::Client,
     expiry_time: Duration,
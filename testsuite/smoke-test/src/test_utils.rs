@@ -9,6 +9,17 @@ use aptos_sdk::{
 use cached_packages::aptos_stdlib;
 use forge::{reconfig, LocalSwarm, NodeExt, Swarm};
 use rand::random;
+use std::path::PathBuf;
+
+/// The local Aptos Framework package, for Move packages published in tests
+pub fn aptos_framework_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("aptos-move")
+        .join("framework")
+        .join("aptos-framework")
+}
 
 pub async fn create_and_fund_account(swarm: &'_ mut dyn Swarm, amount: u64) -> LocalAccount {
     let mut info = swarm.aptos_public_info();
@@ -25,6 +25,17 @@ pub static APTOS_TRANSACTION_VALIDATION: Lazy<TransactionValidation> =
         user_epilogue_name: Identifier::new("epilogue").unwrap(),
     });
 
+/// Name of the prologue for sponsored transactions. The `TransactionValidation` resource was
+/// published at genesis and has no field for it, so it is looked up in the same module.
+pub const FEE_PAYER_PROLOGUE_NAME: &IdentStr = ident_str!("fee_payer_script_prologue");
+
+/// Name of the epilogue which charges gas to an account other than the sender.
+pub const USER_EPILOGUE_GAS_PAYER_NAME: &IdentStr = ident_str!("epilogue_gas_payer");
+
+/// Name of the epilogue which also refunds the storage fee for the state freed by the transaction.
+pub const USER_EPILOGUE_WITH_STORAGE_REFUND_NAME: &IdentStr =
+    ident_str!("epilogue_with_storage_refund");
+
 /// A Rust representation of chain-specific account information
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
@@ -0,0 +1,48 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::on_chain_config::OnChainConfig;
+use serde::{Deserialize, Serialize};
+
+/// The feature flags defined in the Move module `std::features`. The numbers must be kept in sync
+/// with the ones defined there.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FeatureFlag {
+    SHA_512_AND_RIPEMD_160_NATIVES = 1,
+    BLAKE2B_256_NATIVE = 2,
+    SECP256R1_ECDSA_AUTHENTICATOR = 3,
+    FEE_PAYER_ENABLED = 4,
+    MULTISIG_ACCOUNTS = 5,
+}
+
+/// The set of enabled feature flags, stored on chain as a bitset.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Features {
+    #[serde(with = "serde_bytes")]
+    pub features: Vec<u8>,
+}
+
+impl Features {
+    pub fn is_enabled(&self, flag: FeatureFlag) -> bool {
+        let flag = flag as u64;
+        let byte_index = (flag / 8) as usize;
+        let bit_mask = 1 << (flag % 8);
+        byte_index < self.features.len() && (self.features[byte_index] & bit_mask) != 0
+    }
+
+    pub fn enable(&mut self, flag: FeatureFlag) {
+        let flag = flag as u64;
+        let byte_index = (flag / 8) as usize;
+        let bit_mask = 1 << (flag % 8);
+        if self.features.len() <= byte_index {
+            self.features.resize(byte_index + 1, 0);
+        }
+        self.features[byte_index] |= bit_mask;
+    }
+}
+
+impl OnChainConfig for Features {
+    const MODULE_IDENTIFIER: &'static str = "features";
+    const TYPE_IDENTIFIER: &'static str = "Features";
+}
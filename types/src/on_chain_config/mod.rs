@@ -16,12 +16,14 @@ use move_deps::move_core_types::{
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{collections::HashMap, fmt, sync::Arc};
 
+mod aptos_features;
 mod aptos_version;
 mod consensus_config;
 mod gas_schedule;
 mod validator_set;
 
 pub use self::{
+    aptos_features::{FeatureFlag, Features},
     aptos_version::{
         Version, APTOS_MAX_KNOWN_VERSION, APTOS_VERSION_2, APTOS_VERSION_3, APTOS_VERSION_4,
     },
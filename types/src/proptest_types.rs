@@ -386,6 +386,15 @@ fn new_raw_transaction(
             expiration_time_secs,
             chain_id,
         ),
+        payload @ TransactionPayload::Multisig(_) => RawTransaction::new(
+            sender,
+            sequence_number,
+            payload,
+            max_gas_amount,
+            gas_unit_price,
+            expiration_time_secs,
+            chain_id,
+        ),
     }
 }
 
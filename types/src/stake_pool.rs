@@ -2,6 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{account_address::AccountAddress, event::EventHandle};
+use move_deps::move_core_types::{
+    ident_str,
+    identifier::IdentStr,
+    move_resource::{MoveResource, MoveStructType},
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -27,3 +32,10 @@ pub struct StakePool {
     withdraw_stake_events: EventHandle,
     leave_validator_set_events: EventHandle,
 }
+
+impl MoveStructType for StakePool {
+    const MODULE_NAME: &'static IdentStr = ident_str!("stake");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("StakePool");
+}
+
+impl MoveResource for StakePool {}
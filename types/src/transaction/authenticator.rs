@@ -3,13 +3,14 @@
 
 use crate::{
     account_address::AccountAddress,
-    transaction::{RawTransaction, RawTransactionWithData},
+    transaction::{webauthn::WebAuthnSignature, RawTransaction, RawTransactionWithData},
 };
 use anyhow::{ensure, Error, Result};
 use aptos_crypto::{
     ed25519::{Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    secp256r1_ecdsa::{Secp256r1EcdsaPublicKey, Secp256r1EcdsaSignature},
     traits::Signature,
     CryptoMaterialError, HashValue, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
 };
@@ -57,6 +58,16 @@ pub enum TransactionAuthenticator {
         secondary_signer_addresses: Vec<AccountAddress>,
         secondary_signers: Vec<AccountAuthenticator>,
     },
+    /// Single sender, authenticated by any account authenticator.
+    SingleSender { sender: AccountAuthenticator },
+    /// Sponsored transaction, where the fee payer pays for gas on behalf of the sender.
+    FeePayer {
+        sender: AccountAuthenticator,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<AccountAuthenticator>,
+        fee_payer_address: AccountAddress,
+        fee_payer_signer: AccountAuthenticator,
+    },
 }
 
 impl TransactionAuthenticator {
@@ -92,6 +103,28 @@ impl TransactionAuthenticator {
         }
     }
 
+    /// Create a single-sender authenticator
+    pub fn single_sender(sender: AccountAuthenticator) -> Self {
+        Self::SingleSender { sender }
+    }
+
+    /// Create a fee payer authenticator
+    pub fn fee_payer(
+        sender: AccountAuthenticator,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<AccountAuthenticator>,
+        fee_payer_address: AccountAddress,
+        fee_payer_signer: AccountAuthenticator,
+    ) -> Self {
+        Self::FeePayer {
+            sender,
+            secondary_signer_addresses,
+            secondary_signers,
+            fee_payer_address,
+            fee_payer_signer,
+        }
+    }
+
     /// Return Ok if all AccountAuthenticator's public keys match their signatures, Err otherwise
     pub fn verify(&self, raw_txn: &RawTransaction) -> Result<()> {
         let num_sigs: usize = self.sender().number_of_signatures()
@@ -99,7 +132,10 @@ impl TransactionAuthenticator {
                 .secondary_signers()
                 .iter()
                 .map(|auth| auth.number_of_signatures())
-                .sum::<usize>();
+                .sum::<usize>()
+            + self
+                .fee_payer_signer()
+                .map_or(0, |auth| auth.number_of_signatures());
         if num_sigs > MAX_NUM_OF_SIGS {
             return Err(Error::new(AuthenticationError::MaxSignaturesExceeded));
         }
@@ -127,6 +163,25 @@ impl TransactionAuthenticator {
                 }
                 Ok(())
             }
+            Self::SingleSender { sender } => sender.verify(raw_txn),
+            Self::FeePayer {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer_signer,
+            } => {
+                let message = RawTransactionWithData::new_multi_agent_with_fee_payer(
+                    raw_txn.clone(),
+                    secondary_signer_addresses.clone(),
+                    *fee_payer_address,
+                );
+                sender.verify(&message)?;
+                for signer in secondary_signers {
+                    signer.verify(&message)?;
+                }
+                fee_payer_signer.verify(&message)
+            }
         }
     }
 
@@ -144,6 +199,8 @@ impl TransactionAuthenticator {
                 signature,
             } => AccountAuthenticator::multi_ed25519(public_key.clone(), signature.clone()),
             Self::MultiAgent { sender, .. } => sender.clone(),
+            Self::SingleSender { sender } => sender.clone(),
+            Self::FeePayer { sender, .. } => sender.clone(),
         }
     }
 
@@ -153,11 +210,16 @@ impl TransactionAuthenticator {
             | Self::MultiEd25519 {
                 public_key: _,
                 signature: _,
-            } => vec![],
+            }
+            | Self::SingleSender { .. } => vec![],
             Self::MultiAgent {
                 sender: _,
                 secondary_signer_addresses,
                 ..
+            }
+            | Self::FeePayer {
+                secondary_signer_addresses,
+                ..
             } => secondary_signer_addresses.to_vec(),
         }
     }
@@ -168,14 +230,38 @@ impl TransactionAuthenticator {
             | Self::MultiEd25519 {
                 public_key: _,
                 signature: _,
-            } => vec![],
+            }
+            | Self::SingleSender { .. } => vec![],
             Self::MultiAgent {
                 sender: _,
                 secondary_signer_addresses: _,
                 secondary_signers,
+            }
+            | Self::FeePayer {
+                secondary_signers, ..
             } => secondary_signers.to_vec(),
         }
     }
+
+    /// Returns the address of the account paying for gas, if it differs from the sender.
+    pub fn fee_payer_address(&self) -> Option<AccountAddress> {
+        match self {
+            Self::FeePayer {
+                fee_payer_address, ..
+            } => Some(*fee_payer_address),
+            _ => None,
+        }
+    }
+
+    /// Returns the authenticator of the account paying for gas, if it differs from the sender.
+    pub fn fee_payer_signer(&self) -> Option<AccountAuthenticator> {
+        match self {
+            Self::FeePayer {
+                fee_payer_signer, ..
+            } => Some(fee_payer_signer.clone()),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for TransactionAuthenticator {
@@ -224,6 +310,40 @@ impl fmt::Display for TransactionAuthenticator {
                     sender, sec_addrs, sec_signers,
                 )
             }
+            Self::SingleSender { sender } => {
+                write!(
+                    f,
+                    "TransactionAuthenticator[scheme: SingleSender, sender: {}]",
+                    sender
+                )
+            }
+            Self::FeePayer {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer_signer,
+            } => {
+                let mut sec_addrs: String = "".to_string();
+                for sec_addr in secondary_signer_addresses {
+                    sec_addrs = format!("{}\n\t\t\t{:#?},", sec_addrs, sec_addr);
+                }
+                let mut sec_signers: String = "".to_string();
+                for sec_signer in secondary_signers {
+                    sec_signers = format!("{}\n\t\t\t{:#?},", sec_signers, sec_signer);
+                }
+                write!(
+                    f,
+                    "TransactionAuthenticator[\n\
+                        \tscheme: FeePayer, \n\
+                        \tsender: {}\n\
+                        \tsecondary signer addresses: {}\n\
+                        \tsecondary signers: {}\n\
+                        \tfee payer address: {}\n\
+                        \tfee payer signer: {}]",
+                    sender, sec_addrs, sec_signers, fee_payer_address, fee_payer_signer,
+                )
+            }
         }
     }
 }
@@ -241,6 +361,7 @@ impl fmt::Display for TransactionAuthenticator {
 pub enum Scheme {
     Ed25519 = 0,
     MultiEd25519 = 1,
+    Secp256r1Ecdsa = 2,
     // ... add more schemes here
 }
 
@@ -249,6 +370,7 @@ impl fmt::Display for Scheme {
         let display = match self {
             Scheme::Ed25519 => "Ed25519",
             Scheme::MultiEd25519 => "MultiEd25519",
+            Scheme::Secp256r1Ecdsa => "Secp256r1Ecdsa",
         };
         write!(f, "Scheme::{}", display)
     }
@@ -266,6 +388,16 @@ pub enum AccountAuthenticator {
         public_key: MultiEd25519PublicKey,
         signature: MultiEd25519Signature,
     },
+    /// Single ECDSA signature over the NIST P-256 curve
+    Secp256r1Ecdsa {
+        public_key: Secp256r1EcdsaPublicKey,
+        signature: Secp256r1EcdsaSignature,
+    },
+    /// WebAuthn assertion produced by a P-256 credential, e.g., a passkey
+    WebAuthn {
+        public_key: Secp256r1EcdsaPublicKey,
+        signature: WebAuthnSignature,
+    },
     // ... add more schemes here
 }
 
@@ -275,6 +407,9 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { .. } => Scheme::Ed25519,
             Self::MultiEd25519 { .. } => Scheme::MultiEd25519,
+            // Both authenticators are backed by the same key type, so that an account can be
+            // used both with a passkey and with the raw key.
+            Self::Secp256r1Ecdsa { .. } | Self::WebAuthn { .. } => Scheme::Secp256r1Ecdsa,
         }
     }
 
@@ -297,6 +432,25 @@ impl AccountAuthenticator {
         }
     }
 
+    /// Create a single-signature secp256r1 ECDSA authenticator
+    pub fn secp256r1_ecdsa(
+        public_key: Secp256r1EcdsaPublicKey,
+        signature: Secp256r1EcdsaSignature,
+    ) -> Self {
+        Self::Secp256r1Ecdsa {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a WebAuthn authenticator
+    pub fn webauthn(public_key: Secp256r1EcdsaPublicKey, signature: WebAuthnSignature) -> Self {
+        Self::WebAuthn {
+            public_key,
+            signature,
+        }
+    }
+
     /// Return Ok if the authenticator's public key matches its signature, Err otherwise
     pub fn verify<T: Serialize + CryptoHash>(&self, message: &T) -> Result<()> {
         match self {
@@ -308,6 +462,14 @@ impl AccountAuthenticator {
                 public_key,
                 signature,
             } => signature.verify(message, public_key),
+            Self::Secp256r1Ecdsa {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
+            Self::WebAuthn {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
         }
     }
 
@@ -316,6 +478,9 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { public_key, .. } => public_key.to_bytes().to_vec(),
             Self::MultiEd25519 { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::Secp256r1Ecdsa { public_key, .. } | Self::WebAuthn { public_key, .. } => {
+                public_key.to_bytes().to_vec()
+            }
         }
     }
 
@@ -324,6 +489,10 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { signature, .. } => signature.to_bytes().to_vec(),
             Self::MultiEd25519 { signature, .. } => signature.to_bytes().to_vec(),
+            Self::Secp256r1Ecdsa { signature, .. } => signature.to_bytes().to_vec(),
+            Self::WebAuthn { signature, .. } => {
+                bcs::to_bytes(signature).expect("WebAuthn signature serialization can't fail")
+            }
         }
     }
 
@@ -342,6 +511,7 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { .. } => 1,
             Self::MultiEd25519 { signature, .. } => signature.signatures().len(),
+            Self::Secp256r1Ecdsa { .. } | Self::WebAuthn { .. } => 1,
         }
     }
 }
@@ -394,6 +564,11 @@ impl AuthenticationKey {
         Self::from_preimage(&AuthenticationKeyPreimage::multi_ed25519(public_key))
     }
 
+    /// Create an authentication key from a Secp256r1 ECDSA public key
+    pub fn secp256r1_ecdsa(public_key: &Secp256r1EcdsaPublicKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::secp256r1_ecdsa(public_key))
+    }
+
     /// Return an address derived from the last `AccountAddress::LENGTH` bytes of this
     /// authentication key.
     pub fn derived_address(&self) -> AccountAddress {
@@ -449,6 +624,11 @@ impl AuthenticationKeyPreimage {
         Self::new(public_key.to_bytes(), Scheme::MultiEd25519)
     }
 
+    /// Construct a preimage from a Secp256r1 ECDSA public key
+    pub fn secp256r1_ecdsa(public_key: &Secp256r1EcdsaPublicKey) -> AuthenticationKeyPreimage {
+        Self::new(public_key.to_bytes().to_vec(), Scheme::Secp256r1Ecdsa)
+    }
+
     /// Construct a vector from this authentication key
     pub fn into_vec(self) -> Vec<u8> {
         self.0
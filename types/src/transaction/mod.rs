@@ -19,6 +19,7 @@ use aptos_crypto::{
     ed25519::*,
     hash::{CryptoHash, EventAccumulatorHasher},
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    secp256r1_ecdsa::{Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey},
     traits::{signing_message, SigningKey},
     HashValue,
 };
@@ -37,11 +38,14 @@ use std::{
 pub mod authenticator;
 mod change_set;
 mod module;
+mod multisig;
 mod script;
 mod transaction_argument;
+pub mod webauthn;
 
 pub use change_set::ChangeSet;
 pub use module::{Module, ModuleBundle};
+pub use multisig::{Multisig, MultisigTransactionPayload};
 pub use script::{
     ArgumentABI, EntryABI, EntryFunction, EntryFunctionABI, Script, TransactionScriptABI,
     TypeArgumentABI,
@@ -222,6 +226,42 @@ impl RawTransaction {
         )))
     }
 
+    /// Signs the given `RawTransaction` with a Secp256r1 ECDSA key. Note that this consumes the
+    /// `RawTransaction` and turns it into a `SignatureCheckedTransaction`.
+    pub fn sign_secp256r1_ecdsa(
+        self,
+        private_key: &Secp256r1EcdsaPrivateKey,
+        public_key: Secp256r1EcdsaPublicKey,
+    ) -> Result<SignatureCheckedTransaction> {
+        let signature = private_key.sign(&self);
+        Ok(SignatureCheckedTransaction(
+            SignedTransaction::new_single_sender(
+                self,
+                AccountAuthenticator::secp256r1_ecdsa(public_key, signature),
+            ),
+        ))
+    }
+
+    /// Signs the given `RawTransaction` like a WebAuthn authenticator (e.g., a passkey) holding
+    /// the Secp256r1 ECDSA key would, when used from `origin`. Note that this consumes the
+    /// `RawTransaction` and turns it into a `SignatureCheckedTransaction`.
+    pub fn sign_webauthn(
+        self,
+        private_key: &Secp256r1EcdsaPrivateKey,
+        public_key: Secp256r1EcdsaPublicKey,
+        authenticator_data: Vec<u8>,
+        origin: &str,
+    ) -> Result<SignatureCheckedTransaction> {
+        let signature =
+            webauthn::WebAuthnSignature::sign(&self, private_key, authenticator_data, origin);
+        Ok(SignatureCheckedTransaction(
+            SignedTransaction::new_single_sender(
+                self,
+                AccountAuthenticator::webauthn(public_key, signature),
+            ),
+        ))
+    }
+
     /// Signs the given multi-agent `RawTransaction`, which is a transaction with secondary
     /// signers in addition to a sender. The private keys of the sender and the
     /// secondary signers are used to sign the transaction.
@@ -266,6 +306,63 @@ impl RawTransaction {
         ))
     }
 
+    /// Signs the given fee payer `RawTransaction`, in which the fee payer pays for gas on behalf
+    /// of the sender. The sender, the secondary signers and the fee payer all sign over the
+    /// transaction together with the secondary signer addresses and the fee payer address.
+    ///
+    /// The order and length of the secondary keys provided here have to match the order and
+    /// length of the `secondary_signers`.
+    pub fn sign_fee_payer(
+        self,
+        sender_private_key: &Ed25519PrivateKey,
+        secondary_signers: Vec<AccountAddress>,
+        secondary_private_keys: Vec<&Ed25519PrivateKey>,
+        fee_payer_address: AccountAddress,
+        fee_payer_private_key: &Ed25519PrivateKey,
+    ) -> Result<SignatureCheckedTransaction> {
+        let message = RawTransactionWithData::new_multi_agent_with_fee_payer(
+            self.clone(),
+            secondary_signers.clone(),
+            fee_payer_address,
+        );
+        let sender_signature = sender_private_key.sign(&message);
+        let sender_authenticator = AccountAuthenticator::ed25519(
+            Ed25519PublicKey::from(sender_private_key),
+            sender_signature,
+        );
+
+        if secondary_private_keys.len() != secondary_signers.len() {
+            return Err(format_err!(
+                "number of secondary private keys and number of secondary signers don't match"
+            ));
+        }
+        let mut secondary_authenticators = vec![];
+        for priv_key in secondary_private_keys {
+            let signature = priv_key.sign(&message);
+            secondary_authenticators.push(AccountAuthenticator::ed25519(
+                Ed25519PublicKey::from(priv_key),
+                signature,
+            ));
+        }
+
+        let fee_payer_signature = fee_payer_private_key.sign(&message);
+        let fee_payer_authenticator = AccountAuthenticator::ed25519(
+            Ed25519PublicKey::from(fee_payer_private_key),
+            fee_payer_signature,
+        );
+
+        Ok(SignatureCheckedTransaction(
+            SignedTransaction::new_fee_payer(
+                self,
+                sender_authenticator,
+                secondary_signers,
+                secondary_authenticators,
+                fee_payer_address,
+                fee_payer_authenticator,
+            ),
+        ))
+    }
+
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn multi_sign_for_testing(
         self,
@@ -293,6 +390,21 @@ impl RawTransaction {
                 script_fn.args().to_vec(),
             ),
             TransactionPayload::ModuleBundle(_) => ("module publishing".to_string(), vec![]),
+            TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+                Some(MultisigTransactionPayload::EntryFunction(entry_function)) => (
+                    format!(
+                        "multisig {}: {}::{}",
+                        multisig.multisig_address,
+                        entry_function.module(),
+                        entry_function.function()
+                    ),
+                    entry_function.args().to_vec(),
+                ),
+                None => (
+                    format!("multisig {}: stored payload", multisig.multisig_address),
+                    vec![],
+                ),
+            },
         };
         let mut f_args: String = "".to_string();
         for arg in args {
@@ -341,6 +453,11 @@ pub enum RawTransactionWithData {
         raw_txn: RawTransaction,
         secondary_signer_addresses: Vec<AccountAddress>,
     },
+    MultiAgentWithFeePayer {
+        raw_txn: RawTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        fee_payer_address: AccountAddress,
+    },
 }
 
 impl RawTransactionWithData {
@@ -353,6 +470,18 @@ impl RawTransactionWithData {
             secondary_signer_addresses,
         }
     }
+
+    pub fn new_multi_agent_with_fee_payer(
+        raw_txn: RawTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        fee_payer_address: AccountAddress,
+    ) -> Self {
+        Self::MultiAgentWithFeePayer {
+            raw_txn,
+            secondary_signer_addresses,
+            fee_payer_address,
+        }
+    }
 }
 
 /// Different kinds of transactions.
@@ -364,6 +493,9 @@ pub enum TransactionPayload {
     ModuleBundle(ModuleBundle),
     /// A transaction that executes an existing entry function published on-chain.
     EntryFunction(EntryFunction),
+    /// A multisig transaction that allows an owner of a multisig account to execute a pre-approved
+    /// transaction as the multisig account.
+    Multisig(Multisig),
 }
 
 impl TransactionPayload {
@@ -511,6 +643,35 @@ impl SignedTransaction {
         }
     }
 
+    pub fn new_fee_payer(
+        raw_txn: RawTransaction,
+        sender: AccountAuthenticator,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<AccountAuthenticator>,
+        fee_payer_address: AccountAddress,
+        fee_payer_signer: AccountAuthenticator,
+    ) -> Self {
+        SignedTransaction {
+            raw_txn,
+            authenticator: TransactionAuthenticator::fee_payer(
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer_signer,
+            ),
+            size: OnceCell::new(),
+        }
+    }
+
+    pub fn new_single_sender(raw_txn: RawTransaction, sender: AccountAuthenticator) -> Self {
+        SignedTransaction {
+            raw_txn,
+            authenticator: TransactionAuthenticator::single_sender(sender),
+            size: OnceCell::new(),
+        }
+    }
+
     pub fn new_with_authenticator(
         raw_txn: RawTransaction,
         authenticator: TransactionAuthenticator,
@@ -582,6 +743,7 @@ impl SignedTransaction {
     pub fn contains_duplicate_signers(&self) -> bool {
         let mut all_signer_addresses = self.authenticator.secondary_signer_addreses();
         all_signer_addresses.push(self.sender());
+        all_signer_addresses.extend(self.authenticator.fee_payer_address());
         let mut s = BTreeSet::new();
         all_signer_addresses.iter().any(|a| !s.insert(*a))
     }
@@ -604,6 +766,16 @@ impl SignedTransaction {
         )
     }
 
+    /// Returns the address of the account paying for gas, if it is not the sender.
+    pub fn fee_payer(&self) -> Option<AccountAddress> {
+        self.authenticator.fee_payer_address()
+    }
+
+    /// Returns the address of the account that is charged for gas.
+    pub fn gas_payer(&self) -> AccountAddress {
+        self.fee_payer().unwrap_or_else(|| self.sender())
+    }
+
     /// Returns the hash when the transaction is commited onchain.
     pub fn committed_hash(self) -> HashValue {
         Transaction::UserTransaction(self).hash()
@@ -0,0 +1,244 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for transactions signed by WebAuthn authenticators (e.g., passkeys).
+//!
+//! A WebAuthn authenticator does not sign arbitrary messages: it signs the concatenation of its
+//! `authenticatorData` and the SHA2-256 hash of a `clientDataJSON` document built by the client,
+//! which embeds a challenge chosen by the relying party. For Aptos transactions, the challenge is
+//! the SHA3-256 hash of the signing message of the transaction, so that an assertion is bound to
+//! exactly one transaction.
+
+use anyhow::{bail, ensure, Result};
+use aptos_crypto::{
+    hash::CryptoHash,
+    secp256r1_ecdsa::{Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey, Secp256r1EcdsaSignature},
+    traits::{signing_message, Signature},
+    HashValue,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The `type` of the client data of an assertion.
+const WEBAUTHN_GET_TYPE: &str = "webauthn.get";
+
+/// The minimum length of the authenticator data: the relying party id hash (32 bytes), the flags
+/// (1 byte) and the signature counter (4 bytes).
+const MIN_AUTHENTICATOR_DATA_LENGTH: usize = 37;
+
+/// The index of the flags in the authenticator data.
+const AUTHENTICATOR_DATA_FLAGS_INDEX: usize = 32;
+
+/// The "user present" flag of the authenticator data.
+const USER_PRESENT_FLAG: u8 = 0x01;
+
+/// The "user verified" flag of the authenticator data.
+const USER_VERIFIED_FLAG: u8 = 0x04;
+
+/// The subset of the `clientDataJSON` fields relevant for the verification of an assertion.
+#[derive(Deserialize)]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    type_: String,
+    challenge: String,
+}
+
+/// A WebAuthn assertion over a transaction, produced by a P-256 (`ES256`) credential.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct WebAuthnSignature {
+    /// The ECDSA signature over `authenticator_data || sha256(client_data_json)`.
+    pub signature: Secp256r1EcdsaSignature,
+    /// The raw authenticator data returned by the authenticator.
+    #[serde(with = "serde_bytes")]
+    pub authenticator_data: Vec<u8>,
+    /// The raw client data JSON, as signed by the authenticator.
+    #[serde(with = "serde_bytes")]
+    pub client_data_json: Vec<u8>,
+}
+
+impl WebAuthnSignature {
+    pub fn new(
+        signature: Secp256r1EcdsaSignature,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Self {
+        Self {
+            signature,
+            authenticator_data,
+            client_data_json,
+        }
+    }
+
+    /// Produce the assertion a WebAuthn authenticator holding `private_key` returns for
+    /// `message`, when used from `origin`. This allows signing transactions like a passkey does,
+    /// without a browser, e.g., to test passkey flows.
+    pub fn sign<T: CryptoHash + Serialize>(
+        message: &T,
+        private_key: &Secp256r1EcdsaPrivateKey,
+        authenticator_data: Vec<u8>,
+        origin: &str,
+    ) -> Self {
+        let client_data_json = serde_json::json!({
+            "type": WEBAUTHN_GET_TYPE,
+            "challenge": Self::encoded_challenge(message),
+            "origin": origin,
+            "crossOrigin": false,
+        })
+        .to_string()
+        .into_bytes();
+        let signature = private_key
+            .sign_webauthn_assertion(&verification_data(&authenticator_data, &client_data_json));
+        Self::new(signature, authenticator_data, client_data_json)
+    }
+
+    /// Return the authenticator data of an assertion for the relying party `rp_id`, with the
+    /// user present and user verified flags set.
+    pub fn authenticator_data(rp_id: &str, sign_count: u32) -> Vec<u8> {
+        let mut data = Sha256::digest(rp_id.as_bytes()).to_vec();
+        data.push(USER_PRESENT_FLAG | USER_VERIFIED_FLAG);
+        data.extend_from_slice(&sign_count.to_be_bytes());
+        data
+    }
+
+    /// Return the challenge a WebAuthn client must request the assertion for, in order to
+    /// authenticate `message`.
+    pub fn challenge<T: CryptoHash + Serialize>(message: &T) -> HashValue {
+        HashValue::sha3_256_of(&signing_message(message))
+    }
+
+    /// Return the challenge encoded as it appears in the client data, i.e., in base64url without
+    /// padding.
+    pub fn encoded_challenge<T: CryptoHash + Serialize>(message: &T) -> String {
+        base64::encode_config(Self::challenge(message).as_ref(), base64::URL_SAFE_NO_PAD)
+    }
+
+    /// Return the message signed by the authenticator for this assertion.
+    pub fn verification_data(&self) -> Vec<u8> {
+        verification_data(&self.authenticator_data, &self.client_data_json)
+    }
+
+    /// Return Ok if this assertion was produced by `public_key` for `message`, Err otherwise.
+    pub fn verify<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_key: &Secp256r1EcdsaPublicKey,
+    ) -> Result<()> {
+        ensure!(
+            self.authenticator_data.len() >= MIN_AUTHENTICATOR_DATA_LENGTH,
+            "WebAuthn authenticator data is too short"
+        );
+        ensure!(
+            self.authenticator_data[AUTHENTICATOR_DATA_FLAGS_INDEX] & USER_PRESENT_FLAG != 0,
+            "WebAuthn assertion was produced without user presence"
+        );
+
+        let client_data: CollectedClientData = serde_json::from_slice(&self.client_data_json)?;
+        if client_data.type_ != WEBAUTHN_GET_TYPE {
+            bail!(
+                "Unexpected WebAuthn client data type: {}",
+                client_data.type_
+            );
+        }
+        ensure!(
+            client_data.challenge == Self::encoded_challenge(message),
+            "WebAuthn challenge does not match the signed message"
+        );
+
+        self.signature
+            .verify_arbitrary_msg(&self.verification_data(), public_key)
+    }
+}
+
+/// The message signed by an authenticator: its authenticator data followed by the SHA2-256 hash
+/// of the client data.
+fn verification_data(authenticator_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+    let mut data = authenticator_data.to_vec();
+    data.extend_from_slice(&Sha256::digest(client_data_json));
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{test_utils::TestAptosCrypto, PrivateKey, Uniform};
+    use rand::rngs::OsRng;
+
+    fn assertion(
+        private_key: &Secp256r1EcdsaPrivateKey,
+        flags: u8,
+        client_data_json: String,
+    ) -> WebAuthnSignature {
+        let mut authenticator_data = vec![0u8; MIN_AUTHENTICATOR_DATA_LENGTH];
+        authenticator_data[AUTHENTICATOR_DATA_FLAGS_INDEX] = flags;
+        let mut assertion = WebAuthnSignature::new(
+            Secp256r1EcdsaSignature::dummy_signature(),
+            authenticator_data,
+            client_data_json.into_bytes(),
+        );
+        assertion.signature = private_key.sign_webauthn_assertion(&assertion.verification_data());
+        assertion
+    }
+
+    fn client_data(type_: &str, challenge: &str) -> String {
+        format!(
+            r#"{{"type":"{}","challenge":"{}","origin":"https://aptoslabs.com","crossOrigin":false}}"#,
+            type_, challenge
+        )
+    }
+
+    #[test]
+    fn test_webauthn_assertion() {
+        let mut rng = OsRng;
+        let private_key = Secp256r1EcdsaPrivateKey::generate(&mut rng);
+        let public_key = private_key.public_key();
+        let message = TestAptosCrypto("Hello, World".to_string());
+        let other_message = TestAptosCrypto("Hello, Aptos".to_string());
+        let challenge = WebAuthnSignature::encoded_challenge(&message);
+
+        let valid = assertion(
+            &private_key,
+            USER_PRESENT_FLAG,
+            client_data(WEBAUTHN_GET_TYPE, &challenge),
+        );
+        assert!(valid.verify(&message, &public_key).is_ok());
+        assert!(valid.verify(&other_message, &public_key).is_err());
+
+        let other_key = Secp256r1EcdsaPrivateKey::generate(&mut rng).public_key();
+        assert!(valid.verify(&message, &other_key).is_err());
+
+        let wrong_type = assertion(
+            &private_key,
+            USER_PRESENT_FLAG,
+            client_data("webauthn.create", &challenge),
+        );
+        assert!(wrong_type.verify(&message, &public_key).is_err());
+
+        let no_user_presence =
+            assertion(&private_key, 0, client_data(WEBAUTHN_GET_TYPE, &challenge));
+        assert!(no_user_presence.verify(&message, &public_key).is_err());
+
+        let mut tampered = valid;
+        tampered.authenticator_data[0] ^= 1;
+        assert!(tampered.verify(&message, &public_key).is_err());
+    }
+
+    #[test]
+    fn test_webauthn_sign() {
+        let private_key = Secp256r1EcdsaPrivateKey::generate(&mut OsRng);
+        let public_key = private_key.public_key();
+        let message = TestAptosCrypto("Hello, World".to_string());
+
+        let authenticator_data = WebAuthnSignature::authenticator_data("aptoslabs.com", 1);
+        assert_eq!(authenticator_data.len(), MIN_AUTHENTICATOR_DATA_LENGTH);
+        let assertion = WebAuthnSignature::sign(
+            &message,
+            &private_key,
+            authenticator_data,
+            "https://aptoslabs.com",
+        );
+        assert!(assertion.verify(&message, &public_key).is_ok());
+        assert!(assertion
+            .verify(&TestAptosCrypto("Hello, Aptos".to_string()), &public_key)
+            .is_err());
+    }
+}
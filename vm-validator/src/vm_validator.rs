@@ -119,3 +119,16 @@ pub fn get_account_sequence_number(
         None => Ok(AccountSequenceInfo::Sequential(0)),
     }
 }
+
+/// returns account's AptosCoin balance from storage
+pub fn get_account_balance(state_view: &DbStateView, address: AccountAddress) -> Result<u64> {
+    fail_point!("vm_validator::get_account_balance", |_| {
+        Err(anyhow::anyhow!("Injected error in get_account_balance"))
+    });
+
+    let account_state_view = state_view.as_account_with_state_view(&address);
+
+    Ok(account_state_view
+        .get_coin_store_resource()?
+        .map_or(0, |coin_store| coin_store.coin()))
+}